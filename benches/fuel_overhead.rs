@@ -0,0 +1,99 @@
+//! What fuel metering costs.
+//!
+//! Times a `loop_effect` and a chain of `map`s run without a fuel budget.
+//! Without the `fuel` feature the combinators contain no metering code, so
+//! compare the two builds to see the cost un-metered pipelines pay when the
+//! feature is on:
+//!
+//! ```text
+//! cargo bench --bench fuel_overhead
+//! cargo bench --bench fuel_overhead --features fuel
+//! ```
+//!
+//! With the feature, the same workloads are also timed under
+//! `run_with_fuel`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use futures::executor::block_on;
+use stillwater::effect::prelude::*;
+
+const STEPS: u64 = 1_000_000;
+const CHAINS: u64 = 100_000;
+
+fn counting_loop() -> impl Effect<Output = u64, Error = (), Env = ()> {
+    loop_effect(0u64, |n| {
+        pure(if n < black_box(STEPS) {
+            Loop::Continue(n + 1)
+        } else {
+            Loop::Break(n)
+        })
+    })
+}
+
+fn map_chain(x: u64) -> impl Effect<Output = u64, Error = (), Env = ()> {
+    pure(x)
+        .map(|x| x + 1)
+        .map(|x| x * 3)
+        .map(|x| x ^ 0x5a)
+        .map(|x| x + 7)
+        .map(|x| x / 2)
+        .map(|x| x + 11)
+        .map(|x| x * 5)
+        .map(|x| x ^ 0xa5)
+}
+
+fn report(name: &str, steps: u64, elapsed: Duration) {
+    println!(
+        "{:<28} {:>10.2?} total {:>8.2} ns/step",
+        name,
+        elapsed,
+        elapsed.as_nanos() as f64 / steps as f64
+    );
+}
+
+fn time(name: &str, steps: u64, run: impl Fn()) {
+    // Warm up once, then keep the best of a few runs
+    run();
+    let best = (0..5)
+        .map(|_| {
+            let start = Instant::now();
+            run();
+            start.elapsed()
+        })
+        .min()
+        .unwrap();
+    report(name, steps, best);
+}
+
+fn main() {
+    println!(
+        "fuel feature: {}",
+        if cfg!(feature = "fuel") { "on" } else { "off" }
+    );
+
+    time("loop_effect", STEPS, || {
+        black_box(block_on(counting_loop().execute(&()))).unwrap();
+    });
+    time("map chain (8 maps)", CHAINS * 8, || {
+        for i in 0..CHAINS {
+            black_box(block_on(map_chain(black_box(i)).execute(&()))).unwrap();
+        }
+    });
+
+    #[cfg(feature = "fuel")]
+    {
+        time("loop_effect, metered", STEPS, || {
+            black_box(block_on(counting_loop().run_with_fuel(&(), u64::MAX))).unwrap();
+        });
+        time("map chain (8 maps), metered", CHAINS * 8, || {
+            for i in 0..CHAINS {
+                black_box(block_on(
+                    map_chain(black_box(i)).run_with_fuel(&(), u64::MAX),
+                ))
+                .unwrap();
+            }
+        });
+    }
+}
@@ -13,7 +13,7 @@ use stillwater::effect::bracket::{bracket_full, BracketError};
 use stillwater::effect::context::{EffectContext, EffectContextChain};
 use stillwater::effect::prelude::*;
 use stillwater::effect::retry::{retry, with_timeout};
-use stillwater::retry::RetryExhausted;
+use stillwater::retry::{RetryExhausted, RetrySuccess};
 use stillwater::validation::ValidateAll;
 use stillwater::{RetryPolicy, TimeoutError, Validation};
 
@@ -347,7 +347,8 @@ fn process_order(
 /// From COMPARISON.md: Retry with exponential backoff
 fn fetch_with_retry(
     url: String,
-) -> impl Effect<Output = RetryExhausted<String>, Error = RetryExhausted<String>, Env = AppEnv> {
+) -> impl Effect<Output = RetrySuccess<String>, Error = RetryExhausted<TimeoutError<String>>, Env = AppEnv>
+{
     retry(
         move || {
             let url = url.clone();
@@ -46,7 +46,8 @@ async fn example_par_all() {
     ];
 
     let start = Instant::now();
-    match par_all(effects, &env).await {
+    let results: Result<Vec<_>, _> = par_all(effects, &env).await;
+    match results {
         Ok(users) => {
             println!("✓ Loaded {} users in {:?}", users.len(), start.elapsed());
             for user in users {
@@ -82,7 +83,8 @@ async fn example_par_all_with_errors() {
         fail(AppError("Network error".into())).boxed(),
     ];
 
-    match par_all(effects, &env).await {
+    let results: Result<Vec<_>, _> = par_all(effects, &env).await;
+    match results {
         Ok(_) => println!("All succeeded"),
         Err(errors) => {
             println!("✓ Collected {} errors:", errors.len());
@@ -247,7 +249,8 @@ async fn example_batch_user_loading() {
         .map(|&id| fetch_user(id, 40).boxed())
         .collect();
 
-    match par_all(user_effects, &env).await {
+    let results: Result<Vec<_>, _> = par_all(user_effects, &env).await;
+    match results {
         Ok(users) => {
             println!("✓ Loaded {} users in {:?}", users.len(), start.elapsed());
             for user in &users {
@@ -285,7 +288,8 @@ async fn example_graceful_degradation() {
             fail(AppError("Analytics unavailable".into())).boxed(), // This one fails
         ];
 
-        match par_all(optional_effects, &env).await {
+        let results: Result<Vec<_>, _> = par_all(optional_effects, &env).await;
+        match results {
             Ok(_features) => {
                 println!("✓ Core data loaded, all features available");
             }
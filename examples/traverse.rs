@@ -215,7 +215,7 @@ async fn example_effect_traverse() {
 
     let numbers = vec![1, 2, 3, 4, 5];
     println!("Processing numbers with effect:");
-    let effect = traverse_effect(numbers, process_number);
+    let effect: BoxedEffect<Vec<_>, _, _> = traverse_effect(numbers, process_number);
     match effect.run_standalone().await {
         Ok(results) => println!("  Results: {:?}", results),
         Err(error) => println!("  Error: {}", error),
@@ -235,7 +235,7 @@ async fn example_effect_traverse() {
 
     let mixed = vec![1, 2, -3, 4];
     println!("\nProcessing with validation (fail-fast):");
-    let effect = traverse_effect(mixed, validate_and_process);
+    let effect: BoxedEffect<Vec<_>, _, _> = traverse_effect(mixed, validate_and_process);
     match effect.run_standalone().await {
         Ok(results) => println!("  Results: {:?}", results),
         Err(error) => println!("  Error (stopped at first): {}", error),
@@ -280,7 +280,7 @@ async fn example_batch_file_processing() {
     ];
 
     println!("Reading files:");
-    let effect = traverse_effect(files, read_file);
+    let effect: BoxedEffect<Vec<_>, _, _> = traverse_effect(files, read_file);
     match effect.run_standalone().await {
         Ok(contents) => {
             println!("  Read {} files:", contents.len());
@@ -298,7 +298,7 @@ async fn example_batch_file_processing() {
     ];
 
     println!("\nReading mixed files (fail-fast):");
-    let effect = traverse_effect(mixed_files, read_file);
+    let effect: BoxedEffect<Vec<_>, _, _> = traverse_effect(mixed_files, read_file);
     match effect.run_standalone().await {
         Ok(contents) => println!("  Read: {:?}", contents),
         Err(error) => println!("  Error: {}", error),
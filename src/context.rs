@@ -40,6 +40,8 @@
 use std::error::Error as StdError;
 use std::fmt;
 
+use crate::effect::request_context::current_request_id;
+
 /// An error wrapper that accumulates context as it propagates
 ///
 /// `ContextError<E>` wraps an underlying error of type `E` and maintains a trail
@@ -61,10 +63,15 @@ use std::fmt;
 /// //   -> connecting to database
 /// //     -> initializing user service
 /// ```
+///
+/// Errors created or given context while an effect runs with a
+/// [`RequestContext`](crate::effect::request_context::RequestContext)
+/// record its request id, available from [`request_id`](Self::request_id).
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ContextError<E> {
     error: E,
     context: Vec<String>,
+    request_id: Option<String>,
 }
 
 impl<E> ContextError<E> {
@@ -86,6 +93,7 @@ impl<E> ContextError<E> {
         ContextError {
             error,
             context: Vec::new(),
+            request_id: current_request_id(),
         }
     }
 
@@ -107,6 +115,9 @@ impl<E> ContextError<E> {
     /// ```
     pub fn context(mut self, msg: impl Into<String>) -> Self {
         self.context.push(msg.into());
+        if self.request_id.is_none() {
+            self.request_id = current_request_id();
+        }
         self
     }
 
@@ -144,6 +155,33 @@ impl<E> ContextError<E> {
         self.error
     }
 
+    /// Transform the underlying error, keeping the context trail and
+    /// request id
+    ///
+    /// Use this to convert the wrapped error into a higher-level type
+    /// without unwrapping it, which would drop the context. When `f` keeps
+    /// the old error as its `source()`, the full chain stays reachable
+    /// from the `ContextError`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stillwater::ContextError;
+    ///
+    /// let err = ContextError::new(404).context("fetching user");
+    /// let err = err.map_inner(|code| format!("status {}", code));
+    ///
+    /// assert_eq!(err.inner(), "status 404");
+    /// assert_eq!(err.context_trail(), &["fetching user"]);
+    /// ```
+    pub fn map_inner<F>(self, f: impl FnOnce(E) -> F) -> ContextError<F> {
+        ContextError {
+            error: f(self.error),
+            context: self.context,
+            request_id: self.request_id,
+        }
+    }
+
     /// Get the context trail
     ///
     /// Returns a slice of all context messages in the order they were added.
@@ -162,12 +200,31 @@ impl<E> ContextError<E> {
     pub fn context_trail(&self) -> &[String] {
         &self.context
     }
+
+    /// Get the id of the request during which the error was created or
+    /// first given context
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stillwater::ContextError;
+    ///
+    /// // Outside of a request context, no id is recorded
+    /// let err = ContextError::new("error").context("step 1");
+    /// assert_eq!(err.request_id(), None);
+    /// ```
+    pub fn request_id(&self) -> Option<&str> {
+        self.request_id.as_deref()
+    }
 }
 
 impl<E: fmt::Display> fmt::Display for ContextError<E> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         // Display underlying error
         write!(f, "Error: {}", self.error)?;
+        if let Some(request_id) = &self.request_id {
+            write!(f, " [request {}]", request_id)?;
+        }
 
         // Display context trail with indentation
         for ctx in &self.context {
@@ -282,6 +339,36 @@ mod tests {
         assert!(ctx_err.source().is_some());
     }
 
+    #[test]
+    fn test_map_inner_keeps_source_chain() {
+        use std::error::Error;
+
+        #[derive(Debug)]
+        struct Wrapper(std::io::Error);
+
+        impl fmt::Display for Wrapper {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("loading failed")
+            }
+        }
+
+        impl Error for Wrapper {
+            fn source(&self) -> Option<&(dyn Error + 'static)> {
+                Some(&self.0)
+            }
+        }
+
+        let err = ContextError::new(std::io::Error::other("disk offline"))
+            .context("reading config")
+            .map_inner(Wrapper);
+
+        let chain: Vec<String> = std::iter::successors(err.source(), |e| (*e).source())
+            .map(|e| e.to_string())
+            .collect();
+        assert_eq!(chain, ["loading failed", "disk offline"]);
+        assert_eq!(err.context_trail(), &["reading config"]);
+    }
+
     #[test]
     fn test_debug_format() {
         let err = ContextError::new("error").context("context");
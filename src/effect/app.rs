@@ -0,0 +1,239 @@
+//! The `define_app_effect!` macro.
+
+/// Define an application's effect vocabulary for a fixed error and
+/// environment.
+///
+/// Application code usually runs every effect with the same error type and
+/// the same environment, yet spells out
+/// `impl Effect<Output = T, Error = AppError, Env = AppEnv>` and
+/// `pure::<_, AppError, AppEnv>(..)` at every turn. This macro generates,
+/// in the module where it is invoked:
+///
+/// - a trait `AppEffect<T>`, implemented by every effect with that error
+///   and environment, to write `impl AppEffect<T>` in signatures;
+/// - a type alias `BoxedAppEffect<T>` for
+///   `BoxedEffect<T, AppError, AppEnv>`;
+/// - optionally, a module of constructors with the error and environment
+///   filled in: `pure`, `fail`, `from_fn`, `from_async`, `from_result`,
+///   `from_validation`, and `asks`.
+///
+/// The generated items take the visibility written before the trait name,
+/// so a crate can define them once and re-export them.
+///
+/// Inside a function returning `impl AppEffect<T>`, the crate's own
+/// constructors infer the error and environment from the return type; the
+/// helper module is for the places where nothing pins them down, such as
+/// `let` bindings and closures. It imports the invoking module's items, so
+/// the error and environment types may be named as they are there; with
+/// `helpers`, invoke the macro at module level rather than inside a
+/// function body.
+///
+/// # Example
+///
+/// ```rust
+/// use stillwater::effect::define_app_effect;
+/// use stillwater::effect::prelude::*;
+///
+/// #[derive(Debug, PartialEq)]
+/// pub enum AppError {
+///     NotFound(u64),
+/// }
+///
+/// pub struct AppEnv {
+///     greeting: &'static str,
+/// }
+///
+/// define_app_effect!(pub AppEffect {
+///     boxed: BoxedAppEffect,
+///     error: AppError,
+///     env: AppEnv,
+///     helpers: app,
+/// });
+///
+/// fn greet(id: u64) -> impl AppEffect<String> {
+///     app::asks(|env| env.greeting).and_then(move |greeting| {
+///         if id == 0 {
+///             app::fail(AppError::NotFound(id)).boxed()
+///         } else {
+///             pure(format!("{}, user {}", greeting, id)).boxed()
+///         }
+///     })
+/// }
+///
+/// fn greet_both(first: u64, second: u64) -> BoxedAppEffect<(String, String)> {
+///     greet(first).zip(greet(second)).boxed()
+/// }
+///
+/// # fn main() {
+/// # tokio_test::block_on(async {
+/// let env = AppEnv { greeting: "hello" };
+/// assert_eq!(greet(7).run(&env).await, Ok("hello, user 7".to_string()));
+/// assert_eq!(greet_both(1, 0).run(&env).await, Err(AppError::NotFound(0)));
+/// # });
+/// # }
+/// ```
+#[macro_export]
+macro_rules! define_app_effect {
+    (
+        $(#[$meta:meta])*
+        $vis:vis $effect:ident {
+            boxed: $boxed:ident,
+            error: $error:ty,
+            env: $env:ty
+            $(, helpers: $helpers:ident)?
+            $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis trait $effect<T>:
+            $crate::effect::Effect<Output = T, Error = $error, Env = $env>
+        {
+        }
+
+        impl<T, Eff> $effect<T> for Eff where
+            Eff: $crate::effect::Effect<Output = T, Error = $error, Env = $env>
+        {
+        }
+
+        #[allow(dead_code)]
+        $vis type $boxed<T> = $crate::effect::BoxedEffect<T, $error, $env>;
+
+        $(
+            #[allow(dead_code)]
+            $vis mod $helpers {
+                #[allow(unused_imports)]
+                use super::*;
+
+                /// Effect that succeeds with `value`.
+                pub fn pure<T: Send>(value: T) -> $crate::effect::Pure<T, $error, $env> {
+                    $crate::effect::pure(value)
+                }
+
+                /// Effect that fails with `error`.
+                pub fn fail<T: Send>(error: $error) -> $crate::effect::Fail<T, $error, $env> {
+                    $crate::effect::fail(error)
+                }
+
+                /// Effect from a synchronous function of the environment.
+                pub fn from_fn<T, F>(f: F) -> $crate::effect::FromFn<F, $env>
+                where
+                    T: Send,
+                    F: FnOnce(&$env) -> ::std::result::Result<T, $error> + Send,
+                {
+                    $crate::effect::from_fn(f)
+                }
+
+                /// Effect from an async function of the environment.
+                pub fn from_async<T, F, Fut>(f: F) -> $crate::effect::FromAsync<F, $env>
+                where
+                    T: Send,
+                    F: FnOnce(&$env) -> Fut + Send,
+                    Fut: ::std::future::Future<Output = ::std::result::Result<T, $error>> + Send,
+                {
+                    $crate::effect::from_async(f)
+                }
+
+                /// Effect that returns `result`.
+                pub fn from_result<T: Send>(
+                    result: ::std::result::Result<T, $error>,
+                ) -> $crate::effect::FromResult<T, $error, $env> {
+                    $crate::effect::from_result(result)
+                }
+
+                /// Effect that returns the outcome of `validation`.
+                pub fn from_validation<T: Send>(
+                    validation: $crate::Validation<T, $error>,
+                ) -> $crate::effect::FromResult<T, $error, $env> {
+                    $crate::effect::from_validation(validation)
+                }
+
+                /// Effect that reads a value from the environment.
+                pub fn asks<U, F>(f: F) -> $crate::effect::Asks<F, $error, $env>
+                where
+                    U: Send,
+                    F: FnOnce(&$env) -> U + Send,
+                {
+                    $crate::effect::asks(f)
+                }
+            }
+        )?
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::effect::prelude::*;
+    use crate::Validation;
+
+    #[derive(Debug, PartialEq)]
+    enum Error {
+        Missing(&'static str),
+        Invalid(Vec<&'static str>),
+    }
+
+    struct Env {
+        name: Option<&'static str>,
+    }
+
+    define_app_effect!(TestEffect {
+        boxed: BoxedTestEffect,
+        error: Error,
+        env: Env,
+        helpers: test_app,
+    });
+
+    fn name() -> impl TestEffect<&'static str> {
+        test_app::asks(|env| env.name)
+            .and_then(|name| test_app::from_result(name.ok_or(Error::Missing("name"))))
+    }
+
+    fn shout() -> BoxedTestEffect<String> {
+        name().map(str::to_uppercase).boxed()
+    }
+
+    #[tokio::test]
+    async fn test_generated_items_fix_error_and_env() {
+        let env = Env { name: Some("ada") };
+        assert_eq!(shout().run(&env).await, Ok("ADA".to_string()));
+
+        let anonymous = Env { name: None };
+        assert_eq!(shout().run(&anonymous).await, Err(Error::Missing("name")));
+    }
+
+    #[tokio::test]
+    async fn test_return_type_infers_plain_constructors() {
+        fn answer() -> impl TestEffect<u32> {
+            pure(42)
+        }
+
+        fn checked(n: u32) -> impl TestEffect<u32> {
+            test_app::from_validation(if n > 10 {
+                Validation::success(n)
+            } else {
+                Validation::failure(Error::Invalid(vec!["too small"]))
+            })
+        }
+
+        let env = Env { name: None };
+        assert_eq!(answer().and_then(checked).run(&env).await, Ok(42));
+        assert_eq!(
+            test_app::pure(3).and_then(checked).run(&env).await,
+            Err(Error::Invalid(vec!["too small"]))
+        );
+    }
+
+    #[test]
+    fn test_helpers_are_optional() {
+        define_app_effect!(Bare {
+            boxed: BoxedBare,
+            error: String,
+            env: (),
+        });
+
+        fn bare() -> impl Bare<()> {
+            fail("no helpers".to_string())
+        }
+
+        let _: BoxedBare<()> = bare().boxed();
+    }
+}
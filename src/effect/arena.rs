@@ -0,0 +1,420 @@
+//! Arena allocation for boxed effects.
+//!
+//! This module provides [`EffectArena`], a bump allocator, and
+//! [`ArenaEffect`], a type-erased effect stored in the arena instead of in
+//! an individual heap allocation. Feature-gated behind
+//! `#[cfg(feature = "allocator_api")]` and requires nightly Rust.
+//!
+//! Use this for workloads that must box heavily, such as dynamically built
+//! pipelines that create thousands of short-lived boxed effects. Memory is
+//! reclaimed all at once when the arena is reset or dropped.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! #![feature(allocator_api)]
+//! use stillwater::effect::arena::{EffectArena, EffectArenaExt};
+//! use stillwater::effect::prelude::*;
+//!
+//! let arena = EffectArena::new();
+//! let effects: Vec<_> = (0..1000)
+//!     .map(|i| pure::<_, String, ()>(i).map(|x| x * 2).boxed_in(&arena))
+//!     .collect();
+//!
+//! for effect in effects {
+//!     effect.execute(&()).await?;
+//! }
+//! ```
+
+use std::alloc::{AllocError, Allocator, Global, Layout};
+use std::future::Future;
+use std::marker::PhantomData;
+use std::ptr::NonNull;
+use std::sync::Mutex;
+
+use crate::effect::boxed::BoxFuture;
+use crate::effect::trait_def::Effect;
+
+/// Default size of each arena chunk in bytes.
+const DEFAULT_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Alignment of every chunk; larger alignments are handled by padding.
+const CHUNK_ALIGN: usize = 16;
+
+/// The second argument ties the future's lifetime `'e` to the arena's: it
+/// can only exist when `'a: 'e`.
+type ArenaRunFn<'a, T, E, Env> = Box<
+    dyn for<'e> FnOnce(&'e Env, PhantomData<&'e &'a ()>) -> BoxFuture<'e, Result<T, E>> + Send + 'a,
+    &'a EffectArena,
+>;
+
+/// A bump allocator for boxed effects.
+///
+/// Allocations are carved out of large chunks and never freed individually;
+/// deallocation is a no-op. All memory is released when the arena is
+/// dropped, or recycled with [`reset`](EffectArena::reset).
+///
+/// The arena is `Sync`, so effects allocated in it remain `Send`.
+pub struct EffectArena {
+    chunk_size: usize,
+    state: Mutex<ArenaState>,
+}
+
+struct ArenaState {
+    chunks: Vec<Chunk>,
+    offset: usize,
+    allocations: usize,
+    allocated_bytes: usize,
+}
+
+/// A block of memory obtained from the global allocator.
+struct Chunk {
+    ptr: NonNull<u8>,
+    layout: Layout,
+}
+
+impl Chunk {
+    fn new(size: usize) -> Result<Self, AllocError> {
+        let layout = Layout::from_size_align(size, CHUNK_ALIGN).map_err(|_| AllocError)?;
+        let ptr = Global.allocate(layout)?.cast::<u8>();
+        Ok(Chunk { ptr, layout })
+    }
+
+    fn len(&self) -> usize {
+        self.layout.size()
+    }
+}
+
+impl Drop for Chunk {
+    fn drop(&mut self) {
+        // SAFETY: `ptr` was allocated by `Global` with exactly this layout.
+        unsafe { Global.deallocate(self.ptr, self.layout) }
+    }
+}
+
+// SAFETY: a chunk uniquely owns its memory; access is serialized by the
+// arena's mutex.
+unsafe impl Send for Chunk {}
+
+impl std::fmt::Debug for EffectArena {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        f.debug_struct("EffectArena")
+            .field("chunk_size", &self.chunk_size)
+            .field("chunks", &state.chunks.len())
+            .field("allocations", &state.allocations)
+            .field("allocated_bytes", &state.allocated_bytes)
+            .finish()
+    }
+}
+
+impl Default for EffectArena {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EffectArena {
+    /// Create an arena with the default chunk size (64 KiB).
+    pub fn new() -> Self {
+        Self::with_chunk_size(DEFAULT_CHUNK_SIZE)
+    }
+
+    /// Create an arena that allocates chunks of the given size.
+    ///
+    /// Allocations larger than the chunk size get a dedicated chunk.
+    pub fn with_chunk_size(chunk_size: usize) -> Self {
+        EffectArena {
+            chunk_size: chunk_size.max(1),
+            state: Mutex::new(ArenaState {
+                chunks: Vec::new(),
+                offset: 0,
+                allocations: 0,
+                allocated_bytes: 0,
+            }),
+        }
+    }
+
+    /// Number of allocations served since creation or the last reset.
+    pub fn allocations(&self) -> usize {
+        self.lock().allocations
+    }
+
+    /// Number of bytes handed out since creation or the last reset.
+    pub fn allocated_bytes(&self) -> usize {
+        self.lock().allocated_bytes
+    }
+
+    /// Number of chunks currently owned by the arena.
+    pub fn chunks(&self) -> usize {
+        self.lock().chunks.len()
+    }
+
+    /// Release all allocations, keeping only the most recent chunk for reuse.
+    ///
+    /// Requires `&mut self`, so no effect allocated in the arena can still
+    /// be alive.
+    pub fn reset(&mut self) {
+        let state = self.state.get_mut().unwrap_or_else(|e| e.into_inner());
+        if let Some(last) = state.chunks.pop() {
+            state.chunks.clear();
+            state.chunks.push(last);
+        }
+        state.offset = 0;
+        state.allocations = 0;
+        state.allocated_bytes = 0;
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, ArenaState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+unsafe impl Allocator for EffectArena {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        if layout.size() == 0 {
+            return Global.allocate(layout);
+        }
+
+        let mut state = self.lock();
+        let offset = state.offset;
+        let fits = state.chunks.last().and_then(|chunk| {
+            let base = chunk.ptr.as_ptr() as usize;
+            let start = (base + offset).checked_next_multiple_of(layout.align())? - base;
+            let end = start.checked_add(layout.size())?;
+            (end <= chunk.len()).then_some(start)
+        });
+
+        let start = match fits {
+            Some(start) => start,
+            None => {
+                let needed = layout
+                    .size()
+                    .checked_add(layout.align())
+                    .ok_or(AllocError)?;
+                let chunk = Chunk::new(self.chunk_size.max(needed))?;
+                let base = chunk.ptr.as_ptr() as usize;
+                state.chunks.push(chunk);
+                base.next_multiple_of(layout.align()) - base
+            }
+        };
+
+        state.offset = start + layout.size();
+        state.allocations += 1;
+        state.allocated_bytes += layout.size();
+
+        let chunk = state.chunks.last().ok_or(AllocError)?;
+        // SAFETY: `start + layout.size()` is within the chunk, checked above.
+        let ptr = unsafe { chunk.ptr.add(start) };
+        Ok(NonNull::slice_from_raw_parts(ptr, layout.size()))
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        if layout.size() == 0 {
+            // SAFETY: zero-sized allocations were delegated to `Global`.
+            unsafe { Global.deallocate(ptr, layout) }
+        }
+        // Non-empty allocations are reclaimed when the arena is reset or dropped.
+    }
+}
+
+/// A type-erased effect allocated in an [`EffectArena`].
+///
+/// Behaves like [`BoxedEffect`](crate::effect::BoxedEffect), but the stored
+/// effect lives in the arena, and it is bounded by the arena's lifetime
+/// rather than `'static`. The environment is borrowed when the effect is
+/// run.
+///
+/// The future produced by [`run`](Effect::run) is still boxed on the global
+/// heap: pinned memory must not be reclaimed without running its destructor,
+/// which a resettable arena cannot guarantee.
+///
+/// Created by [`EffectArenaExt::boxed_in`].
+pub struct ArenaEffect<'a, T, E, Env> {
+    run_fn: ArenaRunFn<'a, T, E, Env>,
+}
+
+impl<T, E, Env> std::fmt::Debug for ArenaEffect<'_, T, E, Env> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ArenaEffect")
+            .field("run_fn", &"<function>")
+            .finish()
+    }
+}
+
+impl<'a, T, E, Env> ArenaEffect<'a, T, E, Env>
+where
+    T: Send + 'a,
+    E: Send + 'a,
+    Env: Send + Sync + 'a,
+{
+    /// Allocate an effect in the given arena.
+    pub fn new<Eff>(effect: Eff, arena: &'a EffectArena) -> Self
+    where
+        Eff: Effect<Output = T, Error = E, Env = Env> + 'a,
+    {
+        let run_fn: ArenaRunFn<'a, T, E, Env> = Box::new_in(
+            move |env: &Env, _: PhantomData<&&'a ()>| -> BoxFuture<'_, Result<T, E>> {
+                Box::pin(effect.run(env))
+            },
+            arena,
+        );
+        ArenaEffect { run_fn }
+    }
+}
+
+impl<'a, T, E, Env> Effect for ArenaEffect<'a, T, E, Env>
+where
+    T: Send,
+    E: Send,
+    Env: Send + Sync,
+{
+    type Output = T;
+    type Error = E;
+    type Env = Env;
+
+    fn run(self, env: &Env) -> impl Future<Output = Result<T, E>> + Send {
+        // The boxed future lives as long as the shorter of the arena and
+        // `env`, which has no name here; the async block carries both.
+        async move { (self.run_fn)(env, PhantomData).await }
+    }
+}
+
+/// Extension trait for allocating effects in an [`EffectArena`].
+///
+/// This trait is only available when the `allocator_api` feature is enabled.
+pub trait EffectArenaExt: Effect {
+    /// Convert to an arena-allocated effect for type erasure.
+    ///
+    /// Use this instead of [`boxed`](crate::effect::EffectExt::boxed) when
+    /// creating many short-lived boxed effects.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let arena = EffectArena::new();
+    /// let effects: Vec<ArenaEffect<'_, i32, String, ()>> = vec![
+    ///     pure(1).boxed_in(&arena),
+    ///     pure(2).map(|x| x * 2).boxed_in(&arena),
+    /// ];
+    /// ```
+    fn boxed_in<'a>(
+        self,
+        arena: &'a EffectArena,
+    ) -> ArenaEffect<'a, Self::Output, Self::Error, Self::Env>
+    where
+        Self: 'a,
+        Self::Output: 'a,
+        Self::Error: 'a,
+        Self::Env: 'a,
+    {
+        ArenaEffect::new(self, arena)
+    }
+}
+
+impl<E: Effect> EffectArenaExt for E {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::effect::constructors::{asks, fail, pure};
+    use crate::effect::ext::EffectExt;
+
+    #[tokio::test]
+    async fn test_boxed_in_runs_effect() {
+        let arena = EffectArena::new();
+        let effect = pure::<_, String, ()>(21).map(|x| x * 2).boxed_in(&arena);
+
+        assert_eq!(effect.execute(&()).await, Ok(42));
+    }
+
+    #[tokio::test]
+    async fn test_boxed_in_borrows_env() {
+        struct Db {
+            rows: Vec<i32>,
+        }
+
+        let arena = EffectArena::new();
+        let effect = asks::<_, String, Db, _>(|db: &Db| db.rows.len()).boxed_in(&arena);
+
+        let db = Db {
+            rows: vec![1, 2, 3],
+        };
+        assert_eq!(effect.execute(&db).await, Ok(3));
+    }
+
+    #[tokio::test]
+    async fn test_boxed_in_propagates_error() {
+        let arena = EffectArena::new();
+        let effect = fail::<i32, _, ()>("boom".to_string()).boxed_in(&arena);
+
+        assert_eq!(effect.execute(&()).await, Err("boom".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_boxed_in_allows_collection_storage() {
+        let arena = EffectArena::new();
+        let effects: Vec<ArenaEffect<'_, i32, String, ()>> = vec![
+            pure(1).boxed_in(&arena),
+            pure(2).map(|x| x * 2).boxed_in(&arena),
+            pure(3).and_then(|x| pure(x * 3)).boxed_in(&arena),
+        ];
+
+        let mut results = Vec::new();
+        for effect in effects {
+            results.push(effect.execute(&()).await.unwrap());
+        }
+        assert_eq!(results, vec![1, 4, 9]);
+    }
+
+    #[tokio::test]
+    async fn test_many_effects_share_chunks() {
+        let arena = EffectArena::new();
+        let effects: Vec<_> = (0..1000)
+            .map(|i| pure::<_, String, ()>(i).map(|x| x + 1).boxed_in(&arena))
+            .collect();
+
+        let mut sum = 0;
+        for effect in effects {
+            sum += effect.execute(&()).await.unwrap();
+        }
+
+        assert_eq!(sum, (1..=1000).sum::<i32>());
+        assert_eq!(arena.allocations(), 1000);
+        assert!(arena.chunks() < 10);
+    }
+
+    #[test]
+    fn test_allocations_are_aligned() {
+        let arena = EffectArena::with_chunk_size(128);
+        for align in [1, 2, 4, 8, 16, 32, 64] {
+            let layout = Layout::from_size_align(3, align).unwrap();
+            let ptr = arena.allocate(layout).unwrap();
+            assert_eq!(ptr.as_ptr() as *mut u8 as usize % align, 0);
+        }
+    }
+
+    #[test]
+    fn test_oversized_allocation_gets_own_chunk() {
+        let arena = EffectArena::with_chunk_size(16);
+        let layout = Layout::from_size_align(100, 8).unwrap();
+        let ptr = arena.allocate(layout).unwrap();
+
+        assert_eq!(ptr.len(), 100);
+        assert_eq!(arena.chunks(), 1);
+    }
+
+    #[test]
+    fn test_reset_reuses_last_chunk() {
+        let mut arena = EffectArena::with_chunk_size(64);
+        let layout = Layout::from_size_align(48, 8).unwrap();
+        arena.allocate(layout).unwrap();
+        arena.allocate(layout).unwrap();
+        assert_eq!(arena.chunks(), 2);
+
+        arena.reset();
+        assert_eq!(arena.chunks(), 1);
+        assert_eq!(arena.allocations(), 0);
+        assert_eq!(arena.allocated_bytes(), 0);
+    }
+}
@@ -0,0 +1,287 @@
+//! Effects whose output borrows from the environment.
+//!
+//! [`Effect::Output`] has no lifetime, so an effect can only hand back data
+//! it owns: reading a multi-megabyte blob out of an in-memory cache in the
+//! environment means cloning it, even when the caller only needs to hash
+//! it, measure it, or parse a few bytes. The `Output` of [`EffectRef`] is
+//! a generic associated type parameterised by the lifetime of the borrowed
+//! environment, so it can be `&'env Config` or `&'env [u8]` instead.
+//!
+//! Borrowed outputs cannot outlive the `run_ref` call that produced them,
+//! which rules out the parts of the API that store or move results:
+//! boxing, the parallel functions, retries, and so on. `EffectRef` is
+//! therefore a separate, smaller trait. Use [`asks_ref`] to borrow from the
+//! environment and then either:
+//!
+//! - call [`run_ref`](EffectRef::run_ref) directly on a hot path, and use the
+//!   borrowed value in place; or
+//! - turn the borrow into an owned value or a follow-up effect with
+//!   [`map_borrowed`](EffectRefExt::map_borrowed) or
+//!   [`and_then_borrowed`](EffectRefExt::and_then_borrowed), which produce an
+//!   ordinary [`Effect`] that composes with everything else.
+//!
+//! # Example
+//!
+//! ```rust
+//! use stillwater::effect::borrowed::{asks_ref, EffectRef, EffectRefExt};
+//! use stillwater::effect::prelude::*;
+//!
+//! struct Env {
+//!     blob: Vec<u8>,
+//! }
+//!
+//! # tokio_test::block_on(async {
+//! let env = Env { blob: vec![7; 4 * 1024 * 1024] };
+//!
+//! // Borrow the blob in place, without copying four megabytes.
+//! let blob: &[u8] = asks_ref::<_, String, _, _>(|env: &Env| env.blob.as_slice())
+//!     .run_ref(&env)
+//!     .await
+//!     .unwrap();
+//! assert_eq!(blob.len(), 4 * 1024 * 1024);
+//!
+//! // Or derive an owned value from it and keep composing.
+//! let checksum = asks_ref::<_, String, _, _>(|env: &Env| env.blob.as_slice())
+//!     .map_borrowed(|blob: &[u8]| blob.iter().map(|&b| u64::from(b)).sum::<u64>())
+//!     .map(|sum| sum % 1000);
+//! assert_eq!(checksum.run(&env).await, Ok(29_360_128 % 1000));
+//! # });
+//! ```
+
+use std::future::Future;
+use std::marker::PhantomData;
+
+use crate::effect::trait_def::Effect;
+
+/// An effect whose output may borrow from the environment it runs in.
+///
+/// See the [module documentation](self) for how this relates to
+/// [`Effect`].
+pub trait EffectRef: Sized + Send {
+    /// The success type, borrowing from an environment that lives for
+    /// `'env`.
+    type Output<'env>: Send
+    where
+        Self::Env: 'env;
+
+    /// The error type.
+    type Error: Send;
+
+    /// The environment type.
+    type Env: Send + Sync;
+
+    /// Run this effect, returning output that borrows from `env`.
+    fn run_ref<'env>(
+        self,
+        env: &'env Self::Env,
+    ) -> impl Future<Output = Result<Self::Output<'env>, Self::Error>> + Send;
+}
+
+/// Borrow a value from the environment.
+///
+/// Like [`asks`](crate::effect::asks), but the query returns a reference
+/// into the environment instead of an owned value.
+pub fn asks_ref<U, E, Env, F>(f: F) -> AsksRef<F, E, Env>
+where
+    F: FnOnce(&Env) -> &U + Send,
+    U: ?Sized + Sync + 'static,
+    E: Send,
+    Env: Send + Sync,
+{
+    AsksRef {
+        f,
+        _phantom: PhantomData,
+    }
+}
+
+/// Borrows a value from the environment.
+///
+/// Created by [`asks_ref`].
+pub struct AsksRef<F, E, Env> {
+    pub(crate) f: F,
+    _phantom: PhantomData<fn() -> (E, Env)>,
+}
+
+impl<F, E, Env> std::fmt::Debug for AsksRef<F, E, Env> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AsksRef").field("f", &"<function>").finish()
+    }
+}
+
+impl<F, U, E, Env> EffectRef for AsksRef<F, E, Env>
+where
+    F: FnOnce(&Env) -> &U + Send,
+    U: ?Sized + Sync + 'static,
+    E: Send,
+    Env: Send + Sync,
+{
+    type Output<'env>
+        = &'env U
+    where
+        Env: 'env;
+    type Error = E;
+    type Env = Env;
+
+    fn run_ref(self, env: &Env) -> impl Future<Output = Result<&U, E>> + Send {
+        std::future::ready(Ok((self.f)(env)))
+    }
+}
+
+/// Combinators for [`EffectRef`] that end the borrow.
+pub trait EffectRefExt: EffectRef {
+    /// Turn the borrowed output into an owned value.
+    ///
+    /// `f` must accept the output for any lifetime, so closures need their
+    /// argument type written out, as in `|config: &Config| ...`.
+    fn map_borrowed<F, U>(self, f: F) -> MapBorrowed<Self, F>
+    where
+        F: for<'env> FnOnce(Self::Output<'env>) -> U + Send,
+        U: Send,
+    {
+        MapBorrowed { inner: self, f }
+    }
+
+    /// Choose the next effect from the borrowed output.
+    fn and_then_borrowed<F, E2>(self, f: F) -> AndThenBorrowed<Self, F>
+    where
+        F: for<'env> FnOnce(Self::Output<'env>) -> E2 + Send,
+        E2: Effect<Error = Self::Error, Env = Self::Env>,
+    {
+        AndThenBorrowed { inner: self, f }
+    }
+}
+
+impl<E: EffectRef> EffectRefExt for E {}
+
+/// Turns the borrowed output of an [`EffectRef`] into an owned value.
+///
+/// Created by [`EffectRefExt::map_borrowed`].
+pub struct MapBorrowed<Inner, F> {
+    pub(crate) inner: Inner,
+    pub(crate) f: F,
+}
+
+impl<Inner, F> std::fmt::Debug for MapBorrowed<Inner, F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MapBorrowed")
+            .field("inner", &"<effect>")
+            .field("f", &"<function>")
+            .finish()
+    }
+}
+
+impl<Inner, F, U> Effect for MapBorrowed<Inner, F>
+where
+    Inner: EffectRef,
+    F: for<'env> FnOnce(Inner::Output<'env>) -> U + Send,
+    U: Send,
+{
+    type Output = U;
+    type Error = Inner::Error;
+    type Env = Inner::Env;
+
+    async fn run(self, env: &Self::Env) -> Result<U, Self::Error> {
+        let value = self.inner.run_ref(env).await?;
+        Ok((self.f)(value))
+    }
+}
+
+/// Chooses the next effect from the borrowed output of an [`EffectRef`].
+///
+/// Created by [`EffectRefExt::and_then_borrowed`].
+pub struct AndThenBorrowed<Inner, F> {
+    pub(crate) inner: Inner,
+    pub(crate) f: F,
+}
+
+impl<Inner, F> std::fmt::Debug for AndThenBorrowed<Inner, F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AndThenBorrowed")
+            .field("inner", &"<effect>")
+            .field("f", &"<function>")
+            .finish()
+    }
+}
+
+impl<Inner, F, E2> Effect for AndThenBorrowed<Inner, F>
+where
+    Inner: EffectRef,
+    F: for<'env> FnOnce(Inner::Output<'env>) -> E2 + Send,
+    E2: Effect<Error = Inner::Error, Env = Inner::Env>,
+{
+    type Output = E2::Output;
+    type Error = Inner::Error;
+    type Env = Inner::Env;
+
+    async fn run(self, env: &Self::Env) -> Result<E2::Output, Self::Error> {
+        let value = self.inner.run_ref(env).await?;
+        (self.f)(value).run(env).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::effect::constructors::{asks, fail};
+    use crate::effect::ext::EffectExt;
+
+    struct Config {
+        name: String,
+        limit: usize,
+    }
+
+    struct Env {
+        config: Config,
+        blob: Vec<u8>,
+    }
+
+    fn env() -> Env {
+        Env {
+            config: Config {
+                name: "svc".to_string(),
+                limit: 2,
+            },
+            blob: vec![1, 2, 3],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run_ref_borrows_without_copying() {
+        let env = env();
+
+        let config = asks_ref::<_, String, _, _>(|env: &Env| &env.config)
+            .run_ref(&env)
+            .await
+            .unwrap();
+        let blob = asks_ref::<_, String, _, _>(|env: &Env| env.blob.as_slice())
+            .run_ref(&env)
+            .await
+            .unwrap();
+
+        assert!(std::ptr::eq(config, &env.config));
+        assert_eq!(blob.as_ptr(), env.blob.as_ptr());
+    }
+
+    #[tokio::test]
+    async fn test_map_borrowed_composes_as_an_effect() {
+        let effect = asks_ref::<_, String, _, _>(|env: &Env| &env.config)
+            .map_borrowed(|config: &Config| config.name.len())
+            .and_then(|n| asks(move |env: &Env| n + env.blob.len()));
+
+        assert_eq!(effect.run(&env()).await, Ok(6));
+    }
+
+    #[tokio::test]
+    async fn test_and_then_borrowed_picks_next_effect() {
+        let effect = asks_ref::<_, String, _, _>(|env: &Env| env.blob.as_slice())
+            .and_then_borrowed(|blob: &[u8]| {
+                if blob.len() > 2 {
+                    fail("too large".to_string()).boxed()
+                } else {
+                    asks(|env: &Env| env.config.limit).boxed()
+                }
+            });
+
+        assert_eq!(effect.run(&env()).await, Err("too large".to_string()));
+    }
+}
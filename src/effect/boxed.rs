@@ -5,18 +5,131 @@
 //! - Return different effects from match arms
 //! - Create recursive effect functions
 //!
-//! Boxing clones the environment to achieve `'static` lifetime.
-//! This is cheap when `Env` contains `Arc`-wrapped resources.
+//! A boxed effect borrows the environment while it runs, just like an
+//! unboxed one, so `Env` does not need to be `Clone`.
 
+use std::any::Any;
 use std::future::Future;
 use std::marker::PhantomData;
 use std::pin::Pin;
 
+#[cfg(feature = "fuel")]
+use crate::effect::fuel;
+use crate::effect::in_child_execution;
 use crate::effect::trait_def::Effect;
 
 /// A boxed future that is Send + 'static
 pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
 
+/// A type-erased intermediate value flowing through a boxed effect chain.
+type ErasedValue = Box<dyn Any + Send>;
+
+/// A type-erased leaf effect: runs against a borrowed environment.
+type ErasedRun<E, Env> =
+    Box<dyn for<'a> FnOnce(&'a Env) -> BoxFuture<'a, Result<ErasedValue, E>> + Send>;
+
+/// A type-erased continuation: turns the previous value into the next chain.
+type Continuation<E, Env> = Box<dyn FnOnce(ErasedValue) -> Chain<E, Env> + Send>;
+
+/// The head of a boxed effect chain.
+enum Head<E, Env> {
+    /// A value that is already available.
+    Pure(ErasedValue),
+    /// An effect that must be run to produce a value.
+    Run(ErasedRun<E, Env>),
+}
+
+/// A single boxed effect whose output type is still known.
+///
+/// Boxing an effect stores it here, so that running it on its own costs
+/// one allocation for the future and none for its output. Only chaining
+/// with [`BoxedEffect::and_then`] or [`BoxedEffect::map`] turns it into an
+/// erased [`Head`].
+trait SingleRun<T, E, Env>: Send {
+    /// Run the effect, boxing only its future.
+    fn run_boxed<'a>(self: Box<Self>, env: &'a Env) -> BoxFuture<'a, Result<T, E>>;
+
+    /// Erase the output type so the effect can head a [`Chain`].
+    fn into_head(self: Box<Self>) -> Head<E, Env>;
+}
+
+struct Single<Eff>(Eff);
+
+impl<Eff> SingleRun<Eff::Output, Eff::Error, Eff::Env> for Single<Eff>
+where
+    Eff: Effect + 'static,
+    Eff::Output: 'static,
+{
+    fn run_boxed<'a>(
+        self: Box<Self>,
+        env: &'a Eff::Env,
+    ) -> BoxFuture<'a, Result<Eff::Output, Eff::Error>> {
+        Box::pin(self.0.run(env))
+    }
+
+    fn into_head(self: Box<Self>) -> Head<Eff::Error, Eff::Env> {
+        erased_head(self.0)
+    }
+}
+
+/// Box `effect` as the head of a chain.
+fn erased_head<Eff>(effect: Eff) -> Head<Eff::Error, Eff::Env>
+where
+    Eff: Effect + 'static,
+    Eff::Output: 'static,
+{
+    Head::Run(Box::new(move |env: &Eff::Env| -> BoxFuture<'_, _> {
+        Box::pin(async move { effect.run(env).await.map(|v| Box::new(v) as ErasedValue) })
+    }))
+}
+
+/// How a [`BoxedEffect`] is stored.
+enum Repr<T, E, Env> {
+    /// One effect, not yet chained.
+    Single(Box<dyn SingleRun<T, E, Env>>),
+    /// A chain of effects with erased intermediate values.
+    Chain(Chain<E, Env>),
+}
+
+/// A flattened sequence of effects: a head followed by continuations.
+///
+/// Chains are executed by a loop in [`run_chain`] rather than by nesting
+/// futures, which keeps both poll depth and drop depth constant no matter
+/// how many `and_then`/`map` steps have been composed.
+struct Chain<E, Env> {
+    head: Head<E, Env>,
+    continuations: Vec<Continuation<E, Env>>,
+}
+
+/// Trampoline executor for boxed effect chains.
+///
+/// Continuations that return another chain have it spliced onto a heap
+/// allocated stack instead of awaiting it recursively.
+async fn run_chain<E, Env>(chain: Chain<E, Env>, env: &Env) -> Result<ErasedValue, E> {
+    let mut pending: Vec<Continuation<E, Env>> = chain.continuations.into_iter().rev().collect();
+    let mut head = chain.head;
+    let mut continued = false;
+
+    loop {
+        let value = match head {
+            Head::Pure(value) => value,
+            Head::Run(run) if continued => in_child_execution(run(env)).await?,
+            Head::Run(run) => run(env).await?,
+        };
+        match pending.pop() {
+            None => return Ok(value),
+            Some(continuation) => {
+                #[cfg(feature = "fuel")]
+                fuel::tick().await;
+                let next = continuation(value);
+                pending.extend(next.continuations.into_iter().rev());
+                head = next.head;
+                continued = true;
+            }
+        }
+    }
+}
+
 /// A type-erased effect.
 ///
 /// Use `BoxedEffect` when you need type erasure for:
@@ -24,8 +137,28 @@ pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
 /// - Returning different effects from match arms
 /// - Creating recursive effect functions
 ///
-/// **Note**: Boxing clones the environment to achieve `'static` lifetime.
-/// This is cheap when `Env` contains `Arc`-wrapped resources.
+/// **Note**: The effect is boxed, not the environment: `run` borrows `Env`
+/// for as long as the returned future is alive, and never clones it.
+///
+/// # Stack Safety
+///
+/// `BoxedEffect` has inherent [`and_then`](BoxedEffect::and_then) and
+/// [`map`](BoxedEffect::map) methods that append to a flat chain instead of
+/// nesting futures. The chain is run by a trampoline, so arbitrarily deep
+/// sequential compositions and recursive effects built from boxed steps run
+/// in constant stack space. For recursion, box the effect *before* chaining
+/// so the recursive call goes through the trampoline.
+///
+/// These methods shadow the [`EffectExt`](crate::effect::EffectExt) ones
+/// and, because each step is boxed, need `Send + 'static` functions and
+/// `'static` outputs. To chain a closure that borrows, call the trait
+/// method explicitly, as in `EffectExt::map(effect, f)`.
+///
+/// # Allocations
+///
+/// Boxing allocates once for the effect, and running a boxed effect on its
+/// own allocates once more for its future. Each step of a chain adds a
+/// boxed continuation and a boxed intermediate value.
 ///
 /// # Example
 ///
@@ -38,27 +171,31 @@ pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
 ///     pure(2).and_then(|x| pure(x * 2)).boxed(),
 /// ];
 ///
-/// // Recursive effect
+/// // Recursive effect (stack-safe)
 /// fn countdown(n: i32) -> BoxedEffect<i32, String, ()> {
 ///     if n <= 0 {
 ///         pure(0).boxed()
 ///     } else {
 ///         pure(n)
-///             .and_then(move |x| countdown(x - 1).map(move |sum| x + sum))
 ///             .boxed()
+///             .and_then(move |x| countdown(x - 1).map(move |sum| x + sum))
 ///     }
 /// }
 /// ```
 pub struct BoxedEffect<T, E, Env> {
-    // Takes OWNED Env (cloned from reference at run time)
-    run_fn: Box<dyn FnOnce(Env) -> BoxFuture<'static, Result<T, E>> + Send>,
-    _phantom: PhantomData<Env>,
+    repr: Repr<T, E, Env>,
+    _phantom: PhantomData<fn() -> (T, Env)>,
 }
 
 impl<T, E, Env> std::fmt::Debug for BoxedEffect<T, E, Env> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let continuations = match &self.repr {
+            Repr::Single(_) => 0,
+            Repr::Chain(chain) => chain.continuations.len(),
+        };
         f.debug_struct("BoxedEffect")
             .field("run_fn", &"<function>")
+            .field("continuations", &continuations)
             .finish()
     }
 }
@@ -67,38 +204,125 @@ impl<T, E, Env> BoxedEffect<T, E, Env>
 where
     T: Send + 'static,
     E: Send + 'static,
-    Env: Clone + Send + Sync + 'static,
+    Env: Send + Sync + 'static,
 {
     /// Create a boxed effect from any effect.
     ///
-    /// The environment will be cloned when the effect is run.
+    /// Boxing an effect that is already a `BoxedEffect` returns it
+    /// unchanged.
     pub fn new<Eff>(effect: Eff) -> Self
     where
         Eff: Effect<Output = T, Error = E, Env = Env> + 'static,
     {
+        let mut slot = Some(effect);
+        if let Some(boxed) = (&mut slot as &mut dyn Any).downcast_mut::<Option<Self>>() {
+            return boxed.take().expect("slot is filled above");
+        }
+        let effect = slot.expect("slot is only taken for BoxedEffect");
+
+        BoxedEffect {
+            repr: Repr::Single(Box::new(Single(effect))),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Chain a dependent effect without nesting futures.
+    ///
+    /// Behaves like [`EffectExt::and_then`](crate::effect::EffectExt::and_then),
+    /// but stays boxed and appends to a flat chain executed by a trampoline,
+    /// so deep or recursive chains don't grow the stack. When the function
+    /// returns a `BoxedEffect`, its chain is spliced in directly.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use stillwater::effect::prelude::*;
+    ///
+    /// # tokio_test::block_on(async {
+    /// let mut effect = pure::<_, String, ()>(0).boxed();
+    /// for _ in 0..100_000 {
+    ///     effect = effect.and_then(|x| pure(x + 1));
+    /// }
+    /// assert_eq!(effect.execute(&()).await, Ok(100_000));
+    /// # });
+    /// ```
+    pub fn and_then<U, E2, F>(self, f: F) -> BoxedEffect<U, E, Env>
+    where
+        U: Send + 'static,
+        E2: Effect<Output = U, Error = E, Env = Env> + 'static,
+        F: FnOnce(T) -> E2 + Send + 'static,
+    {
+        let mut chain = self.into_chain();
+        chain
+            .continuations
+            .push(Box::new(move |value: ErasedValue| {
+                BoxedEffect::new(f(Self::unerase(value))).into_chain()
+            }));
+        BoxedEffect::from_chain(chain)
+    }
+
+    /// Transform the success value without nesting futures.
+    ///
+    /// Behaves like [`EffectExt::map`](crate::effect::EffectExt::map), but
+    /// stays boxed and appends to the same flat chain as
+    /// [`and_then`](BoxedEffect::and_then).
+    pub fn map<U, F>(self, f: F) -> BoxedEffect<U, E, Env>
+    where
+        U: Send + 'static,
+        F: FnOnce(T) -> U + Send + 'static,
+    {
+        let mut chain = self.into_chain();
+        chain
+            .continuations
+            .push(Box::new(move |value: ErasedValue| Chain {
+                head: Head::Pure(Box::new(f(Self::unerase(value)))),
+                continuations: Vec::new(),
+            }));
+        BoxedEffect::from_chain(chain)
+    }
+
+    fn from_chain(chain: Chain<E, Env>) -> Self {
         BoxedEffect {
-            run_fn: Box::new(move |env: Env| {
-                // env is now owned, so the async block is 'static
-                Box::pin(async move { effect.run(&env).await })
-            }),
+            repr: Repr::Chain(chain),
             _phantom: PhantomData,
         }
     }
+
+    fn into_chain(self) -> Chain<E, Env> {
+        match self.repr {
+            Repr::Single(single) => Chain {
+                head: single.into_head(),
+                continuations: Vec::new(),
+            },
+            Repr::Chain(chain) => chain,
+        }
+    }
+
+    fn unerase(value: ErasedValue) -> T {
+        *value
+            .downcast::<T>()
+            .expect("boxed effect chain produced a value of the wrong type")
+    }
 }
 
 impl<T, E, Env> Effect for BoxedEffect<T, E, Env>
 where
-    T: Send,
-    E: Send,
-    Env: Clone + Send + Sync,
+    T: Send + 'static,
+    E: Send + 'static,
+    Env: Send + Sync + 'static,
 {
     type Output = T;
     type Error = E;
     type Env = Env;
 
     fn run(self, env: &Env) -> impl Future<Output = Result<T, E>> + Send {
-        let env_owned = env.clone(); // Clone here for 'static lifetime
-        (self.run_fn)(env_owned)
+        let future: BoxFuture<'_, Result<T, E>> = match self.repr {
+            Repr::Single(single) => single.run_boxed(env),
+            Repr::Chain(chain) => {
+                Box::pin(async move { run_chain(chain, env).await.map(Self::unerase) })
+            }
+        };
+        future
     }
 }
 
@@ -34,6 +34,18 @@
 //! .run(&env)
 //! .await;
 //! ```
+//!
+//! # Cancellation
+//!
+//! Server frameworks routinely drop a request's future before it finishes.
+//! If a bracket is dropped during acquire, nothing has been acquired and
+//! nothing needs releasing. If it is dropped after acquire, every variant
+//! still releases what it holds, newest first. Async releases are spawned
+//! onto the current tokio runtime, which needs the `async` feature; without
+//! one the release is skipped with a warning rather than blocking the
+//! dropping thread. [`bracket_sync`] runs its release in place.
+//!
+//! Async releases therefore need `'static` futures and errors.
 
 use std::future::Future;
 use std::marker::PhantomData;
@@ -161,7 +173,8 @@ impl<E: std::error::Error + 'static> std::error::Error for BracketError<E> {
 /// The bracket pattern has three phases:
 /// 1. **Acquire**: Obtain the resource
 /// 2. **Use**: Use the resource to produce a result
-/// 3. **Release**: Release the resource (always runs, even on error)
+/// 3. **Release**: Release the resource (always runs, even on error or
+///    cancellation)
 ///
 /// Release errors are logged and the use result is returned.
 pub struct Bracket<Acquire, Use, Release> {
@@ -191,6 +204,110 @@ impl<Acquire, Use, Release> Bracket<Acquire, Use, Release> {
     }
 }
 
+/// Holds an acquired resource until it is released.
+///
+/// If dropped first, because the bracket's future was cancelled or the use
+/// effect panicked, it hands the resource and release to `on_drop`, which
+/// is [`release_detached`] for async releases.
+pub(crate) struct ReleaseGuard<R, Release> {
+    pending: Option<(R, Release)>,
+    on_drop: fn(R, Release),
+}
+
+impl<R, Release> ReleaseGuard<R, Release> {
+    /// Guard a resource with an async release, detached if the guard is
+    /// dropped.
+    pub(crate) fn new<RelFut, E>(resource: R, release: Release) -> Self
+    where
+        Release: FnOnce(R) -> RelFut,
+        RelFut: Future<Output = Result<(), E>> + Send + 'static,
+        E: Send + std::fmt::Debug + 'static,
+    {
+        ReleaseGuard {
+            pending: Some((resource, release)),
+            on_drop: |resource, release| release_detached(release(resource)),
+        }
+    }
+
+    /// Guard a resource with a synchronous release, run in place if the
+    /// guard is dropped.
+    fn new_sync<E>(resource: R, release: Release) -> Self
+    where
+        Release: FnOnce(R) -> Result<(), E>,
+        E: std::fmt::Debug,
+    {
+        ReleaseGuard {
+            pending: Some((resource, release)),
+            on_drop: |resource, release| {
+                if let Err(rel_err) = release(resource) {
+                    log_cancelled_release_error(&rel_err);
+                }
+            },
+        }
+    }
+
+    pub(crate) fn resource(&self) -> &R {
+        &self.pending.as_ref().expect("held until released").0
+    }
+
+    /// Disarm the guard, returning what the release needs.
+    pub(crate) fn into_parts(mut self) -> (R, Release) {
+        self.pending.take().expect("released only once")
+    }
+
+    /// Disarm the guard and start the release.
+    pub(crate) fn release<Out>(self) -> Out
+    where
+        Release: FnOnce(R) -> Out,
+    {
+        let (resource, release) = self.into_parts();
+        release(resource)
+    }
+}
+
+impl<R, Release> Drop for ReleaseGuard<R, Release> {
+    fn drop(&mut self) {
+        if let Some((resource, release)) = self.pending.take() {
+            (self.on_drop)(resource, release);
+        }
+    }
+}
+
+/// Run a release future that can no longer be awaited by its bracket.
+///
+/// With the `async` feature and a tokio runtime on the current thread, the
+/// release is spawned onto that runtime. Otherwise there is nothing that
+/// could drive it without blocking the dropping thread, so the release is
+/// dropped unrun and a warning is logged.
+fn release_detached<RelFut, E>(release: RelFut)
+where
+    RelFut: Future<Output = Result<(), E>> + Send + 'static,
+    E: Send + std::fmt::Debug + 'static,
+{
+    #[cfg(feature = "async")]
+    if let Ok(handle) = tokio::runtime::Handle::try_current() {
+        handle.spawn(async move {
+            if let Err(rel_err) = release.await {
+                log_cancelled_release_error(&rel_err);
+            }
+        });
+        return;
+    }
+
+    drop(release);
+    #[cfg(feature = "tracing")]
+    tracing::warn!("Bracket cancelled outside a tokio runtime; resource release skipped");
+    #[cfg(not(feature = "tracing"))]
+    eprintln!("Bracket cancelled outside a tokio runtime; resource release skipped");
+}
+
+fn log_cancelled_release_error<E: std::fmt::Debug>(rel_err: &E) {
+    #[cfg(feature = "tracing")]
+    tracing::warn!("Resource cleanup after cancellation failed: {:?}", rel_err);
+    #[cfg(not(feature = "tracing"))]
+    eprintln!("Resource cleanup after cancellation failed: {:?}", rel_err);
+}
+
 impl<Acquire, Use, Release, UseEffect, R, T, E, Env, RelFut> Effect
     for Bracket<Acquire, Use, Release>
 where
@@ -198,11 +315,11 @@ where
     Use: FnOnce(&R) -> UseEffect + Send,
     UseEffect: Effect<Output = T, Error = E, Env = Env>,
     Release: FnOnce(R) -> RelFut + Send,
-    RelFut: Future<Output = Result<(), E>> + Send,
+    RelFut: Future<Output = Result<(), E>> + Send + 'static,
     R: Send,
     T: Send,
-    E: Send + std::fmt::Debug,
-    Env: Clone + Send + Sync,
+    E: Send + std::fmt::Debug + 'static,
+    Env: Send + Sync,
 {
     type Output = T;
     type Error = E;
@@ -212,11 +329,16 @@ where
         // Acquire the resource
         let resource = self.acquire.run(env).await?;
 
+        // From here on the guard releases the resource if this future is
+        // dropped before the release below completes
+        let guard = ReleaseGuard::new(resource, self.release);
+
         // Use the resource (borrowing for use, moving for release)
-        let result = (self.use_fn)(&resource).run(env).await;
+        let result = (self.use_fn)(guard.resource()).run(env).await;
 
-        // Release runs regardless of use result
-        let release_result = (self.release)(resource).await;
+        // Release runs regardless of use result. Once started it is awaited
+        // here; dropping this future mid-release drops the release too.
+        let release_result = guard.release().await;
 
         // Log cleanup errors if any
         if let Err(ref rel_err) = release_result {
@@ -235,6 +357,9 @@ where
 /// Acquires a resource, uses it, and guarantees release even on error.
 /// Release errors are logged and the use result is returned.
 ///
+/// Release also runs if the bracket is cancelled after acquiring; see
+/// [Cancellation](self#cancellation).
+///
 /// # Type Parameters
 ///
 /// * `Acquire` - Effect that acquires the resource
@@ -262,11 +387,11 @@ where
     Use: FnOnce(&R) -> UseEffect + Send,
     UseEffect: Effect<Output = T, Error = E, Env = Env>,
     Release: FnOnce(R) -> RelFut + Send,
-    RelFut: Future<Output = Result<(), E>> + Send,
+    RelFut: Future<Output = Result<(), E>> + Send + 'static,
     R: Send,
     T: Send,
-    E: Send + std::fmt::Debug,
-    Env: Clone + Send + Sync,
+    E: Send + std::fmt::Debug + 'static,
+    Env: Send + Sync,
 {
     Bracket::new(acquire, use_fn, release)
 }
@@ -310,11 +435,11 @@ where
     Use: FnOnce(&R) -> UseEffect + Send,
     UseEffect: Effect<Output = T, Error = E, Env = Env>,
     Release: FnOnce(R) -> RelFut + Send,
-    RelFut: Future<Output = Result<(), E>> + Send,
+    RelFut: Future<Output = Result<(), E>> + Send + 'static,
     R: Send,
     T: Send,
-    E: Send,
-    Env: Clone + Send + Sync,
+    E: Send + std::fmt::Debug + 'static,
+    Env: Send + Sync,
 {
     type Output = T;
     type Error = BracketError<E>;
@@ -327,11 +452,14 @@ where
             Err(e) => return Err(BracketError::AcquireError(e)),
         };
 
+        // The guard releases the resource if this future is dropped early
+        let guard = ReleaseGuard::new(resource, self.release);
+
         // Use resource
-        let use_result = (self.use_fn)(&resource).run(env).await;
+        let use_result = (self.use_fn)(guard.resource()).run(env).await;
 
         // Release resource
-        let release_result = (self.release)(resource).await;
+        let release_result = guard.release().await;
 
         // Combine results
         match (use_result, release_result) {
@@ -382,11 +510,11 @@ where
     Use: FnOnce(&R) -> UseEffect + Send,
     UseEffect: Effect<Output = T, Error = E, Env = Env>,
     Release: FnOnce(R) -> RelFut + Send,
-    RelFut: Future<Output = Result<(), E>> + Send,
+    RelFut: Future<Output = Result<(), E>> + Send + 'static,
     R: Send,
     T: Send,
-    E: Send,
-    Env: Clone + Send + Sync,
+    E: Send + std::fmt::Debug + 'static,
+    Env: Send + Sync,
 {
     BracketFull::new(acquire, use_fn, release)
 }
@@ -432,7 +560,7 @@ where
     R: Send + std::panic::UnwindSafe,
     T: Send,
     E: Send + std::fmt::Debug,
-    Env: Clone + Send + Sync + std::panic::RefUnwindSafe,
+    Env: Send + Sync + std::panic::RefUnwindSafe,
 {
     type Output = T;
     type Error = E;
@@ -442,9 +570,12 @@ where
         // Acquire resource
         let resource = self.acquire.run(env).await?;
 
+        // The guard releases the resource if this future is dropped early
+        let guard = ReleaseGuard::new_sync(resource, self.release);
+
         // Use resource with panic catching
         let use_result = {
-            let resource_ref = &resource;
+            let resource_ref = guard.resource();
             let env_for_use = env;
             let use_fn = self.use_fn;
 
@@ -455,7 +586,7 @@ where
         };
 
         // Release resource (always runs, even after panic)
-        let release_result = (self.release)(resource);
+        let release_result = guard.release();
 
         // Handle results
         match use_result {
@@ -524,7 +655,7 @@ where
     R: Send + std::panic::UnwindSafe,
     T: Send,
     E: Send + std::fmt::Debug,
-    Env: Clone + Send + Sync + std::panic::RefUnwindSafe,
+    Env: Send + Sync + std::panic::RefUnwindSafe,
 {
     BracketSync::new(acquire, use_fn, release)
 }
@@ -562,14 +693,14 @@ where
     Use: FnOnce(&R1, &R2) -> UseEffect + Send,
     UseEffect: Effect<Output = T, Error = E, Env = Env>,
     Rel1: FnOnce(R1) -> RelFut1 + Send,
-    RelFut1: Future<Output = Result<(), E>> + Send,
+    RelFut1: Future<Output = Result<(), E>> + Send + 'static,
     Rel2: FnOnce(R2) -> RelFut2 + Send,
-    RelFut2: Future<Output = Result<(), E>> + Send,
+    RelFut2: Future<Output = Result<(), E>> + Send + 'static,
     R1: Send,
     R2: Send,
     T: Send,
-    E: Send + std::fmt::Debug,
-    Env: Clone + Send + Sync,
+    E: Send + std::fmt::Debug + 'static,
+    Env: Send + Sync,
 {
     type Output = T;
     type Error = E;
@@ -577,14 +708,14 @@ where
 
     async fn run(self, env: &Self::Env) -> Result<T, E> {
         // Acquire first resource
-        let r1 = self.acquire1.run(env).await?;
+        let r1 = ReleaseGuard::new(self.acquire1.run(env).await?, self.release1);
 
         // Acquire second resource
         let r2 = match self.acquire2.run(env).await {
-            Ok(r) => r,
+            Ok(r) => ReleaseGuard::new(r, self.release2),
             Err(e) => {
                 // Release first resource on failure
-                let release_result = (self.release1)(r1).await;
+                let release_result = r1.release().await;
                 if let Err(ref rel_err) = release_result {
                     #[cfg(feature = "tracing")]
                     tracing::warn!("Resource cleanup failed: {:?}", rel_err);
@@ -596,10 +727,10 @@ where
         };
 
         // Use both resources
-        let result = (self.use_fn)(&r1, &r2).run(env).await;
+        let result = (self.use_fn)(r1.resource(), r2.resource()).run(env).await;
 
         // Release in reverse order (LIFO)
-        let rel2_result = (self.release2)(r2).await;
+        let rel2_result = r2.release().await;
         if let Err(ref rel_err) = rel2_result {
             #[cfg(feature = "tracing")]
             tracing::warn!("Resource cleanup failed: {:?}", rel_err);
@@ -607,7 +738,7 @@ where
             eprintln!("Resource cleanup failed: {:?}", rel_err);
         }
 
-        let rel1_result = (self.release1)(r1).await;
+        let rel1_result = r1.release().await;
         if let Err(ref rel_err) = rel1_result {
             #[cfg(feature = "tracing")]
             tracing::warn!("Resource cleanup failed: {:?}", rel_err);
@@ -650,14 +781,14 @@ where
     Use: FnOnce(&R1, &R2) -> UseEffect + Send,
     UseEffect: Effect<Output = T, Error = E, Env = Env>,
     Rel1: FnOnce(R1) -> RelFut1 + Send,
-    RelFut1: Future<Output = Result<(), E>> + Send,
+    RelFut1: Future<Output = Result<(), E>> + Send + 'static,
     Rel2: FnOnce(R2) -> RelFut2 + Send,
-    RelFut2: Future<Output = Result<(), E>> + Send,
+    RelFut2: Future<Output = Result<(), E>> + Send + 'static,
     R1: Send,
     R2: Send,
     T: Send,
-    E: Send + std::fmt::Debug,
-    Env: Clone + Send + Sync,
+    E: Send + std::fmt::Debug + 'static,
+    Env: Send + Sync,
 {
     Bracket2 {
         acquire1,
@@ -721,17 +852,17 @@ where
     Use: FnOnce(&R1, &R2, &R3) -> UseEffect + Send,
     UseEffect: Effect<Output = T, Error = E, Env = Env>,
     Rel1: FnOnce(R1) -> RelFut1 + Send,
-    RelFut1: Future<Output = Result<(), E>> + Send,
+    RelFut1: Future<Output = Result<(), E>> + Send + 'static,
     Rel2: FnOnce(R2) -> RelFut2 + Send,
-    RelFut2: Future<Output = Result<(), E>> + Send,
+    RelFut2: Future<Output = Result<(), E>> + Send + 'static,
     Rel3: FnOnce(R3) -> RelFut3 + Send,
-    RelFut3: Future<Output = Result<(), E>> + Send,
+    RelFut3: Future<Output = Result<(), E>> + Send + 'static,
     R1: Send,
     R2: Send,
     R3: Send,
     T: Send,
-    E: Send + std::fmt::Debug,
-    Env: Clone + Send + Sync,
+    E: Send + std::fmt::Debug + 'static,
+    Env: Send + Sync,
 {
     type Output = T;
     type Error = E;
@@ -739,33 +870,35 @@ where
 
     async fn run(self, env: &Self::Env) -> Result<T, E> {
         // Acquire first resource
-        let r1 = self.acquire1.run(env).await?;
+        let r1 = ReleaseGuard::new(self.acquire1.run(env).await?, self.release1);
 
         // Acquire second resource
         let r2 = match self.acquire2.run(env).await {
-            Ok(r) => r,
+            Ok(r) => ReleaseGuard::new(r, self.release2),
             Err(e) => {
-                let _ = (self.release1)(r1).await;
+                let _ = r1.release().await;
                 return Err(e);
             }
         };
 
         // Acquire third resource
         let r3 = match self.acquire3.run(env).await {
-            Ok(r) => r,
+            Ok(r) => ReleaseGuard::new(r, self.release3),
             Err(e) => {
                 // Release in reverse order
-                let _ = (self.release2)(r2).await;
-                let _ = (self.release1)(r1).await;
+                let _ = r2.release().await;
+                let _ = r1.release().await;
                 return Err(e);
             }
         };
 
         // Use all resources
-        let result = (self.use_fn)(&r1, &r2, &r3).run(env).await;
+        let result = (self.use_fn)(r1.resource(), r2.resource(), r3.resource())
+            .run(env)
+            .await;
 
         // Release in reverse order (LIFO)
-        let rel3_result = (self.release3)(r3).await;
+        let rel3_result = r3.release().await;
         if let Err(ref rel_err) = rel3_result {
             #[cfg(feature = "tracing")]
             tracing::warn!("Resource cleanup failed: {:?}", rel_err);
@@ -773,7 +906,7 @@ where
             eprintln!("Resource cleanup failed: {:?}", rel_err);
         }
 
-        let rel2_result = (self.release2)(r2).await;
+        let rel2_result = r2.release().await;
         if let Err(ref rel_err) = rel2_result {
             #[cfg(feature = "tracing")]
             tracing::warn!("Resource cleanup failed: {:?}", rel_err);
@@ -781,7 +914,7 @@ where
             eprintln!("Resource cleanup failed: {:?}", rel_err);
         }
 
-        let rel1_result = (self.release1)(r1).await;
+        let rel1_result = r1.release().await;
         if let Err(ref rel_err) = rel1_result {
             #[cfg(feature = "tracing")]
             tracing::warn!("Resource cleanup failed: {:?}", rel_err);
@@ -828,17 +961,17 @@ where
     Use: FnOnce(&R1, &R2, &R3) -> UseEffect + Send,
     UseEffect: Effect<Output = T, Error = E, Env = Env>,
     Rel1: FnOnce(R1) -> RelFut1 + Send,
-    RelFut1: Future<Output = Result<(), E>> + Send,
+    RelFut1: Future<Output = Result<(), E>> + Send + 'static,
     Rel2: FnOnce(R2) -> RelFut2 + Send,
-    RelFut2: Future<Output = Result<(), E>> + Send,
+    RelFut2: Future<Output = Result<(), E>> + Send + 'static,
     Rel3: FnOnce(R3) -> RelFut3 + Send,
-    RelFut3: Future<Output = Result<(), E>> + Send,
+    RelFut3: Future<Output = Result<(), E>> + Send + 'static,
     R1: Send,
     R2: Send,
     R3: Send,
     T: Send,
-    E: Send + std::fmt::Debug,
-    Env: Clone + Send + Sync,
+    E: Send + std::fmt::Debug + 'static,
+    Env: Send + Sync,
 {
     Bracket3 {
         acquire1,
@@ -879,10 +1012,10 @@ pub struct Resource<T, E, Env>
 where
     T: Send + 'static,
     E: Send + 'static,
-    Env: Clone + Send + Sync + 'static,
+    Env: Send + Sync + 'static,
 {
     #[allow(clippy::type_complexity)]
-    acquire: Box<dyn FnOnce(&Env) -> BoxFuture<'static, Result<T, E>> + Send>,
+    acquire: Box<dyn for<'a> FnOnce(&'a Env) -> BoxFuture<'a, Result<T, E>> + Send>,
     release: Box<dyn FnOnce(T) -> BoxFuture<'static, Result<(), E>> + Send>,
 }
 
@@ -890,7 +1023,7 @@ impl<T, E, Env> std::fmt::Debug for Resource<T, E, Env>
 where
     T: Send + 'static,
     E: Send + 'static,
-    Env: Clone + Send + Sync + 'static,
+    Env: Send + Sync + 'static,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Resource")
@@ -904,7 +1037,7 @@ impl<T, E, Env> Resource<T, E, Env>
 where
     T: Send + 'static,
     E: Send + std::fmt::Debug + 'static,
-    Env: Clone + Send + Sync + 'static,
+    Env: Send + Sync + 'static,
 {
     /// Create a new resource with acquire effect and release function.
     pub fn new<Acq, Rel, RelFut>(acquire: Acq, release: Rel) -> Self
@@ -914,9 +1047,8 @@ where
         RelFut: Future<Output = Result<(), E>> + Send + 'static,
     {
         Resource {
-            acquire: Box::new(move |env: &Env| {
-                let env = env.clone();
-                Box::pin(async move { acquire.run(&env).await })
+            acquire: Box::new(move |env: &Env| -> BoxFuture<'_, Result<T, E>> {
+                Box::pin(async move { acquire.run(env).await })
             }),
             release: Box::new(move |t| Box::pin(release(t))),
         }
@@ -960,11 +1092,10 @@ where
         let release2_for_release = release2;
 
         Resource {
-            acquire: Box::new(move |env: &Env| {
-                let env = env.clone();
+            acquire: Box::new(move |env: &Env| -> BoxFuture<'_, Result<(T, T2), E>> {
                 Box::pin(async move {
-                    let t1 = acquire1(&env).await?;
-                    match acquire2(&env).await {
+                    let t1 = acquire1(env).await?;
+                    match acquire2(env).await {
                         Ok(t2) => Ok((t1, t2)),
                         Err(acquire_err) => {
                             // Release t1 if t2 acquisition fails
@@ -1020,7 +1151,7 @@ pub struct ResourceWith<T, U, E, Env, F>
 where
     T: Send + 'static,
     E: Send + 'static,
-    Env: Clone + Send + Sync + 'static,
+    Env: Send + Sync + 'static,
 {
     resource: Resource<T, E, Env>,
     use_fn: F,
@@ -1031,7 +1162,7 @@ impl<T, U, E, Env, F> std::fmt::Debug for ResourceWith<T, U, E, Env, F>
 where
     T: Send + 'static,
     E: Send + 'static,
-    Env: Clone + Send + Sync + 'static,
+    Env: Send + Sync + 'static,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("ResourceWith")
@@ -1046,7 +1177,7 @@ where
     T: Send + 'static,
     U: Send + 'static,
     E: Send + std::fmt::Debug + 'static,
-    Env: Clone + Send + Sync + 'static,
+    Env: Send + Sync + 'static,
     F: FnOnce(&T) -> UseEffect + Send + 'static,
     UseEffect: Effect<Output = U, Error = E, Env = Env>,
 {
@@ -1057,12 +1188,13 @@ where
     async fn run(self, env: &Self::Env) -> Result<U, E> {
         // Acquire
         let resource = (self.resource.acquire)(env).await?;
+        let guard = ReleaseGuard::new(resource, self.resource.release);
 
         // Use
-        let result = (self.use_fn)(&resource).run(env).await;
+        let result = (self.use_fn)(guard.resource()).run(env).await;
 
         // Release
-        let release_result = (self.resource.release)(resource).await;
+        let release_result = guard.release().await;
         if let Err(ref rel_err) = release_result {
             #[cfg(feature = "tracing")]
             tracing::warn!("Resource cleanup failed: {:?}", rel_err);
@@ -1115,7 +1247,7 @@ pub struct Acquiring<T, E, Env>
 where
     T: Send + 'static,
     E: Send + 'static,
-    Env: Clone + Send + Sync + 'static,
+    Env: Send + Sync + 'static,
 {
     resource: Resource<T, E, Env>,
 }
@@ -1124,7 +1256,7 @@ impl<T, E, Env> std::fmt::Debug for Acquiring<T, E, Env>
 where
     T: Send + 'static,
     E: Send + 'static,
-    Env: Clone + Send + Sync + 'static,
+    Env: Send + Sync + 'static,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Acquiring")
@@ -1149,7 +1281,7 @@ pub fn acquiring<R, E, Env, Acq, Rel, RelFut>(acquire: Acq, release: Rel) -> Acq
 where
     R: Send + 'static,
     E: Send + std::fmt::Debug + 'static,
-    Env: Clone + Send + Sync + 'static,
+    Env: Send + Sync + 'static,
     Acq: Effect<Output = R, Error = E, Env = Env> + 'static,
     Rel: FnOnce(R) -> RelFut + Send + 'static,
     RelFut: Future<Output = Result<(), E>> + Send + 'static,
@@ -1163,7 +1295,7 @@ impl<T, E, Env> Acquiring<T, E, Env>
 where
     T: Send + 'static,
     E: Send + std::fmt::Debug + 'static,
-    Env: Clone + Send + Sync + 'static,
+    Env: Send + Sync + 'static,
 {
     /// Add another resource to the acquisition chain.
     ///
@@ -1205,7 +1337,7 @@ where
     A: Send + 'static,
     B: Send + 'static,
     E: Send + std::fmt::Debug + 'static,
-    Env: Clone + Send + Sync + 'static,
+    Env: Send + Sync + 'static,
 {
     /// Use with flattened parameter access for two resources.
     ///
@@ -1238,7 +1370,7 @@ where
     B: Send + 'static,
     C: Send + 'static,
     E: Send + std::fmt::Debug + 'static,
-    Env: Clone + Send + Sync + 'static,
+    Env: Send + Sync + 'static,
 {
     /// Use with flattened parameter access for three resources.
     ///
@@ -1273,7 +1405,7 @@ where
     C: Send + 'static,
     D: Send + 'static,
     E: Send + std::fmt::Debug + 'static,
-    Env: Clone + Send + Sync + 'static,
+    Env: Send + Sync + 'static,
 {
     /// Use with flattened parameter access for four resources.
     ///
@@ -1332,7 +1464,7 @@ where
     R: Clone + Send,
     T: Send,
     E: Send,
-    Env: Clone + Send + Sync,
+    Env: Send + Sync,
 {
     BracketSimple {
         acquire,
@@ -1358,7 +1490,7 @@ where
     R: Clone + Send,
     T: Send,
     E: Send,
-    Env: Clone + Send + Sync,
+    Env: Send + Sync,
 {
     type Output = T;
     type Error = E;
@@ -1366,8 +1498,12 @@ where
 
     async fn run(self, env: &Self::Env) -> Result<T, E> {
         let resource = self.acquire.run(env).await?;
-        let result = (self.use_fn)(resource.clone()).run(env).await;
-        (self.release_fn)(resource);
+        let guard = ReleaseGuard {
+            pending: Some((resource, self.release_fn)),
+            on_drop: |resource, release_fn| release_fn(resource),
+        };
+        let result = (self.use_fn)(guard.resource().clone()).run(env).await;
+        guard.release::<()>();
         result
     }
 }
@@ -2279,6 +2415,170 @@ mod tests {
         );
     }
 
+    // ========================================================================
+    // Cancellation
+    // ========================================================================
+
+    #[tokio::test]
+    async fn bracket_cancelled_during_acquire_releases_nothing() {
+        use crate::effect::constructors::from_async;
+        use std::time::Duration;
+
+        let released = Arc::new(AtomicBool::new(false));
+        let released_clone = released.clone();
+
+        let effect = bracket(
+            from_async(|_: &()| async {
+                tokio::time::sleep(Duration::from_secs(60)).await;
+                Ok::<_, String>(1)
+            }),
+            move |_: i32| {
+                released_clone.store(true, Ordering::SeqCst);
+                async { Ok(()) }
+            },
+            |val: &i32| pure::<_, String, ()>(*val),
+        );
+
+        let timed_out = tokio::time::timeout(Duration::from_millis(10), effect.run(&())).await;
+
+        assert!(timed_out.is_err());
+        assert!(!released.load(Ordering::SeqCst));
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn bracket_cancelled_during_use_still_releases() {
+        use crate::effect::constructors::from_async;
+        use crate::effect::EffectExt;
+        use std::time::Duration;
+
+        let (released_tx, released_rx) = tokio::sync::oneshot::channel();
+        let cancelled = Arc::new(AtomicBool::new(false));
+
+        let effect = bracket(
+            pure::<_, String, ()>(1),
+            move |resource: i32| async move {
+                let _ = released_tx.send(resource);
+                Ok(())
+            },
+            |_: &i32| {
+                let cancelled = cancelled.clone();
+                from_async(|_: &()| async {
+                    tokio::time::sleep(Duration::from_secs(60)).await;
+                    Ok::<_, String>(())
+                })
+                .on_cancel(move || cancelled.store(true, Ordering::SeqCst))
+            },
+        );
+
+        let timed_out = tokio::time::timeout(Duration::from_millis(10), effect.run(&())).await;
+        assert!(timed_out.is_err());
+
+        let released = tokio::time::timeout(Duration::from_secs(5), released_rx).await;
+        assert_eq!(released.unwrap(), Ok(1), "release must run on cancellation");
+        assert!(cancelled.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn bracket_cancelled_outside_runtime_skips_release() {
+        use crate::effect::constructors::from_async;
+
+        let released = Arc::new(AtomicBool::new(false));
+        let released_clone = released.clone();
+
+        let effect = bracket(
+            pure::<_, String, ()>(1),
+            move |_: i32| async move {
+                released_clone.store(true, Ordering::SeqCst);
+                Ok(())
+            },
+            |_: &i32| {
+                from_async(|_: &()| async {
+                    std::future::pending::<()>().await;
+                    Ok::<_, String>(())
+                })
+            },
+        );
+
+        let mut running = Box::pin(effect.run(&()));
+        futures::executor::block_on(async {
+            assert!(futures::poll!(running.as_mut()).is_pending());
+        });
+        assert!(!released.load(Ordering::SeqCst));
+
+        // Dropping must not block trying to drive the release
+        drop(running);
+        assert!(!released.load(Ordering::SeqCst));
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn bracket2_cancelled_during_use_releases_both_in_reverse_order() {
+        use crate::effect::constructors::from_async;
+        use std::time::Duration;
+
+        let (released_tx, mut released_rx) = tokio::sync::mpsc::unbounded_channel();
+        let released_tx2 = released_tx.clone();
+
+        let effect = bracket2(
+            pure::<_, String, ()>("first"),
+            pure::<_, String, ()>("second"),
+            move |r: &'static str| async move {
+                let _ = released_tx.send(r);
+                Ok(())
+            },
+            move |r: &'static str| async move {
+                let _ = released_tx2.send(r);
+                Ok(())
+            },
+            |_: &&str, _: &&str| {
+                from_async(|_: &()| async {
+                    tokio::time::sleep(Duration::from_secs(60)).await;
+                    Ok::<_, String>(())
+                })
+            },
+        );
+
+        let timed_out = tokio::time::timeout(Duration::from_millis(10), effect.run(&())).await;
+        assert!(timed_out.is_err());
+
+        let mut order = Vec::new();
+        while order.len() < 2 {
+            let next = tokio::time::timeout(Duration::from_secs(5), released_rx.recv()).await;
+            order.push(next.unwrap().unwrap());
+        }
+        assert_eq!(order, vec!["second", "first"]);
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn bracket_full_cancelled_during_use_still_releases() {
+        use crate::effect::constructors::from_async;
+        use std::time::Duration;
+
+        let (released_tx, released_rx) = tokio::sync::oneshot::channel();
+
+        let effect = bracket_full(
+            pure::<_, String, ()>(7),
+            move |resource: i32| async move {
+                let _ = released_tx.send(resource);
+                Ok(())
+            },
+            |_: &i32| {
+                from_async(|_: &()| async {
+                    tokio::time::sleep(Duration::from_secs(60)).await;
+                    Ok::<_, String>(())
+                })
+            },
+        );
+
+        let timed_out = tokio::time::timeout(Duration::from_millis(10), effect.run(&())).await;
+        assert!(timed_out.is_err());
+
+        let released = tokio::time::timeout(Duration::from_secs(5), released_rx).await;
+        assert_eq!(released.unwrap(), Ok(7));
+    }
+
     // ========================================================================
     // with_flat4 test (spec 039)
     // ========================================================================
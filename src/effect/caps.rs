@@ -0,0 +1,354 @@
+//! Compile-time capability tracking for effects.
+//!
+//! Resource tracking (see [`resource`](crate::effect::resource)) records
+//! *what an effect holds*; capability tracking records *what kind of I/O an
+//! effect performs*. Each effect implementing [`EffectCaps`] carries a
+//! type-level set of capabilities in its `Caps` associated type, so a
+//! codebase can require at compile time that its "pure core" only builds
+//! effects with `Caps = Empty` while the imperative shell is free to use
+//! [`NetCap`], [`ClockCap`], [`FsCap`], or capabilities of its own.
+//!
+//! Like resource tracking, this is purely type-level and opt-in:
+//!
+//! - `Pure` and `Fail` use no capabilities
+//! - `Map` and `MapErr` keep their inner effect's capabilities
+//! - `AndThen` combines the capabilities of both effects
+//! - [`WithCaps`] declares capabilities explicitly, usually through
+//!   [`CapsExt::uses`]
+//!
+//! Nothing checks that an effect declared with `Caps = Empty` really does
+//! no I/O; the markers document and enforce the boundary the team draws.
+//!
+//! # Example
+//!
+//! ```rust
+//! use stillwater::effect::caps::*;
+//! use stillwater::effect::prelude::*;
+//!
+//! // Shell: talks to the network and says so
+//! fn fetch_price(
+//!     symbol: &'static str,
+//! ) -> impl EffectCaps<Output = u64, Error = String, Env = (), Caps = Uses<NetCap>> {
+//!     pure(if symbol == "ACME" { 120 } else { 80 }).uses::<NetCap>()
+//! }
+//!
+//! // Core: only computes, and the signature guarantees it
+//! fn apply_discount(
+//!     price: u64,
+//! ) -> impl EffectCaps<Output = u64, Error = String, Env = (), Caps = Empty> {
+//!     assert_pure(pure(price * 9 / 10))
+//! }
+//!
+//! # tokio_test::block_on(async {
+//! let quote = fetch_price("ACME").and_then(apply_discount);
+//! assert_eq!(quote.run(&()).await, Ok(108));
+//! # });
+//! ```
+//!
+//! Passing the network-using effect to [`assert_pure`] fails to compile:
+//!
+//! ```rust,compile_fail
+//! use stillwater::effect::caps::*;
+//! use stillwater::effect::prelude::*;
+//!
+//! let _ = assert_pure(pure::<_, String, ()>(1).uses::<NetCap>());
+//! ```
+
+use std::fmt;
+use std::marker::PhantomData;
+
+use crate::effect::combinators::{AndThen, Fail, Map, MapErr, Pure};
+use crate::effect::trait_def::Effect;
+
+pub use crate::effect::resource::Empty;
+
+// =============================================================================
+// Capability markers
+// =============================================================================
+
+/// Marker trait for capabilities.
+///
+/// Implement this trait on a zero-sized type to define a capability of your
+/// own, such as access to a message queue or to process environment
+/// variables.
+pub trait Capability: Send + Sync + 'static {
+    /// Human-readable name for debugging.
+    const NAME: &'static str;
+}
+
+/// Network access capability.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NetCap;
+
+impl Capability for NetCap {
+    const NAME: &'static str = "Net";
+}
+
+/// Wall-clock and timer capability.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ClockCap;
+
+impl Capability for ClockCap {
+    const NAME: &'static str = "Clock";
+}
+
+/// Filesystem access capability.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FsCap;
+
+impl Capability for FsCap {
+    const NAME: &'static str = "Fs";
+}
+
+// =============================================================================
+// Capability sets
+// =============================================================================
+
+/// Marker trait for type-level capability sets.
+///
+/// Implemented for [`Empty`] and [`Uses<C, Rest>`].
+pub trait CapSet: Send + Sync + 'static {}
+
+impl CapSet for Empty {}
+
+/// Non-empty capability set - capability `C` plus `Rest`.
+///
+/// `Uses<NetCap>` is the set holding only the network capability, and
+/// `Uses<NetCap, Uses<FsCap>>` holds both network and filesystem access.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Uses<C: Capability, Rest: CapSet = Empty>(PhantomData<(C, Rest)>);
+
+impl<C: Capability, Rest: CapSet> Default for Uses<C, Rest> {
+    fn default() -> Self {
+        Uses(PhantomData)
+    }
+}
+
+impl<C: Capability, Rest: CapSet> fmt::Debug for Uses<C, Rest> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Uses<{}>", C::NAME)
+    }
+}
+
+impl<C: Capability, Rest: CapSet> CapSet for Uses<C, Rest> {}
+
+/// Type-level union of two capability sets.
+pub trait CapUnion<Other: CapSet>: CapSet {
+    /// The set holding the capabilities of both sets.
+    type Output: CapSet;
+}
+
+impl<S: CapSet> CapUnion<Empty> for S {
+    type Output = S;
+}
+
+impl<C: Capability, Rest: CapSet> CapUnion<Uses<C, Rest>> for Empty {
+    type Output = Uses<C, Rest>;
+}
+
+impl<C1: Capability, Rest1: CapSet, C2: Capability, Rest2: CapSet> CapUnion<Uses<C2, Rest2>>
+    for Uses<C1, Rest1>
+where
+    Rest1: CapUnion<Uses<C2, Rest2>>,
+{
+    type Output = Uses<C1, <Rest1 as CapUnion<Uses<C2, Rest2>>>::Output>;
+}
+
+// =============================================================================
+// EffectCaps
+// =============================================================================
+
+/// An effect with a compile-time record of the capabilities it uses.
+pub trait EffectCaps: Effect {
+    /// Capabilities this effect uses.
+    type Caps: CapSet;
+}
+
+/// Wrapper declaring the capabilities of any effect.
+///
+/// Runs exactly as the inner effect does; `Caps` exists only at the type
+/// level.
+pub struct WithCaps<Eff, Caps: CapSet = Empty> {
+    inner: Eff,
+    _caps: PhantomData<Caps>,
+}
+
+impl<Eff, Caps: CapSet> WithCaps<Eff, Caps> {
+    /// Declare that `inner` uses the capabilities `Caps`.
+    pub fn new(inner: Eff) -> Self {
+        WithCaps {
+            inner,
+            _caps: PhantomData,
+        }
+    }
+
+    /// Add capability `C` to the declared set.
+    pub fn also_uses<C: Capability>(self) -> WithCaps<Eff, Uses<C, Caps>> {
+        WithCaps::new(self.inner)
+    }
+
+    /// Unwrap the inner effect.
+    pub fn into_inner(self) -> Eff {
+        self.inner
+    }
+}
+
+impl<Eff: Clone, Caps: CapSet> Clone for WithCaps<Eff, Caps> {
+    fn clone(&self) -> Self {
+        WithCaps::new(self.inner.clone())
+    }
+}
+
+impl<Eff, Caps: CapSet> fmt::Debug for WithCaps<Eff, Caps> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WithCaps")
+            .field("inner", &"<effect>")
+            .field("caps", &std::any::type_name::<Caps>())
+            .finish()
+    }
+}
+
+impl<Eff: Effect, Caps: CapSet> Effect for WithCaps<Eff, Caps> {
+    type Output = Eff::Output;
+    type Error = Eff::Error;
+    type Env = Eff::Env;
+
+    async fn run(self, env: &Self::Env) -> Result<Self::Output, Self::Error> {
+        self.inner.run(env).await
+    }
+}
+
+impl<Eff: Effect, Caps: CapSet> EffectCaps for WithCaps<Eff, Caps> {
+    type Caps = Caps;
+}
+
+impl<T, E, Env> EffectCaps for Pure<T, E, Env>
+where
+    T: Send,
+    E: Send,
+    Env: Send + Sync,
+{
+    type Caps = Empty;
+}
+
+impl<T, E, Env> EffectCaps for Fail<T, E, Env>
+where
+    T: Send,
+    E: Send,
+    Env: Send + Sync,
+{
+    type Caps = Empty;
+}
+
+impl<Inner, F, U> EffectCaps for Map<Inner, F>
+where
+    Inner: EffectCaps,
+    F: FnOnce(Inner::Output) -> U + Send,
+    U: Send,
+{
+    type Caps = Inner::Caps;
+}
+
+impl<Inner, F, E2> EffectCaps for MapErr<Inner, F>
+where
+    Inner: EffectCaps,
+    F: FnOnce(Inner::Error) -> E2 + Send,
+    E2: Send,
+{
+    type Caps = Inner::Caps;
+}
+
+impl<Inner, F, E2> EffectCaps for AndThen<Inner, F>
+where
+    Inner: EffectCaps,
+    E2: EffectCaps<Error = Inner::Error, Env = Inner::Env>,
+    F: FnOnce(Inner::Output) -> E2 + Send,
+    Inner::Caps: CapUnion<E2::Caps>,
+{
+    type Caps = <Inner::Caps as CapUnion<E2::Caps>>::Output;
+}
+
+// =============================================================================
+// Extension methods and assertions
+// =============================================================================
+
+/// Extension methods for declaring an effect's capabilities.
+///
+/// Implemented for every [`Effect`].
+pub trait CapsExt: Effect + Sized {
+    /// Declare that this effect uses capability `C`.
+    fn uses<C: Capability>(self) -> WithCaps<Self, Uses<C>> {
+        WithCaps::new(self)
+    }
+
+    /// Declare that this effect uses no capabilities.
+    ///
+    /// For effects such as `from_fn` that compute without I/O but have no
+    /// [`EffectCaps`] implementation of their own.
+    fn pure_core(self) -> WithCaps<Self, Empty> {
+        WithCaps::new(self)
+    }
+}
+
+impl<E: Effect> CapsExt for E {}
+
+/// Accept only effects that use no capabilities (compile-time check).
+pub fn assert_pure<Eff>(effect: Eff) -> Eff
+where
+    Eff: EffectCaps<Caps = Empty>,
+{
+    effect
+}
+
+/// Effects that use no capabilities.
+pub trait IsPure: EffectCaps<Caps = Empty> {}
+
+impl<E: EffectCaps<Caps = Empty>> IsPure for E {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::effect::constructors::{fail, from_fn, pure};
+    use crate::effect::ext::EffectExt;
+
+    fn _assert_caps<T: EffectCaps<Caps = C>, C: CapSet>() {}
+    fn _assert_pure<T: IsPure>() {}
+
+    #[test]
+    fn test_constructors_and_mapping_stay_pure() {
+        _assert_pure::<Pure<i32, String, ()>>();
+        _assert_pure::<Fail<i32, String, ()>>();
+
+        let effect = pure::<_, i32, ()>(1)
+            .map(|x| x + 1)
+            .map_err(|e| e.to_string())
+            .and_then(|x| fail::<i32, _, ()>(format!("{x}")));
+        let _ = assert_pure(effect);
+    }
+
+    #[test]
+    fn test_and_then_combines_capabilities() {
+        let effect = pure::<_, String, ()>("/tmp/x")
+            .uses::<FsCap>()
+            .and_then(|_| pure(3).uses::<NetCap>().also_uses::<ClockCap>())
+            .map(|n| n * 2);
+
+        fn check<T: EffectCaps<Caps = Uses<FsCap, Uses<ClockCap, Uses<NetCap>>>>>(_: T) {}
+        check(effect);
+    }
+
+    #[test]
+    fn test_sets_are_zero_sized_and_named() {
+        assert_eq!(std::mem::size_of::<Uses<NetCap, Uses<FsCap>>>(), 0);
+        assert_eq!(format!("{:?}", Uses::<ClockCap>::default()), "Uses<Clock>");
+    }
+
+    #[tokio::test]
+    async fn test_declared_effects_run_unchanged() {
+        let effect = from_fn(|env: &i32| Ok::<_, String>(*env * 2))
+            .pure_core()
+            .and_then(|x| pure(x + 1).uses::<NetCap>());
+
+        assert_eq!(effect.run(&20).await, Ok(41));
+    }
+}
@@ -1,11 +1,17 @@
 //! AndThen combinator - chains dependent effects.
 
+use crate::effect::describe::type_name;
+#[cfg(feature = "fuel")]
+use crate::effect::fuel;
+use crate::effect::in_child_execution;
 use crate::effect::trait_def::Effect;
 
 /// AndThen combinator - chains dependent effects.
 ///
 /// Zero-cost: no heap allocation. The `AndThen` struct stores only
-/// the inner effect and the function that produces the next effect.
+/// the inner effect and the function that produces the next effect. With
+/// the `fuel` feature, it also charges one unit of fuel when run under
+/// `run_with_fuel`.
 ///
 /// The error type of the chained effect must match the error type
 /// of the original effect. Use `map_err` to convert error types
@@ -26,6 +32,7 @@ use crate::effect::trait_def::Effect;
 ///     .and_then(|x| pure(x * 2));
 /// assert_eq!(effect.execute(&()).await, Ok(42));
 /// ```
+#[derive(Clone)]
 pub struct AndThen<Inner, F> {
     pub(crate) inner: Inner,
     pub(crate) f: F,
@@ -33,10 +40,7 @@ pub struct AndThen<Inner, F> {
 
 impl<Inner, F> std::fmt::Debug for AndThen<Inner, F> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("AndThen")
-            .field("inner", &"<effect>")
-            .field("f", &"<function>")
-            .finish()
+        f.write_str(&type_name::<Self>())
     }
 }
 
@@ -52,7 +56,9 @@ where
 
     async fn run(self, env: &Self::Env) -> Result<Self::Output, Self::Error> {
         let value = self.inner.run(env).await?;
-        (self.f)(value).run(env).await
+        #[cfg(feature = "fuel")]
+        fuel::tick().await;
+        in_child_execution((self.f)(value).run(env)).await
     }
 }
 
@@ -87,4 +93,13 @@ where
             Err(e) => (Err(e), writes1),
         }
     }
+
+    async fn run_writer_buffered(
+        self,
+        env: &Self::Env,
+        buffer: &crate::effect::writer::WriteBuffer<Self::Writes>,
+    ) -> Result<Self::Output, Self::Error> {
+        let value = self.inner.run_writer_buffered(env, buffer).await?;
+        (self.f)(value).run_writer_buffered(env, buffer).await
+    }
 }
@@ -2,18 +2,26 @@
 
 use std::marker::PhantomData;
 
+use crate::effect::describe::type_name;
+#[cfg(feature = "fuel")]
+use crate::effect::fuel;
 use crate::effect::trait_def::Effect;
 
 /// An effect that chains with automatic error conversion.
 ///
 /// Created by [`EffectExt::and_then_auto`](crate::effect::ext::EffectExt::and_then_auto).
-#[derive(Debug)]
 pub struct AndThenAuto<E, F, E2> {
     pub(crate) inner: E,
     pub(crate) f: F,
     pub(crate) _marker: PhantomData<E2>,
 }
 
+impl<E, F, E2> std::fmt::Debug for AndThenAuto<E, F, E2> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&type_name::<Self>())
+    }
+}
+
 impl<E, F, E2> Effect for AndThenAuto<E, F, E2>
 where
     E: Effect,
@@ -27,6 +35,8 @@ where
 
     async fn run(self, env: &Self::Env) -> Result<Self::Output, Self::Error> {
         let value = self.inner.run(env).await?;
+        #[cfg(feature = "fuel")]
+        fuel::tick().await;
         (self.f)(value).run(env).await.map_err(E::Error::from)
     }
 }
@@ -1,17 +1,23 @@
 //! Check combinator - fail if predicate is false.
 
+use crate::effect::describe::type_name;
 use crate::effect::trait_def::Effect;
 
 /// An effect that fails with an error if a predicate returns false.
 ///
 /// Created by [`EffectExt::check`](crate::effect::ext::EffectExt::check).
-#[derive(Debug)]
 pub struct Check<E, P, F> {
     pub(crate) inner: E,
     pub(crate) predicate: P,
     pub(crate) error_fn: F,
 }
 
+impl<E, P, F> std::fmt::Debug for Check<E, P, F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&type_name::<Self>())
+    }
+}
+
 impl<E, P, F> Effect for Check<E, P, F>
 where
     E: Effect,
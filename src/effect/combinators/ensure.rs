@@ -1,5 +1,6 @@
 //! Ensure combinator for validating effect outputs with closure predicates.
 
+use crate::effect::describe::type_name;
 use crate::effect::Effect;
 
 /// Validates the effect's output with a closure predicate.
@@ -28,11 +29,7 @@ pub struct Ensure<E, P, Err> {
 
 impl<E, P, Err> std::fmt::Debug for Ensure<E, P, Err> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("Ensure")
-            .field("inner", &"<effect>")
-            .field("predicate", &"<function>")
-            .field("error", &"<error>")
-            .finish()
+        f.write_str(&type_name::<Self>())
     }
 }
 
@@ -1,5 +1,6 @@
 //! EnsurePred combinator for validation using Predicate trait.
 
+use crate::effect::describe::type_name;
 use crate::effect::Effect;
 use crate::predicate::Predicate;
 
@@ -32,11 +33,7 @@ pub struct EnsurePred<E, P, Err> {
 
 impl<E, P, Err> std::fmt::Debug for EnsurePred<E, P, Err> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("EnsurePred")
-            .field("inner", &"<effect>")
-            .field("predicate", &"<predicate>")
-            .field("error", &"<error>")
-            .finish()
+        f.write_str(&type_name::<Self>())
     }
 }
 
@@ -1,5 +1,6 @@
 //! EnsureWith combinator for validation with lazy error construction.
 
+use crate::effect::describe::type_name;
 use crate::effect::Effect;
 
 /// Validates with an error factory function.
@@ -29,11 +30,7 @@ pub struct EnsureWith<E, P, F> {
 
 impl<E, P, F> std::fmt::Debug for EnsureWith<E, P, F> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("EnsureWith")
-            .field("inner", &"<effect>")
-            .field("predicate", &"<function>")
-            .field("error_fn", &"<function>")
-            .finish()
+        f.write_str(&type_name::<Self>())
     }
 }
 
@@ -1,5 +1,6 @@
 //! Fallback combinator for providing default values on error.
 
+use crate::effect::describe::type_name;
 use crate::effect::Effect;
 
 /// Provides a default value on any error.
@@ -28,10 +29,7 @@ where
     E: Effect,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("Fallback")
-            .field("inner", &"<effect>")
-            .field("default", &"<value>")
-            .finish()
+        f.write_str(&type_name::<Self>())
     }
 }
 
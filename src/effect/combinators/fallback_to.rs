@@ -1,5 +1,6 @@
 //! FallbackTo combinator for alternative effects on error.
 
+use crate::effect::describe::type_name;
 use crate::effect::Effect;
 
 /// Tries an alternative effect on any error.
@@ -22,10 +23,7 @@ pub struct FallbackTo<E1, E2> {
 
 impl<E1, E2> std::fmt::Debug for FallbackTo<E1, E2> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("FallbackTo")
-            .field("primary", &"<effect>")
-            .field("alternative", &"<effect>")
-            .finish()
+        f.write_str(&type_name::<Self>())
     }
 }
 
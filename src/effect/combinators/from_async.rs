@@ -24,6 +24,15 @@ pub struct FromAsync<F, Env> {
     pub(crate) _phantom: PhantomData<Env>,
 }
 
+impl<F: Clone, Env> Clone for FromAsync<F, Env> {
+    fn clone(&self) -> Self {
+        FromAsync {
+            f: self.f.clone(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
 impl<F, Env> std::fmt::Debug for FromAsync<F, Env> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("FromAsync")
@@ -48,7 +57,7 @@ where
     Fut: Future<Output = Result<T, E>> + Send,
     T: Send,
     E: Send,
-    Env: Clone + Send + Sync,
+    Env: Send + Sync,
 {
     type Output = T;
     type Error = E;
@@ -25,6 +25,15 @@ pub struct FromFn<F, Env> {
     pub(crate) _phantom: PhantomData<Env>,
 }
 
+impl<F: Clone, Env> Clone for FromFn<F, Env> {
+    fn clone(&self) -> Self {
+        FromFn {
+            f: self.f.clone(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
 impl<F, Env> std::fmt::Debug for FromFn<F, Env> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("FromFn").field("f", &"<function>").finish()
@@ -46,7 +55,7 @@ where
     F: FnOnce(&Env) -> Result<T, E> + Send,
     T: Send,
     E: Send,
-    Env: Clone + Send + Sync,
+    Env: Send + Sync,
 {
     type Output = T;
     type Error = E;
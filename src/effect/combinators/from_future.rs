@@ -0,0 +1,59 @@
+//! FromFuture - effect from a future that ignores the environment.
+
+use std::future::Future;
+use std::marker::PhantomData;
+
+use crate::effect::trait_def::Effect;
+
+/// Effect from a future that does not need the environment.
+///
+/// Zero-cost: the future is stored directly in the struct and awaited
+/// when the effect is run. Usually created with
+/// [`FutureIntoEffect::into_effect`](crate::effect::FutureIntoEffect::into_effect).
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use stillwater::effect::prelude::*;
+///
+/// let effect: FromFuture<_, ()> = FromFuture::new(async { Ok::<_, String>(42) });
+/// assert_eq!(effect.execute(&()).await, Ok(42));
+/// ```
+pub struct FromFuture<Fut, Env> {
+    pub(crate) future: Fut,
+    pub(crate) _phantom: PhantomData<Env>,
+}
+
+impl<Fut, Env> std::fmt::Debug for FromFuture<Fut, Env> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FromFuture")
+            .field("future", &"<future>")
+            .finish()
+    }
+}
+
+impl<Fut, Env> FromFuture<Fut, Env> {
+    /// Create a new FromFuture effect.
+    pub fn new(future: Fut) -> Self {
+        FromFuture {
+            future,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<Fut, T, E, Env> Effect for FromFuture<Fut, Env>
+where
+    Fut: Future<Output = Result<T, E>> + Send,
+    T: Send,
+    E: Send,
+    Env: Send + Sync,
+{
+    type Output = T;
+    type Error = E;
+    type Env = Env;
+
+    fn run(self, _env: &Env) -> impl Future<Output = Result<T, E>> + Send {
+        self.future
+    }
+}
@@ -25,6 +25,15 @@ pub struct FromResult<T, E, Env> {
     pub(crate) _phantom: PhantomData<Env>,
 }
 
+impl<T: Clone, E: Clone, Env> Clone for FromResult<T, E, Env> {
+    fn clone(&self) -> Self {
+        FromResult {
+            result: self.result.clone(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
 impl<T, E, Env> std::fmt::Debug for FromResult<T, E, Env>
 where
     T: std::fmt::Debug,
@@ -51,7 +60,7 @@ impl<T, E, Env> Effect for FromResult<T, E, Env>
 where
     T: Send,
     E: Send,
-    Env: Clone + Send + Sync,
+    Env: Send + Sync,
 {
     type Output = T;
     type Error = E;
@@ -0,0 +1,181 @@
+//! LoopEffect - iterate an effect with explicit state until it breaks.
+
+use std::marker::PhantomData;
+
+#[cfg(feature = "fuel")]
+use crate::effect::fuel;
+use crate::effect::trait_def::Effect;
+
+/// Control flow signal returned by each step of a [`LoopEffect`].
+///
+/// - `Continue(state)` runs the step again with the new state
+/// - `Break(result)` stops the loop and produces the final result
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Loop<S, R> {
+    /// Run another iteration with the given state.
+    Continue(S),
+    /// Stop iterating and succeed with the given result.
+    Break(R),
+}
+
+impl<S, R> Loop<S, R> {
+    /// Returns `true` if this is `Loop::Continue`.
+    pub fn is_continue(&self) -> bool {
+        matches!(self, Loop::Continue(_))
+    }
+
+    /// Returns `true` if this is `Loop::Break`.
+    pub fn is_break(&self) -> bool {
+        matches!(self, Loop::Break(_))
+    }
+}
+
+/// Effect that repeatedly runs a step effect, threading explicit state.
+///
+/// Each iteration calls the step function with the current state and runs
+/// the returned effect. The loop runs inside a single future, so it is
+/// stack-safe and performs no per-iteration boxing, unlike recursive
+/// `BoxedEffect` construction.
+///
+/// Created by [`loop_effect`](crate::effect::constructors::loop_effect).
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use stillwater::effect::prelude::*;
+///
+/// // Sum 1..=n without recursion
+/// let effect = loop_effect((10, 0), |(n, acc)| {
+///     if n == 0 {
+///         pure::<_, String, ()>(Loop::Break(acc))
+///     } else {
+///         pure(Loop::Continue((n - 1, acc + n)))
+///     }
+/// });
+/// assert_eq!(effect.execute(&()).await, Ok(55));
+/// ```
+pub struct LoopEffect<S, F, Eff> {
+    pub(crate) initial: S,
+    pub(crate) step: F,
+    pub(crate) _marker: PhantomData<fn() -> Eff>,
+}
+
+impl<S, F, Eff> std::fmt::Debug for LoopEffect<S, F, Eff> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LoopEffect")
+            .field("initial", &"<state>")
+            .field("step", &"<function>")
+            .finish()
+    }
+}
+
+impl<S, F, Eff> LoopEffect<S, F, Eff> {
+    /// Create a new LoopEffect starting from the given state.
+    pub fn new(initial: S, step: F) -> Self {
+        LoopEffect {
+            initial,
+            step,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<S, R, F, Eff> Effect for LoopEffect<S, F, Eff>
+where
+    S: Send,
+    R: Send,
+    F: FnMut(S) -> Eff + Send,
+    Eff: Effect<Output = Loop<S, R>>,
+{
+    type Output = R;
+    type Error = Eff::Error;
+    type Env = Eff::Env;
+
+    async fn run(mut self, env: &Self::Env) -> Result<R, Self::Error> {
+        let mut state = self.initial;
+        loop {
+            #[cfg(feature = "fuel")]
+            fuel::tick().await;
+            match (self.step)(state).run(env).await? {
+                Loop::Continue(next) => state = next,
+                Loop::Break(result) => return Ok(result),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Loop;
+    use crate::effect::constructors::{fail, from_fn, loop_effect, pure};
+    use crate::effect::EffectExt;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_loop_effect_breaks_with_result() {
+        let effect = loop_effect((10, 0), |(n, acc): (i32, i32)| {
+            if n == 0 {
+                pure::<_, String, ()>(Loop::Break(acc))
+            } else {
+                pure(Loop::Continue((n - 1, acc + n)))
+            }
+        });
+        assert_eq!(effect.execute(&()).await, Ok(55));
+    }
+
+    #[tokio::test]
+    async fn test_loop_effect_breaks_immediately() {
+        let effect = loop_effect(7, |n: i32| {
+            pure::<Loop<i32, i32>, String, ()>(Loop::Break(n))
+        });
+        assert_eq!(effect.execute(&()).await, Ok(7));
+    }
+
+    #[tokio::test]
+    async fn test_loop_effect_stops_on_error() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        let effect = loop_effect(0, move |n: i32| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            if n == 3 {
+                fail::<Loop<i32, i32>, _, ()>("boom".to_string()).boxed()
+            } else {
+                pure(Loop::Continue(n + 1)).boxed()
+            }
+        });
+        assert_eq!(effect.execute(&()).await, Err("boom".to_string()));
+        assert_eq!(calls.load(Ordering::SeqCst), 4);
+    }
+
+    #[tokio::test]
+    async fn test_loop_effect_reads_environment() {
+        #[derive(Clone)]
+        struct Env {
+            limit: i32,
+        }
+
+        let effect = loop_effect(0, |n: i32| {
+            from_fn(move |env: &Env| {
+                Ok::<_, String>(if n >= env.limit {
+                    Loop::Break(n)
+                } else {
+                    Loop::Continue(n + 1)
+                })
+            })
+        });
+        assert_eq!(effect.execute(&Env { limit: 5 }).await, Ok(5));
+    }
+
+    #[tokio::test]
+    async fn test_loop_effect_is_stack_safe() {
+        let effect = loop_effect(0u64, |n: u64| {
+            pure::<_, String, ()>(if n == 1_000_000 {
+                Loop::Break(n)
+            } else {
+                Loop::Continue(n + 1)
+            })
+        });
+        assert_eq!(effect.execute(&()).await, Ok(1_000_000));
+    }
+}
@@ -1,11 +1,16 @@
 //! Map combinator - transforms the success value of an effect.
 
+use crate::effect::describe::type_name;
+#[cfg(feature = "fuel")]
+use crate::effect::fuel;
 use crate::effect::trait_def::Effect;
 
 /// Map combinator - transforms the success value.
 ///
 /// Zero-cost: no heap allocation. The `Map` struct stores only
-/// the inner effect and the transformation function.
+/// the inner effect and the transformation function. With the `fuel`
+/// feature, it also charges one unit of fuel when run under
+/// `run_with_fuel`.
 ///
 /// # Example
 ///
@@ -15,6 +20,7 @@ use crate::effect::trait_def::Effect;
 /// let effect = pure::<_, String, ()>(21).map(|x| x * 2);
 /// assert_eq!(effect.execute(&()).await, Ok(42));
 /// ```
+#[derive(Clone)]
 pub struct Map<Inner, F> {
     pub(crate) inner: Inner,
     pub(crate) f: F,
@@ -22,10 +28,7 @@ pub struct Map<Inner, F> {
 
 impl<Inner, F> std::fmt::Debug for Map<Inner, F> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("Map")
-            .field("inner", &"<effect>")
-            .field("f", &"<function>")
-            .finish()
+        f.write_str(&type_name::<Self>())
     }
 }
 
@@ -41,6 +44,8 @@ where
 
     async fn run(self, env: &Self::Env) -> Result<U, Self::Error> {
         let value = self.inner.run(env).await?;
+        #[cfg(feature = "fuel")]
+        fuel::tick().await;
         Ok((self.f)(value))
     }
 }
@@ -61,4 +66,15 @@ where
         let (result, writes) = self.inner.run_writer(env).await;
         (result.map(self.f), writes)
     }
+
+    async fn run_writer_buffered(
+        self,
+        env: &Self::Env,
+        buffer: &crate::effect::writer::WriteBuffer<Self::Writes>,
+    ) -> Result<Self::Output, Self::Error> {
+        self.inner
+            .run_writer_buffered(env, buffer)
+            .await
+            .map(self.f)
+    }
 }
@@ -1,5 +1,6 @@
 //! MapErr combinator - transforms the error value of an effect.
 
+use crate::effect::describe::type_name;
 use crate::effect::trait_def::Effect;
 
 /// MapErr combinator - transforms the error value.
@@ -16,6 +17,7 @@ use crate::effect::trait_def::Effect;
 ///     .map_err(|e: &str| format!("wrapped: {}", e));
 /// assert_eq!(effect.execute(&()).await, Err("wrapped: error".to_string()));
 /// ```
+#[derive(Clone)]
 pub struct MapErr<Inner, F> {
     pub(crate) inner: Inner,
     pub(crate) f: F,
@@ -23,10 +25,7 @@ pub struct MapErr<Inner, F> {
 
 impl<Inner, F> std::fmt::Debug for MapErr<Inner, F> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("MapErr")
-            .field("inner", &"<effect>")
-            .field("f", &"<function>")
-            .finish()
+        f.write_str(&type_name::<Self>())
     }
 }
 
@@ -61,4 +60,15 @@ where
         let (result, writes) = self.inner.run_writer(env).await;
         (result.map_err(self.f), writes)
     }
+
+    async fn run_writer_buffered(
+        self,
+        env: &Self::Env,
+        buffer: &crate::effect::writer::WriteBuffer<Self::Writes>,
+    ) -> Result<Self::Output, Self::Error> {
+        self.inner
+            .run_writer_buffered(env, buffer)
+            .await
+            .map_err(self.f)
+    }
 }
@@ -20,18 +20,25 @@ mod fallback;
 mod fallback_to;
 mod from_async;
 mod from_fn;
+mod from_future;
 mod from_result;
+mod loop_effect;
 mod map;
 mod map_err;
+mod on_cancel;
 mod or_else;
 mod pure;
 mod recover;
 mod recover_some;
 mod recover_with;
+mod sequence;
 mod tap;
 mod unless;
 mod with;
 mod zip;
+mod zip_asks;
+mod zip_join;
+mod zip_par;
 mod zip_with;
 
 pub use and_then::AndThen;
@@ -46,18 +53,25 @@ pub use fallback::Fallback;
 pub use fallback_to::FallbackTo;
 pub use from_async::FromAsync;
 pub use from_fn::FromFn;
+pub use from_future::FromFuture;
 pub use from_result::FromResult;
+pub use loop_effect::{Loop, LoopEffect};
 pub use map::Map;
 pub use map_err::MapErr;
+pub use on_cancel::OnCancel;
 pub use or_else::OrElse;
 pub use pure::Pure;
 pub use recover::Recover;
 pub use recover_some::RecoverSome;
 pub use recover_with::RecoverWith;
+pub use sequence::{SequenceOption, SequenceResult};
 pub use tap::Tap;
 pub use unless::Unless;
 pub use with::With;
 pub use zip::{Zip, Zip3, Zip4, Zip5, Zip6, Zip7, Zip8};
+pub use zip_asks::ZipAsks;
+pub use zip_join::ZipJoin;
+pub use zip_par::ZipPar;
 pub use zip_with::ZipWith;
 
 #[cfg(test)]
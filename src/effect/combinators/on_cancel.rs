@@ -0,0 +1,115 @@
+//! OnCancel combinator - run a synchronous hook if an effect is cancelled.
+
+use crate::effect::describe::type_name;
+use crate::effect::trait_def::Effect;
+
+/// An effect that runs a cleanup hook if it is dropped before completing.
+///
+/// Created by [`EffectExt::on_cancel`](crate::effect::ext::EffectExt::on_cancel).
+pub struct OnCancel<E, F> {
+    pub(crate) inner: E,
+    pub(crate) cleanup: F,
+}
+
+impl<E, F> std::fmt::Debug for OnCancel<E, F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&type_name::<Self>())
+    }
+}
+
+/// Runs the hook when dropped, unless disarmed first.
+struct CancelGuard<F: FnOnce()> {
+    cleanup: Option<F>,
+}
+
+impl<F: FnOnce()> CancelGuard<F> {
+    fn disarm(mut self) {
+        self.cleanup = None;
+    }
+}
+
+impl<F: FnOnce()> Drop for CancelGuard<F> {
+    fn drop(&mut self) {
+        if let Some(cleanup) = self.cleanup.take() {
+            cleanup();
+        }
+    }
+}
+
+impl<E, F> Effect for OnCancel<E, F>
+where
+    E: Effect,
+    F: FnOnce() + Send,
+{
+    type Output = E::Output;
+    type Error = E::Error;
+    type Env = E::Env;
+
+    async fn run(self, env: &Self::Env) -> Result<Self::Output, Self::Error> {
+        let guard = CancelGuard {
+            cleanup: Some(self.cleanup),
+        };
+        let result = self.inner.run(env).await;
+        guard.disarm();
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use crate::effect::constructors::{fail, from_async, pure};
+    use crate::effect::{Effect, EffectExt};
+
+    fn counter() -> (Arc<AtomicUsize>, impl FnOnce() + Send) {
+        let count = Arc::new(AtomicUsize::new(0));
+        let hook_count = Arc::clone(&count);
+        (count, move || {
+            hook_count.fetch_add(1, Ordering::SeqCst);
+        })
+    }
+
+    #[tokio::test]
+    async fn test_on_cancel_runs_when_dropped_mid_effect() {
+        let (cancelled, hook) = counter();
+        let effect = from_async(|_: &()| async {
+            tokio::time::sleep(Duration::from_secs(60)).await;
+            Ok::<_, String>(())
+        })
+        .on_cancel(hook);
+
+        let timed_out = tokio::time::timeout(Duration::from_millis(10), effect.run(&())).await;
+
+        assert!(timed_out.is_err());
+        assert_eq!(cancelled.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_on_cancel_skipped_when_effect_completes() {
+        let (succeeded, hook) = counter();
+        let result = pure::<_, String, ()>(1).on_cancel(hook).run(&()).await;
+        assert_eq!(result, Ok(1));
+        assert_eq!(succeeded.load(Ordering::SeqCst), 0);
+
+        let (failed, hook) = counter();
+        let result = fail::<i32, _, ()>("boom".to_string())
+            .on_cancel(hook)
+            .run(&())
+            .await;
+        assert_eq!(result, Err("boom".to_string()));
+        assert_eq!(failed.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_on_cancel_skipped_when_never_polled() {
+        let (cancelled, hook) = counter();
+        let effect = pure::<_, String, ()>(1).on_cancel(hook);
+
+        drop(effect.run(&()));
+
+        assert_eq!(cancelled.load(Ordering::SeqCst), 0);
+    }
+}
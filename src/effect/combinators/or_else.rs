@@ -1,5 +1,6 @@
 //! OrElse combinator - recovers from errors.
 
+use crate::effect::describe::type_name;
 use crate::effect::trait_def::Effect;
 
 /// OrElse combinator - recovers from errors.
@@ -20,6 +21,7 @@ use crate::effect::trait_def::Effect;
 ///     .or_else(|_| pure(42));
 /// assert_eq!(effect.execute(&()).await, Ok(42));
 /// ```
+#[derive(Clone)]
 pub struct OrElse<Inner, F> {
     pub(crate) inner: Inner,
     pub(crate) f: F,
@@ -27,10 +29,7 @@ pub struct OrElse<Inner, F> {
 
 impl<Inner, F> std::fmt::Debug for OrElse<Inner, F> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("OrElse")
-            .field("inner", &"<effect>")
-            .field("f", &"<function>")
-            .finish()
+        f.write_str(&type_name::<Self>())
     }
 }
 
@@ -82,4 +81,15 @@ where
             }
         }
     }
+
+    async fn run_writer_buffered(
+        self,
+        env: &Self::Env,
+        buffer: &crate::effect::writer::WriteBuffer<Self::Writes>,
+    ) -> Result<Self::Output, Self::Error> {
+        match self.inner.run_writer_buffered(env, buffer).await {
+            Ok(value) => Ok(value),
+            Err(e) => (self.f)(e).run_writer_buffered(env, buffer).await,
+        }
+    }
 }
@@ -37,7 +37,7 @@ impl<T, E, Env> Effect for Pure<T, E, Env>
 where
     T: Send,
     E: Send,
-    Env: Clone + Send + Sync,
+    Env: Send + Sync,
 {
     type Output = T;
     type Error = E;
@@ -1,5 +1,6 @@
 //! Recover combinator for selective error recovery.
 
+use crate::effect::describe::type_name;
 use crate::effect::Effect;
 use crate::predicate::Predicate;
 use std::marker::PhantomData;
@@ -35,11 +36,7 @@ pub struct Recover<E, P, H, E2> {
 
 impl<E, P, H, E2> std::fmt::Debug for Recover<E, P, H, E2> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("Recover")
-            .field("inner", &"<effect>")
-            .field("predicate", &"<predicate>")
-            .field("handler", &"<handler>")
-            .finish()
+        f.write_str(&type_name::<Self>())
     }
 }
 
@@ -1,5 +1,6 @@
 //! RecoverSome combinator for Option-based partial recovery.
 
+use crate::effect::describe::type_name;
 use crate::effect::Effect;
 use std::marker::PhantomData;
 
@@ -36,10 +37,7 @@ pub struct RecoverSome<E, F, E2> {
 
 impl<E, F, E2> std::fmt::Debug for RecoverSome<E, F, E2> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("RecoverSome")
-            .field("inner", &"<effect>")
-            .field("partial_fn", &"<function>")
-            .finish()
+        f.write_str(&type_name::<Self>())
     }
 }
 
@@ -1,5 +1,6 @@
 //! RecoverWith combinator for Result-returning recovery.
 
+use crate::effect::describe::type_name;
 use crate::effect::Effect;
 use crate::predicate::Predicate;
 
@@ -27,11 +28,7 @@ pub struct RecoverWith<E, P, F> {
 
 impl<E, P, F> std::fmt::Debug for RecoverWith<E, P, F> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("RecoverWith")
-            .field("inner", &"<effect>")
-            .field("predicate", &"<predicate>")
-            .field("handler", &"<handler>")
-            .finish()
+        f.write_str(&type_name::<Self>())
     }
 }
 
@@ -0,0 +1,88 @@
+//! SequenceOption and SequenceResult - effects from an optional effect.
+
+use crate::effect::describe::type_name;
+use crate::effect::trait_def::Effect;
+
+/// Effect from an `Option` of an effect.
+///
+/// Runs the effect if there is one and wraps its output in `Some`;
+/// succeeds with `None` otherwise.
+///
+/// Zero-cost: no heap allocation, and no boxing to unify the two branches.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use stillwater::effect::prelude::*;
+/// use stillwater::traverse::sequence_option;
+///
+/// let effect = sequence_option(Some(pure::<_, String, ()>(42)));
+/// assert_eq!(effect.execute(&()).await, Ok(Some(42)));
+/// ```
+pub struct SequenceOption<Eff> {
+    pub(crate) inner: Option<Eff>,
+}
+
+impl<Eff> std::fmt::Debug for SequenceOption<Eff> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&type_name::<Self>())
+    }
+}
+
+impl<Eff: Effect> Effect for SequenceOption<Eff> {
+    type Output = Option<Eff::Output>;
+    type Error = Eff::Error;
+    type Env = Eff::Env;
+
+    async fn run(self, env: &Self::Env) -> Result<Self::Output, Self::Error> {
+        match self.inner {
+            Some(effect) => effect.run(env).await.map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Effect from a `Result` of an effect.
+///
+/// Runs the effect on `Ok` and wraps its output in `Ok`; succeeds with the
+/// `Err` value otherwise. The `Err` value is part of the output, not a
+/// failure of the effect.
+///
+/// Zero-cost: no heap allocation, and no boxing to unify the two branches.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use stillwater::effect::prelude::*;
+/// use stillwater::traverse::sequence_result;
+///
+/// let skipped: Result<Pure<i32, String, ()>, &str> = Err("not configured");
+/// let effect = sequence_result(skipped);
+/// assert_eq!(effect.execute(&()).await, Ok(Err("not configured")));
+/// ```
+pub struct SequenceResult<Eff, X> {
+    pub(crate) inner: Result<Eff, X>,
+}
+
+impl<Eff, X> std::fmt::Debug for SequenceResult<Eff, X> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&type_name::<Self>())
+    }
+}
+
+impl<Eff, X> Effect for SequenceResult<Eff, X>
+where
+    Eff: Effect,
+    X: Send,
+{
+    type Output = Result<Eff::Output, X>;
+    type Error = Eff::Error;
+    type Env = Eff::Env;
+
+    async fn run(self, env: &Self::Env) -> Result<Self::Output, Self::Error> {
+        match self.inner {
+            Ok(effect) => effect.run(env).await.map(Ok),
+            Err(skipped) => Ok(Err(skipped)),
+        }
+    }
+}
@@ -2,18 +2,34 @@
 
 use std::marker::PhantomData;
 
+use crate::effect::describe::type_name;
 use crate::effect::trait_def::Effect;
 
 /// An effect that performs a side effect and returns the original value.
 ///
 /// Created by [`EffectExt::tap`](crate::effect::ext::EffectExt::tap).
-#[derive(Debug)]
 pub struct Tap<E, F, E2> {
     pub(crate) inner: E,
     pub(crate) f: F,
     pub(crate) _marker: PhantomData<E2>,
 }
 
+impl<E: Clone, F: Clone, E2> Clone for Tap<E, F, E2> {
+    fn clone(&self) -> Self {
+        Tap {
+            inner: self.inner.clone(),
+            f: self.f.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<E, F, E2> std::fmt::Debug for Tap<E, F, E2> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&type_name::<Self>())
+    }
+}
+
 impl<E, F, E2> Effect for Tap<E, F, E2>
 where
     E: Effect,
@@ -1,5 +1,6 @@
 //! Unless combinator for inverse validation logic.
 
+use crate::effect::describe::type_name;
 use crate::effect::Effect;
 
 /// Fails if the predicate returns true (inverse of Ensure).
@@ -25,11 +26,7 @@ pub struct Unless<E, P, Err> {
 
 impl<E, P, Err> std::fmt::Debug for Unless<E, P, Err> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("Unless")
-            .field("inner", &"<effect>")
-            .field("predicate", &"<function>")
-            .field("error", &"<error>")
-            .finish()
+        f.write_str(&type_name::<Self>())
     }
 }
 
@@ -2,18 +2,24 @@
 
 use std::marker::PhantomData;
 
+use crate::effect::describe::type_name;
 use crate::effect::trait_def::Effect;
 
 /// An effect that combines two effects, returning a tuple of their results.
 ///
 /// Created by [`EffectExt::with`](crate::effect::ext::EffectExt::with).
-#[derive(Debug)]
 pub struct With<E, F, E2> {
     pub(crate) inner: E,
     pub(crate) f: F,
     pub(crate) _marker: PhantomData<E2>,
 }
 
+impl<E, F, E2> std::fmt::Debug for With<E, F, E2> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&type_name::<Self>())
+    }
+}
+
 impl<E, F, E2> Effect for With<E, F, E2>
 where
     E: Effect,
@@ -1,5 +1,6 @@
 //! Zip combinator - combines two independent effects into a tuple.
 
+use crate::effect::describe::type_name;
 use crate::effect::trait_def::Effect;
 
 /// Combines two effects, running them sequentially and returning both results.
@@ -10,8 +11,9 @@ use crate::effect::trait_def::Effect;
 /// # Execution Order
 ///
 /// Effects are executed sequentially (first, then second) for simplicity
-/// and predictability. Use `zip_par` variants (via `par2`, `par3`, etc.)
-/// when concurrent execution is needed.
+/// and predictability. Use [`ZipPar`](super::ZipPar) or
+/// [`ZipJoin`](super::ZipJoin), via `zip_par` and `zip_join`, when
+/// concurrent execution is needed.
 ///
 /// # Example
 ///
@@ -21,12 +23,18 @@ use crate::effect::trait_def::Effect;
 /// let effect = pure::<_, String, ()>(1).zip(pure(2));
 /// assert_eq!(effect.execute(&()).await, Ok((1, 2)));
 /// ```
-#[derive(Debug)]
+#[derive(Clone)]
 pub struct Zip<E1, E2> {
     pub(crate) first: E1,
     pub(crate) second: E2,
 }
 
+impl<E1, E2> std::fmt::Debug for Zip<E1, E2> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&type_name::<Self>())
+    }
+}
+
 impl<E1, E2> Zip<E1, E2> {
     /// Create a new Zip combinator from two effects.
     pub fn new(first: E1, second: E2) -> Self {
@@ -95,13 +103,19 @@ where
 /// let effect = zip3(pure(1), pure(2), pure(3));
 /// assert_eq!(effect.execute(&()).await, Ok((1, 2, 3)));
 /// ```
-#[derive(Debug)]
+#[derive(Clone)]
 pub struct Zip3<E1, E2, E3> {
     e1: E1,
     e2: E2,
     e3: E3,
 }
 
+impl<E1, E2, E3> std::fmt::Debug for Zip3<E1, E2, E3> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&type_name::<Self>())
+    }
+}
+
 impl<E1, E2, E3> Zip3<E1, E2, E3> {
     /// Create a new Zip3 combinator from three effects.
     pub fn new(e1: E1, e2: E2, e3: E3) -> Self {
@@ -130,7 +144,7 @@ where
 /// Combines four effects into a flat tuple.
 ///
 /// This is zero-cost: no heap allocation occurs.
-#[derive(Debug)]
+#[derive(Clone)]
 pub struct Zip4<E1, E2, E3, E4> {
     e1: E1,
     e2: E2,
@@ -138,6 +152,12 @@ pub struct Zip4<E1, E2, E3, E4> {
     e4: E4,
 }
 
+impl<E1, E2, E3, E4> std::fmt::Debug for Zip4<E1, E2, E3, E4> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&type_name::<Self>())
+    }
+}
+
 impl<E1, E2, E3, E4> Zip4<E1, E2, E3, E4> {
     /// Create a new Zip4 combinator from four effects.
     pub fn new(e1: E1, e2: E2, e3: E3, e4: E4) -> Self {
@@ -168,7 +188,7 @@ where
 /// Combines five effects into a flat tuple.
 ///
 /// This is zero-cost: no heap allocation occurs.
-#[derive(Debug)]
+#[derive(Clone)]
 pub struct Zip5<E1, E2, E3, E4, E5> {
     e1: E1,
     e2: E2,
@@ -177,6 +197,12 @@ pub struct Zip5<E1, E2, E3, E4, E5> {
     e5: E5,
 }
 
+impl<E1, E2, E3, E4, E5> std::fmt::Debug for Zip5<E1, E2, E3, E4, E5> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&type_name::<Self>())
+    }
+}
+
 impl<E1, E2, E3, E4, E5> Zip5<E1, E2, E3, E4, E5> {
     /// Create a new Zip5 combinator from five effects.
     pub fn new(e1: E1, e2: E2, e3: E3, e4: E4, e5: E5) -> Self {
@@ -209,7 +235,7 @@ where
 /// Combines six effects into a flat tuple.
 ///
 /// This is zero-cost: no heap allocation occurs.
-#[derive(Debug)]
+#[derive(Clone)]
 pub struct Zip6<E1, E2, E3, E4, E5, E6> {
     e1: E1,
     e2: E2,
@@ -219,6 +245,12 @@ pub struct Zip6<E1, E2, E3, E4, E5, E6> {
     e6: E6,
 }
 
+impl<E1, E2, E3, E4, E5, E6> std::fmt::Debug for Zip6<E1, E2, E3, E4, E5, E6> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&type_name::<Self>())
+    }
+}
+
 impl<E1, E2, E3, E4, E5, E6> Zip6<E1, E2, E3, E4, E5, E6> {
     /// Create a new Zip6 combinator from six effects.
     pub fn new(e1: E1, e2: E2, e3: E3, e4: E4, e5: E5, e6: E6) -> Self {
@@ -267,7 +299,7 @@ where
 /// Combines seven effects into a flat tuple.
 ///
 /// This is zero-cost: no heap allocation occurs.
-#[derive(Debug)]
+#[derive(Clone)]
 pub struct Zip7<E1, E2, E3, E4, E5, E6, E7> {
     e1: E1,
     e2: E2,
@@ -278,6 +310,12 @@ pub struct Zip7<E1, E2, E3, E4, E5, E6, E7> {
     e7: E7,
 }
 
+impl<E1, E2, E3, E4, E5, E6, E7> std::fmt::Debug for Zip7<E1, E2, E3, E4, E5, E6, E7> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&type_name::<Self>())
+    }
+}
+
 impl<E1, E2, E3, E4, E5, E6, E7> Zip7<E1, E2, E3, E4, E5, E6, E7> {
     /// Create a new Zip7 combinator from seven effects.
     pub fn new(e1: E1, e2: E2, e3: E3, e4: E4, e5: E5, e6: E6, e7: E7) -> Self {
@@ -330,7 +368,7 @@ where
 /// Combines eight effects into a flat tuple.
 ///
 /// This is zero-cost: no heap allocation occurs.
-#[derive(Debug)]
+#[derive(Clone)]
 pub struct Zip8<E1, E2, E3, E4, E5, E6, E7, E8> {
     e1: E1,
     e2: E2,
@@ -342,6 +380,12 @@ pub struct Zip8<E1, E2, E3, E4, E5, E6, E7, E8> {
     e8: E8,
 }
 
+impl<E1, E2, E3, E4, E5, E6, E7, E8> std::fmt::Debug for Zip8<E1, E2, E3, E4, E5, E6, E7, E8> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&type_name::<Self>())
+    }
+}
+
 impl<E1, E2, E3, E4, E5, E6, E7, E8> Zip8<E1, E2, E3, E4, E5, E6, E7, E8> {
     /// Create a new Zip8 combinator from eight effects.
     #[allow(clippy::too_many_arguments)]
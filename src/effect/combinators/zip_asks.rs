@@ -0,0 +1,76 @@
+//! ZipAsks combinator - pairs the success value with a value read from the environment.
+
+use crate::effect::describe::type_name;
+#[cfg(feature = "fuel")]
+use crate::effect::fuel;
+use crate::effect::trait_def::Effect;
+
+/// ZipAsks combinator - pairs the success value with a projection of the environment.
+///
+/// Zero-cost: no heap allocation. The projection runs against the same
+/// environment the inner effect ran with, after it succeeds. With the `fuel`
+/// feature, it also charges one unit of fuel when run under `run_with_fuel`.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use stillwater::effect::prelude::*;
+///
+/// let effect = pure::<_, String, Config>(42).zip_asks(|cfg: &Config| cfg.limit);
+/// assert_eq!(effect.execute(&config).await, Ok((42, 100)));
+/// ```
+pub struct ZipAsks<Inner, F> {
+    pub(crate) inner: Inner,
+    pub(crate) f: F,
+}
+
+impl<Inner, F> std::fmt::Debug for ZipAsks<Inner, F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&type_name::<Self>())
+    }
+}
+
+impl<Inner, F, U> Effect for ZipAsks<Inner, F>
+where
+    Inner: Effect,
+    F: FnOnce(&Inner::Env) -> U + Send,
+    U: Send,
+{
+    type Output = (Inner::Output, U);
+    type Error = Inner::Error;
+    type Env = Inner::Env;
+
+    async fn run(self, env: &Self::Env) -> Result<Self::Output, Self::Error> {
+        let value = self.inner.run(env).await?;
+        #[cfg(feature = "fuel")]
+        fuel::tick().await;
+        Ok((value, (self.f)(env)))
+    }
+}
+
+// WriterEffect implementation for ZipAsks - passes writes through unchanged
+impl<Inner, F, U> crate::effect::writer::WriterEffect for ZipAsks<Inner, F>
+where
+    Inner: crate::effect::writer::WriterEffect,
+    F: FnOnce(&Inner::Env) -> U + Send,
+    U: Send,
+{
+    type Writes = Inner::Writes;
+
+    async fn run_writer(
+        self,
+        env: &Self::Env,
+    ) -> (Result<Self::Output, Self::Error>, Self::Writes) {
+        let (result, writes) = self.inner.run_writer(env).await;
+        (result.map(|value| (value, (self.f)(env))), writes)
+    }
+
+    async fn run_writer_buffered(
+        self,
+        env: &Self::Env,
+        buffer: &crate::effect::writer::WriteBuffer<Self::Writes>,
+    ) -> Result<Self::Output, Self::Error> {
+        let value = self.inner.run_writer_buffered(env, buffer).await?;
+        Ok((value, (self.f)(env)))
+    }
+}
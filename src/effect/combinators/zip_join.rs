@@ -0,0 +1,85 @@
+//! ZipJoin combinator - runs two effects concurrently, waiting for both.
+
+use crate::effect::describe::type_name;
+use crate::effect::trait_def::Effect;
+use crate::Semigroup;
+
+/// Runs two effects concurrently and waits for both, combining their
+/// errors if both fail.
+///
+/// Neither side is cancelled when the other fails. If both fail, the
+/// errors are combined with [`Semigroup::combine`], first then second.
+///
+/// Created by [`EffectExt::zip_join`](crate::effect::EffectExt::zip_join).
+pub struct ZipJoin<E1, E2> {
+    pub(crate) first: E1,
+    pub(crate) second: E2,
+}
+
+impl<E1, E2> std::fmt::Debug for ZipJoin<E1, E2> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&type_name::<Self>())
+    }
+}
+
+impl<E1, E2> Effect for ZipJoin<E1, E2>
+where
+    E1: Effect,
+    E1::Error: Semigroup,
+    E2: Effect<Error = E1::Error, Env = E1::Env>,
+{
+    type Output = (E1::Output, E2::Output);
+    type Error = E1::Error;
+    type Env = E1::Env;
+
+    async fn run(self, env: &Self::Env) -> Result<Self::Output, Self::Error> {
+        match futures::future::join(self.first.run(env), self.second.run(env)).await {
+            (Ok(first), Ok(second)) => Ok((first, second)),
+            (Err(first), Err(second)) => Err(first.combine(second)),
+            (Err(e), Ok(_)) | (Ok(_), Err(e)) => Err(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use crate::effect::constructors::{fail, from_async, pure};
+    use crate::effect::{Effect, EffectExt};
+
+    #[tokio::test]
+    async fn test_zip_join_combines_both_errors() {
+        let result = fail::<u32, _, ()>(vec!["left"])
+            .zip_join(fail::<u32, _, ()>(vec!["right"]))
+            .run(&())
+            .await;
+
+        assert_eq!(result, Err(vec!["left", "right"]));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_zip_join_awaits_other_side_on_failure() {
+        let finished = Arc::new(AtomicBool::new(false));
+        let flag = Arc::clone(&finished);
+        let slow = from_async(move |_: &()| async move {
+            tokio::time::sleep(Duration::from_secs(60)).await;
+            flag.store(true, Ordering::SeqCst);
+            Ok::<_, Vec<&str>>(1)
+        });
+
+        let result = fail::<u32, _, ()>(vec!["boom"])
+            .zip_join(slow)
+            .run(&())
+            .await;
+
+        assert_eq!(result, Err(vec!["boom"]));
+        assert!(finished.load(Ordering::SeqCst));
+        assert_eq!(
+            pure::<_, Vec<&str>, ()>(1).zip_join(pure(2)).run(&()).await,
+            Ok((1, 2))
+        );
+    }
+}
@@ -0,0 +1,83 @@
+//! ZipPar combinator - runs two effects concurrently, failing fast.
+
+use crate::effect::describe::type_name;
+use crate::effect::trait_def::Effect;
+
+/// Runs two effects concurrently and returns both results, failing as soon
+/// as either fails.
+///
+/// When one side fails, the other is dropped, and so cancelled, without
+/// waiting for it to finish.
+///
+/// Created by [`EffectExt::zip_par`](crate::effect::EffectExt::zip_par).
+pub struct ZipPar<E1, E2> {
+    pub(crate) first: E1,
+    pub(crate) second: E2,
+}
+
+impl<E1, E2> std::fmt::Debug for ZipPar<E1, E2> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&type_name::<Self>())
+    }
+}
+
+impl<E1, E2> Effect for ZipPar<E1, E2>
+where
+    E1: Effect,
+    E2: Effect<Error = E1::Error, Env = E1::Env>,
+{
+    type Output = (E1::Output, E2::Output);
+    type Error = E1::Error;
+    type Env = E1::Env;
+
+    async fn run(self, env: &Self::Env) -> Result<Self::Output, Self::Error> {
+        futures::future::try_join(self.first.run(env), self.second.run(env)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use crate::effect::constructors::{fail, from_async, pure};
+    use crate::effect::{Effect, EffectExt};
+
+    #[tokio::test(start_paused = true)]
+    async fn test_zip_par_runs_both_sides_concurrently() {
+        let slow = |value: u32| {
+            from_async(move |_: &()| async move {
+                tokio::time::sleep(Duration::from_secs(1)).await;
+                Ok::<_, String>(value)
+            })
+        };
+
+        let start = tokio::time::Instant::now();
+        assert_eq!(slow(1).zip_par(slow(2)).run(&()).await, Ok((1, 2)));
+        assert_eq!(start.elapsed(), Duration::from_secs(1));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_zip_par_cancels_other_side_on_failure() {
+        let finished = Arc::new(AtomicBool::new(false));
+        let flag = Arc::clone(&finished);
+        let slow = from_async(move |_: &()| async move {
+            tokio::time::sleep(Duration::from_secs(60)).await;
+            flag.store(true, Ordering::SeqCst);
+            Ok::<_, String>(1)
+        });
+
+        let result = slow
+            .zip_par(fail::<u32, _, ()>("boom".to_string()))
+            .run(&())
+            .await;
+
+        assert_eq!(result, Err("boom".to_string()));
+        assert!(!finished.load(Ordering::SeqCst));
+        assert_eq!(
+            pure::<_, String, ()>(1).zip_par(pure(2)).run(&()).await,
+            Ok((1, 2))
+        );
+    }
+}
@@ -1,5 +1,6 @@
 //! ZipWith combinator - combines two effects with a function.
 
+use crate::effect::describe::type_name;
 use crate::effect::trait_def::Effect;
 
 /// Combines two effects with a function.
@@ -16,13 +17,18 @@ use crate::effect::trait_def::Effect;
 ///     .zip_with(pure(3), |a, b| a * b);
 /// assert_eq!(effect.execute(&()).await, Ok(6));
 /// ```
-#[derive(Debug)]
 pub struct ZipWith<E1, E2, F> {
     pub(crate) first: E1,
     pub(crate) second: E2,
     pub(crate) f: F,
 }
 
+impl<E1, E2, F> std::fmt::Debug for ZipWith<E1, E2, F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&type_name::<Self>())
+    }
+}
+
 impl<E1, E2, F> ZipWith<E1, E2, F> {
     /// Create a new ZipWith combinator.
     pub fn new(first: E1, second: E2, f: F) -> Self {
@@ -27,6 +27,37 @@
 //! 4. **Add `.boxed()` where needed**: For collections, recursive effects, or match arms
 //!    with different effect types.
 //!
+//! ## Migrating File by File
+//!
+//! Because `LegacyEffect` is an alias for `BoxedEffect`, old and new code
+//! already interoperate. Two bridges make the boundary explicit:
+//!
+//! - [`IntoTraitEffect::into_trait_effect`] hands a legacy effect to new
+//!   code as an opaque `impl Effect`, so nothing downstream depends on the
+//!   boxed type.
+//! - [`IntoLegacyEffect::into_legacy`] boxes any trait-based effect for
+//!   old code that still names `LegacyEffect<T, E, Env>` in signatures.
+//!
+//! ```rust
+//! # #![allow(deprecated)]
+//! use stillwater::effect::compat::{IntoLegacyEffect, IntoTraitEffect, LegacyEffect};
+//! use stillwater::effect::prelude::*;
+//!
+//! // Not yet migrated
+//! fn load_count() -> LegacyEffect<u32, String, ()> {
+//!     pure(41).into_legacy()
+//! }
+//!
+//! // Migrated
+//! fn next_count() -> impl Effect<Output = u32, Error = String, Env = ()> {
+//!     load_count().into_trait_effect().map(|n| n + 1)
+//! }
+//!
+//! # tokio_test::block_on(async {
+//! assert_eq!(next_count().run(&()).await, Ok(42));
+//! # });
+//! ```
+//!
 //! ## Example Migration
 //!
 //! ```rust,ignore
@@ -55,6 +86,7 @@
 
 use crate::effect::boxed::BoxedEffect;
 use crate::effect::ext::EffectExt;
+use crate::effect::trait_def::Effect;
 
 /// Type alias for backward compatibility.
 ///
@@ -75,20 +107,22 @@ pub trait LegacyConstructors<T, E, Env>: Sized {
     /// Create a pure effect (boxed).
     ///
     /// Deprecated: Use `pure(value).boxed()` instead.
+    #[deprecated(since = "0.11.0", note = "Use `pure(value).boxed()` instead")]
     fn legacy_pure(value: T) -> BoxedEffect<T, E, Env>
     where
         T: Send + 'static,
         E: Send + 'static,
-        Env: Clone + Send + Sync + 'static;
+        Env: Send + Sync + 'static;
 
     /// Create a failing effect (boxed).
     ///
     /// Deprecated: Use `fail(error).boxed()` instead.
+    #[deprecated(since = "0.11.0", note = "Use `fail(error).boxed()` instead")]
     fn legacy_fail(error: E) -> BoxedEffect<T, E, Env>
     where
         T: Send + 'static,
         E: Send + 'static,
-        Env: Clone + Send + Sync + 'static;
+        Env: Send + Sync + 'static;
 }
 
 #[allow(deprecated)]
@@ -97,7 +131,7 @@ impl<T, E, Env> LegacyConstructors<T, E, Env> for BoxedEffect<T, E, Env> {
     where
         T: Send + 'static,
         E: Send + 'static,
-        Env: Clone + Send + Sync + 'static,
+        Env: Send + Sync + 'static,
     {
         crate::effect::constructors::pure(value).boxed()
     }
@@ -106,12 +140,52 @@ impl<T, E, Env> LegacyConstructors<T, E, Env> for BoxedEffect<T, E, Env> {
     where
         T: Send + 'static,
         E: Send + 'static,
-        Env: Clone + Send + Sync + 'static,
+        Env: Send + Sync + 'static,
     {
         crate::effect::constructors::fail(error).boxed()
     }
 }
 
+/// Conversion from a legacy effect to an opaque trait-based effect.
+///
+/// Use at the edge of migrated code so it only relies on the `Effect`
+/// trait. The conversion is free: the boxed effect is returned as is.
+pub trait IntoTraitEffect<T, E, Env> {
+    /// Hide the legacy boxed type behind `impl Effect`.
+    fn into_trait_effect(self) -> impl Effect<Output = T, Error = E, Env = Env>;
+}
+
+impl<T, E, Env> IntoTraitEffect<T, E, Env> for BoxedEffect<T, E, Env>
+where
+    T: Send + 'static,
+    E: Send + 'static,
+    Env: Send + Sync + 'static,
+{
+    fn into_trait_effect(self) -> impl Effect<Output = T, Error = E, Env = Env> {
+        self
+    }
+}
+
+/// Conversion from any trait-based effect to the legacy boxed form.
+///
+/// Use where migrated code is called from code that still names
+/// `LegacyEffect<T, E, Env>`. This boxes the effect; once the caller is
+/// migrated, drop the call to keep the zero-cost type.
+pub trait IntoLegacyEffect: Effect + Sized {
+    /// Box this effect as a `LegacyEffect`.
+    fn into_legacy(self) -> BoxedEffect<Self::Output, Self::Error, Self::Env>
+    where
+        Self: 'static,
+        Self::Output: 'static,
+        Self::Error: 'static,
+        Self::Env: 'static,
+    {
+        self.boxed()
+    }
+}
+
+impl<Eff: Effect> IntoLegacyEffect for Eff {}
+
 /// Extension trait for running effects with unit environment.
 #[allow(async_fn_in_trait)]
 pub trait RunStandalone: crate::effect::trait_def::Effect<Env = ()> {
@@ -126,3 +200,23 @@ impl<E: crate::effect::trait_def::Effect<Env = ()>> RunStandalone for E {
         self.run(&()).await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::effect::constructors::{fail, pure};
+
+    #[tokio::test]
+    #[allow(deprecated)]
+    async fn test_round_trip_through_legacy() {
+        let legacy: LegacyEffect<i32, String, ()> = pure(20).map(|x| x * 2).into_legacy();
+        let effect = legacy.into_trait_effect().map(|x| x + 2);
+        assert_eq!(effect.run(&()).await, Ok(42));
+
+        let failing: LegacyEffect<i32, String, ()> = fail("gone".to_string()).into_legacy();
+        assert_eq!(
+            failing.into_trait_effect().run(&()).await,
+            Err("gone".to_string())
+        );
+    }
+}
@@ -0,0 +1,56 @@
+//! Misuse of resource and capability tracking that is rejected at compile
+//! time.
+//!
+//! [`resource`](crate::effect::resource) and [`caps`](crate::effect::caps)
+//! express their guarantees as trait bounds, so a loosened bound would let
+//! these programs compile without any test noticing. Each example below is
+//! included verbatim from `tests/compile_fail/`, where `trybuild` also
+//! checks the error the compiler reports; here they run as `compile_fail`
+//! doctests.
+//!
+//! # Resource tracking
+//!
+//! An effect that acquires a resource is not resource-neutral:
+//!
+//! ```compile_fail
+#![doc = include_str!("../../tests/compile_fail/resource_not_neutral.rs")]
+//! ```
+//!
+//! A function promising a neutral effect cannot leak an acquisition through
+//! `map`:
+//!
+//! ```compile_fail
+#![doc = include_str!("../../tests/compile_fail/resource_leak_in_signature.rs")]
+//! ```
+//!
+//! A tracked bracket only accepts an acquire effect for its own resource
+//! kind:
+//!
+//! ```compile_fail
+#![doc = include_str!("../../tests/compile_fail/tracked_bracket_wrong_kind.rs")]
+//! ```
+//!
+//! # Capability tracking
+//!
+//! An effect that uses a capability cannot pass
+//! [`assert_pure`](crate::effect::caps::assert_pure):
+//!
+//! ```compile_fail
+#![doc = include_str!("../../tests/compile_fail/caps_not_pure.rs")]
+//! ```
+//!
+//! `and_then` carries capabilities forward, so a pure-core signature rejects
+//! a chain that reads the clock:
+//!
+//! ```compile_fail
+#![doc = include_str!("../../tests/compile_fail/caps_leak_through_and_then.rs")]
+//! ```
+//!
+//! A declared capability set must match the capabilities used:
+//!
+//! ```compile_fail
+#![doc = include_str!("../../tests/compile_fail/caps_undeclared.rs")]
+//! ```
+
+// The examples are complete programs shared with trybuild.
+#![allow(clippy::needless_doctest_main)]
@@ -33,6 +33,9 @@
 //! - [`from_option`] - Lift an `Option` into an effect
 //! - [`from_validation`] - Convert `Validation` to effect
 //!
+//! ## Looping
+//! - [`loop_effect`] - Iterate an effect with explicit state (stack-safe)
+//!
 //! ## Reader Operations
 //! - [`ask`] - Get the entire environment
 //! - [`asks`] - Query a value from environment
@@ -77,7 +80,7 @@
 use std::future::Future;
 
 use crate::effect::combinators::{
-    Fail, FromAsync, FromFn, FromResult, Pure, Zip3, Zip4, Zip5, Zip6, Zip7, Zip8,
+    Fail, FromAsync, FromFn, FromResult, Loop, LoopEffect, Pure, Zip3, Zip4, Zip5, Zip6, Zip7, Zip8,
 };
 use crate::effect::reader::{Ask, Asks, Local};
 use crate::effect::trait_def::Effect;
@@ -100,7 +103,7 @@ pub fn pure<T, E, Env>(value: T) -> Pure<T, E, Env>
 where
     T: Send,
     E: Send,
-    Env: Clone + Send + Sync,
+    Env: Send + Sync,
 {
     Pure::new(value)
 }
@@ -123,7 +126,7 @@ pub fn fail<T, E, Env>(error: E) -> Fail<T, E, Env>
 where
     T: Send,
     E: Send,
-    Env: Clone + Send + Sync,
+    Env: Send + Sync,
 {
     Fail::new(error)
 }
@@ -150,7 +153,7 @@ where
     F: FnOnce(&Env) -> Result<T, E> + Send,
     T: Send,
     E: Send,
-    Env: Clone + Send + Sync,
+    Env: Send + Sync,
 {
     FromFn::new(f)
 }
@@ -175,7 +178,7 @@ where
     Fut: Future<Output = Result<T, E>> + Send,
     T: Send,
     E: Send,
-    Env: Clone + Send + Sync,
+    Env: Send + Sync,
 {
     FromAsync::new(f)
 }
@@ -199,7 +202,7 @@ pub fn from_result<T, E, Env>(result: Result<T, E>) -> FromResult<T, E, Env>
 where
     T: Send,
     E: Send,
-    Env: Clone + Send + Sync,
+    Env: Send + Sync,
 {
     FromResult::new(result)
 }
@@ -226,7 +229,7 @@ pub fn from_option<T, E, Env>(
 where
     T: Send,
     E: Send,
-    Env: Clone + Send + Sync,
+    Env: Send + Sync,
 {
     FromResult::new(option.ok_or_else(error_fn))
 }
@@ -250,7 +253,7 @@ where
 /// ```
 pub fn ask<E, Env>() -> Ask<E, Env>
 where
-    Env: Clone + Send + Sync,
+    Env: Send + Sync,
     E: Send,
 {
     Ask::new()
@@ -281,7 +284,7 @@ where
     F: FnOnce(&Env) -> U + Send,
     U: Send,
     E: Send,
-    Env: Clone + Send + Sync,
+    Env: Send + Sync,
 {
     Asks::new(f)
 }
@@ -316,7 +319,7 @@ pub fn local<Inner, F, Env2>(f: F, inner: Inner) -> Local<Inner, F, Env2>
 where
     Inner: Effect,
     F: FnOnce(&Env2) -> Inner::Env + Send,
-    Env2: Clone + Send + Sync,
+    Env2: Send + Sync,
 {
     Local::new(inner, f)
 }
@@ -346,7 +349,7 @@ pub fn from_validation<T, E, Env>(validation: crate::Validation<T, E>) -> FromRe
 where
     T: Send,
     E: Send,
-    Env: Clone + Send + Sync,
+    Env: Send + Sync,
 {
     let result = match validation {
         crate::Validation::Success(value) => Ok(value),
@@ -355,6 +358,40 @@ where
     FromResult::new(result)
 }
 
+/// Loop over an effect with explicit state until it signals `Loop::Break`.
+///
+/// The step function receives the current state and returns an effect
+/// producing either `Loop::Continue(next_state)` or `Loop::Break(result)`.
+/// Iteration happens inside a single future, so deep loops are stack-safe
+/// and don't require building recursive `BoxedEffect` chains.
+///
+/// # Example
+///
+/// ```rust
+/// use stillwater::effect::prelude::*;
+///
+/// # tokio_test::block_on(async {
+/// // Countdown without recursive boxing
+/// let effect = loop_effect((5, 0), |(n, sum): (i32, i32)| {
+///     if n <= 0 {
+///         pure::<_, String, ()>(Loop::Break(sum))
+///     } else {
+///         pure(Loop::Continue((n - 1, sum + n)))
+///     }
+/// });
+/// assert_eq!(effect.execute(&()).await, Ok(15));
+/// # });
+/// ```
+pub fn loop_effect<S, R, F, Eff>(initial: S, step: F) -> LoopEffect<S, F, Eff>
+where
+    S: Send,
+    R: Send,
+    F: FnMut(S) -> Eff + Send,
+    Eff: Effect<Output = Loop<S, R>>,
+{
+    LoopEffect::new(initial, step)
+}
+
 /// Combine three effects into a flat tuple.
 ///
 /// Zero-cost: returns a concrete `Zip3` type, no heap allocation.
@@ -0,0 +1,210 @@
+//! Readable names for effect types.
+//!
+//! Combinator types nest deeply: `pure(1).map(f).and_then(g)` has a type
+//! like `AndThen<Map<Pure<i32, String, ()>, {closure}>, {closure}>`, and the
+//! full path spelled out by [`std::any::type_name`] is longer still. This
+//! module turns such a type into a compact chain, `Pure -> Map -> AndThen`,
+//! listing the stages in the order they run. Effects a combinator nests,
+//! such as the second half of a `zip`, are shown in parentheses:
+//! `Pure -> Zip(Pure -> Map)`.
+//!
+//! The `Debug` output of the combinator types uses the same description.
+//!
+//! Only effect types named in the type are shown. The effect returned by a
+//! closure, as in `and_then`, is hidden inside the closure's type and does
+//! not appear.
+//!
+//! # Example
+//!
+//! ```rust
+//! use stillwater::effect::prelude::*;
+//! use stillwater::effect::describe::type_name_of_val;
+//!
+//! let effect = pure::<_, String, ()>(1)
+//!     .map(|x| x + 1)
+//!     .and_then(|x| pure(x * 2))
+//!     .zip(fail::<i32, _, ()>("boom".to_string()));
+//!
+//! assert_eq!(type_name_of_val(&effect), "Pure -> Map -> AndThen -> Zip(Fail)");
+//! assert_eq!(format!("{:?}", effect), "Pure -> Map -> AndThen -> Zip(Fail)");
+//! ```
+
+/// Combinators and the positions of their effect type parameters.
+///
+/// The first position is the effect the combinator wraps; the rest are
+/// effects it runs besides, shown in parentheses. Types not listed here
+/// are shown by name alone.
+const COMBINATORS: &[(&str, &[usize])] = &[
+    ("AndThen", &[0]),
+    ("AndThenAuto", &[0, 2]),
+    ("AndThenRef", &[0, 2]),
+    ("Censor", &[0]),
+    ("Check", &[0]),
+    ("Ensure", &[0]),
+    ("EnsurePred", &[0]),
+    ("EnsureWith", &[0]),
+    ("Fallback", &[0]),
+    ("FallbackTo", &[0, 1]),
+    ("Instrument", &[0]),
+    ("IntoSink", &[0]),
+    ("IntoWriter", &[0]),
+    ("Listen", &[0]),
+    ("Local", &[0]),
+    ("Map", &[0]),
+    ("MapEnvFor", &[0]),
+    ("MapErr", &[0]),
+    ("OrElse", &[0]),
+    ("Pass", &[0]),
+    ("Profile", &[0]),
+    ("Recover", &[0, 3]),
+    ("RecoverSome", &[0, 2]),
+    ("RecoverWith", &[0]),
+    ("Refine", &[0]),
+    ("RefineAccumulate", &[0]),
+    ("SequenceOption", &[0]),
+    ("SequenceResult", &[0]),
+    ("SinkAndThen", &[0]),
+    ("SinkGroupByKey", &[0]),
+    ("SinkMap", &[0]),
+    ("SinkMapErr", &[0]),
+    ("SinkOrElse", &[0]),
+    ("SinkSlidingWindow", &[0]),
+    ("SinkWindow", &[0]),
+    ("SinkZip", &[0, 1]),
+    ("Tap", &[0, 2]),
+    ("TapEmit", &[0]),
+    ("TapTell", &[0]),
+    ("Traced", &[0]),
+    ("Tracked", &[0]),
+    ("Unless", &[0]),
+    ("With", &[0, 2]),
+    ("WithPermit", &[0]),
+    ("WithRequestContext", &[0]),
+    ("WriterAndThen", &[0]),
+    ("WriterMap", &[0]),
+    ("WriterMapErr", &[0]),
+    ("WriterOrElse", &[0]),
+    ("WriterZip", &[0, 1]),
+    ("Zip", &[0, 1]),
+    ("Zip3", &[0, 1, 2]),
+    ("Zip4", &[0, 1, 2, 3]),
+    ("Zip5", &[0, 1, 2, 3, 4]),
+    ("Zip6", &[0, 1, 2, 3, 4, 5]),
+    ("Zip7", &[0, 1, 2, 3, 4, 5, 6]),
+    ("Zip8", &[0, 1, 2, 3, 4, 5, 6, 7]),
+    ("ZipAsks", &[0]),
+    ("ZipWith", &[0, 1]),
+];
+
+/// A compact description of the effect type `Eff`.
+///
+/// # Example
+///
+/// ```rust
+/// use stillwater::effect::combinators::{Map, Pure};
+/// use stillwater::effect::describe::type_name;
+///
+/// assert_eq!(type_name::<Map<Pure<i32, String, ()>, fn(i32) -> i32>>(), "Pure -> Map");
+/// ```
+pub fn type_name<Eff: ?Sized>() -> String {
+    describe(std::any::type_name::<Eff>())
+}
+
+/// A compact description of the type of `effect`.
+///
+/// Useful when the type is an `impl Effect` that cannot be named.
+pub fn type_name_of_val<Eff: ?Sized>(_effect: &Eff) -> String {
+    type_name::<Eff>()
+}
+
+fn describe(full: &str) -> String {
+    let (path, args) = split_generics(full.trim());
+    let name = short_name(path);
+    let positions = match path.strip_prefix("stillwater::") {
+        Some(_) => COMBINATORS
+            .iter()
+            .find(|(combinator, _)| *combinator == name)
+            .map(|(_, positions)| *positions),
+        None => None,
+    };
+    let Some((&first, rest)) = positions.and_then(|p| p.split_first()) else {
+        return name.to_string();
+    };
+    let Some(inner) = args.get(first) else {
+        return name.to_string();
+    };
+
+    let mut out = format!("{} -> {}", describe(inner), name);
+    let nested: Vec<String> = rest
+        .iter()
+        .filter_map(|&i| args.get(i))
+        .map(|arg| describe(arg))
+        .collect();
+    if !nested.is_empty() {
+        out.push('(');
+        out.push_str(&nested.join(", "));
+        out.push(')');
+    }
+    out
+}
+
+/// Split `a::B<C, D<E>>` into `a::B` and its top-level arguments.
+fn split_generics(full: &str) -> (&str, Vec<&str>) {
+    let Some(open) = full.find('<') else {
+        return (full, Vec::new());
+    };
+    let Some(body) = full[open + 1..].strip_suffix('>') else {
+        return (full, Vec::new());
+    };
+
+    let mut args = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (i, c) in body.char_indices() {
+        match c {
+            '<' | '(' | '[' | '{' => depth += 1,
+            '>' | ')' | ']' | '}' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                args.push(body[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    args.push(body[start..].trim());
+    (&full[..open], args)
+}
+
+/// The last path segment, ignoring `::` inside brackets.
+fn short_name(path: &str) -> &str {
+    if path.starts_with(['(', '[', '&', '*']) || path.starts_with("dyn ") {
+        return path;
+    }
+    path.rsplit("::").next().unwrap_or(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::effect::constructors::{fail, from_fn, pure};
+    use crate::effect::ext::EffectExt;
+
+    #[test]
+    fn test_nested_effects_in_parentheses() {
+        let effect = pure::<_, String, ()>(1)
+            .tap(|_| pure(()))
+            .zip(fail::<i32, _, ()>("no".to_string()).map_err(|e| e));
+
+        assert_eq!(
+            type_name_of_val(&effect),
+            "Pure -> Tap(Pure) -> Zip(Fail -> MapErr)"
+        );
+    }
+
+    #[test]
+    fn test_foreign_and_boxed_types_show_name_only() {
+        let boxed = from_fn(|_: &()| Ok::<_, String>(1)).boxed().zip(pure(2));
+        assert_eq!(format!("{:?}", boxed), "BoxedEffect -> Zip(Pure)");
+        assert_eq!(type_name::<Vec<String>>(), "Vec");
+    }
+}
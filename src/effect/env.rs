@@ -0,0 +1,221 @@
+//! Running library effects in an application's environment.
+//!
+//! A library that ships effects has to pick their `Env`. Requiring the
+//! application's environment is impossible, and a long list of trait bounds
+//! on a generic `Env` leaks into every signature. Instead, the library can
+//! declare a small environment holding only the capabilities it uses, such
+//! as a clock and an HTTP client, and write its effects against that.
+//!
+//! The application adapts those effects with one [`Project`] impl, which
+//! builds the library's environment from its own, and calls
+//! [`project_env`](crate::effect::EffectExt::project_env) where it uses
+//! them:
+//!
+//! ```rust
+//! use std::sync::Arc;
+//! use stillwater::effect::prelude::*;
+//!
+//! // --- In the library crate ---------------------------------------------
+//!
+//! pub trait Clock: Send + Sync {
+//!     fn now(&self) -> u64;
+//! }
+//!
+//! pub trait HttpClient: Send + Sync {
+//!     fn get(&self, url: &str) -> Result<String, String>;
+//! }
+//!
+//! /// Everything the library's effects need, and nothing else.
+//! pub struct FetchEnv {
+//!     pub clock: Arc<dyn Clock>,
+//!     pub http: Arc<dyn HttpClient>,
+//! }
+//!
+//! pub fn fetch_stamped(url: &'static str) -> impl Effect<Output = String, Error = String, Env = FetchEnv> {
+//!     from_fn(move |env: &FetchEnv| {
+//!         let body = env.http.get(url)?;
+//!         Ok(format!("{} @ {}", body, env.clock.now()))
+//!     })
+//! }
+//!
+//! // --- In the application -----------------------------------------------
+//!
+//! struct FixedClock;
+//! impl Clock for FixedClock {
+//!     fn now(&self) -> u64 {
+//!         1_700_000_000
+//!     }
+//! }
+//!
+//! struct EchoClient;
+//! impl HttpClient for EchoClient {
+//!     fn get(&self, url: &str) -> Result<String, String> {
+//!         Ok(format!("GET {}", url))
+//!     }
+//! }
+//!
+//! struct AppEnv {
+//!     clock: Arc<dyn Clock>,
+//!     http: Arc<dyn HttpClient>,
+//!     database_url: String,
+//! }
+//!
+//! impl Project<FetchEnv> for AppEnv {
+//!     fn project(&self) -> FetchEnv {
+//!         FetchEnv {
+//!             clock: self.clock.clone(),
+//!             http: self.http.clone(),
+//!         }
+//!     }
+//! }
+//!
+//! # tokio_test::block_on(async {
+//! let env = AppEnv {
+//!     clock: Arc::new(FixedClock),
+//!     http: Arc::new(EchoClient),
+//!     database_url: "postgres://localhost".to_string(),
+//! };
+//!
+//! let effect = fetch_stamped("/health")
+//!     .project_env::<AppEnv>()
+//!     .and_then(|status| asks(move |env: &AppEnv| format!("{} ({})", status, env.database_url)));
+//!
+//! assert_eq!(
+//!     effect.run(&env).await,
+//!     Ok("GET /health @ 1700000000 (postgres://localhost)".to_string())
+//! );
+//! # });
+//! ```
+//!
+//! The projection runs once per run of the adapted effect, so keep it cheap:
+//! hold shared services behind `Arc` and clone the handles. When the
+//! library's environment is already a part of the application's, such as an
+//! element of a tuple environment, [`local_has`](crate::effect::local_has)
+//! borrows it without building anything.
+
+use std::marker::PhantomData;
+
+use crate::effect::trait_def::Effect;
+
+/// An environment that can build the smaller environment `To`.
+///
+/// Implement this on the application's environment once for each library
+/// environment it adapts; see the [module documentation](self).
+pub trait Project<To> {
+    /// Build the `To` environment from this one.
+    fn project(&self) -> To;
+}
+
+/// Runs an effect in an environment projected from a larger one.
+///
+/// Created by [`EffectExt::project_env`](crate::effect::EffectExt::project_env).
+pub struct ProjectEnv<Inner, Outer> {
+    pub(crate) inner: Inner,
+    pub(crate) _phantom: PhantomData<fn(&Outer)>,
+}
+
+impl<Inner: Clone, Outer> Clone for ProjectEnv<Inner, Outer> {
+    fn clone(&self) -> Self {
+        ProjectEnv {
+            inner: self.inner.clone(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<Inner, Outer> std::fmt::Debug for ProjectEnv<Inner, Outer> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProjectEnv")
+            .field("inner", &"<effect>")
+            .finish()
+    }
+}
+
+impl<Inner, Outer> Effect for ProjectEnv<Inner, Outer>
+where
+    Inner: Effect,
+    Outer: Project<Inner::Env> + Send + Sync,
+{
+    type Output = Inner::Output;
+    type Error = Inner::Error;
+    type Env = Outer;
+
+    async fn run(self, env: &Outer) -> Result<Self::Output, Self::Error> {
+        let projected = env.project();
+        self.inner.run(&projected).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::effect::constructors::{asks, fail};
+    use crate::effect::ext::EffectExt;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    struct Limits {
+        max: u32,
+    }
+
+    struct Tokens {
+        prefix: &'static str,
+    }
+
+    struct App {
+        max: u32,
+        prefix: &'static str,
+        projections: AtomicU32,
+    }
+
+    impl Project<Limits> for App {
+        fn project(&self) -> Limits {
+            self.projections.fetch_add(1, Ordering::SeqCst);
+            Limits { max: self.max }
+        }
+    }
+
+    impl Project<Tokens> for App {
+        fn project(&self) -> Tokens {
+            Tokens {
+                prefix: self.prefix,
+            }
+        }
+    }
+
+    fn app() -> App {
+        App {
+            max: 3,
+            prefix: "tok",
+            projections: AtomicU32::new(0),
+        }
+    }
+
+    fn clamp(n: u32) -> impl Effect<Output = u32, Error = String, Env = Limits> {
+        asks(move |limits: &Limits| n.min(limits.max))
+    }
+
+    fn token(n: u32) -> impl Effect<Output = String, Error = String, Env = Tokens> {
+        asks(move |tokens: &Tokens| format!("{}-{}", tokens.prefix, n))
+    }
+
+    #[tokio::test]
+    async fn test_effects_from_different_envs_compose() {
+        let env = app();
+
+        let effect = clamp(10)
+            .project_env::<App>()
+            .and_then(|n| token(n).project_env());
+
+        assert_eq!(effect.run(&env).await, Ok("tok-3".to_string()));
+        assert_eq!(env.projections.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_errors_pass_through() {
+        let effect = clamp(1)
+            .and_then(|_| fail::<u32, _, Limits>("over budget".to_string()))
+            .project_env::<App>();
+
+        assert_eq!(effect.run(&app()).await, Err("over budget".to_string()));
+    }
+}
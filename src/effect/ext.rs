@@ -4,15 +4,24 @@
 //! that implement `Effect`. It provides ergonomic combinator methods
 //! like `map`, `and_then`, `or_else`, and `boxed`.
 
+use std::future::Future;
 use std::marker::PhantomData;
 
 use crate::effect::boxed::BoxedEffect;
 use crate::effect::combinators::{
     AndThen, AndThenAuto, AndThenRef, Check, Ensure, EnsurePred, EnsureWith, Fallback, FallbackTo,
-    Map, MapErr, OrElse, Recover, RecoverSome, RecoverWith, Tap, Unless, With, Zip, ZipWith,
+    Map, MapErr, OnCancel, OrElse, Recover, RecoverSome, RecoverWith, Tap, Unless, With, Zip,
+    ZipAsks, ZipJoin, ZipPar, ZipWith,
 };
-use crate::effect::reader::Local;
+use crate::effect::env::{Project, ProjectEnv};
+#[cfg(feature = "fuel")]
+use crate::effect::fuel::{self, FuelError};
+use crate::effect::global;
+use crate::effect::profile::{Profile, ProfileReport};
+use crate::effect::reader::{Local, MapEnvFor};
+use crate::effect::request_context::Propagation;
 use crate::effect::trait_def::Effect;
+use crate::refined::{Refine, RefineAccumulate};
 
 /// Extension trait providing combinator methods for all Effects.
 ///
@@ -67,6 +76,63 @@ pub trait EffectExt: Effect {
         MapErr { inner: self, f }
     }
 
+    /// Convert the error into `W`, keeping the original as its source.
+    ///
+    /// Shorthand for `map_err(W::from)`, restricted to error types so the
+    /// conversion can keep the causal chain that `Error::source()`,
+    /// debuggers, and `anyhow` walk. The `From` impl should store the
+    /// original error and return it from `source()`, as `thiserror`'s
+    /// `#[from]` does; an impl that formats the error into a string
+    /// breaks the chain.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::error::Error;
+    /// use std::fmt;
+    /// use stillwater::effect::prelude::*;
+    ///
+    /// #[derive(Debug)]
+    /// struct ConfigError(std::io::Error);
+    ///
+    /// impl fmt::Display for ConfigError {
+    ///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    ///         f.write_str("could not load configuration")
+    ///     }
+    /// }
+    ///
+    /// impl Error for ConfigError {
+    ///     fn source(&self) -> Option<&(dyn Error + 'static)> {
+    ///         Some(&self.0)
+    ///     }
+    /// }
+    ///
+    /// impl From<std::io::Error> for ConfigError {
+    ///     fn from(e: std::io::Error) -> Self {
+    ///         ConfigError(e)
+    ///     }
+    /// }
+    ///
+    /// # tokio_test::block_on(async {
+    /// let effect = fail::<(), _, ()>(std::io::Error::other("disk offline"))
+    ///     .wrap_err::<ConfigError>();
+    ///
+    /// let err = effect.execute(&()).await.unwrap_err();
+    /// assert_eq!(err.source().unwrap().to_string(), "disk offline");
+    /// # });
+    /// ```
+    #[allow(clippy::type_complexity)]
+    fn wrap_err<W>(self) -> MapErr<Self, fn(Self::Error) -> W>
+    where
+        Self::Error: std::error::Error + 'static,
+        W: From<Self::Error> + std::error::Error + Send,
+    {
+        MapErr {
+            inner: self,
+            f: W::from,
+        }
+    }
+
     /// Chain a dependent effect.
     ///
     /// If this effect succeeds, apply the function to produce the next effect.
@@ -198,7 +264,9 @@ pub trait EffectExt: Effect {
     /// being passed to the partial function, so it can be returned if
     /// `None` is produced.
     ///
-    /// For non-Clone errors, use `recover` or `recover_with` instead.
+    /// For non-Clone errors, use `recover` or `recover_with` instead, or
+    /// [`recover_matching!`](crate::recover_matching) to recover with a
+    /// value.
     ///
     /// # Example
     ///
@@ -286,11 +354,112 @@ pub trait EffectExt: Effect {
     fn local<F, Env2>(self, f: F) -> Local<Self, F, Env2>
     where
         F: FnOnce(&Env2) -> Self::Env + Send,
-        Env2: Clone + Send + Sync,
+        Env2: Send + Sync,
     {
         Local::new(self, f)
     }
 
+    /// Run this effect in a larger environment that can build its own.
+    ///
+    /// `Outer` is the larger environment; it builds this effect's
+    /// environment with its [`Project`] impl each time the effect runs.
+    /// This lets a library write effects against a small environment of
+    /// its own and an application adapt them with one impl, without a
+    /// closure at every call site as with [`local`](EffectExt::local). See
+    /// [`effect::env`](crate::effect::env) for the full pattern.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use stillwater::effect::prelude::*;
+    ///
+    /// struct Limits {
+    ///     max: u32,
+    /// }
+    ///
+    /// struct App {
+    ///     max_items: u32,
+    /// }
+    ///
+    /// impl Project<Limits> for App {
+    ///     fn project(&self) -> Limits {
+    ///         Limits { max: self.max_items }
+    ///     }
+    /// }
+    ///
+    /// fn clamp(n: u32) -> impl Effect<Output = u32, Error = String, Env = Limits> {
+    ///     asks(move |limits: &Limits| n.min(limits.max))
+    /// }
+    ///
+    /// # tokio_test::block_on(async {
+    /// let effect = clamp(50).project_env::<App>();
+    /// assert_eq!(effect.run(&App { max_items: 20 }).await, Ok(20));
+    /// # });
+    /// ```
+    fn project_env<Outer>(self) -> ProjectEnv<Self, Outer>
+    where
+        Outer: Project<Self::Env> + Send + Sync,
+    {
+        ProjectEnv {
+            inner: self,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Continue the pipeline in an environment derived from this one.
+    ///
+    /// After this effect succeeds, `derive` builds the environment for the
+    /// rest of the pipeline from the current environment and the output,
+    /// then `f` turns the output into the follow-up effect, which runs in
+    /// the derived environment. Unlike [`local`](EffectExt::local), the
+    /// derived environment can depend on an earlier result, such as a
+    /// tenant resolved from a request, and it is built once for the whole
+    /// subtree.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use stillwater::effect::prelude::*;
+    ///
+    /// struct App {
+    ///     region: &'static str,
+    /// }
+    ///
+    /// struct Tenant {
+    ///     name: String,
+    ///     region: &'static str,
+    /// }
+    ///
+    /// fn greeting() -> impl Effect<Output = String, Error = String, Env = Tenant> {
+    ///     asks(|t: &Tenant| format!("hello {} in {}", t.name, t.region))
+    /// }
+    ///
+    /// # tokio_test::block_on(async {
+    /// let effect = pure::<_, String, App>("acme".to_string()).map_env_for(
+    ///     |app: &App, name: &String| Tenant {
+    ///         name: name.clone(),
+    ///         region: app.region,
+    ///     },
+    ///     |_| greeting(),
+    /// );
+    ///
+    /// let app = App { region: "eu-west" };
+    /// assert_eq!(effect.run(&app).await, Ok("hello acme in eu-west".to_string()));
+    /// # });
+    /// ```
+    fn map_env_for<SubEff, D, F>(self, derive: D, f: F) -> MapEnvFor<Self, D, F>
+    where
+        D: FnOnce(&Self::Env, &Self::Output) -> SubEff::Env + Send,
+        F: FnOnce(Self::Output) -> SubEff + Send,
+        SubEff: Effect<Error = Self::Error>,
+    {
+        MapEnvFor {
+            inner: self,
+            derive,
+            f,
+        }
+    }
+
     /// Convert to a boxed effect for type erasure.
     ///
     /// Use this when you need to:
@@ -344,6 +513,52 @@ pub trait EffectExt: Effect {
         }
     }
 
+    /// Run a synchronous cleanup hook if this effect is cancelled.
+    ///
+    /// An effect is cancelled when the future running it is dropped before
+    /// it finishes, for example when a request handler's client disconnects
+    /// or a `timeout` elapses. `cleanup` runs at that point, during the
+    /// drop, so it cannot await. It does not run if the effect finishes,
+    /// whether with a value or an error, or if the future is dropped before
+    /// it was first polled, since then nothing has started.
+    ///
+    /// The [`bracket`](crate::effect::bracket::bracket) variants already
+    /// release their resources when cancelled; use `on_cancel` for cleanup
+    /// that is not tied to a bracket.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::sync::atomic::{AtomicBool, Ordering};
+    /// use std::sync::Arc;
+    /// use std::time::Duration;
+    /// use stillwater::effect::prelude::*;
+    ///
+    /// # tokio_test::block_on(async {
+    /// let reservation_released = Arc::new(AtomicBool::new(false));
+    /// let released = reservation_released.clone();
+    ///
+    /// let checkout = from_async(|_: &()| async {
+    ///     tokio::time::sleep(Duration::from_secs(60)).await;
+    ///     Ok::<_, String>("order placed")
+    /// })
+    /// .on_cancel(move || released.store(true, Ordering::SeqCst));
+    ///
+    /// let timed_out = tokio::time::timeout(Duration::from_millis(10), checkout.run(&())).await;
+    /// assert!(timed_out.is_err());
+    /// assert!(reservation_released.load(Ordering::SeqCst));
+    /// # });
+    /// ```
+    fn on_cancel<F>(self, cleanup: F) -> OnCancel<Self, F>
+    where
+        F: FnOnce() + Send,
+    {
+        OnCancel {
+            inner: self,
+            cleanup,
+        }
+    }
+
     /// Fail with error if predicate returns false.
     ///
     /// Provides a declarative way to express validation conditions.
@@ -491,6 +706,120 @@ pub trait EffectExt: Effect {
         self.run(env).await
     }
 
+    /// Run the effect with the default value of its environment.
+    ///
+    /// Handy for effects whose environment is `()` or a config struct with
+    /// sensible defaults, in examples, scripts, and tests.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use stillwater::effect::prelude::*;
+    ///
+    /// # tokio_test::block_on(async {
+    /// let effect = asks::<_, String, Vec<i32>, _>(|items: &Vec<i32>| items.len());
+    /// assert_eq!(effect.run_default().await, Ok(0));
+    /// # });
+    /// ```
+    #[allow(async_fn_in_trait)]
+    async fn run_default(self) -> Result<Self::Output, Self::Error>
+    where
+        Self::Env: Default,
+    {
+        self.run(&Self::Env::default()).await
+    }
+
+    /// Bake in the environment, producing a plain `'static` future.
+    ///
+    /// The future owns both the effect and `env`, so it can be handed to
+    /// APIs that take futures rather than effects, such as
+    /// `tokio::spawn` or `select!`, without a closure that captures the
+    /// environment by hand. Pass a clone when the environment is needed
+    /// afterwards.
+    ///
+    /// The current [request context](crate::effect::request_context) and,
+    /// with the `tracing` feature, the current span are captured here and
+    /// re-entered whenever the future is polled, so a spawned effect stays
+    /// in the request that spawned it.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use stillwater::effect::prelude::*;
+    ///
+    /// # tokio_test::block_on(async {
+    /// let env = 20;
+    /// let effect = asks::<_, String, i32, _>(|n: &i32| n * 2).map(|n| n + 2);
+    ///
+    /// let handle = tokio::spawn(effect.provide(env));
+    /// assert_eq!(handle.await.unwrap(), Ok(42));
+    /// # });
+    /// ```
+    fn provide(
+        self,
+        env: Self::Env,
+    ) -> impl Future<Output = Result<Self::Output, Self::Error>> + Send + 'static
+    where
+        Self: 'static,
+    {
+        Propagation::capture().scope(async move { self.run(&env).await })
+    }
+
+    /// Run the effect with the environment installed by
+    /// [`with_global_env`](crate::effect::global::with_global_env).
+    ///
+    /// Meant for binaries and tests; see the
+    /// [`global`](crate::effect::global) module.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no global environment of type `Self::Env` is installed.
+    #[allow(async_fn_in_trait)]
+    async fn run_global(self) -> Result<Self::Output, Self::Error>
+    where
+        Self::Env: 'static,
+    {
+        let env = global::global_env::<Self::Env>().unwrap_or_else(|| {
+            panic!(
+                "no global environment installed for {}; call with_global_env first",
+                std::any::type_name::<Self::Env>()
+            )
+        });
+        self.run(&env).await
+    }
+
+    /// Execute this effect with a fuel budget (requires the `fuel` feature).
+    ///
+    /// Each combinator stage and each item of a parallel traversal consumes
+    /// one unit of fuel. If the budget runs out, execution stops and
+    /// [`FuelError::FuelExhausted`] is returned, so effects built from
+    /// untrusted input can't run unbounded. See the
+    /// [`fuel`](crate::effect::fuel) module for what is metered.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let pipeline = build_pipeline(&user_config)?;
+    ///
+    /// match pipeline.run_with_fuel(&env, 10_000).await {
+    ///     Ok(output) => println!("{:?}", output),
+    ///     Err(FuelError::FuelExhausted { budget }) => eprintln!("gave up after {budget} steps"),
+    ///     Err(FuelError::Inner(e)) => eprintln!("pipeline failed: {e}"),
+    /// }
+    /// ```
+    #[cfg(feature = "fuel")]
+    #[allow(async_fn_in_trait)]
+    async fn run_with_fuel(
+        self,
+        env: &Self::Env,
+        budget: u64,
+    ) -> Result<Self::Output, FuelError<Self::Error>>
+    where
+        Self: Sized,
+    {
+        fuel::run_metered(self, env, budget).await
+    }
+
     /// Combine this effect with another, returning both results as a tuple.
     ///
     /// `zip` is useful when you have two independent effects and need both results.
@@ -499,15 +828,23 @@ pub trait EffectExt: Effect {
     ///
     /// # Execution Order
     ///
-    /// The current implementation runs effects sequentially for simplicity.
-    /// Use parallel combinators (`par2`, `par3`, etc.) for concurrent execution.
+    /// `zip` runs the effects sequentially: the second starts only after the
+    /// first succeeds, and never runs if the first fails. Two variants run
+    /// both sides concurrently instead:
+    ///
+    /// | Combinator | Runs | When one side fails |
+    /// |------------|------|---------------------|
+    /// | `zip` | sequentially | the second side never starts |
+    /// | [`zip_par`](Self::zip_par) | concurrently | the other side is cancelled |
+    /// | [`zip_join`](Self::zip_join) | concurrently | the other side is awaited, and both errors are combined |
     ///
     /// # Error Handling
     ///
     /// Uses fail-fast semantics: if either effect fails, the combined effect
     /// fails with that error. Errors are not accumulated.
     ///
-    /// For error accumulation, use `Validation::all()` instead.
+    /// For error accumulation, use [`zip_join`](Self::zip_join) or
+    /// `Validation::all()` instead.
     ///
     /// # Example
     ///
@@ -535,6 +872,7 @@ pub trait EffectExt: Effect {
     /// - `zip_with` - combine with a function directly
     /// - `zip3`, `zip4`, etc. - combine multiple effects
     /// - `and_then` - for dependent/sequential effects
+    /// - `zip_par`, `zip_join` - for concurrent execution of two effects
     /// - `par2`, `par3`, etc. - for parallel execution
     fn zip<E2>(self, other: E2) -> Zip<Self, E2>
     where
@@ -543,6 +881,107 @@ pub trait EffectExt: Effect {
         Zip::new(self, other)
     }
 
+    /// Run this effect and another concurrently, returning both results,
+    /// and fail as soon as either fails.
+    ///
+    /// When one side fails, the other is cancelled without waiting for it.
+    /// Use this when the other side's work is useless once one fails. See
+    /// [`zip`](Self::zip) for how the zip variants compare.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use stillwater::effect::prelude::*;
+    ///
+    /// # tokio_test::block_on(async {
+    /// let slow_report = from_async(|_: &()| async {
+    ///     tokio::time::sleep(Duration::from_secs(3600)).await;
+    ///     Ok::<_, String>("report")
+    /// });
+    /// let lookup = fail::<u32, _, ()>("user not found".to_string());
+    ///
+    /// // Fails immediately; the report is cancelled rather than awaited
+    /// let result = slow_report.zip_par(lookup).run(&()).await;
+    /// assert_eq!(result, Err("user not found".to_string()));
+    /// # });
+    /// ```
+    fn zip_par<E2>(self, other: E2) -> ZipPar<Self, E2>
+    where
+        E2: Effect<Error = Self::Error, Env = Self::Env>,
+    {
+        ZipPar {
+            first: self,
+            second: other,
+        }
+    }
+
+    /// Run this effect and another concurrently, waiting for both and
+    /// combining their errors if both fail.
+    ///
+    /// Neither side is cancelled when the other fails, so both always run
+    /// to completion; if both fail, the errors are combined with
+    /// [`Semigroup`](crate::Semigroup), this effect's first. See
+    /// [`zip`](Self::zip) for how the zip variants compare.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use stillwater::effect::prelude::*;
+    ///
+    /// # tokio_test::block_on(async {
+    /// let name = fail::<String, _, ()>(vec!["name is empty".to_string()]);
+    /// let age = fail::<u32, _, ()>(vec!["age is negative".to_string()]);
+    ///
+    /// let result = name.zip_join(age).run(&()).await;
+    /// assert_eq!(
+    ///     result,
+    ///     Err(vec!["name is empty".to_string(), "age is negative".to_string()])
+    /// );
+    /// # });
+    /// ```
+    fn zip_join<E2>(self, other: E2) -> ZipJoin<Self, E2>
+    where
+        Self::Error: crate::Semigroup,
+        E2: Effect<Error = Self::Error, Env = Self::Env>,
+    {
+        ZipJoin {
+            first: self,
+            second: other,
+        }
+    }
+
+    /// Pair the success value with a value read from the environment.
+    ///
+    /// Shorthand for `.and_then(move |x| asks(f).map(move |v| (x, v)))`:
+    /// `f` runs against the same environment after this effect succeeds.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use stillwater::effect::prelude::*;
+    ///
+    /// struct Config {
+    ///     currency: &'static str,
+    /// }
+    ///
+    /// # tokio_test::block_on(async {
+    /// let effect = pure::<_, String, Config>(1299)
+    ///     .zip_asks(|cfg: &Config| cfg.currency)
+    ///     .map(|(cents, currency)| format!("{} {}", cents, currency));
+    ///
+    /// let env = Config { currency: "EUR" };
+    /// assert_eq!(effect.run(&env).await, Ok("1299 EUR".to_string()));
+    /// # });
+    /// ```
+    fn zip_asks<F, U>(self, f: F) -> ZipAsks<Self, F>
+    where
+        F: FnOnce(&Self::Env) -> U + Send,
+        U: Send,
+    {
+        ZipAsks { inner: self, f }
+    }
+
     /// Combine this effect with another using a function.
     ///
     /// More efficient than `zip().map()` as it's a single combinator.
@@ -643,6 +1082,58 @@ pub trait EffectExt: Effect {
         EnsurePred::new(self, predicate, error)
     }
 
+    /// Promote the output to a refined type, checking predicate `P`.
+    ///
+    /// Predicate failures are converted into the effect's error type.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use stillwater::refined::{NonEmpty, Refined};
+    ///
+    /// let effect = fetch_display_name(id)
+    ///     .refine::<NonEmpty>()
+    ///     .map(|name: Refined<String, NonEmpty>| greet(name));
+    /// ```
+    fn refine<P>(self) -> Refine<Self, P>
+    where
+        P: crate::refined::Predicate<Self::Output>,
+        P::Error: Into<Self::Error>,
+        Self: Sized,
+    {
+        Refine {
+            inner: self,
+            _predicate: PhantomData,
+        }
+    }
+
+    /// Promote the output to a refined type, returning predicate failures
+    /// as a [`Validation`](crate::Validation) instead of failing the effect.
+    ///
+    /// Use this to accumulate refinement errors across several effects.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use stillwater::refined::{NonEmpty, Positive};
+    ///
+    /// let effect = fetch_name(id)
+    ///     .refine_accumulate::<NonEmpty>()
+    ///     .zip(fetch_age(id).refine_accumulate::<Positive>())
+    ///     .map(|(name, age)| name.and(age));
+    /// ```
+    fn refine_accumulate<P>(self) -> RefineAccumulate<Self, P>
+    where
+        P: crate::refined::Predicate<Self::Output>,
+        P::Error: Send,
+        Self: Sized,
+    {
+        RefineAccumulate {
+            inner: self,
+            _predicate: PhantomData,
+        }
+    }
+
     /// Alias for `ensure` - filter with a fallback error.
     ///
     /// Named to match common FP convention.
@@ -673,6 +1164,34 @@ pub trait EffectExt: Effect {
     {
         Unless::new(self, predicate, error)
     }
+
+    /// Record wall time, poll count, and outcome of this effect as a named
+    /// stage in a [`ProfileReport`].
+    ///
+    /// With the `alloc_stats` feature and
+    /// [`CountingAllocator`](crate::effect::profile) installed, allocation
+    /// counts are recorded as well. See the [`profile`](crate::effect::profile)
+    /// module for details.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let report = ProfileReport::new();
+    /// let effect = fetch_user(id).profile("fetch_user", &report);
+    ///
+    /// effect.execute(&env).await?;
+    /// println!("{:?}", report.stage("fetch_user"));
+    /// ```
+    fn profile(self, name: impl Into<String>, report: &ProfileReport) -> Profile<Self>
+    where
+        Self: Sized,
+    {
+        Profile {
+            inner: self,
+            name: name.into(),
+            report: report.clone(),
+        }
+    }
 }
 
 // Blanket implementation for all Effect types
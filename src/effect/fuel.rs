@@ -0,0 +1,301 @@
+//! Metered execution with a fuel budget.
+//!
+//! Requires the `fuel` feature. Without it no metering code is compiled
+//! into the combinators, so un-metered pipelines pay nothing for it.
+//!
+//! [`EffectExt::run_with_fuel`](crate::effect::EffectExt::run_with_fuel)
+//! runs an effect with a fixed budget of *fuel*. Every combinator stage
+//! (`and_then`, `map`, each step of a boxed chain or `loop_effect`) and every
+//! item of a parallel traversal consumes one unit. When the budget runs out,
+//! execution stops and the effect fails with [`FuelError::FuelExhausted`].
+//!
+//! This bounds the amount of work done by pipelines assembled from untrusted
+//! input, such as user-supplied configs, even when they loop forever.
+//!
+//! # Scope
+//!
+//! Fuel is tracked for everything polled as part of the metered effect.
+//! Work moved onto other tasks (for example with `tokio::spawn`) is not
+//! metered. Leaf effects such as `from_async` cost nothing themselves, so
+//! pair metering with a timeout if individual leaves may be slow.
+//!
+//! # Example
+//!
+//! ```rust
+//! use stillwater::effect::prelude::*;
+//! use stillwater::effect::fuel::FuelError;
+//!
+//! # tokio_test::block_on(async {
+//! let forever = loop_effect(0u64, |n| pure::<_, String, ()>(Loop::<u64, ()>::Continue(n + 1)));
+//!
+//! let result = forever.run_with_fuel(&(), 1_000).await;
+//! assert_eq!(result, Err(FuelError::FuelExhausted { budget: 1_000 }));
+//! # });
+//! ```
+
+use std::cell::RefCell;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use crate::effect::trait_def::Effect;
+
+/// Error returned by [`run_with_fuel`](crate::effect::EffectExt::run_with_fuel).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FuelError<E> {
+    /// The fuel budget was used up before the effect completed.
+    FuelExhausted {
+        /// The budget the effect was given.
+        budget: u64,
+    },
+    /// The effect failed before running out of fuel.
+    Inner(E),
+}
+
+impl<E> FuelError<E> {
+    /// Returns true if the budget was exhausted.
+    pub fn is_exhausted(&self) -> bool {
+        matches!(self, Self::FuelExhausted { .. })
+    }
+
+    /// Returns true if this is an inner error.
+    pub fn is_inner(&self) -> bool {
+        matches!(self, Self::Inner(_))
+    }
+
+    /// Get the inner error if present.
+    pub fn into_inner(self) -> Option<E> {
+        match self {
+            Self::Inner(e) => Some(e),
+            Self::FuelExhausted { .. } => None,
+        }
+    }
+}
+
+impl<E: std::fmt::Display> std::fmt::Display for FuelError<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::FuelExhausted { budget } => {
+                write!(f, "fuel exhausted after {} steps", budget)
+            }
+            Self::Inner(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl<E: std::error::Error + 'static> std::error::Error for FuelError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::FuelExhausted { .. } => None,
+            Self::Inner(e) => Some(e),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Meter {
+    remaining: AtomicU64,
+    exhausted: AtomicBool,
+}
+
+thread_local! {
+    static CURRENT: RefCell<Option<Arc<Meter>>> = const { RefCell::new(None) };
+}
+
+/// Consume one unit of fuel from the meter of the effect being polled.
+///
+/// Completes immediately outside of [`run_metered`]. When the budget is
+/// used up, it never completes; the enclosing `run_metered` notices the
+/// exhaustion after the current poll and abandons the effect.
+pub(crate) fn tick() -> Tick {
+    Tick
+}
+
+#[derive(Debug)]
+pub(crate) struct Tick;
+
+impl Future for Tick {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<()> {
+        CURRENT.with(|current| match current.borrow().as_ref() {
+            None => Poll::Ready(()),
+            Some(meter) => {
+                let consumed = meter
+                    .remaining
+                    .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| n.checked_sub(1))
+                    .is_ok();
+                if consumed {
+                    Poll::Ready(())
+                } else {
+                    meter.exhausted.store(true, Ordering::Relaxed);
+                    Poll::Pending
+                }
+            }
+        })
+    }
+}
+
+/// Restores the previously installed meter when a poll ends, even on panic.
+struct Installed(Option<Arc<Meter>>);
+
+impl Installed {
+    fn new(meter: &Arc<Meter>) -> Self {
+        Installed(CURRENT.with(|current| current.replace(Some(Arc::clone(meter)))))
+    }
+}
+
+impl Drop for Installed {
+    fn drop(&mut self) {
+        let previous = self.0.take();
+        let _ = CURRENT.try_with(|current| current.replace(previous));
+    }
+}
+
+pub(crate) async fn run_metered<Eff>(
+    effect: Eff,
+    env: &Eff::Env,
+    budget: u64,
+) -> Result<Eff::Output, FuelError<Eff::Error>>
+where
+    Eff: Effect,
+{
+    let meter = Arc::new(Meter {
+        remaining: AtomicU64::new(budget),
+        exhausted: AtomicBool::new(false),
+    });
+    let mut future = std::pin::pin!(effect.run(env));
+
+    std::future::poll_fn(|cx| {
+        let poll = {
+            let _installed = Installed::new(&meter);
+            future.as_mut().poll(cx)
+        };
+        match poll {
+            Poll::Ready(result) => Poll::Ready(result.map_err(FuelError::Inner)),
+            Poll::Pending if meter.exhausted.load(Ordering::Relaxed) => {
+                Poll::Ready(Err(FuelError::FuelExhausted { budget }))
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::effect::boxed::BoxedEffect;
+    use crate::effect::combinators::Loop;
+    use crate::effect::constructors::{fail, from_async, loop_effect, pure};
+    use crate::effect::ext::EffectExt;
+    use crate::traverse::traverse_effect;
+
+    #[tokio::test]
+    async fn test_within_budget_succeeds() {
+        let effect = pure::<_, String, ()>(1)
+            .map(|x| x + 1)
+            .and_then(|x| pure(x * 2));
+
+        assert_eq!(effect.run_with_fuel(&(), 2).await, Ok(4));
+    }
+
+    #[tokio::test]
+    async fn test_exceeding_budget_fails() {
+        let effect = pure::<_, String, ()>(1)
+            .map(|x| x + 1)
+            .and_then(|x| pure(x * 2));
+
+        assert_eq!(
+            effect.run_with_fuel(&(), 1).await,
+            Err(FuelError::FuelExhausted { budget: 1 })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_inner_error_is_preserved() {
+        let effect = fail::<i32, _, ()>("boom".to_string()).map(|x| x + 1);
+
+        assert_eq!(
+            effect.run_with_fuel(&(), 10).await,
+            Err(FuelError::Inner("boom".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_infinite_loop_is_stopped() {
+        let effect = loop_effect(0u64, |n| {
+            pure::<_, String, ()>(Loop::<u64, ()>::Continue(n + 1))
+        });
+
+        let result = effect.run_with_fuel(&(), 500).await;
+        assert!(result.unwrap_err().is_exhausted());
+    }
+
+    #[tokio::test]
+    async fn test_boxed_chain_consumes_fuel_per_step() {
+        fn countdown(n: u32) -> BoxedEffect<u32, String, ()> {
+            if n == 0 {
+                pure(0).boxed()
+            } else {
+                pure(n - 1).boxed().and_then(countdown)
+            }
+        }
+
+        assert_eq!(countdown(10).run_with_fuel(&(), 10).await, Ok(0));
+        assert!(countdown(10)
+            .run_with_fuel(&(), 9)
+            .await
+            .unwrap_err()
+            .is_exhausted());
+    }
+
+    #[tokio::test]
+    async fn test_traverse_consumes_fuel_per_item() {
+        let traversal = || traverse_effect(1..=5, |x: i32| pure::<_, String, ()>(x).boxed());
+
+        assert_eq!(
+            traversal().run_with_fuel(&(), 5).await,
+            Ok(vec![1, 2, 3, 4, 5])
+        );
+        assert!(traversal()
+            .run_with_fuel(&(), 4)
+            .await
+            .unwrap_err()
+            .is_exhausted());
+    }
+
+    #[tokio::test]
+    async fn test_async_leaves_are_free_and_resume() {
+        let effect = from_async(|_: &()| async {
+            tokio::task::yield_now().await;
+            Ok::<_, String>(1)
+        })
+        .map(|x| x + 1);
+
+        assert_eq!(effect.run_with_fuel(&(), 1).await, Ok(2));
+    }
+
+    #[tokio::test]
+    async fn test_unmetered_execution_is_unaffected() {
+        let effect = loop_effect(0u64, |n| {
+            pure::<_, String, ()>(if n < 1_000 {
+                Loop::Continue(n + 1)
+            } else {
+                Loop::Break(n)
+            })
+        });
+
+        assert_eq!(effect.execute(&()).await, Ok(1_000));
+    }
+
+    #[test]
+    fn test_fuel_error_display() {
+        let exhausted: FuelError<String> = FuelError::FuelExhausted { budget: 3 };
+        assert_eq!(exhausted.to_string(), "fuel exhausted after 3 steps");
+        assert_eq!(FuelError::Inner("boom").to_string(), "boom");
+        assert_eq!(FuelError::Inner(1).into_inner(), Some(1));
+    }
+}
@@ -0,0 +1,128 @@
+//! A process-wide default environment for scripts, binaries, and tests.
+//!
+//! Library code should take its environment as a parameter. Small programs
+//! whose environment is fixed for the whole run can instead install it once
+//! with [`with_global_env`] and run effects with
+//! [`EffectExt::run_global`](crate::effect::EffectExt::run_global), without
+//! passing `&env` through every call.
+//!
+//! There is one global environment per `Env` type. Installing one returns a
+//! [`GlobalEnvGuard`]; dropping the guard restores whatever was installed
+//! before it. Guards for the same type should be dropped in reverse order of
+//! creation, and tests running in parallel should not install the same
+//! `Env` type, since they would see each other's environment.
+//!
+//! For environments that implement `Default`, such as `()`,
+//! [`EffectExt::run_default`](crate::effect::EffectExt::run_default) needs no
+//! setup at all.
+//!
+//! # Example
+//!
+//! ```rust
+//! use stillwater::effect::prelude::*;
+//! use stillwater::effect::global::with_global_env;
+//!
+//! struct Config {
+//!     greeting: &'static str,
+//! }
+//!
+//! fn greet(name: &str) -> impl Effect<Output = String, Error = String, Env = Config> + '_ {
+//!     asks(move |config: &Config| format!("{}, {}!", config.greeting, name))
+//! }
+//!
+//! # tokio_test::block_on(async {
+//! let _guard = with_global_env(Config { greeting: "Hello" });
+//!
+//! assert_eq!(greet("world").run_global().await, Ok("Hello, world!".to_string()));
+//! # });
+//! ```
+
+use std::any::{Any, TypeId};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::marker::PhantomData;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+type AnyEnv = Arc<dyn Any + Send + Sync>;
+
+static GLOBAL_ENVS: Mutex<BTreeMap<TypeId, AnyEnv>> = Mutex::new(BTreeMap::new());
+
+fn envs() -> MutexGuard<'static, BTreeMap<TypeId, AnyEnv>> {
+    GLOBAL_ENVS.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Install `env` as the global environment for its type.
+///
+/// The environment stays installed until the returned guard is dropped,
+/// which restores the previous one, if any.
+pub fn with_global_env<Env>(env: Env) -> GlobalEnvGuard<Env>
+where
+    Env: Send + Sync + 'static,
+{
+    let previous = envs().insert(TypeId::of::<Env>(), Arc::new(env));
+    GlobalEnvGuard {
+        previous,
+        _marker: PhantomData,
+    }
+}
+
+/// The global environment installed for `Env`, if any.
+pub fn global_env<Env>() -> Option<Arc<Env>>
+where
+    Env: Send + Sync + 'static,
+{
+    let env = envs().get(&TypeId::of::<Env>())?.clone();
+    env.downcast().ok()
+}
+
+/// Keeps a global environment installed.
+///
+/// Created by [`with_global_env`]. Dropping it restores the environment
+/// that was installed before, or removes it if there was none.
+#[must_use = "the global environment is removed when the guard is dropped"]
+pub struct GlobalEnvGuard<Env: 'static> {
+    previous: Option<AnyEnv>,
+    _marker: PhantomData<fn() -> Env>,
+}
+
+impl<Env: 'static> Drop for GlobalEnvGuard<Env> {
+    fn drop(&mut self) {
+        let mut envs = envs();
+        match self.previous.take() {
+            Some(previous) => envs.insert(TypeId::of::<Env>(), previous),
+            None => envs.remove(&TypeId::of::<Env>()),
+        };
+    }
+}
+
+impl<Env: 'static> fmt::Debug for GlobalEnvGuard<Env> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GlobalEnvGuard")
+            .field("env", &std::any::type_name::<Env>())
+            .field("restores_previous", &self.previous.is_some())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct Port(u16);
+
+    #[test]
+    fn test_guards_restore_previous_env() {
+        assert!(global_env::<Port>().is_none());
+
+        let outer = with_global_env(Port(80));
+        {
+            let _inner = with_global_env(Port(8080));
+            assert_eq!(*global_env::<Port>().unwrap(), Port(8080));
+        }
+        assert_eq!(*global_env::<Port>().unwrap(), Port(80));
+
+        drop(outer);
+        assert!(global_env::<Port>().is_none());
+    }
+}
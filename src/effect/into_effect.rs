@@ -0,0 +1,172 @@
+//! Uniform `.into_effect()` conversions for values from outside the
+//! effect system.
+//!
+//! At integration boundaries a value arrives as a `Result`, an `Option`, a
+//! [`Validation`], or a future, and each has its own constructor
+//! ([`from_result`], [`from_option`], [`from_validation`], [`from_async`]).
+//! These traits give all of them the same method name, with the
+//! environment inferred from where the effect is used:
+//!
+//! | Value | Call | Trait |
+//! |-------|------|-------|
+//! | `Result<T, E>` | `result.into_effect()` | [`IntoEffect`] |
+//! | `Validation<T, E>` | `validation.into_effect()` | [`IntoEffect`] |
+//! | `Option<T>` | `option.into_effect(error)` | [`OptionIntoEffect`] |
+//! | `Future<Output = Result<T, E>>` | `future.into_effect()` | [`FutureIntoEffect`] |
+//!
+//! Futures get a trait of their own because a blanket impl over `Future`
+//! would overlap with the impl for `Result` as far as coherence is
+//! concerned, and `Option` needs the error to fail with.
+//!
+//! # Example
+//!
+//! ```rust
+//! use stillwater::effect::prelude::*;
+//! use stillwater::Validation;
+//!
+//! struct Env {
+//!     base: u32,
+//! }
+//!
+//! fn parse(raw: &str) -> Result<u32, String> {
+//!     raw.parse().map_err(|_| format!("not a number: {raw}"))
+//! }
+//!
+//! fn total(raw: &'static str) -> impl Effect<Output = u32, Error = String, Env = Env> {
+//!     parse(raw)
+//!         .into_effect()
+//!         .and_then(|n| Validation::success(n * 2).into_effect())
+//!         .and_then(|n| Some(n).into_effect("missing".to_string()))
+//!         .and_then(|n| async move { Ok(n + 1) }.into_effect())
+//!         .and_then(|n| asks(move |env: &Env| n + env.base))
+//! }
+//!
+//! # tokio_test::block_on(async {
+//! let env = Env { base: 100 };
+//! assert_eq!(total("20").run(&env).await, Ok(141));
+//! assert_eq!(total("x").run(&env).await, Err("not a number: x".to_string()));
+//! # });
+//! ```
+//!
+//! [`from_result`]: crate::effect::from_result
+//! [`from_option`]: crate::effect::from_option
+//! [`from_validation`]: crate::effect::from_validation
+//! [`from_async`]: crate::effect::from_async
+
+use std::future::Future;
+
+use crate::effect::combinators::{FromFuture, FromResult};
+use crate::effect::constructors::{from_result, from_validation};
+use crate::effect::trait_def::Effect;
+use crate::Validation;
+
+/// Convert a `Result` or a [`Validation`] into an effect.
+pub trait IntoEffect<Env> {
+    /// The effect produced.
+    type Effect: Effect<Env = Env>;
+
+    /// Convert `self` into an effect that ignores the environment.
+    fn into_effect(self) -> Self::Effect;
+}
+
+impl<T, E, Env> IntoEffect<Env> for Result<T, E>
+where
+    T: Send,
+    E: Send,
+    Env: Send + Sync,
+{
+    type Effect = FromResult<T, E, Env>;
+
+    fn into_effect(self) -> Self::Effect {
+        from_result(self)
+    }
+}
+
+impl<T, E, Env> IntoEffect<Env> for Validation<T, E>
+where
+    T: Send,
+    E: Send,
+    Env: Send + Sync,
+{
+    type Effect = FromResult<T, E, Env>;
+
+    fn into_effect(self) -> Self::Effect {
+        from_validation(self)
+    }
+}
+
+/// Convert an `Option` into an effect, failing with a given error on
+/// `None`.
+pub trait OptionIntoEffect<T> {
+    /// Convert `self` into an effect that fails with `error` on `None`.
+    fn into_effect<E, Env>(self, error: E) -> FromResult<T, E, Env>
+    where
+        T: Send,
+        E: Send,
+        Env: Send + Sync;
+}
+
+impl<T> OptionIntoEffect<T> for Option<T> {
+    fn into_effect<E, Env>(self, error: E) -> FromResult<T, E, Env>
+    where
+        T: Send,
+        E: Send,
+        Env: Send + Sync,
+    {
+        from_result(self.ok_or(error))
+    }
+}
+
+/// Convert a future resolving to a `Result` into an effect.
+pub trait FutureIntoEffect<Env>: Future + Sized {
+    /// Convert `self` into an effect that awaits it, ignoring the
+    /// environment.
+    fn into_effect(self) -> FromFuture<Self, Env>;
+}
+
+impl<Fut, T, E, Env> FutureIntoEffect<Env> for Fut
+where
+    Fut: Future<Output = Result<T, E>> + Send,
+    T: Send,
+    E: Send,
+    Env: Send + Sync,
+{
+    fn into_effect(self) -> FromFuture<Self, Env> {
+        FromFuture::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::effect::ext::EffectExt;
+
+    #[tokio::test]
+    async fn test_result_and_validation_convert_both_ways() {
+        let ok: FromResult<_, String, ()> = Ok(1).into_effect();
+        assert_eq!(ok.run(&()).await, Ok(1));
+
+        let invalid: FromResult<i32, _, ()> =
+            Validation::<i32, _>::failure(vec!["bad"]).into_effect();
+        assert_eq!(invalid.run(&()).await, Err(vec!["bad"]));
+    }
+
+    #[tokio::test]
+    async fn test_option_fails_with_given_error() {
+        let some = Some(3).into_effect::<_, ()>("none");
+        assert_eq!(some.run(&()).await, Ok(3));
+
+        let none = None::<i32>.into_effect::<_, ()>("none");
+        assert_eq!(none.run(&()).await, Err("none"));
+    }
+
+    #[tokio::test]
+    async fn test_future_runs_when_effect_runs() {
+        let effect = async { Ok::<_, String>(20) }
+            .into_effect()
+            .map(|n| n * 2)
+            .and_then(|n| crate::effect::constructors::asks(move |env: &i32| n + env));
+
+        assert_eq!(effect.run(&2).await, Ok(42));
+    }
+}
@@ -0,0 +1,266 @@
+//! Execution ids and parent/child lineage for composed effects.
+//!
+//! With the `lineage` feature, parts of a composed effect run in their own
+//! *execution*, identified by a process-unique [`ExecutionId`] and linked
+//! to the execution that started it:
+//!
+//! - the effect produced by an `and_then` continuation runs in a child of
+//!   the current execution;
+//! - each branch of the parallel functions (`par_all`, `par_try_all`,
+//!   `race`, `par2`, and the rest) runs in a child of the current
+//!   execution;
+//! - [`ExecutionExt::in_execution`] runs any effect in a child execution,
+//!   or in a new root when there is no current execution. Wrap the
+//!   top-level effect with it so that every part of one run shares a root.
+//!
+//! [`current_execution`] returns the execution being polled, for logging
+//! and correlating the output of sub-effects within a single run. Unlike
+//! the [request context](crate::effect::request_context), it is not
+//! carried into spawned tasks, and the `par!` macro, which expands in the
+//! calling crate, does not create child executions.
+//!
+//! Without the feature, none of this code is compiled and composition
+//! costs nothing extra.
+//!
+//! # Example
+//!
+//! ```rust
+//! use stillwater::effect::lineage::{current_execution, Execution, ExecutionExt};
+//! use stillwater::effect::prelude::*;
+//!
+//! fn step() -> impl Effect<Output = Execution, Error = String, Env = ()> {
+//!     from_fn(|_: &()| Ok(current_execution().unwrap()))
+//! }
+//!
+//! # tokio_test::block_on(async {
+//! let run = step()
+//!     .and_then(|first| step().map(move |second| (first, second)))
+//!     .in_execution();
+//!
+//! let (first, second) = run.run(&()).await.unwrap();
+//! assert!(first.is_root());
+//! assert_eq!(second.parent(), Some(first.id()));
+//! assert_eq!(second.root(), first.id());
+//! # });
+//! ```
+
+use std::cell::Cell;
+use std::fmt;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::task::Poll;
+
+use crate::effect::describe::type_name;
+use crate::effect::trait_def::Effect;
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+thread_local! {
+    static CURRENT: Cell<Option<Execution>> = const { Cell::new(None) };
+}
+
+/// Identifier of one execution, unique within the process.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ExecutionId(u64);
+
+impl ExecutionId {
+    fn next() -> Self {
+        ExecutionId(NEXT_ID.fetch_add(1, Ordering::Relaxed))
+    }
+
+    /// The id as a number, for structured logging.
+    pub fn get(self) -> u64 {
+        self.0
+    }
+}
+
+impl fmt::Display for ExecutionId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "exec-{}", self.0)
+    }
+}
+
+/// An execution and its place in the lineage of a composed run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Execution {
+    id: ExecutionId,
+    parent: Option<ExecutionId>,
+    root: ExecutionId,
+}
+
+impl Execution {
+    /// A child of `parent`, or a new root if there is no parent.
+    fn child_of(parent: Option<Execution>) -> Self {
+        let id = ExecutionId::next();
+        Execution {
+            id,
+            parent: parent.map(|p| p.id),
+            root: parent.map_or(id, |p| p.root),
+        }
+    }
+
+    /// This execution's id.
+    pub fn id(&self) -> ExecutionId {
+        self.id
+    }
+
+    /// The execution that started this one, if any.
+    pub fn parent(&self) -> Option<ExecutionId> {
+        self.parent
+    }
+
+    /// The outermost execution of the run this one belongs to.
+    pub fn root(&self) -> ExecutionId {
+        self.root
+    }
+
+    /// Whether this execution has no parent.
+    pub fn is_root(&self) -> bool {
+        self.parent.is_none()
+    }
+}
+
+/// The execution currently being polled, if any.
+pub fn current_execution() -> Option<Execution> {
+    CURRENT.with(Cell::get)
+}
+
+/// Restores the previous execution when a poll ends, even on panic.
+struct Installed(Option<Execution>);
+
+impl Installed {
+    fn new(execution: Execution) -> Self {
+        Installed(CURRENT.with(|current| current.replace(Some(execution))))
+    }
+}
+
+impl Drop for Installed {
+    fn drop(&mut self) {
+        let previous = self.0.take();
+        let _ = CURRENT.try_with(|current| current.set(previous));
+    }
+}
+
+/// Run `future` in a new child of the execution current when it is first
+/// polled.
+pub(crate) async fn child<F: Future>(future: F) -> F::Output {
+    let execution = Execution::child_of(current_execution());
+    let mut future = std::pin::pin!(future);
+    std::future::poll_fn(|cx| {
+        let _installed = Installed::new(execution);
+        match future.as_mut().poll(cx) {
+            Poll::Ready(output) => Poll::Ready(output),
+            Poll::Pending => Poll::Pending,
+        }
+    })
+    .await
+}
+
+/// An effect that runs in its own execution.
+///
+/// Created by [`ExecutionExt::in_execution`].
+pub struct InExecution<Eff> {
+    pub(crate) inner: Eff,
+}
+
+impl<Eff> fmt::Debug for InExecution<Eff> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&type_name::<Self>())
+    }
+}
+
+impl<Eff: Effect> Effect for InExecution<Eff> {
+    type Output = Eff::Output;
+    type Error = Eff::Error;
+    type Env = Eff::Env;
+
+    async fn run(self, env: &Self::Env) -> Result<Self::Output, Self::Error> {
+        child(self.inner.run(env)).await
+    }
+}
+
+/// Extension trait for running effects in their own execution.
+pub trait ExecutionExt: Effect {
+    /// Run this effect in a child of the current execution, or in a new
+    /// root execution if there is none.
+    fn in_execution(self) -> InExecution<Self> {
+        InExecution { inner: self }
+    }
+}
+
+impl<E: Effect> ExecutionExt for E {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::effect::constructors::{from_async, from_fn};
+    use crate::effect::ext::EffectExt;
+    use crate::effect::parallel::par_all;
+
+    fn here() -> impl Effect<Output = Execution, Error = String, Env = ()> {
+        from_async(|_: &()| async {
+            tokio::task::yield_now().await;
+            Ok(current_execution().unwrap())
+        })
+    }
+
+    #[tokio::test]
+    async fn test_no_execution_outside_tracked_effects() {
+        let effect = from_fn(|_: &()| Ok::<_, String>(current_execution()));
+
+        assert_eq!(effect.run(&()).await, Ok(None));
+        assert_eq!(current_execution(), None);
+    }
+
+    #[tokio::test]
+    async fn test_and_then_continuations_are_siblings_under_the_run() {
+        let run = here()
+            .and_then(|a| here().map(move |b| (a, b)))
+            .and_then(|(a, b)| here().map(move |c| (a, b, c)))
+            .in_execution();
+
+        let (a, b, c) = run.run(&()).await.unwrap();
+        assert!(a.is_root());
+        assert_eq!(b.parent(), Some(a.id()));
+        assert_eq!(c.parent(), Some(a.id()));
+        assert_ne!(b.id(), c.id());
+        assert_eq!((b.root(), c.root()), (a.id(), a.id()));
+        assert_eq!(current_execution(), None);
+    }
+
+    #[tokio::test]
+    async fn test_boxed_continuations_run_in_children() {
+        let run = here()
+            .boxed()
+            .and_then(|a| here().map(move |b| (a, b)).boxed())
+            .boxed()
+            .in_execution();
+
+        let (a, b) = run.run(&()).await.unwrap();
+        assert_eq!(b.parent(), Some(a.id()));
+        assert_eq!(b.root(), a.id());
+    }
+
+    #[tokio::test]
+    async fn test_parallel_branches_get_distinct_children() {
+        let run = here()
+            .and_then(|parent| {
+                from_async(move |_: &()| async move {
+                    let branches = vec![here().boxed(), here().boxed(), here().boxed()];
+                    let children: Vec<Execution> = par_all(branches, &()).await.unwrap();
+                    Ok((parent, children))
+                })
+            })
+            .in_execution();
+
+        let (parent, children) = run.run(&()).await.unwrap();
+        let continuation = children[0].parent().unwrap();
+        assert_ne!(continuation, parent.id());
+        for child in &children {
+            assert_eq!(child.parent(), Some(continuation));
+            assert_eq!(child.root(), parent.id());
+        }
+        assert_ne!(children[0].id(), children[1].id());
+        assert_ne!(children[1].id(), children[2].id());
+    }
+}
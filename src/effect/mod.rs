@@ -85,8 +85,8 @@
 //!         pure(0).boxed()
 //!     } else {
 //!         pure(n)
-//!             .and_then(move |x| countdown(x - 1).map(move |sum| x + sum))
 //!             .boxed()
+//!             .and_then(move |x| countdown(x - 1).map(move |sum| x + sum))
 //!     }
 //! }
 //! ```
@@ -118,43 +118,91 @@
 //! }
 //! ```
 
+mod app;
+#[cfg(feature = "allocator_api")]
+pub mod arena;
+pub mod borrowed;
 pub mod boxed;
 pub mod bracket;
+pub mod caps;
 pub mod combinators;
 pub mod compat;
+pub mod compile_fail_examples;
 pub mod constructors;
 pub mod context;
+pub mod describe;
+pub mod env;
 pub mod ext;
+#[cfg(feature = "fuel")]
+pub mod fuel;
+pub mod global;
+pub mod into_effect;
+#[cfg(feature = "lineage")]
+pub mod lineage;
 pub mod parallel;
 pub mod prelude;
+pub mod profile;
+#[cfg(feature = "async")]
+pub mod queue;
 pub mod reader;
+mod recover_matching;
+pub mod request_context;
+pub mod rerunnable;
 pub mod resource;
 #[cfg(feature = "async")]
 pub mod retry;
+pub mod shell;
 pub mod sink;
+#[cfg(feature = "async")]
+pub mod soft_timeout;
+pub mod stm;
+#[cfg(feature = "async")]
+pub mod sync_primitives;
+#[cfg(feature = "debug")]
+pub mod traced;
 #[cfg(feature = "tracing")]
 pub mod tracing;
 mod trait_def;
+pub mod tuple_env;
 pub mod writer;
 
+// Re-export the app effect macro
+pub use crate::define_app_effect;
+
+// Re-export the selective recovery macro
+pub use crate::recover_matching;
+
 // Re-export core trait
 pub use trait_def::Effect;
 
 // Re-export extension trait
 pub use ext::EffectExt;
 
+// Re-export re-runnable effects
+pub use rerunnable::ReRunnable;
+
 // Re-export boxed types
 pub use boxed::{BoxFuture, BoxedEffect, BoxedLocalEffect};
 
 // Re-export all combinator types
 pub use combinators::{
     AndThen, AndThenAuto, AndThenRef, Check, Fail, Fallback, FallbackTo, FromAsync, FromFn,
-    FromResult, Map, MapErr, OrElse, Pure, Recover, RecoverSome, RecoverWith, Tap, With, Zip, Zip3,
-    Zip4, Zip5, Zip6, Zip7, Zip8, ZipWith,
+    FromFuture, FromResult, Loop, LoopEffect, Map, MapErr, OnCancel, OrElse, Pure, Recover,
+    RecoverSome, RecoverWith, Tap, With, Zip, Zip3, Zip4, Zip5, Zip6, Zip7, Zip8, ZipJoin, ZipPar,
+    ZipWith,
 };
 
+// Re-export `.into_effect()` conversions
+pub use into_effect::{FutureIntoEffect, IntoEffect, OptionIntoEffect};
+
 // Re-export reader types
-pub use reader::{Ask, Asks, Local};
+pub use reader::{Ask, Asks, Local, MapEnvFor};
+
+// Re-export projections to library environments
+pub use env::{Project, ProjectEnv};
+
+// Re-export tuple environment projections
+pub use tuple_env::{asks_has, local_has, At0, At1, At2, Itself, LocalHas, Provides};
 
 // Re-export bracket
 #[allow(deprecated)]
@@ -166,27 +214,97 @@ pub use bracket::{
 
 // Re-export constructors
 pub use constructors::{
-    ask, asks, fail, from_async, from_fn, from_option, from_result, from_validation, local, pure,
-    zip3, zip4, zip5, zip6, zip7, zip8,
+    ask, asks, fail, from_async, from_fn, from_option, from_result, from_validation, local,
+    loop_effect, pure, zip3, zip4, zip5, zip6, zip7, zip8,
 };
 
 // Re-export parallel functions
-pub use parallel::{par2, par3, par4, par_all, par_all_limit, par_try_all, race};
+pub use parallel::{
+    par2, par3, par4, par_all, par_all_labeled, par_all_limit, par_all_settled, par_try_all,
+    par_validate_all, race, zip_labeled, Labeled, LabeledError,
+};
+#[cfg(feature = "async")]
+pub use parallel::{par_all_joinset, TaskError};
+#[cfg(feature = "async")]
+pub use parallel::{par_all_keyed_limit, KeyedPermit, KeyedSemaphore};
+#[cfg(feature = "async")]
+pub use parallel::{par_all_with, ErrorPolicy, ItemError, ParOptions, ParResults};
+
+// Re-export metered execution (when fuel feature is enabled)
+#[cfg(feature = "fuel")]
+pub use fuel::FuelError;
+
+// Re-export the global environment
+pub use global::{with_global_env, GlobalEnvGuard};
+
+// Re-export program entry points
+pub use shell::{main_effect, run_main};
+
+// Re-export request context
+pub use request_context::{current_request_id, RequestContext, RequestContextExt};
+
+// Re-export execution lineage (when lineage feature is enabled)
+#[cfg(feature = "lineage")]
+pub use lineage::{current_execution, Execution, ExecutionExt, ExecutionId, InExecution};
+
+/// Run a sub-effect's future in a child execution when lineage tracking is
+/// enabled, and unchanged otherwise.
+#[cfg(feature = "lineage")]
+pub(crate) use lineage::child as in_child_execution;
+
+#[cfg(not(feature = "lineage"))]
+pub(crate) fn in_child_execution<F>(future: F) -> F {
+    future
+}
+
+/// The message of a caught panic, when it was a string.
+pub(crate) fn panic_message(payload: Box<dyn std::any::Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&'static str>() {
+            Ok(message) => (*message).to_string(),
+            Err(_) => "<non-string panic payload>".to_string(),
+        },
+    }
+}
+
+// Re-export profiling
+pub use profile::{Profile, ProfileReport, StageProfile};
 
 // Re-export context trait
 pub use context::{EffectContext, EffectContextChain};
 
 // Re-export retry functions (when async feature is enabled)
 #[cfg(feature = "async")]
-pub use retry::{retry, retry_if, retry_with_hooks, with_timeout};
+pub use retry::{
+    retry, retry_classified, retry_if, retry_persistent, retry_with_hooks, with_timeout,
+};
+
+// Re-export soft timeouts (when async feature is enabled)
+#[cfg(feature = "async")]
+pub use soft_timeout::{with_soft_timeout, SoftCancel};
+
+// Re-export effect-level synchronization primitives (when async feature is enabled)
+#[cfg(feature = "async")]
+pub use sync_primitives::{EffectMutex, EffectMutexGuard, EffectPermit, EffectSemaphore};
+
+// Re-export arena allocation (when allocator_api feature is enabled)
+#[cfg(feature = "allocator_api")]
+pub use arena::{ArenaEffect, EffectArena, EffectArenaExt};
 
 // Re-export tracing (when tracing feature is enabled)
 #[cfg(feature = "tracing")]
 pub use tracing::{EffectTracingExt, Instrument};
 
+// Re-export value tracing (when debug feature is enabled)
+#[cfg(feature = "debug")]
+pub use traced::{TraceEntry, TraceLog, TraceValuesExt, Traced};
+
 // Re-export compatibility items
 #[allow(deprecated)]
-pub use compat::{LegacyConstructors, LegacyEffect, RunStandalone};
+pub use compat::{
+    IntoLegacyEffect, IntoTraitEffect, LegacyConstructors, LegacyEffect, RunStandalone,
+};
 
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,247 @@
+//! Parallel execution on separate tokio tasks.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use tokio::task::JoinSet;
+
+use crate::effect::boxed::BoxedEffect;
+use crate::effect::panic_message;
+use crate::effect::request_context::Propagation;
+use crate::effect::trait_def::Effect;
+
+/// Why a task in [`par_all_joinset`] did not produce a value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TaskError<E> {
+    /// The task's effect failed.
+    Failed(E),
+    /// The task panicked; holds the panic message when it was a string.
+    Panicked(String),
+    /// The task was cancelled before completing, e.g. because the runtime
+    /// shut down.
+    Cancelled,
+}
+
+impl<E> TaskError<E> {
+    /// Returns true if the task's own effect failed.
+    pub fn is_failed(&self) -> bool {
+        matches!(self, Self::Failed(_))
+    }
+
+    /// Get the effect's error if present.
+    pub fn into_inner(self) -> Option<E> {
+        match self {
+            Self::Failed(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for TaskError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Failed(e) => write!(f, "{}", e),
+            Self::Panicked(message) => write!(f, "task panicked: {}", message),
+            Self::Cancelled => write!(f, "task was cancelled before completing"),
+        }
+    }
+}
+
+impl<E: std::error::Error + 'static> std::error::Error for TaskError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Failed(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// Execute boxed effects in parallel, each on its own tokio task.
+///
+/// [`par_all`](super::par_all) polls every effect from the calling task,
+/// so CPU-bound effects take turns on one worker thread. This spawns each
+/// effect onto a [`JoinSet`] instead, letting a multi-threaded runtime run
+/// them on all its workers. The results are the same as for `par_all`:
+/// `Ok` with every value, collected in input order into any
+/// `C: FromIterator<T>`, or `Err` with every failure in input order.
+///
+/// A panicking effect does not abort the batch: its panic is caught by
+/// the runtime and reported as [`TaskError::Panicked`]. Dropping the
+/// returned future aborts all tasks still running, so none outlive the
+/// call.
+///
+/// Spawned tasks cannot borrow from the caller, so the environment is
+/// passed in an `Arc` that every task shares. The request context and,
+/// with the `tracing` feature, the current span are carried into the
+/// spawned tasks; fuel metering and execution lineage are not.
+///
+/// Must be called from within a tokio runtime.
+///
+/// # Example
+///
+/// ```rust
+/// use std::sync::Arc;
+/// use stillwater::effect::parallel::par_all_joinset;
+/// use stillwater::effect::prelude::*;
+///
+/// fn checksum(n: u64) -> BoxedEffect<u64, String, ()> {
+///     from_fn(move |_: &()| Ok((0..=n).sum())).boxed()
+/// }
+///
+/// # tokio_test::block_on(async {
+/// let sums: Vec<u64> = par_all_joinset(vec![checksum(10), checksum(100)], &Arc::new(()))
+///     .await
+///     .unwrap();
+/// assert_eq!(sums, vec![55, 5050]);
+/// # });
+/// ```
+pub async fn par_all_joinset<T, E, Env, C>(
+    effects: Vec<BoxedEffect<T, E, Env>>,
+    env: &Arc<Env>,
+) -> Result<C, Vec<TaskError<E>>>
+where
+    C: FromIterator<T>,
+    T: Send + 'static,
+    E: Send + 'static,
+    Env: Send + Sync + 'static,
+{
+    let mut slots: Vec<Option<Result<T, TaskError<E>>>> = effects.iter().map(|_| None).collect();
+
+    let mut tasks = JoinSet::new();
+    let mut indices = HashMap::with_capacity(effects.len());
+    for (index, effect) in effects.into_iter().enumerate() {
+        let env = Arc::clone(env);
+        let handle =
+            tasks.spawn(Propagation::capture().scope(async move { effect.run(&env).await }));
+        indices.insert(handle.id(), index);
+    }
+
+    while let Some(joined) = tasks.join_next_with_id().await {
+        let (id, result) = match joined {
+            Ok((id, result)) => (id, result.map_err(TaskError::Failed)),
+            Err(error) => {
+                let id = error.id();
+                let result = if error.is_panic() {
+                    Err(TaskError::Panicked(panic_message(error.into_panic())))
+                } else {
+                    Err(TaskError::Cancelled)
+                };
+                (id, result)
+            }
+        };
+        slots[indices[&id]] = Some(result);
+    }
+
+    let mut failures = Vec::new();
+    let successes: Vec<T> = slots
+        .into_iter()
+        .map(|slot| slot.unwrap_or(Err(TaskError::Cancelled)))
+        .filter_map(|result| result.map_err(|e| failures.push(e)).ok())
+        .collect();
+
+    if failures.is_empty() {
+        Ok(successes.into_iter().collect())
+    } else {
+        Err(failures)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::effect::constructors::{asks, fail, from_async, from_fn, pure};
+    use crate::effect::ext::EffectExt;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_results_keep_input_order() {
+        let slow = from_async(|_: &()| async {
+            tokio::time::sleep(Duration::from_millis(30)).await;
+            Ok::<_, String>(1)
+        })
+        .boxed();
+        let effects = vec![slow, pure(2).boxed(), pure(3).boxed()];
+
+        let results: Result<Vec<i32>, _> = par_all_joinset(effects, &Arc::new(())).await;
+
+        assert_eq!(results, Ok(vec![1, 2, 3]));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_request_context_reaches_spawned_tasks() {
+        use crate::effect::request_context::{current_request_id, RequestContextExt};
+
+        let effects = vec![
+            from_fn(|_: &()| Ok::<_, String>(current_request_id())).boxed(),
+            from_fn(|_: &()| Ok(current_request_id())).boxed(),
+        ];
+        let effect = from_async(move |_: &()| async move {
+            par_all_joinset::<_, _, _, Vec<_>>(effects, &Arc::new(()))
+                .await
+                .map_err(|_| "task failed".to_string())
+        })
+        .with_request_id("batch-1");
+
+        let expected = Some("batch-1".to_string());
+        assert_eq!(effect.run(&()).await, Ok(vec![expected.clone(), expected]));
+    }
+
+    #[tokio::test]
+    async fn test_panics_and_failures_are_reported_in_order() {
+        let effects: Vec<BoxedEffect<i32, String, ()>> = vec![
+            fail("bad".to_string()).boxed(),
+            pure(2).boxed(),
+            from_fn(|_: &()| -> Result<i32, String> { panic!("boom") }).boxed(),
+        ];
+
+        let results: Result<Vec<i32>, _> = par_all_joinset(effects, &Arc::new(())).await;
+
+        assert_eq!(
+            results,
+            Err(vec![
+                TaskError::Failed("bad".to_string()),
+                TaskError::Panicked("boom".to_string()),
+            ])
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_tasks_run_on_worker_threads() {
+        let caller = std::thread::current().id();
+        let effects: Vec<BoxedEffect<_, String, u32>> = (0..4)
+            .map(|_| {
+                asks(|offset: &u32| (std::thread::current().id(), *offset))
+                    .map(|(thread, offset)| (thread, offset + 1))
+                    .boxed()
+            })
+            .collect();
+
+        let results: Vec<_> = par_all_joinset(effects, &Arc::new(41)).await.unwrap();
+
+        assert!(results.iter().all(|(_, value)| *value == 42));
+        assert!(results.iter().all(|(thread, _)| *thread != caller));
+    }
+
+    #[tokio::test]
+    async fn test_empty_batch() {
+        let effects: Vec<BoxedEffect<i32, String, ()>> = vec![];
+
+        let results: Result<Vec<i32>, _> = par_all_joinset(effects, &Arc::new(())).await;
+
+        assert_eq!(results, Ok(vec![]));
+    }
+
+    #[test]
+    fn test_task_error_display() {
+        assert_eq!(TaskError::Failed("bad").to_string(), "bad");
+        assert_eq!(
+            TaskError::<&str>::Panicked("boom".to_string()).to_string(),
+            "task panicked: boom"
+        );
+        assert_eq!(
+            TaskError::<&str>::Cancelled.to_string(),
+            "task was cancelled before completing"
+        );
+    }
+}
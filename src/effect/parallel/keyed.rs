@@ -0,0 +1,373 @@
+//! Per-key concurrency limiting.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use super::run_item;
+use crate::effect::boxed::BoxedEffect;
+
+type Permits<K> = Arc<Mutex<HashMap<K, Arc<Semaphore>>>>;
+
+/// A set of semaphores, one per key, created on demand.
+///
+/// Use this to cap concurrency per downstream host or tenant rather than
+/// globally. The limit for each key is computed by the limit function the
+/// first time the key is seen; a limit of 0 is treated as 1. Keys with no
+/// outstanding permits or waiters are dropped, so the set doesn't grow
+/// without bound.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use stillwater::effect::parallel::KeyedSemaphore;
+///
+/// let hosts = KeyedSemaphore::new(|host: &String| if host == "api.slow.com" { 1 } else { 4 });
+///
+/// let _permit = hosts.acquire("api.slow.com".to_string()).await;
+/// // at most one request to api.slow.com runs here at a time
+/// ```
+pub struct KeyedSemaphore<K> {
+    permits: Permits<K>,
+    limit: Arc<dyn Fn(&K) -> usize + Send + Sync>,
+}
+
+impl<K> Clone for KeyedSemaphore<K> {
+    fn clone(&self) -> Self {
+        KeyedSemaphore {
+            permits: Arc::clone(&self.permits),
+            limit: Arc::clone(&self.limit),
+        }
+    }
+}
+
+impl<K> std::fmt::Debug for KeyedSemaphore<K> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("KeyedSemaphore")
+            .field("active_keys", &lock(&self.permits).len())
+            .field("limit", &"<function>")
+            .finish()
+    }
+}
+
+impl<K> KeyedSemaphore<K>
+where
+    K: Eq + Hash + Clone,
+{
+    /// Create a keyed semaphore with a per-key limit function.
+    pub fn new<L>(limit: L) -> Self
+    where
+        L: Fn(&K) -> usize + Send + Sync + 'static,
+    {
+        KeyedSemaphore {
+            permits: Arc::new(Mutex::new(HashMap::new())),
+            limit: Arc::new(limit),
+        }
+    }
+
+    /// Wait for a permit for `key`.
+    ///
+    /// The permit is released when the returned guard is dropped.
+    pub async fn acquire(&self, key: K) -> KeyedPermit<K> {
+        let semaphore = {
+            let mut permits = lock(&self.permits);
+            let limit = &self.limit;
+            Arc::clone(
+                permits
+                    .entry(key.clone())
+                    .or_insert_with_key(|key| Arc::new(Semaphore::new(limit(key).max(1)))),
+            )
+        };
+        // Declared before the await so that, if this future is dropped while
+        // waiting, the pending acquire (and its semaphore reference) is
+        // dropped first and the guard can forget the key.
+        let mut waiter = Waiter {
+            key: Some(key),
+            permits: &self.permits,
+        };
+        let permit = semaphore
+            .acquire_owned()
+            .await
+            .expect("keyed semaphores are never closed");
+
+        KeyedPermit {
+            permit: Some(permit),
+            key: waiter.key.take(),
+            permits: Arc::clone(&self.permits),
+        }
+    }
+
+    /// The number of permits currently available for `key`, or `None` if
+    /// the key has no outstanding permits or waiters.
+    pub fn available(&self, key: &K) -> Option<usize> {
+        lock(&self.permits)
+            .get(key)
+            .map(|semaphore| semaphore.available_permits())
+    }
+
+    /// The number of keys with outstanding permits or waiters.
+    pub fn active_keys(&self) -> usize {
+        lock(&self.permits).len()
+    }
+}
+
+/// A permit from a [`KeyedSemaphore`], released on drop.
+pub struct KeyedPermit<K: Eq + Hash> {
+    permit: Option<OwnedSemaphorePermit>,
+    key: Option<K>,
+    permits: Permits<K>,
+}
+
+impl<K: Eq + Hash> std::fmt::Debug for KeyedPermit<K> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("KeyedPermit").finish_non_exhaustive()
+    }
+}
+
+impl<K: Eq + Hash> Drop for KeyedPermit<K> {
+    fn drop(&mut self) {
+        drop(self.permit.take());
+        if let Some(key) = self.key.take() {
+            forget_if_unused(&self.permits, &key);
+        }
+    }
+}
+
+/// Forgets a key from a [`KeyedSemaphore::acquire`] that was dropped before
+/// it got a permit.
+struct Waiter<'a, K: Eq + Hash> {
+    key: Option<K>,
+    permits: &'a Permits<K>,
+}
+
+impl<K: Eq + Hash> Drop for Waiter<'_, K> {
+    fn drop(&mut self) {
+        if let Some(key) = self.key.take() {
+            forget_if_unused(self.permits, &key);
+        }
+    }
+}
+
+fn forget_if_unused<K: Eq + Hash>(permits: &Permits<K>, key: &K) {
+    // Permits and waiters hold their own reference to the semaphore, so a
+    // count of one means nobody else is using this key.
+    let mut permits = lock(permits);
+    if permits
+        .get(key)
+        .is_some_and(|semaphore| Arc::strong_count(semaphore) == 1)
+    {
+        permits.remove(key);
+    }
+}
+
+fn lock<K>(permits: &Permits<K>) -> MutexGuard<'_, HashMap<K, Arc<Semaphore>>> {
+    permits.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Execute keyed boxed effects in parallel with a concurrency limit per key.
+///
+/// Like [`par_all`](super::par_all), all effects run to completion and
+/// results are returned in input order, but at most `limit(key)` effects
+/// sharing the same key run at once. Effects with different keys don't
+/// limit each other.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use stillwater::effect::prelude::*;
+/// use stillwater::effect::parallel::par_all_keyed_limit;
+///
+/// let requests: Vec<(String, BoxedEffect<Page, FetchError, Env>)> = urls
+///     .into_iter()
+///     .map(|url| (host_of(&url), fetch(url).boxed()))
+///     .collect();
+///
+/// // At most 2 concurrent requests per host
+/// let pages = par_all_keyed_limit(requests, |_host| 2, &env).await;
+/// ```
+pub async fn par_all_keyed_limit<K, T, E, Env, L>(
+    effects: Vec<(K, BoxedEffect<T, E, Env>)>,
+    limit: L,
+    env: &Env,
+) -> Result<Vec<T>, Vec<E>>
+where
+    K: Eq + Hash + Clone,
+    L: Fn(&K) -> usize + Send + Sync + 'static,
+    T: Send + 'static,
+    E: Send + 'static,
+    Env: Send + Sync + 'static,
+{
+    let semaphore = KeyedSemaphore::new(limit);
+    let semaphore = &semaphore;
+
+    let futures: Vec<_> = effects
+        .into_iter()
+        .map(|(key, effect)| async move {
+            let _permit = semaphore.acquire(key).await;
+            run_item(effect, env).await
+        })
+        .collect();
+
+    let results: Vec<Result<T, E>> = futures::future::join_all(futures).await;
+
+    let mut successes = Vec::new();
+    let mut failures = Vec::new();
+
+    for result in results {
+        match result {
+            Ok(value) => successes.push(value),
+            Err(e) => failures.push(e),
+        }
+    }
+
+    if failures.is_empty() {
+        Ok(successes)
+    } else {
+        Err(failures)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::effect::constructors::{fail, from_async, pure};
+    use crate::effect::ext::EffectExt;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    type Counters = Arc<Mutex<HashMap<&'static str, (usize, usize)>>>;
+
+    /// An effect that tracks current and peak concurrency for its key.
+    fn tracked(key: &'static str, counters: &Counters) -> BoxedEffect<&'static str, String, ()> {
+        let counters = Arc::clone(counters);
+        from_async(move |_: &()| async move {
+            {
+                let mut map = counters.lock().unwrap();
+                let (active, peak) = map.entry(key).or_default();
+                *active += 1;
+                *peak = (*peak).max(*active);
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            counters.lock().unwrap().get_mut(key).unwrap().0 -= 1;
+            Ok(key)
+        })
+        .boxed()
+    }
+
+    #[tokio::test]
+    async fn test_par_all_keyed_limit_caps_each_key() {
+        let counters = Counters::default();
+        let effects: Vec<_> = ["a", "b", "a", "b", "a", "a", "b", "a"]
+            .into_iter()
+            .map(|key| (key, tracked(key, &counters)))
+            .collect();
+
+        let result = par_all_keyed_limit(effects, |key| if *key == "a" { 2 } else { 1 }, &()).await;
+
+        assert_eq!(result, Ok(vec!["a", "b", "a", "b", "a", "a", "b", "a"]));
+        let counters = counters.lock().unwrap();
+        assert_eq!(counters["a"].1, 2);
+        assert_eq!(counters["b"].1, 1);
+    }
+
+    #[tokio::test]
+    async fn test_par_all_keyed_limit_collects_errors() {
+        let effects: Vec<(u8, BoxedEffect<i32, String, ()>)> = vec![
+            (1, pure(1).boxed()),
+            (1, fail("one".to_string()).boxed()),
+            (2, fail("two".to_string()).boxed()),
+        ];
+
+        let result = par_all_keyed_limit(effects, |_| 1, &()).await;
+        assert_eq!(result, Err(vec!["one".to_string(), "two".to_string()]));
+    }
+
+    #[tokio::test]
+    async fn test_zero_limit_is_treated_as_one() {
+        let effects: Vec<(u8, BoxedEffect<i32, String, ()>)> =
+            vec![(1, pure(1).boxed()), (1, pure(2).boxed())];
+
+        assert_eq!(
+            par_all_keyed_limit(effects, |_| 0, &()).await,
+            Ok(vec![1, 2])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_keyed_semaphore_releases_and_forgets_keys() {
+        let hosts = KeyedSemaphore::new(|_: &&str| 2);
+
+        let first = hosts.acquire("example.com").await;
+        let second = hosts.acquire("example.com").await;
+        assert_eq!(hosts.available(&"example.com"), Some(0));
+        assert_eq!(hosts.active_keys(), 1);
+
+        drop(first);
+        assert_eq!(hosts.available(&"example.com"), Some(1));
+
+        drop(second);
+        assert_eq!(hosts.available(&"example.com"), None);
+        assert_eq!(hosts.active_keys(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_keyed_semaphore_waiter_blocks_until_release() {
+        let hosts = KeyedSemaphore::new(|_: &u8| 1);
+        let acquired = Arc::new(AtomicUsize::new(0));
+
+        let permit = hosts.acquire(7).await;
+        let waiter = {
+            let hosts = hosts.clone();
+            let acquired = Arc::clone(&acquired);
+            tokio::spawn(async move {
+                let _permit = hosts.acquire(7).await;
+                acquired.fetch_add(1, Ordering::SeqCst);
+            })
+        };
+
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert_eq!(acquired.load(Ordering::SeqCst), 0);
+        // The key stays alive while someone is waiting on it
+        drop(permit);
+        assert_eq!(hosts.active_keys(), 1);
+
+        waiter.await.unwrap();
+        assert_eq!(acquired.load(Ordering::SeqCst), 1);
+        assert_eq!(hosts.active_keys(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_keyed_semaphore_forgets_key_of_cancelled_waiter() {
+        let hosts = KeyedSemaphore::new(|_: &u8| 1);
+
+        let permit = hosts.acquire(7).await;
+        let mut waiter = tokio_test::task::spawn(hosts.acquire(7));
+        assert!(waiter.poll().is_pending());
+
+        // The holder leaves while the waiter is queued, then the waiter is
+        // cancelled before it runs again
+        drop(permit);
+        assert_eq!(hosts.active_keys(), 1);
+        drop(waiter);
+
+        assert_eq!(hosts.available(&7), None);
+        assert_eq!(hosts.active_keys(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_keyed_semaphore_cancelled_waiter_keeps_key_in_use() {
+        let hosts = KeyedSemaphore::new(|_: &u8| 1);
+
+        let permit = hosts.acquire(7).await;
+        let timed_out = tokio::time::timeout(Duration::from_millis(10), hosts.acquire(7)).await;
+        assert!(timed_out.is_err());
+
+        // The holder still uses the key, so the cancelled waiter must not
+        // remove it from under them
+        assert_eq!(hosts.available(&7), Some(0));
+        drop(permit);
+        assert_eq!(hosts.active_keys(), 0);
+    }
+}
@@ -0,0 +1,267 @@
+//! Branch labels for errors from parallel and zipped effects.
+
+use std::borrow::Cow;
+use std::fmt;
+
+use super::run_item;
+use crate::effect::boxed::BoxedEffect;
+use crate::effect::combinators::Zip;
+use crate::effect::describe::type_name;
+use crate::effect::trait_def::Effect;
+
+/// An error from one branch of a parallel or zipped group of effects,
+/// tagged with the branch it came from.
+///
+/// Produced by [`par_all_labeled`] and [`zip_labeled`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LabeledError<E> {
+    /// The label given to the failing branch.
+    pub label: Cow<'static, str>,
+    /// The failing branch's position in the group, starting at 0.
+    pub index: usize,
+    /// The branch's own error.
+    pub source: E,
+}
+
+impl<E> LabeledError<E> {
+    /// Transform the branch's error, keeping the label and index.
+    pub fn map<E2>(self, f: impl FnOnce(E) -> E2) -> LabeledError<E2> {
+        LabeledError {
+            label: self.label,
+            index: self.index,
+            source: f(self.source),
+        }
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for LabeledError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "branch {} ({}) failed: {}",
+            self.index, self.label, self.source
+        )
+    }
+}
+
+impl<E: std::error::Error + 'static> std::error::Error for LabeledError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+/// An effect whose error is tagged with a branch label and index.
+///
+/// Created by [`zip_labeled`], or directly with [`Labeled::new`] to label
+/// branches of other combinations.
+pub struct Labeled<Eff> {
+    pub(crate) inner: Eff,
+    pub(crate) label: Cow<'static, str>,
+    pub(crate) index: usize,
+}
+
+impl<Eff> fmt::Debug for Labeled<Eff> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&type_name::<Self>())
+    }
+}
+
+impl<Eff> Labeled<Eff> {
+    /// Tag the errors of `inner` as coming from branch `index`, `label`.
+    pub fn new(inner: Eff, label: impl Into<Cow<'static, str>>, index: usize) -> Self {
+        Labeled {
+            inner,
+            label: label.into(),
+            index,
+        }
+    }
+}
+
+impl<Eff: Effect> Effect for Labeled<Eff> {
+    type Output = Eff::Output;
+    type Error = LabeledError<Eff::Error>;
+    type Env = Eff::Env;
+
+    async fn run(self, env: &Self::Env) -> Result<Self::Output, Self::Error> {
+        self.inner.run(env).await.map_err(|source| LabeledError {
+            label: self.label,
+            index: self.index,
+            source,
+        })
+    }
+}
+
+/// Execute labeled boxed effects in parallel, collecting all results or all
+/// errors, with each error tagged by the branch it came from.
+///
+/// Behaves like [`par_all`](super::par_all): every branch runs to
+/// completion, and the successes are collected into any
+/// `C: FromIterator<T>`. On failure, each [`LabeledError`] carries its
+/// branch's label and position.
+///
+/// # Example
+///
+/// ```rust
+/// use stillwater::effect::parallel::par_all_labeled;
+/// use stillwater::effect::prelude::*;
+///
+/// # tokio_test::block_on(async {
+/// let result: Result<Vec<_>, _> = par_all_labeled(
+///     [
+///         ("users", pure::<_, String, ()>(3).boxed()),
+///         ("orders", fail("orders service unavailable".to_string()).boxed()),
+///     ],
+///     &(),
+/// )
+/// .await;
+///
+/// let errors = result.unwrap_err();
+/// assert_eq!((errors[0].label.as_ref(), errors[0].index), ("orders", 1));
+/// assert_eq!(
+///     errors[0].to_string(),
+///     "branch 1 (orders) failed: orders service unavailable"
+/// );
+/// # });
+/// ```
+pub async fn par_all_labeled<T, E, Env, C, L>(
+    branches: impl IntoIterator<Item = (L, BoxedEffect<T, E, Env>)>,
+    env: &Env,
+) -> Result<C, Vec<LabeledError<E>>>
+where
+    C: FromIterator<T>,
+    L: Into<Cow<'static, str>>,
+    T: Send + 'static,
+    E: Send + 'static,
+    Env: Send + Sync + 'static,
+{
+    let (labels, futures): (Vec<_>, Vec<_>) = branches
+        .into_iter()
+        .map(|(label, effect)| (label.into(), run_item(effect, env)))
+        .unzip();
+
+    let results = futures::future::join_all(futures).await;
+
+    let mut failures = Vec::new();
+    let successes: Vec<T> = results
+        .into_iter()
+        .zip(labels)
+        .enumerate()
+        .filter_map(|(index, (result, label))| {
+            result
+                .map_err(|source| {
+                    failures.push(LabeledError {
+                        label,
+                        index,
+                        source,
+                    })
+                })
+                .ok()
+        })
+        .collect();
+
+    if failures.is_empty() {
+        Ok(successes.into_iter().collect())
+    } else {
+        Err(failures)
+    }
+}
+
+/// Zip two effects, tagging an error with the branch it came from.
+///
+/// Behaves like [`EffectExt::zip`](crate::effect::EffectExt::zip); the
+/// first branch has index 0 and the second index 1.
+///
+/// # Example
+///
+/// ```rust
+/// use stillwater::effect::parallel::zip_labeled;
+/// use stillwater::effect::prelude::*;
+///
+/// # tokio_test::block_on(async {
+/// let profile = zip_labeled(
+///     ("user", pure::<_, String, ()>("ada")),
+///     ("settings", fail::<u32, _, ()>("no settings row".to_string())),
+/// );
+///
+/// let error = profile.run(&()).await.unwrap_err();
+/// assert_eq!(error.label, "settings");
+/// assert_eq!(error.index, 1);
+/// # });
+/// ```
+pub fn zip_labeled<L1, E1, L2, E2>(
+    first: (L1, E1),
+    second: (L2, E2),
+) -> Zip<Labeled<E1>, Labeled<E2>>
+where
+    L1: Into<Cow<'static, str>>,
+    L2: Into<Cow<'static, str>>,
+    E1: Effect,
+    E2: Effect<Error = E1::Error, Env = E1::Env>,
+{
+    Zip::new(
+        Labeled::new(first.1, first.0, 0),
+        Labeled::new(second.1, second.0, 1),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::effect::constructors::{fail, pure};
+    use crate::effect::ext::EffectExt;
+
+    #[tokio::test]
+    async fn test_par_all_labeled_tags_every_failure() {
+        let result: Result<Vec<i32>, _> = par_all_labeled(
+            vec![
+                (
+                    "a".to_string(),
+                    fail::<i32, _, ()>("first".to_string()).boxed(),
+                ),
+                ("b".to_string(), pure(2).boxed()),
+                ("c".to_string(), fail("third".to_string()).boxed()),
+            ],
+            &(),
+        )
+        .await;
+
+        let errors = result.unwrap_err();
+        assert_eq!(
+            errors
+                .iter()
+                .map(|e| (e.label.as_ref(), e.index, e.source.as_str()))
+                .collect::<Vec<_>>(),
+            vec![("a", 0, "first"), ("c", 2, "third")]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_par_all_labeled_collects_successes_in_order() {
+        let result: Result<Vec<i32>, Vec<LabeledError<String>>> =
+            par_all_labeled([("x", pure(1).boxed()), ("y", pure(2).boxed())], &()).await;
+
+        assert_eq!(result, Ok(vec![1, 2]));
+    }
+
+    #[tokio::test]
+    async fn test_zip_labeled_keeps_values_and_labels_first_error() {
+        let ok = zip_labeled(("l", pure::<_, String, ()>(1)), ("r", pure("two")));
+        assert_eq!(ok.run(&()).await, Ok((1, "two")));
+
+        let err = zip_labeled(
+            ("l", fail::<i32, _, ()>("left".to_string())),
+            ("r", fail::<i32, _, ()>("right".to_string())),
+        )
+        .run(&())
+        .await
+        .unwrap_err();
+        assert_eq!(
+            err.map(|s| s.len()),
+            LabeledError {
+                label: "l".into(),
+                index: 0,
+                source: 4,
+            }
+        );
+    }
+}
@@ -3,22 +3,70 @@
 //! This module provides functions for running effects in parallel:
 //! - `par_all` - Run all effects, collecting results or errors
 //! - `par_try_all` - Run all effects, fail-fast on first error
+//! - `par_validate_all` - Run all effects, accumulating errors via `Semigroup`
+//! - `par_all_settled` - Run all effects, returning every outcome
+//! - `par_all_labeled` - Like `par_all`, tagging each error with its branch
 //! - `race` - Race effects, return first to complete
 //! - `par2`, `par3` - Run heterogeneous effects in parallel
+//! - `par_all_with` - Run all effects with timeouts, keeping partial results
+//!   (requires the `async` feature)
+//! - `par_all_keyed_limit` - Run all effects with a concurrency limit per key
+//!   (requires the `async` feature)
+//! - `par_all_joinset` - Run all effects on separate tokio tasks, across
+//!   worker threads (requires the `async` feature)
+
+#[cfg(feature = "async")]
+mod joinset;
+#[cfg(feature = "async")]
+mod keyed;
+mod labeled;
+#[cfg(feature = "async")]
+mod options;
+
+#[cfg(feature = "async")]
+pub use joinset::{par_all_joinset, TaskError};
+#[cfg(feature = "async")]
+pub use keyed::{par_all_keyed_limit, KeyedPermit, KeyedSemaphore};
+pub use labeled::{par_all_labeled, zip_labeled, Labeled, LabeledError};
+#[cfg(feature = "async")]
+pub use options::{par_all_with, ErrorPolicy, ItemError, ParOptions, ParResults};
 
 use crate::effect::boxed::BoxedEffect;
+#[cfg(feature = "fuel")]
+use crate::effect::fuel;
+use crate::effect::in_child_execution;
 use crate::effect::trait_def::Effect;
+use crate::semigroup::Semigroup;
+use crate::validation::Validation;
+
+/// Run one item of a parallel batch, charging one unit of fuel for it
+/// with the `fuel` feature.
+pub(crate) async fn run_item<T, E, Env>(effect: BoxedEffect<T, E, Env>, env: &Env) -> Result<T, E>
+where
+    T: Send + 'static,
+    E: Send + 'static,
+    Env: Send + Sync + 'static,
+{
+    #[cfg(feature = "fuel")]
+    fuel::tick().await;
+    in_child_execution(effect.run(env)).await
+}
 
 /// Execute boxed effects in parallel, collecting all results or all errors.
 ///
 /// Returns `Ok(results)` if all effects succeed, `Err(errors)` if any fail.
 /// All effects run to completion regardless of individual failures.
 ///
+/// Successes are collected into any `C: FromIterator<T>`, such as a `Vec`,
+/// a `HashSet`, a `String`, or a `BTreeMap` from key-value tuples, chosen
+/// by type inference.
+///
 /// Requires boxed effects because `Vec<T>` needs homogeneous types.
 ///
 /// # Example
 ///
 /// ```rust,ignore
+/// use std::collections::BTreeMap;
 /// use stillwater::effect::prelude::*;
 ///
 /// let effects: Vec<BoxedEffect<i32, String, ()>> = vec![
@@ -27,34 +75,38 @@ use crate::effect::trait_def::Effect;
 ///     pure(3).boxed(),
 /// ];
 ///
-/// let result = par_all(effects, &()).await;
+/// let result: Result<Vec<_>, _> = par_all(effects, &()).await;
 /// assert_eq!(result, Ok(vec![1, 2, 3]));
+///
+/// let lookups: Vec<BoxedEffect<(&str, u32), String, ()>> = vec![
+///     pure(("alice", 30)).boxed(),
+///     pure(("bob", 25)).boxed(),
+/// ];
+/// let ages: BTreeMap<&str, u32> = par_all(lookups, &()).await.unwrap();
+/// assert_eq!(ages["bob"], 25);
 /// ```
-pub async fn par_all<T, E, Env>(
+pub async fn par_all<T, E, Env, C>(
     effects: Vec<BoxedEffect<T, E, Env>>,
     env: &Env,
-) -> Result<Vec<T>, Vec<E>>
+) -> Result<C, Vec<E>>
 where
+    C: FromIterator<T>,
     T: Send + 'static,
     E: Send + 'static,
-    Env: Clone + Send + Sync + 'static,
+    Env: Send + Sync + 'static,
 {
-    let futures: Vec<_> = effects.into_iter().map(|eff| eff.run(env)).collect();
+    let futures: Vec<_> = effects.into_iter().map(|eff| run_item(eff, env)).collect();
 
     let results: Vec<Result<T, E>> = futures::future::join_all(futures).await;
 
-    let mut successes = Vec::new();
     let mut failures = Vec::new();
-
-    for result in results {
-        match result {
-            Ok(value) => successes.push(value),
-            Err(e) => failures.push(e),
-        }
-    }
+    let successes: Vec<T> = results
+        .into_iter()
+        .filter_map(|result| result.map_err(|e| failures.push(e)).ok())
+        .collect();
 
     if failures.is_empty() {
-        Ok(successes)
+        Ok(successes.into_iter().collect())
     } else {
         Err(failures)
     }
@@ -85,15 +137,95 @@ pub async fn par_try_all<T, E, Env>(
 where
     T: Send + 'static,
     E: Send + 'static,
-    Env: Clone + Send + Sync + 'static,
+    Env: Send + Sync + 'static,
 {
-    let futures: Vec<_> = effects.into_iter().map(|eff| eff.run(env)).collect();
+    let futures: Vec<_> = effects.into_iter().map(|eff| run_item(eff, env)).collect();
 
     let results: Vec<Result<T, E>> = futures::future::join_all(futures).await;
 
     results.into_iter().collect()
 }
 
+/// Execute boxed effects in parallel, accumulating every error.
+///
+/// Awaits all branches, then combines their outcomes like
+/// [`Validation::all_vec`]: all success values in order if every effect
+/// succeeds, otherwise every error combined with [`Semigroup::combine`].
+/// Use this to validate independent inputs concurrently, or
+/// [`par_all_settled`] to keep each item's outcome separate.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use stillwater::effect::prelude::*;
+///
+/// let effects: Vec<BoxedEffect<i32, Vec<String>, ()>> = vec![
+///     pure(1).boxed(),
+///     fail(vec!["bad email".to_string()]).boxed(),
+///     fail(vec!["bad age".to_string()]).boxed(),
+/// ];
+///
+/// let result = par_validate_all(effects, &()).await;
+/// assert_eq!(
+///     result,
+///     Validation::Failure(vec!["bad email".to_string(), "bad age".to_string()])
+/// );
+/// ```
+pub async fn par_validate_all<T, E, Env>(
+    effects: Vec<BoxedEffect<T, E, Env>>,
+    env: &Env,
+) -> Validation<Vec<T>, E>
+where
+    T: Send + 'static,
+    E: Semigroup + Send + 'static,
+    Env: Send + Sync + 'static,
+{
+    let futures: Vec<_> = effects.into_iter().map(|eff| run_item(eff, env)).collect();
+
+    let results: Vec<Result<T, E>> = futures::future::join_all(futures).await;
+
+    Validation::all_vec(results.into_iter().map(Validation::from_result).collect())
+}
+
+/// Execute boxed effects in parallel, returning every outcome.
+///
+/// Awaits all branches and returns each one's `Result` in input order, for
+/// batch APIs that report on every item. Use [`par_validate_all`] to
+/// combine the errors with [`Semigroup`] instead.
+///
+/// # Example
+///
+/// ```rust
+/// use stillwater::effect::prelude::*;
+///
+/// # tokio_test::block_on(async {
+/// let effects: Vec<BoxedEffect<i32, String, ()>> = vec![
+///     pure(1).boxed(),
+///     fail("item 2 rejected".to_string()).boxed(),
+///     pure(3).boxed(),
+/// ];
+///
+/// let outcomes = par_all_settled(effects, &()).await;
+/// assert_eq!(
+///     outcomes,
+///     vec![Ok(1), Err("item 2 rejected".to_string()), Ok(3)]
+/// );
+/// # });
+/// ```
+pub async fn par_all_settled<T, E, Env>(
+    effects: Vec<BoxedEffect<T, E, Env>>,
+    env: &Env,
+) -> Vec<Result<T, E>>
+where
+    T: Send + 'static,
+    E: Send + 'static,
+    Env: Send + Sync + 'static,
+{
+    let futures: Vec<_> = effects.into_iter().map(|eff| run_item(eff, env)).collect();
+
+    futures::future::join_all(futures).await
+}
+
 /// Race effects, returning the first to complete successfully.
 ///
 /// Returns the result of the first effect to complete.
@@ -120,7 +252,7 @@ pub async fn race<T, E, Env>(effects: Vec<BoxedEffect<T, E, Env>>, env: &Env) ->
 where
     T: Send + 'static,
     E: Send + 'static,
-    Env: Clone + Send + Sync + 'static,
+    Env: Send + Sync + 'static,
 {
     if effects.is_empty() {
         panic!("race called with empty effects vec");
@@ -128,7 +260,7 @@ where
 
     let futures: Vec<_> = effects
         .into_iter()
-        .map(|eff| Box::pin(eff.run(env)))
+        .map(|eff| Box::pin(run_item(eff, env)))
         .collect();
 
     let (result, _index, _remaining) = futures::future::select_all(futures).await;
@@ -160,7 +292,10 @@ where
     E1: Effect,
     E2: Effect<Env = E1::Env>,
 {
-    futures::join!(e1.run(env), e2.run(env))
+    futures::join!(
+        in_child_execution(e1.run(env)),
+        in_child_execution(e2.run(env))
+    )
 }
 
 /// Execute three effects in parallel (heterogeneous).
@@ -181,7 +316,11 @@ where
     E2: Effect<Env = E1::Env>,
     E3: Effect<Env = E1::Env>,
 {
-    futures::join!(e1.run(env), e2.run(env), e3.run(env))
+    futures::join!(
+        in_child_execution(e1.run(env)),
+        in_child_execution(e2.run(env)),
+        in_child_execution(e3.run(env))
+    )
 }
 
 /// Execute four effects in parallel (heterogeneous).
@@ -205,7 +344,12 @@ where
     E3: Effect<Env = E1::Env>,
     E4: Effect<Env = E1::Env>,
 {
-    futures::join!(e1.run(env), e2.run(env), e3.run(env), e4.run(env))
+    futures::join!(
+        in_child_execution(e1.run(env)),
+        in_child_execution(e2.run(env)),
+        in_child_execution(e3.run(env)),
+        in_child_execution(e4.run(env))
+    )
 }
 
 /// Execute boxed effects in parallel with a concurrency limit.
@@ -235,12 +379,12 @@ pub async fn par_all_limit<T, E, Env>(
 where
     T: Send + 'static,
     E: Send + 'static,
-    Env: Clone + Send + Sync + 'static,
+    Env: Send + Sync + 'static,
 {
     use futures::stream::{self, StreamExt};
 
     let results: Vec<Result<T, E>> = stream::iter(effects)
-        .map(|eff| eff.run(env))
+        .map(|eff| run_item(eff, env))
         .buffer_unordered(limit)
         .collect()
         .await;
@@ -328,7 +472,7 @@ mod tests {
         let effects: Vec<BoxedEffect<i32, String, ()>> =
             vec![pure(1).boxed(), pure(2).boxed(), pure(3).boxed()];
 
-        let result = par_all(effects, &()).await;
+        let result: Result<Vec<_>, _> = par_all(effects, &()).await;
         assert_eq!(result, Ok(vec![1, 2, 3]));
     }
 
@@ -341,7 +485,7 @@ mod tests {
             fail("error2".to_string()).boxed(),
         ];
 
-        let result = par_all(effects, &()).await;
+        let result: Result<Vec<_>, _> = par_all(effects, &()).await;
         // Should collect ALL errors, not just the first
         assert_eq!(
             result,
@@ -357,7 +501,7 @@ mod tests {
             fail("error3".to_string()).boxed(),
         ];
 
-        let result = par_all(effects, &()).await;
+        let result: Result<Vec<_>, _> = par_all(effects, &()).await;
         assert_eq!(
             result,
             Err(vec![
@@ -372,7 +516,7 @@ mod tests {
     async fn test_par_all_empty_collection() {
         let effects: Vec<BoxedEffect<i32, String, ()>> = vec![];
 
-        let result = par_all(effects, &()).await;
+        let result: Result<Vec<_>, _> = par_all(effects, &()).await;
         assert_eq!(result, Ok(vec![]));
     }
 
@@ -380,7 +524,7 @@ mod tests {
     async fn test_par_all_single_effect_success() {
         let effects: Vec<BoxedEffect<i32, String, ()>> = vec![pure(42).boxed()];
 
-        let result = par_all(effects, &()).await;
+        let result: Result<Vec<_>, _> = par_all(effects, &()).await;
         assert_eq!(result, Ok(vec![42]));
     }
 
@@ -389,7 +533,7 @@ mod tests {
         let effects: Vec<BoxedEffect<i32, String, ()>> =
             vec![fail("single error".to_string()).boxed()];
 
-        let result = par_all(effects, &()).await;
+        let result: Result<Vec<_>, _> = par_all(effects, &()).await;
         assert_eq!(result, Err(vec!["single error".to_string()]));
     }
 
@@ -403,7 +547,7 @@ mod tests {
         ];
 
         let start = Instant::now();
-        let result = par_all(effects, &()).await;
+        let result: Result<Vec<_>, _> = par_all(effects, &()).await;
         let elapsed = start.elapsed();
 
         assert!(result.is_ok());
@@ -425,7 +569,7 @@ mod tests {
             delayed_failure("third".to_string(), Duration::from_millis(20)),
         ];
 
-        let result = par_all(effects, &()).await;
+        let result: Result<Vec<_>, _> = par_all(effects, &()).await;
         // Errors collected in order they appear in input, regardless of completion time
         assert_eq!(
             result,
@@ -507,6 +651,102 @@ mod tests {
         assert_eq!(result, Err("error".to_string()));
     }
 
+    // ==================== par_validate_all Tests ====================
+
+    #[tokio::test]
+    async fn test_par_validate_all_all_succeed() {
+        let effects: Vec<BoxedEffect<i32, Vec<String>, ()>> =
+            vec![pure(1).boxed(), pure(2).boxed(), pure(3).boxed()];
+
+        let result = par_validate_all(effects, &()).await;
+        assert_eq!(result, Validation::Success(vec![1, 2, 3]));
+    }
+
+    #[tokio::test]
+    async fn test_par_validate_all_combines_errors_in_order() {
+        let effects: Vec<BoxedEffect<i32, Vec<String>, ()>> = vec![
+            from_async(|_: &()| async {
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                Err(vec!["slow".to_string()])
+            })
+            .boxed(),
+            pure(2).boxed(),
+            fail(vec!["fast".to_string(), "also fast".to_string()]).boxed(),
+        ];
+
+        let result = par_validate_all(effects, &()).await;
+        assert_eq!(
+            result,
+            Validation::Failure(vec![
+                "slow".to_string(),
+                "fast".to_string(),
+                "also fast".to_string()
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_par_validate_all_with_nonempty_errors() {
+        use crate::nonempty::NonEmptyVec;
+
+        let effects: Vec<BoxedEffect<i32, NonEmptyVec<&str>, ()>> = vec![
+            fail(NonEmptyVec::singleton("a")).boxed(),
+            fail(NonEmptyVec::singleton("b")).boxed(),
+        ];
+
+        let result = par_validate_all(effects, &()).await;
+        assert_eq!(result.unwrap_err().into_vec(), vec!["a", "b"]);
+    }
+
+    #[tokio::test]
+    async fn test_par_validate_all_runs_in_parallel() {
+        let delay = Duration::from_millis(50);
+        let effects: Vec<BoxedEffect<i32, Vec<String>, ()>> = (0..3)
+            .map(|i| {
+                from_async(move |_: &()| async move {
+                    tokio::time::sleep(delay).await;
+                    Ok(i)
+                })
+                .boxed()
+            })
+            .collect();
+
+        let start = Instant::now();
+        let result = par_validate_all(effects, &()).await;
+        assert!(start.elapsed() < delay * 2);
+        assert_eq!(result, Validation::Success(vec![0, 1, 2]));
+    }
+
+    // ==================== par_all_settled Tests ====================
+
+    #[tokio::test]
+    async fn test_par_all_settled_keeps_every_outcome_in_order() {
+        let effects = vec![
+            delayed_success(1, Duration::from_millis(30)),
+            fail("second".to_string()).boxed(),
+            delayed_success(3, Duration::from_millis(10)),
+            fail("fourth".to_string()).boxed(),
+        ];
+
+        let outcomes = par_all_settled(effects, &()).await;
+
+        assert_eq!(
+            outcomes,
+            vec![
+                Ok(1),
+                Err("second".to_string()),
+                Ok(3),
+                Err("fourth".to_string())
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_par_all_settled_empty() {
+        let effects: Vec<BoxedEffect<i32, String, ()>> = vec![];
+        assert!(par_all_settled(effects, &()).await.is_empty());
+    }
+
     // ==================== race Tests ====================
 
     #[tokio::test]
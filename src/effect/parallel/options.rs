@@ -0,0 +1,374 @@
+//! Parallel execution with timeouts and partial results.
+
+use std::fmt;
+use std::time::Duration;
+
+use futures::stream::{FuturesUnordered, StreamExt};
+
+use super::run_item;
+use crate::effect::boxed::BoxedEffect;
+
+/// How [`par_all_with`] reacts to a failed item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ErrorPolicy {
+    /// Stop at the first failure; items still running are cancelled.
+    FailFast,
+    /// Keep going and report every item's outcome.
+    #[default]
+    CollectPartial,
+}
+
+/// Options for [`par_all_with`].
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use stillwater::effect::parallel::{ErrorPolicy, ParOptions};
+/// use std::time::Duration;
+///
+/// let options = ParOptions::new()
+///     .item_timeout(Duration::from_secs(5))
+///     .deadline(Duration::from_secs(30))
+///     .policy(ErrorPolicy::CollectPartial);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ParOptions {
+    item_timeout: Option<Duration>,
+    deadline: Option<Duration>,
+    policy: ErrorPolicy,
+}
+
+impl ParOptions {
+    /// No timeouts, collecting partial results.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fail any single item that runs longer than `timeout`.
+    pub fn item_timeout(mut self, timeout: Duration) -> Self {
+        self.item_timeout = Some(timeout);
+        self
+    }
+
+    /// Stop waiting for items once `deadline` has elapsed for the whole batch.
+    pub fn deadline(mut self, deadline: Duration) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Set the error policy.
+    pub fn policy(mut self, policy: ErrorPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+}
+
+/// Why an item in [`par_all_with`] did not produce a value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ItemError<E> {
+    /// The item's effect failed.
+    Failed(E),
+    /// The item exceeded the per-item timeout.
+    Timeout {
+        /// The per-item timeout that was exceeded.
+        duration: Duration,
+    },
+    /// The overall deadline passed before the item finished.
+    DeadlineExceeded {
+        /// The overall deadline.
+        deadline: Duration,
+    },
+    /// The item was cancelled because another item failed under
+    /// [`ErrorPolicy::FailFast`].
+    Cancelled,
+}
+
+impl<E> ItemError<E> {
+    /// Returns true if the item's own effect failed.
+    pub fn is_failed(&self) -> bool {
+        matches!(self, Self::Failed(_))
+    }
+
+    /// Get the effect's error if present.
+    pub fn into_inner(self) -> Option<E> {
+        match self {
+            Self::Failed(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for ItemError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Failed(e) => write!(f, "{}", e),
+            Self::Timeout { duration } => write!(f, "item timed out after {:?}", duration),
+            Self::DeadlineExceeded { deadline } => {
+                write!(f, "batch deadline of {:?} exceeded", deadline)
+            }
+            Self::Cancelled => write!(f, "item cancelled after an earlier failure"),
+        }
+    }
+}
+
+impl<E: std::error::Error + 'static> std::error::Error for ItemError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Failed(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// Per-item outcomes of [`par_all_with`], in input order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParResults<T, E> {
+    results: Vec<Result<T, ItemError<E>>>,
+}
+
+impl<T, E> ParResults<T, E> {
+    /// Returns true if every item produced a value.
+    pub fn is_complete(&self) -> bool {
+        self.results.iter().all(Result::is_ok)
+    }
+
+    /// The per-item outcomes, in input order.
+    pub fn results(&self) -> &[Result<T, ItemError<E>>] {
+        &self.results
+    }
+
+    /// Consume into the per-item outcomes, in input order.
+    pub fn into_results(self) -> Vec<Result<T, ItemError<E>>> {
+        self.results
+    }
+
+    /// Split into successful values and errors, each in input order.
+    pub fn partition(self) -> (Vec<T>, Vec<ItemError<E>>) {
+        let mut successes = Vec::new();
+        let mut failures = Vec::new();
+        for result in self.results {
+            match result {
+                Ok(value) => successes.push(value),
+                Err(e) => failures.push(e),
+            }
+        }
+        (successes, failures)
+    }
+}
+
+/// Execute boxed effects in parallel with per-item timeouts, an overall
+/// deadline, and an [`ErrorPolicy`].
+///
+/// Unlike [`par_all`](super::par_all), this always returns the outcome of
+/// every item, so batch jobs can keep the work that finished even when some
+/// items fail or straggle. Items that didn't finish are reported as
+/// [`ItemError::DeadlineExceeded`] or [`ItemError::Cancelled`].
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use stillwater::effect::prelude::*;
+/// use stillwater::effect::parallel::{par_all_with, ParOptions};
+/// use std::time::Duration;
+///
+/// let options = ParOptions::new().item_timeout(Duration::from_millis(100));
+/// let (fetched, failed) = par_all_with(fetches, options, &env).await.partition();
+/// ```
+pub async fn par_all_with<T, E, Env>(
+    effects: Vec<BoxedEffect<T, E, Env>>,
+    options: ParOptions,
+    env: &Env,
+) -> ParResults<T, E>
+where
+    T: Send + 'static,
+    E: Send + 'static,
+    Env: Send + Sync + 'static,
+{
+    let mut slots: Vec<Option<Result<T, ItemError<E>>>> = effects.iter().map(|_| None).collect();
+
+    let mut pending: FuturesUnordered<_> = effects
+        .into_iter()
+        .enumerate()
+        .map(|(index, effect)| async move {
+            let result = match options.item_timeout {
+                Some(duration) => match tokio::time::timeout(duration, run_item(effect, env)).await
+                {
+                    Ok(result) => result.map_err(ItemError::Failed),
+                    Err(_) => Err(ItemError::Timeout { duration }),
+                },
+                None => run_item(effect, env).await.map_err(ItemError::Failed),
+            };
+            (index, result)
+        })
+        .collect();
+
+    let deadline = async {
+        match options.deadline {
+            Some(deadline) => tokio::time::sleep(deadline).await,
+            None => std::future::pending().await,
+        }
+    };
+    tokio::pin!(deadline);
+
+    // Set once the deadline fires; any item still unfinished after the loop
+    // was otherwise cancelled by fail-fast.
+    let mut deadline_hit = None;
+    loop {
+        tokio::select! {
+            next = pending.next() => match next {
+                Some((index, result)) => {
+                    let failed = result.is_err();
+                    slots[index] = Some(result);
+                    if failed && options.policy == ErrorPolicy::FailFast {
+                        break;
+                    }
+                }
+                None => break,
+            },
+            () = &mut deadline => {
+                deadline_hit = options.deadline;
+                break;
+            }
+        }
+    }
+    drop(pending);
+
+    ParResults {
+        results: slots
+            .into_iter()
+            .map(|slot| {
+                slot.unwrap_or(Err(match deadline_hit {
+                    Some(deadline) => ItemError::DeadlineExceeded { deadline },
+                    None => ItemError::Cancelled,
+                }))
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::effect::constructors::{fail, from_async, pure};
+    use crate::effect::ext::EffectExt;
+    use std::time::Instant;
+
+    fn after(ms: u64, value: i32) -> BoxedEffect<i32, String, ()> {
+        from_async(move |_: &()| async move {
+            tokio::time::sleep(Duration::from_millis(ms)).await;
+            Ok(value)
+        })
+        .boxed()
+    }
+
+    #[tokio::test]
+    async fn test_all_succeed_in_order() {
+        let effects = vec![after(20, 1), pure(2).boxed(), after(10, 3)];
+
+        let results = par_all_with(effects, ParOptions::new(), &()).await;
+
+        assert!(results.is_complete());
+        assert_eq!(results.partition(), (vec![1, 2, 3], vec![]));
+    }
+
+    #[tokio::test]
+    async fn test_item_timeout_keeps_other_results() {
+        let effects = vec![pure(1).boxed(), after(500, 2), after(5, 3)];
+        let options = ParOptions::new().item_timeout(Duration::from_millis(50));
+
+        let results = par_all_with(effects, options, &()).await;
+
+        assert_eq!(
+            results.into_results(),
+            vec![
+                Ok(1),
+                Err(ItemError::Timeout {
+                    duration: Duration::from_millis(50)
+                }),
+                Ok(3),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_deadline_returns_partial_results() {
+        let effects = vec![after(5, 1), after(1_000, 2), after(1_000, 3)];
+        let options = ParOptions::new().deadline(Duration::from_millis(50));
+
+        let start = Instant::now();
+        let (successes, failures) = par_all_with(effects, options, &()).await.partition();
+
+        assert!(start.elapsed() < Duration::from_millis(500));
+        assert_eq!(successes, vec![1]);
+        assert_eq!(
+            failures,
+            vec![
+                ItemError::DeadlineExceeded {
+                    deadline: Duration::from_millis(50)
+                };
+                2
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_collect_partial_reports_every_failure() {
+        let effects = vec![
+            fail("a".to_string()).boxed(),
+            pure(2).boxed(),
+            fail("b".to_string()).boxed(),
+        ];
+
+        let (successes, failures) = par_all_with(effects, ParOptions::new(), &())
+            .await
+            .partition();
+
+        assert_eq!(successes, vec![2]);
+        assert_eq!(
+            failures,
+            vec![
+                ItemError::Failed("a".to_string()),
+                ItemError::Failed("b".to_string())
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fail_fast_cancels_remaining_items() {
+        let effects = vec![
+            pure(1).boxed(),
+            fail("boom".to_string()).boxed(),
+            after(1_000, 3),
+        ];
+        let options = ParOptions::new().policy(ErrorPolicy::FailFast);
+
+        let start = Instant::now();
+        let results = par_all_with(effects, options, &()).await;
+
+        assert!(start.elapsed() < Duration::from_millis(500));
+        assert_eq!(
+            results.into_results(),
+            vec![
+                Ok(1),
+                Err(ItemError::Failed("boom".to_string())),
+                Err(ItemError::Cancelled),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_empty_batch() {
+        let results = par_all_with::<i32, String, ()>(vec![], ParOptions::new(), &()).await;
+        assert!(results.is_complete());
+        assert!(results.results().is_empty());
+    }
+
+    #[test]
+    fn test_item_error_display() {
+        assert_eq!(ItemError::Failed("boom").to_string(), "boom");
+        assert_eq!(
+            ItemError::<String>::Cancelled.to_string(),
+            "item cancelled after an earlier failure"
+        );
+    }
+}
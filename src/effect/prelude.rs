@@ -29,7 +29,9 @@
 //! - **Core traits**: [`Effect`], [`EffectExt`], [`EffectContext`]
 //! - **Free function constructors**: [`pure`], [`fail`], [`asks`], [`from_fn`], etc.
 //! - **Combinator types**: [`Map`], [`AndThen`], [`Zip`], etc. (for type signatures)
-//! - **Reader operations**: [`ask`], [`asks`], [`local`]
+//! - **Reader operations**: [`ask`], [`asks`], [`local`], [`asks_has`] and
+//!   [`local_has`] for tuple environments, and [`Project`] for running
+//!   library effects in an application environment
 //! - **Resource management**: [`bracket`], [`bracket2`], etc.
 //! - **Parallel execution**: [`par2`], [`par3`], [`par_all`], etc.
 //! - **Boxing utilities**: [`BoxedEffect`], [`BoxedLocalEffect`]
@@ -49,6 +51,8 @@
 // Traits
 pub use crate::effect::context::{EffectContext, EffectContextChain};
 pub use crate::effect::ext::EffectExt;
+pub use crate::effect::into_effect::{FutureIntoEffect, IntoEffect, OptionIntoEffect};
+pub use crate::effect::rerunnable::ReRunnable;
 pub use crate::effect::trait_def::Effect;
 
 // Boxed Effect
@@ -56,12 +60,21 @@ pub use crate::effect::boxed::{BoxFuture, BoxedEffect, BoxedLocalEffect};
 
 // Combinator Types (for advanced use, usually `impl Effect` suffices)
 pub use crate::effect::combinators::{
-    AndThen, AndThenAuto, AndThenRef, Check, Fail, FromAsync, FromFn, FromResult, Map, MapErr,
-    OrElse, Pure, Tap, With, Zip, Zip3, Zip4, Zip5, Zip6, Zip7, Zip8, ZipWith,
+    AndThen, AndThenAuto, AndThenRef, Check, Fail, FromAsync, FromFn, FromFuture, FromResult, Loop,
+    LoopEffect, Map, MapErr, OnCancel, OrElse, Pure, Tap, With, Zip, Zip3, Zip4, Zip5, Zip6, Zip7,
+    Zip8, ZipJoin, ZipPar, ZipWith,
 };
 
 // Reader Types
-pub use crate::effect::reader::{Ask, Asks, Local};
+pub use crate::effect::reader::{Ask, Asks, Local, MapEnvFor};
+
+// Library environments
+pub use crate::effect::env::{Project, ProjectEnv};
+
+// Tuple environments
+pub use crate::effect::tuple_env::{asks_has, local_has, LocalHas};
+
+pub use crate::effect::request_context::RequestContextExt;
 
 // Bracket types and constructors
 #[allow(deprecated)]
@@ -73,12 +86,15 @@ pub use crate::effect::bracket::{
 
 // Constructors
 pub use crate::effect::constructors::{
-    ask, asks, fail, from_async, from_fn, from_option, from_result, from_validation, local, pure,
-    zip3, zip4, zip5, zip6, zip7, zip8,
+    ask, asks, fail, from_async, from_fn, from_option, from_result, from_validation, local,
+    loop_effect, pure, zip3, zip4, zip5, zip6, zip7, zip8,
 };
 
 // Parallel (homogeneous, requires boxing)
-pub use crate::effect::parallel::{par_all, par_all_limit, par_try_all, race};
+pub use crate::effect::parallel::{
+    par_all, par_all_labeled, par_all_limit, par_all_settled, par_try_all, par_validate_all, race,
+    zip_labeled, LabeledError,
+};
 
 // Parallel (heterogeneous, zero-cost)
 pub use crate::effect::parallel::{par2, par3, par4};
@@ -86,6 +102,9 @@ pub use crate::effect::parallel::{par2, par3, par4};
 // Re-export the par! macro
 pub use crate::par;
 
+// Re-export the recover_matching! macro
+pub use crate::recover_matching;
+
 // Retry functions (when async feature is enabled)
 #[cfg(feature = "async")]
 pub use crate::effect::retry::{retry, retry_if, retry_with_hooks, with_timeout};
@@ -94,5 +113,9 @@ pub use crate::effect::retry::{retry, retry_if, retry_with_hooks, with_timeout};
 #[cfg(feature = "tracing")]
 pub use crate::effect::tracing::EffectTracingExt;
 
+// Value tracing (when debug feature is enabled)
+#[cfg(feature = "debug")]
+pub use crate::effect::traced::TraceValuesExt;
+
 // Compat traits for running effects
 pub use crate::effect::compat::RunStandalone;
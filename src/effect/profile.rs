@@ -0,0 +1,394 @@
+//! Per-stage profiling for effect chains.
+//!
+//! This module provides the [`Profile`] combinator, created by
+//! [`EffectExt::profile`](crate::effect::EffectExt::profile), which records
+//! wall time and poll counts for a named stage into a shared
+//! [`ProfileReport`]. The report is a cheap handle that can be cloned into
+//! nested stages and inspected after the effect has run.
+//!
+//! # Allocation Counts
+//!
+//! With the `alloc_stats` feature, each stage also records how many
+//! allocations (and bytes) were made while it was being polled. This
+//! requires installing [`CountingAllocator`] as the global allocator:
+//!
+//! ```rust,ignore
+//! use stillwater::effect::profile::CountingAllocator;
+//!
+//! #[global_allocator]
+//! static ALLOC: CountingAllocator = CountingAllocator::new();
+//! ```
+//!
+//! Allocations are attributed per poll on the polling thread, so they remain
+//! accurate on multi-threaded runtimes even when a task migrates between
+//! threads. Allocations made by other tasks the stage spawns are not counted.
+//!
+//! # Example
+//!
+//! ```rust
+//! use stillwater::effect::prelude::*;
+//! use stillwater::effect::profile::ProfileReport;
+//!
+//! # tokio_test::block_on(async {
+//! let report = ProfileReport::new();
+//! let inner = report.clone();
+//!
+//! let effect = pure::<_, String, ()>(21)
+//!     .profile("load", &report)
+//!     .and_then(move |x| pure(x * 2).profile("double", &inner));
+//!
+//! assert_eq!(effect.execute(&()).await, Ok(42));
+//!
+//! let stages = report.stages();
+//! assert_eq!(stages.len(), 2);
+//! assert_eq!(stages[0].name, "load");
+//! assert_eq!(stages[1].name, "double");
+//! assert!(stages.iter().all(|s| s.polls >= 1 && s.succeeded));
+//! # });
+//! ```
+
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::effect::trait_def::Effect;
+
+/// Measurements for a single profiled stage.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StageProfile {
+    /// The stage name given to [`profile`](crate::effect::EffectExt::profile).
+    pub name: String,
+    /// Wall time from the first poll to completion.
+    pub wall_time: Duration,
+    /// Number of times the stage's future was polled.
+    pub polls: u64,
+    /// Whether the stage completed with `Ok`.
+    pub succeeded: bool,
+    /// Number of allocations made while polling (requires `alloc_stats`).
+    pub allocations: Option<u64>,
+    /// Number of bytes allocated while polling (requires `alloc_stats`).
+    pub allocated_bytes: Option<u64>,
+}
+
+/// A shared collection of [`StageProfile`]s.
+///
+/// Cloning a report produces another handle to the same collection, so
+/// clones can be moved into closures that build nested stages. Stages are
+/// recorded in completion order; a nested stage completes before the
+/// stage that contains it.
+#[derive(Debug, Clone, Default)]
+pub struct ProfileReport {
+    stages: Arc<Mutex<Vec<StageProfile>>>,
+}
+
+impl ProfileReport {
+    /// Create an empty report.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Snapshot of the recorded stages, in completion order.
+    pub fn stages(&self) -> Vec<StageProfile> {
+        self.lock().clone()
+    }
+
+    /// Look up the most recent measurement for the named stage.
+    pub fn stage(&self, name: &str) -> Option<StageProfile> {
+        self.lock().iter().rev().find(|s| s.name == name).cloned()
+    }
+
+    /// Total wall time across all recorded stages named `name`.
+    pub fn total_time(&self, name: &str) -> Duration {
+        self.lock()
+            .iter()
+            .filter(|s| s.name == name)
+            .map(|s| s.wall_time)
+            .sum()
+    }
+
+    /// Returns `true` if nothing has been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// Remove all recorded stages.
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    fn record(&self, stage: StageProfile) {
+        self.lock().push(stage);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<StageProfile>> {
+        self.stages.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// An effect whose execution is recorded into a [`ProfileReport`].
+///
+/// Created by [`EffectExt::profile`](crate::effect::EffectExt::profile).
+#[derive(Debug)]
+pub struct Profile<E> {
+    pub(crate) inner: E,
+    pub(crate) name: String,
+    pub(crate) report: ProfileReport,
+}
+
+impl<E> Effect for Profile<E>
+where
+    E: Effect,
+{
+    type Output = E::Output;
+    type Error = E::Error;
+    type Env = E::Env;
+
+    async fn run(self, env: &Self::Env) -> Result<Self::Output, Self::Error> {
+        let mut polls = 0u64;
+        let mut started: Option<Instant> = None;
+        let mut allocs = alloc_stats::Counter::new();
+        let mut future = std::pin::pin!(self.inner.run(env));
+
+        let result = std::future::poll_fn(|cx| {
+            started.get_or_insert_with(Instant::now);
+            polls += 1;
+            let snapshot = alloc_stats::snapshot();
+            let poll = future.as_mut().poll(cx);
+            allocs.add_since(snapshot);
+            poll
+        })
+        .await;
+
+        let (allocations, allocated_bytes) = allocs.finish();
+        self.report.record(StageProfile {
+            name: self.name,
+            wall_time: started.map(|s| s.elapsed()).unwrap_or_default(),
+            polls,
+            succeeded: result.is_ok(),
+            allocations,
+            allocated_bytes,
+        });
+        result
+    }
+}
+
+#[cfg(feature = "alloc_stats")]
+pub use alloc_stats::CountingAllocator;
+
+#[cfg(feature = "alloc_stats")]
+mod alloc_stats {
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::cell::Cell;
+
+    thread_local! {
+        static COUNTS: Cell<(u64, u64)> = const { Cell::new((0, 0)) };
+    }
+
+    fn bump(bytes: usize) {
+        let _ = COUNTS.try_with(|c| {
+            let (n, b) = c.get();
+            c.set((n + 1, b + bytes as u64));
+        });
+    }
+
+    /// A global allocator that counts allocations per thread.
+    ///
+    /// Wraps [`System`]; install it with `#[global_allocator]` to enable
+    /// allocation counts in [`StageProfile`](super::StageProfile).
+    #[derive(Debug, Default)]
+    pub struct CountingAllocator;
+
+    impl CountingAllocator {
+        /// Create a counting allocator.
+        pub const fn new() -> Self {
+            CountingAllocator
+        }
+    }
+
+    // SAFETY: all allocation is delegated to `System`; counting only
+    // touches a const-initialized thread-local that never allocates.
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            bump(layout.size());
+            unsafe { System.alloc(layout) }
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            bump(layout.size());
+            unsafe { System.alloc_zeroed(layout) }
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            bump(new_size);
+            unsafe { System.realloc(ptr, layout, new_size) }
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            unsafe { System.dealloc(ptr, layout) }
+        }
+    }
+
+    pub(super) fn snapshot() -> (u64, u64) {
+        COUNTS.try_with(Cell::get).unwrap_or_default()
+    }
+
+    pub(super) struct Counter {
+        allocations: u64,
+        bytes: u64,
+    }
+
+    impl Counter {
+        pub(super) fn new() -> Self {
+            Counter {
+                allocations: 0,
+                bytes: 0,
+            }
+        }
+
+        pub(super) fn add_since(&mut self, (n, b): (u64, u64)) {
+            let (now_n, now_b) = snapshot();
+            self.allocations += now_n.saturating_sub(n);
+            self.bytes += now_b.saturating_sub(b);
+        }
+
+        pub(super) fn finish(self) -> (Option<u64>, Option<u64>) {
+            (Some(self.allocations), Some(self.bytes))
+        }
+    }
+}
+
+#[cfg(not(feature = "alloc_stats"))]
+mod alloc_stats {
+    pub(super) fn snapshot() -> (u64, u64) {
+        (0, 0)
+    }
+
+    pub(super) struct Counter;
+
+    impl Counter {
+        pub(super) fn new() -> Self {
+            Counter
+        }
+
+        pub(super) fn add_since(&mut self, _: (u64, u64)) {}
+
+        pub(super) fn finish(self) -> (Option<u64>, Option<u64>) {
+            (None, None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::effect::constructors::{fail, from_async, pure};
+    use crate::effect::ext::EffectExt;
+
+    #[tokio::test]
+    async fn test_profile_records_successful_stage() {
+        let report = ProfileReport::new();
+        let effect = pure::<_, String, ()>(42).profile("answer", &report);
+
+        assert_eq!(effect.execute(&()).await, Ok(42));
+
+        let stage = report.stage("answer").unwrap();
+        assert_eq!(stage.polls, 1);
+        assert!(stage.succeeded);
+    }
+
+    #[tokio::test]
+    async fn test_profile_records_failed_stage() {
+        let report = ProfileReport::new();
+        let effect = fail::<i32, _, ()>("boom".to_string()).profile("failing", &report);
+
+        assert_eq!(effect.execute(&()).await, Err("boom".to_string()));
+        assert!(!report.stage("failing").unwrap().succeeded);
+    }
+
+    #[tokio::test]
+    async fn test_profile_counts_polls_and_time() {
+        let report = ProfileReport::new();
+        let effect = from_async(|_: &()| async {
+            tokio::task::yield_now().await;
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            Ok::<_, String>(())
+        })
+        .profile("slow", &report);
+
+        effect.execute(&()).await.unwrap();
+
+        let stage = report.stage("slow").unwrap();
+        assert!(
+            stage.polls >= 3,
+            "expected multiple polls, got {}",
+            stage.polls
+        );
+        assert!(stage.wall_time >= Duration::from_millis(10));
+    }
+
+    #[tokio::test]
+    async fn test_nested_stages_complete_before_outer() {
+        let report = ProfileReport::new();
+        let inner = report.clone();
+        let effect = pure::<_, String, ()>(1)
+            .and_then(move |x| pure(x + 1).profile("inner", &inner))
+            .profile("outer", &report);
+
+        assert_eq!(effect.execute(&()).await, Ok(2));
+
+        let names: Vec<_> = report.stages().into_iter().map(|s| s.name).collect();
+        assert_eq!(names, vec!["inner", "outer"]);
+    }
+
+    #[tokio::test]
+    async fn test_repeated_stages_accumulate() {
+        let report = ProfileReport::new();
+        for _ in 0..3 {
+            pure::<_, String, ()>(())
+                .profile("step", &report)
+                .execute(&())
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(report.stages().len(), 3);
+        assert!(report.total_time("step") >= report.stage("step").unwrap().wall_time);
+
+        report.clear();
+        assert!(report.is_empty());
+    }
+
+    #[cfg(feature = "alloc_stats")]
+    #[global_allocator]
+    static ALLOC: CountingAllocator = CountingAllocator::new();
+
+    #[cfg(feature = "alloc_stats")]
+    #[tokio::test]
+    async fn test_allocation_counts_with_feature() {
+        let report = ProfileReport::new();
+        from_async(|_: &()| async { Ok::<_, String>(vec![0u8; 1024]) })
+            .profile("alloc", &report)
+            .execute(&())
+            .await
+            .unwrap();
+
+        let stage = report.stage("alloc").unwrap();
+        assert!(stage.allocations.unwrap() >= 1);
+        assert!(stage.allocated_bytes.unwrap() >= 1024);
+    }
+
+    #[cfg(not(feature = "alloc_stats"))]
+    #[tokio::test]
+    async fn test_allocation_counts_require_feature() {
+        let report = ProfileReport::new();
+        pure::<_, String, ()>(())
+            .profile("step", &report)
+            .execute(&())
+            .await
+            .unwrap();
+
+        let stage = report.stage("step").unwrap();
+        assert_eq!(stage.allocations, None);
+        assert_eq!(stage.allocated_bytes, None);
+    }
+}
@@ -0,0 +1,631 @@
+//! Priority-aware execution queue for effects.
+//!
+//! A [`PriorityExecutor`] runs submitted effects on the Tokio runtime with a
+//! fixed concurrency limit. When a slot frees up, the queued effect with the
+//! highest priority runs next; ties run in submission order. Use it to mix
+//! interactive and batch work in one service without batch jobs crowding out
+//! latency-sensitive ones.
+//!
+//! # Starvation Protection
+//!
+//! With [`with_aging`](PriorityExecutor::with_aging), a queued effect's
+//! priority grows by one for every interval it has waited, so low-priority
+//! work still makes progress under a steady stream of high-priority work.
+//!
+//! # Example
+//!
+//! ```rust
+//! use stillwater::effect::prelude::*;
+//! use stillwater::effect::queue::PriorityExecutor;
+//! use std::time::Duration;
+//!
+//! # tokio_test::block_on(async {
+//! let executor = PriorityExecutor::new((), 2).with_aging(Duration::from_millis(100));
+//!
+//! let report = executor.submit(0, pure::<_, String, ()>("batch report"));
+//! let lookup = executor.submit(10, pure::<_, String, ()>("user lookup"));
+//!
+//! assert_eq!(lookup.await, Ok("user lookup"));
+//! assert_eq!(report.await, Ok("batch report"));
+//! # });
+//! ```
+
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use tokio::sync::oneshot;
+
+use crate::effect::boxed::BoxFuture;
+use crate::effect::request_context::Propagation;
+use crate::effect::trait_def::Effect;
+#[cfg(feature = "prometheus")]
+use crate::integrations::prometheus::{ExecutorMetrics, QueueMetrics};
+
+/// Identifies a job submitted to a [`PriorityExecutor`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct JobId(u64);
+
+impl fmt::Display for JobId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "job-{}", self.0)
+    }
+}
+
+/// A lifecycle event, passed to the executor's hook.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QueueEvent {
+    /// A job was submitted and queued.
+    Submitted {
+        /// The job.
+        id: JobId,
+        /// The priority it was submitted with.
+        priority: u32,
+        /// Jobs waiting in the queue, including this one.
+        queued: usize,
+    },
+    /// A job started running.
+    Started {
+        /// The job.
+        id: JobId,
+        /// The priority it was submitted with.
+        priority: u32,
+        /// How long it waited in the queue.
+        waited: Duration,
+    },
+    /// A job finished.
+    Completed {
+        /// The job.
+        id: JobId,
+        /// Whether the effect succeeded.
+        succeeded: bool,
+        /// How long the effect ran.
+        run_time: Duration,
+    },
+}
+
+/// Error returned by a [`JobHandle`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JobError<E> {
+    /// The effect failed.
+    Failed(E),
+    /// The job was dropped before producing a result, e.g. because the
+    /// runtime shut down or the effect panicked.
+    Cancelled,
+}
+
+impl<E> JobError<E> {
+    /// Get the effect's error if present.
+    pub fn into_inner(self) -> Option<E> {
+        match self {
+            Self::Failed(e) => Some(e),
+            Self::Cancelled => None,
+        }
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for JobError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Failed(e) => write!(f, "{}", e),
+            Self::Cancelled => write!(f, "job was cancelled before completing"),
+        }
+    }
+}
+
+impl<E: std::error::Error + 'static> std::error::Error for JobError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Failed(e) => Some(e),
+            Self::Cancelled => None,
+        }
+    }
+}
+
+/// A handle to a submitted job; await it for the effect's result.
+///
+/// Dropping the handle does not cancel the job.
+pub struct JobHandle<T, E> {
+    id: JobId,
+    receiver: oneshot::Receiver<Result<T, E>>,
+}
+
+impl<T, E> JobHandle<T, E> {
+    /// The job's id, matching the ids in [`QueueEvent`]s.
+    pub fn id(&self) -> JobId {
+        self.id
+    }
+}
+
+impl<T, E> fmt::Debug for JobHandle<T, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JobHandle").field("id", &self.id).finish()
+    }
+}
+
+impl<T, E> Future for JobHandle<T, E> {
+    type Output = Result<T, JobError<E>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.receiver)
+            .poll(cx)
+            .map(|received| match received {
+                Ok(result) => result.map_err(JobError::Failed),
+                Err(_) => Err(JobError::Cancelled),
+            })
+    }
+}
+
+type Job<Env> = Box<dyn FnOnce(Arc<Env>) -> BoxFuture<'static, bool> + Send>;
+type Hook = Arc<dyn Fn(&QueueEvent) + Send + Sync>;
+
+struct Queued<Env> {
+    id: JobId,
+    priority: u32,
+    submitted_at: Instant,
+    job: Job<Env>,
+}
+
+struct State<Env> {
+    queue: Vec<Queued<Env>>,
+    running: usize,
+    next_id: u64,
+}
+
+struct Shared<Env> {
+    env: Arc<Env>,
+    concurrency: usize,
+    aging: Option<Duration>,
+    hook: Option<Hook>,
+    #[cfg(feature = "prometheus")]
+    metrics: Option<ExecutorMetrics>,
+    state: Mutex<State<Env>>,
+}
+
+impl<Env> Shared<Env> {
+    fn lock(&self) -> MutexGuard<'_, State<Env>> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn emit(&self, event: QueueEvent) {
+        #[cfg(feature = "prometheus")]
+        if let Some(metrics) = &self.metrics {
+            metrics.observe(&event);
+            self.record_sizes();
+        }
+        if let Some(hook) = &self.hook {
+            hook(&event);
+        }
+    }
+
+    /// Set the executor's gauges from its current state.
+    #[cfg(feature = "prometheus")]
+    fn record_sizes(&self) {
+        if let Some(metrics) = &self.metrics {
+            let state = self.lock();
+            metrics.set_sizes(state.queue.len(), state.running);
+        }
+    }
+
+    fn effective_priority(&self, queued: &Queued<Env>, now: Instant) -> u64 {
+        let boost = match self.aging {
+            Some(interval) if !interval.is_zero() => {
+                let waited = now.saturating_duration_since(queued.submitted_at);
+                (waited.as_nanos() / interval.as_nanos()) as u64
+            }
+            _ => 0,
+        };
+        u64::from(queued.priority).saturating_add(boost)
+    }
+
+    /// Remove the job that should run next: highest effective priority,
+    /// then earliest submitted.
+    fn take_next(&self, state: &mut State<Env>) -> Option<Queued<Env>> {
+        let now = Instant::now();
+        let index = state
+            .queue
+            .iter()
+            .enumerate()
+            .max_by(|(_, a), (_, b)| {
+                self.effective_priority(a, now)
+                    .cmp(&self.effective_priority(b, now))
+                    .then(b.id.cmp(&a.id))
+            })
+            .map(|(index, _)| index)?;
+        Some(state.queue.remove(index))
+    }
+}
+
+/// Runs submitted effects by priority with bounded concurrency.
+///
+/// Higher priorities run first. Cloning the executor produces another handle
+/// to the same queue. Submitting requires a running Tokio runtime.
+///
+/// Selecting the next job scans the queue, so each dispatch is linear in the
+/// number of waiting jobs.
+pub struct PriorityExecutor<Env> {
+    shared: Arc<Shared<Env>>,
+}
+
+impl<Env> Clone for PriorityExecutor<Env> {
+    fn clone(&self) -> Self {
+        PriorityExecutor {
+            shared: Arc::clone(&self.shared),
+        }
+    }
+}
+
+impl<Env> fmt::Debug for PriorityExecutor<Env> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.shared.lock();
+        f.debug_struct("PriorityExecutor")
+            .field("concurrency", &self.shared.concurrency)
+            .field("aging", &self.shared.aging)
+            .field("queued", &state.queue.len())
+            .field("running", &state.running)
+            .finish()
+    }
+}
+
+impl<Env> PriorityExecutor<Env>
+where
+    Env: Send + Sync + 'static,
+{
+    /// Create an executor that runs at most `concurrency` effects at once
+    /// against `env`. A concurrency of 0 is treated as 1.
+    pub fn new(env: Env, concurrency: usize) -> Self {
+        PriorityExecutor {
+            shared: Arc::new(Shared {
+                env: Arc::new(env),
+                concurrency: concurrency.max(1),
+                aging: None,
+                hook: None,
+                #[cfg(feature = "prometheus")]
+                metrics: None,
+                state: Mutex::new(State {
+                    queue: Vec::new(),
+                    running: 0,
+                    next_id: 0,
+                }),
+            }),
+        }
+    }
+
+    /// Raise a queued job's priority by one for every `interval` it waits.
+    ///
+    /// Must be called before any other handle to the executor exists.
+    pub fn with_aging(self, interval: Duration) -> Self {
+        self.configure(|shared| shared.aging = Some(interval))
+    }
+
+    /// Call `hook` for every [`QueueEvent`].
+    ///
+    /// The hook is synchronous and should not block; use it for logging or
+    /// metrics. Must be called before any other handle to the executor
+    /// exists.
+    pub fn with_hook<H>(self, hook: H) -> Self
+    where
+        H: Fn(&QueueEvent) + Send + Sync + 'static,
+    {
+        self.configure(|shared| shared.hook = Some(Arc::new(hook)))
+    }
+
+    /// Report this executor's queue sizes, waits and run times to
+    /// `metrics`, labelled `executor = name`.
+    ///
+    /// Must be called before any other handle to the executor exists.
+    #[cfg(feature = "prometheus")]
+    pub fn with_metrics(self, metrics: &QueueMetrics, name: &str) -> Self {
+        let metrics = metrics.executor(name);
+        self.configure(|shared| shared.metrics = Some(metrics))
+    }
+
+    fn configure(mut self, f: impl FnOnce(&mut Shared<Env>)) -> Self {
+        f(Arc::get_mut(&mut self.shared)
+            .expect("configure the executor before cloning or submitting to it"));
+        self
+    }
+
+    /// Queue an effect with the given priority; higher runs first.
+    ///
+    /// The job runs in the [request context](crate::effect::request_context)
+    /// and, with the `tracing` feature, the span current at submission.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside a Tokio runtime.
+    pub fn submit<Eff>(&self, priority: u32, effect: Eff) -> JobHandle<Eff::Output, Eff::Error>
+    where
+        Eff: Effect<Env = Env> + 'static,
+    {
+        let (sender, receiver) = oneshot::channel();
+        let propagation = Propagation::capture();
+        let job: Job<Env> = Box::new(move |env: Arc<Env>| {
+            Box::pin(propagation.scope(async move {
+                let result = effect.run(&env).await;
+                let succeeded = result.is_ok();
+                let _ = sender.send(result);
+                succeeded
+            }))
+        });
+
+        let (id, queued) = {
+            let mut state = self.shared.lock();
+            let id = JobId(state.next_id);
+            state.next_id += 1;
+            state.queue.push(Queued {
+                id,
+                priority,
+                submitted_at: Instant::now(),
+                job,
+            });
+            (id, state.queue.len())
+        };
+        self.shared.emit(QueueEvent::Submitted {
+            id,
+            priority,
+            queued,
+        });
+
+        dispatch(&self.shared);
+        JobHandle { id, receiver }
+    }
+
+    /// The number of jobs waiting to run.
+    pub fn queued(&self) -> usize {
+        self.shared.lock().queue.len()
+    }
+
+    /// The number of jobs currently running.
+    pub fn running(&self) -> usize {
+        self.shared.lock().running
+    }
+}
+
+/// Start queued jobs until the concurrency limit is reached.
+fn dispatch<Env>(shared: &Arc<Shared<Env>>)
+where
+    Env: Send + Sync + 'static,
+{
+    loop {
+        let next = {
+            let mut state = shared.lock();
+            if state.running >= shared.concurrency {
+                return;
+            }
+            let Some(next) = shared.take_next(&mut state) else {
+                return;
+            };
+            state.running += 1;
+            next
+        };
+
+        shared.emit(QueueEvent::Started {
+            id: next.id,
+            priority: next.priority,
+            waited: next.submitted_at.elapsed(),
+        });
+
+        let shared = Arc::clone(shared);
+        let future = (next.job)(Arc::clone(&shared.env));
+        tokio::spawn(async move {
+            // Frees the slot even if the effect panics.
+            let slot = Slot(Arc::clone(&shared));
+            let started = Instant::now();
+            let succeeded = future.await;
+            shared.emit(QueueEvent::Completed {
+                id: next.id,
+                succeeded,
+                run_time: started.elapsed(),
+            });
+            drop(slot);
+        });
+    }
+}
+
+struct Slot<Env: Send + Sync + 'static>(Arc<Shared<Env>>);
+
+impl<Env: Send + Sync + 'static> Drop for Slot<Env> {
+    fn drop(&mut self) {
+        self.0.lock().running -= 1;
+        #[cfg(feature = "prometheus")]
+        self.0.record_sizes();
+        dispatch(&self.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::effect::constructors::{fail, from_async, from_fn, pure};
+    use crate::effect::ext::EffectExt;
+
+    type Log = Arc<Mutex<Vec<&'static str>>>;
+
+    fn record(log: &Log, name: &'static str) -> impl Effect<Output = (), Error = String, Env = ()> {
+        let log = Arc::clone(log);
+        from_async(move |_: &()| async move {
+            log.lock().unwrap().push(name);
+            Ok(())
+        })
+    }
+
+    /// An effect that holds its slot until `release` fires.
+    fn gate() -> (
+        oneshot::Sender<()>,
+        impl Effect<Output = (), Error = String, Env = ()>,
+    ) {
+        let (release, wait) = oneshot::channel::<()>();
+        let effect = from_async(move |_: &()| async move {
+            let _ = wait.await;
+            Ok(())
+        });
+        (release, effect)
+    }
+
+    #[tokio::test]
+    async fn test_higher_priority_runs_first() {
+        let executor = PriorityExecutor::new((), 1);
+        let log = Log::default();
+
+        let (release, blocker) = gate();
+        let blocked = executor.submit(100, blocker);
+        let handles = vec![
+            executor.submit(1, record(&log, "low")),
+            executor.submit(5, record(&log, "high")),
+            executor.submit(3, record(&log, "mid")),
+            executor.submit(5, record(&log, "high-later")),
+        ];
+        assert_eq!(executor.queued(), 4);
+
+        release.send(()).unwrap();
+        blocked.await.unwrap();
+        for handle in handles {
+            handle.await.unwrap();
+        }
+
+        assert_eq!(
+            *log.lock().unwrap(),
+            vec!["high", "high-later", "mid", "low"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_job_keeps_submitters_request_context() {
+        use crate::effect::request_context::{current_request_id, RequestContextExt};
+
+        let executor = PriorityExecutor::new((), 1);
+        let (release, blocker) = gate();
+        let blocked = executor.submit(0, blocker.with_request_id("blocker"));
+
+        let submit = {
+            let executor = executor.clone();
+            from_fn(move |_: &()| {
+                Ok::<_, String>(
+                    executor.submit(0, from_fn(|_: &()| Ok::<_, String>(current_request_id()))),
+                )
+            })
+        };
+        let handle = submit.with_request_id("submitter").run(&()).await.unwrap();
+
+        // The job starts when the blocker's slot frees, while no request
+        // context is current.
+        release.send(()).unwrap();
+        blocked.await.unwrap();
+        assert_eq!(handle.await, Ok(Some("submitter".to_string())));
+    }
+
+    #[tokio::test]
+    async fn test_aging_prevents_starvation() {
+        let executor = PriorityExecutor::new((), 1).with_aging(Duration::from_millis(5));
+        let log = Log::default();
+
+        let (release, blocker) = gate();
+        let blocked = executor.submit(0, blocker);
+        let old = executor.submit(0, record(&log, "old-low"));
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        let new = executor.submit(3, record(&log, "new-high"));
+
+        release.send(()).unwrap();
+        blocked.await.unwrap();
+        old.await.unwrap();
+        new.await.unwrap();
+
+        assert_eq!(*log.lock().unwrap(), vec!["old-low", "new-high"]);
+    }
+
+    #[tokio::test]
+    async fn test_concurrency_limit() {
+        let executor = PriorityExecutor::new((), 2);
+        let active = Arc::new(Mutex::new((0usize, 0usize)));
+
+        let handles: Vec<_> = (0..6)
+            .map(|_| {
+                let active = Arc::clone(&active);
+                executor.submit(
+                    0,
+                    from_async(move |_: &()| async move {
+                        {
+                            let mut counts = active.lock().unwrap();
+                            counts.0 += 1;
+                            counts.1 = counts.1.max(counts.0);
+                        }
+                        tokio::time::sleep(Duration::from_millis(10)).await;
+                        active.lock().unwrap().0 -= 1;
+                        Ok::<_, String>(())
+                    }),
+                )
+            })
+            .collect();
+
+        for handle in handles {
+            handle.await.unwrap();
+        }
+        assert_eq!(active.lock().unwrap().1, 2);
+        assert_eq!(executor.running(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_handles_return_results_and_errors() {
+        let executor = PriorityExecutor::new(10, 4);
+
+        let ok = executor.submit(0, pure::<_, String, i32>(1).map(|x| x + 1));
+        let err = executor.submit(0, fail::<i32, _, i32>("boom".to_string()));
+        let env = executor.submit(0, crate::effect::constructors::asks(|n: &i32| *n));
+
+        assert_eq!(ok.await, Ok(2));
+        assert_eq!(err.await, Err(JobError::Failed("boom".to_string())));
+        assert_eq!(env.await, Ok::<_, JobError<String>>(10));
+    }
+
+    #[tokio::test]
+    async fn test_hook_receives_lifecycle_events() {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&events);
+        let executor = PriorityExecutor::new((), 1)
+            .with_hook(move |event| sink.lock().unwrap().push(event.clone()));
+
+        let handle = executor.submit(7, fail::<(), _, ()>("nope".to_string()));
+        let id = handle.id();
+        assert!(handle.await.is_err());
+
+        let events = events.lock().unwrap();
+        assert_eq!(
+            events[0],
+            QueueEvent::Submitted {
+                id,
+                priority: 7,
+                queued: 1
+            }
+        );
+        assert!(matches!(events[1], QueueEvent::Started { priority: 7, .. }));
+        assert!(matches!(
+            events[2],
+            QueueEvent::Completed {
+                succeeded: false,
+                ..
+            }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_panicking_job_is_cancelled_and_frees_slot() {
+        let executor = PriorityExecutor::new((), 1);
+
+        let panicked = executor.submit(
+            0,
+            from_async(|_: &()| async {
+                panic!("job panicked");
+                #[allow(unreachable_code)]
+                Ok::<(), String>(())
+            }),
+        );
+        let next = executor.submit(0, pure::<_, String, ()>(42));
+
+        assert_eq!(panicked.await, Err(JobError::Cancelled));
+        assert_eq!(next.await, Ok(42));
+    }
+}
@@ -6,6 +6,8 @@
 //! - `Ask` - Get the entire environment (cloned)
 //! - `Asks` - Query a value from the environment
 //! - `Local` - Run an effect with a modified environment
+//! - `MapEnvFor` - Run the rest of a pipeline in an environment derived
+//!   from the outer one and an earlier result
 
 use std::marker::PhantomData;
 
@@ -30,6 +32,12 @@ pub struct Ask<E, Env> {
     _phantom: PhantomData<(E, Env)>,
 }
 
+impl<E, Env> Clone for Ask<E, Env> {
+    fn clone(&self) -> Self {
+        Self::new()
+    }
+}
+
 impl<E, Env> std::fmt::Debug for Ask<E, Env> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Ask").finish()
@@ -87,6 +95,12 @@ pub struct Asks<F, E, Env> {
     _phantom: PhantomData<(E, Env)>,
 }
 
+impl<F: Clone, E, Env> Clone for Asks<F, E, Env> {
+    fn clone(&self) -> Self {
+        Asks::new(self.f.clone())
+    }
+}
+
 impl<F, E, Env> std::fmt::Debug for Asks<F, E, Env> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Asks").field("f", &"<function>").finish()
@@ -108,7 +122,7 @@ where
     F: FnOnce(&Env) -> U + Send,
     U: Send,
     E: Send,
-    Env: Clone + Send + Sync,
+    Env: Send + Sync,
 {
     type Output = U;
     type Error = E;
@@ -148,6 +162,16 @@ pub struct Local<Inner, F, Env2> {
     pub(crate) _phantom: PhantomData<Env2>,
 }
 
+impl<Inner: Clone, F: Clone, Env2> Clone for Local<Inner, F, Env2> {
+    fn clone(&self) -> Self {
+        Local {
+            inner: self.inner.clone(),
+            f: self.f.clone(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
 impl<Inner, F, Env2> std::fmt::Debug for Local<Inner, F, Env2> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Local")
@@ -172,7 +196,7 @@ impl<Inner, F, Env2> Effect for Local<Inner, F, Env2>
 where
     Inner: Effect,
     F: FnOnce(&Env2) -> Inner::Env + Send,
-    Env2: Clone + Send + Sync,
+    Env2: Send + Sync,
 {
     type Output = Inner::Output;
     type Error = Inner::Error;
@@ -186,3 +210,46 @@ where
         async move { self.inner.run(&inner_env).await }
     }
 }
+
+/// Run a follow-up effect in an environment derived mid-pipeline.
+///
+/// Zero-cost: no heap allocation. After the inner effect succeeds, the
+/// derived environment is built once from the outer environment and the
+/// inner output, and the follow-up effect runs with a reference to it.
+/// The derived environment can be a different type and does not need to
+/// be `Clone`.
+///
+/// Created by [`EffectExt::map_env_for`](crate::effect::EffectExt::map_env_for).
+pub struct MapEnvFor<Inner, D, F> {
+    pub(crate) inner: Inner,
+    pub(crate) derive: D,
+    pub(crate) f: F,
+}
+
+impl<Inner, D, F> std::fmt::Debug for MapEnvFor<Inner, D, F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MapEnvFor")
+            .field("inner", &"<effect>")
+            .field("derive", &"<function>")
+            .field("f", &"<function>")
+            .finish()
+    }
+}
+
+impl<Inner, D, F, SubEff> Effect for MapEnvFor<Inner, D, F>
+where
+    Inner: Effect,
+    D: FnOnce(&Inner::Env, &Inner::Output) -> SubEff::Env + Send,
+    F: FnOnce(Inner::Output) -> SubEff + Send,
+    SubEff: Effect<Error = Inner::Error>,
+{
+    type Output = SubEff::Output;
+    type Error = Inner::Error;
+    type Env = Inner::Env;
+
+    async fn run(self, env: &Self::Env) -> Result<Self::Output, Self::Error> {
+        let value = self.inner.run(env).await?;
+        let sub_env = (self.derive)(env, &value);
+        (self.f)(value).run(&sub_env).await
+    }
+}
@@ -0,0 +1,104 @@
+//! The `recover_matching!` macro.
+
+/// Recover from the errors matching a pattern with a fallback value.
+///
+/// Each arm pairs a pattern, with an optional guard, with the value to
+/// succeed with when the error matches. Errors matching no arm propagate
+/// unchanged. The patterns may bind parts of the error for use in the
+/// value, and the error type need not be `Clone`.
+///
+/// This replaces a `matches!` closure with
+/// [`recover_with`](crate::effect::EffectExt::recover_with) for the common
+/// case of recovering with a value; to recover with another effect, use
+/// [`recover_some`](crate::effect::EffectExt::recover_some) or
+/// [`recover`](crate::effect::EffectExt::recover).
+///
+/// # Example
+///
+/// ```rust
+/// use stillwater::effect::prelude::*;
+/// use stillwater::recover_matching;
+///
+/// #[derive(Debug, PartialEq)]
+/// enum FetchError {
+///     NotFound,
+///     Gone,
+///     RateLimited { retry_after: u32 },
+///     Unavailable,
+/// }
+///
+/// fn fetch(error: FetchError) -> impl Effect<Output = u32, Error = FetchError, Env = ()> {
+///     fail(error)
+/// }
+///
+/// # tokio_test::block_on(async {
+/// let count = recover_matching!(fetch(FetchError::Gone),
+///     FetchError::NotFound | FetchError::Gone => 0,
+/// );
+/// assert_eq!(count.run(&()).await, Ok(0));
+///
+/// let waited = recover_matching!(fetch(FetchError::RateLimited { retry_after: 30 }),
+///     FetchError::NotFound => 0,
+///     FetchError::RateLimited { retry_after } if retry_after < 60 => retry_after,
+/// );
+/// assert_eq!(waited.run(&()).await, Ok(30));
+///
+/// let down = recover_matching!(fetch(FetchError::Unavailable), FetchError::NotFound => 0);
+/// assert_eq!(down.run(&()).await, Err(FetchError::Unavailable));
+/// # });
+/// ```
+#[macro_export]
+macro_rules! recover_matching {
+    ($effect:expr, $($pattern:pat $(if $guard:expr)? => $value:expr),+ $(,)?) => {
+        $crate::effect::EffectExt::or_else($effect, move |error| {
+            $crate::effect::from_result(match error {
+                $($pattern $(if $guard)? => ::std::result::Result::Ok($value),)+
+                error => ::std::result::Result::Err(error),
+            })
+        })
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::effect::prelude::*;
+
+    #[derive(Debug, PartialEq)]
+    enum Error {
+        Missing(String),
+        Expired(u32),
+        Fatal,
+    }
+
+    #[tokio::test]
+    async fn test_bindings_reach_the_fallback_value() {
+        let effect = recover_matching!(
+            fail::<String, _, ()>(Error::Missing("config".to_string())),
+            Error::Missing(name) => format!("default {}", name),
+        );
+
+        assert_eq!(effect.run(&()).await, Ok("default config".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_failed_guard_and_unmatched_errors_propagate() {
+        let stale = recover_matching!(
+            fail::<u32, _, ()>(Error::Expired(90)),
+            Error::Expired(age) if age < 60 => age,
+        );
+        assert_eq!(stale.run(&()).await, Err(Error::Expired(90)));
+
+        let fatal = recover_matching!(
+            fail::<u32, _, ()>(Error::Fatal),
+            Error::Missing(_) | Error::Expired(_) => 0,
+        );
+        assert_eq!(fatal.run(&()).await, Err(Error::Fatal));
+    }
+
+    #[tokio::test]
+    async fn test_success_passes_through() {
+        let effect = recover_matching!(pure::<_, Error, ()>(7), Error::Fatal => 0);
+
+        assert_eq!(effect.run(&()).await, Ok(7));
+    }
+}
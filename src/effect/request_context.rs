@@ -0,0 +1,371 @@
+//! Request-scoped context for correlating effects.
+//!
+//! A [`RequestContext`] carries a request id, an optional correlation id,
+//! and free-form baggage. Attaching it to an effect with
+//! [`RequestContextExt::with_request_context`] (or the
+//! [`with_request_id`](RequestContextExt::with_request_id) shorthand) makes
+//! it current while that effect and everything it runs are being polled, so
+//! deeply nested effects can call [`current_request_id`] without threading
+//! the id through their environment.
+//!
+//! The current request id is picked up automatically by
+//! [`ContextError`](crate::ContextError), and with the `tracing` feature
+//! the effect runs inside a `request` span recording the ids.
+//!
+//! A spawned task does not inherit the context on its own. The library's
+//! own spawn points, such as
+//! [`par_all_joinset`](crate::effect::parallel::par_all_joinset), the
+//! [`PriorityExecutor`](crate::effect::queue::PriorityExecutor) and
+//! [`provide`](crate::effect::EffectExt::provide), carry the context and,
+//! with the `tracing` feature, the current span into the task. Wrap futures
+//! spawned by hand with [`propagate`].
+//!
+//! # Example
+//!
+//! ```rust
+//! use stillwater::effect::prelude::*;
+//! use stillwater::effect::request_context::{current_request_id, RequestContextExt};
+//!
+//! # tokio_test::block_on(async {
+//! fn load_user() -> impl Effect<Output = String, Error = String, Env = ()> {
+//!     from_fn(|_: &()| {
+//!         let id = current_request_id().unwrap_or_default();
+//!         Ok(format!("user loaded for {}", id))
+//!     })
+//! }
+//!
+//! let handler = load_user().with_request_id("req-42");
+//! assert_eq!(handler.run(&()).await, Ok("user loaded for req-42".to_string()));
+//! assert_eq!(current_request_id(), None);
+//! # });
+//! ```
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use crate::effect::trait_def::Effect;
+
+thread_local! {
+    static CURRENT: RefCell<Option<Arc<RequestContext>>> = const { RefCell::new(None) };
+}
+
+/// Identifiers and baggage describing the request an effect serves.
+///
+/// # Example
+///
+/// ```rust
+/// use stillwater::effect::request_context::RequestContext;
+///
+/// let ctx = RequestContext::new("req-1")
+///     .with_correlation_id("trace-abc")
+///     .with_baggage("tenant", "acme");
+///
+/// assert_eq!(ctx.request_id(), "req-1");
+/// assert_eq!(ctx.correlation_id(), Some("trace-abc"));
+/// assert_eq!(ctx.baggage("tenant"), Some("acme"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RequestContext {
+    request_id: String,
+    correlation_id: Option<String>,
+    baggage: BTreeMap<String, String>,
+}
+
+impl RequestContext {
+    /// Create a context for the request with the given id.
+    pub fn new(request_id: impl Into<String>) -> Self {
+        RequestContext {
+            request_id: request_id.into(),
+            correlation_id: None,
+            baggage: BTreeMap::new(),
+        }
+    }
+
+    /// Set the correlation id shared by related requests.
+    pub fn with_correlation_id(mut self, correlation_id: impl Into<String>) -> Self {
+        self.correlation_id = Some(correlation_id.into());
+        self
+    }
+
+    /// Add a baggage entry, replacing any previous value for `key`.
+    pub fn with_baggage(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.baggage.insert(key.into(), value.into());
+        self
+    }
+
+    /// The request id.
+    pub fn request_id(&self) -> &str {
+        &self.request_id
+    }
+
+    /// The correlation id, if set.
+    pub fn correlation_id(&self) -> Option<&str> {
+        self.correlation_id.as_deref()
+    }
+
+    /// The baggage value for `key`, if set.
+    pub fn baggage(&self, key: &str) -> Option<&str> {
+        self.baggage.get(key).map(String::as_str)
+    }
+
+    /// All baggage entries, ordered by key.
+    pub fn baggage_entries(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.baggage.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+}
+
+/// The request context of the effect currently being polled, if any.
+pub fn current() -> Option<Arc<RequestContext>> {
+    CURRENT.with(|current| current.borrow().clone())
+}
+
+/// The request id of the effect currently being polled, if any.
+pub fn current_request_id() -> Option<String> {
+    CURRENT.with(|current| current.borrow().as_ref().map(|ctx| ctx.request_id.clone()))
+}
+
+/// Carry the current request context and tracing span into `future`.
+///
+/// Both are captured when `propagate` is called, not when the future is
+/// first polled, so call it at the spawn site. The returned future makes
+/// the context current and, with the `tracing` feature, enters the span
+/// on every poll, wherever it runs.
+///
+/// # Example
+///
+/// ```rust
+/// use stillwater::effect::request_context::{current_request_id, propagate};
+/// use stillwater::effect::prelude::*;
+///
+/// # tokio_test::block_on(async {
+/// let handler = from_async(|_: &()| async {
+///     let task = tokio::spawn(propagate(async { current_request_id() }));
+///     Ok::<_, String>(task.await.unwrap())
+/// })
+/// .with_request_id("req-42");
+///
+/// assert_eq!(handler.run(&()).await, Ok(Some("req-42".to_string())));
+/// # });
+/// ```
+pub fn propagate<F: Future>(future: F) -> impl Future<Output = F::Output> {
+    Propagation::capture().scope(future)
+}
+
+/// The request context and tracing span to re-enter in a spawned task.
+pub(crate) struct Propagation {
+    context: Option<Arc<RequestContext>>,
+    #[cfg(feature = "tracing")]
+    span: tracing::Span,
+}
+
+impl Propagation {
+    /// Capture the context and span current on this thread.
+    pub(crate) fn capture() -> Self {
+        Propagation {
+            context: current(),
+            #[cfg(feature = "tracing")]
+            span: tracing::Span::current(),
+        }
+    }
+
+    /// Run `future` inside the captured context and span.
+    pub(crate) fn scope<F: Future>(self, future: F) -> impl Future<Output = F::Output> {
+        let context = self.context;
+        let scoped = async move {
+            let future = std::pin::pin!(future);
+            match context {
+                Some(context) => Scoped { context, future }.await,
+                None => future.await,
+            }
+        };
+
+        #[cfg(feature = "tracing")]
+        let scoped = tracing::Instrument::instrument(scoped, self.span);
+
+        scoped
+    }
+}
+
+/// An effect that runs with a [`RequestContext`] attached.
+///
+/// Created by [`RequestContextExt::with_request_context`].
+pub struct WithRequestContext<Eff> {
+    pub(crate) inner: Eff,
+    pub(crate) context: Arc<RequestContext>,
+}
+
+impl<Eff> fmt::Debug for WithRequestContext<Eff> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WithRequestContext")
+            .field("inner", &"<effect>")
+            .field("context", &self.context)
+            .finish()
+    }
+}
+
+impl<Eff: Effect> Effect for WithRequestContext<Eff> {
+    type Output = Eff::Output;
+    type Error = Eff::Error;
+    type Env = Eff::Env;
+
+    async fn run(self, env: &Self::Env) -> Result<Self::Output, Self::Error> {
+        #[cfg(feature = "tracing")]
+        let span = tracing::info_span!(
+            "request",
+            request_id = %self.context.request_id,
+            correlation_id = self.context.correlation_id.as_deref(),
+        );
+
+        let future = std::pin::pin!(self.inner.run(env));
+        let scoped = Scoped {
+            context: self.context,
+            future,
+        };
+
+        #[cfg(feature = "tracing")]
+        let scoped = tracing::Instrument::instrument(scoped, span);
+
+        scoped.await
+    }
+}
+
+/// Makes `context` current for the duration of each poll of `future`.
+struct Scoped<'a, F> {
+    context: Arc<RequestContext>,
+    future: Pin<&'a mut F>,
+}
+
+impl<F: Future> Future for Scoped<'_, F> {
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<F::Output> {
+        let this = &mut *self;
+        let previous = CURRENT.with(|current| current.replace(Some(Arc::clone(&this.context))));
+        let _restore = Restore(previous);
+        this.future.as_mut().poll(cx)
+    }
+}
+
+/// Restores the previous context on drop, including when a poll panics.
+struct Restore(Option<Arc<RequestContext>>);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        let previous = self.0.take();
+        CURRENT.with(|current| *current.borrow_mut() = previous);
+    }
+}
+
+/// Extension trait for attaching a [`RequestContext`] to effects.
+pub trait RequestContextExt: Effect {
+    /// Run this effect with `context` as the current request context.
+    ///
+    /// An inner `with_request_context` replaces the outer one while the
+    /// inner effect runs.
+    fn with_request_context(self, context: RequestContext) -> WithRequestContext<Self> {
+        WithRequestContext {
+            inner: self,
+            context: Arc::new(context),
+        }
+    }
+
+    /// Run this effect with a fresh [`RequestContext`] for `request_id`.
+    fn with_request_id(self, request_id: impl Into<String>) -> WithRequestContext<Self> {
+        self.with_request_context(RequestContext::new(request_id))
+    }
+}
+
+impl<E: Effect> RequestContextExt for E {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::effect::constructors::{fail, from_async, from_fn};
+    use crate::effect::context::EffectContext;
+    use crate::effect::ext::EffectExt;
+
+    #[tokio::test]
+    async fn test_context_survives_await_points() {
+        let effect = from_async(|_: &()| async {
+            tokio::task::yield_now().await;
+            Ok::<_, String>(current().map(|ctx| ctx.baggage("tenant").map(String::from)))
+        })
+        .with_request_context(RequestContext::new("r").with_baggage("tenant", "acme"));
+
+        assert_eq!(effect.run(&()).await, Ok(Some(Some("acme".to_string()))));
+        assert!(current().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_inner_context_overrides_outer() {
+        let effect = from_fn(|_: &()| Ok::<_, String>(current_request_id()))
+            .with_request_id("inner")
+            .and_then(|inner| from_fn(move |_: &()| Ok((inner.clone(), current_request_id()))))
+            .with_request_id("outer");
+
+        assert_eq!(
+            effect.run(&()).await,
+            Ok((Some("inner".to_string()), Some("outer".to_string())))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_context_error_records_request_id() {
+        let effect = fail::<(), _, ()>("timeout")
+            .context("fetching profile")
+            .with_request_id("req-7");
+
+        let err = effect.run(&()).await.unwrap_err();
+        assert_eq!(err.request_id(), Some("req-7"));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_propagate_carries_context_into_spawned_task() {
+        let effect = from_async(|_: &()| async {
+            let task = tokio::spawn(propagate(async {
+                tokio::task::yield_now().await;
+                current().map(|ctx| ctx.baggage("tenant").map(String::from))
+            }));
+            Ok::<_, String>(task.await.unwrap())
+        })
+        .with_request_context(RequestContext::new("r").with_baggage("tenant", "acme"));
+
+        assert_eq!(effect.run(&()).await, Ok(Some(Some("acme".to_string()))));
+    }
+
+    #[tokio::test]
+    async fn test_propagate_captures_at_call_not_first_poll() {
+        let future = from_fn(|_: &()| Ok::<_, String>(propagate(async { current_request_id() })))
+            .with_request_id("spawner")
+            .run(&())
+            .await
+            .unwrap();
+
+        assert_eq!(future.await, Some("spawner".to_string()));
+        assert!(current().is_none());
+    }
+
+    #[cfg(feature = "tracing")]
+    #[tokio::test]
+    #[tracing_test::traced_test]
+    async fn test_spawned_task_stays_in_request_span() {
+        let effect = from_async(|_: &()| async {
+            let task = tokio::spawn(propagate(async {
+                tracing::info!("inside spawned task");
+            }));
+            task.await.unwrap();
+            Ok::<_, String>(())
+        })
+        .with_request_id("req-9");
+
+        effect.run(&()).await.unwrap();
+        assert!(logs_contain("request{request_id=req-9}"));
+        assert!(logs_contain("inside spawned task"));
+    }
+}
@@ -0,0 +1,104 @@
+//! Running the same effect value more than once.
+//!
+//! [`Effect::run`] consumes the effect, so code that runs an effect
+//! repeatedly, such as a health check or a poller, usually takes a factory
+//! closure that builds a fresh effect each time. An effect built only from
+//! `Clone` parts, such as `Clone` closures and values, can be cloned
+//! instead, and [`ReRunnable::run_ref`] does that for every run, leaving
+//! the original in place.
+//!
+//! The built-in constructors and the main combinators (`map`, `map_err`,
+//! `and_then`, `or_else`, `tap`, `zip`, and the reader effects) are `Clone`
+//! when everything they hold is. Boxed effects are not.
+//!
+//! # Example
+//!
+//! ```rust
+//! use std::sync::atomic::{AtomicUsize, Ordering};
+//! use std::sync::Arc;
+//!
+//! use stillwater::effect::prelude::*;
+//!
+//! struct Service {
+//!     healthy: bool,
+//! }
+//!
+//! # tokio_test::block_on(async {
+//! let checks = Arc::new(AtomicUsize::new(0));
+//! let counter = checks.clone();
+//! let health_check = from_fn(move |service: &Service| {
+//!     counter.fetch_add(1, Ordering::SeqCst);
+//!     if service.healthy {
+//!         Ok(())
+//!     } else {
+//!         Err("unhealthy".to_string())
+//!     }
+//! })
+//! .map(|()| "ok");
+//!
+//! let service = Service { healthy: true };
+//! for _ in 0..3 {
+//!     assert_eq!(health_check.run_ref(&service).await, Ok("ok"));
+//! }
+//! assert_eq!(checks.load(Ordering::SeqCst), 3);
+//! # });
+//! ```
+
+use std::future::Future;
+
+use crate::effect::trait_def::Effect;
+
+/// An effect that can be run any number of times by reference.
+///
+/// Implemented for every effect that is `Clone`.
+pub trait ReRunnable: Effect + Clone {
+    /// Run a clone of this effect, keeping `self` for later runs.
+    fn run_ref(
+        &self,
+        env: &Self::Env,
+    ) -> impl Future<Output = Result<Self::Output, Self::Error>> + Send {
+        self.clone().run(env)
+    }
+}
+
+impl<E: Effect + Clone> ReRunnable for E {}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::effect::constructors::{asks, fail, from_fn, pure};
+    use crate::effect::ext::EffectExt;
+
+    #[tokio::test]
+    async fn test_each_run_starts_from_the_original() {
+        let polls = Arc::new(AtomicU32::new(0));
+        let counter = polls.clone();
+        let poll = from_fn(move |_: &()| Ok::<_, String>(counter.fetch_add(1, Ordering::SeqCst)))
+            .map(|n| n * 10);
+
+        assert_eq!(poll.run_ref(&()).await, Ok(0));
+        assert_eq!(poll.run_ref(&()).await, Ok(10));
+        assert_eq!(poll.run(&()).await, Ok(20));
+    }
+
+    #[tokio::test]
+    async fn test_composed_effects_rerun_against_new_environments() {
+        let effect = asks(|limit: &u32| *limit)
+            .and_then(|limit| {
+                if limit > 5 {
+                    fail("too high".to_string())
+                } else {
+                    fail(format!("limit {}", limit))
+                }
+            })
+            .or_else(|error| pure::<_, String, u32>(error.len()))
+            .zip(pure(true))
+            .map_err(|e: String| e.to_uppercase());
+
+        assert_eq!(effect.run_ref(&3).await, Ok((7, true)));
+        assert_eq!(effect.run_ref(&9).await, Ok((8, true)));
+    }
+}
@@ -15,6 +15,7 @@ use std::marker::PhantomData;
 use super::markers::ResourceKind;
 use super::sets::{Empty, Has};
 use super::tracked::{ResourceEffect, Tracked};
+use crate::effect::bracket::ReleaseGuard;
 use crate::effect::trait_def::Effect;
 
 /// Resource-safe bracket with compile-time tracking.
@@ -95,11 +96,11 @@ where
     Use: FnOnce(&T) -> UseEff + Send,
     UseEff: Effect<Output = U, Error = E, Env = Env>,
     Rel: FnOnce(T) -> RelFut + Send,
-    RelFut: Future<Output = Result<(), E>> + Send,
+    RelFut: Future<Output = Result<(), E>> + Send + 'static,
     T: Send,
     U: Send,
-    E: Send + std::fmt::Debug,
-    Env: Clone + Send + Sync,
+    E: Send + std::fmt::Debug + 'static,
+    Env: Send + Sync,
 {
     type Output = U;
     type Error = E;
@@ -109,11 +110,14 @@ where
         // Acquire the resource
         let resource = self.acquire.run(env).await?;
 
+        // The guard releases the resource if this future is dropped early
+        let guard = ReleaseGuard::new(resource, self.release);
+
         // Use the resource (borrowing for use, moving for release)
-        let result = (self.use_fn)(&resource).run(env).await;
+        let result = (self.use_fn)(guard.resource()).run(env).await;
 
         // Release runs regardless of use result
-        let release_result = (self.release)(resource).await;
+        let release_result = guard.release().await;
 
         // Log cleanup errors if any
         if let Err(ref rel_err) = release_result {
@@ -136,11 +140,11 @@ where
     Use: FnOnce(&T) -> UseEff + Send,
     UseEff: Effect<Output = U, Error = E, Env = Env>,
     Rel: FnOnce(T) -> RelFut + Send,
-    RelFut: Future<Output = Result<(), E>> + Send,
+    RelFut: Future<Output = Result<(), E>> + Send + 'static,
     T: Send,
     U: Send,
-    E: Send + std::fmt::Debug,
-    Env: Clone + Send + Sync,
+    E: Send + std::fmt::Debug + 'static,
+    Env: Send + Sync,
 {
     type Acquires = Empty;
     type Releases = Empty;
@@ -190,11 +194,11 @@ where
     Use: FnOnce(&T) -> UseEff + Send,
     UseEff: Effect<Output = U, Error = E, Env = Env>,
     Rel: FnOnce(T) -> RelFut + Send,
-    RelFut: Future<Output = Result<(), E>> + Send,
+    RelFut: Future<Output = Result<(), E>> + Send + 'static,
     T: Send,
     U: Send,
-    E: Send + std::fmt::Debug,
-    Env: Clone + Send + Sync,
+    E: Send + std::fmt::Debug + 'static,
+    Env: Send + Sync,
 {
     ResourceBracket {
         acquire,
@@ -239,11 +243,11 @@ where
     Use: FnOnce(&T) -> UseEff + Send,
     UseEff: ResourceEffect<Output = U, Error = E, Env = Env, Acquires = Empty, Releases = Empty>,
     Rel: FnOnce(T) -> RelFut + Send,
-    RelFut: Future<Output = Result<(), E>> + Send,
+    RelFut: Future<Output = Result<(), E>> + Send + 'static,
     T: Send,
     U: Send,
-    E: Send + std::fmt::Debug,
-    Env: Clone + Send + Sync,
+    E: Send + std::fmt::Debug + 'static,
+    Env: Send + Sync,
 {
     Tracked::new(ResourceBracket {
         acquire,
@@ -23,7 +23,7 @@ impl<T, E, Env> ResourceEffect for Pure<T, E, Env>
 where
     T: Send,
     E: Send,
-    Env: Clone + Send + Sync,
+    Env: Send + Sync,
 {
     type Acquires = Empty;
     type Releases = Empty;
@@ -37,7 +37,7 @@ impl<T, E, Env> ResourceEffect for Fail<T, E, Env>
 where
     T: Send,
     E: Send,
-    Env: Clone + Send + Sync,
+    Env: Send + Sync,
 {
     type Acquires = Empty;
     type Releases = Empty;
@@ -2,13 +2,198 @@
 //!
 //! This module provides retry combinators that integrate with the existing
 //! `crate::retry::{RetryPolicy, RetryEvent, RetryExhausted, TimeoutError}` types.
+//!
+//! # Tracing
+//!
+//! # Attempt timeouts
+//!
+//! Every retry function here applies [`RetryPolicy::attempt_timeout`]: an
+//! attempt still running when the timeout elapses is dropped and counts as a
+//! failed attempt, so a hung call is retried like any other failure. Errors
+//! are reported as [`TimeoutError`] to tell the two kinds of failure apart:
+//! `Timeout` when an attempt hung, `Inner` when it failed on its own.
+//!
+//! With the `tracing` feature, [`retry`], [`retry_if`], [`retry_classified`],
+//! [`retry_persistent`], and [`retry_with_hooks`] run inside a `retry` span with
+//! target `stillwater::retry`. The span records `policy` (see
+//! [`RetryPolicy::with_name`]) and `max_retries` up front, and `attempts` and
+//! `outcome` (`success`, `exhausted`, or `not_retryable`) when the loop ends.
+//!
+//! Every failed attempt emits an event with the same fields: `policy`,
+//! `attempt` (1-indexed), `elapsed_ms`, and `error_type`. Attempts that will
+//! be retried are logged at `WARN` with `delay_ms`; the final failure is
+//! logged at `ERROR` as "retries exhausted". The error itself is not
+//! formatted, since these functions do not require `Display`; use
+//! [`retry_with_hooks`] to log the error text.
 
-use std::time::{Duration, Instant};
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
 
 use crate::effect::boxed::BoxedEffect;
 use crate::effect::ext::EffectExt;
 use crate::effect::trait_def::Effect;
-use crate::retry::{RetryEvent, RetryExhausted, RetryPolicy, TimeoutError};
+#[cfg(feature = "prometheus")]
+use crate::integrations::prometheus as metrics;
+use crate::retry::{
+    ErrorClass, PersistentRetryState, RetryEvent, RetryExhausted, RetryPolicy, RetryState,
+    RetrySuccess, TimeoutError,
+};
+
+/// The span a retry loop runs in; `attempts` and `outcome` are recorded
+/// when the loop ends.
+#[cfg(all(feature = "tracing", feature = "async"))]
+fn retry_span(policy: &RetryPolicy) -> tracing::Span {
+    tracing::info_span!(
+        target: "stillwater::retry",
+        "retry",
+        policy = policy.name(),
+        max_retries = policy.max_retries(),
+        attempts = tracing::field::Empty,
+        outcome = tracing::field::Empty,
+    )
+}
+
+/// Emit the standard event for a failed attempt.
+///
+/// `delay` is `None` when the policy gives up.
+#[cfg(all(feature = "tracing", feature = "async"))]
+fn trace_failure<E>(
+    policy: &RetryPolicy,
+    attempt: u32,
+    delay: Option<Duration>,
+    elapsed: Duration,
+) {
+    let error_type = std::any::type_name::<E>();
+    match delay {
+        Some(delay) => tracing::warn!(
+            target: "stillwater::retry",
+            policy = policy.name(),
+            attempt,
+            delay_ms = delay.as_millis() as u64,
+            elapsed_ms = elapsed.as_millis() as u64,
+            error_type,
+            "attempt failed, retrying"
+        ),
+        None => tracing::error!(
+            target: "stillwater::retry",
+            policy = policy.name(),
+            attempt,
+            elapsed_ms = elapsed.as_millis() as u64,
+            error_type,
+            "retries exhausted"
+        ),
+    }
+}
+
+/// Record how the retry loop ended on the current span.
+#[cfg(all(feature = "tracing", feature = "async"))]
+fn trace_outcome(attempts: u32, outcome: &'static str) {
+    let span = tracing::Span::current();
+    span.record("attempts", attempts);
+    span.record("outcome", outcome);
+}
+
+/// Run one attempt, giving up once the policy's attempt timeout elapses.
+///
+/// Returns the timeout as the error if the attempt did not finish in time.
+#[cfg(feature = "async")]
+pub(crate) async fn bounded_attempt<Fut: Future>(
+    policy: &RetryPolicy,
+    attempt: Fut,
+) -> Result<Fut::Output, Duration> {
+    match policy.attempt_timeout() {
+        Some(duration) => tokio::time::timeout(duration, attempt)
+            .await
+            .map_err(|_| duration),
+        None => Ok(attempt.await),
+    }
+}
+
+/// Run one fallible attempt, bounded by the policy's attempt timeout.
+#[cfg(feature = "async")]
+pub(crate) async fn timed_attempt<T, E, Fut>(
+    policy: &RetryPolicy,
+    attempt: Fut,
+) -> Result<T, TimeoutError<E>>
+where
+    Fut: Future<Output = Result<T, E>>,
+{
+    match bounded_attempt(policy, attempt).await {
+        Ok(result) => result.map_err(TimeoutError::Inner),
+        Err(duration) => Err(TimeoutError::Timeout { duration }),
+    }
+}
+
+/// The retry loop shared by [`retry`] and the writer and sink retries.
+///
+/// `state` is threaded through the attempts, so each attempt can add to
+/// what the earlier ones produced, such as the writes of a writer effect.
+/// It is returned with the outcome.
+#[cfg(feature = "async")]
+pub(crate) async fn retry_attempts<T, E, S, F, Fut>(
+    policy: &RetryPolicy,
+    state: S,
+    mut attempt_fn: F,
+) -> (Result<RetrySuccess<T>, RetryExhausted<E>>, S)
+where
+    F: FnMut(S) -> Fut,
+    Fut: Future<Output = (Result<T, E>, S)>,
+{
+    let run = async move {
+        let start = Instant::now();
+        let mut state = state;
+        let mut attempt = 0u32;
+        let mut prev_delay: Option<Duration> = None;
+
+        loop {
+            let (result, next) = attempt_fn(state).await;
+            state = next;
+            match result {
+                Ok(value) => {
+                    #[cfg(feature = "tracing")]
+                    trace_outcome(attempt + 1, "success");
+                    #[cfg(feature = "prometheus")]
+                    metrics::retry_outcome(policy, attempt + 1, "success", start.elapsed());
+                    let success = RetrySuccess::new(value, attempt + 1, start.elapsed());
+                    return (Ok(success), state);
+                }
+                Err(error) => {
+                    let delay = policy.delay_with_jitter(attempt, prev_delay);
+                    #[cfg(feature = "tracing")]
+                    trace_failure::<E>(policy, attempt + 1, delay, start.elapsed());
+                    #[cfg(feature = "prometheus")]
+                    metrics::retry_failure(policy);
+
+                    match delay {
+                        Some(d) => {
+                            tokio::time::sleep(d).await;
+                            prev_delay = Some(d);
+                            attempt += 1;
+                        }
+                        None => {
+                            #[cfg(feature = "tracing")]
+                            trace_outcome(attempt + 1, "exhausted");
+                            #[cfg(feature = "prometheus")]
+                            metrics::retry_outcome(
+                                policy,
+                                attempt + 1,
+                                "exhausted",
+                                start.elapsed(),
+                            );
+                            let exhausted =
+                                RetryExhausted::new(error, attempt + 1, start.elapsed());
+                            return (Err(exhausted), state);
+                        }
+                    }
+                }
+            }
+        }
+    };
+    #[cfg(feature = "tracing")]
+    let run = tracing::Instrument::instrument(run, retry_span(policy));
+    run.await
+}
 
 /// Retry an effect using a factory function.
 ///
@@ -27,6 +212,11 @@ use crate::retry::{RetryEvent, RetryExhausted, RetryPolicy, TimeoutError};
 /// Returns a `BoxedEffect` because the retry loop creates dynamic control flow
 /// that cannot be represented as a zero-cost combinator type.
 ///
+/// Each attempt is bounded by the policy's
+/// [attempt timeout](RetryPolicy::with_attempt_timeout), if it has one.
+/// The final error is a [`TimeoutError`]: `Timeout` when the last attempt
+/// hung, `Inner` when it failed on its own.
+///
 /// # Example
 ///
 /// ```rust,ignore
@@ -43,54 +233,80 @@ use crate::retry::{RetryEvent, RetryExhausted, RetryPolicy, TimeoutError};
 /// let result = effect.execute(&()).await.unwrap();
 /// assert_eq!(result.into_value(), 42);
 /// ```
+///
+/// A hung attempt is retried:
+///
+/// ```rust
+/// use stillwater::effect::prelude::*;
+/// use stillwater::effect::retry::retry;
+/// use stillwater::RetryPolicy;
+/// use std::time::Duration;
+///
+/// # tokio_test::block_on(async {
+/// let effect = retry(
+///     || from_async(|_: &()| async {
+///         tokio::time::sleep(Duration::from_secs(10)).await;
+///         Ok::<_, String>(42)
+///     }),
+///     RetryPolicy::constant(Duration::from_millis(1))
+///         .with_max_retries(2)
+///         .with_attempt_timeout(Duration::from_millis(10)),
+/// );
+///
+/// let exhausted = effect.execute(&()).await.unwrap_err();
+/// assert_eq!(exhausted.attempts, 3);
+/// assert!(exhausted.final_error.is_timeout());
+/// # });
+/// ```
 #[cfg(feature = "async")]
 pub fn retry<T, E, Env, F, Eff>(
     make_effect: F,
     policy: RetryPolicy,
-) -> BoxedEffect<RetryExhausted<T>, RetryExhausted<E>, Env>
+) -> BoxedEffect<RetrySuccess<T>, RetryExhausted<TimeoutError<E>>, Env>
 where
     T: Send + 'static,
     E: Send + 'static,
-    Env: Clone + Send + Sync + 'static,
+    Env: Send + Sync + 'static,
     F: Fn() -> Eff + Send + 'static,
     Eff: Effect<Output = T, Error = E, Env = Env> + 'static,
 {
-    crate::effect::constructors::from_async(move |env: &Env| {
-        let env = env.clone();
-        async move {
-            let start = Instant::now();
-            let mut attempt = 0u32;
-            let mut prev_delay: Option<Duration> = None;
+    Retry {
+        make_effect,
+        policy,
+    }
+    .boxed()
+}
 
-            loop {
-                let effect = make_effect();
-                match effect.run(&env).await {
-                    Ok(value) => {
-                        return Ok(RetryExhausted::new(value, attempt + 1, start.elapsed()));
-                    }
-                    Err(error) => {
-                        let delay = policy.delay_with_jitter(attempt, prev_delay);
+/// The effect built by [`retry`].
+#[cfg(feature = "async")]
+struct Retry<F> {
+    make_effect: F,
+    policy: RetryPolicy,
+}
 
-                        match delay {
-                            Some(d) => {
-                                tokio::time::sleep(d).await;
-                                prev_delay = Some(d);
-                                attempt += 1;
-                            }
-                            None => {
-                                return Err(RetryExhausted::new(
-                                    error,
-                                    attempt + 1,
-                                    start.elapsed(),
-                                ));
-                            }
-                        }
-                    }
-                }
-            }
-        }
-    })
-    .boxed()
+#[cfg(feature = "async")]
+impl<F, Eff> Effect for Retry<F>
+where
+    F: Fn() -> Eff + Send,
+    Eff: Effect,
+{
+    type Output = RetrySuccess<Eff::Output>;
+    type Error = RetryExhausted<TimeoutError<Eff::Error>>;
+    type Env = Eff::Env;
+
+    async fn run(self, env: &Self::Env) -> Result<Self::Output, Self::Error> {
+        let Retry {
+            make_effect,
+            policy,
+        } = self;
+        let policy = &policy;
+        let (result, ()) = retry_attempts(policy, (), move |()| {
+            let effect = make_effect();
+            async move { (timed_attempt(policy, effect.run(env)).await, ()) }
+        })
+        .await;
+        result
+    }
 }
 
 /// Retry only when the predicate returns true for the error.
@@ -99,12 +315,16 @@ where
 /// Useful for distinguishing transient errors (retry) from permanent
 /// errors (fail fast).
 ///
+/// An attempt that outlives the policy's
+/// [attempt timeout](RetryPolicy::with_attempt_timeout) is always retried;
+/// the predicate only sees the errors the effect fails with.
+///
 /// # Example
 ///
 /// ```rust,ignore
 /// use stillwater::effect::prelude::*;
 /// use stillwater::effect::retry::retry_if;
-/// use stillwater::RetryPolicy;
+/// use stillwater::{RetryPolicy, TimeoutError};
 /// use std::time::Duration;
 ///
 /// #[derive(Debug, PartialEq, Clone)]
@@ -118,38 +338,95 @@ where
 ///
 /// // Permanent errors are not retried
 /// let result = effect.execute(&()).await;
-/// assert_eq!(result, Err(AppError::Permanent));
+/// assert_eq!(result, Err(TimeoutError::Inner(AppError::Permanent)));
 /// ```
 #[cfg(feature = "async")]
 pub fn retry_if<T, E, Env, F, P, Eff>(
     make_effect: F,
     policy: RetryPolicy,
     should_retry: P,
-) -> BoxedEffect<T, E, Env>
+) -> BoxedEffect<T, TimeoutError<E>, Env>
 where
     T: Send + 'static,
     E: Send + 'static,
-    Env: Clone + Send + Sync + 'static,
+    Env: Send + Sync + 'static,
     F: Fn() -> Eff + Send + 'static,
     P: Fn(&E) -> bool + Send + Sync + 'static,
     Eff: Effect<Output = T, Error = E, Env = Env> + 'static,
 {
-    crate::effect::constructors::from_async(move |env: &Env| {
-        let env = env.clone();
-        async move {
+    RetryIf {
+        make_effect,
+        policy,
+        should_retry,
+    }
+    .boxed()
+}
+
+/// The effect built by [`retry_if`].
+#[cfg(feature = "async")]
+struct RetryIf<F, P> {
+    make_effect: F,
+    policy: RetryPolicy,
+    should_retry: P,
+}
+
+#[cfg(feature = "async")]
+impl<F, P, Eff, T, E, Env> Effect for RetryIf<F, P>
+where
+    F: Fn() -> Eff + Send,
+    P: Fn(&E) -> bool + Send,
+    Eff: Effect<Output = T, Error = E, Env = Env>,
+    T: Send,
+    E: Send,
+    Env: Send + Sync,
+{
+    type Output = T;
+    type Error = TimeoutError<E>;
+    type Env = Env;
+
+    async fn run(self, env: &Env) -> Result<Self::Output, Self::Error> {
+        let RetryIf {
+            make_effect,
+            policy,
+            should_retry,
+        } = self;
+        #[cfg(feature = "tracing")]
+        let span = retry_span(&policy);
+        let run = async move {
+            #[cfg(any(feature = "tracing", feature = "prometheus"))]
+            let start = Instant::now();
             let mut attempt = 0u32;
             let mut prev_delay: Option<Duration> = None;
 
             loop {
                 let effect = make_effect();
-                match effect.run(&env).await {
-                    Ok(value) => return Ok(value),
+                match timed_attempt(&policy, effect.run(env)).await {
+                    Ok(value) => {
+                        #[cfg(feature = "tracing")]
+                        trace_outcome(attempt + 1, "success");
+                        #[cfg(feature = "prometheus")]
+                        metrics::retry_outcome(&policy, attempt + 1, "success", start.elapsed());
+                        return Ok(value);
+                    }
                     Err(error) => {
-                        if !should_retry(&error) {
+                        if matches!(&error, TimeoutError::Inner(e) if !should_retry(e)) {
+                            #[cfg(feature = "tracing")]
+                            trace_outcome(attempt + 1, "not_retryable");
+                            #[cfg(feature = "prometheus")]
+                            metrics::retry_outcome(
+                                &policy,
+                                attempt + 1,
+                                "not_retryable",
+                                start.elapsed(),
+                            );
                             return Err(error);
                         }
 
                         let delay = policy.delay_with_jitter(attempt, prev_delay);
+                        #[cfg(feature = "tracing")]
+                        trace_failure::<E>(&policy, attempt + 1, delay, start.elapsed());
+                        #[cfg(feature = "prometheus")]
+                        metrics::retry_failure(&policy);
 
                         match delay {
                             Some(d) => {
@@ -158,15 +435,74 @@ where
                                 attempt += 1;
                             }
                             None => {
+                                #[cfg(feature = "tracing")]
+                                trace_outcome(attempt + 1, "exhausted");
+                                #[cfg(feature = "prometheus")]
+                                metrics::retry_outcome(
+                                    &policy,
+                                    attempt + 1,
+                                    "exhausted",
+                                    start.elapsed(),
+                                );
                                 return Err(error);
                             }
                         }
                     }
                 }
             }
-        }
-    })
-    .boxed()
+        };
+        #[cfg(feature = "tracing")]
+        let run = tracing::Instrument::instrument(run, span);
+        run.await
+    }
+}
+
+/// Retry only errors that [`ErrorClass`] marks as retryable.
+///
+/// Equivalent to [`retry_if`] with `|e| e.is_retryable()`: transient and
+/// throttled errors are retried, permanent and canceled errors propagate
+/// immediately. Attempts that time out are retried.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use stillwater::effect::prelude::*;
+/// use stillwater::effect::retry::retry_classified;
+/// use stillwater::{error_class, RetryPolicy, TimeoutError};
+/// use std::time::Duration;
+///
+/// #[derive(Debug, PartialEq)]
+/// enum AppError { Unavailable, BadRequest }
+///
+/// error_class!(AppError {
+///     Self::Unavailable => Transient,
+///     Self::BadRequest => Permanent,
+/// });
+///
+/// let effect = retry_classified(
+///     || fail::<(), _, ()>(AppError::BadRequest),
+///     RetryPolicy::constant(Duration::from_millis(10)).with_max_retries(3),
+/// );
+///
+/// // Permanent errors are not retried
+/// assert_eq!(
+///     effect.execute(&()).await,
+///     Err(TimeoutError::Inner(AppError::BadRequest))
+/// );
+/// ```
+#[cfg(feature = "async")]
+pub fn retry_classified<T, E, Env, F, Eff>(
+    make_effect: F,
+    policy: RetryPolicy,
+) -> BoxedEffect<T, TimeoutError<E>, Env>
+where
+    T: Send + 'static,
+    E: ErrorClass + Send + 'static,
+    Env: Send + Sync + 'static,
+    F: Fn() -> Eff + Send + 'static,
+    Eff: Effect<Output = T, Error = E, Env = Env> + 'static,
+{
+    retry_if(make_effect, policy, |error: &E| error.is_retryable())
 }
 
 /// Retry with hooks for observability.
@@ -174,19 +510,20 @@ where
 /// The `on_retry` callback is invoked before each retry attempt,
 /// receiving information about the failed attempt. The callback
 /// is synchronous and should not block; use it for logging/metrics.
+/// Like the final error, the error in each event is a [`TimeoutError`].
 ///
 /// # Example
 ///
 /// ```rust,ignore
 /// use stillwater::effect::prelude::*;
 /// use stillwater::effect::retry::retry_with_hooks;
-/// use stillwater::{RetryPolicy, RetryEvent};
+/// use stillwater::{RetryPolicy, RetryEvent, TimeoutError};
 /// use std::time::Duration;
 ///
 /// let effect = retry_with_hooks(
 ///     || pure::<_, String, ()>(42),
 ///     RetryPolicy::exponential(Duration::from_millis(10)).with_max_retries(3),
-///     |event: &RetryEvent<'_, String>| {
+///     |event: &RetryEvent<'_, TimeoutError<String>>| {
 ///         println!(
 ///             "Attempt {} failed: {:?}, next delay: {:?}",
 ///             event.attempt, event.error, event.next_delay
@@ -199,30 +536,74 @@ pub fn retry_with_hooks<T, E, Env, F, H, Eff>(
     make_effect: F,
     policy: RetryPolicy,
     on_retry: H,
-) -> BoxedEffect<RetryExhausted<T>, RetryExhausted<E>, Env>
+) -> BoxedEffect<RetrySuccess<T>, RetryExhausted<TimeoutError<E>>, Env>
 where
     T: Send + 'static,
     E: Send + 'static,
-    Env: Clone + Send + Sync + 'static,
+    Env: Send + Sync + 'static,
     F: Fn() -> Eff + Send + 'static,
-    H: Fn(&RetryEvent<'_, E>) + Send + Sync + 'static,
+    H: Fn(&RetryEvent<'_, TimeoutError<E>>) + Send + Sync + 'static,
     Eff: Effect<Output = T, Error = E, Env = Env> + 'static,
 {
-    crate::effect::constructors::from_async(move |env: &Env| {
-        let env = env.clone();
-        async move {
+    RetryWithHooks {
+        make_effect,
+        policy,
+        on_retry,
+    }
+    .boxed()
+}
+
+/// The effect built by [`retry_with_hooks`].
+#[cfg(feature = "async")]
+struct RetryWithHooks<F, H> {
+    make_effect: F,
+    policy: RetryPolicy,
+    on_retry: H,
+}
+
+#[cfg(feature = "async")]
+impl<F, H, Eff, T, E, Env> Effect for RetryWithHooks<F, H>
+where
+    F: Fn() -> Eff + Send,
+    H: Fn(&RetryEvent<'_, TimeoutError<E>>) + Send,
+    Eff: Effect<Output = T, Error = E, Env = Env>,
+    T: Send,
+    E: Send,
+    Env: Send + Sync,
+{
+    type Output = RetrySuccess<T>;
+    type Error = RetryExhausted<TimeoutError<E>>;
+    type Env = Env;
+
+    async fn run(self, env: &Env) -> Result<Self::Output, Self::Error> {
+        let RetryWithHooks {
+            make_effect,
+            policy,
+            on_retry,
+        } = self;
+        #[cfg(feature = "tracing")]
+        let span = retry_span(&policy);
+        let run = async move {
             let start = Instant::now();
             let mut attempt = 0u32;
             let mut prev_delay: Option<Duration> = None;
 
             loop {
                 let effect = make_effect();
-                match effect.run(&env).await {
+                match timed_attempt(&policy, effect.run(env)).await {
                     Ok(value) => {
-                        return Ok(RetryExhausted::new(value, attempt + 1, start.elapsed()));
+                        #[cfg(feature = "tracing")]
+                        trace_outcome(attempt + 1, "success");
+                        #[cfg(feature = "prometheus")]
+                        metrics::retry_outcome(&policy, attempt + 1, "success", start.elapsed());
+                        return Ok(RetrySuccess::new(value, attempt + 1, start.elapsed()));
                     }
                     Err(error) => {
                         let delay = policy.delay_with_jitter(attempt, prev_delay);
+                        #[cfg(feature = "tracing")]
+                        trace_failure::<E>(&policy, attempt + 1, delay, start.elapsed());
+                        #[cfg(feature = "prometheus")]
+                        metrics::retry_failure(&policy);
 
                         // Call the hook before retrying
                         {
@@ -242,6 +623,15 @@ where
                                 attempt += 1;
                             }
                             None => {
+                                #[cfg(feature = "tracing")]
+                                trace_outcome(attempt + 1, "exhausted");
+                                #[cfg(feature = "prometheus")]
+                                metrics::retry_outcome(
+                                    &policy,
+                                    attempt + 1,
+                                    "exhausted",
+                                    start.elapsed(),
+                                );
                                 return Err(RetryExhausted::new(
                                     error,
                                     attempt + 1,
@@ -252,16 +642,186 @@ where
                     }
                 }
             }
-        }
-    })
+        };
+        #[cfg(feature = "tracing")]
+        let run = tracing::Instrument::instrument(run, span);
+        run.await
+    }
+}
+
+/// Retry an effect like [`retry`], keeping its progress in a
+/// [`PersistentRetryState`] store so a restarted process resumes the
+/// backoff instead of starting over.
+///
+/// After every failed attempt that will be retried, the attempt count,
+/// the chosen delay, and the time the next attempt is due are saved under
+/// `key`. When the effect runs and a state is found under `key`, it first
+/// waits out whatever remains of the saved delay, then continues the
+/// schedule from the saved attempt. The state is cleared once the effect
+/// succeeds or the policy gives up.
+///
+/// `attempts` in the result counts attempts across restarts; `elapsed`
+/// covers only the current run.
+///
+/// # Example
+///
+/// ```rust
+/// use std::sync::Arc;
+/// use std::time::Duration;
+/// use stillwater::effect::prelude::*;
+/// use stillwater::effect::retry::retry_persistent;
+/// use stillwater::retry::InMemoryRetryStore;
+/// use stillwater::RetryPolicy;
+///
+/// # tokio_test::block_on(async {
+/// let store = Arc::new(InMemoryRetryStore::new());
+/// let effect = retry_persistent(
+///     || pure::<_, String, ()>("synced"),
+///     RetryPolicy::exponential(Duration::from_secs(60)).with_max_retries(10),
+///     store.clone(),
+///     "nightly-sync",
+/// );
+///
+/// let success = effect.execute(&()).await.unwrap();
+/// assert_eq!(success.attempts, 1);
+/// assert!(store.get("nightly-sync").is_none());
+/// # });
+/// ```
+#[cfg(feature = "async")]
+pub fn retry_persistent<T, E, Env, F, Eff, S>(
+    make_effect: F,
+    policy: RetryPolicy,
+    store: Arc<S>,
+    key: impl Into<String>,
+) -> BoxedEffect<RetrySuccess<T>, RetryExhausted<TimeoutError<E>>, Env>
+where
+    T: Send + 'static,
+    E: Send + 'static,
+    Env: Send + Sync + 'static,
+    F: Fn() -> Eff + Send + 'static,
+    Eff: Effect<Output = T, Error = E, Env = Env> + 'static,
+    S: PersistentRetryState + ?Sized + 'static,
+{
+    let key = key.into();
+    RetryPersistent {
+        make_effect,
+        policy,
+        store,
+        key,
+    }
     .boxed()
 }
 
+/// The effect built by [`retry_persistent`].
+#[cfg(feature = "async")]
+struct RetryPersistent<F, S: ?Sized> {
+    make_effect: F,
+    policy: RetryPolicy,
+    store: Arc<S>,
+    key: String,
+}
+
+#[cfg(feature = "async")]
+impl<F, S, Eff, T, E, Env> Effect for RetryPersistent<F, S>
+where
+    F: Fn() -> Eff + Send,
+    S: PersistentRetryState + ?Sized,
+    Eff: Effect<Output = T, Error = E, Env = Env>,
+    T: Send,
+    E: Send,
+    Env: Send + Sync,
+{
+    type Output = RetrySuccess<T>;
+    type Error = RetryExhausted<TimeoutError<E>>;
+    type Env = Env;
+
+    async fn run(self, env: &Env) -> Result<Self::Output, Self::Error> {
+        let RetryPersistent {
+            make_effect,
+            policy,
+            store,
+            key,
+        } = self;
+        #[cfg(feature = "tracing")]
+        let span = retry_span(&policy);
+        let run = async move {
+            let start = Instant::now();
+            let mut attempt = 0u32;
+            let mut prev_delay: Option<Duration> = None;
+
+            if let Some(saved) = store.load(&key).await {
+                tokio::time::sleep(saved.remaining()).await;
+                attempt = saved.attempt;
+                prev_delay = saved.prev_delay;
+            }
+
+            loop {
+                let effect = make_effect();
+                match timed_attempt(&policy, effect.run(env)).await {
+                    Ok(value) => {
+                        store.clear(&key).await;
+                        #[cfg(feature = "tracing")]
+                        trace_outcome(attempt + 1, "success");
+                        #[cfg(feature = "prometheus")]
+                        metrics::retry_outcome(&policy, attempt + 1, "success", start.elapsed());
+                        return Ok(RetrySuccess::new(value, attempt + 1, start.elapsed()));
+                    }
+                    Err(error) => {
+                        let delay = policy.delay_with_jitter(attempt, prev_delay);
+                        #[cfg(feature = "tracing")]
+                        trace_failure::<E>(&policy, attempt + 1, delay, start.elapsed());
+                        #[cfg(feature = "prometheus")]
+                        metrics::retry_failure(&policy);
+
+                        match delay {
+                            Some(d) => {
+                                let state = RetryState {
+                                    attempt: attempt + 1,
+                                    prev_delay: Some(d),
+                                    next_retry_at: SystemTime::now() + d,
+                                };
+                                store.save(&key, state).await;
+                                tokio::time::sleep(d).await;
+                                prev_delay = Some(d);
+                                attempt += 1;
+                            }
+                            None => {
+                                store.clear(&key).await;
+                                #[cfg(feature = "tracing")]
+                                trace_outcome(attempt + 1, "exhausted");
+                                #[cfg(feature = "prometheus")]
+                                metrics::retry_outcome(
+                                    &policy,
+                                    attempt + 1,
+                                    "exhausted",
+                                    start.elapsed(),
+                                );
+                                return Err(RetryExhausted::new(
+                                    error,
+                                    attempt + 1,
+                                    start.elapsed(),
+                                ));
+                            }
+                        }
+                    }
+                }
+            }
+        };
+        #[cfg(feature = "tracing")]
+        let run = tracing::Instrument::instrument(run, span);
+        run.await
+    }
+}
+
 /// Add a timeout to an effect.
 ///
 /// If the effect doesn't complete within the duration, it fails
 /// with a timeout error.
 ///
+/// The effect is dropped as soon as the time is up. To give it a chance to
+/// return a partial result first, use
+/// [`with_soft_timeout`](crate::effect::soft_timeout::with_soft_timeout).
+///
 /// # Example
 ///
 /// ```rust,ignore
@@ -293,20 +853,33 @@ pub fn with_timeout<T, E, Env, Eff>(
 where
     T: Send + 'static,
     E: Send + 'static,
-    Env: Clone + Send + Sync + 'static,
+    Env: Send + Sync + 'static,
     Eff: Effect<Output = T, Error = E, Env = Env> + 'static,
 {
-    crate::effect::constructors::from_async(move |env: &Env| {
-        let env = env.clone();
-        async move {
-            match tokio::time::timeout(duration, effect.run(&env)).await {
-                Ok(Ok(value)) => Ok(value),
-                Ok(Err(e)) => Err(TimeoutError::Inner(e)),
-                Err(_) => Err(TimeoutError::Timeout { duration }),
-            }
+    WithTimeout { effect, duration }.boxed()
+}
+
+/// The effect built by [`with_timeout`].
+#[cfg(feature = "async")]
+struct WithTimeout<Eff> {
+    effect: Eff,
+    duration: Duration,
+}
+
+#[cfg(feature = "async")]
+impl<Eff: Effect> Effect for WithTimeout<Eff> {
+    type Output = Eff::Output;
+    type Error = TimeoutError<Eff::Error>;
+    type Env = Eff::Env;
+
+    async fn run(self, env: &Self::Env) -> Result<Self::Output, Self::Error> {
+        let duration = self.duration;
+        match tokio::time::timeout(duration, self.effect.run(env)).await {
+            Ok(Ok(value)) => Ok(value),
+            Ok(Err(e)) => Err(TimeoutError::Inner(e)),
+            Err(_) => Err(TimeoutError::Timeout { duration }),
         }
-    })
-    .boxed()
+    }
 }
 
 #[cfg(all(test, feature = "async"))]
@@ -351,7 +924,7 @@ mod tests {
         );
 
         let result = effect.execute(&()).await.unwrap();
-        assert_eq!(result.final_error, 42); // Note: final_error holds success value in Ok case
+        assert_eq!(result.value, 42);
         assert_eq!(result.attempts, 1);
         assert!(result.total_duration < Duration::from_millis(100)); // Should be nearly instant
     }
@@ -368,7 +941,7 @@ mod tests {
         );
 
         let result = effect.execute(&()).await.unwrap();
-        assert_eq!(result.final_error, 42);
+        assert_eq!(result.value, 42);
         assert_eq!(result.attempts, 3); // 2 failures + 1 success
         assert_eq!(attempt_counter.load(Ordering::SeqCst), 3);
     }
@@ -382,7 +955,10 @@ mod tests {
         );
 
         let result = effect.execute(&()).await.unwrap_err();
-        assert_eq!(result.final_error, "always fails".to_string());
+        assert_eq!(
+            result.final_error,
+            TimeoutError::Inner("always fails".to_string())
+        );
         assert_eq!(result.attempts, 3); // 1 initial + 2 retries
     }
 
@@ -436,7 +1012,7 @@ mod tests {
         );
 
         let result = effect.execute(&()).await.unwrap();
-        assert_eq!(result.final_error, 100);
+        assert_eq!(result.value, 100);
         assert_eq!(result.attempts, 3);
     }
 
@@ -452,7 +1028,7 @@ mod tests {
         );
 
         let result = effect.execute(&()).await.unwrap();
-        assert_eq!(result.final_error, 50);
+        assert_eq!(result.value, 50);
         assert_eq!(result.attempts, 2);
     }
 
@@ -549,7 +1125,7 @@ mod tests {
         );
 
         let result = effect.execute(&()).await;
-        assert_eq!(result, Err(TestError::Permanent));
+        assert_eq!(result, Err(TimeoutError::Inner(TestError::Permanent)));
         // Only one attempt should be made
         assert_eq!(attempt_counter.load(Ordering::SeqCst), 1);
     }
@@ -580,7 +1156,7 @@ mod tests {
         );
 
         let result = effect.execute(&()).await;
-        assert_eq!(result, Err(TestError::Permanent));
+        assert_eq!(result, Err(TimeoutError::Inner(TestError::Permanent)));
         // 3 attempts: 2 transient + 1 permanent
         assert_eq!(attempt_counter.load(Ordering::SeqCst), 3);
     }
@@ -628,7 +1204,7 @@ mod tests {
         );
 
         let result = effect.execute(&()).await;
-        assert_eq!(result, Err(RetryableError));
+        assert_eq!(result, Err(TimeoutError::Inner(RetryableError)));
     }
 
     #[tokio::test]
@@ -651,6 +1227,40 @@ mod tests {
         assert_eq!(predicate_called.load(Ordering::SeqCst), 0);
     }
 
+    #[tokio::test]
+    async fn test_retry_classified_uses_error_class() {
+        // Test: throttled errors are retried, canceled errors are not
+        #[derive(Debug, PartialEq, Clone)]
+        enum TestError {
+            Throttled,
+            Canceled,
+        }
+
+        crate::error_class!(TestError {
+            Self::Throttled => Throttled,
+            Self::Canceled => Canceled,
+        });
+
+        let attempt_counter = Arc::new(AtomicU32::new(0));
+        let counter_clone = attempt_counter.clone();
+
+        let effect = retry_classified(
+            move || {
+                let count = counter_clone.fetch_add(1, Ordering::SeqCst);
+                if count < 2 {
+                    fail::<i32, _, ()>(TestError::Throttled)
+                } else {
+                    fail::<i32, _, ()>(TestError::Canceled)
+                }
+            },
+            RetryPolicy::constant(Duration::from_millis(1)).with_max_retries(5),
+        );
+
+        let result = effect.execute(&()).await;
+        assert_eq!(result, Err(TimeoutError::Inner(TestError::Canceled)));
+        assert_eq!(attempt_counter.load(Ordering::SeqCst), 3);
+    }
+
     // ==========================================================================
     // Tests for retry_with_hooks() function
     // ==========================================================================
@@ -667,13 +1277,13 @@ mod tests {
         let effect = retry_with_hooks(
             move || flaky_effect(counter_clone.clone(), 2, 42, "error".to_string()),
             RetryPolicy::constant(Duration::from_millis(1)).with_max_retries(5),
-            move |_event: &RetryEvent<'_, String>| {
+            move |_event: &RetryEvent<'_, TimeoutError<String>>| {
                 hook_clone.fetch_add(1, Ordering::SeqCst);
             },
         );
 
         let result = effect.execute(&()).await.unwrap();
-        assert_eq!(result.final_error, 42);
+        assert_eq!(result.value, 42);
         // Hook should be called twice (for the 2 failures)
         assert_eq!(hook_calls.load(Ordering::SeqCst), 2);
     }
@@ -693,10 +1303,10 @@ mod tests {
                 fail::<i32, _, ()>(format!("error_{}", count))
             },
             RetryPolicy::constant(Duration::from_millis(1)).with_max_retries(2),
-            move |event: &RetryEvent<'_, String>| {
+            move |event: &RetryEvent<'_, TimeoutError<String>>| {
                 events_clone.lock().unwrap().push((
                     event.attempt,
-                    event.error.clone(),
+                    event.error.clone().into_inner().unwrap(),
                     event.next_delay.is_some(),
                 ));
             },
@@ -733,13 +1343,13 @@ mod tests {
         let effect = retry_with_hooks(
             || pure::<_, String, ()>(42),
             RetryPolicy::constant(Duration::from_millis(1)).with_max_retries(3),
-            move |_event: &RetryEvent<'_, String>| {
+            move |_event: &RetryEvent<'_, TimeoutError<String>>| {
                 hook_clone.fetch_add(1, Ordering::SeqCst);
             },
         );
 
         let result = effect.execute(&()).await.unwrap();
-        assert_eq!(result.final_error, 42);
+        assert_eq!(result.value, 42);
         assert_eq!(hook_called.load(Ordering::SeqCst), 0);
     }
 
@@ -752,7 +1362,7 @@ mod tests {
         let effect = retry_with_hooks(
             || fail::<i32, _, ()>("error".to_string()),
             RetryPolicy::constant(Duration::from_millis(5)).with_max_retries(2),
-            move |event: &RetryEvent<'_, String>| {
+            move |event: &RetryEvent<'_, TimeoutError<String>>| {
                 times_clone.lock().unwrap().push(event.elapsed);
             },
         );
@@ -783,13 +1393,13 @@ mod tests {
         let effect = retry_with_hooks(
             move || flaky_effect(counter_clone.clone(), 3, 99, "error".to_string()),
             RetryPolicy::constant(Duration::from_millis(1)).with_max_retries(5),
-            move |_: &RetryEvent<'_, String>| {
+            move |_: &RetryEvent<'_, TimeoutError<String>>| {
                 hook_clone.fetch_add(1, Ordering::SeqCst);
             },
         );
 
         let result = effect.execute(&()).await.unwrap();
-        assert_eq!(result.final_error, 99);
+        assert_eq!(result.value, 99);
         assert_eq!(result.attempts, 4);
         // Hook called 3 times for the 3 failures
         assert_eq!(hook_calls.load(Ordering::SeqCst), 3);
@@ -946,14 +1556,134 @@ mod tests {
         );
     }
 
+    // ==========================================================================
+    // Tests for attempt timeouts
+    // ==========================================================================
+
+    /// A factory whose first effect hangs and whose later effects fail.
+    fn hangs_once(
+        attempts: &Arc<AtomicU32>,
+    ) -> impl Fn() -> BoxedEffect<i32, String, ()> + Send + 'static {
+        let attempts = attempts.clone();
+        move || {
+            let hang = attempts.fetch_add(1, Ordering::SeqCst) == 0;
+            from_async(move |_: &()| async move {
+                if hang {
+                    tokio::time::sleep(Duration::from_secs(10)).await;
+                }
+                Err::<i32, _>("down".to_string())
+            })
+            .boxed()
+        }
+    }
+
+    fn timed_policy() -> RetryPolicy {
+        RetryPolicy::constant(Duration::from_millis(1))
+            .with_max_retries(1)
+            .with_attempt_timeout(Duration::from_millis(20))
+    }
+
+    #[tokio::test]
+    async fn test_retry_retries_hung_attempt() {
+        let attempts = Arc::new(AtomicU32::new(0));
+        let counter = attempts.clone();
+        let effect = retry(
+            move || {
+                let counter = counter.clone();
+                from_async(move |_: &()| {
+                    let hang = counter.fetch_add(1, Ordering::SeqCst) == 0;
+                    async move {
+                        if hang {
+                            tokio::time::sleep(Duration::from_secs(10)).await;
+                        }
+                        Ok::<_, String>(7)
+                    }
+                })
+            },
+            RetryPolicy::constant(Duration::from_millis(1))
+                .with_max_retries(2)
+                .with_attempt_timeout(Duration::from_millis(20)),
+        );
+
+        let success = effect.execute(&()).await.unwrap();
+        assert_eq!(success.value, 7);
+        assert_eq!(success.attempts, 2);
+    }
+
+    #[tokio::test]
+    async fn test_retry_reports_timeout_of_last_attempt() {
+        let effect = retry(
+            || {
+                from_async(|_: &()| async {
+                    tokio::time::sleep(Duration::from_secs(10)).await;
+                    Ok::<_, String>(7)
+                })
+            },
+            timed_policy(),
+        );
+
+        let exhausted = effect.execute(&()).await.unwrap_err();
+        assert_eq!(exhausted.attempts, 2);
+        assert_eq!(
+            exhausted.final_error,
+            TimeoutError::Timeout {
+                duration: Duration::from_millis(20)
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_retry_if_retries_timeouts_regardless_of_predicate() {
+        let attempts = Arc::new(AtomicU32::new(0));
+        let effect = retry_if(hangs_once(&attempts), timed_policy(), |_: &String| false);
+
+        let result = effect.execute(&()).await;
+        assert_eq!(result, Err(TimeoutError::Inner("down".to_string())));
+        assert_eq!(attempts.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_retry_with_hooks_reports_timeouts() {
+        let attempts = Arc::new(AtomicU32::new(0));
+        let timeouts = Arc::new(AtomicU32::new(0));
+        let seen = timeouts.clone();
+        let effect = retry_with_hooks(
+            hangs_once(&attempts),
+            timed_policy(),
+            move |event: &RetryEvent<'_, TimeoutError<String>>| {
+                if event.error.is_timeout() {
+                    seen.fetch_add(1, Ordering::SeqCst);
+                }
+            },
+        );
+
+        let exhausted = effect.execute(&()).await.unwrap_err();
+        assert_eq!(exhausted.attempts, 2);
+        assert_eq!(timeouts.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_retry_persistent_applies_attempt_timeout() {
+        let store = Arc::new(crate::retry::InMemoryRetryStore::new());
+        let attempts = Arc::new(AtomicU32::new(0));
+        let effect = retry_persistent(hangs_once(&attempts), timed_policy(), store, "job");
+
+        let exhausted = effect.execute(&()).await.unwrap_err();
+        assert_eq!(exhausted.attempts, 2);
+        assert_eq!(
+            exhausted.final_error,
+            TimeoutError::Inner("down".to_string())
+        );
+    }
+
     // ==========================================================================
     // Additional edge case and integration tests
     // ==========================================================================
 
     #[tokio::test]
     async fn test_retry_with_environment() {
-        // Test: Retry works correctly with a non-unit environment
-        #[derive(Clone)]
+        // Test: Retry works correctly with a non-unit environment, which
+        // it only borrows
         struct Config {
             multiplier: i32,
         }
@@ -977,13 +1707,12 @@ mod tests {
         );
 
         let result = effect.execute(&Config { multiplier: 5 }).await.unwrap();
-        assert_eq!(result.final_error, 50);
+        assert_eq!(result.value, 50);
     }
 
     #[tokio::test]
     async fn test_retry_if_with_environment() {
-        // Test: retry_if works correctly with a custom environment
-        #[derive(Clone)]
+        // Test: retry_if works correctly with a custom, non-Clone environment
         struct AppConfig {
             threshold: u32,
         }
@@ -1017,4 +1746,90 @@ mod tests {
         let result = effect.execute(&AppConfig { threshold: 3 }).await;
         assert_eq!(result, Ok(4));
     }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_retry_persistent_saves_progress_between_attempts() {
+        let store = Arc::new(crate::retry::InMemoryRetryStore::new());
+        let seen = Arc::new(std::sync::Mutex::new(Vec::new()));
+
+        let effect = retry_persistent(
+            {
+                let store = store.clone();
+                let seen = seen.clone();
+                move || {
+                    let saved = store.get("job").map(|s| (s.attempt, s.prev_delay));
+                    seen.lock().unwrap().push(saved);
+                    fail::<(), _, ()>("down".to_string())
+                }
+            },
+            RetryPolicy::exponential(Duration::from_secs(60)).with_max_retries(2),
+            store.clone(),
+            "job",
+        );
+
+        let exhausted = effect.execute(&()).await.unwrap_err();
+        assert_eq!(exhausted.attempts, 3);
+        assert_eq!(
+            *seen.lock().unwrap(),
+            vec![
+                None,
+                Some((1, Some(Duration::from_secs(60)))),
+                Some((2, Some(Duration::from_secs(120)))),
+            ]
+        );
+        assert!(store.get("job").is_none());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_retry_persistent_resumes_saved_schedule() {
+        let store = Arc::new(crate::retry::InMemoryRetryStore::new());
+        store.insert(
+            "job",
+            crate::retry::RetryState {
+                attempt: 3,
+                prev_delay: Some(Duration::from_secs(3600)),
+                next_retry_at: SystemTime::now() + Duration::from_secs(1800),
+            },
+        );
+        let calls = Arc::new(AtomicU32::new(0));
+
+        let start = tokio::time::Instant::now();
+        let effect = retry_persistent(
+            {
+                let calls = calls.clone();
+                move || {
+                    let call = calls.fetch_add(1, Ordering::SeqCst);
+                    from_fn(move |_: &()| if call == 0 { Err("down") } else { Ok(call) })
+                }
+            },
+            RetryPolicy::constant(Duration::from_secs(7200)).with_max_retries(5),
+            store.clone(),
+            "job",
+        );
+
+        let success = effect.execute(&()).await.unwrap();
+        assert_eq!(success.attempts, 5);
+        let waited = start.elapsed();
+        assert!(waited > Duration::from_secs(1790 + 7200));
+        assert!(waited <= Duration::from_secs(1800 + 7200));
+        assert!(store.get("job").is_none());
+    }
+
+    #[cfg(feature = "tracing")]
+    #[tokio::test]
+    #[tracing_test::traced_test]
+    async fn test_retry_emits_standard_events() {
+        let effect = retry(
+            || fail::<(), _, ()>("unavailable".to_string()),
+            RetryPolicy::constant(Duration::from_millis(1))
+                .with_max_retries(1)
+                .with_name("inventory"),
+        );
+
+        assert!(effect.execute(&()).await.is_err());
+        assert!(logs_contain("attempt failed, retrying"));
+        assert!(logs_contain("policy=\"inventory\" attempt=1 delay_ms=1"));
+        assert!(logs_contain("retries exhausted"));
+        assert!(logs_contain("attempt=2"));
+    }
 }
@@ -0,0 +1,211 @@
+//! Program entry points for effect-based applications.
+//!
+//! [`run_main`] is the imperative shell around a pure core: it runs the
+//! application effect once, prints an [`ErrorReport`] to stderr if it
+//! fails, flushes stdout and stderr, and turns the error's [`Class`] into a
+//! process exit code. [`main_effect`] does the same with a configurable
+//! report format.
+//!
+//! | Class | Exit code |
+//! |-------|-----------|
+//! | success | 0 |
+//! | [`Class::Permanent`] | 1 |
+//! | [`Class::Transient`], [`Class::Throttled`] | 75 (`EX_TEMPFAIL`) |
+//! | [`Class::Canceled`] | 130 |
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use std::process::ExitCode;
+//! use stillwater::effect::prelude::*;
+//! use stillwater::effect::shell::run_main;
+//! use stillwater::ContextError;
+//!
+//! #[derive(Clone)]
+//! struct Env {
+//!     config_path: String,
+//! }
+//!
+//! fn app() -> impl Effect<Output = (), Error = ContextError<std::io::Error>, Env = Env> {
+//!     from_fn(|env: &Env| std::fs::read_to_string(&env.config_path).map(|_| ()))
+//!         .context("reading configuration")
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() -> ExitCode {
+//!     let env = Env { config_path: "app.toml".into() };
+//!     run_main(app(), &env).await
+//! }
+//! ```
+
+use std::fmt;
+use std::io::{self, Write};
+use std::process::ExitCode;
+
+use crate::effect::trait_def::Effect;
+use crate::report::{ReportStyle, ToReport};
+use crate::retry::{Class, ErrorClass};
+
+/// How [`MainEffect`] writes the error report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    /// Human-readable text in the given style.
+    Text(ReportStyle),
+    /// A single line of JSON, for log collectors.
+    Json,
+}
+
+impl Default for ReportFormat {
+    fn default() -> Self {
+        ReportFormat::Text(ReportStyle::Plain)
+    }
+}
+
+/// The process exit code for a failure of the given class.
+pub fn exit_code_for(class: Class) -> u8 {
+    match class {
+        Class::Permanent => 1,
+        Class::Transient | Class::Throttled => 75,
+        Class::Canceled => 130,
+    }
+}
+
+/// An application effect prepared to run at the program boundary.
+///
+/// Created by [`main_effect`].
+pub struct MainEffect<Eff> {
+    effect: Eff,
+    format: ReportFormat,
+}
+
+impl<Eff> fmt::Debug for MainEffect<Eff> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MainEffect")
+            .field("effect", &"<effect>")
+            .field("format", &self.format)
+            .finish()
+    }
+}
+
+impl<Eff> MainEffect<Eff>
+where
+    Eff: Effect,
+    Eff::Error: ToReport + ErrorClass,
+{
+    /// Set how the error report is written.
+    pub fn format(mut self, format: ReportFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Run the effect, report any error to stderr, and return the exit code.
+    pub async fn run(self, env: &Eff::Env) -> ExitCode {
+        let result = self.effect.run(env).await;
+        let code = finish(result.err(), self.format, &mut io::stderr().lock());
+        let _ = io::stdout().flush();
+        ExitCode::from(code)
+    }
+}
+
+/// Prepare an application effect to run at the program boundary.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use stillwater::effect::prelude::*;
+/// use stillwater::effect::shell::{main_effect, ReportFormat};
+/// use stillwater::ContextError;
+///
+/// # async fn example() -> std::process::ExitCode {
+/// let app = fail::<(), _, ()>(std::io::Error::other("disk full")).context("writing output");
+/// main_effect(app).format(ReportFormat::Json).run(&()).await
+/// # }
+/// ```
+pub fn main_effect<Eff>(effect: Eff) -> MainEffect<Eff>
+where
+    Eff: Effect,
+    Eff::Error: ToReport + ErrorClass,
+{
+    MainEffect {
+        effect,
+        format: ReportFormat::default(),
+    }
+}
+
+/// Run an application effect at the program boundary with plain-text
+/// error reports.
+///
+/// Shorthand for `main_effect(effect).run(env)`.
+pub async fn run_main<Eff>(effect: Eff, env: &Eff::Env) -> ExitCode
+where
+    Eff: Effect,
+    Eff::Error: ToReport + ErrorClass,
+{
+    main_effect(effect).run(env).await
+}
+
+/// Write the report for `error`, if any, and return the exit code.
+fn finish<E, W>(error: Option<E>, format: ReportFormat, out: &mut W) -> u8
+where
+    E: ToReport + ErrorClass,
+    W: Write,
+{
+    let Some(error) = error else {
+        return 0;
+    };
+    let report = error.to_report();
+    let rendered = match format {
+        ReportFormat::Text(style) => report.render(style),
+        ReportFormat::Json => report.to_json(),
+    };
+    // The exit code still reflects the failure if stderr is gone.
+    let _ = writeln!(out, "{}", rendered);
+    let _ = out.flush();
+    exit_code_for(error.class())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::ContextError;
+    use crate::effect::constructors::pure;
+
+    #[test]
+    fn test_finish_writes_report_and_maps_class() {
+        let error = ContextError::new(io::Error::from(io::ErrorKind::TimedOut)).context("syncing");
+        let mut out = Vec::new();
+
+        let code = finish(Some(error), ReportFormat::default(), &mut out);
+
+        assert_eq!(code, 75);
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "error: timed out\n  -> syncing\n"
+        );
+    }
+
+    #[test]
+    fn test_finish_json() {
+        let error = ContextError::new(io::Error::from(io::ErrorKind::NotFound));
+        let mut out = Vec::new();
+
+        assert_eq!(finish(Some(error), ReportFormat::Json, &mut out), 1);
+        assert!(String::from_utf8(out).unwrap().starts_with("{\"message\":"));
+    }
+
+    #[test]
+    fn test_finish_success_writes_nothing() {
+        let mut out = Vec::new();
+        assert_eq!(
+            finish::<ContextError<io::Error>, _>(None, ReportFormat::default(), &mut out),
+            0
+        );
+        assert!(out.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_run_main_success() {
+        let code = run_main(pure::<_, ContextError<io::Error>, ()>(()), &()).await;
+        assert_eq!(code, ExitCode::SUCCESS);
+    }
+}
@@ -10,11 +10,12 @@ use crate::effect::Effect;
 /// Type alias for the boxed sink effect inner function type.
 ///
 /// This captures the result and any emitted items for later replay.
-type BoxedSinkFn<T, E, Env, Item> = Box<
-    dyn FnOnce(Env) -> Pin<Box<dyn Future<Output = (Result<T, E>, Vec<Item>)> + Send + 'static>>
-        + Send
-        + 'static,
->;
+type BoxedSinkFn<T, E, Env, Item> =
+    Box<dyn for<'a> FnOnce(&'a Env) -> CollectingFuture<'a, T, E, Item> + Send + 'static>;
+
+/// The future returned by [`BoxedSinkFn`], borrowing the environment.
+type CollectingFuture<'a, T, E, Item> =
+    Pin<Box<dyn Future<Output = (Result<T, E>, Vec<Item>)> + Send + 'a>>;
 
 /// A type-erased SinkEffect for use in collections, match arms, or recursive functions.
 ///
@@ -78,7 +79,7 @@ type BoxedSinkFn<T, E, Env, Item> = Box<
 /// ```
 pub struct BoxedSinkEffect<T, E, Env, Item>
 where
-    Env: Clone + Send + Sync + 'static,
+    Env: Send + Sync + 'static,
     Item: Send + 'static,
 {
     inner: BoxedSinkFn<T, E, Env, Item>,
@@ -86,7 +87,7 @@ where
 
 impl<T, E, Env, Item> std::fmt::Debug for BoxedSinkEffect<T, E, Env, Item>
 where
-    Env: Clone + Send + Sync + 'static,
+    Env: Send + Sync + 'static,
     Item: Send + 'static,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -98,7 +99,7 @@ impl<T, E, Env, Item> BoxedSinkEffect<T, E, Env, Item>
 where
     T: Send + 'static,
     E: Send + 'static,
-    Env: Clone + Send + Sync + 'static,
+    Env: Send + Sync + 'static,
     Item: Send + 'static,
 {
     /// Create a new BoxedSinkEffect from a SinkEffect.
@@ -107,14 +108,14 @@ where
         Eff: SinkEffect<Output = T, Error = E, Env = Env, Item = Item> + Send + 'static,
     {
         BoxedSinkEffect {
-            inner: Box::new(move |env: Env| {
+            inner: Box::new(move |env: &Env| -> CollectingFuture<'_, T, E, Item> {
                 Box::pin(async move {
                     // Collect all items during execution
                     let collected: Arc<Mutex<Vec<Item>>> = Arc::new(Mutex::new(Vec::new()));
                     let collected_clone = Arc::clone(&collected);
 
                     let result = effect
-                        .run_with_sink(&env, move |item| {
+                        .run_with_sink(env, move |item| {
                             let collected = Arc::clone(&collected_clone);
                             async move {
                                 collected.lock().expect("mutex poisoned").push(item);
@@ -139,7 +140,7 @@ impl<T, E, Env, Item> Effect for BoxedSinkEffect<T, E, Env, Item>
 where
     T: Send + 'static,
     E: Send + 'static,
-    Env: Clone + Send + Sync + 'static,
+    Env: Send + Sync + 'static,
     Item: Send + 'static,
 {
     type Output = T;
@@ -147,7 +148,7 @@ where
     type Env = Env;
 
     async fn run(self, env: &Self::Env) -> Result<Self::Output, Self::Error> {
-        let (result, _items) = (self.inner)(env.clone()).await;
+        let (result, _items) = (self.inner)(env).await;
         result
     }
 }
@@ -156,7 +157,7 @@ impl<T, E, Env, Item> SinkEffect for BoxedSinkEffect<T, E, Env, Item>
 where
     T: Send + 'static,
     E: Send + 'static,
-    Env: Clone + Send + Sync + 'static,
+    Env: Send + Sync + 'static,
     Item: Send + 'static,
 {
     type Item = Item;
@@ -171,7 +172,7 @@ where
         Fut: Future<Output = ()> + Send,
     {
         // Execute and collect, then replay to actual sink
-        let (result, items) = (self.inner)(env.clone()).await;
+        let (result, items) = (self.inner)(env).await;
 
         for item in items {
             sink(item).await;
@@ -53,7 +53,7 @@ impl<T, E, Env> Effect for Emit<T, E, Env>
 where
     T: Send,
     E: Send,
-    Env: Clone + Send + Sync,
+    Env: Send + Sync,
 {
     type Output = ();
     type Error = E;
@@ -69,7 +69,7 @@ impl<T, E, Env> SinkEffect for Emit<T, E, Env>
 where
     T: Send,
     E: Send,
-    Env: Clone + Send + Sync,
+    Env: Send + Sync,
 {
     type Item = T;
 
@@ -102,7 +102,7 @@ pub fn emit<T, E, Env>(item: T) -> Emit<T, E, Env>
 where
     T: Send,
     E: Send,
-    Env: Clone + Send + Sync,
+    Env: Send + Sync,
 {
     Emit {
         item,
@@ -153,7 +153,7 @@ where
     I: IntoIterator<Item = T> + Send,
     T: Send,
     E: Send,
-    Env: Clone + Send + Sync,
+    Env: Send + Sync,
 {
     type Output = ();
     type Error = E;
@@ -171,7 +171,7 @@ where
     I::IntoIter: Send,
     T: Send,
     E: Send,
-    Env: Clone + Send + Sync,
+    Env: Send + Sync,
 {
     type Item = T;
 
@@ -207,7 +207,7 @@ where
     I: IntoIterator<Item = T> + Send,
     T: Send,
     E: Send,
-    Env: Clone + Send + Sync,
+    Env: Send + Sync,
 {
     EmitMany {
         items,
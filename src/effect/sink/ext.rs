@@ -7,7 +7,10 @@ use crate::effect::sink::boxed::BoxedSinkEffect;
 use crate::effect::sink::map::SinkMap;
 use crate::effect::sink::map_err::SinkMapErr;
 use crate::effect::sink::or_else::SinkOrElse;
+use crate::effect::sink::policy::{self, SinkError, SinkErrorPolicy, TrySink};
+use crate::effect::sink::router::SinkRouter;
 use crate::effect::sink::tap_emit::TapEmit;
+use crate::effect::sink::window::{SinkGroupByKey, SinkSlidingWindow, SinkWindow};
 use crate::effect::sink::zip::SinkZip;
 use crate::effect::sink::SinkEffect;
 
@@ -198,6 +201,99 @@ pub trait SinkEffectExt: SinkEffect {
         TapEmit { inner: self, f }
     }
 
+    /// Emit items in non-overlapping windows of `size`.
+    ///
+    /// At most `size` items are buffered. Items left over when the effect
+    /// finishes are emitted as a final, shorter window.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use stillwater::effect::sink::prelude::*;
+    ///
+    /// # tokio_test::block_on(async {
+    /// let effect = emit_many::<_, _, String, ()>(vec![3, 5, 4, 8, 1]).window(2);
+    ///
+    /// let (_, windows) = effect.run_collecting(&()).await;
+    /// let totals: Vec<i32> = windows.iter().map(|w| w.iter().sum()).collect();
+    /// assert_eq!(totals, vec![8, 12, 1]);
+    /// # });
+    /// ```
+    fn window(self, size: usize) -> SinkWindow<Self>
+    where
+        Self: Sized,
+    {
+        assert!(size > 0, "window size must be at least 1");
+        SinkWindow { inner: self, size }
+    }
+
+    /// Emit each run of `size` consecutive items, advancing one item at a
+    /// time.
+    ///
+    /// At most `size` items are buffered. If the effect emits fewer than
+    /// `size` items, they are emitted as one shorter window.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use stillwater::effect::sink::prelude::*;
+    ///
+    /// # tokio_test::block_on(async {
+    /// let effect = emit_many::<_, _, String, ()>(vec![1.0, 2.0, 6.0]).sliding_window(2);
+    ///
+    /// let (_, windows) = effect.run_collecting(&()).await;
+    /// let averages: Vec<f64> = windows.iter().map(|w| w.iter().sum::<f64>() / 2.0).collect();
+    /// assert_eq!(averages, vec![1.5, 4.0]);
+    /// # });
+    /// ```
+    fn sliding_window(self, size: usize) -> SinkSlidingWindow<Self>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+    {
+        assert!(size > 0, "window size must be at least 1");
+        SinkSlidingWindow { inner: self, size }
+    }
+
+    /// Emit consecutive items with the same key together, as
+    /// `(key, items)`.
+    ///
+    /// A group is emitted as soon as an item with a different key arrives,
+    /// so only the current group is buffered. Items are expected to arrive
+    /// ordered by key, as with timestamps bucketed by minute; a key that
+    /// reappears later starts a new group.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use stillwater::effect::sink::prelude::*;
+    ///
+    /// # tokio_test::block_on(async {
+    /// let effect = emit_many::<_, _, String, ()>(vec!["ant", "ape", "bee", "cat", "cow"])
+    ///     .group_by_key(|word| word.chars().next());
+    ///
+    /// let (_, groups) = effect.run_collecting(&()).await;
+    /// assert_eq!(groups[0], (Some('a'), vec!["ant", "ape"]));
+    /// assert_eq!(groups.len(), 3);
+    /// # });
+    /// ```
+    fn group_by_key<F, K>(self, key: F) -> SinkGroupByKey<Self, F>
+    where
+        Self: Sized,
+        F: Fn(&Self::Item) -> K + Send + Sync,
+        K: PartialEq + Send,
+    {
+        SinkGroupByKey { inner: self, key }
+    }
+
     /// Execute and collect all emissions (for testing).
     ///
     /// This bridges SinkEffect to WriterEffect-like semantics,
@@ -249,6 +345,110 @@ pub trait SinkEffectExt: SinkEffect {
         (result, items)
     }
 
+    /// Execute with a sink that can fail, choosing what a failure does.
+    ///
+    /// Each emission is delivered with [`TrySink::try_send`], and a panic
+    /// in the sink counts as a failure. `policy` decides whether failures
+    /// are ignored (fail open), stop the effect (fail closed), or are
+    /// buffered and retried; see [`SinkErrorPolicy`]. The error is
+    /// [`SinkError::Effect`] when the effect itself failed and
+    /// [`SinkError::Sink`] when the policy gave up on the sink.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::sync::atomic::{AtomicBool, Ordering};
+    /// use stillwater::effect::sink::prelude::*;
+    ///
+    /// # tokio_test::block_on(async {
+    /// let audit_log_up = AtomicBool::new(false);
+    /// let sink = |line: String| {
+    ///     let up = audit_log_up.load(Ordering::SeqCst);
+    ///     async move {
+    ///         if up {
+    ///             Ok(())
+    ///         } else {
+    ///             Err(format!("cannot record {:?}", line))
+    ///         }
+    ///     }
+    /// };
+    /// let transfer = || {
+    ///     emit::<_, String, ()>("debit alice".to_string())
+    ///         .and_then(|_| emit("credit bob".to_string()))
+    ///         .map(|_| "done")
+    /// };
+    ///
+    /// // Debug logs can fail open...
+    /// let result = transfer()
+    ///     .run_with_try_sink(&(), &sink, SinkErrorPolicy::Ignore)
+    ///     .await;
+    /// assert_eq!(result, Ok("done"));
+    ///
+    /// // ...but an audit trail must fail closed.
+    /// let result = transfer()
+    ///     .run_with_try_sink(&(), &sink, SinkErrorPolicy::Fail)
+    ///     .await;
+    /// assert!(matches!(result, Err(SinkError::Sink { undelivered: 1, .. })));
+    /// # });
+    /// ```
+    #[allow(async_fn_in_trait)]
+    async fn run_with_try_sink<S>(
+        self,
+        env: &Self::Env,
+        sink: S,
+        policy: SinkErrorPolicy,
+    ) -> Result<Self::Output, SinkError<Self::Error, S::Error>>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+        S: TrySink<Self::Item>,
+    {
+        policy::run_with_try_sink(self, env, sink, policy).await
+    }
+
+    /// Execute, sending each emission to the sink chosen by `router`.
+    ///
+    /// Use this when emissions of different kinds belong in different
+    /// places, such as logs to a file, metrics to a collector, and alerts
+    /// to a channel. See [`SinkRouter`] for how routes are matched.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    /// use std::sync::Arc;
+    /// use stillwater::effect::sink::prelude::*;
+    ///
+    /// # tokio_test::block_on(async {
+    /// let errors = Arc::new(AtomicUsize::new(0));
+    /// let counter = errors.clone();
+    /// let router = SinkRouter::new()
+    ///     .route(|line: &String| line.starts_with("ERROR"), move |_| {
+    ///         counter.fetch_add(1, Ordering::SeqCst);
+    ///         async {}
+    ///     })
+    ///     .fallback(|line| async move { println!("{}", line) });
+    ///
+    /// let effect = emit::<_, String, ()>("ERROR disk full".to_string())
+    ///     .and_then(|_| emit("INFO retrying".to_string()));
+    ///
+    /// assert_eq!(effect.run_with_routed_sinks(&(), router).await, Ok(()));
+    /// assert_eq!(errors.load(Ordering::SeqCst), 1);
+    /// # });
+    /// ```
+    #[allow(async_fn_in_trait)]
+    async fn run_with_routed_sinks(
+        self,
+        env: &Self::Env,
+        router: SinkRouter<Self::Item>,
+    ) -> Result<Self::Output, Self::Error>
+    where
+        Self: Sized,
+        Self::Item: 'static,
+    {
+        self.run_with_sink(env, |item| router.dispatch(item)).await
+    }
+
     /// Execute, discarding all emissions.
     ///
     /// Useful when you only care about the result, not the output.
@@ -306,7 +506,7 @@ pub trait SinkEffectExt: SinkEffect {
         Self: Sized + Send + 'static,
         Self::Output: Send + 'static,
         Self::Error: Send + 'static,
-        Self::Env: Clone + Send + Sync + 'static,
+        Self::Env: Send + Sync + 'static,
         Self::Item: Send + 'static,
     {
         BoxedSinkEffect::new(self)
@@ -129,7 +129,7 @@ where
 pub fn into_sink<E, Env, T>(effect: E) -> IntoSink<E, T>
 where
     E: Effect<Env = Env>,
-    Env: Clone + Send + Sync,
+    Env: Send + Sync,
     T: Send,
 {
     IntoSink {
@@ -57,6 +57,12 @@
 //! - [`SinkEffectExt`] - Extension trait providing combinator methods
 //! - [`emit()`], [`emit_many`] - Functions to emit items
 //! - [`into_sink()`] - Lift regular Effects into SinkEffect
+//! - [`SinkEffectExt::window`], [`SinkEffectExt::sliding_window`],
+//!   [`SinkEffectExt::group_by_key`] - Batch emissions with bounded memory
+//! - [`TrySink`], [`SinkErrorPolicy`] - Sinks that can fail, and whether a
+//!   failure is ignored, fails the effect, or is buffered and retried
+//! - `retry_sink` - Retry with every attempt's items streamed to the sink
+//!   (requires the `async` feature)
 //!
 //! # Example: Testing vs Production
 //!
@@ -85,9 +91,14 @@ mod into_sink;
 mod map;
 mod map_err;
 mod or_else;
+mod policy;
 pub mod prelude;
+#[cfg(feature = "async")]
+mod retry;
+mod router;
 mod tap_emit;
 mod trait_def;
+mod window;
 mod zip;
 
 // Re-export core trait
@@ -108,8 +119,19 @@ pub use map::SinkMap;
 pub use map_err::SinkMapErr;
 pub use or_else::SinkOrElse;
 pub use tap_emit::TapEmit;
+pub use window::{SinkGroupByKey, SinkSlidingWindow, SinkWindow};
 pub use zip::SinkZip;
 
+// Re-export fallible sinks
+pub use policy::{SinkError, SinkErrorPolicy, SinkFailure, TrySink};
+
+// Re-export routing
+pub use router::SinkRouter;
+
+// Re-export retries
+#[cfg(feature = "async")]
+pub use retry::{retry_sink, RetrySink};
+
 // Re-export boxed types
 pub use boxed::BoxedSinkEffect;
 
@@ -0,0 +1,254 @@
+//! Fallible sinks and what a failed delivery does to the effect.
+//!
+//! The sink passed to [`run_with_sink`](crate::effect::sink::SinkEffect::run_with_sink)
+//! cannot report an error, so a sink that writes to a file or a socket has
+//! to swallow its failures or panic. A [`TrySink`] returns a `Result`
+//! instead, and
+//! [`run_with_try_sink`](crate::effect::sink::SinkEffectExt::run_with_try_sink)
+//! applies a [`SinkErrorPolicy`] to each failure. A panic in the sink counts
+//! as a failure too.
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::future::{poll_fn, Future};
+use std::panic::AssertUnwindSafe;
+use std::pin::pin;
+use std::sync::Mutex;
+use std::task::Poll;
+
+use futures::FutureExt;
+
+use crate::effect::panic_message;
+use crate::effect::sink::SinkEffect;
+
+/// A sink whose delivery can fail.
+///
+/// Implemented for every `Fn(Item) -> impl Future<Output = Result<(), E>>`
+/// closure, so most sinks need no impl of their own. A sink type with its
+/// own impl is passed by value, so keep anything the caller needs to
+/// inspect afterwards behind an `Arc`.
+pub trait TrySink<Item>: Send + Sync {
+    /// Why an item could not be delivered.
+    type Error: Send;
+
+    /// Deliver one item.
+    fn try_send(&self, item: Item) -> impl Future<Output = Result<(), Self::Error>> + Send;
+}
+
+impl<F, Fut, Item, Err> TrySink<Item> for F
+where
+    F: Fn(Item) -> Fut + Send + Sync,
+    Fut: Future<Output = Result<(), Err>> + Send,
+    Err: Send,
+{
+    type Error = Err;
+
+    fn try_send(&self, item: Item) -> impl Future<Output = Result<(), Err>> + Send {
+        self(item)
+    }
+}
+
+/// What [`run_with_try_sink`](crate::effect::sink::SinkEffectExt::run_with_try_sink)
+/// does when the sink fails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SinkErrorPolicy {
+    /// Fail open: drop the item and carry on. The effect's result does not
+    /// depend on the sink.
+    Ignore,
+    /// Fail closed: stop the effect where it emitted the item and return
+    /// the sink's error. Nothing after that emission runs.
+    Fail,
+    /// Keep items that could not be delivered and retry them, in order,
+    /// before each later item and once more when the effect finishes.
+    /// Fails like [`Fail`](SinkErrorPolicy::Fail) when more than `capacity`
+    /// items are waiting, or when items are still waiting at the end.
+    ///
+    /// Retrying needs a copy of each item, so items are cloned before
+    /// delivery.
+    Buffer {
+        /// How many undelivered items to hold before failing.
+        capacity: usize,
+    },
+}
+
+/// Why the sink failed to deliver an item.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SinkFailure<S> {
+    /// The sink returned an error.
+    Failed(S),
+    /// The sink panicked; holds the panic message when it was a string.
+    Panicked(String),
+}
+
+impl<S: fmt::Display> fmt::Display for SinkFailure<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Failed(e) => write!(f, "{}", e),
+            Self::Panicked(message) => write!(f, "sink panicked: {}", message),
+        }
+    }
+}
+
+/// The error of [`run_with_try_sink`](crate::effect::sink::SinkEffectExt::run_with_try_sink).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SinkError<E, S> {
+    /// The effect itself failed.
+    Effect(E),
+    /// The sink failed and the policy does not tolerate it.
+    Sink {
+        /// The most recent delivery failure.
+        failure: SinkFailure<S>,
+        /// How many emitted items were never delivered.
+        undelivered: usize,
+    },
+}
+
+impl<E, S> SinkError<E, S> {
+    /// Get the effect's error if present.
+    pub fn into_effect_error(self) -> Option<E> {
+        match self {
+            Self::Effect(e) => Some(e),
+            Self::Sink { .. } => None,
+        }
+    }
+}
+
+impl<E: fmt::Display, S: fmt::Display> fmt::Display for SinkError<E, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Effect(e) => write!(f, "{}", e),
+            Self::Sink {
+                failure,
+                undelivered,
+            } => write!(f, "{} item(s) not delivered: {}", undelivered, failure),
+        }
+    }
+}
+
+impl<E, S> std::error::Error for SinkError<E, S>
+where
+    E: std::error::Error + 'static,
+    S: fmt::Debug + fmt::Display,
+{
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Effect(e) => Some(e),
+            Self::Sink { .. } => None,
+        }
+    }
+}
+
+/// Deliver one item, turning a panic in the sink into a failure.
+async fn deliver<S, Item>(sink: &S, item: Item) -> Result<(), SinkFailure<S::Error>>
+where
+    S: TrySink<Item>,
+{
+    let future = std::panic::catch_unwind(AssertUnwindSafe(|| sink.try_send(item)))
+        .map_err(|payload| SinkFailure::Panicked(panic_message(payload)))?;
+    match AssertUnwindSafe(future).catch_unwind().await {
+        Ok(result) => result.map_err(SinkFailure::Failed),
+        Err(payload) => Err(SinkFailure::Panicked(panic_message(payload))),
+    }
+}
+
+/// Deliver queued items in order, stopping at the first failure.
+async fn flush<S, Item>(sink: &S, queue: &mut VecDeque<Item>) -> Result<(), SinkFailure<S::Error>>
+where
+    S: TrySink<Item>,
+    Item: Clone,
+{
+    while let Some(item) = queue.front().cloned() {
+        deliver(sink, item).await?;
+        queue.pop_front();
+    }
+    Ok(())
+}
+
+struct State<Item, S> {
+    tripped: Option<(SinkFailure<S>, usize)>,
+    buffer: VecDeque<Item>,
+}
+
+pub(crate) async fn run_with_try_sink<Eff, S>(
+    effect: Eff,
+    env: &Eff::Env,
+    sink: S,
+    policy: SinkErrorPolicy,
+) -> Result<Eff::Output, SinkError<Eff::Error, S::Error>>
+where
+    Eff: SinkEffect,
+    Eff::Item: Clone,
+    S: TrySink<Eff::Item>,
+{
+    let state = Mutex::new(State {
+        tripped: None,
+        buffer: VecDeque::new(),
+    });
+    let lock = || state.lock().expect("sink state mutex poisoned");
+    let sink = &sink;
+
+    // A delivery that trips the policy never completes, which suspends the
+    // effect at that emission; the loop below then sees the trip and
+    // returns instead of polling the effect again.
+    let run = effect.run_with_sink(env, move |item| async move {
+        if lock().tripped.is_some() {
+            return std::future::pending().await;
+        }
+        match policy {
+            SinkErrorPolicy::Ignore => {
+                let _ = deliver(sink, item).await;
+            }
+            SinkErrorPolicy::Fail => {
+                if let Err(failure) = deliver(sink, item).await {
+                    lock().tripped = Some((failure, 1));
+                    std::future::pending::<()>().await;
+                }
+            }
+            SinkErrorPolicy::Buffer { capacity } => {
+                let mut queue = std::mem::take(&mut lock().buffer);
+                queue.push_back(item);
+                if let Err(failure) = flush(sink, &mut queue).await {
+                    let overflowed = {
+                        let mut state = lock();
+                        queue.append(&mut state.buffer);
+                        state.buffer = queue;
+                        let waiting = state.buffer.len();
+                        if waiting > capacity {
+                            state.tripped = Some((failure, waiting));
+                        }
+                        waiting > capacity
+                    };
+                    if overflowed {
+                        std::future::pending::<()>().await;
+                    }
+                }
+            }
+        }
+    });
+
+    let mut run = pin!(run);
+    let result = poll_fn(|cx| match run.as_mut().poll(cx) {
+        Poll::Ready(result) => Poll::Ready(Some(result)),
+        Poll::Pending if lock().tripped.is_some() => Poll::Ready(None),
+        Poll::Pending => Poll::Pending,
+    })
+    .await;
+
+    if let Some((failure, undelivered)) = lock().tripped.take() {
+        return Err(SinkError::Sink {
+            failure,
+            undelivered,
+        });
+    }
+    let result = result.expect("effect stops early only when the policy trips");
+
+    let mut queue = std::mem::take(&mut lock().buffer);
+    match (result, flush(sink, &mut queue).await) {
+        (Err(e), _) => Err(SinkError::Effect(e)),
+        (Ok(_), Err(failure)) => Err(SinkError::Sink {
+            failure,
+            undelivered: queue.len(),
+        }),
+        (Ok(value), Ok(())) => Ok(value),
+    }
+}
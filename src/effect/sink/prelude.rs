@@ -34,8 +34,19 @@ pub use crate::effect::sink::map::SinkMap;
 pub use crate::effect::sink::map_err::SinkMapErr;
 pub use crate::effect::sink::or_else::SinkOrElse;
 pub use crate::effect::sink::tap_emit::TapEmit;
+pub use crate::effect::sink::window::{SinkGroupByKey, SinkSlidingWindow, SinkWindow};
 pub use crate::effect::sink::zip::SinkZip;
 
+// Fallible sinks
+pub use crate::effect::sink::policy::{SinkError, SinkErrorPolicy, SinkFailure, TrySink};
+
+// Routing
+pub use crate::effect::sink::router::SinkRouter;
+
+// Retries
+#[cfg(feature = "async")]
+pub use crate::effect::sink::retry::{retry_sink, RetrySink};
+
 // Boxed type
 pub use crate::effect::sink::boxed::BoxedSinkEffect;
 
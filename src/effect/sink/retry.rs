@@ -0,0 +1,144 @@
+//! Retrying sink effects.
+
+use std::future::Future;
+
+use crate::effect::retry::{retry_attempts, timed_attempt};
+use crate::effect::sink::SinkEffect;
+use crate::effect::Effect;
+use crate::retry::{RetryExhausted, RetryPolicy, RetrySuccess, TimeoutError};
+
+/// Retry a sink effect, streaming the items of every attempt to the sink.
+///
+/// Like [`retry`](crate::effect::retry::retry), each attempt runs a fresh
+/// effect from `make_effect` until one succeeds or the policy gives up. All
+/// attempts share the sink passed to
+/// [`run_with_sink`](SinkEffect::run_with_sink), so the items emitted by a
+/// failed attempt have already been delivered when the next one starts, and
+/// the logs leading up to each failure are kept.
+///
+/// Each attempt is bounded by the policy's
+/// [attempt timeout](RetryPolicy::with_attempt_timeout), if it has one, and
+/// errors are reported as [`TimeoutError`].
+///
+/// # Example
+///
+/// ```rust
+/// use std::sync::atomic::{AtomicU32, Ordering};
+/// use std::sync::{Arc, Mutex};
+/// use std::time::Duration;
+///
+/// use stillwater::effect::from_fn;
+/// use stillwater::effect::sink::prelude::*;
+/// use stillwater::RetryPolicy;
+///
+/// # tokio_test::block_on(async {
+/// let calls = Arc::new(AtomicU32::new(0));
+/// let effect = retry_sink(
+///     move || {
+///         let n = calls.fetch_add(1, Ordering::SeqCst) + 1;
+///         emit::<_, String, ()>(format!("attempt {}", n)).and_then(move |_| {
+///             into_sink::<_, _, String>(from_fn(move |_: &()| {
+///                 if n < 2 {
+///                     Err("unavailable".to_string())
+///                 } else {
+///                     Ok(n)
+///                 }
+///             }))
+///         })
+///     },
+///     RetryPolicy::constant(Duration::from_millis(1)).with_max_retries(3),
+/// );
+///
+/// let logs = Mutex::new(Vec::new());
+/// let result = effect
+///     .run_with_sink(&(), |line| {
+///         logs.lock().unwrap().push(line);
+///         async {}
+///     })
+///     .await;
+///
+/// assert_eq!(result.unwrap().attempts, 2);
+/// assert_eq!(*logs.lock().unwrap(), vec!["attempt 1", "attempt 2"]);
+/// # });
+/// ```
+pub fn retry_sink<F, Eff>(make_effect: F, policy: RetryPolicy) -> RetrySink<F>
+where
+    F: Fn() -> Eff + Send,
+    Eff: SinkEffect,
+{
+    RetrySink {
+        make_effect,
+        policy,
+    }
+}
+
+/// Retries a sink effect, streaming the items of every attempt.
+///
+/// Created by [`retry_sink`].
+pub struct RetrySink<F> {
+    pub(crate) make_effect: F,
+    pub(crate) policy: RetryPolicy,
+}
+
+impl<F> std::fmt::Debug for RetrySink<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RetrySink")
+            .field("make_effect", &"<function>")
+            .field("policy", &self.policy)
+            .finish()
+    }
+}
+
+impl<F, Eff> Effect for RetrySink<F>
+where
+    F: Fn() -> Eff + Send,
+    Eff: SinkEffect,
+{
+    type Output = RetrySuccess<Eff::Output>;
+    type Error = RetryExhausted<TimeoutError<Eff::Error>>;
+    type Env = Eff::Env;
+
+    async fn run(self, env: &Self::Env) -> Result<Self::Output, Self::Error> {
+        let policy = &self.policy;
+        let make_effect = self.make_effect;
+        let (result, ()) = retry_attempts(policy, (), move |()| {
+            let effect = make_effect();
+            async move { (timed_attempt(policy, effect.run(env)).await, ()) }
+        })
+        .await;
+        result
+    }
+}
+
+impl<F, Eff> SinkEffect for RetrySink<F>
+where
+    F: Fn() -> Eff + Send,
+    Eff: SinkEffect,
+{
+    type Item = Eff::Item;
+
+    async fn run_with_sink<S, Fut>(
+        self,
+        env: &Self::Env,
+        sink: S,
+    ) -> Result<Self::Output, Self::Error>
+    where
+        S: Fn(Self::Item) -> Fut + Send + Sync,
+        Fut: Future<Output = ()> + Send,
+    {
+        let policy = &self.policy;
+        let make_effect = self.make_effect;
+        let sink = &sink;
+        let (result, ()) = retry_attempts(policy, (), move |()| {
+            let effect = make_effect();
+            async move {
+                (
+                    timed_attempt(policy, effect.run_with_sink(env, sink)).await,
+                    (),
+                )
+            }
+        })
+        .await;
+        result
+    }
+}
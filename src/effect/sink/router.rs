@@ -0,0 +1,158 @@
+//! SinkRouter - dispatch emitted items to different sinks.
+
+use std::fmt;
+use std::future::Future;
+
+use crate::effect::boxed::BoxFuture;
+
+type RouteFn<Item> = Box<dyn Fn(Item) -> Result<BoxFuture<'static, ()>, Item> + Send + Sync>;
+
+/// Routes emitted items to one of several sinks.
+///
+/// Routes are tried in the order they were added, and each item goes to
+/// the first route that accepts it. Items no route accepts go to the
+/// [`fallback`](SinkRouter::fallback) sink, or are dropped if there is none.
+///
+/// Used with
+/// [`SinkEffectExt::run_with_routed_sinks`](crate::effect::sink::SinkEffectExt::run_with_routed_sinks).
+///
+/// # Example
+///
+/// ```rust
+/// use std::sync::{Arc, Mutex};
+/// use stillwater::effect::sink::prelude::*;
+///
+/// #[derive(Debug, Clone, PartialEq)]
+/// enum Event {
+///     Log(String),
+///     Metric(&'static str, f64),
+///     Alert(String),
+/// }
+///
+/// # tokio_test::block_on(async {
+/// let logs = Arc::new(Mutex::new(Vec::new()));
+/// let metrics = Arc::new(Mutex::new(Vec::new()));
+///
+/// let router = SinkRouter::new()
+///     .route(|e: &Event| matches!(e, Event::Log(_)), {
+///         let logs = logs.clone();
+///         move |e| {
+///             logs.lock().unwrap().push(e);
+///             async {}
+///         }
+///     })
+///     .route_map(
+///         |e| match e {
+///             Event::Metric(name, value) => Ok((name, value)),
+///             other => Err(other),
+///         },
+///         {
+///             let metrics = metrics.clone();
+///             move |(name, value)| {
+///                 metrics.lock().unwrap().push(format!("{}:{}", name, value));
+///                 async {}
+///             }
+///         },
+///     );
+///
+/// let effect = emit_many::<_, _, String, ()>(vec![
+///     Event::Log("started".into()),
+///     Event::Metric("latency_ms", 12.5),
+///     Event::Alert("disk full".into()),
+/// ]);
+/// effect.run_with_routed_sinks(&(), router).await.unwrap();
+///
+/// assert_eq!(*logs.lock().unwrap(), vec![Event::Log("started".into())]);
+/// assert_eq!(*metrics.lock().unwrap(), vec!["latency_ms:12.5".to_string()]);
+/// # });
+/// ```
+pub struct SinkRouter<Item> {
+    routes: Vec<RouteFn<Item>>,
+    fallback: Option<Box<dyn Fn(Item) -> BoxFuture<'static, ()> + Send + Sync>>,
+}
+
+impl<Item: Send + 'static> SinkRouter<Item> {
+    /// Create a router with no routes, which drops every item.
+    pub fn new() -> Self {
+        SinkRouter {
+            routes: Vec::new(),
+            fallback: None,
+        }
+    }
+
+    /// Send items matching `predicate` to `sink`.
+    pub fn route<P, S, Fut>(self, predicate: P, sink: S) -> Self
+    where
+        P: Fn(&Item) -> bool + Send + Sync + 'static,
+        S: Fn(Item) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.route_map(
+            move |item| {
+                if predicate(&item) {
+                    Ok(item)
+                } else {
+                    Err(item)
+                }
+            },
+            sink,
+        )
+    }
+
+    /// Send items that `extract` accepts to `sink`.
+    ///
+    /// `extract` returns `Ok` with the value to pass on, such as the
+    /// payload of one enum variant, or gives the item back in `Err` to try
+    /// the next route.
+    pub fn route_map<T, X, S, Fut>(mut self, extract: X, sink: S) -> Self
+    where
+        X: Fn(Item) -> Result<T, Item> + Send + Sync + 'static,
+        S: Fn(T) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.routes.push(Box::new(move |item| {
+            extract(item).map(|value| Box::pin(sink(value)) as BoxFuture<'static, ()>)
+        }));
+        self
+    }
+
+    /// Send items no route accepts to `sink` instead of dropping them.
+    pub fn fallback<S, Fut>(mut self, sink: S) -> Self
+    where
+        S: Fn(Item) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.fallback = Some(Box::new(move |item| Box::pin(sink(item))));
+        self
+    }
+
+    /// The future delivering `item` to its sink.
+    pub(crate) fn dispatch(&self, item: Item) -> BoxFuture<'static, ()> {
+        let mut item = item;
+        for route in &self.routes {
+            match route(item) {
+                Ok(send) => return send,
+                Err(rejected) => item = rejected,
+            }
+        }
+        match &self.fallback {
+            Some(fallback) => fallback(item),
+            None => Box::pin(async {}),
+        }
+    }
+}
+
+impl<Item: Send + 'static> Default for SinkRouter<Item> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Item> fmt::Debug for SinkRouter<Item> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SinkRouter")
+            .field("routes", &self.routes.len())
+            .field("fallback", &self.fallback.as_ref().map(|_| "<function>"))
+            .finish()
+    }
+}
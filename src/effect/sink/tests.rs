@@ -216,6 +216,86 @@ mod tap_emit_tests {
     }
 }
 
+mod window_tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn window_flushes_partial_window_on_error() {
+        let effect = emit_many::<_, _, String, ()>(vec![1, 2, 3])
+            .and_then(|_| into_sink(fail::<(), String, ()>("stopped".to_string())))
+            .window(2);
+
+        let (result, windows) = effect.run_collecting(&()).await;
+
+        assert_eq!(result, Err("stopped".to_string()));
+        assert_eq!(windows, vec![vec![1, 2], vec![3]]);
+    }
+
+    #[tokio::test]
+    async fn sliding_window_short_input_emits_once() {
+        let effect = emit_many::<_, _, String, ()>(vec!['a', 'b']).sliding_window(3);
+
+        let (_, windows) = effect.run_collecting(&()).await;
+
+        assert_eq!(windows, vec![vec!['a', 'b']]);
+    }
+
+    #[tokio::test]
+    async fn group_by_key_starts_new_group_when_key_returns() {
+        let effect = emit_many::<_, _, String, ()>(vec![1, 3, 2, 5]).group_by_key(|n| n % 2 == 0);
+
+        let (_, groups) = effect.run_collecting(&()).await;
+
+        assert_eq!(
+            groups,
+            vec![(false, vec![1, 3]), (true, vec![2]), (false, vec![5])]
+        );
+    }
+}
+
+mod router_tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    #[tokio::test]
+    async fn first_matching_route_wins_and_rest_fall_back() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let record = |label: &'static str| {
+            let seen = Arc::clone(&seen);
+            move |n: i32| {
+                seen.lock().expect("mutex").push((label, n));
+                async {}
+            }
+        };
+        let router = SinkRouter::new()
+            .route(|n: &i32| *n > 10, record("big"))
+            .route(|n: &i32| *n > 5, record("medium"))
+            .fallback(record("small"));
+
+        let result = emit_many::<_, _, String, ()>(vec![20, 7, 1])
+            .run_with_routed_sinks(&(), router)
+            .await;
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            *seen.lock().expect("mutex"),
+            vec![("big", 20), ("medium", 7), ("small", 1)]
+        );
+    }
+
+    #[tokio::test]
+    async fn unrouted_items_are_dropped_without_fallback() {
+        let router = SinkRouter::new().route(|_: &i32| false, |_| async {});
+
+        let result = emit::<_, String, ()>(1)
+            .map(|_| "done")
+            .run_with_routed_sinks(&(), router)
+            .await;
+
+        assert_eq!(result, Ok("done"));
+    }
+}
+
 mod traverse_sink_tests {
     use super::*;
 
@@ -405,7 +485,7 @@ mod integration_tests {
     #[tokio::test]
     async fn dual_execution_patterns() {
         // Create a logging effect that works with both patterns
-        fn log_operation<Env: Clone + Send + Sync>(
+        fn log_operation<Env: Send + Sync>(
             name: &str,
             value: i32,
         ) -> impl SinkEffect<Output = i32, Error = String, Env = Env, Item = String> {
@@ -441,3 +521,187 @@ mod integration_tests {
         assert_eq!(streamed.lock().expect("mutex").len(), 2);
     }
 }
+
+mod try_sink_tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    /// A sink that fails the deliveries whose 1-based numbers are listed
+    /// and records the rest.
+    struct Flaky {
+        failing: Vec<usize>,
+        calls: AtomicUsize,
+        delivered: Arc<Mutex<Vec<i32>>>,
+    }
+
+    impl Flaky {
+        /// The sink, and a handle to the items it delivered.
+        fn new(failing: Vec<usize>) -> (Self, Arc<Mutex<Vec<i32>>>) {
+            let delivered = Arc::new(Mutex::new(Vec::new()));
+            let sink = Flaky {
+                failing,
+                calls: AtomicUsize::new(0),
+                delivered: delivered.clone(),
+            };
+            (sink, delivered)
+        }
+    }
+
+    impl TrySink<i32> for Flaky {
+        type Error = String;
+
+        async fn try_send(&self, item: i32) -> Result<(), String> {
+            let call = self.calls.fetch_add(1, Ordering::SeqCst) + 1;
+            if self.failing.contains(&call) {
+                Err(format!("call {} failed", call))
+            } else {
+                self.delivered.lock().expect("mutex").push(item);
+                Ok(())
+            }
+        }
+    }
+
+    fn three_items(
+        ran_to_end: &AtomicUsize,
+    ) -> impl SinkEffect<Output = i32, Error = String, Env = (), Item = i32> + '_ {
+        emit_many::<_, _, String, ()>(vec![1, 2, 3]).map(move |_| {
+            ran_to_end.fetch_add(1, Ordering::SeqCst);
+            42
+        })
+    }
+
+    #[tokio::test]
+    async fn test_ignore_drops_failed_items() {
+        let (sink, delivered) = Flaky::new(vec![2]);
+        let ran = AtomicUsize::new(0);
+
+        let result = three_items(&ran)
+            .run_with_try_sink(&(), sink, SinkErrorPolicy::Ignore)
+            .await;
+
+        assert_eq!(result, Ok(42));
+        assert_eq!(*delivered.lock().expect("mutex"), vec![1, 3]);
+    }
+
+    #[tokio::test]
+    async fn test_fail_stops_the_effect() {
+        let (sink, delivered) = Flaky::new(vec![2]);
+        let ran = AtomicUsize::new(0);
+
+        let result = three_items(&ran)
+            .run_with_try_sink(&(), sink, SinkErrorPolicy::Fail)
+            .await;
+
+        assert_eq!(
+            result,
+            Err(SinkError::Sink {
+                failure: SinkFailure::Failed("call 2 failed".to_string()),
+                undelivered: 1,
+            })
+        );
+        assert_eq!(*delivered.lock().expect("mutex"), vec![1]);
+        assert_eq!(ran.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_buffer_redelivers_in_order() {
+        let (sink, delivered) = Flaky::new(vec![1, 2]);
+        let ran = AtomicUsize::new(0);
+
+        let result = three_items(&ran)
+            .run_with_try_sink(&(), sink, SinkErrorPolicy::Buffer { capacity: 4 })
+            .await;
+
+        assert_eq!(result, Ok(42));
+        assert_eq!(*delivered.lock().expect("mutex"), vec![1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn test_buffer_fails_when_full_or_left_over() {
+        let ran = AtomicUsize::new(0);
+        let (overflowing, _) = Flaky::new(vec![1, 2, 3]);
+        let (down_at_end, delivered) = Flaky::new(vec![3, 4]);
+
+        let overflowed = three_items(&ran)
+            .run_with_try_sink(&(), overflowing, SinkErrorPolicy::Buffer { capacity: 1 })
+            .await;
+        let left_over = three_items(&ran)
+            .run_with_try_sink(&(), down_at_end, SinkErrorPolicy::Buffer { capacity: 4 })
+            .await;
+
+        assert!(matches!(
+            overflowed,
+            Err(SinkError::Sink { undelivered: 2, .. })
+        ));
+        assert!(matches!(
+            left_over,
+            Err(SinkError::Sink { undelivered: 1, .. })
+        ));
+        assert_eq!(*delivered.lock().expect("mutex"), vec![1, 2]);
+    }
+
+    #[tokio::test]
+    async fn test_sink_panic_is_a_failure_and_effect_errors_pass_through() {
+        let panicking = |_: i32| async { panic!("disk on fire") };
+
+        let result = emit::<_, String, ()>(1)
+            .run_with_try_sink(&(), panicking, SinkErrorPolicy::Fail)
+            .await;
+        let failed = fail::<(), String, ()>("bad input".to_string());
+        let effect_error = emit::<i32, String, ()>(1)
+            .and_then(|_| into_sink(failed))
+            .run_with_try_sink(
+                &(),
+                |_: i32| async { Ok::<_, String>(()) },
+                SinkErrorPolicy::Fail,
+            )
+            .await;
+
+        assert_eq!(
+            result,
+            Err(SinkError::Sink {
+                failure: SinkFailure::<String>::Panicked("disk on fire".to_string()),
+                undelivered: 1,
+            })
+        );
+        assert_eq!(
+            effect_error,
+            Err(SinkError::Effect("bad input".to_string()))
+        );
+    }
+}
+
+#[cfg(feature = "async")]
+mod retry_tests {
+    use super::*;
+    use crate::effect::from_fn;
+    use crate::RetryPolicy;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_retry_sink_delivers_items_of_failed_attempts() {
+        let calls = Arc::new(AtomicU32::new(0));
+        let effect = retry_sink(
+            move || {
+                let n = calls.fetch_add(1, Ordering::SeqCst) + 1;
+                emit::<_, String, ()>(n).and_then(move |_| {
+                    into_sink::<_, _, u32>(from_fn(move |_: &()| {
+                        Err::<(), _>(format!("failure {}", n))
+                    }))
+                })
+            },
+            RetryPolicy::constant(Duration::from_millis(1)).with_max_retries(2),
+        );
+
+        let (result, collected) = effect.run_collecting(&()).await;
+
+        assert_eq!(
+            result.unwrap_err().final_error,
+            crate::TimeoutError::Inner("failure 3".to_string())
+        );
+        assert_eq!(collected, vec![1, 2, 3]);
+    }
+}
@@ -0,0 +1,289 @@
+//! Windowing combinators - batch emitted items before they reach the sink.
+//!
+//! Each combinator buffers at most one window of items, so memory stays
+//! bounded by the window size rather than the number of emissions. Items
+//! still buffered when the inner effect finishes are flushed as a final,
+//! possibly shorter window, whether the effect succeeded or failed.
+
+use std::collections::VecDeque;
+use std::future::Future;
+use std::sync::Mutex;
+
+use crate::effect::sink::SinkEffect;
+use crate::effect::Effect;
+
+/// Emits items in non-overlapping windows of a fixed size.
+///
+/// Created by [`SinkEffectExt::window`](crate::effect::sink::SinkEffectExt::window).
+///
+/// # Example
+///
+/// ```rust
+/// use stillwater::effect::sink::prelude::*;
+///
+/// # tokio_test::block_on(async {
+/// let effect = emit_many::<_, _, String, ()>(vec![1, 2, 3, 4, 5]).window(2);
+///
+/// let (_, windows) = effect.run_collecting(&()).await;
+/// assert_eq!(windows, vec![vec![1, 2], vec![3, 4], vec![5]]);
+/// # });
+/// ```
+pub struct SinkWindow<E> {
+    pub(crate) inner: E,
+    pub(crate) size: usize,
+}
+
+impl<E> std::fmt::Debug for SinkWindow<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SinkWindow")
+            .field("inner", &"<effect>")
+            .field("size", &self.size)
+            .finish()
+    }
+}
+
+impl<E: SinkEffect> Effect for SinkWindow<E> {
+    type Output = E::Output;
+    type Error = E::Error;
+    type Env = E::Env;
+
+    async fn run(self, env: &Self::Env) -> Result<Self::Output, Self::Error> {
+        self.inner.run(env).await
+    }
+}
+
+impl<E: SinkEffect> SinkEffect for SinkWindow<E> {
+    type Item = Vec<E::Item>;
+
+    async fn run_with_sink<S, Fut>(
+        self,
+        env: &Self::Env,
+        sink: S,
+    ) -> Result<Self::Output, Self::Error>
+    where
+        S: Fn(Self::Item) -> Fut + Send + Sync,
+        Fut: Future<Output = ()> + Send,
+    {
+        let size = self.size;
+        let buffer = Mutex::new(Vec::with_capacity(size));
+        let sink = &sink;
+
+        let result = self
+            .inner
+            .run_with_sink(env, |item| {
+                let full = {
+                    let mut buffer = buffer.lock().expect("mutex poisoned");
+                    buffer.push(item);
+                    (buffer.len() == size)
+                        .then(|| std::mem::replace(&mut *buffer, Vec::with_capacity(size)))
+                };
+                let send = full.map(sink);
+                async move {
+                    if let Some(send) = send {
+                        send.await;
+                    }
+                }
+            })
+            .await;
+
+        let rest = buffer.into_inner().expect("mutex poisoned");
+        if !rest.is_empty() {
+            sink(rest).await;
+        }
+        result
+    }
+}
+
+/// Emits overlapping windows of the most recent items.
+///
+/// Created by
+/// [`SinkEffectExt::sliding_window`](crate::effect::sink::SinkEffectExt::sliding_window).
+///
+/// # Example
+///
+/// ```rust
+/// use stillwater::effect::sink::prelude::*;
+///
+/// # tokio_test::block_on(async {
+/// let effect = emit_many::<_, _, String, ()>(vec![1, 2, 3, 4]).sliding_window(3);
+///
+/// let (_, windows) = effect.run_collecting(&()).await;
+/// assert_eq!(windows, vec![vec![1, 2, 3], vec![2, 3, 4]]);
+/// # });
+/// ```
+pub struct SinkSlidingWindow<E> {
+    pub(crate) inner: E,
+    pub(crate) size: usize,
+}
+
+impl<E> std::fmt::Debug for SinkSlidingWindow<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SinkSlidingWindow")
+            .field("inner", &"<effect>")
+            .field("size", &self.size)
+            .finish()
+    }
+}
+
+impl<E> Effect for SinkSlidingWindow<E>
+where
+    E: SinkEffect,
+    E::Item: Clone,
+{
+    type Output = E::Output;
+    type Error = E::Error;
+    type Env = E::Env;
+
+    async fn run(self, env: &Self::Env) -> Result<Self::Output, Self::Error> {
+        self.inner.run(env).await
+    }
+}
+
+impl<E> SinkEffect for SinkSlidingWindow<E>
+where
+    E: SinkEffect,
+    E::Item: Clone,
+{
+    type Item = Vec<E::Item>;
+
+    async fn run_with_sink<S, Fut>(
+        self,
+        env: &Self::Env,
+        sink: S,
+    ) -> Result<Self::Output, Self::Error>
+    where
+        S: Fn(Self::Item) -> Fut + Send + Sync,
+        Fut: Future<Output = ()> + Send,
+    {
+        let size = self.size;
+        let buffer = Mutex::new(VecDeque::with_capacity(size));
+        let sink = &sink;
+
+        let result = self
+            .inner
+            .run_with_sink(env, |item| {
+                let full = {
+                    let mut buffer = buffer.lock().expect("mutex poisoned");
+                    if buffer.len() == size {
+                        buffer.pop_front();
+                    }
+                    buffer.push_back(item);
+                    (buffer.len() == size).then(|| buffer.iter().cloned().collect())
+                };
+                let send = full.map(sink);
+                async move {
+                    if let Some(send) = send {
+                        send.await;
+                    }
+                }
+            })
+            .await;
+
+        // Fewer items than one window: emit what there is.
+        let rest = buffer.into_inner().expect("mutex poisoned");
+        if !rest.is_empty() && rest.len() < size {
+            sink(rest.into()).await;
+        }
+        result
+    }
+}
+
+/// Emits runs of consecutive items that share a key.
+///
+/// Created by
+/// [`SinkEffectExt::group_by_key`](crate::effect::sink::SinkEffectExt::group_by_key).
+///
+/// # Example
+///
+/// ```rust
+/// use stillwater::effect::sink::prelude::*;
+///
+/// # tokio_test::block_on(async {
+/// // (second, value) samples, grouped into one-minute buckets
+/// let samples = vec![(5, 1.0), (42, 3.0), (61, 2.0), (130, 4.0)];
+/// let effect = emit_many::<_, _, String, ()>(samples).group_by_key(|(secs, _)| secs / 60);
+///
+/// let (_, groups) = effect.run_collecting(&()).await;
+/// let keys: Vec<_> = groups.iter().map(|(minute, samples)| (*minute, samples.len())).collect();
+/// assert_eq!(keys, vec![(0, 2), (1, 1), (2, 1)]);
+/// # });
+/// ```
+pub struct SinkGroupByKey<E, F> {
+    pub(crate) inner: E,
+    pub(crate) key: F,
+}
+
+impl<E, F> std::fmt::Debug for SinkGroupByKey<E, F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SinkGroupByKey")
+            .field("inner", &"<effect>")
+            .field("key", &"<function>")
+            .finish()
+    }
+}
+
+impl<E, F, K> Effect for SinkGroupByKey<E, F>
+where
+    E: SinkEffect,
+    F: Fn(&E::Item) -> K + Send + Sync,
+    K: PartialEq + Send,
+{
+    type Output = E::Output;
+    type Error = E::Error;
+    type Env = E::Env;
+
+    async fn run(self, env: &Self::Env) -> Result<Self::Output, Self::Error> {
+        self.inner.run(env).await
+    }
+}
+
+impl<E, F, K> SinkEffect for SinkGroupByKey<E, F>
+where
+    E: SinkEffect,
+    F: Fn(&E::Item) -> K + Send + Sync,
+    K: PartialEq + Send,
+{
+    type Item = (K, Vec<E::Item>);
+
+    async fn run_with_sink<S, Fut>(
+        self,
+        env: &Self::Env,
+        sink: S,
+    ) -> Result<Self::Output, Self::Error>
+    where
+        S: Fn(Self::Item) -> Fut + Send + Sync,
+        Fut: Future<Output = ()> + Send,
+    {
+        let key_of = &self.key;
+        let current: Mutex<Option<Self::Item>> = Mutex::new(None);
+        let sink = &sink;
+
+        let result = self
+            .inner
+            .run_with_sink(env, |item| {
+                let key = key_of(&item);
+                let finished = {
+                    let mut current = current.lock().expect("mutex poisoned");
+                    match current.as_mut() {
+                        Some((current_key, items)) if *current_key == key => {
+                            items.push(item);
+                            None
+                        }
+                        _ => current.replace((key, vec![item])),
+                    }
+                };
+                let send = finished.map(sink);
+                async move {
+                    if let Some(send) = send {
+                        send.await;
+                    }
+                }
+            })
+            .await;
+
+        if let Some(group) = current.into_inner().expect("mutex poisoned") {
+            sink(group).await;
+        }
+        result
+    }
+}
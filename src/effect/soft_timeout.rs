@@ -0,0 +1,256 @@
+//! Timeouts that ask an effect to wrap up before cancelling it.
+//!
+//! [`with_timeout`](crate::effect::retry::with_timeout) drops an effect the
+//! moment its time is up, throwing away whatever it had done so far.
+//! [`with_soft_timeout`] escalates instead: when the timeout passes it
+//! raises a [`SoftCancel`] signal that the effect was given when it was
+//! built, then allows a grace period for the effect to notice and return
+//! early, typically with a partial result. Only if the grace period also
+//! runs out is the effect dropped and the fallback used.
+//!
+//! The signal is cooperative. An effect that never checks it behaves as
+//! under a hard timeout of `timeout + grace`.
+//!
+//! Requires the `async` feature.
+//!
+//! # Example
+//!
+//! ```rust
+//! use std::time::Duration;
+//! use stillwater::effect::prelude::*;
+//! use stillwater::effect::soft_timeout::with_soft_timeout;
+//!
+//! # tokio_test::block_on(async {
+//! // Scans pages until told to stop, then reports what it has
+//! let search = with_soft_timeout(
+//!     |cancel| {
+//!         from_async(move |_: &()| async move {
+//!             let mut pages_scanned = 0;
+//!             while !cancel.is_requested() {
+//!                 tokio::time::sleep(Duration::from_millis(5)).await;
+//!                 pages_scanned += 1;
+//!             }
+//!             Ok::<_, String>(pages_scanned)
+//!         })
+//!     },
+//!     Duration::from_millis(50),
+//!     Duration::from_millis(50),
+//!     || Err("search did not stop in time".to_string()),
+//! );
+//!
+//! let pages = search.run(&()).await.unwrap();
+//! assert!(pages > 0);
+//! # });
+//! ```
+
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::Notify;
+
+use crate::effect::boxed::BoxedEffect;
+use crate::effect::ext::EffectExt;
+use crate::effect::trait_def::Effect;
+
+/// A signal asking an effect to finish early.
+///
+/// Handed to the effect factory of [`with_soft_timeout`], and raised when
+/// the timeout passes. Cloning gives another handle to the same signal.
+#[derive(Clone, Default)]
+pub struct SoftCancel {
+    shared: Arc<Shared>,
+}
+
+#[derive(Default)]
+struct Shared {
+    requested: AtomicBool,
+    notify: Notify,
+}
+
+impl SoftCancel {
+    /// A signal that has not been raised.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether the effect has been asked to finish.
+    pub fn is_requested(&self) -> bool {
+        self.shared.requested.load(Ordering::SeqCst)
+    }
+
+    /// Wait until the effect is asked to finish.
+    ///
+    /// Useful in a `select!` against the effect's own work.
+    pub async fn requested(&self) {
+        let notified = self.shared.notify.notified();
+        tokio::pin!(notified);
+        notified.as_mut().enable();
+        if self.is_requested() {
+            return;
+        }
+        notified.await;
+    }
+
+    /// Raise the signal, waking everything waiting in
+    /// [`requested`](Self::requested).
+    pub fn request(&self) {
+        self.shared.requested.store(true, Ordering::SeqCst);
+        self.shared.notify.notify_waiters();
+    }
+}
+
+impl fmt::Debug for SoftCancel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SoftCancel")
+            .field("requested", &self.is_requested())
+            .finish()
+    }
+}
+
+/// Run the effect built by `make_effect`, asking it to finish once
+/// `timeout` has passed and cancelling it after a further `grace`.
+///
+/// - If the effect completes within `timeout`, its result is returned.
+/// - Otherwise its [`SoftCancel`] is raised, and if it completes within
+///   `grace`, that result is returned.
+/// - Otherwise it is dropped and `on_timeout` supplies the result.
+pub fn with_soft_timeout<T, E, Env, F, Eff, Fb>(
+    make_effect: F,
+    timeout: Duration,
+    grace: Duration,
+    on_timeout: Fb,
+) -> BoxedEffect<T, E, Env>
+where
+    T: Send + 'static,
+    E: Send + 'static,
+    Env: Send + Sync + 'static,
+    F: FnOnce(SoftCancel) -> Eff + Send + 'static,
+    Eff: Effect<Output = T, Error = E, Env = Env> + 'static,
+    Fb: FnOnce() -> Result<T, E> + Send + 'static,
+{
+    SoftTimeout {
+        make_effect,
+        timeout,
+        grace,
+        on_timeout,
+    }
+    .boxed()
+}
+
+/// The effect built by [`with_soft_timeout`].
+struct SoftTimeout<F, Fb> {
+    make_effect: F,
+    timeout: Duration,
+    grace: Duration,
+    on_timeout: Fb,
+}
+
+impl<T, E, Env, F, Eff, Fb> Effect for SoftTimeout<F, Fb>
+where
+    T: Send,
+    E: Send,
+    Env: Send + Sync,
+    F: FnOnce(SoftCancel) -> Eff + Send,
+    Eff: Effect<Output = T, Error = E, Env = Env>,
+    Fb: FnOnce() -> Result<T, E> + Send,
+{
+    type Output = T;
+    type Error = E;
+    type Env = Env;
+
+    async fn run(self, env: &Env) -> Result<T, E> {
+        let cancel = SoftCancel::new();
+        let run = (self.make_effect)(cancel.clone()).run(env);
+        tokio::pin!(run);
+
+        if let Ok(result) = tokio::time::timeout(self.timeout, &mut run).await {
+            return result;
+        }
+        cancel.request();
+        match tokio::time::timeout(self.grace, run).await {
+            Ok(result) => result,
+            Err(_) => (self.on_timeout)(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::effect::constructors::{from_async, pure};
+
+    fn partial_sum(cancel: SoftCancel) -> impl Effect<Output = u32, Error = String, Env = ()> {
+        from_async(move |_: &()| async move {
+            let mut sum = 0;
+            for step in 1..=100 {
+                tokio::select! {
+                    _ = tokio::time::sleep(Duration::from_secs(1)) => sum += step,
+                    _ = cancel.requested() => break,
+                }
+            }
+            Ok(sum)
+        })
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_effect_finishing_in_time_is_untouched() {
+        let effect = with_soft_timeout(
+            |cancel| pure::<_, String, ()>(cancel.is_requested()),
+            Duration::from_secs(1),
+            Duration::from_secs(1),
+            || Err("fallback".to_string()),
+        );
+
+        assert_eq!(effect.run(&()).await, Ok(false));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_cooperative_effect_returns_partial_result() {
+        let start = tokio::time::Instant::now();
+        let effect = with_soft_timeout(
+            partial_sum,
+            Duration::from_millis(3500),
+            Duration::from_secs(10),
+            || Err("fallback".to_string()),
+        );
+
+        assert_eq!(effect.run(&()).await, Ok(1 + 2 + 3));
+        assert_eq!(start.elapsed(), Duration::from_millis(3500));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_uncooperative_effect_falls_back_after_grace() {
+        let start = tokio::time::Instant::now();
+        let effect = with_soft_timeout(
+            |_cancel| {
+                from_async(|_: &()| async {
+                    tokio::time::sleep(Duration::from_secs(3600)).await;
+                    Ok::<u32, String>(1)
+                })
+            },
+            Duration::from_secs(2),
+            Duration::from_secs(3),
+            || Ok(0),
+        );
+
+        assert_eq!(effect.run(&()).await, Ok(0));
+        assert_eq!(start.elapsed(), Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn test_requested_returns_once_raised() {
+        let cancel = SoftCancel::new();
+        let waiter = tokio::spawn({
+            let cancel = cancel.clone();
+            async move { cancel.requested().await }
+        });
+
+        tokio::task::yield_now().await;
+        cancel.request();
+        waiter.await.unwrap();
+        assert!(cancel.is_requested());
+        cancel.requested().await;
+    }
+}
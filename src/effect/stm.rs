@@ -0,0 +1,663 @@
+//! Software transactional memory for in-memory state.
+//!
+//! A [`TVar`] is a shared variable that is read and written inside a
+//! transaction. [`atomically`] turns a transaction into an effect: the
+//! transaction runs against a private log, and its writes are committed only
+//! if none of the variables it read changed in the meantime. On a conflict
+//! the transaction is discarded and run again, so it must not perform side
+//! effects of its own.
+//!
+//! Every read is checked against the earlier reads of the same run, so a
+//! transaction never observes a mix of values from before and after another
+//! commit. A transaction that returns [`StmError::Retry`] is parked until
+//! another transaction commits a write to one of the variables it read.
+//!
+//! This gives concurrent effects a way to update several pieces of state
+//! together without choosing lock orders by hand.
+//!
+//! # Example
+//!
+//! ```rust
+//! use stillwater::effect::prelude::*;
+//! use stillwater::effect::stm::{atomically, StmError, TVar};
+//!
+//! # tokio_test::block_on(async {
+//! let checking = TVar::new(100_u32);
+//! let savings = TVar::new(0_u32);
+//!
+//! let transfer = |amount: u32| {
+//!     let (from, to) = (checking.clone(), savings.clone());
+//!     atomically::<_, _, String, ()>(move |tx| {
+//!         let balance = tx.read(&from)?;
+//!         if balance < amount {
+//!             return Err(StmError::Abort(format!("insufficient funds: {}", balance)));
+//!         }
+//!         tx.write(&from, balance - amount);
+//!         tx.modify(&to, |b| b + amount)?;
+//!         Ok(balance - amount)
+//!     })
+//! };
+//!
+//! assert_eq!(transfer(60).run(&()).await, Ok(40));
+//! assert!(transfer(60).run(&()).await.is_err());
+//! assert_eq!((checking.load(), savings.load()), (40, 60));
+//! # });
+//! ```
+
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::future::Future;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::task::{Context, Poll, Waker};
+
+use crate::effect::trait_def::Effect;
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Why a transaction stopped before committing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StmError<E> {
+    /// Discard the transaction and run it again, e.g. because a value it
+    /// read is stale or a condition it waits for doesn't hold yet.
+    Retry,
+    /// Discard the transaction and fail the effect with this error.
+    Abort(E),
+}
+
+impl<E: fmt::Display> fmt::Display for StmError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Retry => write!(f, "transaction retry"),
+            Self::Abort(e) => write!(f, "transaction aborted: {}", e),
+        }
+    }
+}
+
+impl<E: std::error::Error + 'static> std::error::Error for StmError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Retry => None,
+            Self::Abort(e) => Some(e),
+        }
+    }
+}
+
+/// Result of a transaction step.
+pub type StmResult<T, E = std::convert::Infallible> = Result<T, StmError<E>>;
+
+struct Slot<T> {
+    version: u64,
+    value: T,
+    /// Transactions parked on [`StmError::Retry`] after reading this
+    /// variable, woken by the next commit that writes it.
+    waiters: Vec<Waker>,
+}
+
+/// A transactional variable.
+///
+/// Cloning produces another handle to the same variable.
+pub struct TVar<T> {
+    id: u64,
+    slot: Arc<Mutex<Slot<T>>>,
+}
+
+impl<T: Clone + Send + 'static> TVar<T> {
+    /// Create a variable holding `value`.
+    pub fn new(value: T) -> Self {
+        TVar {
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+            slot: Arc::new(Mutex::new(Slot {
+                version: 0,
+                value,
+                waiters: Vec::new(),
+            })),
+        }
+    }
+
+    /// Read the committed value outside of a transaction.
+    pub fn load(&self) -> T {
+        lock(&self.slot).value.clone()
+    }
+}
+
+impl<T> Clone for TVar<T> {
+    fn clone(&self) -> Self {
+        TVar {
+            id: self.id,
+            slot: Arc::clone(&self.slot),
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for TVar<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let slot = lock(&self.slot);
+        f.debug_struct("TVar")
+            .field("id", &self.id)
+            .field("version", &slot.version)
+            .field("value", &slot.value)
+            .finish()
+    }
+}
+
+fn lock<T>(slot: &Mutex<Slot<T>>) -> MutexGuard<'_, Slot<T>> {
+    slot.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Type-erased access to a variable's slot, used for validation, commits,
+/// and parking.
+trait Cell: Send + Sync {
+    fn lock(&self) -> Box<dyn LockedCell + '_>;
+    fn version(&self) -> u64;
+}
+
+trait LockedCell {
+    fn version(&self) -> u64;
+    /// Store a new value, returning the parked transactions to wake.
+    fn store(&mut self, value: Box<dyn Any + Send>) -> Vec<Waker>;
+    fn park(&mut self, waker: &Waker);
+}
+
+impl<T: Send + 'static> Cell for Mutex<Slot<T>> {
+    fn lock(&self) -> Box<dyn LockedCell + '_> {
+        Box::new(lock(self))
+    }
+
+    fn version(&self) -> u64 {
+        lock(self).version
+    }
+}
+
+impl<T: 'static> LockedCell for MutexGuard<'_, Slot<T>> {
+    fn version(&self) -> u64 {
+        self.version
+    }
+
+    fn store(&mut self, value: Box<dyn Any + Send>) -> Vec<Waker> {
+        self.value = *value
+            .downcast::<T>()
+            .expect("transaction log holds the variable's type");
+        self.version += 1;
+        std::mem::take(&mut self.waiters)
+    }
+
+    fn park(&mut self, waker: &Waker) {
+        if !self.waiters.iter().any(|w| w.will_wake(waker)) {
+            self.waiters.push(waker.clone());
+        }
+    }
+}
+
+struct Entry {
+    cell: Arc<dyn Cell>,
+    read_version: Option<u64>,
+    write: Option<Box<dyn Any + Send>>,
+}
+
+/// The log of a running transaction, passed to the closure given to
+/// [`atomically`].
+pub struct Tx {
+    entries: BTreeMap<u64, Entry>,
+}
+
+impl Tx {
+    fn new() -> Self {
+        Tx {
+            entries: BTreeMap::new(),
+        }
+    }
+
+    fn entry<T: Send + 'static>(&mut self, var: &TVar<T>) -> &mut Entry {
+        self.entries.entry(var.id).or_insert_with(|| Entry {
+            cell: Arc::clone(&var.slot) as Arc<dyn Cell>,
+            read_version: None,
+            write: None,
+        })
+    }
+
+    /// Read a variable, seeing this transaction's own writes.
+    ///
+    /// Returns [`StmError::Retry`] if the variable changed since this
+    /// transaction first read it, or if reading it for the first time finds
+    /// that a variable read earlier has changed. Either way the values read
+    /// so far no longer belong to one snapshot, and the transaction is run
+    /// again instead of continuing on them.
+    pub fn read<T, E>(&mut self, var: &TVar<T>) -> StmResult<T, E>
+    where
+        T: Clone + Send + 'static,
+    {
+        let entry = self.entry(var);
+        if let Some(value) = &entry.write {
+            return Ok(value
+                .downcast_ref::<T>()
+                .expect("transaction log holds the variable's type")
+                .clone());
+        }
+
+        let (version, value) = {
+            let slot = lock(&var.slot);
+            (slot.version, slot.value.clone())
+        };
+        match entry.read_version {
+            Some(read_version) if read_version != version => Err(StmError::Retry),
+            Some(_) => Ok(value),
+            None => {
+                entry.read_version = Some(version);
+                // Versions only grow, so an earlier read that still has its
+                // version now had it when `var` was read too.
+                if self.reads_are_current(var.id) {
+                    Ok(value)
+                } else {
+                    Err(StmError::Retry)
+                }
+            }
+        }
+    }
+
+    /// Write a variable; the value becomes visible to others on commit.
+    pub fn write<T>(&mut self, var: &TVar<T>, value: T)
+    where
+        T: Clone + Send + 'static,
+    {
+        self.entry(var).write = Some(Box::new(value));
+    }
+
+    /// Replace a variable's value with `f` applied to it.
+    pub fn modify<T, E, F>(&mut self, var: &TVar<T>, f: F) -> StmResult<(), E>
+    where
+        T: Clone + Send + 'static,
+        F: FnOnce(T) -> T,
+    {
+        let value = self.read(var)?;
+        self.write(var, f(value));
+        Ok(())
+    }
+
+    /// Whether every variable read, other than `skip`, is still at the
+    /// version it was read at. The variables are checked one at a time.
+    fn reads_are_current(&self, skip: u64) -> bool {
+        self.entries.iter().all(|(id, entry)| {
+            *id == skip || entry.read_version.is_none_or(|v| v == entry.cell.version())
+        })
+    }
+
+    /// The variables read and the versions they were read at, for parking
+    /// on [`StmError::Retry`].
+    fn read_set(&self) -> Vec<(Arc<dyn Cell>, u64)> {
+        self.entries
+            .values()
+            .filter_map(|entry| Some((Arc::clone(&entry.cell), entry.read_version?)))
+            .collect()
+    }
+
+    /// Whether every variable read is still at the version it was read at.
+    ///
+    /// The variables are locked together, in id order, so a `true` means
+    /// the reads formed one consistent snapshot.
+    fn is_consistent(&self) -> bool {
+        let guards: Vec<_> = self
+            .entries
+            .values()
+            .map(|entry| (entry.cell.lock(), entry.read_version))
+            .collect();
+        guards
+            .iter()
+            .all(|(guard, read_version)| read_version.is_none_or(|v| v == guard.version()))
+    }
+
+    /// Commit the log, returning `false` if a read variable has changed.
+    ///
+    /// Variables are locked in id order, so concurrent commits can't
+    /// deadlock. Transactions parked on a written variable are woken once
+    /// the locks are released.
+    fn commit(self) -> bool {
+        let (cells, logs): (Vec<_>, Vec<_>) = self
+            .entries
+            .into_values()
+            .map(|entry| (entry.cell, (entry.read_version, entry.write)))
+            .unzip();
+        let mut guards: Vec<_> = cells.iter().map(|cell| cell.lock()).collect();
+
+        let valid = guards
+            .iter()
+            .zip(&logs)
+            .all(|(guard, (read_version, _))| read_version.is_none_or(|v| v == guard.version()));
+        let mut woken = Vec::new();
+        if valid {
+            for (guard, (_, write)) in guards.iter_mut().zip(logs) {
+                if let Some(value) = write {
+                    woken.extend(guard.store(value));
+                }
+            }
+        }
+        drop(guards);
+        woken.into_iter().for_each(Waker::wake);
+        valid
+    }
+}
+
+impl fmt::Debug for Tx {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Tx")
+            .field("vars", &self.entries.len())
+            .finish()
+    }
+}
+
+/// Effect returned by [`atomically`].
+pub struct Atomically<F, E, Env> {
+    pub(crate) f: F,
+    _phantom: PhantomData<(E, Env)>,
+}
+
+impl<F, E, Env> fmt::Debug for Atomically<F, E, Env> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Atomically")
+            .field("f", &"<function>")
+            .finish()
+    }
+}
+
+impl<T, F, E, Env> Effect for Atomically<F, E, Env>
+where
+    T: Send,
+    F: Fn(&mut Tx) -> StmResult<T, E> + Send,
+    E: Send,
+    Env: Send + Sync,
+{
+    type Output = T;
+    type Error = E;
+    type Env = Env;
+
+    async fn run(self, _env: &Self::Env) -> Result<T, E> {
+        loop {
+            let mut tx = Tx::new();
+            match (self.f)(&mut tx) {
+                Ok(value) => {
+                    if tx.commit() {
+                        return Ok(value);
+                    }
+                }
+                // An abort decided on a stale snapshot may be one no serial
+                // run could produce, so it only counts if the reads hold.
+                Err(StmError::Abort(e)) => {
+                    if tx.is_consistent() {
+                        return Err(e);
+                    }
+                }
+                Err(StmError::Retry) => {
+                    WaitForChange {
+                        reads: tx.read_set(),
+                    }
+                    .await;
+                    continue;
+                }
+            }
+            YieldNow(false).await;
+        }
+    }
+}
+
+/// Run a transaction as an effect, retrying it until it commits.
+///
+/// The closure may run several times, so it should only touch state through
+/// the [`Tx`]. Returning [`StmError::Retry`] parks the effect until another
+/// transaction commits a write to one of the variables this run read, then
+/// runs it again; a run that read nothing before retrying waits forever.
+/// Returning [`StmError::Abort`]
+/// fails the effect without committing, once the values the transaction
+/// read are confirmed to be current; if any changed, the abort is
+/// discarded and the transaction runs again.
+///
+/// # Example
+///
+/// ```rust
+/// use stillwater::effect::prelude::*;
+/// use stillwater::effect::stm::{atomically, TVar};
+///
+/// # tokio_test::block_on(async {
+/// let hits = TVar::new(0_u64);
+/// let record = atomically::<_, _, String, ()>({
+///     let hits = hits.clone();
+///     move |tx| {
+///         tx.modify(&hits, |n| n + 1)?;
+///         tx.read(&hits)
+///     }
+/// });
+/// assert_eq!(record.run(&()).await, Ok(1));
+/// # });
+/// ```
+pub fn atomically<T, F, E, Env>(f: F) -> Atomically<F, E, Env>
+where
+    F: Fn(&mut Tx) -> StmResult<T, E>,
+{
+    Atomically {
+        f,
+        _phantom: PhantomData,
+    }
+}
+
+/// Parks a transaction that returned [`StmError::Retry`] until one of the
+/// variables it read has a newer version.
+///
+/// Each variable is checked and parked on under its own lock, and commits
+/// bump the version and take the waiters under the same lock, so a commit
+/// landing between the check and the park can't be missed.
+struct WaitForChange {
+    reads: Vec<(Arc<dyn Cell>, u64)>,
+}
+
+impl Future for WaitForChange {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        for (cell, read_version) in &self.reads {
+            let mut guard = cell.lock();
+            if guard.version() != *read_version {
+                return Poll::Ready(());
+            }
+            guard.park(cx.waker());
+        }
+        Poll::Pending
+    }
+}
+
+/// Yields to the executor once, so a transaction that lost a conflict lets
+/// the effects it conflicts with run.
+struct YieldNow(bool);
+
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.0 {
+            Poll::Ready(())
+        } else {
+            self.0 = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_concurrent_transfers_preserve_total() {
+        let a = TVar::new(1_000_i64);
+        let b = TVar::new(1_000_i64);
+
+        let handles: Vec<_> = (0..50)
+            .map(|i| {
+                let (from, to) = if i % 2 == 0 {
+                    (a.clone(), b.clone())
+                } else {
+                    (b.clone(), a.clone())
+                };
+                let effect = atomically::<_, _, String, ()>(move |tx| {
+                    tx.modify(&from, |v| v - 7)?;
+                    tx.modify(&to, |v| v + 7)
+                });
+                tokio::spawn(async move { effect.run(&()).await })
+            })
+            .collect();
+        for handle in handles {
+            handle.await.unwrap().unwrap();
+        }
+
+        assert_eq!(a.load() + b.load(), 2_000);
+        assert_eq!(a.load(), 1_000);
+    }
+
+    #[tokio::test]
+    async fn test_abort_on_inconsistent_snapshot_is_retried() {
+        use std::sync::atomic::AtomicUsize;
+
+        // Invariant: a + b == 100.
+        let a = TVar::new(50_i32);
+        let b = TVar::new(50_i32);
+        let runs = Arc::new(AtomicUsize::new(0));
+
+        let check = atomically::<_, _, String, ()>({
+            let (a, b, runs) = (a.clone(), b.clone(), Arc::clone(&runs));
+            move |tx| {
+                let seen_a = tx.read(&a)?;
+                if runs.fetch_add(1, Ordering::SeqCst) == 0 {
+                    // A transfer commits between the two reads.
+                    let mut transfer = Tx::new();
+                    transfer.modify::<_, (), _>(&a, |v| v - 30).unwrap();
+                    transfer.modify::<_, (), _>(&b, |v| v + 30).unwrap();
+                    assert!(transfer.commit());
+                }
+                let seen_b = tx.read(&b)?;
+                if seen_a + seen_b != 100 {
+                    return Err(StmError::Abort(format!("broken: {} + {}", seen_a, seen_b)));
+                }
+                Ok((seen_a, seen_b))
+            }
+        });
+
+        assert_eq!(check.run(&()).await, Ok((20, 80)));
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_abort_discards_writes() {
+        let var = TVar::new(1);
+        let effect = atomically::<(), _, &str, ()>({
+            let var = var.clone();
+            move |tx| {
+                tx.write(&var, 99);
+                Err(StmError::Abort("nope"))
+            }
+        });
+
+        assert_eq!(effect.run(&()).await, Err("nope"));
+        assert_eq!(var.load(), 1);
+    }
+
+    #[test]
+    fn test_commit_detects_stale_read() {
+        let var = TVar::new(0);
+        let mut tx = Tx::new();
+        assert_eq!(tx.read::<_, ()>(&var), Ok(0));
+        tx.write(&var, 1);
+
+        let mut other = Tx::new();
+        other.write(&var, 5);
+        assert!(other.commit());
+
+        assert!(!tx.commit());
+        assert_eq!(var.load(), 5);
+    }
+
+    #[test]
+    fn test_new_read_detects_stale_earlier_read() {
+        let a = TVar::new(1);
+        let b = TVar::new(2);
+        let mut tx = Tx::new();
+        assert_eq!(tx.read::<_, ()>(&a), Ok(1));
+
+        let mut other = Tx::new();
+        other.write(&a, 10);
+        other.write(&b, 20);
+        assert!(other.commit());
+
+        assert_eq!(tx.read::<_, ()>(&b), Err(StmError::Retry));
+    }
+
+    #[tokio::test]
+    async fn test_retry_parks_until_a_read_variable_is_written() {
+        use std::sync::atomic::AtomicUsize;
+        use std::time::Duration;
+
+        let ready = TVar::new(false);
+        let unrelated = TVar::new(0);
+        let runs = Arc::new(AtomicUsize::new(0));
+        let waiter = atomically::<_, _, String, ()>({
+            let (ready, runs) = (ready.clone(), Arc::clone(&runs));
+            move |tx| {
+                runs.fetch_add(1, Ordering::SeqCst);
+                if tx.read(&ready)? {
+                    Ok("done")
+                } else {
+                    Err(StmError::Retry)
+                }
+            }
+        });
+        let handle = tokio::spawn(async move { waiter.run(&()).await });
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+
+        atomically::<_, _, String, ()>(move |tx| tx.modify(&unrelated, |n| n + 1))
+            .run(&())
+            .await
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+
+        atomically::<_, _, String, ()>(move |tx| {
+            tx.write(&ready, true);
+            Ok(())
+        })
+        .run(&())
+        .await
+        .unwrap();
+
+        assert_eq!(handle.await.unwrap(), Ok("done"));
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_retry_waits_for_condition() {
+        let ready = TVar::new(false);
+        let waiter = atomically::<_, _, String, ()>({
+            let ready = ready.clone();
+            move |tx| {
+                if tx.read(&ready)? {
+                    Ok("done")
+                } else {
+                    Err(StmError::Retry)
+                }
+            }
+        });
+        let handle = tokio::spawn(async move { waiter.run(&()).await });
+
+        atomically::<_, _, String, ()>(move |tx| {
+            tx.write(&ready, true);
+            Ok(())
+        })
+        .run(&())
+        .await
+        .unwrap();
+
+        assert_eq!(handle.await.unwrap(), Ok("done"));
+    }
+}
@@ -0,0 +1,456 @@
+//! Effect-level mutex and semaphore.
+//!
+//! [`EffectMutex`] and [`EffectSemaphore`] wrap the Tokio primitives so that
+//! taking a lock or permit is itself an effect. The acquiring effects are
+//! tracked as acquiring [`LockRes`], which makes them fit the resource
+//! bracket: the bracket releases the lock even when the use effect fails,
+//! and the compiler rejects code that acquires without releasing.
+//!
+//! For the common case of a short critical section, [`EffectMutex::with_lock`]
+//! and [`EffectSemaphore::with_permit`] acquire, run, and release in one
+//! resource-neutral effect.
+//!
+//! # Example
+//!
+//! ```rust
+//! use stillwater::effect::prelude::*;
+//! use stillwater::effect::resource::{bracket, LockRes};
+//! use stillwater::effect::sync_primitives::{EffectMutex, EffectMutexGuard};
+//!
+//! # tokio_test::block_on(async {
+//! let balance = EffectMutex::new(100_u32);
+//!
+//! // Short critical section
+//! let withdraw = balance.with_lock::<_, String, (), _>(|b| {
+//!     *b -= 30;
+//!     *b
+//! });
+//! assert_eq!(withdraw.run(&()).await, Ok(70));
+//!
+//! // Full bracket: the guard is released after the use effect, even on failure
+//! let audit = bracket::<LockRes>()
+//!     .acquire(balance.lock::<String, ()>())
+//!     .release(EffectMutexGuard::unlock)
+//!     .use_fn(|guard: &EffectMutexGuard<u32>| pure(format!("balance is {}", **guard)));
+//! assert_eq!(audit.run(&()).await, Ok("balance is 70".to_string()));
+//! # });
+//! ```
+
+use std::fmt;
+use std::future::{ready, Ready};
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
+
+use tokio::sync::{Mutex, OwnedMutexGuard, OwnedSemaphorePermit, Semaphore};
+
+use crate::effect::resource::{Empty, Has, LockRes, ResourceEffect, Tracked};
+use crate::effect::trait_def::Effect;
+
+/// A mutex whose lock is acquired by running an effect.
+///
+/// Cloning produces another handle to the same mutex.
+pub struct EffectMutex<T> {
+    inner: Arc<Mutex<T>>,
+}
+
+impl<T> EffectMutex<T> {
+    /// Create a mutex holding `value`.
+    pub fn new(value: T) -> Self {
+        EffectMutex {
+            inner: Arc::new(Mutex::new(value)),
+        }
+    }
+
+    /// An effect that waits for the lock and returns its guard.
+    ///
+    /// The effect is tracked as acquiring [`LockRes`]; pair it with
+    /// [`EffectMutexGuard::unlock`] in a resource bracket.
+    pub fn lock<E, Env>(&self) -> Tracked<Lock<T, E, Env>, Has<LockRes>> {
+        Tracked::new(Lock {
+            mutex: Arc::clone(&self.inner),
+            _phantom: PhantomData,
+        })
+    }
+
+    /// An effect that runs `f` on the locked value and returns its result.
+    ///
+    /// The lock is released when `f` returns, or if it panics.
+    pub fn with_lock<U, E, Env, F>(&self, f: F) -> WithLock<T, F, E, Env>
+    where
+        F: FnOnce(&mut T) -> U,
+    {
+        WithLock {
+            mutex: Arc::clone(&self.inner),
+            f,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Take the lock without waiting, or `None` if it is held.
+    pub fn try_lock(&self) -> Option<EffectMutexGuard<T>> {
+        Arc::clone(&self.inner)
+            .try_lock_owned()
+            .ok()
+            .map(|guard| EffectMutexGuard { guard })
+    }
+}
+
+impl<T> Clone for EffectMutex<T> {
+    fn clone(&self) -> Self {
+        EffectMutex {
+            inner: Arc::clone(&self.inner),
+        }
+    }
+}
+
+impl<T: Default> Default for EffectMutex<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T> fmt::Debug for EffectMutex<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EffectMutex").finish_non_exhaustive()
+    }
+}
+
+/// Exclusive access to the value in an [`EffectMutex`], released on drop.
+pub struct EffectMutexGuard<T> {
+    guard: OwnedMutexGuard<T>,
+}
+
+impl<T> EffectMutexGuard<T> {
+    /// Release the lock; the release function for a resource bracket.
+    pub fn unlock<E>(guard: Self) -> Ready<Result<(), E>> {
+        drop(guard);
+        ready(Ok(()))
+    }
+}
+
+impl<T> Deref for EffectMutexGuard<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.guard
+    }
+}
+
+impl<T> DerefMut for EffectMutexGuard<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.guard
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for EffectMutexGuard<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("EffectMutexGuard")
+            .field(&*self.guard)
+            .finish()
+    }
+}
+
+/// Effect returned by [`EffectMutex::lock`].
+pub struct Lock<T, E, Env> {
+    pub(crate) mutex: Arc<Mutex<T>>,
+    _phantom: PhantomData<(E, Env)>,
+}
+
+impl<T, E, Env> fmt::Debug for Lock<T, E, Env> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Lock").field("mutex", &"<mutex>").finish()
+    }
+}
+
+impl<T, E, Env> Effect for Lock<T, E, Env>
+where
+    T: Send + 'static,
+    E: Send,
+    Env: Send + Sync,
+{
+    type Output = EffectMutexGuard<T>;
+    type Error = E;
+    type Env = Env;
+
+    async fn run(self, _env: &Self::Env) -> Result<Self::Output, E> {
+        Ok(EffectMutexGuard {
+            guard: self.mutex.lock_owned().await,
+        })
+    }
+}
+
+/// Effect returned by [`EffectMutex::with_lock`].
+pub struct WithLock<T, F, E, Env> {
+    pub(crate) mutex: Arc<Mutex<T>>,
+    pub(crate) f: F,
+    _phantom: PhantomData<(E, Env)>,
+}
+
+impl<T, F, E, Env> fmt::Debug for WithLock<T, F, E, Env> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WithLock")
+            .field("mutex", &"<mutex>")
+            .field("f", &"<function>")
+            .finish()
+    }
+}
+
+impl<T, U, F, E, Env> Effect for WithLock<T, F, E, Env>
+where
+    T: Send,
+    U: Send,
+    F: FnOnce(&mut T) -> U + Send,
+    E: Send,
+    Env: Send + Sync,
+{
+    type Output = U;
+    type Error = E;
+    type Env = Env;
+
+    async fn run(self, _env: &Self::Env) -> Result<U, E> {
+        let mut guard = self.mutex.lock().await;
+        Ok((self.f)(&mut guard))
+    }
+}
+
+impl<T, U, F, E, Env> ResourceEffect for WithLock<T, F, E, Env>
+where
+    T: Send,
+    U: Send,
+    F: FnOnce(&mut T) -> U + Send,
+    E: Send,
+    Env: Send + Sync,
+{
+    type Acquires = Empty;
+    type Releases = Empty;
+}
+
+/// A semaphore whose permits are acquired by running an effect.
+///
+/// Cloning produces another handle to the same semaphore.
+#[derive(Clone)]
+pub struct EffectSemaphore {
+    inner: Arc<Semaphore>,
+}
+
+impl EffectSemaphore {
+    /// Create a semaphore with `permits` permits.
+    pub fn new(permits: usize) -> Self {
+        EffectSemaphore {
+            inner: Arc::new(Semaphore::new(permits)),
+        }
+    }
+
+    /// An effect that waits for a permit and returns it.
+    ///
+    /// The effect is tracked as acquiring [`LockRes`]; pair it with
+    /// [`EffectPermit::release`] in a resource bracket.
+    pub fn acquire<E, Env>(&self) -> Tracked<AcquirePermit<E, Env>, Has<LockRes>> {
+        Tracked::new(AcquirePermit {
+            semaphore: Arc::clone(&self.inner),
+            _phantom: PhantomData,
+        })
+    }
+
+    /// An effect that runs `effect` while holding a permit.
+    ///
+    /// The permit is released when `effect` finishes, whether it succeeds
+    /// or fails.
+    pub fn with_permit<Eff: Effect>(&self, effect: Eff) -> WithPermit<Eff> {
+        WithPermit {
+            semaphore: Arc::clone(&self.inner),
+            effect,
+        }
+    }
+
+    /// The number of permits currently available.
+    pub fn available_permits(&self) -> usize {
+        self.inner.available_permits()
+    }
+}
+
+impl fmt::Debug for EffectSemaphore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EffectSemaphore")
+            .field("available_permits", &self.available_permits())
+            .finish()
+    }
+}
+
+/// A permit from an [`EffectSemaphore`], released on drop.
+#[derive(Debug)]
+pub struct EffectPermit {
+    _permit: OwnedSemaphorePermit,
+}
+
+impl EffectPermit {
+    /// Return the permit; the release function for a resource bracket.
+    pub fn release<E>(permit: Self) -> Ready<Result<(), E>> {
+        drop(permit);
+        ready(Ok(()))
+    }
+}
+
+/// Effect returned by [`EffectSemaphore::acquire`].
+pub struct AcquirePermit<E, Env> {
+    pub(crate) semaphore: Arc<Semaphore>,
+    _phantom: PhantomData<(E, Env)>,
+}
+
+impl<E, Env> fmt::Debug for AcquirePermit<E, Env> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AcquirePermit")
+            .field("semaphore", &"<semaphore>")
+            .finish()
+    }
+}
+
+impl<E, Env> Effect for AcquirePermit<E, Env>
+where
+    E: Send,
+    Env: Send + Sync,
+{
+    type Output = EffectPermit;
+    type Error = E;
+    type Env = Env;
+
+    async fn run(self, _env: &Self::Env) -> Result<EffectPermit, E> {
+        let permit = self
+            .semaphore
+            .acquire_owned()
+            .await
+            .expect("effect semaphores are never closed");
+        Ok(EffectPermit { _permit: permit })
+    }
+}
+
+/// Effect returned by [`EffectSemaphore::with_permit`].
+pub struct WithPermit<Eff> {
+    pub(crate) semaphore: Arc<Semaphore>,
+    pub(crate) effect: Eff,
+}
+
+impl<Eff> fmt::Debug for WithPermit<Eff> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WithPermit")
+            .field("semaphore", &"<semaphore>")
+            .field("effect", &"<effect>")
+            .finish()
+    }
+}
+
+impl<Eff: Effect> Effect for WithPermit<Eff> {
+    type Output = Eff::Output;
+    type Error = Eff::Error;
+    type Env = Eff::Env;
+
+    async fn run(self, env: &Self::Env) -> Result<Self::Output, Self::Error> {
+        let _permit = self
+            .semaphore
+            .acquire()
+            .await
+            .expect("effect semaphores are never closed");
+        self.effect.run(env).await
+    }
+}
+
+impl<Eff: Effect> ResourceEffect for WithPermit<Eff> {
+    type Acquires = Empty;
+    type Releases = Empty;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::effect::constructors::{fail, from_async, pure};
+    use crate::effect::resource::{assert_resource_neutral, bracket};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_with_lock_serializes_updates() {
+        let counter = EffectMutex::new(0_u32);
+        let handles: Vec<_> = (0..20)
+            .map(|_| {
+                let effect = counter.with_lock::<_, String, (), _>(|n| *n += 1);
+                tokio::spawn(async move { effect.run(&()).await })
+            })
+            .collect();
+        for handle in handles {
+            handle.await.unwrap().unwrap();
+        }
+        assert_eq!(*counter.try_lock().unwrap(), 20);
+    }
+
+    #[tokio::test]
+    async fn test_bracket_releases_lock_on_failure() {
+        let mutex = EffectMutex::new(Vec::<i32>::new());
+        let effect = assert_resource_neutral(
+            bracket::<LockRes>()
+                .acquire(mutex.lock::<String, ()>())
+                .release(EffectMutexGuard::unlock)
+                .use_fn(|_guard: &EffectMutexGuard<Vec<i32>>| {
+                    fail::<(), _, ()>("boom".to_string())
+                }),
+        );
+
+        assert_eq!(effect.run(&()).await, Err("boom".to_string()));
+        assert!(mutex.try_lock().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_try_lock_while_held() {
+        let mutex = EffectMutex::new(1);
+        let guard = mutex.lock::<String, ()>().run(&()).await.unwrap();
+        assert!(mutex.try_lock().is_none());
+        EffectMutexGuard::unlock::<String>(guard).await.unwrap();
+        assert!(mutex.try_lock().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_with_permit_limits_concurrency() {
+        let semaphore = EffectSemaphore::new(2);
+        let active = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+
+        let handles: Vec<_> = (0..6)
+            .map(|_| {
+                let active = active.clone();
+                let peak = peak.clone();
+                let work = from_async(move |_: &()| {
+                    let active = active.clone();
+                    let peak = peak.clone();
+                    async move {
+                        let now = active.fetch_add(1, Ordering::SeqCst) + 1;
+                        peak.fetch_max(now, Ordering::SeqCst);
+                        tokio::time::sleep(Duration::from_millis(5)).await;
+                        active.fetch_sub(1, Ordering::SeqCst);
+                        Ok::<_, String>(())
+                    }
+                });
+                let effect = semaphore.with_permit(work);
+                tokio::spawn(async move { effect.run(&()).await })
+            })
+            .collect();
+        for handle in handles {
+            handle.await.unwrap().unwrap();
+        }
+
+        assert_eq!(peak.load(Ordering::SeqCst), 2);
+        assert_eq!(semaphore.available_permits(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_semaphore_bracket() {
+        let semaphore = EffectSemaphore::new(1);
+        let observed = semaphore.clone();
+        let effect = bracket::<LockRes>()
+            .acquire(semaphore.acquire::<String, ()>())
+            .release(EffectPermit::release)
+            .use_fn(move |_permit: &EffectPermit| pure(observed.available_permits()));
+
+        assert_eq!(effect.run(&()).await, Ok(0));
+        assert_eq!(semaphore.available_permits(), 1);
+    }
+}
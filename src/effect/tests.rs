@@ -201,6 +201,65 @@ async fn test_boxed_allows_recursion() {
     assert_eq!(countdown(5).run_standalone().await, Ok(15)); // 5+4+3+2+1+0
 }
 
+#[tokio::test]
+async fn test_boxed_deep_sequential_chain_is_stack_safe() {
+    let mut effect = pure::<_, String, ()>(0u64).boxed();
+    for _ in 0..100_000 {
+        effect = effect.and_then(|x| pure(x + 1)).map(|x| x);
+    }
+
+    assert_eq!(effect.run_standalone().await, Ok(100_000));
+}
+
+#[tokio::test]
+async fn test_boxed_deep_recursion_is_stack_safe() {
+    fn countdown(n: u64) -> BoxedEffect<u64, String, ()> {
+        if n == 0 {
+            pure(0).boxed()
+        } else {
+            pure(n)
+                .boxed()
+                .and_then(move |x| countdown(x - 1).map(move |sum| x + sum))
+        }
+    }
+
+    assert_eq!(
+        countdown(100_000).run_standalone().await,
+        Ok(100_000 * 100_001 / 2)
+    );
+}
+
+#[tokio::test]
+async fn test_boxed_chain_short_circuits_on_error() {
+    let effect = pure::<_, String, ()>(1)
+        .boxed()
+        .and_then(|_| fail::<i32, _, ()>("boom".to_string()))
+        .map(|_: i32| -> i32 { panic!("should not run") });
+
+    assert_eq!(effect.run_standalone().await, Err("boom".to_string()));
+}
+
+#[tokio::test]
+async fn test_boxed_effect_ext_map_accepts_borrowing_closure() {
+    let suffix = String::from("!");
+    let effect = pure::<_, String, ()>("done".to_string()).boxed();
+
+    let result = EffectExt::map(effect, |s| s + &suffix)
+        .run_standalone()
+        .await;
+
+    assert_eq!(result, Ok("done!".to_string()));
+}
+
+#[tokio::test]
+async fn test_boxing_boxed_effect_is_identity() {
+    let effect = pure::<_, String, ()>(1).boxed().map(|x| x + 1);
+    let reboxed = BoxedEffect::new(effect);
+
+    assert!(format!("{:?}", reboxed).contains("continuations: 1"));
+    assert_eq!(reboxed.run_standalone().await, Ok(2));
+}
+
 #[tokio::test]
 async fn test_boxed_allows_match_arms() {
     fn get_value(use_double: bool) -> BoxedEffect<i32, String, ()> {
@@ -232,7 +291,7 @@ async fn test_par_all_collects_errors() {
         fail("error2".to_string()).boxed(),
     ];
 
-    let result = par_all(effects, &()).await;
+    let result: Result<Vec<_>, _> = par_all(effects, &()).await;
     assert_eq!(
         result,
         Err(vec!["error1".to_string(), "error2".to_string()])
@@ -629,3 +688,155 @@ async fn test_zip3_heterogeneous_types() {
         Ok((42, "hello".to_string(), true))
     );
 }
+
+// ==================== ZipAsks Tests ====================
+
+#[tokio::test]
+async fn test_zip_asks_pairs_value_with_env() {
+    let effect = pure::<_, String, i32>(2).zip_asks(|env: &i32| env * 10);
+    assert_eq!(effect.run(&5).await, Ok((2, 50)));
+}
+
+#[tokio::test]
+async fn test_zip_asks_skips_projection_on_failure() {
+    let effect = fail::<i32, _, i32>("boom".to_string())
+        .zip_asks(|_: &i32| -> i32 { panic!("projection must not run") });
+    assert_eq!(effect.run(&5).await, Err("boom".to_string()));
+}
+
+// ==================== MapEnvFor Tests ====================
+
+#[tokio::test]
+async fn test_map_env_for_builds_derived_env_once() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    let builds = AtomicUsize::new(0);
+    let effect = pure::<_, String, i32>(3).map_env_for(
+        |base: &i32, n: &i32| {
+            builds.fetch_add(1, Ordering::SeqCst);
+            Counters {
+                hits: std::sync::Mutex::new((base * n) as u32),
+            }
+        },
+        |_| hit().and_then(|_| hit()),
+    );
+
+    assert_eq!(effect.run(&10).await, Ok(32));
+    assert_eq!(builds.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_map_env_for_skips_derive_on_failure() {
+    let effect = fail::<i32, _, i32>("boom".to_string()).map_env_for(
+        |_: &i32, _: &i32| -> Counters { panic!("derive must not run") },
+        |_| hit(),
+    );
+    assert_eq!(effect.run(&0).await, Err("boom".to_string()));
+}
+
+// ==================== WrapErr Tests ====================
+
+#[derive(Debug)]
+struct LoadError(std::io::Error);
+
+impl std::fmt::Display for LoadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("load failed")
+    }
+}
+
+impl std::error::Error for LoadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.0)
+    }
+}
+
+impl From<std::io::Error> for LoadError {
+    fn from(e: std::io::Error) -> Self {
+        LoadError(e)
+    }
+}
+
+#[tokio::test]
+async fn test_wrap_err_chain_survives_context() {
+    let effect = fail::<(), _, ()>(std::io::Error::other("disk offline"))
+        .wrap_err::<LoadError>()
+        .context("starting up");
+
+    let err = effect.run_standalone().await.unwrap_err();
+    let chain: Vec<String> =
+        std::iter::successors(Some(&err as &dyn std::error::Error), |e| (*e).source())
+            .skip(1)
+            .map(|e| e.to_string())
+            .collect();
+    assert_eq!(chain, ["load failed", "disk offline"]);
+}
+
+#[tokio::test]
+async fn test_wrap_err_passes_success_through() {
+    let effect = pure::<_, std::io::Error, ()>(7).wrap_err::<LoadError>();
+    assert_eq!(effect.run_standalone().await.unwrap(), 7);
+}
+
+// ==================== Non-Clone Environment Tests ====================
+
+struct Counters {
+    hits: std::sync::Mutex<u32>,
+}
+
+fn hit() -> impl Effect<Output = u32, Error = String, Env = Counters> {
+    from_fn(|env: &Counters| {
+        let mut hits = env.hits.lock().unwrap();
+        *hits += 1;
+        Ok(*hits)
+    })
+}
+
+#[tokio::test]
+async fn test_non_clone_env_runs_by_reference() {
+    let env = Counters {
+        hits: std::sync::Mutex::new(0),
+    };
+
+    let effect = hit().and_then(|_| hit()).map(|n| n * 10);
+
+    assert_eq!(effect.run(&env).await, Ok(20));
+    assert_eq!(*env.hits.lock().unwrap(), 2);
+}
+
+#[tokio::test]
+async fn test_non_clone_env_boxed() {
+    let env = Counters {
+        hits: std::sync::Mutex::new(0),
+    };
+
+    let effects: Vec<BoxedEffect<u32, String, Counters>> = vec![
+        hit().boxed(),
+        pure(7).boxed(),
+        hit().map(|n| n + 100).boxed(),
+    ];
+
+    let mut results = Vec::new();
+    for effect in effects {
+        results.push(effect.run(&env).await.unwrap());
+    }
+    assert_eq!(results, vec![1, 7, 102]);
+}
+
+#[tokio::test]
+async fn test_provide_future_works_with_select() {
+    let slow = from_async(|delay: &u64| {
+        let delay = *delay;
+        async move {
+            tokio::time::sleep(std::time::Duration::from_millis(delay)).await;
+            Ok::<_, String>("slow")
+        }
+    });
+    let fast = pure::<_, String, u64>("fast");
+
+    let winner = tokio::select! {
+        result = slow.provide(10_000) => result,
+        result = fast.provide(0) => result,
+    };
+    assert_eq!(winner, Ok("fast"));
+}
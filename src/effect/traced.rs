@@ -0,0 +1,372 @@
+//! Recording intermediate values for debugging long pipelines.
+//!
+//! Calling [`TraceValuesExt::traced_values`] switches an effect into traced
+//! mode. The `map`, `map_err`, `and_then`, and `or_else` methods of the
+//! returned [`Traced`] record each stage's input and output, formatted with
+//! `Debug`, into a shared [`TraceLog`]. After the run, the log shows how
+//! every value flowed through the pipeline, without a `tap` at every step.
+//!
+//! Only stages built through [`Traced`]'s own methods are recorded. Other
+//! combinators from [`EffectExt`](crate::effect::EffectExt) still work on a
+//! `Traced` effect, but they leave traced mode.
+//!
+//! Feature-gated behind `#[cfg(feature = "debug")]`.
+//!
+//! # Example
+//!
+//! ```rust
+//! use stillwater::effect::prelude::*;
+//! use stillwater::effect::traced::TraceValuesExt;
+//!
+//! # tokio_test::block_on(async {
+//! let pipeline = pure::<_, String, ()>("42")
+//!     .traced_values()
+//!     .map(|s| s.parse::<i32>().unwrap_or(0))
+//!     .and_then(|n| pure(n * 2))
+//!     .map(|n| n + 1);
+//! let log = pipeline.log();
+//!
+//! assert_eq!(pipeline.run(&()).await, Ok(85));
+//! assert_eq!(
+//!     log.to_string(),
+//!     "#0 source -> Ok(\"42\")\n#1 map \"42\" -> Ok(42)\n#2 and_then 42 -> Ok(84)\n#3 map 84 -> Ok(85)"
+//! );
+//! # });
+//! ```
+
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use crate::effect::ext::EffectExt;
+use crate::effect::trait_def::Effect;
+
+/// One recorded pipeline stage.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceEntry {
+    /// Position in the order the stages completed, starting at 0.
+    pub stage: usize,
+    /// The combinator that ran, such as `"map"` or `"and_then"`.
+    pub combinator: &'static str,
+    /// The value the stage received, or `None` for the source effect.
+    pub input: Option<String>,
+    /// The value the stage produced, or the error it failed with.
+    pub output: Result<String, String>,
+}
+
+impl fmt::Display for TraceEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{} {}", self.stage, self.combinator)?;
+        if let Some(input) = &self.input {
+            write!(f, " {}", input)?;
+        }
+        match &self.output {
+            Ok(value) => write!(f, " -> Ok({})", value),
+            Err(error) => write!(f, " -> Err({})", error),
+        }
+    }
+}
+
+/// Shared record of the stages a [`Traced`] pipeline ran.
+///
+/// Cloning the log gives another handle to the same entries, so it can be
+/// kept before running the pipeline and read afterwards.
+#[derive(Clone, Default)]
+pub struct TraceLog {
+    entries: Arc<Mutex<Vec<TraceEntry>>>,
+}
+
+impl TraceLog {
+    /// Create an empty log.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The recorded stages, in completion order.
+    pub fn entries(&self) -> Vec<TraceEntry> {
+        self.lock().clone()
+    }
+
+    /// Number of recorded stages.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Whether no stage has been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// Remove all recorded stages.
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    fn record(
+        &self,
+        combinator: &'static str,
+        input: Option<String>,
+        output: Result<String, String>,
+    ) {
+        let mut entries = self.lock();
+        let stage = entries.len();
+        entries.push(TraceEntry {
+            stage,
+            combinator,
+            input,
+            output,
+        });
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<TraceEntry>> {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl fmt::Debug for TraceLog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TraceLog")
+            .field("entries", &*self.lock())
+            .finish()
+    }
+}
+
+/// One stage per line, as `#stage combinator input -> output`.
+impl fmt::Display for TraceLog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, entry) in self.lock().iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}", entry)?;
+        }
+        Ok(())
+    }
+}
+
+/// An effect whose result is recorded as one stage.
+struct Recorded<Eff> {
+    inner: Eff,
+    log: TraceLog,
+    combinator: &'static str,
+    input: Option<String>,
+}
+
+impl<Eff> Effect for Recorded<Eff>
+where
+    Eff: Effect,
+    Eff::Output: fmt::Debug,
+    Eff::Error: fmt::Debug,
+{
+    type Output = Eff::Output;
+    type Error = Eff::Error;
+    type Env = Eff::Env;
+
+    async fn run(self, env: &Self::Env) -> Result<Self::Output, Self::Error> {
+        let result = self.inner.run(env).await;
+        let output = match &result {
+            Ok(value) => Ok(format!("{:?}", value)),
+            Err(error) => Err(format!("{:?}", error)),
+        };
+        self.log.record(self.combinator, self.input, output);
+        result
+    }
+}
+
+/// An effect in traced mode.
+///
+/// Created by [`TraceValuesExt::traced_values`]. Its combinator methods
+/// record each stage into [`log`](Traced::log); running it behaves exactly
+/// like the untraced pipeline.
+pub struct Traced<Eff> {
+    inner: Eff,
+    log: TraceLog,
+}
+
+impl<Eff> fmt::Debug for Traced<Eff> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Traced")
+            .field("inner", &"<effect>")
+            .field("log", &self.log)
+            .finish()
+    }
+}
+
+impl<Eff> Traced<Eff>
+where
+    Eff: Effect,
+    Eff::Output: fmt::Debug,
+    Eff::Error: fmt::Debug,
+{
+    /// A handle to the log this pipeline records into.
+    pub fn log(&self) -> TraceLog {
+        self.log.clone()
+    }
+
+    /// Transform the success value, recording input and output.
+    pub fn map<U, F>(
+        self,
+        f: F,
+    ) -> Traced<impl Effect<Output = U, Error = Eff::Error, Env = Eff::Env>>
+    where
+        F: FnOnce(Eff::Output) -> U + Send,
+        U: fmt::Debug + Send,
+    {
+        let log = self.log.clone();
+        Traced {
+            inner: self.inner.map(move |value| {
+                let input = format!("{:?}", value);
+                let output = f(value);
+                log.record("map", Some(input), Ok(format!("{:?}", output)));
+                output
+            }),
+            log: self.log,
+        }
+    }
+
+    /// Transform the error, recording input and output.
+    pub fn map_err<E2, F>(
+        self,
+        f: F,
+    ) -> Traced<impl Effect<Output = Eff::Output, Error = E2, Env = Eff::Env>>
+    where
+        F: FnOnce(Eff::Error) -> E2 + Send,
+        E2: fmt::Debug + Send,
+    {
+        let log = self.log.clone();
+        Traced {
+            inner: self.inner.map_err(move |error| {
+                let input = format!("{:?}", error);
+                let output = f(error);
+                log.record("map_err", Some(input), Err(format!("{:?}", output)));
+                output
+            }),
+            log: self.log,
+        }
+    }
+
+    /// Chain a dependent effect, recording its input and result.
+    pub fn and_then<E2, F>(
+        self,
+        f: F,
+    ) -> Traced<impl Effect<Output = E2::Output, Error = Eff::Error, Env = Eff::Env>>
+    where
+        F: FnOnce(Eff::Output) -> E2 + Send,
+        E2: Effect<Error = Eff::Error, Env = Eff::Env>,
+        E2::Output: fmt::Debug,
+    {
+        let log = self.log.clone();
+        Traced {
+            inner: self.inner.and_then(move |value| Recorded {
+                input: Some(format!("{:?}", value)),
+                inner: f(value),
+                log,
+                combinator: "and_then",
+            }),
+            log: self.log,
+        }
+    }
+
+    /// Recover from an error, recording the error and the recovery result.
+    pub fn or_else<E2, F>(
+        self,
+        f: F,
+    ) -> Traced<impl Effect<Output = Eff::Output, Error = E2::Error, Env = Eff::Env>>
+    where
+        F: FnOnce(Eff::Error) -> E2 + Send,
+        E2: Effect<Output = Eff::Output, Env = Eff::Env>,
+        E2::Error: fmt::Debug,
+    {
+        let log = self.log.clone();
+        Traced {
+            inner: self.inner.or_else(move |error| Recorded {
+                input: Some(format!("{:?}", error)),
+                inner: f(error),
+                log,
+                combinator: "or_else",
+            }),
+            log: self.log,
+        }
+    }
+}
+
+impl<Eff: Effect> Effect for Traced<Eff> {
+    type Output = Eff::Output;
+    type Error = Eff::Error;
+    type Env = Eff::Env;
+
+    async fn run(self, env: &Self::Env) -> Result<Self::Output, Self::Error> {
+        self.inner.run(env).await
+    }
+}
+
+/// Extension trait for switching effects into traced mode.
+///
+/// This trait is only available when the `debug` feature is enabled.
+pub trait TraceValuesExt: Effect
+where
+    Self::Output: fmt::Debug,
+    Self::Error: fmt::Debug,
+{
+    /// Record this effect's result as stage 0 and trace the stages built
+    /// on top of it.
+    fn traced_values(
+        self,
+    ) -> Traced<impl Effect<Output = Self::Output, Error = Self::Error, Env = Self::Env>> {
+        let log = TraceLog::new();
+        Traced {
+            inner: Recorded {
+                inner: self,
+                log: log.clone(),
+                combinator: "source",
+                input: None,
+            },
+            log,
+        }
+    }
+}
+
+impl<E> TraceValuesExt for E
+where
+    E: Effect,
+    E::Output: fmt::Debug,
+    E::Error: fmt::Debug,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::effect::constructors::{fail, pure};
+
+    #[tokio::test]
+    async fn test_failed_stage_records_error_and_recovery() {
+        let pipeline = pure::<_, String, ()>(5)
+            .traced_values()
+            .and_then(|n| fail::<i32, _, ()>(format!("{} is odd", n)))
+            .map(|n| n * 10)
+            .or_else(|_| pure::<_, String, ()>(0));
+        let log = pipeline.log();
+
+        assert_eq!(pipeline.run(&()).await, Ok(0));
+        let entries = log.entries();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[1].combinator, "and_then");
+        assert_eq!(entries[1].output, Err("\"5 is odd\"".to_string()));
+        assert_eq!(entries[2].input.as_deref(), Some("\"5 is odd\""));
+        assert_eq!(entries[2].output, Ok("0".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_map_err_records_error_transformation() {
+        let pipeline = fail::<i32, _, ()>(404)
+            .traced_values()
+            .map_err(|code| format!("status {}", code));
+        let log = pipeline.log();
+
+        assert_eq!(pipeline.run(&()).await, Err("status 404".to_string()));
+        assert_eq!(
+            log.to_string(),
+            "#0 source -> Err(404)\n#1 map_err 404 -> Err(\"status 404\")"
+        );
+    }
+}
@@ -11,21 +11,23 @@
 //! - Combinators return concrete types (zero-cost abstractions)
 //! - Use `.boxed()` when you need type erasure
 //!
-//! # Environment Cloning
+//! # Borrowed Environments
 //!
-//! The `Env` type requires `Clone` to enable boxing. When an effect is boxed,
-//! the environment must be cloned into the boxed future to achieve `'static`
-//! lifetime. This is typically cheap when environments contain `Arc`-wrapped
-//! resources:
+//! [`Effect::run`] borrows the environment, and the future it returns may
+//! hold that borrow until it completes. Neither the combinators nor boxing
+//! clone it, so `Env` does not need to implement `Clone`: an environment
+//! owning a connection pool, a lock, or a large cache can be used directly.
 //!
 //! ```rust,ignore
-//! #[derive(Clone)]
 //! struct AppEnv {
-//!     db: Arc<DatabasePool>,
-//!     config: Arc<Config>,
-//!     http: Arc<HttpClient>,
+//!     db: DatabasePool,
+//!     cache: Mutex<LruCache<UserId, User>>,
 //! }
 //! ```
+//!
+//! Only [`ask`](crate::effect::constructors::ask), which returns a copy of the
+//! whole environment, and [`BoxedLocalEffect`](crate::effect::BoxedLocalEffect),
+//! whose future outlives the borrow, ask for `Env: Clone`.
 
 use std::future::Future;
 
@@ -40,7 +42,7 @@ use std::future::Future;
 ///
 /// * `Output` - The success type produced by this effect (must be `Send`)
 /// * `Error` - The error type that may be produced (must be `Send`)
-/// * `Env` - The environment type required to run this effect (must be `Clone + Send + Sync`)
+/// * `Env` - The environment type required to run this effect (must be `Send + Sync`)
 ///
 /// # Example
 ///
@@ -61,8 +63,9 @@ pub trait Effect: Sized + Send {
 
     /// The environment type required to run this effect.
     ///
-    /// Must be `Clone` to support boxing (cloning is deferred until boxing).
-    type Env: Clone + Send + Sync;
+    /// Effects borrow it for the duration of [`run`](Effect::run), so it
+    /// does not need to be `Clone`.
+    type Env: Send + Sync;
 
     /// Execute this effect with the given environment.
     ///
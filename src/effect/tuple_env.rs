@@ -0,0 +1,248 @@
+//! Tuple environments without declaring a struct.
+//!
+//! Application code usually gives its environment a struct with an
+//! `AsRef<T>` impl per service. For scripts and tests that is a lot of
+//! ceremony, so [`Provides<T, Index>`] lets a tuple `(A, B)` or `(A, B, C)` serve
+//! as the environment instead, with each element reachable by its type:
+//!
+//! - [`asks_has`] reads from one element of the environment;
+//! - [`local_has`] runs an effect written against one element's type in the
+//!   whole environment, by reference and without cloning.
+//!
+//! Every type also provides itself, so effects built this way run against a
+//! single-value environment too.
+//!
+//! The `Index` parameter ([`Itself`], [`At0`], [`At1`], [`At2`]) only keeps
+//! the impls apart and is inferred. When two elements have the same type
+//! the lookup is ambiguous, and the index has to be named:
+//! `asks_has::<_, At1, _, _, _, _>(...)`.
+//!
+//! # Example
+//!
+//! ```rust
+//! use stillwater::effect::prelude::*;
+//!
+//! struct Db {
+//!     rows: Vec<u32>,
+//! }
+//! struct Config {
+//!     limit: usize,
+//! }
+//!
+//! fn count() -> impl Effect<Output = usize, Error = String, Env = Db> {
+//!     asks(|db: &Db| db.rows.len())
+//! }
+//!
+//! # tokio_test::block_on(async {
+//! let env = (Db { rows: vec![1, 2, 3] }, Config { limit: 2 });
+//!
+//! let effect = local_has(count())
+//!     .and_then(|n| asks_has(move |config: &Config| n.min(config.limit)));
+//!
+//! assert_eq!(effect.run(&env).await, Ok(2));
+//! # });
+//! ```
+
+use std::marker::PhantomData;
+
+use crate::effect::reader::Asks;
+use crate::effect::trait_def::Effect;
+
+/// An environment that provides a `T`, found at position `Index`.
+pub trait Provides<T, Index> {
+    /// Borrow the `T` held by this environment.
+    fn project(&self) -> &T;
+}
+
+/// Index of a value that is the whole environment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Itself;
+
+/// Index of the first element of a tuple environment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct At0;
+
+/// Index of the second element of a tuple environment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct At1;
+
+/// Index of the third element of a tuple environment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct At2;
+
+impl<T> Provides<T, Itself> for T {
+    fn project(&self) -> &T {
+        self
+    }
+}
+
+impl<A, B> Provides<A, At0> for (A, B) {
+    fn project(&self) -> &A {
+        &self.0
+    }
+}
+
+impl<A, B> Provides<B, At1> for (A, B) {
+    fn project(&self) -> &B {
+        &self.1
+    }
+}
+
+impl<A, B, C> Provides<A, At0> for (A, B, C) {
+    fn project(&self) -> &A {
+        &self.0
+    }
+}
+
+impl<A, B, C> Provides<B, At1> for (A, B, C) {
+    fn project(&self) -> &B {
+        &self.1
+    }
+}
+
+impl<A, B, C> Provides<C, At2> for (A, B, C) {
+    fn project(&self) -> &C {
+        &self.2
+    }
+}
+
+/// Query a value from one element of the environment.
+///
+/// Like [`asks`](crate::effect::asks), but `f` receives the `T` held by the
+/// environment rather than the whole environment.
+///
+/// # Example
+///
+/// ```rust
+/// use stillwater::effect::prelude::*;
+///
+/// struct Port(u16);
+///
+/// # tokio_test::block_on(async {
+/// let effect = asks_has::<_, _, _, String, _, _>(|port: &Port| port.0);
+/// assert_eq!(effect.run(&("localhost", Port(8080))).await, Ok(8080));
+/// # });
+/// ```
+pub fn asks_has<T, Index, U, E, Env, F>(f: F) -> Asks<impl FnOnce(&Env) -> U + Send, E, Env>
+where
+    Env: Provides<T, Index> + Send + Sync,
+    F: FnOnce(&T) -> U + Send,
+    U: Send,
+    E: Send,
+{
+    Asks::new(move |env: &Env| f(env.project()))
+}
+
+/// Run an effect that needs one element of the environment in the whole
+/// environment.
+///
+/// Like [`local`](crate::effect::local), but the inner effect borrows its
+/// environment from the outer one instead of receiving a new value.
+///
+/// # Example
+///
+/// ```rust
+/// use stillwater::effect::prelude::*;
+///
+/// struct Greeting(&'static str);
+///
+/// fn greet() -> impl Effect<Output = String, Error = String, Env = Greeting> {
+///     asks(|g: &Greeting| format!("{}, world", g.0))
+/// }
+///
+/// # tokio_test::block_on(async {
+/// let effect = local_has(greet());
+/// assert_eq!(
+///     effect.run(&(Greeting("hello"), 42_u8)).await,
+///     Ok("hello, world".to_string())
+/// );
+/// # });
+/// ```
+pub fn local_has<Inner, Env, Index>(inner: Inner) -> LocalHas<Inner, Env, Index>
+where
+    Inner: Effect,
+    Env: Provides<Inner::Env, Index> + Send + Sync,
+{
+    LocalHas {
+        inner,
+        _phantom: PhantomData,
+    }
+}
+
+/// Runs an effect in the element of a larger environment it needs.
+///
+/// Created by [`local_has`].
+pub struct LocalHas<Inner, Env, Index> {
+    pub(crate) inner: Inner,
+    pub(crate) _phantom: PhantomData<fn() -> (Env, Index)>,
+}
+
+impl<Inner, Env, Index> std::fmt::Debug for LocalHas<Inner, Env, Index> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LocalHas")
+            .field("inner", &"<effect>")
+            .finish()
+    }
+}
+
+impl<Inner, Env, Index> Effect for LocalHas<Inner, Env, Index>
+where
+    Inner: Effect,
+    Env: Provides<Inner::Env, Index> + Send + Sync,
+{
+    type Output = Inner::Output;
+    type Error = Inner::Error;
+    type Env = Env;
+
+    async fn run(self, env: &Env) -> Result<Self::Output, Self::Error> {
+        self.inner.run(env.project()).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::effect::constructors::asks;
+    use crate::effect::ext::EffectExt;
+
+    struct Db(u32);
+    struct Cache(u32);
+    struct Config(u32);
+
+    fn from_db() -> impl Effect<Output = u32, Error = String, Env = Db> {
+        asks(|db: &Db| db.0)
+    }
+
+    fn from_cache() -> impl Effect<Output = u32, Error = String, Env = Cache> {
+        asks(|cache: &Cache| cache.0)
+    }
+
+    #[tokio::test]
+    async fn test_local_has_finds_each_element_by_type() {
+        let effect = local_has(from_db())
+            .zip(local_has(from_cache()))
+            .and_then(|(db, cache)| {
+                asks_has(move |config: &Config| db * 100 + cache * 10 + config.0)
+            });
+
+        assert_eq!(effect.run(&(Db(1), Cache(2), Config(3))).await, Ok(123));
+        assert_eq!(local_has(from_db()).run(&(Cache(0), Db(7))).await, Ok(7));
+    }
+
+    #[tokio::test]
+    async fn test_single_value_environment_provides_itself() {
+        let effect = local_has(from_db()).and_then(|n| asks_has(move |db: &Db| n + db.0));
+
+        assert_eq!(effect.run(&Db(21)).await, Ok(42));
+    }
+
+    #[tokio::test]
+    async fn test_explicit_index_picks_between_equal_types() {
+        let env = (Db(1), Db(2));
+        let first = asks_has::<Db, At0, _, String, _, _>(|db| db.0);
+        let second = local_has::<_, _, At1>(from_db());
+
+        assert_eq!(first.run(&env).await, Ok(1));
+        assert_eq!(second.run(&env).await, Ok(2));
+    }
+}
@@ -1,6 +1,6 @@
 //! WriterAndThen combinator - chains dependent WriterEffects.
 
-use crate::effect::writer::WriterEffect;
+use crate::effect::writer::{WriteBuffer, WriterEffect};
 use crate::effect::Effect;
 use crate::Semigroup;
 
@@ -83,4 +83,13 @@ where
             Err(e) => (Err(e), writes1),
         }
     }
+
+    async fn run_writer_buffered(
+        self,
+        env: &Self::Env,
+        buffer: &WriteBuffer<Self::Writes>,
+    ) -> Result<E2::Output, E::Error> {
+        let value = self.inner.run_writer_buffered(env, buffer).await?;
+        (self.f)(value).run_writer_buffered(env, buffer).await
+    }
 }
@@ -3,17 +3,21 @@
 use std::future::Future;
 use std::pin::Pin;
 
-use crate::effect::writer::WriterEffect;
+use crate::effect::writer::{WriteBuffer, WriterEffect};
 use crate::effect::Effect;
 use crate::Monoid;
 
-// Type alias for the boxed writer effect inner function type
+// Type alias for the boxed writer effect inner function type. When given a
+// buffer, the effect runs buffered and returns empty writes.
 type BoxedWriterFn<T, E, Env, W> = Box<
-    dyn FnOnce(Env) -> Pin<Box<dyn Future<Output = (Result<T, E>, W)> + Send + 'static>>
+    dyn for<'a> FnOnce(&'a Env, Option<&'a WriteBuffer<W>>) -> WriterFuture<'a, T, E, W>
         + Send
         + 'static,
 >;
 
+// The future returned by the inner function, borrowing the environment
+type WriterFuture<'a, T, E, W> = Pin<Box<dyn Future<Output = (Result<T, E>, W)> + Send + 'a>>;
+
 /// A type-erased WriterEffect for use in collections, match arms, or recursive functions.
 ///
 /// Similar to `BoxedEffect`, this enables:
@@ -70,7 +74,7 @@ type BoxedWriterFn<T, E, Env, W> = Box<
 /// ```
 pub struct BoxedWriterEffect<T, E, Env, W>
 where
-    Env: Clone + Send + Sync + 'static,
+    Env: Send + Sync + 'static,
     W: Monoid + Send + 'static,
 {
     inner: BoxedWriterFn<T, E, Env, W>,
@@ -78,7 +82,7 @@ where
 
 impl<T, E, Env, W> std::fmt::Debug for BoxedWriterEffect<T, E, Env, W>
 where
-    Env: Clone + Send + Sync + 'static,
+    Env: Send + Sync + 'static,
     W: Monoid + Send + 'static,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -90,7 +94,7 @@ impl<T, E, Env, W> BoxedWriterEffect<T, E, Env, W>
 where
     T: Send + 'static,
     E: Send + 'static,
-    Env: Clone + Send + Sync + 'static,
+    Env: Send + Sync + 'static,
     W: Monoid + Send + 'static,
 {
     /// Create a new BoxedWriterEffect from a WriterEffect.
@@ -99,7 +103,18 @@ where
         Eff: WriterEffect<Output = T, Error = E, Env = Env, Writes = W> + Send + 'static,
     {
         BoxedWriterEffect {
-            inner: Box::new(move |env: Env| Box::pin(async move { effect.run_writer(&env).await })),
+            inner: Box::new(
+                move |env: &Env, buffer: Option<&WriteBuffer<W>>| -> WriterFuture<'_, T, E, W> {
+                    Box::pin(async move {
+                        match buffer {
+                            Some(buffer) => {
+                                (effect.run_writer_buffered(env, buffer).await, W::empty())
+                            }
+                            None => effect.run_writer(env).await,
+                        }
+                    })
+                },
+            ),
         }
     }
 }
@@ -108,7 +123,7 @@ impl<T, E, Env, W> Effect for BoxedWriterEffect<T, E, Env, W>
 where
     T: Send + 'static,
     E: Send + 'static,
-    Env: Clone + Send + Sync + 'static,
+    Env: Send + Sync + 'static,
     W: Monoid + Send + 'static,
 {
     type Output = T;
@@ -116,7 +131,7 @@ where
     type Env = Env;
 
     async fn run(self, env: &Self::Env) -> Result<Self::Output, Self::Error> {
-        let (result, _writes) = (self.inner)(env.clone()).await;
+        let (result, _writes) = (self.inner)(env, None).await;
         result
     }
 }
@@ -125,7 +140,7 @@ impl<T, E, Env, W> WriterEffect for BoxedWriterEffect<T, E, Env, W>
 where
     T: Send + 'static,
     E: Send + 'static,
-    Env: Clone + Send + Sync + 'static,
+    Env: Send + Sync + 'static,
     W: Monoid + Send + 'static,
 {
     type Writes = W;
@@ -134,6 +149,15 @@ where
         self,
         env: &Self::Env,
     ) -> (Result<Self::Output, Self::Error>, Self::Writes) {
-        (self.inner)(env.clone()).await
+        (self.inner)(env, None).await
+    }
+
+    async fn run_writer_buffered(
+        self,
+        env: &Self::Env,
+        buffer: &WriteBuffer<Self::Writes>,
+    ) -> Result<Self::Output, Self::Error> {
+        let (result, _writes) = (self.inner)(env, Some(buffer)).await;
+        result
     }
 }
@@ -1,7 +1,7 @@
 //! Collection combinators for WriterEffect.
 
 use crate::effect::writer::boxed::BoxedWriterEffect;
-use crate::effect::writer::WriterEffect;
+use crate::effect::writer::{WriteBuffer, WriterEffect};
 use crate::Monoid;
 
 /// Traverse a collection, running a writer effect for each item and accumulating all writes.
@@ -39,7 +39,7 @@ where
     T: Send + 'static,
     U: Send + 'static,
     E: Send + 'static,
-    Env: Clone + Send + Sync + 'static,
+    Env: Send + Sync + 'static,
     W: Monoid + Send + 'static,
     F: Fn(T) -> Eff + Send + 'static,
     Eff: WriterEffect<Output = U, Error = E, Env = Env, Writes = W> + Send + 'static,
@@ -57,7 +57,7 @@ where
     T: Send + 'static,
     U: Send + 'static,
     E: Send + 'static,
-    Env: Clone + Send + Sync + 'static,
+    Env: Send + Sync + 'static,
     W: Monoid + Send + 'static,
     F: Fn(T) -> Eff + Send + 'static,
     Eff: WriterEffect<Output = U, Error = E, Env = Env, Writes = W> + Send + 'static,
@@ -77,7 +77,7 @@ where
     T: Send + 'static,
     U: Send + 'static,
     E: Send + 'static,
-    Env: Clone + Send + Sync + 'static,
+    Env: Send + Sync + 'static,
     W: Monoid + Send + 'static,
     F: Fn(T) -> Eff + Send + 'static,
     Eff: WriterEffect<Output = U, Error = E, Env = Env, Writes = W> + Send + 'static,
@@ -104,6 +104,18 @@ where
 
         (Ok(results), all_writes)
     }
+
+    async fn run_writer_buffered(
+        self,
+        env: &Self::Env,
+        buffer: &WriteBuffer<Self::Writes>,
+    ) -> Result<Self::Output, Self::Error> {
+        let mut results = Vec::with_capacity(self.items.len());
+        for item in self.items {
+            results.push((self.f)(item).run_writer_buffered(env, buffer).await?);
+        }
+        Ok(results)
+    }
 }
 
 /// Fold a collection with a writer effect, accumulating writes at each step.
@@ -143,7 +155,7 @@ where
     T: Send + 'static,
     A: Send + 'static,
     E: Send + 'static,
-    Env: Clone + Send + Sync + 'static,
+    Env: Send + Sync + 'static,
     W: Monoid + Send + 'static,
     F: Fn(A, T) -> Eff + Send + 'static,
     Eff: WriterEffect<Output = A, Error = E, Env = Env, Writes = W> + Send + 'static,
@@ -162,7 +174,7 @@ where
     T: Send + 'static,
     A: Send + 'static,
     E: Send + 'static,
-    Env: Clone + Send + Sync + 'static,
+    Env: Send + Sync + 'static,
     W: Monoid + Send + 'static,
     F: Fn(A, T) -> Eff + Send + 'static,
     Eff: WriterEffect<Output = A, Error = E, Env = Env, Writes = W> + Send + 'static,
@@ -182,7 +194,7 @@ where
     T: Send + 'static,
     A: Send + 'static,
     E: Send + 'static,
-    Env: Clone + Send + Sync + 'static,
+    Env: Send + Sync + 'static,
     W: Monoid + Send + 'static,
     F: Fn(A, T) -> Eff + Send + 'static,
     Eff: WriterEffect<Output = A, Error = E, Env = Env, Writes = W> + Send + 'static,
@@ -209,4 +221,18 @@ where
 
         (Ok(accumulator), all_writes)
     }
+
+    async fn run_writer_buffered(
+        self,
+        env: &Self::Env,
+        buffer: &WriteBuffer<Self::Writes>,
+    ) -> Result<Self::Output, Self::Error> {
+        let mut accumulator = self.init;
+        for item in self.items {
+            accumulator = (self.f)(accumulator, item)
+                .run_writer_buffered(env, buffer)
+                .await?;
+        }
+        Ok(accumulator)
+    }
 }
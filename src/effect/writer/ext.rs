@@ -4,6 +4,8 @@ use crate::effect::writer::boxed::BoxedWriterEffect;
 use crate::effect::writer::censor::Censor;
 use crate::effect::writer::listen::Listen;
 use crate::effect::writer::pass::Pass;
+use crate::effect::writer::sampled::WriteFilter;
+use crate::effect::writer::streaming::WriterGuard;
 use crate::effect::writer::tap_tell::TapTell;
 use crate::effect::writer::WriterEffect;
 use crate::Monoid;
@@ -182,6 +184,94 @@ pub trait WriterEffectExt: WriterEffect {
         result
     }
 
+    /// Execute the effect, keeping only the writes `filter` admits.
+    ///
+    /// The filter runs after the effect completes, so every write is still
+    /// collected first. To bound memory, emit entries through
+    /// [`WriteFilter::entry`] instead.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use stillwater::effect::writer::prelude::*;
+    /// use stillwater::effect::prelude::*;
+    ///
+    /// # tokio_test::block_on(async {
+    /// let effect = traverse_writer((1..=10).collect::<Vec<u32>>(), |n| {
+    ///     tell_one::<_, String, ()>(n).map(move |_| n)
+    /// });
+    ///
+    /// let (result, sampled) = effect
+    ///     .run_writer_filtered(&(), &WriteFilter::every(4))
+    ///     .await;
+    /// assert_eq!(result.map(|v| v.len()), Ok(10));
+    /// assert_eq!(sampled, vec![1, 5, 9]);
+    /// # });
+    /// ```
+    #[allow(async_fn_in_trait)]
+    async fn run_writer_filtered<T>(
+        self,
+        env: &Self::Env,
+        filter: &WriteFilter<T>,
+    ) -> (Result<Self::Output, Self::Error>, Vec<T>)
+    where
+        Self: WriterEffect<Writes = Vec<T>> + Sized,
+    {
+        let (result, writes) = WriterEffect::run_writer(self, env).await;
+        (result, filter.apply(writes))
+    }
+
+    /// Run the effect, passing its writes to `sink` as each step finishes.
+    ///
+    /// Await the returned [`WriterGuard`] for the result. If the guard is
+    /// dropped before the effect completes, for example because the effect
+    /// was cancelled, the writes of the steps that did finish are still
+    /// passed to `sink`. See [`WriterGuard`] for which combinators pass on
+    /// writes per step.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::sync::{Arc, Mutex};
+    /// use std::time::Duration;
+    /// use stillwater::effect::writer::prelude::*;
+    /// use stillwater::effect::prelude::*;
+    ///
+    /// # tokio_test::block_on(async {
+    /// let audit = Arc::new(Mutex::new(Vec::new()));
+    ///
+    /// let slow_transfer = from_async(|_: &()| async {
+    ///     tokio::time::sleep(Duration::from_secs(60)).await;
+    ///     Ok::<_, String>(())
+    /// });
+    /// let effect = tell_one::<_, String, ()>("debited account")
+    ///     .and_then(|_| into_writer::<_, _, Vec<&str>>(slow_transfer))
+    ///     .and_then(|_| tell_one("credited account"));
+    ///
+    /// let sink = {
+    ///     let audit = audit.clone();
+    ///     move |entries: Vec<&'static str>| audit.lock().unwrap().extend(entries)
+    /// };
+    /// let run = effect.run_writer_streaming(&(), sink);
+    ///
+    /// // Cancelled part way: the first entry is still flushed.
+    /// let timed_out = tokio::time::timeout(Duration::from_millis(10), run).await;
+    /// assert!(timed_out.is_err());
+    /// assert_eq!(*audit.lock().unwrap(), vec!["debited account"]);
+    /// # });
+    /// ```
+    fn run_writer_streaming<'a, S>(
+        self,
+        env: &'a Self::Env,
+        sink: S,
+    ) -> WriterGuard<'a, Self::Output, Self::Error, Self::Writes, S>
+    where
+        Self: Sized + 'a,
+        S: FnMut(Self::Writes),
+    {
+        WriterGuard::new(self, env, sink)
+    }
+
     /// Convert to a boxed WriterEffect for type erasure.
     ///
     /// Use this when you need to:
@@ -214,7 +304,7 @@ pub trait WriterEffectExt: WriterEffect {
         Self: Sized + Send + 'static,
         Self::Output: Send + 'static,
         Self::Error: Send + 'static,
-        Self::Env: Clone + Send + Sync + 'static,
+        Self::Env: Send + Sync + 'static,
         Self::Writes: Monoid + Send + 'static,
     {
         BoxedWriterEffect::new(self)
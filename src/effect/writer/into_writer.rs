@@ -128,7 +128,7 @@ where
 pub fn into_writer<E, Env, W>(effect: E) -> IntoWriter<E, W>
 where
     E: Effect<Env = Env>,
-    Env: Clone + Send + Sync,
+    Env: Send + Sync,
     W: Monoid + Send,
 {
     IntoWriter {
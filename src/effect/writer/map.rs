@@ -1,6 +1,6 @@
 //! WriterMap combinator - transform the output of a WriterEffect.
 
-use crate::effect::writer::WriterEffect;
+use crate::effect::writer::{WriteBuffer, WriterEffect};
 use crate::effect::Effect;
 
 /// An effect that transforms the output of a WriterEffect.
@@ -68,4 +68,15 @@ where
         let (result, writes) = self.inner.run_writer(env).await;
         (result.map(self.f), writes)
     }
+
+    async fn run_writer_buffered(
+        self,
+        env: &Self::Env,
+        buffer: &WriteBuffer<Self::Writes>,
+    ) -> Result<Self::Output, Self::Error> {
+        self.inner
+            .run_writer_buffered(env, buffer)
+            .await
+            .map(self.f)
+    }
 }
@@ -46,6 +46,11 @@
 //! - [`WriterEffectExt`] - Extension trait providing combinator methods
 //! - [`tell()`], [`tell_one`] - Functions to emit values
 //! - [`into_writer()`] - Lift regular Effects into WriterEffect
+//! - [`WriteFilter`], [`SampledWriter`] - Sample or level-filter verbose logs
+//! - [`tell_summary`], [`Summarize`], [`Counts`] - Fold entries into a summary
+//!   instead of storing them
+//! - `retry_writer` - Retry keeping the writes of failed attempts (requires
+//!   the `async` feature)
 //!
 //! # Example: Audit Logging
 //!
@@ -82,6 +87,11 @@ mod map_err;
 mod or_else;
 mod pass;
 pub mod prelude;
+#[cfg(feature = "async")]
+mod retry;
+mod sampled;
+mod streaming;
+mod summary;
 mod tap_tell;
 mod tell;
 mod trait_def;
@@ -111,9 +121,22 @@ pub use tap_tell::TapTell;
 pub use tell::Tell;
 pub use zip::WriterZip;
 
+// Re-export retries
+#[cfg(feature = "async")]
+pub use retry::{retry_writer, RetryWriter};
+
 // Re-export boxed types
 pub use boxed::BoxedWriterEffect;
 
+// Re-export sampling types
+pub use sampled::{SampledWriter, WriteFilter};
+
+// Re-export streaming types
+pub use streaming::{WriteBuffer, WriterGuard};
+
+// Re-export summary types
+pub use summary::{tell_summary, Counts, Summarize};
+
 // Re-export collection combinators
 pub use combinators::{fold_writer, traverse_writer};
 
@@ -1,6 +1,6 @@
 //! WriterOrElse combinator - recover from errors while preserving writes.
 
-use crate::effect::writer::WriterEffect;
+use crate::effect::writer::{WriteBuffer, WriterEffect};
 use crate::effect::Effect;
 use crate::Semigroup;
 
@@ -81,4 +81,15 @@ where
             }
         }
     }
+
+    async fn run_writer_buffered(
+        self,
+        env: &Self::Env,
+        buffer: &WriteBuffer<Self::Writes>,
+    ) -> Result<Self::Output, E2::Error> {
+        match self.inner.run_writer_buffered(env, buffer).await {
+            Ok(value) => Ok(value),
+            Err(e) => (self.f)(e).run_writer_buffered(env, buffer).await,
+        }
+    }
 }
@@ -40,8 +40,15 @@ pub use crate::effect::writer::pass::Pass;
 pub use crate::effect::writer::tap_tell::TapTell;
 pub use crate::effect::writer::zip::WriterZip;
 
+// Retries
+#[cfg(feature = "async")]
+pub use crate::effect::writer::retry::{retry_writer, RetryWriter};
+
 // Boxed type
 pub use crate::effect::writer::boxed::BoxedWriterEffect;
+pub use crate::effect::writer::sampled::{SampledWriter, WriteFilter};
+pub use crate::effect::writer::streaming::{WriteBuffer, WriterGuard};
+pub use crate::effect::writer::summary::{tell_summary, Counts, Summarize};
 
 // Collection combinators
 pub use crate::effect::writer::combinators::{fold_writer, traverse_writer};
@@ -0,0 +1,154 @@
+//! Retrying writer effects without losing their writes.
+
+use crate::effect::retry::{bounded_attempt, retry_attempts, timed_attempt};
+use crate::effect::writer::{WriteBuffer, WriterEffect};
+use crate::effect::Effect;
+use crate::retry::{RetryExhausted, RetryPolicy, RetrySuccess, TimeoutError};
+use crate::{Monoid, Semigroup};
+
+/// Retry a writer effect, keeping the writes of every attempt.
+///
+/// Like [`retry`](crate::effect::retry::retry), each attempt runs a fresh
+/// effect from `make_effect` until one succeeds or the policy gives up.
+/// Retrying a writer effect with `retry` would discard the writes of each
+/// failed attempt together with the attempt, which are usually the logs
+/// needed to see why it failed. Here the writes of all attempts are combined
+/// in order, whether the retry succeeds or is exhausted.
+///
+/// With [`run_writer_streaming`](crate::effect::writer::WriterEffectExt::run_writer_streaming),
+/// the writes of each attempt reach the sink as soon as that attempt ends.
+///
+/// Each attempt is bounded by the policy's
+/// [attempt timeout](RetryPolicy::with_attempt_timeout), if it has one, and
+/// errors are reported as [`TimeoutError`].
+///
+/// # Example
+///
+/// ```rust
+/// use std::sync::atomic::{AtomicU32, Ordering};
+/// use std::sync::Arc;
+/// use std::time::Duration;
+///
+/// use stillwater::effect::prelude::*;
+/// use stillwater::effect::writer::prelude::*;
+/// use stillwater::RetryPolicy;
+///
+/// # tokio_test::block_on(async {
+/// let calls = Arc::new(AtomicU32::new(0));
+/// let effect = retry_writer(
+///     move || {
+///         let n = calls.fetch_add(1, Ordering::SeqCst) + 1;
+///         tell_one::<_, String, ()>(format!("attempt {}", n)).and_then(move |_| {
+///             into_writer::<_, _, Vec<String>>(if n < 3 {
+///                 fail("unavailable".to_string()).boxed()
+///             } else {
+///                 pure(n).boxed()
+///             })
+///         })
+///     },
+///     RetryPolicy::constant(Duration::from_millis(1)).with_max_retries(5),
+/// );
+///
+/// let (result, logs) = effect.run_writer(&()).await;
+/// assert_eq!(result.unwrap().into_value(), 3);
+/// assert_eq!(logs, vec!["attempt 1", "attempt 2", "attempt 3"]);
+/// # });
+/// ```
+pub fn retry_writer<F, Eff>(make_effect: F, policy: RetryPolicy) -> RetryWriter<F>
+where
+    F: Fn() -> Eff + Send,
+    Eff: WriterEffect,
+{
+    RetryWriter {
+        make_effect,
+        policy,
+    }
+}
+
+/// Retries a writer effect, combining the writes of every attempt.
+///
+/// Created by [`retry_writer`].
+pub struct RetryWriter<F> {
+    pub(crate) make_effect: F,
+    pub(crate) policy: RetryPolicy,
+}
+
+impl<F> std::fmt::Debug for RetryWriter<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RetryWriter")
+            .field("make_effect", &"<function>")
+            .field("policy", &self.policy)
+            .finish()
+    }
+}
+
+impl<F, Eff> Effect for RetryWriter<F>
+where
+    F: Fn() -> Eff + Send,
+    Eff: WriterEffect,
+{
+    type Output = RetrySuccess<Eff::Output>;
+    type Error = RetryExhausted<TimeoutError<Eff::Error>>;
+    type Env = Eff::Env;
+
+    async fn run(self, env: &Self::Env) -> Result<Self::Output, Self::Error> {
+        let policy = &self.policy;
+        let make_effect = self.make_effect;
+        let (result, ()) = retry_attempts(policy, (), move |()| {
+            let effect = make_effect();
+            async move { (timed_attempt(policy, effect.run(env)).await, ()) }
+        })
+        .await;
+        result
+    }
+}
+
+impl<F, Eff> WriterEffect for RetryWriter<F>
+where
+    F: Fn() -> Eff + Send,
+    Eff: WriterEffect,
+{
+    type Writes = Eff::Writes;
+
+    async fn run_writer(
+        self,
+        env: &Self::Env,
+    ) -> (Result<Self::Output, Self::Error>, Self::Writes) {
+        let policy = &self.policy;
+        let make_effect = self.make_effect;
+        retry_attempts(policy, Eff::Writes::empty(), move |writes| {
+            let effect = make_effect();
+            async move {
+                // The writes of an attempt that timed out are lost with it
+                match bounded_attempt(policy, effect.run_writer(env)).await {
+                    Ok((result, attempt_writes)) => (
+                        result.map_err(TimeoutError::Inner),
+                        writes.combine(attempt_writes),
+                    ),
+                    Err(duration) => (Err(TimeoutError::Timeout { duration }), writes),
+                }
+            }
+        })
+        .await
+    }
+
+    async fn run_writer_buffered(
+        self,
+        env: &Self::Env,
+        buffer: &WriteBuffer<Self::Writes>,
+    ) -> Result<Self::Output, Self::Error> {
+        let policy = &self.policy;
+        let make_effect = self.make_effect;
+        let (result, ()) = retry_attempts(policy, (), move |()| {
+            let effect = make_effect();
+            async move {
+                (
+                    timed_attempt(policy, effect.run_writer_buffered(env, buffer)).await,
+                    (),
+                )
+            }
+        })
+        .await;
+        result
+    }
+}
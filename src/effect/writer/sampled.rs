@@ -0,0 +1,223 @@
+//! Sampling and level filtering for verbose writer logs.
+//!
+//! A [`WriteFilter`] decides which log entries to keep: entries below a
+//! level threshold are dropped, and of the rest only every Nth is kept.
+//! It can be applied in two places:
+//!
+//! - [`WriteFilter::entry`] filters as entries are emitted, producing a
+//!   [`SampledWriter`] to `tell`. Dropped entries are never stored, so memory
+//!   stays bounded however verbose the pipeline is.
+//! - [`WriterEffectExt::run_writer_filtered`](crate::effect::writer::WriterEffectExt::run_writer_filtered)
+//!   filters a `Vec` of writes after the effect has run, which keeps the
+//!   pipeline unchanged but still collects every entry first.
+//!
+//! # Example
+//!
+//! ```rust
+//! use stillwater::effect::writer::prelude::*;
+//! use stillwater::effect::prelude::*;
+//!
+//! #[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+//! enum Level {
+//!     Debug,
+//!     Info,
+//! }
+//!
+//! # tokio_test::block_on(async {
+//! let filter = WriteFilter::min_level(Level::Info, |entry: &(Level, u32)| entry.0);
+//!
+//! let effect = traverse_writer((0..6).collect::<Vec<u32>>(), move |n| {
+//!     let level = if n % 2 == 0 { Level::Info } else { Level::Debug };
+//!     tell::<_, String, ()>(filter.entry((level, n)))
+//! });
+//!
+//! let (_, log) = effect.run_writer(&()).await;
+//! assert_eq!(log.entries(), &[(Level::Info, 0), (Level::Info, 2), (Level::Info, 4)]);
+//! assert_eq!(log.dropped(), 3);
+//! # });
+//! ```
+
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use crate::{Monoid, Semigroup};
+
+type KeepFn<T> = Arc<dyn Fn(&T) -> bool + Send + Sync>;
+
+/// Decides which writer entries to keep.
+///
+/// Entries rejected by the level or predicate check are dropped first; the
+/// remaining entries are counted and every Nth one is kept, starting with
+/// the first. The count is shared by clones of the filter, so one filter
+/// samples a whole run consistently.
+pub struct WriteFilter<T> {
+    every: usize,
+    keep: Option<KeepFn<T>>,
+    seen: Arc<AtomicUsize>,
+}
+
+impl<T> WriteFilter<T> {
+    /// A filter that keeps every entry.
+    pub fn all() -> Self {
+        WriteFilter {
+            every: 1,
+            keep: None,
+            seen: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// A filter that keeps the first entry and every `n`th after it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero.
+    pub fn every(n: usize) -> Self {
+        Self::all().sample_every(n)
+    }
+
+    /// A filter that keeps entries whose level is at least `min`.
+    pub fn min_level<L, F>(min: L, level_of: F) -> Self
+    where
+        T: 'static,
+        L: PartialOrd + Send + Sync + 'static,
+        F: Fn(&T) -> L + Send + Sync + 'static,
+    {
+        Self::all().keep_if(move |entry| level_of(entry) >= min)
+    }
+
+    /// Keep only every `n`th entry that passes the other checks.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero.
+    pub fn sample_every(mut self, n: usize) -> Self {
+        assert!(n > 0, "sampling interval must be at least 1");
+        self.every = n;
+        self
+    }
+
+    /// Drop entries for which `predicate` returns `false`.
+    ///
+    /// Combined with any existing predicate; an entry must pass both.
+    pub fn keep_if<F>(mut self, predicate: F) -> Self
+    where
+        T: 'static,
+        F: Fn(&T) -> bool + Send + Sync + 'static,
+    {
+        self.keep = Some(match self.keep.take() {
+            Some(existing) => Arc::new(move |entry: &T| existing(entry) && predicate(entry)),
+            None => Arc::new(predicate),
+        });
+        self
+    }
+
+    /// Whether `entry` should be kept, counting it toward the sample.
+    pub fn admit(&self, entry: &T) -> bool {
+        if let Some(keep) = &self.keep {
+            if !keep(entry) {
+                return false;
+            }
+        }
+        self.seen.fetch_add(1, Ordering::Relaxed) % self.every == 0
+    }
+
+    /// Wrap `entry` for [`tell`](crate::effect::writer::tell), dropping it
+    /// now if the filter rejects it.
+    pub fn entry(&self, entry: T) -> SampledWriter<T> {
+        if self.admit(&entry) {
+            SampledWriter {
+                entries: vec![entry],
+                dropped: 0,
+            }
+        } else {
+            SampledWriter {
+                entries: Vec::new(),
+                dropped: 1,
+            }
+        }
+    }
+
+    /// Keep the admitted entries of `entries`, in order.
+    pub fn apply(&self, entries: Vec<T>) -> Vec<T> {
+        entries
+            .into_iter()
+            .filter(|entry| self.admit(entry))
+            .collect()
+    }
+}
+
+impl<T> Clone for WriteFilter<T> {
+    fn clone(&self) -> Self {
+        WriteFilter {
+            every: self.every,
+            keep: self.keep.clone(),
+            seen: Arc::clone(&self.seen),
+        }
+    }
+}
+
+impl<T> Default for WriteFilter<T> {
+    fn default() -> Self {
+        Self::all()
+    }
+}
+
+impl<T> fmt::Debug for WriteFilter<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WriteFilter")
+            .field("every", &self.every)
+            .field("keep", &self.keep.as_ref().map(|_| "<function>"))
+            .field("seen", &self.seen.load(Ordering::Relaxed))
+            .finish()
+    }
+}
+
+/// Writer output that keeps only the entries a [`WriteFilter`] admitted.
+///
+/// Combines by concatenating kept entries and adding up the dropped count.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SampledWriter<T> {
+    entries: Vec<T>,
+    dropped: usize,
+}
+
+impl<T> SampledWriter<T> {
+    /// The kept entries, in emission order.
+    pub fn entries(&self) -> &[T] {
+        &self.entries
+    }
+
+    /// How many entries the filter dropped.
+    pub fn dropped(&self) -> usize {
+        self.dropped
+    }
+
+    /// Consume the writer, returning the kept entries.
+    pub fn into_entries(self) -> Vec<T> {
+        self.entries
+    }
+}
+
+impl<T> Semigroup for SampledWriter<T> {
+    fn combine(mut self, other: Self) -> Self {
+        self.entries.extend(other.entries);
+        self.dropped += other.dropped;
+        self
+    }
+}
+
+impl<T> Monoid for SampledWriter<T> {
+    fn empty() -> Self {
+        SampledWriter {
+            entries: Vec::new(),
+            dropped: 0,
+        }
+    }
+}
+
+impl<T> Default for SampledWriter<T> {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
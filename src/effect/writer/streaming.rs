@@ -0,0 +1,153 @@
+//! Streaming writer output to a sink, with a flush on drop.
+//!
+//! [`run_writer`](WriterEffect::run_writer) hands back the accumulated
+//! writes only when the effect finishes, so an effect that is cancelled
+//! part way, for example by a timeout or a `race`, loses everything it had
+//! logged. [`run_writer_streaming`](WriterEffectExt::run_writer_streaming)
+//! instead collects the writes of each finished step in a [`WriteBuffer`]
+//! and passes them to a sink as the effect runs. The returned
+//! [`WriterGuard`] flushes whatever is still buffered when it is dropped,
+//! so the writes of every completed step reach the sink even if the effect
+//! never completes.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+
+use crate::effect::boxed::BoxFuture;
+use crate::effect::writer::WriterEffect;
+use crate::Monoid;
+
+/// Writes collected by a streaming run that have not reached the sink yet.
+///
+/// Passed to [`WriterEffect::run_writer_buffered`]. Cloning it gives
+/// another handle to the same buffer.
+pub struct WriteBuffer<W> {
+    writes: Arc<Mutex<Option<W>>>,
+}
+
+impl<W> Clone for WriteBuffer<W> {
+    fn clone(&self) -> Self {
+        WriteBuffer {
+            writes: Arc::clone(&self.writes),
+        }
+    }
+}
+
+impl<W> std::fmt::Debug for WriteBuffer<W> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WriteBuffer").finish_non_exhaustive()
+    }
+}
+
+impl<W: Monoid> WriteBuffer<W> {
+    pub(crate) fn new() -> Self {
+        WriteBuffer {
+            writes: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Add the writes of a finished step after those already buffered.
+    pub fn push(&self, writes: W) {
+        let mut buffered = self.writes.lock().unwrap_or_else(|e| e.into_inner());
+        *buffered = Some(match buffered.take() {
+            Some(earlier) => earlier.combine(writes),
+            None => writes,
+        });
+    }
+
+    /// Take everything buffered so far.
+    pub(crate) fn take(&self) -> Option<W> {
+        self.writes.lock().unwrap_or_else(|e| e.into_inner()).take()
+    }
+}
+
+/// A streaming writer run.
+///
+/// Created by
+/// [`WriterEffectExt::run_writer_streaming`](crate::effect::writer::WriterEffectExt::run_writer_streaming).
+/// Await it for the effect's result. Each time it is polled, writes from
+/// the steps finished so far are passed to the sink; dropping it before
+/// the effect completes passes the remaining buffered writes to the sink
+/// once more, on a best-effort basis.
+///
+/// Sequential combinators such as `and_then`, `map`, and `traverse_writer`
+/// buffer each step's writes as soon as it finishes. Combinators that need
+/// the writes of their whole inner effect, such as `censor` or `listen`,
+/// and `zip`, whose sides run concurrently, buffer them only when the inner
+/// effect finishes.
+#[must_use = "the effect does not run unless the guard is awaited"]
+pub struct WriterGuard<'a, T, E, W, S>
+where
+    W: Monoid,
+    S: FnMut(W),
+{
+    run: BoxFuture<'a, Result<T, E>>,
+    buffer: WriteBuffer<W>,
+    sink: S,
+}
+
+impl<'a, T, E, W, S> WriterGuard<'a, T, E, W, S>
+where
+    W: Monoid,
+    S: FnMut(W),
+{
+    pub(crate) fn new<Eff>(effect: Eff, env: &'a Eff::Env, sink: S) -> Self
+    where
+        Eff: WriterEffect<Output = T, Error = E, Writes = W> + 'a,
+        W: Send + 'a,
+    {
+        let buffer = WriteBuffer::new();
+        let run_buffer = buffer.clone();
+        WriterGuard {
+            run: Box::pin(async move { effect.run_writer_buffered(env, &run_buffer).await }),
+            buffer,
+            sink,
+        }
+    }
+
+    fn flush(&mut self) {
+        if let Some(writes) = self.buffer.take() {
+            (self.sink)(writes);
+        }
+    }
+}
+
+impl<T, E, W, S> Future for WriterGuard<'_, T, E, W, S>
+where
+    W: Monoid,
+    S: FnMut(W) + Unpin,
+{
+    type Output = Result<T, E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let poll = this.run.as_mut().poll(cx);
+        this.flush();
+        poll
+    }
+}
+
+impl<W, S, T, E> Drop for WriterGuard<'_, T, E, W, S>
+where
+    W: Monoid,
+    S: FnMut(W),
+{
+    fn drop(&mut self) {
+        self.flush();
+    }
+}
+
+impl<W, S, T, E> std::fmt::Debug for WriterGuard<'_, T, E, W, S>
+where
+    W: Monoid,
+    S: FnMut(W),
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WriterGuard")
+            .field("run", &"<future>")
+            .field("sink", &"<function>")
+            .finish()
+    }
+}
@@ -0,0 +1,194 @@
+//! Folding writer entries into a summary as they are emitted.
+//!
+//! A high-volume pipeline that only needs aggregates, such as how many
+//! entries were logged at each level, does not have to keep every entry.
+//! [`tell_summary`] turns each entry into a [`Summarize`] monoid the moment
+//! it is emitted, and the writer combines those summaries as the effects
+//! run, so memory is bounded by the size of the summary instead of the
+//! number of entries.
+//!
+//! The effect stays a pure writer: switching the summary type back to
+//! `Vec<T>` gives the full log, which is handy in tests.
+//!
+//! # Example
+//!
+//! ```rust
+//! use stillwater::effect::writer::prelude::*;
+//!
+//! #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+//! enum Level {
+//!     Debug,
+//!     Warn,
+//! }
+//!
+//! # tokio_test::block_on(async {
+//! let effect = traverse_writer((0..1000).collect::<Vec<u32>>(), |n| {
+//!     let level = if n % 100 == 0 { Level::Warn } else { Level::Debug };
+//!     tell_summary::<Counts<Level>, _, String, ()>(level)
+//! });
+//!
+//! let (_, counts) = effect.run_writer(&()).await;
+//! assert_eq!(counts.get(&Level::Warn), 10);
+//! assert_eq!(counts.get(&Level::Debug), 990);
+//! # });
+//! ```
+
+use std::collections::BTreeMap;
+use std::ops::Add;
+
+use crate::effect::writer::tell::Tell;
+use crate::monoid::Sum;
+use crate::{Monoid, Semigroup};
+
+/// A monoid that can summarize a single writer entry.
+///
+/// `summarize` builds the summary of one entry; the writer combines the
+/// summaries with [`Semigroup::combine`].
+pub trait Summarize<T>: Monoid {
+    /// The summary of a single entry.
+    fn summarize(entry: T) -> Self;
+}
+
+/// Keeps every entry, for when the full log is wanted.
+impl<T> Summarize<T> for Vec<T> {
+    fn summarize(entry: T) -> Self {
+        vec![entry]
+    }
+}
+
+/// Adds up numeric entries.
+impl<T: Add<Output = T> + Default> Summarize<T> for Sum<T> {
+    fn summarize(entry: T) -> Self {
+        Sum(entry)
+    }
+}
+
+/// Emit `entry` folded into the summary `S`.
+///
+/// The entry itself is not stored; only its summary is combined into the
+/// writer's output.
+pub fn tell_summary<S, T, E, Env>(entry: T) -> Tell<S, E, Env>
+where
+    S: Summarize<T> + Send,
+    E: Send,
+    Env: Send + Sync,
+{
+    crate::effect::writer::tell(S::summarize(entry))
+}
+
+/// Number of entries seen per key.
+///
+/// Summarizes entries that are themselves keys, such as log levels or
+/// histogram buckets. Map richer entries to a key before emitting them.
+///
+/// # Example
+///
+/// ```rust
+/// use stillwater::effect::writer::Counts;
+/// use stillwater::Semigroup;
+///
+/// // A latency histogram with 100ms buckets
+/// let histogram = [40u64, 120, 180, 95, 310]
+///     .into_iter()
+///     .map(|ms| Counts::of(ms / 100 * 100))
+///     .fold(Counts::new(), Semigroup::combine);
+///
+/// assert_eq!(histogram.get(&0), 2);
+/// assert_eq!(histogram.get(&100), 2);
+/// assert_eq!(histogram.total(), 5);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Counts<K> {
+    counts: BTreeMap<K, usize>,
+}
+
+impl<K: Ord> Counts<K> {
+    /// An empty count.
+    pub fn new() -> Self {
+        Counts {
+            counts: BTreeMap::new(),
+        }
+    }
+
+    /// A count of one for `key`.
+    pub fn of(key: K) -> Self {
+        Counts {
+            counts: BTreeMap::from([(key, 1)]),
+        }
+    }
+
+    /// How many times `key` was seen.
+    pub fn get(&self, key: &K) -> usize {
+        self.counts.get(key).copied().unwrap_or(0)
+    }
+
+    /// How many entries were seen in total.
+    pub fn total(&self) -> usize {
+        self.counts.values().sum()
+    }
+
+    /// The keys seen and their counts, in key order.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, usize)> {
+        self.counts.iter().map(|(key, count)| (key, *count))
+    }
+
+    /// Consume the counts, returning the underlying map.
+    pub fn into_map(self) -> BTreeMap<K, usize> {
+        self.counts
+    }
+}
+
+impl<K: Ord> Default for Counts<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Ord> Semigroup for Counts<K> {
+    fn combine(mut self, other: Self) -> Self {
+        for (key, count) in other.counts {
+            *self.counts.entry(key).or_insert(0) += count;
+        }
+        self
+    }
+}
+
+impl<K: Ord> Monoid for Counts<K> {
+    fn empty() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Ord> Summarize<K> for Counts<K> {
+    fn summarize(entry: K) -> Self {
+        Self::of(entry)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::effect::writer::WriterEffect;
+    use crate::effect::EffectExt;
+
+    #[tokio::test]
+    async fn test_vec_summary_keeps_full_log() {
+        let effect = tell_summary::<Vec<&str>, _, String, ()>("a")
+            .and_then(|_| tell_summary("b"))
+            .map(|_| 1);
+
+        let (result, log) = effect.run_writer(&()).await;
+
+        assert_eq!(result, Ok(1));
+        assert_eq!(log, vec!["a", "b"]);
+    }
+
+    #[tokio::test]
+    async fn test_sum_summary_adds_entries() {
+        let effect = tell_summary::<Sum<u64>, _, String, ()>(3).and_then(|_| tell_summary(4));
+
+        let (_, total) = effect.run_writer(&()).await;
+
+        assert_eq!(total, Sum(7));
+    }
+}
@@ -53,7 +53,7 @@ impl<W, E, Env> Effect for Tell<W, E, Env>
 where
     W: Monoid + Send,
     E: Send,
-    Env: Clone + Send + Sync,
+    Env: Send + Sync,
 {
     type Output = ();
     type Error = E;
@@ -68,7 +68,7 @@ impl<W, E, Env> WriterEffect for Tell<W, E, Env>
 where
     W: Monoid + Send,
     E: Send,
-    Env: Clone + Send + Sync,
+    Env: Send + Sync,
 {
     type Writes = W;
 
@@ -132,7 +132,7 @@ pub fn tell<W, E, Env>(w: W) -> Tell<W, E, Env>
 where
     W: Monoid + Send,
     E: Send,
-    Env: Clone + Send + Sync,
+    Env: Send + Sync,
 {
     Tell {
         writes: w,
@@ -163,7 +163,7 @@ pub fn tell_one<T, E, Env>(item: T) -> Tell<Vec<T>, E, Env>
 where
     T: Send,
     E: Send,
-    Env: Clone + Send + Sync,
+    Env: Send + Sync,
 {
     Tell {
         writes: vec![item],
@@ -435,3 +435,153 @@ async fn test_complex_chain() {
         ]
     );
 }
+
+// ============================================================================
+// Sampling Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_write_filter_level_then_sample() {
+    let filter = WriteFilter::min_level(2, |entry: &(u8, u32)| entry.0).sample_every(2);
+    let emitter = filter.clone();
+
+    let effect = traverse_writer((0..8).collect::<Vec<u32>>(), move |n| {
+        let level = if n % 4 == 0 { 1 } else { 3 };
+        tell::<_, String, ()>(emitter.entry((level, n))).map(move |_| n)
+    });
+
+    let (result, log) = effect.run_writer(&()).await;
+
+    assert_eq!(result.map(|v| v.len()), Ok(8));
+    // 0 and 4 are below the level; of 1, 2, 3, 5, 6, 7 every second is kept.
+    assert_eq!(log.into_entries(), vec![(3, 1), (3, 3), (3, 6)]);
+    assert!(format!("{:?}", filter).contains("seen: 6"));
+}
+
+#[tokio::test]
+async fn test_sampled_writer_counts_dropped() {
+    let filter = WriteFilter::every(3);
+    let effect = tell::<_, String, ()>(filter.entry("a"))
+        .and_then(|_| tell(filter.entry("b")))
+        .and_then(|_| tell(filter.entry("c")))
+        .and_then(|_| tell(filter.entry("d")));
+
+    let (_, log) = effect.run_writer(&()).await;
+
+    assert_eq!(log.entries(), &["a", "d"]);
+    assert_eq!(log.dropped(), 2);
+}
+
+#[tokio::test]
+async fn test_run_writer_filtered_all_keeps_everything() {
+    let effect = tell_one::<_, String, ()>(1).and_then(|_| tell_one(2));
+    let (result, writes) = effect.run_writer_filtered(&(), &WriteFilter::all()).await;
+
+    assert_eq!(result, Ok(()));
+    assert_eq!(writes, vec![1, 2]);
+}
+
+#[test]
+#[should_panic(expected = "sampling interval must be at least 1")]
+fn test_write_filter_rejects_zero_interval() {
+    let _ = WriteFilter::<()>::every(0);
+}
+
+#[tokio::test]
+async fn test_run_writer_streaming_passes_all_writes_to_sink() {
+    let effect = traverse_writer(vec![1, 2, 3], |n| {
+        tell_one::<_, String, ()>(n).map(move |_| n * 10)
+    })
+    .boxed_writer();
+    let mut streamed = Vec::new();
+
+    let result = effect
+        .run_writer_streaming(&(), |writes: Vec<i32>| streamed.extend(writes))
+        .await;
+
+    assert_eq!(result, Ok(vec![10, 20, 30]));
+    assert_eq!(streamed, vec![1, 2, 3]);
+}
+
+#[tokio::test]
+async fn test_writer_guard_flushes_completed_steps_on_drop() {
+    let effect = tell_one::<_, String, ()>("started")
+        .and_then(|_| tell_one("loaded"))
+        .and_then(|_| {
+            into_writer::<_, _, Vec<&str>>(from_async(|_: &()| {
+                std::future::pending::<Result<(), String>>()
+            }))
+        })
+        .and_then(|_| tell_one("finished"));
+    let mut streamed = Vec::new();
+
+    let run = effect.run_writer_streaming(&(), |writes| streamed.extend(writes));
+    let timed_out = tokio::time::timeout(std::time::Duration::from_millis(10), run).await;
+
+    assert!(timed_out.is_err());
+
+    assert_eq!(streamed, vec!["started", "loaded"]);
+}
+
+// ============================================================================
+// Retry Tests
+// ============================================================================
+
+#[cfg(feature = "async")]
+mod retry_tests {
+    use super::*;
+    use crate::RetryPolicy;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    fn flaky(
+        calls: Arc<AtomicU32>,
+        succeed_on: u32,
+    ) -> impl Fn() -> BoxedWriterEffect<u32, String, (), Vec<String>> + Send {
+        move || {
+            let n = calls.fetch_add(1, Ordering::SeqCst) + 1;
+            tell_one::<_, String, ()>(format!("attempt {}", n))
+                .and_then(move |_| {
+                    into_writer::<_, _, Vec<String>>(if n < succeed_on {
+                        fail(format!("failure {}", n)).boxed()
+                    } else {
+                        pure(n).boxed()
+                    })
+                })
+                .boxed_writer()
+        }
+    }
+
+    fn policy(max_retries: u32) -> RetryPolicy {
+        RetryPolicy::constant(Duration::from_millis(1)).with_max_retries(max_retries)
+    }
+
+    #[tokio::test]
+    async fn test_retry_writer_keeps_writes_when_exhausted() {
+        let effect = retry_writer(flaky(Arc::new(AtomicU32::new(0)), 10), policy(2));
+
+        let (result, writes) = effect.run_writer(&()).await;
+
+        let exhausted = result.unwrap_err();
+        assert_eq!(
+            exhausted.final_error,
+            crate::TimeoutError::Inner("failure 3".to_string())
+        );
+        assert_eq!(exhausted.attempts, 3);
+        assert_eq!(writes, vec!["attempt 1", "attempt 2", "attempt 3"]);
+    }
+
+    #[tokio::test]
+    async fn test_retry_writer_streams_each_attempt() {
+        let effect = retry_writer(flaky(Arc::new(AtomicU32::new(0)), 2), policy(3));
+        let mut streamed = Vec::new();
+
+        let result = effect
+            .run_writer_streaming(&(), |writes: Vec<String>| streamed.extend(writes))
+            .await;
+
+        assert_eq!(result.unwrap().into_value(), 2);
+        assert_eq!(streamed, vec!["attempt 1", "attempt 2"]);
+    }
+}
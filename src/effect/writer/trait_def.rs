@@ -2,6 +2,7 @@
 
 use std::future::Future;
 
+use crate::effect::writer::WriteBuffer;
 use crate::effect::Effect;
 use crate::Monoid;
 
@@ -64,4 +65,25 @@ pub trait WriterEffect: Effect {
         self,
         env: &Self::Env,
     ) -> impl Future<Output = (Result<Self::Output, Self::Error>, Self::Writes)> + Send;
+
+    /// Execute this effect, adding its writes to `buffer` as they are
+    /// produced.
+    ///
+    /// Used by
+    /// [`run_writer_streaming`](crate::effect::writer::WriterEffectExt::run_writer_streaming).
+    /// The default runs [`run_writer`](Self::run_writer) and buffers all of
+    /// the writes when it finishes. Sequential combinators override it to
+    /// buffer the writes of each step as soon as that step finishes, so they
+    /// survive if a later step is cancelled.
+    fn run_writer_buffered(
+        self,
+        env: &Self::Env,
+        buffer: &WriteBuffer<Self::Writes>,
+    ) -> impl Future<Output = Result<Self::Output, Self::Error>> + Send {
+        async move {
+            let (result, writes) = self.run_writer(env).await;
+            buffer.push(writes);
+            result
+        }
+    }
 }
@@ -43,6 +43,8 @@
 //! assert_eq!(description, "From cache: cached");
 //! ```
 
+use alloc::vec::Vec;
+
 use crate::Validation;
 
 /// A value that is either `Left(L)` or `Right(R)`.
@@ -788,7 +790,7 @@ where
 
 impl<L, R> IntoIterator for Either<L, R> {
     type Item = R;
-    type IntoIter = std::option::IntoIter<R>;
+    type IntoIter = core::option::IntoIter<R>;
 
     fn into_iter(self) -> Self::IntoIter {
         self.into_right().into_iter()
@@ -797,7 +799,7 @@ impl<L, R> IntoIterator for Either<L, R> {
 
 impl<'a, L, R> IntoIterator for &'a Either<L, R> {
     type Item = &'a R;
-    type IntoIter = std::option::IntoIter<&'a R>;
+    type IntoIter = core::option::IntoIter<&'a R>;
 
     fn into_iter(self) -> Self::IntoIter {
         self.as_ref().into_right().into_iter()
@@ -0,0 +1,7 @@
+//! Integrations with other crates, each behind a feature of the same name.
+//!
+//! - [`prometheus`]: Prometheus metrics for retries and the
+//!   [`PriorityExecutor`](crate::effect::queue::PriorityExecutor).
+
+#[cfg(feature = "prometheus")]
+pub mod prometheus;
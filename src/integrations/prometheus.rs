@@ -0,0 +1,447 @@
+//! Prometheus metrics for retries and the priority executor.
+//!
+//! Each component has a metrics handle that registers its metrics with a
+//! [`Registry`] once; the application then serves that registry as usual.
+//!
+//! - [`RetryMetrics`] covers the retry loops of
+//!   [`effect::retry`](mod@crate::effect::retry). Those are free functions, so
+//!   the handle is installed process-wide and every loop reports into it,
+//!   labelled by [`RetryPolicy::with_name`](crate::RetryPolicy::with_name).
+//! - [`QueueMetrics`] covers
+//!   [`PriorityExecutor`](crate::effect::queue::PriorityExecutor)s attached
+//!   with [`with_metrics`](crate::effect::queue::PriorityExecutor::with_metrics),
+//!   labelled by executor name.
+//!
+//! # Example
+//!
+//! ```rust
+//! use std::time::Duration;
+//!
+//! use prometheus::{Encoder, Registry, TextEncoder};
+//! use stillwater::effect::prelude::*;
+//! use stillwater::effect::retry::retry;
+//! use stillwater::integrations::prometheus::RetryMetrics;
+//! use stillwater::RetryPolicy;
+//!
+//! # tokio_test::block_on(async {
+//! let registry = Registry::new();
+//! RetryMetrics::register(&registry).unwrap().install().unwrap();
+//!
+//! let effect = retry(
+//!     || pure::<_, String, ()>("ok"),
+//!     RetryPolicy::constant(Duration::from_millis(10))
+//!         .with_max_retries(3)
+//!         .with_name("inventory"),
+//! );
+//! effect.execute(&()).await.unwrap();
+//!
+//! let mut text = Vec::new();
+//! TextEncoder::new().encode(&registry.gather(), &mut text).unwrap();
+//! let text = String::from_utf8(text).unwrap();
+//! assert!(text.contains(r#"stillwater_retry_outcomes_total{outcome="success",policy="inventory"} 1"#));
+//! # });
+//! ```
+
+use std::fmt;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use ::prometheus::{
+    Histogram, HistogramOpts, HistogramVec, IntCounter, IntCounterVec, IntGauge, IntGaugeVec, Opts,
+    Registry, Result,
+};
+
+use crate::effect::queue::QueueEvent;
+use crate::retry::RetryPolicy;
+
+static RETRY_METRICS: OnceLock<RetryMetrics> = OnceLock::new();
+
+/// Metrics for the retry loops in [`effect::retry`](mod@crate::effect::retry).
+///
+/// | Metric | Type | Labels |
+/// |--------|------|--------|
+/// | `stillwater_retry_attempts_total` | counter | `policy` |
+/// | `stillwater_retry_failures_total` | counter | `policy` |
+/// | `stillwater_retry_outcomes_total` | counter | `policy`, `outcome` |
+/// | `stillwater_retry_duration_seconds` | histogram | `policy`, `outcome` |
+///
+/// `outcome` is `success`, `exhausted`, or `not_retryable`, as in the
+/// tracing span. Attempts are counted when the loop ends, so a loop still
+/// backing off shows up in `failures` but not yet in `attempts`.
+///
+/// Covers [`retry`](crate::effect::retry::retry),
+/// [`retry_if`](crate::effect::retry::retry_if),
+/// [`retry_classified`](crate::effect::retry::retry_classified),
+/// [`retry_persistent`](crate::effect::retry::retry_persistent),
+/// [`retry_with_hooks`](crate::effect::retry::retry_with_hooks), and the
+/// writer and sink retries.
+#[derive(Clone)]
+pub struct RetryMetrics {
+    attempts: IntCounterVec,
+    failures: IntCounterVec,
+    outcomes: IntCounterVec,
+    duration: HistogramVec,
+}
+
+impl RetryMetrics {
+    /// Create the retry metrics and register them with `registry`.
+    ///
+    /// Fails if `registry` already holds metrics with the same names.
+    pub fn register(registry: &Registry) -> Result<Self> {
+        let metrics = RetryMetrics {
+            attempts: IntCounterVec::new(
+                Opts::new(
+                    "stillwater_retry_attempts_total",
+                    "Attempts made by finished retry loops.",
+                ),
+                &["policy"],
+            )?,
+            failures: IntCounterVec::new(
+                Opts::new(
+                    "stillwater_retry_failures_total",
+                    "Failed attempts, retried or not.",
+                ),
+                &["policy"],
+            )?,
+            outcomes: IntCounterVec::new(
+                Opts::new(
+                    "stillwater_retry_outcomes_total",
+                    "Finished retry loops by outcome.",
+                ),
+                &["policy", "outcome"],
+            )?,
+            duration: HistogramVec::new(
+                HistogramOpts::new(
+                    "stillwater_retry_duration_seconds",
+                    "Time from the first attempt to the outcome.",
+                ),
+                &["policy", "outcome"],
+            )?,
+        };
+        registry.register(Box::new(metrics.attempts.clone()))?;
+        registry.register(Box::new(metrics.failures.clone()))?;
+        registry.register(Box::new(metrics.outcomes.clone()))?;
+        registry.register(Box::new(metrics.duration.clone()))?;
+        Ok(metrics)
+    }
+
+    /// Make these the metrics every retry loop in the process reports to.
+    ///
+    /// Can be done once; returns the metrics back if others are already
+    /// installed.
+    pub fn install(self) -> std::result::Result<(), Self> {
+        RETRY_METRICS.set(self)
+    }
+
+    /// The installed retry metrics, if any.
+    pub fn installed() -> Option<&'static RetryMetrics> {
+        RETRY_METRICS.get()
+    }
+}
+
+impl fmt::Debug for RetryMetrics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RetryMetrics").finish_non_exhaustive()
+    }
+}
+
+/// Count a failed attempt of a retry loop.
+pub(crate) fn retry_failure(policy: &RetryPolicy) {
+    if let Some(metrics) = RETRY_METRICS.get() {
+        metrics.failures.with_label_values(&[policy.name()]).inc();
+    }
+}
+
+/// Record how a retry loop ended.
+pub(crate) fn retry_outcome(
+    policy: &RetryPolicy,
+    attempts: u32,
+    outcome: &'static str,
+    elapsed: Duration,
+) {
+    if let Some(metrics) = RETRY_METRICS.get() {
+        let name = policy.name();
+        metrics
+            .attempts
+            .with_label_values(&[name])
+            .inc_by(u64::from(attempts));
+        metrics.outcomes.with_label_values(&[name, outcome]).inc();
+        metrics
+            .duration
+            .with_label_values(&[name, outcome])
+            .observe(elapsed.as_secs_f64());
+    }
+}
+
+/// Metrics for [`PriorityExecutor`](crate::effect::queue::PriorityExecutor)s.
+///
+/// | Metric | Type | Labels |
+/// |--------|------|--------|
+/// | `stillwater_queue_queued` | gauge | `executor` |
+/// | `stillwater_queue_running` | gauge | `executor` |
+/// | `stillwater_queue_jobs_total` | counter | `executor`, `outcome` |
+/// | `stillwater_queue_wait_seconds` | histogram | `executor` |
+/// | `stillwater_queue_run_seconds` | histogram | `executor` |
+///
+/// `outcome` is `success` or `failure`; jobs that panic or are cancelled
+/// leave the `running` gauge but are not counted. Register once and attach
+/// the same handle to every executor under its own name.
+#[derive(Clone)]
+pub struct QueueMetrics {
+    queued: IntGaugeVec,
+    running: IntGaugeVec,
+    jobs: IntCounterVec,
+    wait: HistogramVec,
+    run: HistogramVec,
+}
+
+impl QueueMetrics {
+    /// Create the executor metrics and register them with `registry`.
+    ///
+    /// Fails if `registry` already holds metrics with the same names.
+    pub fn register(registry: &Registry) -> Result<Self> {
+        let metrics = QueueMetrics {
+            queued: IntGaugeVec::new(
+                Opts::new("stillwater_queue_queued", "Jobs waiting to run."),
+                &["executor"],
+            )?,
+            running: IntGaugeVec::new(
+                Opts::new("stillwater_queue_running", "Jobs currently running."),
+                &["executor"],
+            )?,
+            jobs: IntCounterVec::new(
+                Opts::new("stillwater_queue_jobs_total", "Finished jobs by outcome."),
+                &["executor", "outcome"],
+            )?,
+            wait: HistogramVec::new(
+                HistogramOpts::new(
+                    "stillwater_queue_wait_seconds",
+                    "Time jobs spent queued before starting.",
+                ),
+                &["executor"],
+            )?,
+            run: HistogramVec::new(
+                HistogramOpts::new("stillwater_queue_run_seconds", "Time jobs spent running."),
+                &["executor"],
+            )?,
+        };
+        registry.register(Box::new(metrics.queued.clone()))?;
+        registry.register(Box::new(metrics.running.clone()))?;
+        registry.register(Box::new(metrics.jobs.clone()))?;
+        registry.register(Box::new(metrics.wait.clone()))?;
+        registry.register(Box::new(metrics.run.clone()))?;
+        Ok(metrics)
+    }
+
+    /// The metrics of the executor called `name`.
+    pub(crate) fn executor(&self, name: &str) -> ExecutorMetrics {
+        ExecutorMetrics {
+            queued: self.queued.with_label_values(&[name]),
+            running: self.running.with_label_values(&[name]),
+            succeeded: self.jobs.with_label_values(&[name, "success"]),
+            failed: self.jobs.with_label_values(&[name, "failure"]),
+            wait: self.wait.with_label_values(&[name]),
+            run: self.run.with_label_values(&[name]),
+        }
+    }
+}
+
+impl fmt::Debug for QueueMetrics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("QueueMetrics").finish_non_exhaustive()
+    }
+}
+
+/// One executor's metrics, with the label already applied.
+pub(crate) struct ExecutorMetrics {
+    queued: IntGauge,
+    running: IntGauge,
+    succeeded: IntCounter,
+    failed: IntCounter,
+    wait: Histogram,
+    run: Histogram,
+}
+
+impl ExecutorMetrics {
+    /// Set the gauges from the executor's state.
+    pub(crate) fn set_sizes(&self, queued: usize, running: usize) {
+        self.queued.set(queued as i64);
+        self.running.set(running as i64);
+    }
+
+    /// Record the timings and outcome carried by `event`.
+    pub(crate) fn observe(&self, event: &QueueEvent) {
+        match event {
+            QueueEvent::Submitted { .. } => {}
+            QueueEvent::Started { waited, .. } => self.wait.observe(waited.as_secs_f64()),
+            QueueEvent::Completed {
+                succeeded,
+                run_time,
+                ..
+            } => {
+                self.run.observe(run_time.as_secs_f64());
+                if *succeeded {
+                    self.succeeded.inc();
+                } else {
+                    self.failed.inc();
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::effect::constructors::{fail, from_async, pure};
+    use crate::effect::ext::EffectExt;
+    use crate::effect::queue::PriorityExecutor;
+    use crate::effect::retry::{retry, retry_if};
+    use crate::effect::Effect;
+    use tokio::sync::oneshot;
+
+    /// The process-wide retry metrics; every test uses its own policy name.
+    fn retry_metrics() -> &'static RetryMetrics {
+        static REGISTRY: OnceLock<Registry> = OnceLock::new();
+        let registry = REGISTRY.get_or_init(Registry::new);
+        if let Ok(metrics) = RetryMetrics::register(registry) {
+            let _ = metrics.install();
+        }
+        RetryMetrics::installed().expect("retry metrics installed")
+    }
+
+    #[tokio::test]
+    async fn test_retry_records_failures_and_outcome() {
+        let metrics = retry_metrics();
+        let calls = std::sync::Arc::new(std::sync::atomic::AtomicU32::new(0));
+        let effect = retry(
+            move || {
+                let n = calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                if n < 2 {
+                    fail::<u32, _, ()>("unavailable".to_string()).boxed()
+                } else {
+                    pure(n).boxed()
+                }
+            },
+            RetryPolicy::constant(Duration::from_millis(1))
+                .with_max_retries(5)
+                .with_name("metrics-success"),
+        );
+
+        effect.execute(&()).await.unwrap();
+
+        let name = "metrics-success";
+        assert_eq!(metrics.attempts.with_label_values(&[name]).get(), 3);
+        assert_eq!(metrics.failures.with_label_values(&[name]).get(), 2);
+        assert_eq!(
+            metrics.outcomes.with_label_values(&[name, "success"]).get(),
+            1
+        );
+        assert_eq!(
+            metrics
+                .duration
+                .with_label_values(&[name, "success"])
+                .get_sample_count(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_retry_records_exhausted_and_not_retryable() {
+        let metrics = retry_metrics();
+        let exhausted = retry(
+            || fail::<(), _, ()>("down".to_string()),
+            RetryPolicy::constant(Duration::from_millis(1))
+                .with_max_retries(1)
+                .with_name("metrics-exhausted"),
+        );
+        let rejected = retry_if(
+            || fail::<(), _, ()>("bad request".to_string()),
+            RetryPolicy::constant(Duration::from_millis(1))
+                .with_max_retries(3)
+                .with_name("metrics-rejected"),
+            |_: &String| false,
+        );
+
+        assert!(exhausted.execute(&()).await.is_err());
+        assert!(rejected.execute(&()).await.is_err());
+
+        let outcomes = &metrics.outcomes;
+        assert_eq!(
+            outcomes
+                .with_label_values(&["metrics-exhausted", "exhausted"])
+                .get(),
+            1
+        );
+        assert_eq!(
+            metrics
+                .failures
+                .with_label_values(&["metrics-exhausted"])
+                .get(),
+            2
+        );
+        assert_eq!(
+            outcomes
+                .with_label_values(&["metrics-rejected", "not_retryable"])
+                .get(),
+            1
+        );
+        assert_eq!(
+            metrics
+                .attempts
+                .with_label_values(&["metrics-rejected"])
+                .get(),
+            1
+        );
+    }
+
+    /// An effect that runs until `release` fires.
+    fn gate() -> (
+        oneshot::Sender<()>,
+        impl Effect<Output = (), Error = String, Env = ()>,
+    ) {
+        let (release, wait) = oneshot::channel::<()>();
+        let effect = from_async(move |_: &()| async move {
+            let _ = wait.await;
+            Ok(())
+        });
+        (release, effect)
+    }
+
+    #[tokio::test]
+    async fn test_executor_reports_sizes_and_outcomes() {
+        let registry = Registry::new();
+        let metrics = QueueMetrics::register(&registry).unwrap();
+        let executor = PriorityExecutor::new((), 1).with_metrics(&metrics, "batch");
+
+        let (release_first, first) = gate();
+        let (release_last, last) = gate();
+        let first = executor.submit(0, first);
+        let failing = executor.submit(0, fail::<(), _, ()>("boom".to_string()));
+        let last = executor.submit(0, last);
+
+        let batch = metrics.executor("batch");
+        assert_eq!(batch.queued.get(), 2);
+        assert_eq!(batch.running.get(), 1);
+
+        // Each job completes before its slot is handed to the next, so the
+        // first two are counted while the last is still running.
+        release_first.send(()).unwrap();
+        first.await.unwrap();
+        assert!(failing.await.is_err());
+        assert_eq!(batch.succeeded.get(), 1);
+        assert_eq!(batch.failed.get(), 1);
+        assert_eq!(batch.queued.get(), 0);
+        assert_eq!(batch.wait.get_sample_count(), 3);
+
+        release_last.send(()).unwrap();
+        last.await.unwrap();
+    }
+
+    #[test]
+    fn test_registering_twice_fails() {
+        let registry = Registry::new();
+        QueueMetrics::register(&registry).unwrap();
+        assert!(QueueMetrics::register(&registry).is_err());
+    }
+}
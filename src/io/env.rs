@@ -0,0 +1,273 @@
+//! Reading configuration from environment variables.
+//!
+//! [`var_refined`] reads one variable, parses it, and checks it against a
+//! refined predicate. Failures are collected as [`EnvVarError`]s in a
+//! [`Validation`], so combining several variables with
+//! [`Validation::and`] reports every missing or invalid one at once,
+//! instead of stopping at the first `unwrap`. [`vars_validated`] runs such
+//! a combination against the process environment, and [`EnvVars`] runs it
+//! against a fixed set of variables for tests.
+//!
+//! # Example
+//!
+//! ```rust
+//! use stillwater::io::env::{EnvVarError, EnvVars};
+//! use stillwater::refined::{InRange, NonEmpty, NonZero, Refined};
+//! use stillwater::Validation;
+//!
+//! #[derive(Debug)]
+//! struct Config {
+//!     host: Refined<String, NonEmpty>,
+//!     port: Refined<u16, NonZero>,
+//!     workers: Refined<u8, InRange<1, 64>>,
+//! }
+//!
+//! fn config(vars: &EnvVars) -> Validation<Config, Vec<EnvVarError>> {
+//!     vars.var_refined("HOST")
+//!         .and(vars.var_refined("PORT"))
+//!         .and(vars.var_refined("WORKERS"))
+//!         .map(|((host, port), workers)| Config { host, port, workers })
+//! }
+//!
+//! // In the application: `vars_validated(config)` reads the real environment.
+//! let vars = EnvVars::from_iter([("PORT", "http"), ("WORKERS", "128")]);
+//! let errors = vars.validated(config).unwrap_err();
+//!
+//! let names: Vec<_> = errors.iter().map(EnvVarError::name).collect();
+//! assert_eq!(names, vec!["HOST", "PORT", "WORKERS"]);
+//! ```
+
+use std::collections::BTreeMap;
+use std::ffi::OsString;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::refined::{Predicate, Refined};
+use crate::Validation;
+
+/// A missing or invalid environment variable.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EnvVarError {
+    /// The variable is not set.
+    Missing {
+        /// The variable's name
+        name: String,
+    },
+    /// The variable's value is not valid Unicode.
+    NotUnicode {
+        /// The variable's name
+        name: String,
+    },
+    /// The value could not be parsed or failed its predicate.
+    ///
+    /// The value itself is not kept, since variables often hold secrets.
+    Invalid {
+        /// The variable's name
+        name: String,
+        /// Why the value was rejected
+        reason: String,
+    },
+}
+
+impl EnvVarError {
+    /// The name of the variable.
+    pub fn name(&self) -> &str {
+        match self {
+            EnvVarError::Missing { name }
+            | EnvVarError::NotUnicode { name }
+            | EnvVarError::Invalid { name, .. } => name,
+        }
+    }
+}
+
+impl fmt::Display for EnvVarError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EnvVarError::Missing { name } => write!(f, "{} is not set", name),
+            EnvVarError::NotUnicode { name } => write!(f, "{} is not valid unicode", name),
+            EnvVarError::Invalid { name, reason } => write!(f, "{} is invalid: {}", name, reason),
+        }
+    }
+}
+
+impl std::error::Error for EnvVarError {}
+
+/// A set of environment variables to read configuration from.
+///
+/// [`EnvVars::process`] reads the real environment each time a variable is
+/// looked up; [`EnvVars::from_iter`] holds a fixed set, for tests.
+#[derive(Debug, Clone)]
+pub struct EnvVars {
+    fixed: Option<BTreeMap<OsString, OsString>>,
+}
+
+impl EnvVars {
+    /// The environment of the current process.
+    pub fn process() -> Self {
+        EnvVars { fixed: None }
+    }
+
+    fn get(&self, name: &str) -> Option<OsString> {
+        match &self.fixed {
+            Some(vars) => vars.get(std::ffi::OsStr::new(name)).cloned(),
+            None => std::env::var_os(name),
+        }
+    }
+
+    /// Read `name`, returning `None` if it is not set.
+    fn read(&self, name: &str) -> Result<Option<String>, EnvVarError> {
+        match self.get(name) {
+            None => Ok(None),
+            Some(value) => value
+                .into_string()
+                .map(Some)
+                .map_err(|_| EnvVarError::NotUnicode { name: name.into() }),
+        }
+    }
+
+    /// Read `name`, parse it as `T`, and check it against `P`.
+    pub fn var_refined<T, P>(&self, name: &str) -> Validation<Refined<T, P>, Vec<EnvVarError>>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+        P: Predicate<T>,
+        P::Error: fmt::Display,
+    {
+        match self.var_refined_opt(name) {
+            Validation::Success(Some(value)) => Validation::Success(value),
+            Validation::Success(None) => {
+                Validation::Failure(vec![EnvVarError::Missing { name: name.into() }])
+            }
+            Validation::Failure(errors) => Validation::Failure(errors),
+        }
+    }
+
+    /// Like [`var_refined`](Self::var_refined), but an unset variable is
+    /// `None` rather than an error.
+    pub fn var_refined_opt<T, P>(
+        &self,
+        name: &str,
+    ) -> Validation<Option<Refined<T, P>>, Vec<EnvVarError>>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+        P: Predicate<T>,
+        P::Error: fmt::Display,
+    {
+        let parsed = self.read(name).and_then(|value| {
+            value
+                .map(|value| value.parse::<Refined<T, P>>())
+                .transpose()
+                .map_err(|e| EnvVarError::Invalid {
+                    name: name.into(),
+                    reason: e.to_string(),
+                })
+        });
+        Validation::from_result(parsed.map_err(|e| vec![e]))
+    }
+
+    /// Build a value from these variables with `spec`.
+    pub fn validated<T, F>(&self, spec: F) -> Validation<T, Vec<EnvVarError>>
+    where
+        F: FnOnce(&Self) -> Validation<T, Vec<EnvVarError>>,
+    {
+        spec(self)
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for EnvVars
+where
+    K: Into<OsString>,
+    V: Into<OsString>,
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(vars: I) -> Self {
+        EnvVars {
+            fixed: Some(
+                vars.into_iter()
+                    .map(|(k, v)| (k.into(), v.into()))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+/// Read the process environment variable `name`, parse it as `T`, and check
+/// it against `P`.
+///
+/// # Example
+///
+/// ```rust
+/// use stillwater::io::env::var_refined;
+/// use stillwater::refined::{NonZero, Refined};
+///
+/// let port = var_refined::<u16, NonZero>("STILLWATER_DOC_UNSET_PORT");
+/// assert_eq!(
+///     port.unwrap_err()[0].to_string(),
+///     "STILLWATER_DOC_UNSET_PORT is not set"
+/// );
+/// ```
+pub fn var_refined<T, P>(name: &str) -> Validation<Refined<T, P>, Vec<EnvVarError>>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+    P: Predicate<T>,
+    P::Error: fmt::Display,
+{
+    EnvVars::process().var_refined(name)
+}
+
+/// Build a value from the process environment with `spec`, collecting every
+/// missing or invalid variable.
+pub fn vars_validated<T, F>(spec: F) -> Validation<T, Vec<EnvVarError>>
+where
+    F: FnOnce(&EnvVars) -> Validation<T, Vec<EnvVarError>>,
+{
+    EnvVars::process().validated(spec)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::refined::{NonZero, Positive};
+
+    #[test]
+    fn test_var_refined_reports_parse_and_predicate_errors() {
+        let vars = EnvVars::from_iter([("PORT", "0"), ("RETRIES", "three")]);
+
+        let port = vars.var_refined::<u16, NonZero>("PORT").unwrap_err();
+        assert_eq!(
+            port[0].to_string(),
+            "PORT is invalid: value must be non-zero"
+        );
+
+        let retries = vars.var_refined::<i32, Positive>("RETRIES").unwrap_err();
+        assert!(matches!(&retries[0], EnvVarError::Invalid { name, .. } if name == "RETRIES"));
+    }
+
+    #[test]
+    fn test_var_refined_opt_allows_unset() {
+        let vars = EnvVars::from_iter([("PORT", "8080")]);
+
+        let port = vars
+            .var_refined_opt::<u16, NonZero>("PORT")
+            .into_result()
+            .unwrap();
+        let timeout = vars
+            .var_refined_opt::<i64, Positive>("TIMEOUT")
+            .into_result()
+            .unwrap();
+
+        assert_eq!(port.map(|p| *p.get()), Some(8080));
+        assert!(timeout.is_none());
+    }
+
+    #[test]
+    fn test_vars_validated_reads_process_env() {
+        std::env::set_var("STILLWATER_TEST_ENV_WORKERS", "4");
+
+        let workers =
+            vars_validated(|vars| vars.var_refined::<u8, NonZero>("STILLWATER_TEST_ENV_WORKERS"));
+
+        assert_eq!(*workers.into_result().unwrap().get(), 4);
+    }
+}
@@ -0,0 +1,426 @@
+//! Effects for common file system operations.
+//!
+//! Building blocks for the imperative shell: each function returns an effect
+//! that does nothing until it is run, fails with [`std::io::Error`], and
+//! works with any environment.
+//!
+//! - [`read_to_string`] - Read a whole file as UTF-8
+//! - [`write_atomic`] - Replace a file's contents via a temporary file and rename
+//! - [`with_tempdir`] - Use a fresh temporary directory that is always removed
+//! - [`walk_dir`] - Emit every file under a directory as a [`SinkEffect`]
+//!
+//! The operations use blocking `std::fs` calls on the task that runs them.
+//!
+//! # Example
+//!
+//! ```rust
+//! use stillwater::effect::prelude::*;
+//! use stillwater::io::fs::{read_to_string, with_tempdir, write_atomic};
+//!
+//! # tokio_test::block_on(async {
+//! let effect = with_tempdir(|dir| {
+//!     let path = dir.path().join("config.toml");
+//!     write_atomic(path.clone(), "port = 8080")
+//!         .and_then(move |_| read_to_string(path))
+//! });
+//!
+//! assert_eq!(effect.run(&()).await.unwrap(), "port = 8080");
+//! # });
+//! ```
+
+use std::fmt;
+use std::fs;
+use std::future::Future;
+use std::io::{self, Write};
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::effect::bracket::bracket;
+use crate::effect::constructors::from_fn;
+use crate::effect::sink::SinkEffect;
+use crate::effect::Effect;
+
+static UNIQUE: AtomicUsize = AtomicUsize::new(0);
+
+/// A name no other call in this process has used.
+fn unique_name(prefix: &str) -> String {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.subsec_nanos());
+    format!(
+        "{}-{}-{}-{}",
+        prefix,
+        std::process::id(),
+        UNIQUE.fetch_add(1, Ordering::Relaxed),
+        nanos
+    )
+}
+
+// ============================================================================
+// read_to_string
+// ============================================================================
+
+/// Effect that reads a file as a string.
+///
+/// Created by [`read_to_string`].
+pub struct ReadToString<Env> {
+    path: PathBuf,
+    _phantom: PhantomData<fn() -> Env>,
+}
+
+impl<Env> fmt::Debug for ReadToString<Env> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReadToString")
+            .field("path", &self.path)
+            .finish()
+    }
+}
+
+impl<Env: Send + Sync> Effect for ReadToString<Env> {
+    type Output = String;
+    type Error = io::Error;
+    type Env = Env;
+
+    async fn run(self, _env: &Self::Env) -> Result<String, io::Error> {
+        fs::read_to_string(&self.path)
+    }
+}
+
+/// Read the whole file at `path` as UTF-8.
+///
+/// Fails if the file cannot be read or is not valid UTF-8.
+pub fn read_to_string<Env>(path: impl Into<PathBuf>) -> ReadToString<Env>
+where
+    Env: Send + Sync,
+{
+    ReadToString {
+        path: path.into(),
+        _phantom: PhantomData,
+    }
+}
+
+// ============================================================================
+// write_atomic
+// ============================================================================
+
+/// Effect that replaces a file's contents atomically.
+///
+/// Created by [`write_atomic`].
+pub struct WriteAtomic<Env> {
+    path: PathBuf,
+    contents: Vec<u8>,
+    _phantom: PhantomData<fn() -> Env>,
+}
+
+impl<Env> fmt::Debug for WriteAtomic<Env> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WriteAtomic")
+            .field("path", &self.path)
+            .field("len", &self.contents.len())
+            .finish()
+    }
+}
+
+impl<Env: Send + Sync> Effect for WriteAtomic<Env> {
+    type Output = ();
+    type Error = io::Error;
+    type Env = Env;
+
+    async fn run(self, _env: &Self::Env) -> Result<(), io::Error> {
+        replace_file(&self.path, &self.contents)
+    }
+}
+
+/// Write `contents` to a uniquely named temporary file next to `path`, sync
+/// it, rename it over `path`, and sync the parent directory so the rename
+/// itself survives a crash.
+pub(crate) fn replace_file(path: &Path, contents: &[u8]) -> io::Result<()> {
+    let file_name = path.file_name().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("{} has no file name", path.display()),
+        )
+    })?;
+    let temp = path.with_file_name(unique_name(&format!(
+        ".{}.tmp",
+        file_name.to_string_lossy()
+    )));
+
+    let written = fs::File::create(&temp).and_then(|mut file| {
+        file.write_all(contents)?;
+        file.sync_all()
+    });
+    if let Err(e) = written.and_then(|()| fs::rename(&temp, path)) {
+        let _ = fs::remove_file(&temp);
+        return Err(e);
+    }
+    sync_parent(path)
+}
+
+#[cfg(unix)]
+fn sync_parent(path: &Path) -> io::Result<()> {
+    let parent = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    fs::File::open(parent)?.sync_all()
+}
+
+// Directories can't be opened as files on other platforms; the rename is
+// as durable as the platform makes it.
+#[cfg(not(unix))]
+fn sync_parent(_path: &Path) -> io::Result<()> {
+    Ok(())
+}
+
+/// Replace the contents of the file at `path` with `contents`.
+///
+/// The contents are written and synced to a temporary file in the same
+/// directory, which is then renamed over `path`; on Unix the directory is
+/// synced afterwards so the rename survives a crash. Readers see either the
+/// old contents or the new ones, never a partial write, and a failed write
+/// leaves the original file untouched.
+pub fn write_atomic<Env>(path: impl Into<PathBuf>, contents: impl Into<Vec<u8>>) -> WriteAtomic<Env>
+where
+    Env: Send + Sync,
+{
+    WriteAtomic {
+        path: path.into(),
+        contents: contents.into(),
+        _phantom: PhantomData,
+    }
+}
+
+// ============================================================================
+// with_tempdir
+// ============================================================================
+
+/// A temporary directory created by [`with_tempdir`].
+#[derive(Debug)]
+pub struct TempDir {
+    path: PathBuf,
+}
+
+impl TempDir {
+    fn create() -> io::Result<Self> {
+        let path = std::env::temp_dir().join(unique_name("stillwater"));
+        fs::create_dir(&path)?;
+        Ok(TempDir { path })
+    }
+
+    /// The directory's path.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+/// Effect that runs an effect with a temporary directory.
+///
+/// Created by [`with_tempdir`].
+pub struct WithTempdir<Use, E, Env> {
+    use_fn: Use,
+    _phantom: PhantomData<fn() -> (E, Env)>,
+}
+
+impl<Use, E, Env> fmt::Debug for WithTempdir<Use, E, Env> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WithTempdir")
+            .field("use_fn", &"<function>")
+            .finish()
+    }
+}
+
+impl<Use, UseEffect, T, E, Env> Effect for WithTempdir<Use, E, Env>
+where
+    Use: FnOnce(&TempDir) -> UseEffect + Send,
+    UseEffect: Effect<Output = T, Error = E, Env = Env>,
+    T: Send,
+    E: From<io::Error> + Send + fmt::Debug + 'static,
+    Env: Send + Sync,
+{
+    type Output = T;
+    type Error = E;
+    type Env = Env;
+
+    async fn run(self, env: &Self::Env) -> Result<T, E> {
+        bracket(
+            from_fn(|_: &Env| TempDir::create().map_err(E::from)),
+            |dir: TempDir| async move { fs::remove_dir_all(dir.path).map_err(E::from) },
+            self.use_fn,
+        )
+        .run(env)
+        .await
+    }
+}
+
+/// Run the effect built by `use_fn` with a new, empty temporary directory.
+///
+/// The directory is created under [`std::env::temp_dir`] and removed with
+/// everything in it once the effect finishes, whether it succeeded or
+/// failed, or is cancelled. Cleanup failures are logged, as with
+/// [`bracket`].
+pub fn with_tempdir<Use, UseEffect, T, E, Env>(use_fn: Use) -> WithTempdir<Use, E, Env>
+where
+    Use: FnOnce(&TempDir) -> UseEffect + Send,
+    UseEffect: Effect<Output = T, Error = E, Env = Env>,
+    T: Send,
+    E: From<io::Error> + Send + fmt::Debug + 'static,
+    Env: Send + Sync,
+{
+    WithTempdir {
+        use_fn,
+        _phantom: PhantomData,
+    }
+}
+
+// ============================================================================
+// walk_dir
+// ============================================================================
+
+/// Sink effect that emits the files under a directory.
+///
+/// Created by [`walk_dir`].
+pub struct WalkDir<Env> {
+    root: PathBuf,
+    _phantom: PhantomData<fn() -> Env>,
+}
+
+impl<Env> fmt::Debug for WalkDir<Env> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WalkDir").field("root", &self.root).finish()
+    }
+}
+
+impl<Env: Send + Sync> Effect for WalkDir<Env> {
+    type Output = ();
+    type Error = io::Error;
+    type Env = Env;
+
+    async fn run(self, env: &Self::Env) -> Result<(), io::Error> {
+        // Still walk the tree, so unreadable directories are reported.
+        self.run_with_sink(env, |_| async {}).await
+    }
+}
+
+impl<Env: Send + Sync> SinkEffect for WalkDir<Env> {
+    type Item = PathBuf;
+
+    async fn run_with_sink<S, Fut>(self, _env: &Self::Env, sink: S) -> Result<(), io::Error>
+    where
+        S: Fn(Self::Item) -> Fut + Send + Sync,
+        Fut: Future<Output = ()> + Send,
+    {
+        let mut pending = vec![self.root];
+        while let Some(dir) = pending.pop() {
+            let mut entries = fs::read_dir(&dir)?
+                .map(|entry| entry.and_then(|e| Ok((e.path(), e.file_type()?.is_dir()))))
+                .collect::<io::Result<Vec<_>>>()?;
+            entries.sort();
+
+            let mut subdirs = Vec::new();
+            for (path, is_dir) in entries {
+                if is_dir {
+                    subdirs.push(path);
+                } else {
+                    sink(path).await;
+                }
+            }
+            pending.extend(subdirs.into_iter().rev());
+        }
+        Ok(())
+    }
+}
+
+/// Emit the path of every file under `root`, recursively.
+///
+/// Each directory's files are emitted in name order before its
+/// subdirectories are visited, also in name order. Symbolic links are
+/// emitted as files and not followed. The walk stops at the first directory
+/// that cannot be read.
+///
+/// # Example
+///
+/// ```rust
+/// use std::path::Path;
+/// use stillwater::effect::sink::prelude::*;
+/// use stillwater::io::fs::walk_dir;
+///
+/// # tokio_test::block_on(async {
+/// let (result, files) = walk_dir::<()>("src").run_collecting(&()).await;
+///
+/// assert!(result.is_ok());
+/// assert!(files.iter().any(|path| path == Path::new("src/lib.rs")));
+/// # });
+/// ```
+pub fn walk_dir<Env>(root: impl Into<PathBuf>) -> WalkDir<Env>
+where
+    Env: Send + Sync,
+{
+    WalkDir {
+        root: root.into(),
+        _phantom: PhantomData,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::effect::sink::SinkEffectExt;
+    use crate::effect::EffectExt;
+
+    #[tokio::test]
+    async fn test_write_atomic_replaces_contents() {
+        let result = with_tempdir(|dir| {
+            let path = dir.path().join("data.txt");
+            let reread = path.clone();
+            write_atomic(path.clone(), "old")
+                .and_then(move |_| write_atomic(path, "new"))
+                .and_then(move |_| read_to_string(reread))
+        })
+        .run(&())
+        .await;
+
+        assert_eq!(result.unwrap(), "new");
+    }
+
+    #[tokio::test]
+    async fn test_with_tempdir_removes_dir_on_failure() {
+        let kept = std::sync::Mutex::new(None);
+        let result = with_tempdir::<_, _, (), _, ()>(|dir| {
+            *kept.lock().unwrap() = Some(dir.path().to_path_buf());
+            read_to_string(dir.path().join("missing")).map(|_| ())
+        })
+        .run(&())
+        .await;
+
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::NotFound);
+        assert!(!kept.into_inner().unwrap().unwrap().exists());
+    }
+
+    #[tokio::test]
+    async fn test_walk_dir_emits_files_in_order() {
+        let root = std::env::temp_dir().join(unique_name("stillwater-walk"));
+        fs::create_dir_all(root.join("b/c")).unwrap();
+        fs::create_dir_all(root.join("a")).unwrap();
+        for file in ["z.txt", "a/1.txt", "b/2.txt", "b/c/3.txt"] {
+            fs::write(root.join(file), file).unwrap();
+        }
+
+        let (result, paths) = walk_dir::<()>(root.clone()).run_collecting(&()).await;
+        fs::remove_dir_all(&root).unwrap();
+
+        result.unwrap();
+        let relative: Vec<_> = paths
+            .iter()
+            .map(|p| {
+                p.strip_prefix(&root)
+                    .unwrap()
+                    .to_string_lossy()
+                    .replace('\\', "/")
+            })
+            .collect();
+        assert_eq!(relative, vec!["z.txt", "a/1.txt", "b/2.txt", "b/c/3.txt"]);
+    }
+}
@@ -11,6 +11,15 @@
 //! - `IO::read_async()` - Create effects from read-only async operations
 //! - `IO::write_async()` - Create effects from mutating async operations
 //!
+//! The [`env`] module reads configuration from environment variables into
+//! refined types, collecting every problem in a `Validation`.
+//!
+//! For the file system, the [`fs`] module provides ready-made effects such
+//! as [`fs::read_to_string`] and [`fs::write_atomic`], and with the `async`
+//! feature the `process` module runs subprocesses with captured output and
+//! the `net` module opens TCP and Unix socket connections. The `signals`
+//! module waits for SIGINT and SIGTERM for graceful shutdown.
+//!
 //! # Environment Pattern
 //!
 //! The IO module uses `AsRef<T>` for automatic environment extraction.
@@ -105,6 +114,15 @@
 //! # });
 //! ```
 
+pub mod env;
+pub mod fs;
+#[cfg(feature = "async")]
+pub mod net;
+#[cfg(feature = "async")]
+pub mod process;
+#[cfg(all(feature = "async", any(unix, windows)))]
+pub mod signals;
+
 use std::convert::Infallible;
 use std::future::Future;
 
@@ -175,7 +193,7 @@ impl IO {
         F: FnOnce(&T) -> R + Send + 'static,
         R: Send + 'static,
         T: Send + Sync + 'static,
-        Env: AsRef<T> + Clone + Send + Sync + 'static,
+        Env: AsRef<T> + Send + Sync + 'static,
     {
         from_fn(move |env: &Env| Ok(f(env.as_ref()))).boxed()
     }
@@ -240,7 +258,7 @@ impl IO {
         F: FnOnce(&T) -> R + Send + 'static,
         R: Send + 'static,
         T: Send + Sync + 'static,
-        Env: AsRef<T> + Clone + Send + Sync + 'static,
+        Env: AsRef<T> + Send + Sync + 'static,
     {
         from_fn(move |env: &Env| Ok(f(env.as_ref()))).boxed()
     }
@@ -302,7 +320,7 @@ impl IO {
         Fut: Future<Output = R> + Send + 'static,
         R: Send + 'static,
         T: Send + Sync + 'static,
-        Env: AsRef<T> + Clone + Send + Sync + 'static,
+        Env: AsRef<T> + Send + Sync + 'static,
     {
         from_async(move |env: &Env| {
             let fut = f(env.as_ref());
@@ -369,7 +387,7 @@ impl IO {
         Fut: Future<Output = R> + Send + 'static,
         R: Send + 'static,
         T: Send + Sync + 'static,
-        Env: AsRef<T> + Clone + Send + Sync + 'static,
+        Env: AsRef<T> + Send + Sync + 'static,
     {
         from_async(move |env: &Env| {
             let fut = f(env.as_ref());
@@ -0,0 +1,692 @@
+//! TCP and Unix socket connections as tracked resources.
+//!
+//! [`connect_tcp`] and [`connect_unix`] return effects that open a
+//! [`Socket`] and are marked with `Acquires = Has<SocketRes>`, so the
+//! resource tracking in [`effect::resource`](crate::effect::resource) can
+//! check that every connection is closed. [`with_connection`] brackets a
+//! connection: it opens the socket, runs an effect with it, and shuts the
+//! socket down whether the effect succeeded or failed.
+//!
+//! Reads and writes are effects too. A connection can have an idle timeout,
+//! which fails any connect, read, or write that makes no progress for that
+//! long with [`io::ErrorKind::TimedOut`].
+//!
+//! Requires the `async` feature.
+//!
+//! # Example
+//!
+//! ```rust
+//! use std::time::Duration;
+//! use stillwater::effect::prelude::*;
+//! use stillwater::io::net::{connect_tcp, with_connection};
+//! use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+//!
+//! # tokio_test::block_on(async {
+//! // A line-based echo server to talk to
+//! let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+//! let addr = listener.local_addr().unwrap().to_string();
+//! tokio::spawn(async move {
+//!     let (stream, _) = listener.accept().await.unwrap();
+//!     let (read, mut write) = stream.into_split();
+//!     let mut lines = BufReader::new(read).lines();
+//!     while let Some(line) = lines.next_line().await.unwrap() {
+//!         write.write_all(format!("echo: {}\n", line).as_bytes()).await.unwrap();
+//!     }
+//! });
+//!
+//! let effect = with_connection(
+//!     connect_tcp(addr).idle_timeout(Duration::from_secs(5)),
+//!     |socket| {
+//!         let socket = socket.clone();
+//!         socket
+//!             .write_all("ping\n")
+//!             .and_then(move |_| socket.read_line())
+//!     },
+//! );
+//!
+//! assert_eq!(effect.run(&()).await.unwrap(), "echo: ping\n");
+//! # });
+//! ```
+
+use std::fmt;
+use std::future::Future;
+use std::io;
+use std::marker::PhantomData;
+#[cfg(unix)]
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+#[cfg(unix)]
+use tokio::net::UnixStream;
+use tokio::sync::Mutex;
+
+use crate::effect::resource::{resource_bracket, Empty, Has, ResourceEffect, SocketRes};
+use crate::effect::{Effect, EffectExt};
+
+/// Run `operation`, failing if it takes longer than `limit`.
+async fn within<T>(
+    limit: Option<Duration>,
+    operation: impl Future<Output = io::Result<T>>,
+) -> io::Result<T> {
+    match limit {
+        Some(limit) => tokio::time::timeout(limit, operation)
+            .await
+            .unwrap_or_else(|_| {
+                Err(io::Error::new(
+                    io::ErrorKind::TimedOut,
+                    format!("no progress for {:?}", limit),
+                ))
+            }),
+        None => operation.await,
+    }
+}
+
+// ============================================================================
+// Socket
+// ============================================================================
+
+enum Stream {
+    Tcp(TcpStream),
+    #[cfg(unix)]
+    Unix(UnixStream),
+}
+
+impl AsyncRead for Stream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut tokio::io::ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        match self.get_mut() {
+            Stream::Tcp(stream) => Pin::new(stream).poll_read(cx, buf),
+            #[cfg(unix)]
+            Stream::Unix(stream) => Pin::new(stream).poll_read(cx, buf),
+        }
+    }
+}
+
+impl AsyncWrite for Stream {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        match self.get_mut() {
+            Stream::Tcp(stream) => Pin::new(stream).poll_write(cx, buf),
+            #[cfg(unix)]
+            Stream::Unix(stream) => Pin::new(stream).poll_write(cx, buf),
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match self.get_mut() {
+            Stream::Tcp(stream) => Pin::new(stream).poll_flush(cx),
+            #[cfg(unix)]
+            Stream::Unix(stream) => Pin::new(stream).poll_flush(cx),
+        }
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match self.get_mut() {
+            Stream::Tcp(stream) => Pin::new(stream).poll_shutdown(cx),
+            #[cfg(unix)]
+            Stream::Unix(stream) => Pin::new(stream).poll_shutdown(cx),
+        }
+    }
+}
+
+struct SocketInner {
+    peer: String,
+    idle_timeout: Option<Duration>,
+    stream: Mutex<BufReader<Stream>>,
+}
+
+/// An open TCP or Unix socket connection.
+///
+/// Cloning a `Socket` gives another handle to the same connection, so the
+/// effects built inside [`with_connection`] can own one. Reads and writes
+/// on the same connection run one at a time.
+#[derive(Clone)]
+pub struct Socket {
+    inner: Arc<SocketInner>,
+}
+
+impl Socket {
+    fn new(peer: String, idle_timeout: Option<Duration>, stream: Stream) -> Self {
+        Socket {
+            inner: Arc::new(SocketInner {
+                peer,
+                idle_timeout,
+                stream: Mutex::new(BufReader::new(stream)),
+            }),
+        }
+    }
+
+    /// The address or path this socket is connected to.
+    pub fn peer(&self) -> &str {
+        &self.inner.peer
+    }
+
+    /// The idle timeout applied to each read and write, if any.
+    pub fn idle_timeout(&self) -> Option<Duration> {
+        self.inner.idle_timeout
+    }
+
+    /// Effect that writes all of `bytes` to the socket.
+    pub fn write_all<Env>(&self, bytes: impl Into<Vec<u8>>) -> SocketWrite<Env> {
+        SocketWrite {
+            socket: self.clone(),
+            bytes: bytes.into(),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Effect that reads whatever is available, up to `max` bytes.
+    ///
+    /// Succeeds with an empty buffer once the peer has closed the
+    /// connection.
+    pub fn read<Env>(&self, max: usize) -> SocketRead<Env> {
+        self.reading(ReadMode::Upto(max))
+    }
+
+    /// Effect that reads one line, including its trailing newline.
+    ///
+    /// Succeeds with an empty string once the peer has closed the
+    /// connection. Fails if the line is not valid UTF-8.
+    pub fn read_line<Env>(&self) -> SocketReadLine<Env> {
+        SocketReadLine {
+            socket: self.clone(),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Effect that reads until the peer closes the connection.
+    ///
+    /// The idle timeout applies to each chunk, not to the whole read.
+    pub fn read_to_end<Env>(&self) -> SocketRead<Env> {
+        self.reading(ReadMode::ToEnd)
+    }
+
+    /// Effect that shuts the connection down, releasing the socket.
+    pub fn close<Env>(self) -> SocketClose<Env> {
+        SocketClose {
+            socket: self,
+            _phantom: PhantomData,
+        }
+    }
+
+    fn reading<Env>(&self, mode: ReadMode) -> SocketRead<Env> {
+        SocketRead {
+            socket: self.clone(),
+            mode,
+            _phantom: PhantomData,
+        }
+    }
+
+    async fn shutdown(&self) -> io::Result<()> {
+        let mut stream = self.inner.stream.lock().await;
+        match stream.get_mut().shutdown().await {
+            // The peer already closed the connection.
+            Err(e) if e.kind() == io::ErrorKind::NotConnected => Ok(()),
+            result => result,
+        }
+    }
+}
+
+impl fmt::Debug for Socket {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Socket")
+            .field("peer", &self.inner.peer)
+            .field("idle_timeout", &self.inner.idle_timeout)
+            .finish()
+    }
+}
+
+// ============================================================================
+// Connecting
+// ============================================================================
+
+#[derive(Debug)]
+enum Target {
+    Tcp(String),
+    #[cfg(unix)]
+    Unix(PathBuf),
+}
+
+/// Effect that opens a socket connection.
+///
+/// Created by [`connect_tcp`] or [`connect_unix`].
+pub struct Connect<Env> {
+    target: Target,
+    idle_timeout: Option<Duration>,
+    _phantom: PhantomData<fn() -> Env>,
+}
+
+impl<Env> Connect<Env> {
+    /// Fail connecting, and every read or write on the connection, that
+    /// makes no progress for `limit`.
+    pub fn idle_timeout(mut self, limit: Duration) -> Self {
+        self.idle_timeout = Some(limit);
+        self
+    }
+}
+
+impl<Env> fmt::Debug for Connect<Env> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Connect")
+            .field("target", &self.target)
+            .field("idle_timeout", &self.idle_timeout)
+            .finish()
+    }
+}
+
+impl<Env: Send + Sync> Effect for Connect<Env> {
+    type Output = Socket;
+    type Error = io::Error;
+    type Env = Env;
+
+    async fn run(self, _env: &Self::Env) -> io::Result<Socket> {
+        let limit = self.idle_timeout;
+        match self.target {
+            Target::Tcp(addr) => {
+                let stream = within(limit, TcpStream::connect(addr.as_str())).await?;
+                Ok(Socket::new(addr, limit, Stream::Tcp(stream)))
+            }
+            #[cfg(unix)]
+            Target::Unix(path) => {
+                let stream = within(limit, UnixStream::connect(&path)).await?;
+                let peer = path.to_string_lossy().into_owned();
+                Ok(Socket::new(peer, limit, Stream::Unix(stream)))
+            }
+        }
+    }
+}
+
+impl<Env: Send + Sync> ResourceEffect for Connect<Env> {
+    type Acquires = Has<SocketRes>;
+    type Releases = Empty;
+}
+
+/// Open a TCP connection to `addr`, such as `"localhost:6379"`.
+pub fn connect_tcp<Env>(addr: impl Into<String>) -> Connect<Env>
+where
+    Env: Send + Sync,
+{
+    Connect {
+        target: Target::Tcp(addr.into()),
+        idle_timeout: None,
+        _phantom: PhantomData,
+    }
+}
+
+/// Open a connection to the Unix domain socket at `path`.
+#[cfg(unix)]
+pub fn connect_unix<Env>(path: impl Into<PathBuf>) -> Connect<Env>
+where
+    Env: Send + Sync,
+{
+    Connect {
+        target: Target::Unix(path.into()),
+        idle_timeout: None,
+        _phantom: PhantomData,
+    }
+}
+
+// ============================================================================
+// Reading and writing
+// ============================================================================
+
+/// Effect that writes to a socket.
+///
+/// Created by [`Socket::write_all`].
+pub struct SocketWrite<Env> {
+    socket: Socket,
+    bytes: Vec<u8>,
+    _phantom: PhantomData<fn() -> Env>,
+}
+
+impl<Env> fmt::Debug for SocketWrite<Env> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SocketWrite")
+            .field("peer", &self.socket.peer())
+            .field("len", &self.bytes.len())
+            .finish()
+    }
+}
+
+impl<Env: Send + Sync> Effect for SocketWrite<Env> {
+    type Output = ();
+    type Error = io::Error;
+    type Env = Env;
+
+    async fn run(self, _env: &Self::Env) -> io::Result<()> {
+        let mut stream = self.socket.inner.stream.lock().await;
+        let stream = stream.get_mut();
+        within(self.socket.inner.idle_timeout, async {
+            stream.write_all(&self.bytes).await?;
+            stream.flush().await
+        })
+        .await
+    }
+}
+
+impl<Env: Send + Sync> ResourceEffect for SocketWrite<Env> {
+    type Acquires = Empty;
+    type Releases = Empty;
+}
+
+#[derive(Debug, Clone, Copy)]
+enum ReadMode {
+    Upto(usize),
+    ToEnd,
+}
+
+/// Effect that reads from a socket.
+///
+/// Created by [`Socket::read`] or [`Socket::read_to_end`].
+pub struct SocketRead<Env> {
+    socket: Socket,
+    mode: ReadMode,
+    _phantom: PhantomData<fn() -> Env>,
+}
+
+impl<Env> fmt::Debug for SocketRead<Env> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SocketRead")
+            .field("peer", &self.socket.peer())
+            .field("mode", &self.mode)
+            .finish()
+    }
+}
+
+impl<Env: Send + Sync> Effect for SocketRead<Env> {
+    type Output = Vec<u8>;
+    type Error = io::Error;
+    type Env = Env;
+
+    async fn run(self, _env: &Self::Env) -> io::Result<Vec<u8>> {
+        let limit = self.socket.inner.idle_timeout;
+        let mut stream = self.socket.inner.stream.lock().await;
+        let mut buf = Vec::new();
+        match self.mode {
+            ReadMode::Upto(max) => {
+                buf.resize(max, 0);
+                let n = within(limit, stream.read(&mut buf)).await?;
+                buf.truncate(n);
+            }
+            ReadMode::ToEnd => {
+                let mut chunk = [0; 8192];
+                loop {
+                    let n = within(limit, stream.read(&mut chunk)).await?;
+                    if n == 0 {
+                        break;
+                    }
+                    buf.extend_from_slice(&chunk[..n]);
+                }
+            }
+        }
+        Ok(buf)
+    }
+}
+
+impl<Env: Send + Sync> ResourceEffect for SocketRead<Env> {
+    type Acquires = Empty;
+    type Releases = Empty;
+}
+
+/// Effect that reads a line from a socket.
+///
+/// Created by [`Socket::read_line`].
+pub struct SocketReadLine<Env> {
+    socket: Socket,
+    _phantom: PhantomData<fn() -> Env>,
+}
+
+impl<Env> fmt::Debug for SocketReadLine<Env> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SocketReadLine")
+            .field("peer", &self.socket.peer())
+            .finish()
+    }
+}
+
+impl<Env: Send + Sync> Effect for SocketReadLine<Env> {
+    type Output = String;
+    type Error = io::Error;
+    type Env = Env;
+
+    async fn run(self, _env: &Self::Env) -> io::Result<String> {
+        let limit = self.socket.inner.idle_timeout;
+        let mut stream = self.socket.inner.stream.lock().await;
+        let mut line = String::new();
+        within(limit, stream.read_line(&mut line)).await?;
+        Ok(line)
+    }
+}
+
+impl<Env: Send + Sync> ResourceEffect for SocketReadLine<Env> {
+    type Acquires = Empty;
+    type Releases = Empty;
+}
+
+/// Effect that closes a socket.
+///
+/// Created by [`Socket::close`].
+pub struct SocketClose<Env> {
+    socket: Socket,
+    _phantom: PhantomData<fn() -> Env>,
+}
+
+impl<Env> fmt::Debug for SocketClose<Env> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SocketClose")
+            .field("peer", &self.socket.peer())
+            .finish()
+    }
+}
+
+impl<Env: Send + Sync> Effect for SocketClose<Env> {
+    type Output = ();
+    type Error = io::Error;
+    type Env = Env;
+
+    async fn run(self, _env: &Self::Env) -> io::Result<()> {
+        self.socket.shutdown().await
+    }
+}
+
+impl<Env: Send + Sync> ResourceEffect for SocketClose<Env> {
+    type Acquires = Empty;
+    type Releases = Has<SocketRes>;
+}
+
+// ============================================================================
+// with_connection
+// ============================================================================
+
+/// Effect that runs an effect with an open connection.
+///
+/// Created by [`with_connection`].
+pub struct WithConnection<Use, E, Env> {
+    connect: Connect<Env>,
+    use_fn: Use,
+    _phantom: PhantomData<fn() -> E>,
+}
+
+impl<Use, E, Env> fmt::Debug for WithConnection<Use, E, Env> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WithConnection")
+            .field("connect", &self.connect)
+            .field("use_fn", &"<function>")
+            .finish()
+    }
+}
+
+impl<Use, UseEffect, T, E, Env> Effect for WithConnection<Use, E, Env>
+where
+    Use: FnOnce(&Socket) -> UseEffect + Send,
+    UseEffect: Effect<Output = T, Error = E, Env = Env>,
+    T: Send,
+    E: From<io::Error> + Send + fmt::Debug + 'static,
+    Env: Send + Sync,
+{
+    type Output = T;
+    type Error = E;
+    type Env = Env;
+
+    async fn run(self, env: &Self::Env) -> Result<T, E> {
+        resource_bracket::<SocketRes, _, _, _, _, _, _, _, _, _>(
+            self.connect.map_err(E::from),
+            |socket: Socket| async move { socket.shutdown().await.map_err(E::from) },
+            self.use_fn,
+        )
+        .run(env)
+        .await
+    }
+}
+
+impl<Use, E, Env> ResourceEffect for WithConnection<Use, E, Env>
+where
+    WithConnection<Use, E, Env>: Effect,
+{
+    type Acquires = Empty;
+    type Releases = Empty;
+}
+
+/// Open a connection, run the effect built by `use_fn` with it, and shut
+/// the connection down afterwards.
+///
+/// The connection is shut down whether the effect succeeded or failed.
+/// Shutdown failures are logged, as with
+/// [`resource_bracket`](crate::effect::resource::resource_bracket), and the
+/// whole effect is resource-neutral.
+pub fn with_connection<Use, UseEffect, T, E, Env>(
+    connect: Connect<Env>,
+    use_fn: Use,
+) -> WithConnection<Use, E, Env>
+where
+    Use: FnOnce(&Socket) -> UseEffect + Send,
+    UseEffect: Effect<Output = T, Error = E, Env = Env>,
+    T: Send,
+    E: From<io::Error> + Send + fmt::Debug + 'static,
+    Env: Send + Sync,
+{
+    WithConnection {
+        connect,
+        use_fn,
+        _phantom: PhantomData,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::effect::resource::assert_resource_neutral;
+    use tokio::net::TcpListener;
+
+    fn acquires_socket<E>(effect: E) -> E
+    where
+        E: ResourceEffect<Acquires = Has<SocketRes>, Releases = Empty>,
+    {
+        effect
+    }
+
+    fn releases_socket<E>(effect: E) -> E
+    where
+        E: ResourceEffect<Acquires = Empty, Releases = Has<SocketRes>>,
+    {
+        effect
+    }
+
+    /// Accept one connection and echo everything back.
+    async fn echo_server() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let (mut read, mut write) = stream.split();
+            let _ = tokio::io::copy(&mut read, &mut write).await;
+        });
+        addr
+    }
+
+    #[tokio::test]
+    async fn test_with_connection_round_trip() {
+        let addr = echo_server().await;
+
+        let effect = assert_resource_neutral(with_connection(connect_tcp(addr), |socket| {
+            let socket = socket.clone();
+            socket
+                .write_all("first line\nsecond")
+                .and_then({
+                    let socket = socket.clone();
+                    move |_| socket.read_line()
+                })
+                .and_then(move |line| socket.read(64).map(|rest| (line, rest)))
+        }));
+
+        let (line, rest) = effect.run(&()).await.unwrap();
+        assert_eq!(line, "first line\n");
+        assert_eq!(rest, b"second");
+    }
+
+    #[tokio::test]
+    async fn test_idle_timeout_fails_stalled_read() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        // Accept, then never write anything.
+        let server = tokio::spawn(async move { listener.accept().await.unwrap() });
+
+        let effect = with_connection(
+            connect_tcp(addr).idle_timeout(Duration::from_millis(50)),
+            |socket: &Socket| socket.read_to_end::<()>(),
+        );
+
+        let err = effect.run(&()).await.unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+        drop(server.await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_close_releases_socket() {
+        let addr = echo_server().await;
+
+        let socket = acquires_socket(connect_tcp::<()>(addr))
+            .run(&())
+            .await
+            .unwrap();
+        releases_socket(socket.clone().close::<()>())
+            .run(&())
+            .await
+            .unwrap();
+
+        // Echo server sees EOF and closes its side too.
+        assert_eq!(socket.read_to_end::<()>().run(&()).await.unwrap(), b"");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_connect_unix() {
+        let path = std::env::temp_dir().join(format!("stillwater-{}.sock", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let listener = tokio::net::UnixListener::bind(&path).unwrap();
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            stream.write_all(b"hello from unix\n").await.unwrap();
+        });
+
+        let line = with_connection(connect_unix(&path), |socket: &Socket| {
+            socket.read_line::<()>()
+        })
+        .run(&())
+        .await;
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(line.unwrap(), "hello from unix\n");
+    }
+}
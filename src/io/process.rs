@@ -0,0 +1,483 @@
+//! Running subprocesses as effects.
+//!
+//! [`run_command`] turns a [`Command`] into an effect that starts the
+//! process, captures its stdout and stderr, and checks its exit code. The
+//! child never outlives the effect: it is killed when its timeout expires,
+//! and also when the running effect is dropped, for example because an
+//! enclosing [`with_timeout`](crate::effect::EffectExt::with_timeout) or
+//! `race` gave up on it.
+//!
+//! Requires the `async` feature.
+//!
+//! # Example
+//!
+//! ```rust
+//! # #[cfg(unix)]
+//! # tokio_test::block_on(async {
+//! use std::time::Duration;
+//! use stillwater::effect::prelude::*;
+//! use stillwater::io::process::{run_command, Command};
+//!
+//! struct Env {
+//!     region: String,
+//! }
+//!
+//! let env = Env { region: "eu-west-1".into() };
+//!
+//! let effect = run_command(
+//!     Command::new("sh")
+//!         .arg("-c")
+//!         .arg("echo deploying to $REGION")
+//!         .timeout(Duration::from_secs(5)),
+//! )
+//! .env_from(|env: &Env| [("REGION", env.region.clone())]);
+//!
+//! let output = effect.run(&env).await.unwrap();
+//! assert_eq!(output.stdout_lossy(), "deploying to eu-west-1\n");
+//! # });
+//! ```
+
+use std::borrow::Cow;
+use std::ffi::OsString;
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::time::Duration;
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::process::Child;
+
+use crate::effect::Effect;
+use crate::predicate::{BoxedPredicate, Predicate, PredicateExt};
+
+/// A description of a process to run.
+///
+/// By default the process inherits this process's environment variables and
+/// working directory, gets an empty stdin, has no timeout, and must exit
+/// with code 0.
+pub struct Command {
+    program: OsString,
+    args: Vec<OsString>,
+    envs: Vec<(OsString, OsString)>,
+    current_dir: Option<PathBuf>,
+    stdin: Option<Vec<u8>>,
+    timeout: Option<Duration>,
+    accept_exit: Option<BoxedPredicate<i32>>,
+}
+
+impl Command {
+    /// A command running `program`, looked up on the `PATH` if it is not a
+    /// path itself.
+    pub fn new(program: impl Into<OsString>) -> Self {
+        Command {
+            program: program.into(),
+            args: Vec::new(),
+            envs: Vec::new(),
+            current_dir: None,
+            stdin: None,
+            timeout: None,
+            accept_exit: None,
+        }
+    }
+
+    /// Add an argument.
+    pub fn arg(mut self, arg: impl Into<OsString>) -> Self {
+        self.args.push(arg.into());
+        self
+    }
+
+    /// Add several arguments.
+    pub fn args<I>(mut self, args: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<OsString>,
+    {
+        self.args.extend(args.into_iter().map(Into::into));
+        self
+    }
+
+    /// Set an environment variable for the process.
+    pub fn env(mut self, key: impl Into<OsString>, value: impl Into<OsString>) -> Self {
+        self.envs.push((key.into(), value.into()));
+        self
+    }
+
+    /// Run the process in `dir`.
+    pub fn current_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.current_dir = Some(dir.into());
+        self
+    }
+
+    /// Write `input` to the process's stdin, then close it.
+    pub fn stdin(mut self, input: impl Into<Vec<u8>>) -> Self {
+        self.stdin = Some(input.into());
+        self
+    }
+
+    /// Kill the process if it has not exited after `limit`.
+    pub fn timeout(mut self, limit: Duration) -> Self {
+        self.timeout = Some(limit);
+        self
+    }
+
+    /// Accept exit codes matching `predicate` instead of only 0.
+    ///
+    /// A process terminated by a signal has no exit code and is always
+    /// rejected.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use stillwater::io::process::Command;
+    /// use stillwater::predicate::*;
+    ///
+    /// // grep exits with 1 when nothing matched, which is not an error here
+    /// let grep = Command::new("grep").arg("TODO").arg("notes.txt").accept_exit(between(0, 1));
+    /// ```
+    pub fn accept_exit<P>(mut self, predicate: P) -> Self
+    where
+        P: Predicate<i32> + 'static,
+    {
+        self.accept_exit = Some(predicate.boxed());
+        self
+    }
+
+    fn program(&self) -> String {
+        self.program.to_string_lossy().into_owned()
+    }
+
+    fn accepts(&self, code: Option<i32>) -> bool {
+        match (&self.accept_exit, code) {
+            (_, None) => false,
+            (None, Some(code)) => code == 0,
+            (Some(predicate), Some(code)) => predicate.check(&code),
+        }
+    }
+}
+
+impl fmt::Debug for Command {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Command")
+            .field("program", &self.program)
+            .field("args", &self.args)
+            .field("envs", &self.envs)
+            .field("current_dir", &self.current_dir)
+            .field("stdin", &self.stdin.as_ref().map(Vec::len))
+            .field("timeout", &self.timeout)
+            .field("accept_exit", &self.accept_exit)
+            .finish()
+    }
+}
+
+/// What a finished process wrote and how it exited.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandOutput {
+    /// The exit code, or `None` if the process was terminated by a signal
+    pub code: Option<i32>,
+    /// Everything the process wrote to stdout
+    pub stdout: Vec<u8>,
+    /// Everything the process wrote to stderr
+    pub stderr: Vec<u8>,
+}
+
+impl CommandOutput {
+    /// Stdout as text, with invalid UTF-8 replaced.
+    pub fn stdout_lossy(&self) -> Cow<'_, str> {
+        String::from_utf8_lossy(&self.stdout)
+    }
+
+    /// Stderr as text, with invalid UTF-8 replaced.
+    pub fn stderr_lossy(&self) -> Cow<'_, str> {
+        String::from_utf8_lossy(&self.stderr)
+    }
+}
+
+/// Error from running a process.
+#[derive(Debug)]
+pub enum ProcessError {
+    /// The process could not be started.
+    Spawn {
+        /// The program that was run
+        program: String,
+        /// Why it could not be started
+        source: io::Error,
+    },
+    /// Reading the process's output or waiting for it failed.
+    Io(io::Error),
+    /// The process ran past its timeout and was killed.
+    TimedOut {
+        /// The program that was run
+        program: String,
+        /// The timeout that expired
+        after: Duration,
+    },
+    /// The process exited with a code the command does not accept.
+    Rejected {
+        /// The program that was run
+        program: String,
+        /// What the process wrote and how it exited
+        output: CommandOutput,
+    },
+}
+
+impl fmt::Display for ProcessError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProcessError::Spawn { program, source } => {
+                write!(f, "failed to start {}: {}", program, source)
+            }
+            ProcessError::Io(e) => write!(f, "process I/O failed: {}", e),
+            ProcessError::TimedOut { program, after } => {
+                write!(f, "{} timed out after {:?}", program, after)
+            }
+            ProcessError::Rejected { program, output } => match output.code {
+                Some(code) => write!(f, "{} exited with code {}", program, code),
+                None => write!(f, "{} was terminated by a signal", program),
+            },
+        }
+    }
+}
+
+impl std::error::Error for ProcessError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ProcessError::Spawn { source, .. } => Some(source),
+            ProcessError::Io(e) => Some(e),
+            ProcessError::TimedOut { .. } | ProcessError::Rejected { .. } => None,
+        }
+    }
+}
+
+type EnvFromFn<Env> = Box<dyn FnOnce(&Env) -> Vec<(OsString, OsString)> + Send>;
+
+/// Effect that runs a process.
+///
+/// Created by [`run_command`].
+pub struct RunCommand<Env> {
+    command: Command,
+    env_from: Option<EnvFromFn<Env>>,
+}
+
+impl<Env> RunCommand<Env> {
+    /// Set environment variables for the process from the effect's
+    /// environment, such as credentials or a target region.
+    ///
+    /// These are applied after the variables set on the [`Command`].
+    pub fn env_from<F, I, K, V>(mut self, f: F) -> Self
+    where
+        F: FnOnce(&Env) -> I + Send + 'static,
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<OsString>,
+        V: Into<OsString>,
+    {
+        self.env_from = Some(Box::new(move |env| {
+            f(env)
+                .into_iter()
+                .map(|(key, value)| (key.into(), value.into()))
+                .collect()
+        }));
+        self
+    }
+}
+
+impl<Env> fmt::Debug for RunCommand<Env> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RunCommand")
+            .field("command", &self.command)
+            .field("env_from", &self.env_from.as_ref().map(|_| "<function>"))
+            .finish()
+    }
+}
+
+impl<Env: Send + Sync> Effect for RunCommand<Env> {
+    type Output = CommandOutput;
+    type Error = ProcessError;
+    type Env = Env;
+
+    async fn run(self, env: &Self::Env) -> Result<CommandOutput, ProcessError> {
+        let mut command = self.command;
+
+        let mut process = tokio::process::Command::new(&command.program);
+        process
+            .args(&command.args)
+            .envs(command.envs.iter().map(|(k, v)| (k, v)))
+            .stdin(if command.stdin.is_some() {
+                Stdio::piped()
+            } else {
+                Stdio::null()
+            })
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+        if let Some(dir) = &command.current_dir {
+            process.current_dir(dir);
+        }
+        if let Some(env_from) = self.env_from {
+            process.envs(env_from(env));
+        }
+
+        let mut child = process.spawn().map_err(|source| ProcessError::Spawn {
+            program: command.program(),
+            source,
+        })?;
+        let input = command.stdin.take();
+
+        let output = match command.timeout {
+            Some(limit) => match tokio::time::timeout(limit, collect(&mut child, input)).await {
+                Ok(output) => output,
+                Err(_) => {
+                    // Kill and reap the child so it does not linger as a zombie.
+                    let _ = child.kill().await;
+                    return Err(ProcessError::TimedOut {
+                        program: command.program(),
+                        after: limit,
+                    });
+                }
+            },
+            None => collect(&mut child, input).await,
+        }
+        .map_err(ProcessError::Io)?;
+
+        if command.accepts(output.code) {
+            Ok(output)
+        } else {
+            Err(ProcessError::Rejected {
+                program: command.program(),
+                output,
+            })
+        }
+    }
+}
+
+/// Feed stdin, drain stdout and stderr concurrently, and wait for exit.
+async fn collect(child: &mut Child, input: Option<Vec<u8>>) -> io::Result<CommandOutput> {
+    let stdin = child.stdin.take();
+    let mut stdout = child.stdout.take().expect("stdout is piped");
+    let mut stderr = child.stderr.take().expect("stderr is piped");
+
+    let write_stdin = async move {
+        if let (Some(mut pipe), Some(input)) = (stdin, input) {
+            match pipe.write_all(&input).await {
+                // The process exited or closed stdin without reading it all.
+                Err(e) if e.kind() == io::ErrorKind::BrokenPipe => {}
+                result => result?,
+            }
+        }
+        Ok::<_, io::Error>(())
+    };
+    let read_stdout = async {
+        let mut buf = Vec::new();
+        stdout.read_to_end(&mut buf).await.map(|_| buf)
+    };
+    let read_stderr = async {
+        let mut buf = Vec::new();
+        stderr.read_to_end(&mut buf).await.map(|_| buf)
+    };
+
+    let (written, stdout, stderr) = tokio::join!(write_stdin, read_stdout, read_stderr);
+    written?;
+    let status = child.wait().await?;
+
+    Ok(CommandOutput {
+        code: status.code(),
+        stdout: stdout?,
+        stderr: stderr?,
+    })
+}
+
+/// Run `command`, capturing its output.
+///
+/// Succeeds with the [`CommandOutput`] if the process exits with an
+/// accepted code; see [`Command::accept_exit`]. Use
+/// [`RunCommand::env_from`] to pass values from the environment to the
+/// process.
+pub fn run_command<Env>(command: Command) -> RunCommand<Env>
+where
+    Env: Send + Sync,
+{
+    RunCommand {
+        command,
+        env_from: None,
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::predicate::between;
+    use std::time::Instant;
+
+    fn sh(script: &str) -> Command {
+        Command::new("sh").arg("-c").arg(script)
+    }
+
+    #[tokio::test]
+    async fn test_captures_output_and_stdin() {
+        let output = run_command::<()>(sh("cat; echo oops >&2").stdin("hello"))
+            .run(&())
+            .await
+            .unwrap();
+
+        assert_eq!(output.code, Some(0));
+        assert_eq!(output.stdout_lossy(), "hello");
+        assert_eq!(output.stderr_lossy(), "oops\n");
+    }
+
+    #[tokio::test]
+    async fn test_rejects_unaccepted_exit_codes() {
+        let err = run_command::<()>(sh("echo failed >&2; exit 3"))
+            .run(&())
+            .await
+            .unwrap_err();
+        match err {
+            ProcessError::Rejected { output, .. } => {
+                assert_eq!(output.code, Some(3));
+                assert_eq!(output.stderr_lossy(), "failed\n");
+            }
+            other => panic!("unexpected error: {}", other),
+        }
+
+        let accepted = run_command::<()>(sh("exit 1").accept_exit(between(0, 1)))
+            .run(&())
+            .await;
+        assert_eq!(accepted.unwrap().code, Some(1));
+    }
+
+    #[tokio::test]
+    async fn test_timeout_kills_process() {
+        let start = Instant::now();
+        let err = run_command::<()>(sh("sleep 5").timeout(Duration::from_millis(100)))
+            .run(&())
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, ProcessError::TimedOut { .. }));
+        assert!(start.elapsed() < Duration::from_secs(2));
+    }
+
+    #[tokio::test]
+    async fn test_env_from_injects_variables() {
+        struct Env {
+            token: &'static str,
+        }
+
+        let output = run_command(sh("printf %s \"$TOKEN\""))
+            .env_from(|env: &Env| [("TOKEN", env.token)])
+            .run(&Env { token: "s3cret" })
+            .await
+            .unwrap();
+
+        assert_eq!(output.stdout_lossy(), "s3cret");
+    }
+
+    #[tokio::test]
+    async fn test_spawn_failure() {
+        let err = run_command::<()>(Command::new("stillwater-no-such-program"))
+            .run(&())
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, ProcessError::Spawn { .. }));
+    }
+}
@@ -0,0 +1,208 @@
+//! Waiting for termination signals, for graceful shutdown.
+//!
+//! [`on_shutdown_signal`] is an effect that succeeds with the first
+//! [`Signal`] asking the process to stop: SIGINT or SIGTERM on Unix, and
+//! Ctrl-C, console close, or system shutdown on Windows.
+//! [`until_shutdown`] runs a long-lived effect, such as a server loop, until
+//! it finishes or such a signal arrives, whichever comes first. Cleanup can
+//! then be chained on as ordinary effects.
+//!
+//! Once a signal listener is installed, the signal no longer terminates the
+//! process by default; the program is expected to shut itself down.
+//!
+//! Requires the `async` feature.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use stillwater::effect::prelude::*;
+//! use stillwater::io::signals::until_shutdown;
+//! use stillwater::Either;
+//!
+//! # async fn serve() -> Result<(), std::io::Error> { Ok(()) }
+//! # async fn flush_metrics() -> Result<(), std::io::Error> { Ok(()) }
+//! # tokio_test::block_on(async {
+//! let service = until_shutdown(from_async(|_: &()| serve()))
+//!     .and_then(|outcome| {
+//!         if let Either::Left(signal) = outcome {
+//!             println!("received {}, shutting down", signal);
+//!         }
+//!         from_async(|_: &()| flush_metrics())
+//!     });
+//!
+//! service.run(&()).await.unwrap();
+//! # });
+//! ```
+
+use std::fmt;
+use std::io;
+use std::marker::PhantomData;
+
+use crate::effect::Effect;
+use crate::Either;
+
+/// A request for the process to stop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Signal {
+    /// SIGINT on Unix, Ctrl-C or Ctrl-Break on Windows.
+    Interrupt,
+    /// SIGTERM on Unix, console close or system shutdown on Windows.
+    Terminate,
+}
+
+impl fmt::Display for Signal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Signal::Interrupt => f.write_str("interrupt signal"),
+            Signal::Terminate => f.write_str("terminate signal"),
+        }
+    }
+}
+
+#[cfg(unix)]
+async fn wait_for_signal() -> io::Result<Signal> {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let mut interrupt = signal(SignalKind::interrupt())?;
+    let mut terminate = signal(SignalKind::terminate())?;
+    tokio::select! {
+        _ = interrupt.recv() => Ok(Signal::Interrupt),
+        _ = terminate.recv() => Ok(Signal::Terminate),
+    }
+}
+
+#[cfg(windows)]
+async fn wait_for_signal() -> io::Result<Signal> {
+    use tokio::signal::windows;
+
+    let mut ctrl_c = windows::ctrl_c()?;
+    let mut ctrl_break = windows::ctrl_break()?;
+    let mut close = windows::ctrl_close()?;
+    let mut shutdown = windows::ctrl_shutdown()?;
+    tokio::select! {
+        _ = ctrl_c.recv() => Ok(Signal::Interrupt),
+        _ = ctrl_break.recv() => Ok(Signal::Interrupt),
+        _ = close.recv() => Ok(Signal::Terminate),
+        _ = shutdown.recv() => Ok(Signal::Terminate),
+    }
+}
+
+/// Effect that waits for a shutdown signal.
+///
+/// Created by [`on_shutdown_signal`].
+pub struct ShutdownSignal<Env> {
+    _phantom: PhantomData<fn() -> Env>,
+}
+
+impl<Env> fmt::Debug for ShutdownSignal<Env> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ShutdownSignal")
+    }
+}
+
+impl<Env: Send + Sync> Effect for ShutdownSignal<Env> {
+    type Output = Signal;
+    type Error = io::Error;
+    type Env = Env;
+
+    async fn run(self, _env: &Self::Env) -> io::Result<Signal> {
+        wait_for_signal().await
+    }
+}
+
+/// Wait until the process is asked to stop.
+///
+/// Fails only if the signal listeners cannot be installed.
+pub fn on_shutdown_signal<Env>() -> ShutdownSignal<Env>
+where
+    Env: Send + Sync,
+{
+    ShutdownSignal {
+        _phantom: PhantomData,
+    }
+}
+
+/// Effect that runs an effect until a shutdown signal arrives.
+///
+/// Created by [`until_shutdown`].
+pub struct UntilShutdown<Eff> {
+    inner: Eff,
+}
+
+impl<Eff> fmt::Debug for UntilShutdown<Eff> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UntilShutdown")
+            .field("inner", &"<effect>")
+            .finish()
+    }
+}
+
+impl<Eff> Effect for UntilShutdown<Eff>
+where
+    Eff: Effect,
+    Eff::Error: From<io::Error>,
+{
+    type Output = Either<Signal, Eff::Output>;
+    type Error = Eff::Error;
+    type Env = Eff::Env;
+
+    async fn run(self, env: &Self::Env) -> Result<Self::Output, Self::Error> {
+        tokio::select! {
+            result = self.inner.run(env) => result.map(Either::Right),
+            signal = wait_for_signal() => Ok(Either::Left(signal?)),
+        }
+    }
+}
+
+/// Run `effect` until it finishes or a shutdown signal arrives.
+///
+/// Succeeds with `Right` and the effect's output if it finished first, or
+/// with `Left` and the signal if the signal came first. In that case the
+/// effect is dropped where it was suspended, so anything it still has to
+/// clean up belongs in a [`bracket`](crate::effect::bracket::bracket)
+/// around it or in effects chained after this one.
+pub fn until_shutdown<Eff>(effect: Eff) -> UntilShutdown<Eff>
+where
+    Eff: Effect,
+    Eff::Error: From<io::Error>,
+{
+    UntilShutdown { inner: effect }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::effect::constructors::{from_async, pure};
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_until_shutdown_returns_finished_output() {
+        let result = until_shutdown(pure::<_, io::Error, ()>(7)).run(&()).await;
+
+        assert_eq!(result.unwrap(), Either::Right(7));
+    }
+
+    #[tokio::test]
+    async fn test_until_shutdown_stops_on_sigterm() {
+        let forever = from_async(|_: &()| async {
+            std::future::pending::<()>().await;
+            Ok::<_, io::Error>(())
+        });
+        let running = tokio::spawn(until_shutdown(forever).run(&()));
+
+        // Give the listener time to install before signalling ourselves.
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let status = std::process::Command::new("kill")
+            .arg("-TERM")
+            .arg(std::process::id().to_string())
+            .status()
+            .unwrap();
+        assert!(status.success());
+
+        let result = tokio::time::timeout(Duration::from_secs(5), running)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(result.unwrap(), Either::Left(Signal::Terminate));
+    }
+}
@@ -1,4 +1,6 @@
+#![cfg_attr(not(feature = "std"), no_std)]
 #![cfg_attr(feature = "try_trait", feature(try_trait_v2))]
+#![cfg_attr(feature = "allocator_api", feature(allocator_api))]
 //! # Stillwater
 //!
 //! A Rust library for pragmatic effect composition and validation.
@@ -67,58 +69,117 @@
 //! }
 //! ```
 //!
+//! ## `no_std` Support
+//!
+//! The validation core builds without the standard library. Disable default
+//! features to get [`Validation`], [`Either`], [`NonEmptyVec`],
+//! [`Semigroup`], [`Monoid`], [`predicate`], [`rules`], and [`refined`]
+//! under `no_std` with `alloc`:
+//!
+//! ```toml
+//! [dependencies]
+//! stillwater = { version = "1", default-features = false }
+//! ```
+//!
+//! The `std` feature (on by default) adds effects, retry, traversal,
+//! pipelines, error reports, and testing helpers, along with the
+//! `HashMap` and `HashSet` instances of the algebraic traits. Optional
+//! integrations other than `serde` enable `std`.
+//!
 //! For more examples, see the [examples](https://github.com/iepathos/stillwater/tree/master/examples) directory.
 
 #![warn(missing_docs)]
 #![warn(missing_debug_implementations)]
 
+extern crate alloc;
+
+#[cfg(feature = "std")]
 pub mod context;
+#[cfg(feature = "std")]
 pub mod effect;
 pub mod either;
+#[cfg(feature = "prometheus")]
+pub mod integrations;
+#[cfg(feature = "std")]
 pub mod io;
 pub mod monoid;
 pub mod nonempty;
+#[cfg(feature = "std")]
+pub mod pipeline;
 pub mod predicate;
+#[cfg(feature = "examples")]
+pub mod recipes;
 pub mod refined;
+#[cfg(feature = "std")]
+pub mod report;
+#[cfg(feature = "std")]
 pub mod retry;
+pub mod rules;
 pub mod semigroup;
+#[cfg(feature = "std")]
 pub mod testing;
+#[cfg(feature = "std")]
 pub mod traverse;
 pub mod validation;
 
 // Re-exports - Effect system (zero-cost by default)
+#[cfg(feature = "std")]
 pub use effect::{BoxedEffect, Effect, EffectContext, EffectContextChain, EffectExt};
 
 // Re-export boxed types
+#[cfg(feature = "std")]
 pub use effect::boxed::{BoxFuture, BoxedLocalEffect};
 
 // Re-export constructors
+#[cfg(feature = "std")]
 pub use effect::constructors::{
-    ask, asks, fail, from_async, from_fn, from_option, from_result, from_validation, local, pure,
-    zip3, zip4, zip5, zip6, zip7, zip8,
+    ask, asks, fail, from_async, from_fn, from_option, from_result, from_validation, local,
+    loop_effect, pure, zip3, zip4, zip5, zip6, zip7, zip8,
 };
 
+// Re-export projections to library environments
+#[cfg(feature = "std")]
+pub use effect::env::Project;
+
+// Re-export tuple environment projections
+#[cfg(feature = "std")]
+pub use effect::tuple_env::{asks_has, local_has, Provides};
+
 // Re-export parallel functions
-pub use effect::parallel::{par2, par3, par4, par_all, par_all_limit, par_try_all, race};
+#[cfg(feature = "std")]
+pub use effect::parallel::{
+    par2, par3, par4, par_all, par_all_labeled, par_all_limit, par_all_settled, par_try_all,
+    par_validate_all, race, zip_labeled, LabeledError,
+};
 
 // Re-export combinator types (for advanced use)
+#[cfg(feature = "std")]
 pub use effect::combinators::{
-    AndThen, AndThenAuto, AndThenRef, Check, Fail, FromAsync, FromFn, FromResult, Map, MapErr,
-    OrElse, Pure, Tap, With, Zip, Zip3, Zip4, Zip5, Zip6, Zip7, Zip8, ZipWith,
+    AndThen, AndThenAuto, AndThenRef, Check, Fail, FromAsync, FromFn, FromFuture, FromResult, Loop,
+    LoopEffect, Map, MapErr, OrElse, Pure, Tap, With, Zip, Zip3, Zip4, Zip5, Zip6, Zip7, Zip8,
+    ZipJoin, ZipPar, ZipWith,
 };
 
+// Re-export `.into_effect()` conversions
+#[cfg(feature = "std")]
+pub use effect::into_effect::{FutureIntoEffect, IntoEffect, OptionIntoEffect};
+
 // Re-export reader types
-pub use effect::reader::{Ask, Asks, Local};
+#[cfg(feature = "std")]
+pub use effect::reader::{Ask, Asks, Local, MapEnvFor};
 
 // Re-export bracket
+#[cfg(feature = "std")]
 #[allow(deprecated)]
 pub use effect::bracket::bracket_simple;
+#[cfg(feature = "std")]
 pub use effect::bracket::{
     acquiring, bracket, bracket2, bracket3, bracket_full, bracket_sync, Acquiring, Bracket,
     Bracket2, Bracket3, BracketError, BracketFull, BracketSync, Resource, ResourceWith,
 };
 
 // Re-export compat items
+#[cfg(feature = "std")]
 #[allow(deprecated)]
 pub use effect::compat::{LegacyConstructors, LegacyEffect, RunStandalone};
 
@@ -126,15 +187,25 @@ pub use effect::compat::{LegacyConstructors, LegacyEffect, RunStandalone};
 #[cfg(feature = "tracing")]
 pub use effect::tracing::{EffectTracingExt, Instrument};
 
+// Re-export value tracing (when feature enabled)
+#[cfg(feature = "debug")]
+pub use effect::traced::{TraceLog, TraceValuesExt};
+
 // Other re-exports
+#[cfg(feature = "std")]
 pub use context::ContextError;
+#[cfg(feature = "std")]
 pub use io::IO;
 pub use monoid::Monoid;
 pub use nonempty::NonEmptyVec;
+#[cfg(feature = "std")]
+pub use report::{ErrorReport, ToReport};
+#[cfg(feature = "std")]
 pub use retry::{
-    JitterStrategy, RetryEvent, RetryExhausted, RetryPolicy, RetryStrategy, TimeoutError,
+    ErrorClass, JitterStrategy, RetryEvent, RetryExhausted, RetryPolicy, RetryStrategy,
+    RetrySuccess, TimeoutError,
 };
-pub use semigroup::{First, Intersection, Last, Semigroup};
+pub use semigroup::{Dedup, First, Intersection, Last, MergeMap, Semigroup, Union};
 pub use validation::Validation;
 
 // Re-export Either type and utilities
@@ -143,18 +214,142 @@ pub use either::Either;
 /// Prelude module for convenient imports
 pub mod prelude {
     // Effect system
+    #[cfg(feature = "std")]
     pub use crate::effect::prelude::*;
 
     // Other types
+    #[cfg(feature = "std")]
     pub use crate::context::ContextError;
     pub use crate::either::Either;
+    #[cfg(feature = "std")]
     pub use crate::io::IO;
     pub use crate::monoid::Monoid;
     pub use crate::nonempty::NonEmptyVec;
-    pub use crate::retry::{RetryEvent, RetryExhausted, RetryPolicy, TimeoutError};
-    pub use crate::semigroup::{First, Intersection, Last, Semigroup};
+    #[cfg(feature = "std")]
+    pub use crate::retry::{RetryEvent, RetryExhausted, RetryPolicy, RetrySuccess, TimeoutError};
+    pub use crate::semigroup::{Dedup, First, Intersection, Last, MergeMap, Semigroup, Union};
+    #[cfg(feature = "std")]
     pub use crate::testing::{MockEnv, TestEffect};
-    pub use crate::traverse::{sequence, sequence_effect, traverse, traverse_effect};
+    #[cfg(feature = "std")]
+    pub use crate::traverse::{
+        par_traverse_unordered, sequence, sequence_effect, sequence_option, sequence_result,
+        traverse, traverse_effect, traverse_option, traverse_result, traverse_resumable,
+        traverse_with_dlq,
+    };
     pub use crate::validation::Validation;
+    #[cfg(feature = "std")]
     pub use crate::{assert_failure, assert_success, assert_validation_errors};
+
+    /// Curated prelude without legacy APIs or conflicting names
+    ///
+    /// Covers the zero-cost Effect system, Writer and Sink effects,
+    /// validation, and refined types. Compatibility traits such as
+    /// `RunStandalone`, combinator structs, and test helpers are left out.
+    ///
+    /// The `predicate` and `refined` modules both define a `Predicate`
+    /// trait, so they're exported as [`RuntimePredicate`] and
+    /// [`RefinedPredicate`]. `And`, `Or`, and `Not` are the refined
+    /// combinators; compose runtime predicates with [`PredicateExt`] methods
+    /// instead.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use stillwater::prelude::v2::*;
+    ///
+    /// # tokio_test::block_on(async {
+    /// type Username = Refined<String, And<NonEmpty, MaxLength<16>>>;
+    ///
+    /// let valid_age = ge(0).and(le(150));
+    /// assert!(valid_age.check(&25));
+    ///
+    /// let effect = pure::<_, String, ()>("alice".to_string())
+    ///     .map(|name| Username::new(name).map_err(|e| e.to_string()))
+    ///     .and_then(from_result);
+    /// assert_eq!(effect.run(&()).await.unwrap().get(), "alice");
+    ///
+    /// let (value, logs) = tell_one::<_, String, ()>("step".to_string())
+    ///     .map(|_| 1)
+    ///     .run_writer(&())
+    ///     .await;
+    /// assert_eq!((value, logs), (Ok(1), vec!["step".to_string()]));
+    /// # });
+    /// ```
+    #[cfg(feature = "std")]
+    pub mod v2 {
+        // Effect system
+        pub use crate::effect::bracket::{
+            acquiring, bracket, bracket2, bracket3, bracket_full, bracket_sync, BracketError,
+            Resource,
+        };
+        pub use crate::effect::constructors::{
+            ask, asks, fail, from_async, from_fn, from_option, from_result, from_validation, local,
+            loop_effect, pure, zip3, zip4, zip5, zip6, zip7, zip8,
+        };
+        pub use crate::effect::context::{EffectContext, EffectContextChain};
+        pub use crate::effect::parallel::{
+            par2, par3, par4, par_all, par_all_labeled, par_all_limit, par_all_settled,
+            par_try_all, par_validate_all, race, zip_labeled, LabeledError,
+        };
+        pub use crate::effect::request_context::RequestContextExt;
+        #[cfg(feature = "async")]
+        pub use crate::effect::retry::{retry, retry_if, retry_with_hooks, with_timeout};
+        #[cfg(feature = "debug")]
+        pub use crate::effect::traced::TraceValuesExt;
+        #[cfg(feature = "tracing")]
+        pub use crate::effect::tracing::EffectTracingExt;
+        pub use crate::effect::tuple_env::{asks_has, local_has};
+        pub use crate::effect::{BoxedEffect, Effect, EffectExt};
+        pub use crate::par;
+        pub use crate::recover_matching;
+
+        // Writer and Sink effects
+        pub use crate::effect::sink::prelude::{
+            emit, emit_many, into_sink, traverse_sink, BoxedSinkEffect, SinkEffect,
+        };
+        pub use crate::effect::writer::prelude::{
+            into_writer, tell, tell_one, traverse_writer, BoxedWriterEffect, WriterEffect,
+            WriterEffectExt,
+        };
+
+        // Validation and errors
+        pub use crate::context::ContextError;
+        pub use crate::either::Either;
+        pub use crate::monoid::Monoid;
+        pub use crate::nonempty::NonEmptyVec;
+        pub use crate::report::{ErrorReport, ToReport};
+        pub use crate::retry::{ErrorClass, RetryPolicy};
+        pub use crate::semigroup::Semigroup;
+        pub use crate::traverse::{
+            sequence, sequence_effect, sequence_option, sequence_result, traverse, traverse_effect,
+            traverse_option, traverse_result,
+        };
+        pub use crate::validation::Validation;
+
+        // Runtime predicates
+        #[cfg(feature = "regex")]
+        pub use crate::predicate::matches;
+        #[cfg(feature = "url")]
+        pub use crate::predicate::valid_url;
+        pub use crate::predicate::{
+            all, all_chars, all_of, any, any_char, any_of, between, contains, contains_ci,
+            contains_element, ends_with, eq, ge, gt, has_len, has_max_len, has_min_len,
+            is_alphabetic, is_alphanumeric, is_ascii, is_empty, is_not_empty, is_numeric, le,
+            len_between, len_eq, len_max, len_min, lt, ne, negative, non_negative, none_of,
+            not_empty, positive, starts_with, starts_with_ci, valid_hostname, valid_ip_addr,
+            valid_port, valid_uuid, validate, validate_with,
+        };
+        #[cfg(feature = "unicode")]
+        pub use crate::predicate::{graphemes_max, is_normalized_nfc};
+        #[cfg(feature = "chrono")]
+        pub use crate::predicate::{in_future, in_past, valid_rfc3339, within_days};
+        pub use crate::predicate::{Predicate as RuntimePredicate, PredicateExt};
+
+        // Refined types
+        pub use crate::refined::{
+            And, FieldError, InRange, MaxLength, MaxSize, MinLength, MinSize, Negative, NonEmpty,
+            NonNegative, NonZero, Not, Or, Positive, Predicate as RefinedPredicate, Refined,
+            RefinedValidationExt, Trimmed, ValidationFieldExt,
+        };
+    }
 }
@@ -50,8 +50,12 @@
 //! assert_eq!(total, Sum(10));
 //! ```
 
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ops::{Add, Mul};
+
+use crate::semigroup::{Dedup, MergeMap, Union};
 use crate::Semigroup;
-use std::ops::{Add, Mul};
 
 /// A `Monoid` is a `Semigroup` with an identity element.
 ///
@@ -136,10 +140,14 @@ impl_monoid_tuple!(0 T1, 1 T2, 2 T3, 3 T4, 4 T5, 5 T6, 6 T7, 7 T8, 8 T9, 9 T10,
 impl_monoid_tuple!(0 T1, 1 T2, 2 T3, 3 T4, 4 T5, 5 T6, 6 T7, 7 T8, 8 T9, 9 T10, 10 T11, 11 T12);
 
 // Monoid instances for collection types
-use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
-use std::hash::Hash;
+use alloc::collections::{BTreeMap, BTreeSet};
+#[cfg(feature = "std")]
+use core::hash::Hash;
+#[cfg(feature = "std")]
+use std::collections::{HashMap, HashSet};
 
 /// Monoid for HashMap - empty map is identity
+#[cfg(feature = "std")]
 impl<K, V> Monoid for HashMap<K, V>
 where
     K: Eq + Hash + Clone,
@@ -151,6 +159,7 @@ where
 }
 
 /// Monoid for HashSet - empty set is identity
+#[cfg(feature = "std")]
 impl<T> Monoid for HashSet<T>
 where
     T: Eq + Hash,
@@ -181,6 +190,48 @@ where
     }
 }
 
+/// Monoid for `Union` of a HashSet - empty set is identity
+#[cfg(feature = "std")]
+impl<T> Monoid for Union<HashSet<T>>
+where
+    T: Eq + Hash,
+{
+    fn empty() -> Self {
+        Union(HashSet::new())
+    }
+}
+
+/// Monoid for `Union` of a BTreeSet - empty set is identity
+impl<T> Monoid for Union<BTreeSet<T>>
+where
+    T: Ord,
+{
+    fn empty() -> Self {
+        Union(BTreeSet::new())
+    }
+}
+
+/// Monoid for `Dedup` - empty vector is identity
+impl<T> Monoid for Dedup<Vec<T>>
+where
+    T: PartialEq,
+{
+    fn empty() -> Self {
+        Dedup(Vec::new())
+    }
+}
+
+/// Monoid for `MergeMap` - empty map is identity
+impl<K, V> Monoid for MergeMap<K, V>
+where
+    K: Ord,
+    V: Semigroup,
+{
+    fn empty() -> Self {
+        MergeMap::default()
+    }
+}
+
 /// Monoid for numeric types under addition.
 ///
 /// Identity: 0
@@ -450,6 +501,18 @@ mod tests {
         assert_eq!(empty.combine(v.clone()), v);
     }
 
+    #[test]
+    fn test_wrapper_identities() {
+        let dedup = Dedup(vec![1, 2]);
+        assert_eq!(Dedup::empty().combine(dedup.clone()), dedup);
+
+        let merged = MergeMap::single("a", vec![1]);
+        assert_eq!(merged.clone().combine(MergeMap::empty()), merged);
+
+        let union = Union(BTreeSet::from([1]));
+        assert_eq!(Union::empty().combine(union.clone()), union);
+    }
+
     #[test]
     fn test_string_right_identity() {
         let s = "hello".to_string();
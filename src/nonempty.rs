@@ -21,6 +21,9 @@
 //! - Aggregations: Operations like `head()`, `max()`, `min()` require non-empty data
 //! - Type safety: Prevent `None`/`panic!` in operations that need elements
 
+use alloc::vec;
+use alloc::vec::Vec;
+
 use crate::Semigroup;
 
 /// A non-empty vector guaranteed to contain at least one element.
@@ -313,7 +316,7 @@ impl<T> NonEmptyVec<T> {
     /// assert_eq!(sum, 6);
     /// ```
     pub fn iter(&self) -> impl Iterator<Item = &T> {
-        std::iter::once(&self.head).chain(self.tail.iter())
+        core::iter::once(&self.head).chain(self.tail.iter())
     }
 }
 
@@ -329,10 +332,10 @@ impl<T> Semigroup for NonEmptyVec<T> {
 // IntoIterator
 impl<T> IntoIterator for NonEmptyVec<T> {
     type Item = T;
-    type IntoIter = std::iter::Chain<std::iter::Once<T>, std::vec::IntoIter<T>>;
+    type IntoIter = core::iter::Chain<core::iter::Once<T>, alloc::vec::IntoIter<T>>;
 
     fn into_iter(self) -> Self::IntoIter {
-        std::iter::once(self.head).chain(self.tail)
+        core::iter::once(self.head).chain(self.tail)
     }
 }
 
@@ -340,7 +343,7 @@ impl<T> IntoIterator for NonEmptyVec<T> {
 // Instead, use NonEmptyVec::from_vec(vec) where vec is collected from an iterator.
 
 // Index
-impl<T> std::ops::Index<usize> for NonEmptyVec<T> {
+impl<T> core::ops::Index<usize> for NonEmptyVec<T> {
     type Output = T;
 
     fn index(&self, index: usize) -> &Self::Output {
@@ -0,0 +1,293 @@
+//! Declarative pipeline construction from configuration (feature-gated).
+//!
+//! This module maps a declarative list of stages, typically deserialized
+//! from JSON or YAML, onto stage factories registered in a
+//! [`StageRegistry`]. Feature-gated behind `#[cfg(feature = "serde")]`.
+//!
+//! The config types are format-agnostic: pick a params type `P` that your
+//! deserializer understands, such as `serde_json::Value` or your own enum.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use stillwater::effect::prelude::*;
+//! use stillwater::pipeline::{from_config, PipelineConfig, Stage, StageRegistry};
+//!
+//! let registry = StageRegistry::<serde_json::Value, String, ()>::new()
+//!     .register("parse", |_| {
+//!         Ok(Stage::new("parse", |s: String| {
+//!             from_result(s.parse::<i64>().map_err(|e| e.to_string()))
+//!         }))
+//!     })
+//!     .register("scale", |params| {
+//!         let factor = params["factor"].as_i64().ok_or("missing 'factor'".to_string())?;
+//!         Ok(Stage::new("scale", move |n: i64| pure(n * factor)))
+//!     });
+//!
+//! let config: PipelineConfig<serde_json::Value> = serde_json::from_str(r#"{
+//!     "stages": [
+//!         { "name": "parse", "factory": "parse" },
+//!         { "name": "triple", "factory": "scale", "params": { "factor": 3 } }
+//!     ]
+//! }"#)?;
+//!
+//! let pipeline = from_config::<String, i64, _, _, _>(&config, &registry).into_result()?;
+//! assert_eq!(pipeline.effect("14".to_string()).execute(&()).await, Ok(42));
+//! ```
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use super::{CompiledPipeline, Pipeline, PipelineError, Stage};
+use crate::validation::Validation;
+
+type StageFactory<P, E, Env> = Arc<dyn Fn(&P) -> Result<Stage<E, Env>, String> + Send + Sync>;
+
+/// A declarative pipeline description.
+///
+/// # Type Parameters
+///
+/// * `P` - The per-stage parameters type (e.g. `serde_json::Value`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(bound(deserialize = "P: Deserialize<'de> + Default"))]
+pub struct PipelineConfig<P> {
+    /// The stages in execution order.
+    pub stages: Vec<StageConfig<P>>,
+    /// Capabilities the environment provides.
+    #[serde(default)]
+    pub provides: Vec<String>,
+}
+
+/// A single stage in a [`PipelineConfig`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StageConfig<P> {
+    /// The stage name, unique within the pipeline.
+    pub name: String,
+    /// The registered factory used to build the stage.
+    pub factory: String,
+    /// Parameters passed to the factory.
+    #[serde(default)]
+    pub params: P,
+}
+
+/// A registry of named stage factories.
+///
+/// Factories receive the stage's params and return a [`Stage`], or an error
+/// message if the params are invalid. The stage is renamed to the name given
+/// in the config, so one factory can back several stages.
+pub struct StageRegistry<P, E, Env> {
+    factories: HashMap<String, StageFactory<P, E, Env>>,
+}
+
+impl<P, E, Env> std::fmt::Debug for StageRegistry<P, E, Env> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut names: Vec<_> = self.factories.keys().collect();
+        names.sort();
+        f.debug_struct("StageRegistry")
+            .field("factories", &names)
+            .finish()
+    }
+}
+
+impl<P, E, Env> Default for StageRegistry<P, E, Env> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<P, E, Env> StageRegistry<P, E, Env> {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        StageRegistry {
+            factories: HashMap::new(),
+        }
+    }
+
+    /// Register a stage factory under the given name.
+    ///
+    /// Registering the same name twice replaces the earlier factory.
+    pub fn register<F>(mut self, name: impl Into<String>, factory: F) -> Self
+    where
+        F: Fn(&P) -> Result<Stage<E, Env>, String> + Send + Sync + 'static,
+    {
+        self.factories.insert(name.into(), Arc::new(factory));
+        self
+    }
+
+    /// Returns `true` if a factory is registered under the given name.
+    pub fn contains(&self, name: &str) -> bool {
+        self.factories.contains_key(name)
+    }
+}
+
+/// Build and validate a pipeline from a declarative config.
+///
+/// Every stage is looked up in the registry and built from its params.
+/// Unknown factories and rejected params are accumulated; if there are
+/// none, the assembled pipeline goes through the usual pre-flight
+/// [`validate`](Pipeline::validate) and its errors are returned instead.
+pub fn from_config<I, O, P, E, Env>(
+    config: &PipelineConfig<P>,
+    registry: &StageRegistry<P, E, Env>,
+) -> Validation<CompiledPipeline<I, O, E, Env>, Vec<PipelineError>>
+where
+    I: Send + 'static,
+    O: Send + 'static,
+    E: Send + 'static,
+    Env: Send + Sync + 'static,
+{
+    let mut errors = Vec::new();
+    let mut pipeline = Pipeline::new();
+
+    for capability in &config.provides {
+        pipeline = pipeline.provides(capability.clone());
+    }
+
+    for stage_config in &config.stages {
+        let Some(factory) = registry.factories.get(&stage_config.factory) else {
+            errors.push(PipelineError::UnknownFactory {
+                stage: stage_config.name.clone(),
+                factory: stage_config.factory.clone(),
+            });
+            continue;
+        };
+
+        match factory(&stage_config.params) {
+            Ok(mut stage) => {
+                stage.name = stage_config.name.clone();
+                pipeline = pipeline.stage(stage);
+            }
+            Err(message) => errors.push(PipelineError::InvalidParams {
+                stage: stage_config.name.clone(),
+                message,
+            }),
+        }
+    }
+
+    if errors.is_empty() {
+        pipeline.build()
+    } else {
+        Validation::failure(errors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::effect::constructors::{from_result, pure};
+    use crate::effect::ext::EffectExt;
+    use serde_json::{json, Value};
+
+    fn registry() -> StageRegistry<Value, String, ()> {
+        StageRegistry::new()
+            .register("parse", |_| {
+                Ok(Stage::new("parse", |s: String| {
+                    from_result(s.trim().parse::<i64>().map_err(|e| e.to_string()))
+                }))
+            })
+            .register("scale", |params: &Value| {
+                let factor = params["factor"]
+                    .as_i64()
+                    .ok_or_else(|| "missing integer 'factor'".to_string())?;
+                Ok(Stage::new("scale", move |n: i64| pure(n * factor)))
+            })
+            .register("render", |_| {
+                Ok(Stage::new("render", |n: i64| pure(n.to_string())).requires("stdout"))
+            })
+    }
+
+    fn config(value: Value) -> PipelineConfig<Value> {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_from_config_builds_runnable_pipeline() {
+        let config = config(json!({
+            "stages": [
+                { "name": "parse", "factory": "parse" },
+                { "name": "double", "factory": "scale", "params": { "factor": 2 } },
+                { "name": "triple", "factory": "scale", "params": { "factor": 3 } }
+            ]
+        }));
+
+        let pipeline = from_config::<String, i64, _, _, _>(&config, &registry())
+            .into_result()
+            .unwrap();
+
+        assert_eq!(
+            pipeline.stage_names().collect::<Vec<_>>(),
+            vec!["parse", "double", "triple"]
+        );
+        assert_eq!(pipeline.effect("7".to_string()).execute(&()).await, Ok(42));
+    }
+
+    #[test]
+    fn test_from_config_accumulates_factory_errors() {
+        let config = config(json!({
+            "stages": [
+                { "name": "load", "factory": "missing" },
+                { "name": "scale", "factory": "scale", "params": {} }
+            ]
+        }));
+
+        let errors = from_config::<String, i64, _, _, _>(&config, &registry()).unwrap_err();
+
+        assert_eq!(
+            errors,
+            vec![
+                PipelineError::UnknownFactory {
+                    stage: "load".to_string(),
+                    factory: "missing".to_string(),
+                },
+                PipelineError::InvalidParams {
+                    stage: "scale".to_string(),
+                    message: "missing integer 'factor'".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_from_config_runs_preflight_validation() {
+        let config = config(json!({
+            "stages": [
+                { "name": "parse", "factory": "parse" },
+                { "name": "render", "factory": "render" }
+            ]
+        }));
+
+        let errors = from_config::<String, String, _, _, _>(&config, &registry()).unwrap_err();
+        assert_eq!(
+            errors,
+            vec![PipelineError::MissingCapability {
+                stage: "render".to_string(),
+                capability: "stdout".to_string(),
+            }]
+        );
+
+        let config = PipelineConfig {
+            provides: vec!["stdout".to_string()],
+            ..config
+        };
+        assert!(from_config::<String, String, _, _, _>(&config, &registry()).is_success());
+    }
+
+    #[test]
+    fn test_config_round_trips_through_json() {
+        let config = config(json!({
+            "stages": [{ "name": "parse", "factory": "parse" }],
+            "provides": ["stdout"]
+        }));
+
+        let json = serde_json::to_value(&config).unwrap();
+        assert_eq!(
+            json,
+            json!({
+                "stages": [{ "name": "parse", "factory": "parse", "params": null }],
+                "provides": ["stdout"]
+            })
+        );
+    }
+}
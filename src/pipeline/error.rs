@@ -0,0 +1,115 @@
+//! Error types for pipeline pre-flight validation.
+
+use std::fmt;
+
+/// A configuration error found while validating a [`Pipeline`](super::Pipeline).
+///
+/// Pre-flight validation accumulates every error it finds, so a single call
+/// to [`Pipeline::validate`](super::Pipeline::validate) reports all problems
+/// with a pipeline configuration at once.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PipelineError {
+    /// Two stages share the same name.
+    DuplicateStage {
+        /// The repeated stage name.
+        name: String,
+    },
+    /// A stage's input type doesn't match the previous stage's output type.
+    TypeMismatch {
+        /// The stage whose input doesn't line up.
+        stage: String,
+        /// The type produced by the previous stage (or the pipeline input).
+        expected: &'static str,
+        /// The type the stage accepts.
+        found: &'static str,
+    },
+    /// The last stage doesn't produce the pipeline's declared output type.
+    OutputMismatch {
+        /// The pipeline's declared output type.
+        expected: &'static str,
+        /// The type produced by the last stage (or the pipeline input).
+        found: &'static str,
+    },
+    /// A stage requires a capability the environment doesn't provide.
+    MissingCapability {
+        /// The stage declaring the requirement.
+        stage: String,
+        /// The missing capability.
+        capability: String,
+    },
+    /// A stage releases a resource that no earlier stage acquired.
+    ReleaseWithoutAcquire {
+        /// The stage releasing the resource.
+        stage: String,
+        /// The resource kind name.
+        resource: &'static str,
+    },
+    /// A resource acquired by a stage is never released.
+    UnreleasedResource {
+        /// The stage that acquired the resource.
+        stage: String,
+        /// The resource kind name.
+        resource: &'static str,
+    },
+    /// A config entry names a stage factory that isn't registered.
+    UnknownFactory {
+        /// The stage being configured.
+        stage: String,
+        /// The unregistered factory name.
+        factory: String,
+    },
+    /// A stage factory rejected the params given in the config.
+    InvalidParams {
+        /// The stage being configured.
+        stage: String,
+        /// The factory's error message.
+        message: String,
+    },
+}
+
+impl fmt::Display for PipelineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PipelineError::DuplicateStage { name } => {
+                write!(f, "duplicate stage name '{}'", name)
+            }
+            PipelineError::TypeMismatch {
+                stage,
+                expected,
+                found,
+            } => write!(
+                f,
+                "stage '{}' accepts {} but previous stage produces {}",
+                stage, found, expected
+            ),
+            PipelineError::OutputMismatch { expected, found } => write!(
+                f,
+                "pipeline must produce {} but last stage produces {}",
+                expected, found
+            ),
+            PipelineError::MissingCapability { stage, capability } => write!(
+                f,
+                "stage '{}' requires capability '{}' which is not provided",
+                stage, capability
+            ),
+            PipelineError::ReleaseWithoutAcquire { stage, resource } => write!(
+                f,
+                "stage '{}' releases {} which was never acquired",
+                stage, resource
+            ),
+            PipelineError::UnreleasedResource { stage, resource } => write!(
+                f,
+                "{} acquired by stage '{}' is never released",
+                resource, stage
+            ),
+            PipelineError::UnknownFactory { stage, factory } => {
+                write!(f, "stage '{}' uses unknown factory '{}'", stage, factory)
+            }
+            PipelineError::InvalidParams { stage, message } => {
+                write!(f, "stage '{}' has invalid params: {}", stage, message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for PipelineError {}
@@ -0,0 +1,292 @@
+//! Named effect pipelines with pre-flight validation.
+//!
+//! This module provides a [`Pipeline`] builder that assembles named
+//! [`Stage`]s and checks the whole configuration before anything runs:
+//!
+//! - **Stage compatibility**: each stage accepts what the previous one produces
+//! - **Env capabilities**: every capability a stage requires is provided
+//! - **Resource usage**: every acquired resource is released, and nothing is
+//!   released before it is acquired
+//! - **Naming**: stage names are unique
+//!
+//! Validation accumulates all errors into a [`Validation`], so pipelines
+//! assembled from user input (ETL jobs, configurable workflows) can report
+//! every configuration problem at once. This is the runtime counterpart to
+//! the compile-time checks in [`effect::resource`](crate::effect::resource).
+//!
+//! # Example
+//!
+//! ```rust
+//! use stillwater::effect::prelude::*;
+//! use stillwater::pipeline::Pipeline;
+//!
+//! # tokio_test::block_on(async {
+//! let pipeline = Pipeline::<String, i64, String, ()>::new()
+//!     .then("parse", |line: String| {
+//!         from_result(line.trim().parse::<i64>().map_err(|e| e.to_string()))
+//!     })
+//!     .then("double", |n: i64| pure(n * 2));
+//!
+//! let compiled = pipeline.build().into_result().unwrap();
+//! let result = compiled.effect(" 21 ".to_string()).execute(&()).await;
+//! assert_eq!(result, Ok(42));
+//! # });
+//! ```
+//!
+//! # Pre-flight Errors
+//!
+//! ```rust
+//! use stillwater::effect::prelude::*;
+//! use stillwater::pipeline::{Pipeline, PipelineError, Stage};
+//!
+//! let pipeline = Pipeline::<String, i64, String, ()>::new()
+//!     .stage(Stage::new("load", |s: String| pure(s.len())).requires("fs"))
+//!     .stage(Stage::new("load", |n: u8| pure(n as i64)));
+//!
+//! let errors = pipeline.validate().unwrap_err();
+//! assert_eq!(errors.len(), 3); // missing capability, duplicate name, type mismatch
+//! ```
+//!
+//! # Declarative Configuration
+//!
+//! With the `serde` feature, [`from_config`] builds a pipeline from a
+//! deserialized [`PipelineConfig`] by looking up each stage's factory in a
+//! [`StageRegistry`]. Unknown factories, rejected params, and pre-flight
+//! errors are all reported together.
+
+#[cfg(feature = "serde")]
+mod config;
+mod error;
+mod stage;
+
+#[cfg(feature = "serde")]
+pub use config::{from_config, PipelineConfig, StageConfig, StageRegistry};
+pub use error::PipelineError;
+pub use stage::Stage;
+
+use std::collections::HashSet;
+use std::marker::PhantomData;
+
+use crate::effect::boxed::BoxedEffect;
+use crate::effect::constructors::pure;
+use crate::effect::ext::EffectExt;
+use crate::effect::Effect;
+use crate::validation::Validation;
+use stage::{StageFn, StageValue, TypeInfo};
+
+/// A builder for a sequence of named stages from `I` to `O`.
+///
+/// Stages are type-erased so they can be assembled at runtime; the
+/// [`validate`](Pipeline::validate) pre-flight check re-establishes type
+/// safety before execution. Use [`build`](Pipeline::build) to validate and
+/// obtain a runnable [`CompiledPipeline`].
+///
+/// # Type Parameters
+///
+/// * `I` - The pipeline input type
+/// * `O` - The pipeline output type
+/// * `E` - The error type shared by all stages
+/// * `Env` - The environment type shared by all stages
+pub struct Pipeline<I, O, E, Env> {
+    stages: Vec<Stage<E, Env>>,
+    capabilities: Vec<String>,
+    _marker: PhantomData<fn(I) -> O>,
+}
+
+impl<I, O, E, Env> std::fmt::Debug for Pipeline<I, O, E, Env> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Pipeline")
+            .field("stages", &self.stages)
+            .field("capabilities", &self.capabilities)
+            .finish()
+    }
+}
+
+impl<I, O, E, Env> Default for Pipeline<I, O, E, Env> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I, O, E, Env> Pipeline<I, O, E, Env> {
+    /// Create an empty pipeline.
+    pub fn new() -> Self {
+        Pipeline {
+            stages: Vec::new(),
+            capabilities: Vec::new(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Append a stage.
+    pub fn stage(mut self, stage: Stage<E, Env>) -> Self {
+        self.stages.push(stage);
+        self
+    }
+
+    /// Declare a capability the environment provides.
+    pub fn provides(mut self, capability: impl Into<String>) -> Self {
+        self.capabilities.push(capability.into());
+        self
+    }
+
+    /// The stages in execution order.
+    pub fn stages(&self) -> &[Stage<E, Env>] {
+        &self.stages
+    }
+}
+
+impl<I, O, E, Env> Pipeline<I, O, E, Env>
+where
+    I: Send + 'static,
+    O: Send + 'static,
+    E: Send + 'static,
+    Env: Send + Sync + 'static,
+{
+    /// Append a stage built from a name and function.
+    ///
+    /// Shorthand for `.stage(Stage::new(name, f))`.
+    pub fn then<A, B, Eff, F>(self, name: impl Into<String>, f: F) -> Self
+    where
+        A: Send + 'static,
+        B: Send + 'static,
+        Eff: Effect<Output = B, Error = E, Env = Env> + 'static,
+        F: Fn(A) -> Eff + Send + Sync + 'static,
+    {
+        self.stage(Stage::new(name, f))
+    }
+
+    /// Check the pipeline configuration without running anything.
+    ///
+    /// Returns every [`PipelineError`] found, in stage order.
+    pub fn validate(&self) -> Validation<(), Vec<PipelineError>> {
+        let mut errors = Vec::new();
+        let mut names = HashSet::new();
+        let mut open_resources: Vec<(&'static str, &str)> = Vec::new();
+        let mut current = TypeInfo::of::<I>();
+
+        for stage in &self.stages {
+            if !names.insert(stage.name.as_str()) {
+                errors.push(PipelineError::DuplicateStage {
+                    name: stage.name.clone(),
+                });
+            }
+
+            if stage.input.id != current.id {
+                errors.push(PipelineError::TypeMismatch {
+                    stage: stage.name.clone(),
+                    expected: current.name,
+                    found: stage.input.name,
+                });
+            }
+            current = stage.output;
+
+            for capability in &stage.requires {
+                if !self.capabilities.contains(capability) {
+                    errors.push(PipelineError::MissingCapability {
+                        stage: stage.name.clone(),
+                        capability: capability.clone(),
+                    });
+                }
+            }
+
+            for &resource in &stage.releases {
+                match open_resources.iter().rposition(|(r, _)| *r == resource) {
+                    Some(index) => {
+                        open_resources.remove(index);
+                    }
+                    None => errors.push(PipelineError::ReleaseWithoutAcquire {
+                        stage: stage.name.clone(),
+                        resource,
+                    }),
+                }
+            }
+            for &resource in &stage.acquires {
+                open_resources.push((resource, stage.name.as_str()));
+            }
+        }
+
+        let output = TypeInfo::of::<O>();
+        if current.id != output.id {
+            errors.push(PipelineError::OutputMismatch {
+                expected: output.name,
+                found: current.name,
+            });
+        }
+
+        for (resource, stage) in open_resources {
+            errors.push(PipelineError::UnreleasedResource {
+                stage: stage.to_string(),
+                resource,
+            });
+        }
+
+        if errors.is_empty() {
+            Validation::success(())
+        } else {
+            Validation::failure(errors)
+        }
+    }
+
+    /// Validate the pipeline and, if valid, produce a runnable pipeline.
+    pub fn build(self) -> Validation<CompiledPipeline<I, O, E, Env>, Vec<PipelineError>> {
+        self.validate().map(|()| CompiledPipeline {
+            stages: self
+                .stages
+                .into_iter()
+                .map(|stage| (stage.name, stage.run))
+                .collect(),
+            _marker: PhantomData,
+        })
+    }
+}
+
+/// A pipeline that passed pre-flight validation.
+///
+/// Created by [`Pipeline::build`]. A compiled pipeline can be run any
+/// number of times; each call to [`effect`](CompiledPipeline::effect)
+/// produces a fresh effect for one input.
+pub struct CompiledPipeline<I, O, E, Env> {
+    stages: Vec<(String, StageFn<E, Env>)>,
+    _marker: PhantomData<fn(I) -> O>,
+}
+
+impl<I, O, E, Env> std::fmt::Debug for CompiledPipeline<I, O, E, Env> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CompiledPipeline")
+            .field("stages", &self.stage_names().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl<I, O, E, Env> CompiledPipeline<I, O, E, Env> {
+    /// The stage names in execution order.
+    pub fn stage_names(&self) -> impl Iterator<Item = &str> {
+        self.stages.iter().map(|(name, _)| name.as_str())
+    }
+}
+
+impl<I, O, E, Env> CompiledPipeline<I, O, E, Env>
+where
+    I: Send + 'static,
+    O: Send + 'static,
+    E: Send + 'static,
+    Env: Send + Sync + 'static,
+{
+    /// Create an effect that runs every stage in order on the given input.
+    pub fn effect(&self, input: I) -> BoxedEffect<O, E, Env> {
+        let mut effect = pure::<StageValue, E, Env>(Box::new(input)).boxed();
+        for (_, run) in &self.stages {
+            let run = run.clone();
+            effect = effect.and_then(move |value| run(value));
+        }
+        effect.map(|value| {
+            *value
+                .downcast::<O>()
+                .expect("pipeline validation guarantees the output type")
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests;
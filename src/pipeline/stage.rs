@@ -0,0 +1,169 @@
+//! Named, type-erased pipeline stages.
+
+use std::any::{Any, TypeId};
+use std::sync::Arc;
+
+use crate::effect::boxed::BoxedEffect;
+use crate::effect::ext::EffectExt;
+use crate::effect::resource::ResourceKind;
+use crate::effect::Effect;
+
+/// A type-erased value flowing between stages.
+pub(crate) type StageValue = Box<dyn Any + Send>;
+
+/// A type-erased stage body.
+pub(crate) type StageFn<E, Env> =
+    Arc<dyn Fn(StageValue) -> BoxedEffect<StageValue, E, Env> + Send + Sync>;
+
+/// Runtime description of a stage's input or output type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct TypeInfo {
+    pub(crate) id: TypeId,
+    pub(crate) name: &'static str,
+}
+
+impl TypeInfo {
+    pub(crate) fn of<T: 'static>() -> Self {
+        TypeInfo {
+            id: TypeId::of::<T>(),
+            name: std::any::type_name::<T>(),
+        }
+    }
+}
+
+/// A named step in a [`Pipeline`](super::Pipeline).
+///
+/// A stage wraps a function from an input value to an effect, along with
+/// metadata used by pre-flight validation: its input and output types, the
+/// environment capabilities it requires, and the resources it acquires and
+/// releases.
+///
+/// # Example
+///
+/// ```rust
+/// use stillwater::effect::prelude::*;
+/// use stillwater::effect::resource::DbRes;
+/// use stillwater::pipeline::Stage;
+///
+/// let stage = Stage::<String, ()>::new("parse", |line: String| {
+///     from_result(line.trim().parse::<i64>().map_err(|e| e.to_string()))
+/// })
+/// .requires("parser")
+/// .acquires::<DbRes>();
+///
+/// assert_eq!(stage.name(), "parse");
+/// assert_eq!(stage.output_type(), "i64");
+/// ```
+pub struct Stage<E, Env> {
+    pub(crate) name: String,
+    pub(crate) input: TypeInfo,
+    pub(crate) output: TypeInfo,
+    pub(crate) requires: Vec<String>,
+    pub(crate) acquires: Vec<&'static str>,
+    pub(crate) releases: Vec<&'static str>,
+    pub(crate) run: StageFn<E, Env>,
+}
+
+impl<E, Env> std::fmt::Debug for Stage<E, Env> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Stage")
+            .field("name", &self.name)
+            .field("input", &self.input.name)
+            .field("output", &self.output.name)
+            .field("requires", &self.requires)
+            .field("acquires", &self.acquires)
+            .field("releases", &self.releases)
+            .finish()
+    }
+}
+
+impl<E, Env> Clone for Stage<E, Env> {
+    fn clone(&self) -> Self {
+        Stage {
+            name: self.name.clone(),
+            input: self.input,
+            output: self.output,
+            requires: self.requires.clone(),
+            acquires: self.acquires.clone(),
+            releases: self.releases.clone(),
+            run: Arc::clone(&self.run),
+        }
+    }
+}
+
+impl<E, Env> Stage<E, Env>
+where
+    E: Send + 'static,
+    Env: Send + Sync + 'static,
+{
+    /// Create a stage from a function producing an effect.
+    ///
+    /// The function is called once per pipeline run with the previous
+    /// stage's output (or the pipeline input for the first stage).
+    pub fn new<I, O, Eff, F>(name: impl Into<String>, f: F) -> Self
+    where
+        I: Send + 'static,
+        O: Send + 'static,
+        Eff: Effect<Output = O, Error = E, Env = Env> + 'static,
+        F: Fn(I) -> Eff + Send + Sync + 'static,
+    {
+        let run: StageFn<E, Env> = Arc::new(move |value: StageValue| {
+            let input = *value
+                .downcast::<I>()
+                .expect("pipeline validation guarantees stage input types");
+            f(input)
+                .boxed()
+                .map(|output| Box::new(output) as StageValue)
+        });
+
+        Stage {
+            name: name.into(),
+            input: TypeInfo::of::<I>(),
+            output: TypeInfo::of::<O>(),
+            requires: Vec::new(),
+            acquires: Vec::new(),
+            releases: Vec::new(),
+            run,
+        }
+    }
+}
+
+impl<E, Env> Stage<E, Env> {
+    /// Declare an environment capability this stage needs.
+    pub fn requires(mut self, capability: impl Into<String>) -> Self {
+        self.requires.push(capability.into());
+        self
+    }
+
+    /// Declare that this stage acquires a resource of kind `R`.
+    pub fn acquires<R: ResourceKind>(mut self) -> Self {
+        self.acquires.push(R::NAME);
+        self
+    }
+
+    /// Declare that this stage releases a resource of kind `R`.
+    pub fn releases<R: ResourceKind>(mut self) -> Self {
+        self.releases.push(R::NAME);
+        self
+    }
+
+    /// The stage name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The name of the type this stage accepts.
+    pub fn input_type(&self) -> &'static str {
+        self.input.name
+    }
+
+    /// The name of the type this stage produces.
+    pub fn output_type(&self) -> &'static str {
+        self.output.name
+    }
+
+    /// The capabilities this stage requires.
+    pub fn required_capabilities(&self) -> &[String] {
+        &self.requires
+    }
+}
@@ -0,0 +1,186 @@
+use super::*;
+use crate::effect::constructors::{asks, fail, from_result};
+use crate::effect::resource::{DbRes, FileRes};
+
+fn parse_stage() -> Stage<String, ()> {
+    Stage::new("parse", |line: String| {
+        from_result(line.trim().parse::<i64>().map_err(|e| e.to_string()))
+    })
+}
+
+#[test]
+fn test_valid_pipeline_passes_validation() {
+    let pipeline = Pipeline::<String, i64, String, ()>::new()
+        .stage(parse_stage())
+        .then("double", |n: i64| pure(n * 2));
+
+    assert!(pipeline.validate().is_success());
+}
+
+#[test]
+fn test_empty_pipeline_is_identity_only() {
+    let identity = Pipeline::<i32, i32, String, ()>::new();
+    assert!(identity.validate().is_success());
+
+    let mismatched = Pipeline::<i32, String, String, ()>::new();
+    assert_eq!(
+        mismatched.validate().unwrap_err(),
+        vec![PipelineError::OutputMismatch {
+            expected: "alloc::string::String",
+            found: "i32",
+        }]
+    );
+}
+
+#[test]
+fn test_detects_stage_type_mismatch() {
+    let pipeline = Pipeline::<String, i64, String, ()>::new()
+        .stage(parse_stage())
+        .then("narrow", |n: i32| pure(n as i64));
+
+    assert_eq!(
+        pipeline.validate().unwrap_err(),
+        vec![PipelineError::TypeMismatch {
+            stage: "narrow".to_string(),
+            expected: "i64",
+            found: "i32",
+        }]
+    );
+}
+
+#[test]
+fn test_detects_duplicate_stage_names() {
+    let pipeline = Pipeline::<String, i64, String, ()>::new()
+        .stage(parse_stage())
+        .then("parse", |n: i64| pure(n));
+
+    assert_eq!(
+        pipeline.validate().unwrap_err(),
+        vec![PipelineError::DuplicateStage {
+            name: "parse".to_string()
+        }]
+    );
+}
+
+#[test]
+fn test_detects_missing_capability() {
+    let pipeline = Pipeline::<String, i64, String, ()>::new()
+        .provides("parser")
+        .stage(parse_stage().requires("parser").requires("db"));
+
+    assert_eq!(
+        pipeline.validate().unwrap_err(),
+        vec![PipelineError::MissingCapability {
+            stage: "parse".to_string(),
+            capability: "db".to_string(),
+        }]
+    );
+}
+
+#[test]
+fn test_balanced_resources_pass() {
+    let pipeline = Pipeline::<i32, i32, String, ()>::new()
+        .stage(Stage::new("open", |n: i32| pure(n)).acquires::<FileRes>())
+        .stage(Stage::new("close", |n: i32| pure(n)).releases::<FileRes>());
+
+    assert!(pipeline.validate().is_success());
+}
+
+#[test]
+fn test_detects_resource_protocol_violations() {
+    let pipeline = Pipeline::<i32, i32, String, ()>::new()
+        .stage(Stage::new("commit", |n: i32| pure(n)).releases::<DbRes>())
+        .stage(Stage::new("open", |n: i32| pure(n)).acquires::<FileRes>());
+
+    assert_eq!(
+        pipeline.validate().unwrap_err(),
+        vec![
+            PipelineError::ReleaseWithoutAcquire {
+                stage: "commit".to_string(),
+                resource: "Database",
+            },
+            PipelineError::UnreleasedResource {
+                stage: "open".to_string(),
+                resource: "File",
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_accumulates_all_errors() {
+    let pipeline = Pipeline::<String, String, String, ()>::new()
+        .stage(parse_stage().requires("parser"))
+        .then("parse", |s: String| pure(s));
+
+    // Missing capability, duplicate name, and type mismatch are all reported
+    let errors = pipeline.validate().unwrap_err();
+    assert_eq!(errors.len(), 3);
+}
+
+#[test]
+fn test_build_fails_on_invalid_pipeline() {
+    let pipeline = Pipeline::<String, String, String, ()>::new().stage(parse_stage());
+    assert!(pipeline.build().is_failure());
+}
+
+#[tokio::test]
+async fn test_compiled_pipeline_runs_stages_in_order() {
+    let compiled = Pipeline::<String, String, String, ()>::new()
+        .stage(parse_stage())
+        .then("double", |n: i64| pure(n * 2))
+        .then("render", |n: i64| pure(format!("result={}", n)))
+        .build()
+        .into_result()
+        .unwrap();
+
+    assert_eq!(
+        compiled.stage_names().collect::<Vec<_>>(),
+        vec!["parse", "double", "render"]
+    );
+    assert_eq!(
+        compiled.effect("21".to_string()).execute(&()).await,
+        Ok("result=42".to_string())
+    );
+    // Compiled pipelines are reusable
+    assert_eq!(
+        compiled.effect("5".to_string()).execute(&()).await,
+        Ok("result=10".to_string())
+    );
+}
+
+#[tokio::test]
+async fn test_compiled_pipeline_short_circuits_on_stage_error() {
+    let compiled = Pipeline::<String, i64, String, ()>::new()
+        .stage(parse_stage())
+        .then("reject", |_: i64| {
+            fail::<i64, _, ()>("rejected".to_string())
+        })
+        .build()
+        .into_result()
+        .unwrap();
+
+    assert_eq!(
+        compiled.effect("1".to_string()).execute(&()).await,
+        Err("rejected".to_string())
+    );
+}
+
+#[tokio::test]
+async fn test_compiled_pipeline_uses_environment() {
+    #[derive(Clone)]
+    struct Env {
+        offset: i64,
+    }
+
+    let compiled = Pipeline::<i64, i64, String, Env>::new()
+        .then("offset", |n: i64| asks(move |env: &Env| n + env.offset))
+        .build()
+        .into_result()
+        .unwrap();
+
+    assert_eq!(
+        compiled.effect(1).execute(&Env { offset: 41 }).await,
+        Ok(42)
+    );
+}
@@ -2,7 +2,13 @@
 //!
 //! This module provides common predicates for collection validation.
 
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Debug;
+
 use super::combinators::Predicate;
+use super::explain::Explanation;
 
 /// Predicate that checks if a collection is empty.
 #[derive(Clone, Copy, Default, Debug)]
@@ -13,6 +19,13 @@ impl<T> Predicate<Vec<T>> for IsEmpty {
     fn check(&self, value: &Vec<T>) -> bool {
         value.is_empty()
     }
+
+    fn explain(&self, value: &Vec<T>) -> Explanation
+    where
+        Vec<T>: Debug,
+    {
+        explain_len("is_empty", self.check(value), value.len())
+    }
 }
 
 impl<T> Predicate<[T]> for IsEmpty {
@@ -20,6 +33,13 @@ impl<T> Predicate<[T]> for IsEmpty {
     fn check(&self, value: &[T]) -> bool {
         value.is_empty()
     }
+
+    fn explain(&self, value: &[T]) -> Explanation
+    where
+        [T]: Debug,
+    {
+        explain_len("is_empty", self.check(value), value.len())
+    }
 }
 
 /// Create a predicate that checks if a collection is empty.
@@ -45,6 +65,13 @@ impl<T> Predicate<Vec<T>> for IsNotEmpty {
     fn check(&self, value: &Vec<T>) -> bool {
         !value.is_empty()
     }
+
+    fn explain(&self, value: &Vec<T>) -> Explanation
+    where
+        Vec<T>: Debug,
+    {
+        explain_len("is_not_empty", self.check(value), value.len())
+    }
 }
 
 impl<T> Predicate<[T]> for IsNotEmpty {
@@ -52,6 +79,13 @@ impl<T> Predicate<[T]> for IsNotEmpty {
     fn check(&self, value: &[T]) -> bool {
         !value.is_empty()
     }
+
+    fn explain(&self, value: &[T]) -> Explanation
+    where
+        [T]: Debug,
+    {
+        explain_len("is_not_empty", self.check(value), value.len())
+    }
 }
 
 /// Create a predicate that checks if a collection is not empty.
@@ -79,6 +113,17 @@ impl<T> Predicate<Vec<T>> for HasLen {
     fn check(&self, value: &Vec<T>) -> bool {
         value.len() == self.expected
     }
+
+    fn explain(&self, value: &Vec<T>) -> Explanation
+    where
+        Vec<T>: Debug,
+    {
+        explain_len(
+            format!("has_len({})", self.expected),
+            self.check(value),
+            value.len(),
+        )
+    }
 }
 
 impl<T> Predicate<[T]> for HasLen {
@@ -86,6 +131,17 @@ impl<T> Predicate<[T]> for HasLen {
     fn check(&self, value: &[T]) -> bool {
         value.len() == self.expected
     }
+
+    fn explain(&self, value: &[T]) -> Explanation
+    where
+        [T]: Debug,
+    {
+        explain_len(
+            format!("has_len({})", self.expected),
+            self.check(value),
+            value.len(),
+        )
+    }
 }
 
 /// Create a predicate that checks if collection has exact length.
@@ -113,6 +169,17 @@ impl<T> Predicate<Vec<T>> for HasMinLen {
     fn check(&self, value: &Vec<T>) -> bool {
         value.len() >= self.min
     }
+
+    fn explain(&self, value: &Vec<T>) -> Explanation
+    where
+        Vec<T>: Debug,
+    {
+        explain_len(
+            format!("has_min_len({})", self.min),
+            self.check(value),
+            value.len(),
+        )
+    }
 }
 
 impl<T> Predicate<[T]> for HasMinLen {
@@ -120,6 +187,17 @@ impl<T> Predicate<[T]> for HasMinLen {
     fn check(&self, value: &[T]) -> bool {
         value.len() >= self.min
     }
+
+    fn explain(&self, value: &[T]) -> Explanation
+    where
+        [T]: Debug,
+    {
+        explain_len(
+            format!("has_min_len({})", self.min),
+            self.check(value),
+            value.len(),
+        )
+    }
 }
 
 /// Create a predicate that checks if collection has at least min elements.
@@ -148,6 +226,17 @@ impl<T> Predicate<Vec<T>> for HasMaxLen {
     fn check(&self, value: &Vec<T>) -> bool {
         value.len() <= self.max
     }
+
+    fn explain(&self, value: &Vec<T>) -> Explanation
+    where
+        Vec<T>: Debug,
+    {
+        explain_len(
+            format!("has_max_len({})", self.max),
+            self.check(value),
+            value.len(),
+        )
+    }
 }
 
 impl<T> Predicate<[T]> for HasMaxLen {
@@ -155,6 +244,17 @@ impl<T> Predicate<[T]> for HasMaxLen {
     fn check(&self, value: &[T]) -> bool {
         value.len() <= self.max
     }
+
+    fn explain(&self, value: &[T]) -> Explanation
+    where
+        [T]: Debug,
+    {
+        explain_len(
+            format!("has_max_len({})", self.max),
+            self.check(value),
+            value.len(),
+        )
+    }
 }
 
 /// Create a predicate that checks if collection has at most max elements.
@@ -181,6 +281,13 @@ impl<T, P: Predicate<T>> Predicate<Vec<T>> for All<P> {
     fn check(&self, value: &Vec<T>) -> bool {
         value.iter().all(|item| self.0.check(item))
     }
+
+    fn explain(&self, value: &Vec<T>) -> Explanation
+    where
+        Vec<T>: Debug,
+    {
+        explain_elements("all", &self.0, value, true)
+    }
 }
 
 impl<T, P: Predicate<T>> Predicate<[T]> for All<P> {
@@ -188,6 +295,13 @@ impl<T, P: Predicate<T>> Predicate<[T]> for All<P> {
     fn check(&self, value: &[T]) -> bool {
         value.iter().all(|item| self.0.check(item))
     }
+
+    fn explain(&self, value: &[T]) -> Explanation
+    where
+        [T]: Debug,
+    {
+        explain_elements("all", &self.0, value, true)
+    }
 }
 
 /// Create a predicate that checks if all elements satisfy a condition.
@@ -213,6 +327,13 @@ impl<T, P: Predicate<T>> Predicate<Vec<T>> for Any<P> {
     fn check(&self, value: &Vec<T>) -> bool {
         value.iter().any(|item| self.0.check(item))
     }
+
+    fn explain(&self, value: &Vec<T>) -> Explanation
+    where
+        Vec<T>: Debug,
+    {
+        explain_elements("any", &self.0, value, false)
+    }
 }
 
 impl<T, P: Predicate<T>> Predicate<[T]> for Any<P> {
@@ -220,6 +341,13 @@ impl<T, P: Predicate<T>> Predicate<[T]> for Any<P> {
     fn check(&self, value: &[T]) -> bool {
         value.iter().any(|item| self.0.check(item))
     }
+
+    fn explain(&self, value: &[T]) -> Explanation
+    where
+        [T]: Debug,
+    {
+        explain_elements("any", &self.0, value, false)
+    }
 }
 
 /// Create a predicate that checks if any element satisfies a condition.
@@ -245,6 +373,13 @@ impl<T: PartialEq + Send + Sync> Predicate<Vec<T>> for ContainsElement<T> {
     fn check(&self, value: &Vec<T>) -> bool {
         value.contains(&self.0)
     }
+
+    fn explain(&self, value: &Vec<T>) -> Explanation
+    where
+        Vec<T>: Debug,
+    {
+        Explanation::leaf("contains_element", self.check(value)).with_actual(format!("{value:?}"))
+    }
 }
 
 impl<T: PartialEq + Send + Sync> Predicate<[T]> for ContainsElement<T> {
@@ -252,6 +387,13 @@ impl<T: PartialEq + Send + Sync> Predicate<[T]> for ContainsElement<T> {
     fn check(&self, value: &[T]) -> bool {
         value.contains(&self.0)
     }
+
+    fn explain(&self, value: &[T]) -> Explanation
+    where
+        [T]: Debug,
+    {
+        Explanation::leaf("contains_element", self.check(value)).with_actual(format!("{value:?}"))
+    }
 }
 
 /// Create a predicate that checks if collection contains a specific element.
@@ -268,6 +410,32 @@ pub fn contains_element<T: PartialEq + Send + Sync>(element: T) -> ContainsEleme
     ContainsElement(element)
 }
 
+fn explain_len(label: impl Into<String>, passed: bool, len: usize) -> Explanation {
+    Explanation::leaf(label, passed).with_actual(format!("len {len}"))
+}
+
+/// Explain `all`/`any` over elements, listing the indices of the elements
+/// that failed the inner predicate as children.
+fn explain_elements<T, P: Predicate<T>>(
+    label: &str,
+    predicate: &P,
+    items: &[T],
+    require_all: bool,
+) -> Explanation {
+    let failed: Vec<_> = items
+        .iter()
+        .enumerate()
+        .filter(|(_, item)| !predicate.check(item))
+        .map(|(index, _)| Explanation::leaf(format!("[{index}]"), false))
+        .collect();
+    let passed = if require_all {
+        failed.is_empty()
+    } else {
+        failed.len() < items.len()
+    };
+    Explanation::node(label, passed, failed).with_actual(format!("len {}", items.len()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -3,6 +3,14 @@
 //! This module provides the foundational `Predicate` trait and logical
 //! combinators for composing predicates.
 
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::Debug;
+
+use super::explain::{short_type_name, Explanation};
+
 /// A composable predicate over values of type T.
 ///
 /// Predicates can be combined using logical operators:
@@ -22,6 +30,30 @@
 pub trait Predicate<T: ?Sized>: Send + Sync {
     /// Check if the value satisfies this predicate.
     fn check(&self, value: &T) -> bool;
+
+    /// Explain which parts of this predicate passed or failed for `value`.
+    ///
+    /// Composite predicates evaluate every sub-predicate, without
+    /// short-circuiting, so the explanation shows all failing branches.
+    /// The default implementation produces a leaf labelled with the
+    /// predicate's type name.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use stillwater::predicate::*;
+    ///
+    /// let explanation = gt(0).and(lt(10)).explain(&12);
+    /// assert!(!explanation.passed);
+    /// assert_eq!(explanation.failures()[0].label, "lt(10)");
+    /// ```
+    fn explain(&self, value: &T) -> Explanation
+    where
+        T: Debug,
+    {
+        Explanation::leaf(short_type_name::<Self>(), self.check(value))
+            .with_actual(format!("{value:?}"))
+    }
 }
 
 // Blanket impl for closures
@@ -103,10 +135,55 @@ pub trait PredicateExt<T: ?Sized>: Predicate<T> + Sized {
     fn not(self) -> Not<Self> {
         Not(self)
     }
+
+    /// Box the predicate, erasing its type.
+    ///
+    /// Useful for storing predicates of different types together or
+    /// building predicates at runtime.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use stillwater::predicate::*;
+    ///
+    /// let checks: Vec<BoxedPredicate<i32>> = vec![gt(0).boxed(), lt(10).not().boxed()];
+    /// assert!(checks.iter().any(|p| p.check(&5)));
+    /// ```
+    fn boxed(self) -> BoxedPredicate<T>
+    where
+        Self: 'static,
+    {
+        BoxedPredicate(Box::new(self))
+    }
 }
 
 impl<T: ?Sized, P: Predicate<T>> PredicateExt<T> for P {}
 
+/// A type-erased predicate, created with [`PredicateExt::boxed`].
+pub struct BoxedPredicate<T: ?Sized>(Box<dyn Predicate<T>>);
+
+impl<T: ?Sized> Predicate<T> for BoxedPredicate<T> {
+    #[inline]
+    fn check(&self, value: &T) -> bool {
+        self.0.check(value)
+    }
+
+    fn explain(&self, value: &T) -> Explanation
+    where
+        T: Debug,
+    {
+        self.0.explain(value)
+    }
+}
+
+impl<T: ?Sized> core::fmt::Debug for BoxedPredicate<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("BoxedPredicate")
+            .field(&"<predicate>")
+            .finish()
+    }
+}
+
 /// AND combinator - both predicates must be true.
 #[derive(Clone, Copy, Debug)]
 pub struct And<P1, P2>(pub P1, pub P2);
@@ -116,6 +193,14 @@ impl<T: ?Sized, P1: Predicate<T>, P2: Predicate<T>> Predicate<T> for And<P1, P2>
     fn check(&self, value: &T) -> bool {
         self.0.check(value) && self.1.check(value)
     }
+
+    fn explain(&self, value: &T) -> Explanation
+    where
+        T: Debug,
+    {
+        let (left, right) = (self.0.explain(value), self.1.explain(value));
+        Explanation::node("and", left.passed && right.passed, vec![left, right])
+    }
 }
 
 // Send + Sync are auto-derived when P1 and P2 are Send + Sync
@@ -129,6 +214,14 @@ impl<T: ?Sized, P1: Predicate<T>, P2: Predicate<T>> Predicate<T> for Or<P1, P2>
     fn check(&self, value: &T) -> bool {
         self.0.check(value) || self.1.check(value)
     }
+
+    fn explain(&self, value: &T) -> Explanation
+    where
+        T: Debug,
+    {
+        let (left, right) = (self.0.explain(value), self.1.explain(value));
+        Explanation::node("or", left.passed || right.passed, vec![left, right])
+    }
 }
 
 // Send + Sync are auto-derived when P1 and P2 are Send + Sync
@@ -142,6 +235,14 @@ impl<T: ?Sized, P: Predicate<T>> Predicate<T> for Not<P> {
     fn check(&self, value: &T) -> bool {
         !self.0.check(value)
     }
+
+    fn explain(&self, value: &T) -> Explanation
+    where
+        T: Debug,
+    {
+        let inner = self.0.explain(value);
+        Explanation::node("not", !inner.passed, vec![inner])
+    }
 }
 
 // Send + Sync are auto-derived when P is Send + Sync
@@ -170,6 +271,14 @@ impl<T: ?Sized, P: Predicate<T>, const N: usize> Predicate<T> for AllOf<P, N> {
     fn check(&self, value: &T) -> bool {
         self.0.iter().all(|p| p.check(value))
     }
+
+    fn explain(&self, value: &T) -> Explanation
+    where
+        T: Debug,
+    {
+        let children: Vec<_> = self.0.iter().map(|p| p.explain(value)).collect();
+        Explanation::node("all_of", children.iter().all(|c| c.passed), children)
+    }
 }
 
 /// Create a predicate that checks if all given predicates are satisfied.
@@ -213,6 +322,14 @@ impl<T: ?Sized, P: Predicate<T>, const N: usize> Predicate<T> for AnyOf<P, N> {
     fn check(&self, value: &T) -> bool {
         self.0.iter().any(|p| p.check(value))
     }
+
+    fn explain(&self, value: &T) -> Explanation
+    where
+        T: Debug,
+    {
+        let children: Vec<_> = self.0.iter().map(|p| p.explain(value)).collect();
+        Explanation::node("any_of", children.iter().any(|c| c.passed), children)
+    }
 }
 
 /// Create a predicate that checks if any given predicate is satisfied.
@@ -253,6 +370,14 @@ impl<T: ?Sized, P: Predicate<T>, const N: usize> Predicate<T> for NoneOf<P, N> {
     fn check(&self, value: &T) -> bool {
         !self.0.iter().any(|p| p.check(value))
     }
+
+    fn explain(&self, value: &T) -> Explanation
+    where
+        T: Debug,
+    {
+        let children: Vec<_> = self.0.iter().map(|p| p.explain(value)).collect();
+        Explanation::node("none_of", !children.iter().any(|c| c.passed), children)
+    }
 }
 
 /// Create a predicate that checks if no given predicates are satisfied.
@@ -0,0 +1,564 @@
+//! Serializable predicates built at runtime (feature-gated).
+//!
+//! This module provides [`DynPredicate`], a data representation of a
+//! predicate tree that can be deserialized from configuration and compiled
+//! to the runtime predicate combinators, so validation rules can change
+//! without recompiling. Feature-gated behind `#[cfg(feature = "serde")]`.
+//!
+//! Compilation targets a concrete value type through [`DynTarget`]: string
+//! predicates compile for `str` and `String`, ranges for the primitive
+//! number types. Every problem in the tree is reported at once, with a path
+//! locating it.
+//!
+//! The `regex` predicate compiles only with the `regex` feature enabled;
+//! without it, compilation reports [`DynPredicateError::RegexDisabled`].
+//!
+//! # Example
+//!
+//! ```rust
+//! use stillwater::predicate::{DynPredicate, Predicate};
+//!
+//! let config: DynPredicate = serde_json::from_str(r#"{
+//!     "type": "and",
+//!     "predicates": [
+//!         { "type": "len", "min": 3, "max": 20 },
+//!         { "type": "not", "predicate": { "type": "contains", "value": " " } }
+//!     ]
+//! }"#).unwrap();
+//!
+//! let username = config.compile::<str>().into_result().unwrap();
+//! assert!(username.check("john_doe"));
+//! assert!(!username.check("john doe"));
+//! assert!(!username.check("jo"));
+//! ```
+
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use super::combinators::{And, BoxedPredicate, Not, Or, PredicateExt};
+use super::number::{ge, le};
+use super::string::{contains, ends_with, len_between, not_empty, starts_with};
+use crate::Validation;
+
+/// A serializable predicate tree.
+///
+/// Serialized with a `type` tag naming the predicate, for example
+/// `{ "type": "range", "min": 0, "max": 100 }`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DynPredicate {
+    /// The string is not empty.
+    NotEmpty,
+    /// The string's length in bytes is within the bounds (inclusive).
+    Len {
+        /// The minimum length, if any.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        min: Option<usize>,
+        /// The maximum length, if any.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max: Option<usize>,
+    },
+    /// The number is within the bounds (inclusive).
+    Range {
+        /// The lower bound, if any.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        min: Option<f64>,
+        /// The upper bound, if any.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max: Option<f64>,
+    },
+    /// The string starts with a prefix.
+    StartsWith {
+        /// The prefix.
+        value: String,
+    },
+    /// The string ends with a suffix.
+    EndsWith {
+        /// The suffix.
+        value: String,
+    },
+    /// The string contains a substring.
+    Contains {
+        /// The substring.
+        value: String,
+    },
+    /// The string matches a regular expression (requires the `regex` feature).
+    Regex {
+        /// The regular expression.
+        pattern: String,
+    },
+    /// Every predicate holds (true if empty).
+    And {
+        /// The predicates to combine.
+        predicates: Vec<DynPredicate>,
+    },
+    /// At least one predicate holds (false if empty).
+    Or {
+        /// The predicates to combine.
+        predicates: Vec<DynPredicate>,
+    },
+    /// The predicate does not hold.
+    Not {
+        /// The predicate to invert.
+        predicate: Box<DynPredicate>,
+    },
+}
+
+impl DynPredicate {
+    /// Compile to a runtime predicate over `T`, accumulating every error.
+    pub fn compile<T>(&self) -> Validation<BoxedPredicate<T>, Vec<DynPredicateError>>
+    where
+        T: DynTarget + ?Sized,
+    {
+        Validation::from_result(self.compile_at("$"))
+    }
+
+    fn compile_at<T>(&self, path: &str) -> Result<BoxedPredicate<T>, Vec<DynPredicateError>>
+    where
+        T: DynTarget + ?Sized,
+    {
+        match self {
+            DynPredicate::And { predicates } => {
+                let init = (|_: &T| true).boxed();
+                compile_all(predicates, path, "and", init, |acc, p| And(acc, p).boxed())
+            }
+            DynPredicate::Or { predicates } => {
+                let init = (|_: &T| false).boxed();
+                compile_all(predicates, path, "or", init, |acc, p| Or(acc, p).boxed())
+            }
+            DynPredicate::Not { predicate } => predicate
+                .compile_at(&format!("{path}.not"))
+                .map(|p| Not(p).boxed()),
+            leaf => T::compile_leaf(leaf, path).map_err(|e| vec![e]),
+        }
+    }
+
+    /// The predicate's `type` tag, as used in errors.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            DynPredicate::NotEmpty => "not_empty",
+            DynPredicate::Len { .. } => "len",
+            DynPredicate::Range { .. } => "range",
+            DynPredicate::StartsWith { .. } => "starts_with",
+            DynPredicate::EndsWith { .. } => "ends_with",
+            DynPredicate::Contains { .. } => "contains",
+            DynPredicate::Regex { .. } => "regex",
+            DynPredicate::And { .. } => "and",
+            DynPredicate::Or { .. } => "or",
+            DynPredicate::Not { .. } => "not",
+        }
+    }
+}
+
+fn compile_all<T, F>(
+    predicates: &[DynPredicate],
+    path: &str,
+    kind: &str,
+    init: BoxedPredicate<T>,
+    combine: F,
+) -> Result<BoxedPredicate<T>, Vec<DynPredicateError>>
+where
+    T: DynTarget + ?Sized,
+    F: Fn(BoxedPredicate<T>, BoxedPredicate<T>) -> BoxedPredicate<T>,
+{
+    let mut errors = Vec::new();
+    let mut compiled = Vec::with_capacity(predicates.len());
+
+    for (index, predicate) in predicates.iter().enumerate() {
+        match predicate.compile_at(&format!("{path}.{kind}[{index}]")) {
+            Ok(p) => compiled.push(p),
+            Err(e) => errors.extend(e),
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+    let mut compiled = compiled.into_iter();
+    Ok(match compiled.next() {
+        Some(first) => compiled.fold(first, combine),
+        None => init,
+    })
+}
+
+/// An error compiling a [`DynPredicate`].
+///
+/// Each variant carries the path of the offending predicate, such as
+/// `$.and[1].not`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DynPredicateError {
+    /// The predicate doesn't apply to the target type.
+    Unsupported {
+        /// Where the predicate appears in the tree.
+        path: String,
+        /// The predicate's `type` tag.
+        predicate: &'static str,
+        /// The target type name.
+        target: &'static str,
+    },
+    /// The predicate's bounds are invalid for the target type.
+    InvalidBounds {
+        /// Where the predicate appears in the tree.
+        path: String,
+        /// What is wrong with the bounds.
+        message: String,
+    },
+    /// The regular expression failed to compile.
+    InvalidRegex {
+        /// Where the predicate appears in the tree.
+        path: String,
+        /// The regex compiler's error message.
+        message: String,
+    },
+    /// A `regex` predicate was used without the `regex` feature.
+    RegexDisabled {
+        /// Where the predicate appears in the tree.
+        path: String,
+    },
+}
+
+impl fmt::Display for DynPredicateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DynPredicateError::Unsupported {
+                path,
+                predicate,
+                target,
+            } => write!(
+                f,
+                "{}: predicate '{}' does not apply to {}",
+                path, predicate, target
+            ),
+            DynPredicateError::InvalidBounds { path, message } => {
+                write!(f, "{}: invalid bounds: {}", path, message)
+            }
+            DynPredicateError::InvalidRegex { path, message } => {
+                write!(f, "{}: invalid regex: {}", path, message)
+            }
+            DynPredicateError::RegexDisabled { path } => {
+                write!(f, "{}: regex predicates require the 'regex' feature", path)
+            }
+        }
+    }
+}
+
+impl core::error::Error for DynPredicateError {}
+
+/// A value type that [`DynPredicate`]s can be compiled for.
+///
+/// Implemented for `str`, `String`, and the primitive number types.
+pub trait DynTarget: 'static {
+    /// The type name used in errors.
+    const NAME: &'static str;
+
+    /// Compile a leaf predicate (anything but `and`, `or`, and `not`).
+    fn compile_leaf(
+        predicate: &DynPredicate,
+        path: &str,
+    ) -> Result<BoxedPredicate<Self>, DynPredicateError>;
+}
+
+fn unsupported<T: DynTarget + ?Sized>(predicate: &DynPredicate, path: &str) -> DynPredicateError {
+    DynPredicateError::Unsupported {
+        path: path.to_string(),
+        predicate: predicate.kind(),
+        target: T::NAME,
+    }
+}
+
+fn invalid_bounds(path: &str, message: impl Into<String>) -> DynPredicateError {
+    DynPredicateError::InvalidBounds {
+        path: path.to_string(),
+        message: message.into(),
+    }
+}
+
+macro_rules! impl_string_target {
+    ($ty:ty, $name:literal) => {
+        impl DynTarget for $ty {
+            const NAME: &'static str = $name;
+
+            fn compile_leaf(
+                predicate: &DynPredicate,
+                path: &str,
+            ) -> Result<BoxedPredicate<Self>, DynPredicateError> {
+                match predicate {
+                    DynPredicate::NotEmpty => Ok(not_empty().boxed()),
+                    DynPredicate::Len { min, max } => {
+                        let (min, max) = (min.unwrap_or(0), max.unwrap_or(usize::MAX));
+                        if min > max {
+                            return Err(invalid_bounds(
+                                path,
+                                format!("min {} is greater than max {}", min, max),
+                            ));
+                        }
+                        Ok(len_between(min, max).boxed())
+                    }
+                    DynPredicate::StartsWith { value } => Ok(starts_with(value.clone()).boxed()),
+                    DynPredicate::EndsWith { value } => Ok(ends_with(value.clone()).boxed()),
+                    DynPredicate::Contains { value } => Ok(contains(value.clone()).boxed()),
+                    DynPredicate::Regex { pattern } => compile_regex(pattern, path),
+                    other => Err(unsupported::<Self>(other, path)),
+                }
+            }
+        }
+    };
+}
+
+impl_string_target!(str, "str");
+impl_string_target!(String, "String");
+
+#[cfg(feature = "regex")]
+fn compile_regex<T>(pattern: &str, path: &str) -> Result<BoxedPredicate<T>, DynPredicateError>
+where
+    T: ?Sized + 'static,
+    super::string::Matches: super::Predicate<T>,
+{
+    super::string::matches(pattern)
+        .map(|p| p.boxed())
+        .map_err(|e| DynPredicateError::InvalidRegex {
+            path: path.to_string(),
+            message: e.to_string(),
+        })
+}
+
+#[cfg(not(feature = "regex"))]
+fn compile_regex<T: ?Sized>(
+    _pattern: &str,
+    path: &str,
+) -> Result<BoxedPredicate<T>, DynPredicateError> {
+    Err(DynPredicateError::RegexDisabled {
+        path: path.to_string(),
+    })
+}
+
+/// Compile `range` bounds for a number type, rejecting bounds that are NaN,
+/// out of range, or (for integers) fractional.
+fn range_bounds<N>(
+    min: Option<f64>,
+    max: Option<f64>,
+    path: &str,
+    convert: impl Fn(f64) -> Option<N>,
+) -> Result<(Option<N>, Option<N>), DynPredicateError> {
+    let bound = |value: Option<f64>, which: &str| match value {
+        None => Ok(None),
+        Some(v) => convert(v)
+            .map(Some)
+            .ok_or_else(|| invalid_bounds(path, format!("{} {} is not representable", which, v))),
+    };
+    if let (Some(lo), Some(hi)) = (min, max) {
+        if lo > hi {
+            return Err(invalid_bounds(
+                path,
+                format!("min {} is greater than max {}", lo, hi),
+            ));
+        }
+    }
+    Ok((bound(min, "min")?, bound(max, "max")?))
+}
+
+fn range_predicate<N>(min: Option<N>, max: Option<N>) -> BoxedPredicate<N>
+where
+    N: PartialOrd + Send + Sync + 'static,
+{
+    match (min, max) {
+        (Some(lo), Some(hi)) => And(ge(lo), le(hi)).boxed(),
+        (Some(lo), None) => ge(lo).boxed(),
+        (None, Some(hi)) => le(hi).boxed(),
+        (None, None) => (|_: &N| true).boxed(),
+    }
+}
+
+macro_rules! impl_number_target {
+    ($($ty:ty => $convert:expr),* $(,)?) => {
+        $(
+            impl DynTarget for $ty {
+                const NAME: &'static str = stringify!($ty);
+
+                fn compile_leaf(
+                    predicate: &DynPredicate,
+                    path: &str,
+                ) -> Result<BoxedPredicate<Self>, DynPredicateError> {
+                    match predicate {
+                        DynPredicate::Range { min, max } => {
+                            let (min, max) = range_bounds(*min, *max, path, $convert)?;
+                            Ok(range_predicate(min, max))
+                        }
+                        other => Err(unsupported::<Self>(other, path)),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+// The saturating cast round-trips only for whole numbers within range
+// (`f64::fract` is not available without `std`).
+macro_rules! integer_bound {
+    ($ty:ty) => {
+        |v: f64| (v as $ty as f64 == v).then_some(v as $ty)
+    };
+}
+
+impl_number_target!(
+    i8 => integer_bound!(i8),
+    i16 => integer_bound!(i16),
+    i32 => integer_bound!(i32),
+    i64 => integer_bound!(i64),
+    u8 => integer_bound!(u8),
+    u16 => integer_bound!(u16),
+    u32 => integer_bound!(u32),
+    u64 => integer_bound!(u64),
+    usize => integer_bound!(usize),
+    f32 => |v: f64| (!v.is_nan()).then_some(v as f32),
+    f64 => |v: f64| (!v.is_nan()).then_some(v),
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::predicate::Predicate;
+
+    fn parse(json: &str) -> DynPredicate {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn test_string_tree_compiles_and_checks() {
+        let p = parse(
+            r#"{ "type": "or", "predicates": [
+                { "type": "starts_with", "value": "admin_" },
+                { "type": "and", "predicates": [
+                    { "type": "not_empty" },
+                    { "type": "len", "max": 4 }
+                ]}
+            ]}"#,
+        );
+        let compiled = p.compile::<String>().into_result().unwrap();
+        assert!(compiled.check(&"admin_root".to_string()));
+        assert!(compiled.check(&"bob".to_string()));
+        assert!(!compiled.check(&"".to_string()));
+        assert!(!compiled.check(&"robert".to_string()));
+    }
+
+    #[test]
+    fn test_number_range() {
+        let p = parse(r#"{ "type": "range", "min": 0, "max": 150 }"#);
+        let ages = p.compile::<i32>().into_result().unwrap();
+        assert!(ages.check(&0));
+        assert!(ages.check(&150));
+        assert!(!ages.check(&151));
+
+        let scores = parse(r#"{ "type": "range", "min": 0.5 }"#)
+            .compile::<f64>()
+            .into_result()
+            .unwrap();
+        assert!(scores.check(&0.5));
+        assert!(!scores.check(&0.25));
+    }
+
+    #[test]
+    fn test_empty_and_or() {
+        let always = parse(r#"{ "type": "and", "predicates": [] }"#);
+        let never = parse(r#"{ "type": "or", "predicates": [] }"#);
+        assert!(always.compile::<u8>().into_result().unwrap().check(&7));
+        assert!(!never.compile::<u8>().into_result().unwrap().check(&7));
+    }
+
+    #[test]
+    fn test_errors_accumulate_with_paths() {
+        let p = parse(
+            r#"{ "type": "and", "predicates": [
+                { "type": "len", "min": 5, "max": 2 },
+                { "type": "not", "predicate": { "type": "range", "min": 1 } }
+            ]}"#,
+        );
+        let errors = p.compile::<str>().into_result().unwrap_err();
+        assert_eq!(
+            errors,
+            vec![
+                DynPredicateError::InvalidBounds {
+                    path: "$.and[0]".to_string(),
+                    message: "min 5 is greater than max 2".to_string(),
+                },
+                DynPredicateError::Unsupported {
+                    path: "$.and[1].not".to_string(),
+                    predicate: "range",
+                    target: "str",
+                },
+            ]
+        );
+        assert_eq!(
+            errors[1].to_string(),
+            "$.and[1].not: predicate 'range' does not apply to str"
+        );
+    }
+
+    #[test]
+    fn test_integer_bounds_must_be_representable() {
+        let fractional = parse(r#"{ "type": "range", "max": 2.5 }"#);
+        assert!(fractional.compile::<i64>().is_failure());
+        assert!(fractional.compile::<f32>().is_success());
+
+        let negative = parse(r#"{ "type": "range", "min": -1 }"#);
+        assert!(negative.compile::<u32>().is_failure());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let p = DynPredicate::Not {
+            predicate: Box::new(DynPredicate::Len {
+                min: None,
+                max: Some(3),
+            }),
+        };
+        let json = serde_json::to_value(&p).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({ "type": "not", "predicate": { "type": "len", "max": 3 } })
+        );
+        assert_eq!(serde_json::from_value::<DynPredicate>(json).unwrap(), p);
+    }
+
+    #[test]
+    fn test_explain_compiled_predicate() {
+        let p = parse(
+            r#"{ "type": "and", "predicates": [{ "type": "range", "min": 1 }, { "type": "range", "max": 9 }] }"#,
+        );
+        let explanation = p.compile::<i32>().into_result().unwrap().explain(&10);
+        assert_eq!(explanation.failures()[0].label, "le(9)");
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn test_regex() {
+        let p = parse(r#"{ "type": "regex", "pattern": "^[0-9]{5}$" }"#);
+        let zip = p.compile::<str>().into_result().unwrap();
+        assert!(zip.check("12345"));
+        assert!(!zip.check("1234"));
+
+        let bad = parse(r#"{ "type": "regex", "pattern": "(" }"#);
+        assert!(matches!(
+            bad.compile::<str>().into_result().unwrap_err()[0],
+            DynPredicateError::InvalidRegex { .. }
+        ));
+    }
+
+    #[cfg(not(feature = "regex"))]
+    #[test]
+    fn test_regex_requires_feature() {
+        let p = parse(r#"{ "type": "regex", "pattern": "a" }"#);
+        assert_eq!(
+            p.compile::<str>().into_result().unwrap_err(),
+            vec![DynPredicateError::RegexDisabled {
+                path: "$".to_string()
+            }]
+        );
+    }
+}
@@ -0,0 +1,204 @@
+//! Predicate explanations
+//!
+//! This module provides [`Explanation`], a tree recording which parts of a
+//! composite predicate passed or failed for a given value, produced by
+//! [`Predicate::explain`](super::Predicate::explain).
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+/// A tree describing how a predicate evaluated against a value.
+///
+/// Composite predicates (`and`, `or`, `not`, `all_of`, ...) produce a node
+/// with one child per sub-predicate; built-in leaf predicates record the
+/// value they were checked against.
+///
+/// # Example
+///
+/// ```rust
+/// use stillwater::predicate::*;
+///
+/// let age = ge(0).and(le(150));
+/// let explanation = age.explain(&200);
+///
+/// assert!(!explanation.passed);
+/// assert_eq!(
+///     explanation.render(),
+///     "✗ and\n  ✓ ge(0) (actual: 200)\n  ✗ le(150) (actual: 200)\n"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Explanation {
+    /// A short description of the predicate, such as `gt(5)` or `and`.
+    pub label: String,
+    /// Whether the predicate was satisfied.
+    pub passed: bool,
+    /// The value the predicate was checked against, if recorded.
+    pub actual: Option<String>,
+    /// Explanations of the sub-predicates, if any.
+    pub children: Vec<Explanation>,
+}
+
+impl Explanation {
+    /// Create an explanation with no children.
+    pub fn leaf(label: impl Into<String>, passed: bool) -> Self {
+        Explanation {
+            label: label.into(),
+            passed,
+            actual: None,
+            children: Vec::new(),
+        }
+    }
+
+    /// Create an explanation for a composite predicate.
+    pub fn node(label: impl Into<String>, passed: bool, children: Vec<Explanation>) -> Self {
+        Explanation {
+            label: label.into(),
+            passed,
+            actual: None,
+            children,
+        }
+    }
+
+    /// Record the value the predicate was checked against.
+    pub fn with_actual(mut self, actual: impl Into<String>) -> Self {
+        self.actual = Some(actual.into());
+        self
+    }
+
+    /// The failed nodes that explain the overall failure.
+    ///
+    /// These are the failed nodes none of whose children failed: the
+    /// leaves that rejected the value, or combinators like `not` that
+    /// failed because their children passed.
+    pub fn failures(&self) -> Vec<&Explanation> {
+        let mut failures = Vec::new();
+        self.collect_failures(&mut failures);
+        failures
+    }
+
+    fn collect_failures<'a>(&'a self, failures: &mut Vec<&'a Explanation>) {
+        if self.passed {
+            return;
+        }
+        let before = failures.len();
+        for child in &self.children {
+            child.collect_failures(failures);
+        }
+        if failures.len() == before {
+            failures.push(self);
+        }
+    }
+
+    /// Render the tree as indented, human-readable diagnostics.
+    ///
+    /// Each line is marked `✓` or `✗` and indented two spaces per level.
+    pub fn render(&self) -> String {
+        self.to_string()
+    }
+
+    fn render_into(&self, f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result {
+        let mark = if self.passed { '✓' } else { '✗' };
+        write!(f, "{:indent$}{mark} {}", "", self.label, indent = depth * 2)?;
+        if let Some(actual) = &self.actual {
+            write!(f, " (actual: {actual})")?;
+        }
+        writeln!(f)?;
+        for child in &self.children {
+            child.render_into(f, depth + 1)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.render_into(f, 0)
+    }
+}
+
+/// The unqualified name of a type, used to label predicates that don't
+/// describe themselves.
+pub(crate) fn short_type_name<T: ?Sized>() -> &'static str {
+    let name = core::any::type_name::<T>();
+    let base = name.split('<').next().unwrap_or(name);
+    match base.rsplit("::").next() {
+        Some("{{closure}}") | None => "predicate",
+        Some(short) => short,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::predicate::*;
+
+    #[test]
+    fn test_and_or_not_tree() {
+        let p = gt(0).and(lt(10).or(eq(42))).not();
+        let explanation = p.explain(&5);
+
+        assert!(!explanation.passed);
+        assert_eq!(
+            explanation.render(),
+            "✗ not\n  ✓ and\n    ✓ gt(0) (actual: 5)\n    ✓ or\n      ✓ lt(10) (actual: 5)\n      ✗ eq(42) (actual: 5)\n"
+        );
+    }
+
+    #[test]
+    fn test_failures_point_at_the_failing_branch() {
+        let p = And(len_min(3), starts_with("user_"));
+        let explanation = p.explain("ab");
+
+        let failures: Vec<_> = explanation
+            .failures()
+            .into_iter()
+            .map(|e| e.label.as_str())
+            .collect();
+        assert_eq!(failures, vec!["len_min(3)", "starts_with(\"user_\")"]);
+        assert_eq!(
+            explanation.children[0].actual.as_deref(),
+            Some("\"ab\" (len 2)")
+        );
+    }
+
+    #[test]
+    fn test_failures_of_not_is_the_not_node() {
+        let explanation = gt(0).not().explain(&1);
+        let failures = explanation.failures();
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].label, "not");
+    }
+
+    #[test]
+    fn test_explain_agrees_with_check() {
+        let p = any_of([eq(1), eq(5)]).and(none_of([eq(3)]));
+        for value in 0..7 {
+            assert_eq!(p.explain(&value).passed, p.check(&value));
+        }
+        assert_eq!(p.explain(&5).children[0].children.len(), 2);
+    }
+
+    #[test]
+    fn test_string_and_owned_string_explain_the_same() {
+        let p = And(not_empty(), len_max(3));
+        let owned = String::from("abcd");
+        assert_eq!(p.explain(&owned), p.explain("abcd"));
+    }
+
+    #[test]
+    fn test_collection_all_lists_failing_elements() {
+        let explanation = all(positive::<i32>()).explain(&vec![1, -2, 3, -4]);
+        assert!(!explanation.passed);
+        let labels: Vec<_> = explanation.children.iter().map(|c| &c.label[..]).collect();
+        assert_eq!(labels, vec!["[1]", "[3]"]);
+    }
+
+    #[test]
+    fn test_closure_predicates_get_a_generic_label() {
+        let is_even = |n: &i32| n % 2 == 0;
+        let explanation = is_even.explain(&3);
+        assert_eq!(explanation.label, "predicate");
+        assert_eq!(explanation.actual.as_deref(), Some("3"));
+    }
+}
@@ -41,26 +41,77 @@
 //!     .ensure(len_max(10), "too long");
 //! assert_eq!(result, Validation::success(String::from("hello")));
 //! ```
+//!
+//! # Explaining Failures
+//!
+//! [`Predicate::explain`] reports which branches of a composite predicate
+//! passed or failed, with the values they were checked against:
+//!
+//! ```rust
+//! use stillwater::predicate::*;
+//!
+//! let username = And(len_between(3, 20), starts_with("user_"));
+//! let explanation = username.explain("bob");
+//!
+//! assert_eq!(explanation.failures()[0].label, "starts_with(\"user_\")");
+//! println!("{}", explanation.render());
+//! // ✗ and
+//! //   ✓ len_between(3, 20) (actual: "bob" (len 3))
+//! //   ✗ starts_with("user_") (actual: "bob")
+//! ```
 
 mod collection;
 mod combinators;
+#[cfg(feature = "serde")]
+mod dynamic;
+mod explain;
+mod net;
 mod number;
 mod string;
+#[cfg(feature = "chrono")]
+mod time;
 mod validation;
 
 pub mod prelude;
 
 // Re-export core trait
-pub use combinators::{Predicate, PredicateExt};
+pub use combinators::{BoxedPredicate, Predicate, PredicateExt};
+
+// Re-export dynamic predicates
+#[cfg(feature = "serde")]
+pub use dynamic::{DynPredicate, DynPredicateError, DynTarget};
+
+// Re-export explanation type
+pub use explain::Explanation;
 
 // Re-export combinator types
 pub use combinators::{all_of, any_of, none_of, AllOf, And, AnyOf, NoneOf, Not, Or};
 
 // Re-export string predicates
 pub use string::{
-    all_chars, any_char, contains, ends_with, is_alphabetic, is_alphanumeric, is_ascii, is_numeric,
-    len_between, len_eq, len_max, len_min, not_empty, starts_with, AllChars, AnyChar, Contains,
-    EndsWith, LenBetween, NotEmpty, StartsWith,
+    all_chars, any_char, contains, contains_ci, ends_with, is_alphabetic, is_alphanumeric,
+    is_ascii, is_numeric, len_between, len_eq, len_max, len_min, not_empty, starts_with,
+    starts_with_ci, AllChars, AnyChar, Contains, ContainsCi, EndsWith, LenBetween, NotEmpty,
+    StartsWith, StartsWithCi,
+};
+#[cfg(feature = "unicode")]
+pub use string::{graphemes_max, is_normalized_nfc, GraphemesMax, IsNormalizedNfc};
+
+#[cfg(feature = "regex")]
+pub use string::{matches, Matches};
+
+// Re-export network and identifier predicates
+pub use net::{
+    valid_hostname, valid_ip_addr, valid_port, valid_uuid, ValidHostname, ValidIpAddr, ValidPort,
+    ValidUuid,
+};
+#[cfg(feature = "url")]
+pub use net::{valid_url, ValidUrl};
+
+// Re-export time predicates
+#[cfg(feature = "chrono")]
+pub use time::{
+    in_future, in_past, valid_rfc3339, within_days, InFuture, InPast, ValidRfc3339, WithinDays,
 };
 
 // Re-export number predicates
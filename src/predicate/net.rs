@@ -0,0 +1,259 @@
+//! Network and identifier format predicates
+//!
+//! This module provides predicates for validating common identifiers at
+//! service boundaries: UUIDs, IP addresses, hostnames, ports, and (with the
+//! `url` feature) URLs.
+//!
+//! # Example
+//!
+//! ```rust
+//! use stillwater::predicate::*;
+//!
+//! assert!(valid_uuid().check("67e55044-10b1-426f-9247-bb680e5fe0c8"));
+//! assert!(valid_ip_addr().check("192.168.0.1"));
+//! assert!(valid_hostname().check("api.example.com"));
+//! assert!(valid_port().check("8080"));
+//! ```
+
+use alloc::format;
+use alloc::string::String;
+use core::net::IpAddr;
+
+use super::combinators::Predicate;
+use super::explain::Explanation;
+
+macro_rules! string_predicate {
+    ($(#[$meta:meta])* $name:ident, $label:literal, $check:expr) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, Default, Debug)]
+        pub struct $name;
+
+        impl Predicate<str> for $name {
+            #[inline]
+            fn check(&self, value: &str) -> bool {
+                let check: fn(&str) -> bool = $check;
+                check(value)
+            }
+
+            fn explain(&self, value: &str) -> Explanation {
+                Explanation::leaf($label, self.check(value)).with_actual(format!("{value:?}"))
+            }
+        }
+
+        impl Predicate<String> for $name {
+            #[inline]
+            fn check(&self, value: &String) -> bool {
+                Predicate::<str>::check(self, value)
+            }
+
+            fn explain(&self, value: &String) -> Explanation {
+                Predicate::<str>::explain(self, value)
+            }
+        }
+    };
+}
+
+string_predicate!(
+    /// Predicate that checks if a string is a UUID.
+    ValidUuid,
+    "valid_uuid",
+    is_uuid
+);
+
+string_predicate!(
+    /// Predicate that checks if a string is an IPv4 or IPv6 address.
+    ValidIpAddr,
+    "valid_ip_addr",
+    |value| value.parse::<IpAddr>().is_ok()
+);
+
+string_predicate!(
+    /// Predicate that checks if a string is a valid hostname.
+    ValidHostname,
+    "valid_hostname",
+    is_hostname
+);
+
+string_predicate!(
+    /// Predicate that checks if a string is a TCP/UDP port number.
+    ValidPort,
+    "valid_port",
+    |value| value.parse::<u16>().is_ok_and(|port| port != 0)
+);
+
+#[cfg(feature = "url")]
+string_predicate!(
+    /// Predicate that checks if a string is an absolute URL.
+    ValidUrl,
+    "valid_url",
+    |value| url::Url::parse(value).is_ok()
+);
+
+/// Create a predicate that checks if a string is a UUID.
+///
+/// Accepts the hyphenated form (`67e55044-10b1-426f-9247-bb680e5fe0c8`) and
+/// the 32-digit simple form, in either case.
+///
+/// # Example
+///
+/// ```rust
+/// use stillwater::predicate::*;
+///
+/// assert!(valid_uuid().check("67E55044-10B1-426F-9247-BB680E5FE0C8"));
+/// assert!(valid_uuid().check("67e5504410b1426f9247bb680e5fe0c8"));
+/// assert!(!valid_uuid().check("67e55044-10b1-426f-9247"));
+/// ```
+pub fn valid_uuid() -> ValidUuid {
+    ValidUuid
+}
+
+/// Create a predicate that checks if a string is an IPv4 or IPv6 address.
+///
+/// # Example
+///
+/// ```rust
+/// use stillwater::predicate::*;
+///
+/// assert!(valid_ip_addr().check("10.0.0.1"));
+/// assert!(valid_ip_addr().check("::1"));
+/// assert!(!valid_ip_addr().check("256.0.0.1"));
+/// ```
+pub fn valid_ip_addr() -> ValidIpAddr {
+    ValidIpAddr
+}
+
+/// Create a predicate that checks if a string is a valid hostname.
+///
+/// Follows RFC 1123: at most 253 characters, dot-separated labels of 1 to
+/// 63 ASCII letters, digits, and hyphens, with no label starting or ending
+/// with a hyphen. A single trailing dot is allowed.
+///
+/// # Example
+///
+/// ```rust
+/// use stillwater::predicate::*;
+///
+/// assert!(valid_hostname().check("localhost"));
+/// assert!(valid_hostname().check("api-2.example.com."));
+/// assert!(!valid_hostname().check("-bad.example.com"));
+/// assert!(!valid_hostname().check("under_score.com"));
+/// ```
+pub fn valid_hostname() -> ValidHostname {
+    ValidHostname
+}
+
+/// Create a predicate that checks if a string is a port number from 1 to
+/// 65535.
+///
+/// # Example
+///
+/// ```rust
+/// use stillwater::predicate::*;
+///
+/// assert!(valid_port().check("443"));
+/// assert!(!valid_port().check("0"));
+/// assert!(!valid_port().check("70000"));
+/// ```
+pub fn valid_port() -> ValidPort {
+    ValidPort
+}
+
+/// Create a predicate that checks if a string is an absolute URL.
+///
+/// Uses the WHATWG URL parser from the `url` crate. Requires the `url`
+/// feature.
+///
+/// # Example
+///
+/// ```rust
+/// use stillwater::predicate::*;
+///
+/// assert!(valid_url().check("https://example.com/path?q=1"));
+/// assert!(!valid_url().check("example.com/path"));
+/// ```
+#[cfg(feature = "url")]
+pub fn valid_url() -> ValidUrl {
+    ValidUrl
+}
+
+fn is_uuid(value: &str) -> bool {
+    let bytes = value.as_bytes();
+    match bytes.len() {
+        32 => bytes.iter().all(u8::is_ascii_hexdigit),
+        36 => bytes.iter().enumerate().all(|(i, b)| match i {
+            8 | 13 | 18 | 23 => *b == b'-',
+            _ => b.is_ascii_hexdigit(),
+        }),
+        _ => false,
+    }
+}
+
+fn is_hostname(value: &str) -> bool {
+    let name = value.strip_suffix('.').unwrap_or(value);
+    !name.is_empty()
+        && name.len() <= 253
+        && name.split('.').all(|label| {
+            !label.is_empty()
+                && label.len() <= 63
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label
+                    .bytes()
+                    .all(|b| b.is_ascii_alphanumeric() || b == b'-')
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_uuid() {
+        assert!(valid_uuid().check(&String::from("00000000-0000-0000-0000-000000000000")));
+        assert!(!valid_uuid().check("67e55044x10b1-426f-9247-bb680e5fe0c8"));
+        assert!(!valid_uuid().check("67e55044-10b1-426f-9247-bb680e5fe0cg"));
+        assert!(!valid_uuid().check(""));
+    }
+
+    #[test]
+    fn test_ip_addr() {
+        assert!(valid_ip_addr().check("2001:db8::8a2e:370:7334"));
+        assert!(!valid_ip_addr().check("1.2.3"));
+        assert!(!valid_ip_addr().check("localhost"));
+    }
+
+    #[test]
+    fn test_hostname() {
+        assert!(valid_hostname().check("a"));
+        assert!(valid_hostname().check(&"a".repeat(63)));
+        assert!(!valid_hostname().check(&"a".repeat(64)));
+        assert!(!valid_hostname().check(&["a"; 128].join(".")));
+        assert!(!valid_hostname().check("double..dot"));
+        assert!(!valid_hostname().check("trailing-.com"));
+        assert!(!valid_hostname().check("."));
+    }
+
+    #[test]
+    fn test_port() {
+        assert!(valid_port().check("1"));
+        assert!(valid_port().check("65535"));
+        assert!(!valid_port().check("-1"));
+        assert!(!valid_port().check("http"));
+    }
+
+    #[test]
+    fn test_explain() {
+        let explanation = Predicate::<str>::explain(&valid_port(), "0");
+        assert!(!explanation.passed);
+        assert_eq!(explanation.label, "valid_port");
+        assert_eq!(explanation.actual.as_deref(), Some("\"0\""));
+    }
+
+    #[cfg(feature = "url")]
+    #[test]
+    fn test_url() {
+        assert!(valid_url().check("postgres://user@db.internal:5432/app"));
+        assert!(!valid_url().check("http://"));
+        assert!(!valid_url().check("/relative/path"));
+    }
+}
@@ -2,8 +2,12 @@
 //!
 //! This module provides common predicates for numeric validation.
 
+use alloc::format;
+use core::cmp::PartialOrd;
+use core::fmt::Debug;
+
 use super::combinators::Predicate;
-use std::cmp::PartialOrd;
+use super::explain::Explanation;
 
 /// Predicate for equality.
 #[derive(Clone, Copy, Debug)]
@@ -14,6 +18,14 @@ impl<T: PartialEq + Send + Sync> Predicate<T> for Eq<T> {
     fn check(&self, value: &T) -> bool {
         *value == self.0
     }
+
+    fn explain(&self, value: &T) -> Explanation
+    where
+        T: Debug,
+    {
+        Explanation::leaf(format!("eq({:?})", self.0), self.check(value))
+            .with_actual(format!("{value:?}"))
+    }
 }
 
 /// Create a predicate that checks for equality.
@@ -39,6 +51,14 @@ impl<T: PartialEq + Send + Sync> Predicate<T> for Ne<T> {
     fn check(&self, value: &T) -> bool {
         *value != self.0
     }
+
+    fn explain(&self, value: &T) -> Explanation
+    where
+        T: Debug,
+    {
+        Explanation::leaf(format!("ne({:?})", self.0), self.check(value))
+            .with_actual(format!("{value:?}"))
+    }
 }
 
 /// Create a predicate that checks for inequality.
@@ -65,6 +85,14 @@ impl<T: PartialOrd + Send + Sync> Predicate<T> for Gt<T> {
     fn check(&self, value: &T) -> bool {
         *value > self.0
     }
+
+    fn explain(&self, value: &T) -> Explanation
+    where
+        T: Debug,
+    {
+        Explanation::leaf(format!("gt({:?})", self.0), self.check(value))
+            .with_actual(format!("{value:?}"))
+    }
 }
 
 /// Create a predicate that checks if value is greater than threshold.
@@ -91,6 +119,14 @@ impl<T: PartialOrd + Send + Sync> Predicate<T> for Ge<T> {
     fn check(&self, value: &T) -> bool {
         *value >= self.0
     }
+
+    fn explain(&self, value: &T) -> Explanation
+    where
+        T: Debug,
+    {
+        Explanation::leaf(format!("ge({:?})", self.0), self.check(value))
+            .with_actual(format!("{value:?}"))
+    }
 }
 
 /// Create a predicate that checks if value is greater than or equal to threshold.
@@ -117,6 +153,14 @@ impl<T: PartialOrd + Send + Sync> Predicate<T> for Lt<T> {
     fn check(&self, value: &T) -> bool {
         *value < self.0
     }
+
+    fn explain(&self, value: &T) -> Explanation
+    where
+        T: Debug,
+    {
+        Explanation::leaf(format!("lt({:?})", self.0), self.check(value))
+            .with_actual(format!("{value:?}"))
+    }
 }
 
 /// Create a predicate that checks if value is less than threshold.
@@ -143,6 +187,14 @@ impl<T: PartialOrd + Send + Sync> Predicate<T> for Le<T> {
     fn check(&self, value: &T) -> bool {
         *value <= self.0
     }
+
+    fn explain(&self, value: &T) -> Explanation
+    where
+        T: Debug,
+    {
+        Explanation::leaf(format!("le({:?})", self.0), self.check(value))
+            .with_actual(format!("{value:?}"))
+    }
 }
 
 /// Create a predicate that checks if value is less than or equal to threshold.
@@ -172,6 +224,14 @@ impl<T: PartialOrd + Send + Sync> Predicate<T> for Between<T> {
     fn check(&self, value: &T) -> bool {
         *value >= self.min && *value <= self.max
     }
+
+    fn explain(&self, value: &T) -> Explanation
+    where
+        T: Debug,
+    {
+        let label = format!("between({:?}, {:?})", self.min, self.max);
+        Explanation::leaf(label, self.check(value)).with_actual(format!("{value:?}"))
+    }
 }
 
 /// Create a predicate that checks if value is between min and max (inclusive).
@@ -18,10 +18,24 @@ pub use super::combinators::{Predicate, PredicateExt};
 pub use super::combinators::{all_of, any_of, none_of, And, Not, Or};
 
 // String predicates
+#[cfg(feature = "regex")]
+pub use super::string::matches;
 pub use super::string::{
-    all_chars, any_char, contains, ends_with, is_alphabetic, is_alphanumeric, is_ascii, is_numeric,
-    len_between, len_eq, len_max, len_min, not_empty, starts_with,
+    all_chars, any_char, contains, contains_ci, ends_with, is_alphabetic, is_alphanumeric,
+    is_ascii, is_numeric, len_between, len_eq, len_max, len_min, not_empty, starts_with,
+    starts_with_ci,
 };
+#[cfg(feature = "unicode")]
+pub use super::string::{graphemes_max, is_normalized_nfc};
+
+// Network and identifier predicates
+#[cfg(feature = "url")]
+pub use super::net::valid_url;
+pub use super::net::{valid_hostname, valid_ip_addr, valid_port, valid_uuid};
+
+// Time predicates
+#[cfg(feature = "chrono")]
+pub use super::time::{in_future, in_past, valid_rfc3339, within_days};
 
 // Number predicates
 pub use super::number::{between, eq, ge, gt, le, lt, ne, negative, non_negative, positive};
@@ -2,7 +2,11 @@
 //!
 //! This module provides common predicates for string validation.
 
+use alloc::format;
+use alloc::string::String;
+
 use super::combinators::Predicate;
+use super::explain::Explanation;
 
 /// Predicate that checks if a string is not empty.
 #[derive(Clone, Copy, Default, Debug)]
@@ -13,6 +17,10 @@ impl Predicate<str> for NotEmpty {
     fn check(&self, value: &str) -> bool {
         !value.is_empty()
     }
+
+    fn explain(&self, value: &str) -> Explanation {
+        Explanation::leaf("not_empty", self.check(value)).with_actual(format!("{value:?}"))
+    }
 }
 
 impl Predicate<String> for NotEmpty {
@@ -20,6 +28,10 @@ impl Predicate<String> for NotEmpty {
     fn check(&self, value: &String) -> bool {
         !value.is_empty()
     }
+
+    fn explain(&self, value: &String) -> Explanation {
+        Predicate::<str>::explain(self, value)
+    }
 }
 
 /// Create a predicate that checks if a string is not empty.
@@ -49,6 +61,17 @@ impl Predicate<str> for LenBetween {
         let len = value.len();
         len >= self.min && len <= self.max
     }
+
+    fn explain(&self, value: &str) -> Explanation {
+        let label = match (self.min, self.max) {
+            (min, usize::MAX) => format!("len_min({min})"),
+            (0, max) => format!("len_max({max})"),
+            (min, max) if min == max => format!("len_eq({min})"),
+            (min, max) => format!("len_between({min}, {max})"),
+        };
+        Explanation::leaf(label, self.check(value))
+            .with_actual(format!("{value:?} (len {})", value.len()))
+    }
 }
 
 impl Predicate<String> for LenBetween {
@@ -57,6 +80,10 @@ impl Predicate<String> for LenBetween {
         let len = value.len();
         len >= self.min && len <= self.max
     }
+
+    fn explain(&self, value: &String) -> Explanation {
+        Predicate::<str>::explain(self, value)
+    }
 }
 
 /// Create a predicate that checks if string length is between min and max (inclusive).
@@ -133,6 +160,11 @@ impl<S: AsRef<str> + Send + Sync> Predicate<str> for StartsWith<S> {
     fn check(&self, value: &str) -> bool {
         value.starts_with(self.0.as_ref())
     }
+
+    fn explain(&self, value: &str) -> Explanation {
+        let label = format!("starts_with({:?})", self.0.as_ref());
+        Explanation::leaf(label, self.check(value)).with_actual(format!("{value:?}"))
+    }
 }
 
 impl<S: AsRef<str> + Send + Sync> Predicate<String> for StartsWith<S> {
@@ -140,6 +172,10 @@ impl<S: AsRef<str> + Send + Sync> Predicate<String> for StartsWith<S> {
     fn check(&self, value: &String) -> bool {
         value.starts_with(self.0.as_ref())
     }
+
+    fn explain(&self, value: &String) -> Explanation {
+        Predicate::<str>::explain(self, value)
+    }
 }
 
 /// Create a predicate that checks if string starts with prefix.
@@ -165,6 +201,11 @@ impl<S: AsRef<str> + Send + Sync> Predicate<str> for EndsWith<S> {
     fn check(&self, value: &str) -> bool {
         value.ends_with(self.0.as_ref())
     }
+
+    fn explain(&self, value: &str) -> Explanation {
+        let label = format!("ends_with({:?})", self.0.as_ref());
+        Explanation::leaf(label, self.check(value)).with_actual(format!("{value:?}"))
+    }
 }
 
 impl<S: AsRef<str> + Send + Sync> Predicate<String> for EndsWith<S> {
@@ -172,6 +213,10 @@ impl<S: AsRef<str> + Send + Sync> Predicate<String> for EndsWith<S> {
     fn check(&self, value: &String) -> bool {
         value.ends_with(self.0.as_ref())
     }
+
+    fn explain(&self, value: &String) -> Explanation {
+        Predicate::<str>::explain(self, value)
+    }
 }
 
 /// Create a predicate that checks if string ends with suffix.
@@ -197,6 +242,11 @@ impl<S: AsRef<str> + Send + Sync> Predicate<str> for Contains<S> {
     fn check(&self, value: &str) -> bool {
         value.contains(self.0.as_ref())
     }
+
+    fn explain(&self, value: &str) -> Explanation {
+        let label = format!("contains({:?})", self.0.as_ref());
+        Explanation::leaf(label, self.check(value)).with_actual(format!("{value:?}"))
+    }
 }
 
 impl<S: AsRef<str> + Send + Sync> Predicate<String> for Contains<S> {
@@ -204,6 +254,10 @@ impl<S: AsRef<str> + Send + Sync> Predicate<String> for Contains<S> {
     fn check(&self, value: &String) -> bool {
         value.contains(self.0.as_ref())
     }
+
+    fn explain(&self, value: &String) -> Explanation {
+        Predicate::<str>::explain(self, value)
+    }
 }
 
 /// Create a predicate that checks if string contains substring.
@@ -229,6 +283,10 @@ impl<F: Fn(char) -> bool + Send + Sync> Predicate<str> for AllChars<F> {
     fn check(&self, value: &str) -> bool {
         value.chars().all(&self.0)
     }
+
+    fn explain(&self, value: &str) -> Explanation {
+        Explanation::leaf("all_chars", self.check(value)).with_actual(format!("{value:?}"))
+    }
 }
 
 impl<F: Fn(char) -> bool + Send + Sync> Predicate<String> for AllChars<F> {
@@ -236,6 +294,10 @@ impl<F: Fn(char) -> bool + Send + Sync> Predicate<String> for AllChars<F> {
     fn check(&self, value: &String) -> bool {
         value.chars().all(&self.0)
     }
+
+    fn explain(&self, value: &String) -> Explanation {
+        Predicate::<str>::explain(self, value)
+    }
 }
 
 /// Create a predicate that checks if all characters satisfy a condition.
@@ -261,6 +323,10 @@ impl<F: Fn(char) -> bool + Send + Sync> Predicate<str> for AnyChar<F> {
     fn check(&self, value: &str) -> bool {
         value.chars().any(&self.0)
     }
+
+    fn explain(&self, value: &str) -> Explanation {
+        Explanation::leaf("any_char", self.check(value)).with_actual(format!("{value:?}"))
+    }
 }
 
 impl<F: Fn(char) -> bool + Send + Sync> Predicate<String> for AnyChar<F> {
@@ -268,6 +334,10 @@ impl<F: Fn(char) -> bool + Send + Sync> Predicate<String> for AnyChar<F> {
     fn check(&self, value: &String) -> bool {
         value.chars().any(&self.0)
     }
+
+    fn explain(&self, value: &String) -> Explanation {
+        Predicate::<str>::explain(self, value)
+    }
 }
 
 /// Create a predicate that checks if any character satisfies a condition.
@@ -340,6 +410,260 @@ pub fn is_numeric() -> AllChars<fn(char) -> bool> {
     AllChars(|c| c.is_numeric())
 }
 
+/// Predicate that checks if a string matches a regular expression.
+#[cfg(feature = "regex")]
+#[derive(Clone, Debug)]
+pub struct Matches(pub regex::Regex);
+
+#[cfg(feature = "regex")]
+impl Predicate<str> for Matches {
+    #[inline]
+    fn check(&self, value: &str) -> bool {
+        self.0.is_match(value)
+    }
+
+    fn explain(&self, value: &str) -> Explanation {
+        let label = format!("matches({:?})", self.0.as_str());
+        Explanation::leaf(label, self.check(value)).with_actual(format!("{value:?}"))
+    }
+}
+
+#[cfg(feature = "regex")]
+impl Predicate<String> for Matches {
+    #[inline]
+    fn check(&self, value: &String) -> bool {
+        self.0.is_match(value)
+    }
+
+    fn explain(&self, value: &String) -> Explanation {
+        Predicate::<str>::explain(self, value)
+    }
+}
+
+/// Create a predicate that checks if a string matches a regular expression.
+///
+/// Like [`regex::Regex::is_match`], the pattern matches anywhere in the
+/// string unless anchored with `^` and `$`. Requires the `regex` feature.
+///
+/// # Example
+///
+/// ```rust
+/// use stillwater::predicate::*;
+///
+/// let zip = matches(r"^\d{5}$").unwrap();
+/// assert!(zip.check("12345"));
+/// assert!(!zip.check("1234a"));
+/// ```
+#[cfg(feature = "regex")]
+pub fn matches(pattern: &str) -> Result<Matches, regex::Error> {
+    regex::Regex::new(pattern).map(Matches)
+}
+
+/// Predicate that checks if a string contains a substring, ignoring case.
+#[derive(Clone, Debug)]
+pub struct ContainsCi {
+    needle: String,
+}
+
+impl Predicate<str> for ContainsCi {
+    #[inline]
+    fn check(&self, value: &str) -> bool {
+        value.to_lowercase().contains(&self.needle)
+    }
+
+    fn explain(&self, value: &str) -> Explanation {
+        let label = format!("contains_ci({:?})", self.needle);
+        Explanation::leaf(label, self.check(value)).with_actual(format!("{value:?}"))
+    }
+}
+
+impl Predicate<String> for ContainsCi {
+    #[inline]
+    fn check(&self, value: &String) -> bool {
+        Predicate::<str>::check(self, value)
+    }
+
+    fn explain(&self, value: &String) -> Explanation {
+        Predicate::<str>::explain(self, value)
+    }
+}
+
+/// Create a predicate that checks if a string contains a substring,
+/// ignoring case.
+///
+/// Both strings are compared after Unicode lowercasing with
+/// [`str::to_lowercase`], so this works beyond ASCII.
+///
+/// # Example
+///
+/// ```rust
+/// use stillwater::predicate::*;
+///
+/// assert!(contains_ci("ADMIN").check("SuperAdmin"));
+/// assert!(contains_ci("straße").check("Hauptstraße 5"));
+/// assert!(!contains_ci("root").check("admin"));
+/// ```
+pub fn contains_ci(substring: impl AsRef<str>) -> ContainsCi {
+    ContainsCi {
+        needle: substring.as_ref().to_lowercase(),
+    }
+}
+
+/// Predicate that checks if a string starts with a prefix, ignoring case.
+#[derive(Clone, Debug)]
+pub struct StartsWithCi {
+    prefix: String,
+}
+
+impl Predicate<str> for StartsWithCi {
+    #[inline]
+    fn check(&self, value: &str) -> bool {
+        value.to_lowercase().starts_with(&self.prefix)
+    }
+
+    fn explain(&self, value: &str) -> Explanation {
+        let label = format!("starts_with_ci({:?})", self.prefix);
+        Explanation::leaf(label, self.check(value)).with_actual(format!("{value:?}"))
+    }
+}
+
+impl Predicate<String> for StartsWithCi {
+    #[inline]
+    fn check(&self, value: &String) -> bool {
+        Predicate::<str>::check(self, value)
+    }
+
+    fn explain(&self, value: &String) -> Explanation {
+        Predicate::<str>::explain(self, value)
+    }
+}
+
+/// Create a predicate that checks if a string starts with a prefix,
+/// ignoring case.
+///
+/// Both strings are compared after Unicode lowercasing with
+/// [`str::to_lowercase`].
+///
+/// # Example
+///
+/// ```rust
+/// use stillwater::predicate::*;
+///
+/// assert!(starts_with_ci("https://").check("HTTPS://example.com"));
+/// assert!(!starts_with_ci("https://").check("http://example.com"));
+/// ```
+pub fn starts_with_ci(prefix: impl AsRef<str>) -> StartsWithCi {
+    StartsWithCi {
+        prefix: prefix.as_ref().to_lowercase(),
+    }
+}
+
+/// Predicate that checks if a string is in Unicode Normalization Form C.
+#[cfg(feature = "unicode")]
+#[derive(Clone, Copy, Default, Debug)]
+pub struct IsNormalizedNfc;
+
+#[cfg(feature = "unicode")]
+impl Predicate<str> for IsNormalizedNfc {
+    #[inline]
+    fn check(&self, value: &str) -> bool {
+        unicode_normalization::is_nfc(value)
+    }
+
+    fn explain(&self, value: &str) -> Explanation {
+        Explanation::leaf("is_normalized_nfc", self.check(value)).with_actual(format!("{value:?}"))
+    }
+}
+
+#[cfg(feature = "unicode")]
+impl Predicate<String> for IsNormalizedNfc {
+    #[inline]
+    fn check(&self, value: &String) -> bool {
+        unicode_normalization::is_nfc(value)
+    }
+
+    fn explain(&self, value: &String) -> Explanation {
+        Predicate::<str>::explain(self, value)
+    }
+}
+
+/// Create a predicate that checks if a string is in Unicode Normalization
+/// Form C (NFC).
+///
+/// Strings that look identical can differ in their code points, such as a
+/// precomposed `é` versus `e` followed by a combining accent. Requiring NFC
+/// keeps such strings comparable. Requires the `unicode` feature.
+///
+/// # Example
+///
+/// ```rust
+/// use stillwater::predicate::*;
+///
+/// assert!(is_normalized_nfc().check("caf\u{e9}"));
+/// assert!(!is_normalized_nfc().check("cafe\u{301}"));
+/// ```
+#[cfg(feature = "unicode")]
+pub fn is_normalized_nfc() -> IsNormalizedNfc {
+    IsNormalizedNfc
+}
+
+/// Predicate that checks the number of grapheme clusters in a string.
+#[cfg(feature = "unicode")]
+#[derive(Clone, Copy, Debug)]
+pub struct GraphemesMax {
+    max: usize,
+}
+
+#[cfg(feature = "unicode")]
+impl Predicate<str> for GraphemesMax {
+    #[inline]
+    fn check(&self, value: &str) -> bool {
+        use unicode_segmentation::UnicodeSegmentation;
+        value.graphemes(true).nth(self.max).is_none()
+    }
+
+    fn explain(&self, value: &str) -> Explanation {
+        use unicode_segmentation::UnicodeSegmentation;
+        let count = value.graphemes(true).count();
+        Explanation::leaf(format!("graphemes_max({})", self.max), count <= self.max)
+            .with_actual(format!("{value:?} ({count} graphemes)"))
+    }
+}
+
+#[cfg(feature = "unicode")]
+impl Predicate<String> for GraphemesMax {
+    #[inline]
+    fn check(&self, value: &String) -> bool {
+        Predicate::<str>::check(self, value)
+    }
+
+    fn explain(&self, value: &String) -> Explanation {
+        Predicate::<str>::explain(self, value)
+    }
+}
+
+/// Create a predicate that checks a string has at most `max` grapheme
+/// clusters.
+///
+/// Grapheme clusters are what users perceive as characters, so this is the
+/// right measure for display-name limits: `len_max` counts bytes, and an
+/// emoji with a skin-tone modifier or a flag is several `char`s but one
+/// grapheme. Requires the `unicode` feature.
+///
+/// # Example
+///
+/// ```rust
+/// use stillwater::predicate::*;
+///
+/// let flag = "\u{1F1EF}\u{1F1F5}"; // 🇯🇵, two code points
+/// assert!(graphemes_max(1).check(flag));
+/// assert!(!len_max(1).check(flag));
+/// ```
+#[cfg(feature = "unicode")]
+pub fn graphemes_max(max: usize) -> GraphemesMax {
+    GraphemesMax { max }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -457,4 +781,46 @@ mod tests {
         assert!(!valid_username.check("ab")); // too short
         assert!(!valid_username.check("invalid-name")); // contains hyphen
     }
+
+    #[test]
+    fn test_case_insensitive() {
+        assert!(contains_ci("World").check("hello WORLD"));
+        assert!(contains_ci("ΣΟΦΙΑ").check(&String::from("η σοφια")));
+        assert!(!contains_ci("xyz").check("hello"));
+
+        assert!(starts_with_ci("Dr.").check("DR. Smith"));
+        assert!(!starts_with_ci("Dr.").check("Mr. Smith"));
+        assert_eq!(
+            Predicate::<str>::explain(&starts_with_ci("Dr."), "Mr").label,
+            "starts_with_ci(\"dr.\")"
+        );
+    }
+
+    #[cfg(feature = "unicode")]
+    #[test]
+    fn test_unicode_predicates() {
+        assert!(is_normalized_nfc().check(&String::from("\u{e9}")));
+        assert!(!is_normalized_nfc().check("e\u{301}"));
+
+        // Family emoji: five code points joined with ZWJ, one grapheme
+        let family = "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}";
+        assert!(graphemes_max(1).check(family));
+        assert!(graphemes_max(3).check("abc"));
+        assert!(!graphemes_max(2).check("abc"));
+        assert_eq!(
+            Predicate::<str>::explain(&graphemes_max(2), "abc")
+                .actual
+                .as_deref(),
+            Some("\"abc\" (3 graphemes)")
+        );
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn test_matches() {
+        let p = matches(r"^[a-z]+@[a-z]+\.com$").unwrap();
+        assert!(p.check("user@example.com"));
+        assert!(!p.check(&String::from("not an email")));
+        assert!(matches("(").is_err());
+    }
 }
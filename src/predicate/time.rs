@@ -0,0 +1,235 @@
+//! Date and time predicates (feature-gated).
+//!
+//! This module provides predicates for validating `chrono` timestamps and
+//! timestamp strings. Feature-gated behind `#[cfg(feature = "chrono")]`.
+//!
+//! Predicates that compare against the current time read the system clock
+//! on every check.
+//!
+//! # Example
+//!
+//! ```rust
+//! use chrono::{Duration, Utc};
+//! use stillwater::predicate::*;
+//!
+//! let birthday = Utc::now() - Duration::days(365 * 30);
+//! assert!(in_past().check(&birthday));
+//!
+//! let appointment = Utc::now() + Duration::days(3);
+//! assert!(in_future().check(&appointment));
+//! assert!(within_days(7).check(&appointment));
+//!
+//! assert!(valid_rfc3339().check("2024-05-01T12:00:00Z"));
+//! ```
+
+use core::fmt::Debug;
+
+use chrono::{DateTime, TimeZone, Utc};
+
+use super::combinators::Predicate;
+use super::explain::Explanation;
+
+/// Predicate that checks if a timestamp is before now.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct InPast;
+
+impl<Tz> Predicate<DateTime<Tz>> for InPast
+where
+    Tz: TimeZone + Send + Sync,
+    Tz::Offset: Send + Sync,
+{
+    #[inline]
+    fn check(&self, value: &DateTime<Tz>) -> bool {
+        *value < Utc::now()
+    }
+
+    fn explain(&self, value: &DateTime<Tz>) -> Explanation
+    where
+        DateTime<Tz>: Debug,
+    {
+        Explanation::leaf("in_past", self.check(value)).with_actual(format!("{value:?}"))
+    }
+}
+
+/// Create a predicate that checks if a timestamp is before now.
+///
+/// # Example
+///
+/// ```rust
+/// use chrono::{Duration, Utc};
+/// use stillwater::predicate::*;
+///
+/// assert!(in_past().check(&(Utc::now() - Duration::hours(1))));
+/// assert!(!in_past().check(&(Utc::now() + Duration::hours(1))));
+/// ```
+pub fn in_past() -> InPast {
+    InPast
+}
+
+/// Predicate that checks if a timestamp is after now.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct InFuture;
+
+impl<Tz> Predicate<DateTime<Tz>> for InFuture
+where
+    Tz: TimeZone + Send + Sync,
+    Tz::Offset: Send + Sync,
+{
+    #[inline]
+    fn check(&self, value: &DateTime<Tz>) -> bool {
+        *value > Utc::now()
+    }
+
+    fn explain(&self, value: &DateTime<Tz>) -> Explanation
+    where
+        DateTime<Tz>: Debug,
+    {
+        Explanation::leaf("in_future", self.check(value)).with_actual(format!("{value:?}"))
+    }
+}
+
+/// Create a predicate that checks if a timestamp is after now.
+///
+/// # Example
+///
+/// ```rust
+/// use chrono::{Duration, Utc};
+/// use stillwater::predicate::*;
+///
+/// assert!(in_future().check(&(Utc::now() + Duration::hours(1))));
+/// assert!(!in_future().check(&(Utc::now() - Duration::hours(1))));
+/// ```
+pub fn in_future() -> InFuture {
+    InFuture
+}
+
+/// Predicate that checks if a timestamp is within a number of days of now.
+#[derive(Clone, Copy, Debug)]
+pub struct WithinDays {
+    days: u32,
+}
+
+impl<Tz> Predicate<DateTime<Tz>> for WithinDays
+where
+    Tz: TimeZone + Send + Sync,
+    Tz::Offset: Send + Sync,
+{
+    #[inline]
+    fn check(&self, value: &DateTime<Tz>) -> bool {
+        value
+            .clone()
+            .signed_duration_since(Utc::now())
+            .num_seconds()
+            .unsigned_abs()
+            <= u64::from(self.days) * 86_400
+    }
+
+    fn explain(&self, value: &DateTime<Tz>) -> Explanation
+    where
+        DateTime<Tz>: Debug,
+    {
+        Explanation::leaf(format!("within_days({})", self.days), self.check(value))
+            .with_actual(format!("{value:?}"))
+    }
+}
+
+/// Create a predicate that checks if a timestamp is at most `days` days
+/// before or after now.
+///
+/// # Example
+///
+/// ```rust
+/// use chrono::{Duration, Utc};
+/// use stillwater::predicate::*;
+///
+/// assert!(within_days(7).check(&(Utc::now() - Duration::days(6))));
+/// assert!(!within_days(7).check(&(Utc::now() + Duration::days(8))));
+/// ```
+pub fn within_days(days: u32) -> WithinDays {
+    WithinDays { days }
+}
+
+/// Predicate that checks if a string is an RFC 3339 timestamp.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct ValidRfc3339;
+
+impl Predicate<str> for ValidRfc3339 {
+    #[inline]
+    fn check(&self, value: &str) -> bool {
+        DateTime::parse_from_rfc3339(value).is_ok()
+    }
+
+    fn explain(&self, value: &str) -> Explanation {
+        Explanation::leaf("valid_rfc3339", self.check(value)).with_actual(format!("{value:?}"))
+    }
+}
+
+impl Predicate<String> for ValidRfc3339 {
+    #[inline]
+    fn check(&self, value: &String) -> bool {
+        Predicate::<str>::check(self, value)
+    }
+
+    fn explain(&self, value: &String) -> Explanation {
+        Predicate::<str>::explain(self, value)
+    }
+}
+
+/// Create a predicate that checks if a string is an RFC 3339 timestamp,
+/// such as `2024-05-01T12:00:00+02:00`.
+///
+/// # Example
+///
+/// ```rust
+/// use stillwater::predicate::*;
+///
+/// assert!(valid_rfc3339().check("2024-05-01T12:00:00+02:00"));
+/// assert!(!valid_rfc3339().check("2024-05-01"));
+/// ```
+pub fn valid_rfc3339() -> ValidRfc3339 {
+    ValidRfc3339
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, FixedOffset};
+
+    #[test]
+    fn test_past_and_future_in_any_timezone() {
+        let tokyo = FixedOffset::east_opt(9 * 3600).unwrap();
+        let earlier = (Utc::now() - Duration::minutes(5)).with_timezone(&tokyo);
+        let later = (Utc::now() + Duration::minutes(5)).with_timezone(&tokyo);
+
+        assert!(in_past().check(&earlier));
+        assert!(!in_past().check(&later));
+        assert!(in_future().check(&later));
+        assert!(!in_future().check(&earlier));
+    }
+
+    #[test]
+    fn test_within_days_is_symmetric() {
+        let p = within_days(2);
+        assert!(p.check(&(Utc::now() - Duration::days(1))));
+        assert!(p.check(&(Utc::now() + Duration::days(1))));
+        assert!(!p.check(&(Utc::now() - Duration::days(3))));
+        assert!(!p.check(&(Utc::now() + Duration::days(3))));
+    }
+
+    #[test]
+    fn test_valid_rfc3339() {
+        assert!(valid_rfc3339().check("1985-04-12T23:20:50.52Z"));
+        assert!(valid_rfc3339().check(&String::from("1996-12-19T16:39:57-08:00")));
+        assert!(!valid_rfc3339().check("1985-04-12 23:20"));
+        assert!(!valid_rfc3339().check("2024-13-01T00:00:00Z"));
+    }
+
+    #[test]
+    fn test_explain_shows_timestamp() {
+        let at = Utc.with_ymd_and_hms(2000, 1, 1, 0, 0, 0).unwrap();
+        let explanation = in_future().explain(&at);
+        assert!(!explanation.passed);
+        assert_eq!(explanation.label, "in_future");
+        assert_eq!(explanation.actual.as_deref(), Some("2000-01-01T00:00:00Z"));
+    }
+}
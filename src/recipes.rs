@@ -0,0 +1,478 @@
+//! Complete flows built only from this crate, to copy and adapt.
+//!
+//! Each recipe is a function returning an effect, and each is compiled and
+//! tested with the crate, so it keeps working as the API changes. Together
+//! they make up one flow: fetch a transfer request over HTTP with retries,
+//! validate it, and apply it to a ledger in a single transaction.
+//!
+//! - [`fetch_with_retry`] retries transient HTTP failures and gives up at
+//!   once on permanent ones.
+//! - [`parse_transfer`] validates a request body, reporting every problem
+//!   at once.
+//! - [`apply_transfer`] moves money between two accounts atomically.
+//! - [`import_transfer`] chains the three into one effect.
+//!
+//! Services come from the environment through `AsRef`, as in the rest of
+//! the crate. [`HttpClient`] is a trait so that tests, like the ones below,
+//! can plug in an in-memory server; a real application implements it over
+//! its HTTP library of choice.
+//!
+//! Requires the `examples` feature.
+//!
+//! # Example
+//!
+//! ```rust
+//! use std::time::Duration;
+//! use stillwater::effect::prelude::*;
+//! use stillwater::recipes::{import_transfer, FetchError, Http, HttpClient, Ledger};
+//! use stillwater::{BoxFuture, RetryPolicy};
+//!
+//! struct FixedResponse(&'static str);
+//!
+//! impl HttpClient for FixedResponse {
+//!     fn get(&self, _url: String) -> BoxFuture<'_, Result<String, FetchError>> {
+//!         Box::pin(async move { Ok(self.0.to_string()) })
+//!     }
+//! }
+//!
+//! #[derive(Clone)]
+//! struct AppEnv {
+//!     http: Http,
+//!     ledger: Ledger,
+//! }
+//!
+//! impl AsRef<Http> for AppEnv {
+//!     fn as_ref(&self) -> &Http {
+//!         &self.http
+//!     }
+//! }
+//!
+//! impl AsRef<Ledger> for AppEnv {
+//!     fn as_ref(&self) -> &Ledger {
+//!         &self.ledger
+//!     }
+//! }
+//!
+//! # tokio_test::block_on(async {
+//! let env = AppEnv {
+//!     http: Http::new(FixedResponse("from=alice&to=bob&amount=30")),
+//!     ledger: Ledger::new([("alice", 100), ("bob", 0)]),
+//! };
+//!
+//! let import = import_transfer(
+//!     "https://bank.example/transfers/7",
+//!     RetryPolicy::exponential(Duration::from_millis(10)).with_max_retries(3),
+//! );
+//!
+//! import.run(&env).await.unwrap();
+//! assert_eq!(env.ledger.balance("alice"), Some(70));
+//! assert_eq!(env.ledger.balance("bob"), Some(30));
+//! # });
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
+
+use crate::effect::constructors::{asks, from_async, from_validation};
+use crate::effect::retry::retry_if;
+use crate::effect::stm::{atomically, StmError, TVar};
+use crate::effect::{BoxFuture, BoxedEffect, Effect, EffectExt};
+use crate::{RetryPolicy, Validation};
+
+// ==================== HTTP fetch with retry ====================
+
+/// Why an HTTP request failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FetchError {
+    /// The request never got a response.
+    Connection(String),
+    /// The server answered with a non-success status.
+    Status(u16),
+}
+
+impl FetchError {
+    /// Whether trying again might succeed: connection failures, rate
+    /// limiting, and server errors.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            FetchError::Connection(_) => true,
+            FetchError::Status(status) => *status == 429 || *status >= 500,
+        }
+    }
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FetchError::Connection(reason) => write!(f, "connection failed: {}", reason),
+            FetchError::Status(status) => write!(f, "server responded with status {}", status),
+        }
+    }
+}
+
+impl std::error::Error for FetchError {}
+
+/// The HTTP operations the recipes need.
+pub trait HttpClient: Send + Sync {
+    /// Fetch the body at `url`.
+    fn get(&self, url: String) -> BoxFuture<'_, Result<String, FetchError>>;
+}
+
+/// The HTTP service, as stored in an environment.
+///
+/// Cloning gives another handle to the same client.
+#[derive(Clone)]
+pub struct Http {
+    client: Arc<dyn HttpClient>,
+}
+
+impl Http {
+    /// Wrap `client` for use in an environment.
+    pub fn new(client: impl HttpClient + 'static) -> Self {
+        Http {
+            client: Arc::new(client),
+        }
+    }
+}
+
+impl fmt::Debug for Http {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Http").finish_non_exhaustive()
+    }
+}
+
+/// Fetch the body at `url`, retrying transient failures under `policy`.
+///
+/// Permanent failures, such as a 404, are returned immediately. An attempt
+/// that outlives the policy's attempt timeout is retried, and reported as a
+/// connection failure if it was the last.
+pub fn fetch_with_retry<Env>(
+    url: impl Into<String>,
+    policy: RetryPolicy,
+) -> BoxedEffect<String, FetchError, Env>
+where
+    Env: AsRef<Http> + Send + Sync + 'static,
+{
+    let url = url.into();
+    retry_if(
+        move || {
+            let url = url.clone();
+            from_async(move |env: &Env| {
+                let http: &Http = env.as_ref();
+                let client = Arc::clone(&http.client);
+                async move { client.get(url).await }
+            })
+        },
+        policy,
+        FetchError::is_transient,
+    )
+    .map_err(|error| {
+        error.into_inner_or_else(|duration| {
+            FetchError::Connection(format!("timed out after {:?}", duration))
+        })
+    })
+    .boxed()
+}
+
+// ==================== Validation ====================
+
+/// A validated request to move `amount` from one account to another.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Transfer {
+    /// Account to debit
+    pub from: String,
+    /// Account to credit
+    pub to: String,
+    /// Amount to move, always positive
+    pub amount: u64,
+}
+
+/// Parse a `from=..&to=..&amount=..` body into a [`Transfer`], collecting
+/// every problem with it rather than stopping at the first.
+pub fn parse_transfer(body: &str) -> Validation<Transfer, Vec<String>> {
+    let fields: BTreeMap<&str, &str> = body
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .collect();
+
+    let account = |name: &str| match fields.get(name) {
+        Some(value) if !value.is_empty() => Validation::success(value.to_string()),
+        _ => Validation::failure(vec![format!("{} is required", name)]),
+    };
+    let amount = match fields.get("amount").map(|raw| raw.parse::<u64>()) {
+        Some(Ok(0)) => Validation::failure(vec!["amount must be positive".to_string()]),
+        Some(Ok(amount)) => Validation::success(amount),
+        Some(Err(_)) => Validation::failure(vec!["amount must be a whole number".to_string()]),
+        None => Validation::failure(vec!["amount is required".to_string()]),
+    };
+
+    Validation::<(String, String, u64), Vec<String>>::all((account("from"), account("to"), amount))
+        .and_then(|(from, to, amount)| {
+            if from == to {
+                Validation::failure(vec!["cannot transfer to the same account".to_string()])
+            } else {
+                Validation::success(Transfer { from, to, amount })
+            }
+        })
+}
+
+// ==================== Transaction ====================
+
+/// Account balances, updated transactionally.
+///
+/// Cloning gives another handle to the same ledger.
+#[derive(Clone)]
+pub struct Ledger {
+    accounts: Arc<BTreeMap<String, TVar<u64>>>,
+}
+
+impl Ledger {
+    /// A ledger with the given accounts and opening balances.
+    pub fn new<'a>(accounts: impl IntoIterator<Item = (&'a str, u64)>) -> Self {
+        Ledger {
+            accounts: Arc::new(
+                accounts
+                    .into_iter()
+                    .map(|(name, balance)| (name.to_string(), TVar::new(balance)))
+                    .collect(),
+            ),
+        }
+    }
+
+    /// The committed balance of `account`, if it exists.
+    pub fn balance(&self, account: &str) -> Option<u64> {
+        self.accounts.get(account).map(TVar::load)
+    }
+}
+
+impl fmt::Debug for Ledger {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map()
+            .entries(self.accounts.iter().map(|(name, var)| (name, var.load())))
+            .finish()
+    }
+}
+
+/// Why a transfer was not applied.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TransferError {
+    /// No account has this name.
+    UnknownAccount(String),
+    /// The debited account holds less than the amount.
+    InsufficientFunds {
+        /// Balance of the debited account
+        balance: u64,
+    },
+}
+
+impl fmt::Display for TransferError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TransferError::UnknownAccount(name) => write!(f, "unknown account {:?}", name),
+            TransferError::InsufficientFunds { balance } => {
+                write!(f, "insufficient funds: balance is {}", balance)
+            }
+        }
+    }
+}
+
+impl std::error::Error for TransferError {}
+
+/// Apply `transfer` to `ledger`: both balances change, or neither does.
+pub fn apply_transfer<Env>(
+    ledger: Ledger,
+    transfer: Transfer,
+) -> impl Effect<Output = (), Error = TransferError, Env = Env>
+where
+    Env: Send + Sync,
+{
+    atomically(move |tx| {
+        let account = |name: &String| {
+            ledger
+                .accounts
+                .get(name)
+                .ok_or_else(|| StmError::Abort(TransferError::UnknownAccount(name.clone())))
+        };
+        let (from, to) = (account(&transfer.from)?, account(&transfer.to)?);
+
+        let balance = tx.read(from)?;
+        if balance < transfer.amount {
+            return Err(StmError::Abort(TransferError::InsufficientFunds {
+                balance,
+            }));
+        }
+        tx.write(from, balance - transfer.amount);
+        tx.modify(to, |credited| credited + transfer.amount)
+    })
+}
+
+// ==================== The whole flow ====================
+
+/// Why [`import_transfer`] failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImportError {
+    /// The request could not be fetched.
+    Fetch(FetchError),
+    /// The request was fetched but is malformed.
+    Invalid(Vec<String>),
+    /// The request is well formed but could not be applied.
+    Rejected(TransferError),
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImportError::Fetch(e) => write!(f, "fetch failed: {}", e),
+            ImportError::Invalid(problems) => {
+                write!(f, "invalid transfer: {}", problems.join("; "))
+            }
+            ImportError::Rejected(e) => write!(f, "transfer rejected: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ImportError {}
+
+/// Fetch the transfer request at `url`, validate it, and apply it to the
+/// environment's ledger, returning the transfer that was applied.
+pub fn import_transfer<Env>(
+    url: impl Into<String>,
+    policy: RetryPolicy,
+) -> BoxedEffect<Transfer, ImportError, Env>
+where
+    Env: AsRef<Http> + AsRef<Ledger> + Send + Sync + 'static,
+{
+    fetch_with_retry(url, policy)
+        .map_err(ImportError::Fetch)
+        .and_then(|body| from_validation(parse_transfer(&body)).map_err(ImportError::Invalid))
+        .and_then(|transfer| {
+            asks(|env: &Env| AsRef::<Ledger>::as_ref(env).clone()).and_then(move |ledger| {
+                apply_transfer(ledger, transfer.clone())
+                    .map(move |()| transfer)
+                    .map_err(ImportError::Rejected)
+            })
+        })
+        .boxed()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    /// Answers each request with the next scripted response.
+    struct Scripted {
+        responses: Mutex<VecDeque<Result<String, FetchError>>>,
+    }
+
+    impl HttpClient for Scripted {
+        fn get(&self, _url: String) -> BoxFuture<'_, Result<String, FetchError>> {
+            let response = self.responses.lock().unwrap().pop_front();
+            Box::pin(async move { response.expect("no more scripted responses") })
+        }
+    }
+
+    #[derive(Clone)]
+    struct Env {
+        http: Http,
+        ledger: Ledger,
+    }
+
+    impl AsRef<Http> for Env {
+        fn as_ref(&self) -> &Http {
+            &self.http
+        }
+    }
+
+    impl AsRef<Ledger> for Env {
+        fn as_ref(&self) -> &Ledger {
+            &self.ledger
+        }
+    }
+
+    fn env(responses: impl IntoIterator<Item = Result<&'static str, FetchError>>) -> Env {
+        Env {
+            http: Http::new(Scripted {
+                responses: Mutex::new(
+                    responses
+                        .into_iter()
+                        .map(|r| r.map(str::to_string))
+                        .collect(),
+                ),
+            }),
+            ledger: Ledger::new([("alice", 100), ("bob", 5)]),
+        }
+    }
+
+    fn policy() -> RetryPolicy {
+        RetryPolicy::exponential(Duration::from_secs(1)).with_max_retries(3)
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_import_retries_transient_failures() {
+        let env = env([
+            Err(FetchError::Connection("reset".into())),
+            Err(FetchError::Status(503)),
+            Ok("from=alice&to=bob&amount=40"),
+        ]);
+
+        let applied = import_transfer("/t/1", policy()).run(&env).await.unwrap();
+
+        assert_eq!(applied.amount, 40);
+        assert_eq!(env.ledger.balance("alice"), Some(60));
+        assert_eq!(env.ledger.balance("bob"), Some(45));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_import_does_not_retry_permanent_failures() {
+        let env = env([Err(FetchError::Status(404)), Ok("unreachable")]);
+
+        let result = import_transfer("/t/2", policy()).run(&env).await;
+
+        assert_eq!(result, Err(ImportError::Fetch(FetchError::Status(404))));
+    }
+
+    #[tokio::test]
+    async fn test_import_reports_every_validation_problem() {
+        let env = env([Ok("from=alice&amount=zero")]);
+
+        let result = import_transfer("/t/3", policy()).run(&env).await;
+
+        assert_eq!(
+            result,
+            Err(ImportError::Invalid(vec![
+                "to is required".to_string(),
+                "amount must be a whole number".to_string(),
+            ]))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rejected_transfer_changes_nothing() {
+        let env = env([
+            Ok("from=alice&to=bob&amount=500"),
+            Ok("from=alice&to=carol&amount=1"),
+        ]);
+
+        let overdrawn = import_transfer("/t/4", policy()).run(&env).await;
+        let unknown = import_transfer("/t/5", policy()).run(&env).await;
+
+        assert_eq!(
+            overdrawn,
+            Err(ImportError::Rejected(TransferError::InsufficientFunds {
+                balance: 100
+            }))
+        );
+        assert_eq!(
+            unknown,
+            Err(ImportError::Rejected(TransferError::UnknownAccount(
+                "carol".to_string()
+            )))
+        );
+        assert_eq!(env.ledger.balance("alice"), Some(100));
+        assert_eq!(env.ledger.balance("bob"), Some(5));
+    }
+}
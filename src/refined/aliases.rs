@@ -13,10 +13,20 @@
 //! let balance = NonNegativeI64::new(1000).unwrap();
 //! ```
 
+use alloc::string::String;
+use alloc::vec::Vec;
+
 use super::combinators::And;
 use super::predicates::collection::MaxSize;
+#[cfg(feature = "rust_decimal")]
+use super::predicates::decimal::{CurrencyCode, MaxScale, NonNegativeAmount};
+#[cfg(feature = "url")]
+use super::predicates::net::ValidUrl;
+use super::predicates::net::{ValidHostname, ValidIpAddr, ValidUuid};
 use super::predicates::numeric::{InRange, Negative, NonNegative, NonZero, Positive};
 use super::predicates::string::{MaxLength, MinLength, NonEmpty, Trimmed};
+#[cfg(feature = "chrono")]
+use super::predicates::time::{InFuture, InPast, ValidRfc3339, WithinDays};
 use super::Refined;
 
 // ============================================================================
@@ -32,6 +42,9 @@ pub type TrimmedString = Refined<String, Trimmed>;
 /// A string that is both non-empty and trimmed
 pub type NonEmptyTrimmedString = Refined<String, And<NonEmpty, Trimmed>>;
 
+/// A borrowed string slice that is guaranteed to be non-empty
+pub type NonEmptyStr<'a> = Refined<&'a str, NonEmpty>;
+
 // ============================================================================
 // Signed integer aliases - Positive
 // ============================================================================
@@ -195,6 +208,55 @@ pub type MinLengthString<const MIN: usize> = Refined<String, MinLength<MIN>>;
 /// A bounded collection with maximum size
 pub type BoundedVec<T, const MAX: usize> = Refined<Vec<T>, MaxSize<MAX>>;
 
+// ============================================================================
+// Network and identifier aliases
+// ============================================================================
+
+/// A string holding a UUID
+pub type UuidString = Refined<String, ValidUuid>;
+
+/// A string holding an IPv4 or IPv6 address
+pub type IpAddrString = Refined<String, ValidIpAddr>;
+
+/// A string holding an RFC 1123 hostname
+pub type HostnameString = Refined<String, ValidHostname>;
+
+/// A string holding an absolute URL
+#[cfg(feature = "url")]
+pub type UrlString = Refined<String, ValidUrl>;
+
+// ============================================================================
+// Time aliases (feature `chrono`)
+// ============================================================================
+
+/// A UTC timestamp that was in the past when validated
+#[cfg(feature = "chrono")]
+pub type PastDateTime = Refined<chrono::DateTime<chrono::Utc>, InPast>;
+
+/// A UTC timestamp that was in the future when validated
+#[cfg(feature = "chrono")]
+pub type FutureDateTime = Refined<chrono::DateTime<chrono::Utc>, InFuture>;
+
+/// A UTC timestamp that was within N days of now when validated
+#[cfg(feature = "chrono")]
+pub type RecentDateTime<const N: u32> = Refined<chrono::DateTime<chrono::Utc>, WithinDays<N>>;
+
+/// A string holding an RFC 3339 timestamp
+#[cfg(feature = "chrono")]
+pub type Rfc3339String = Refined<String, ValidRfc3339>;
+
+// ============================================================================
+// Decimal aliases (feature `rust_decimal`)
+// ============================================================================
+
+/// A non-negative decimal amount with at most two fractional digits
+#[cfg(feature = "rust_decimal")]
+pub type Money = Refined<rust_decimal::Decimal, And<NonNegativeAmount, MaxScale<2>>>;
+
+/// A string holding a three-letter currency code, such as `USD`
+#[cfg(feature = "rust_decimal")]
+pub type CurrencyCodeString = Refined<String, CurrencyCode>;
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -210,6 +272,9 @@ mod tests {
         assert!(NonEmptyTrimmedString::new("hello".to_string()).is_ok());
         assert!(NonEmptyTrimmedString::new("".to_string()).is_err());
         assert!(NonEmptyTrimmedString::new("  hello  ".to_string()).is_err());
+
+        assert!(NonEmptyStr::new("hello").is_ok());
+        assert!(NonEmptyStr::new("").is_err());
     }
 
     #[test]
@@ -0,0 +1,114 @@
+//! Zero-copy refined views over borrowed data
+//!
+//! The built-in string predicates work for `String`, `&str`, and `Cow<str>`,
+//! and the collection size predicates work for `&[T]` as well as `Vec<T>`.
+//! That makes it possible to validate request data in place and keep a
+//! `Refined<&str, P>` for read-only handling, only allocating when an owned
+//! value is needed.
+//!
+//! `to_owned_refined` converts a borrowed refined value to its owned form
+//! without re-checking the predicate. This relies on the predicate judging
+//! the borrowed and owned forms the same way, which holds for every built-in
+//! predicate; custom predicates implemented for both forms should keep them
+//! consistent.
+//!
+//! # Example
+//!
+//! ```rust
+//! use stillwater::refined::{And, MaxLength, NonEmpty, Refined};
+//!
+//! type UsernameRef<'a> = Refined<&'a str, And<NonEmpty, MaxLength<16>>>;
+//! type Username = Refined<String, And<NonEmpty, MaxLength<16>>>;
+//!
+//! let body = String::from("alice");
+//! let view = UsernameRef::new(body.as_str()).unwrap(); // no allocation
+//! assert_eq!(view.len(), 5);
+//!
+//! let owned: Username = view.to_owned_refined();
+//! assert_eq!(owned.get(), "alice");
+//! ```
+
+use alloc::borrow::Cow;
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use super::{Predicate, Refined};
+
+impl<'a, P> Refined<&'a str, P>
+where
+    P: Predicate<&'a str> + Predicate<String>,
+{
+    /// Copy into a `Refined<String, P>` without re-checking the predicate.
+    pub fn to_owned_refined(&self) -> Refined<String, P> {
+        Refined::new_unchecked((*self.get()).to_owned())
+    }
+}
+
+impl<'a, P> Refined<Cow<'a, str>, P>
+where
+    P: Predicate<Cow<'a, str>> + Predicate<String>,
+{
+    /// Convert into a `Refined<String, P>` without re-checking the
+    /// predicate, copying only if the data is borrowed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::borrow::Cow;
+    /// use stillwater::refined::{Refined, Trimmed};
+    ///
+    /// let input = "  padded ";
+    /// let cleaned: Cow<str> = Cow::Borrowed(input.trim());
+    /// let refined = Refined::<Cow<str>, Trimmed>::new(cleaned).unwrap();
+    /// assert_eq!(refined.to_owned_refined().get(), "padded");
+    /// ```
+    pub fn to_owned_refined(self) -> Refined<String, P> {
+        Refined::new_unchecked(self.into_inner().into_owned())
+    }
+}
+
+impl<'a, T, P> Refined<&'a [T], P>
+where
+    T: Clone,
+    P: Predicate<&'a [T]> + Predicate<Vec<T>>,
+{
+    /// Copy into a `Refined<Vec<T>, P>` without re-checking the predicate.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use stillwater::refined::{MaxSize, Refined};
+    ///
+    /// let ids = [3, 1, 4];
+    /// let batch = Refined::<&[i32], MaxSize<10>>::new(&ids).unwrap();
+    /// assert_eq!(batch.to_owned_refined().into_inner(), vec![3, 1, 4]);
+    /// ```
+    pub fn to_owned_refined(&self) -> Refined<Vec<T>, P> {
+        Refined::new_unchecked(self.get().to_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::refined::{MinLength, MinSize, NonEmpty, Refined, Trimmed};
+    use alloc::borrow::Cow;
+
+    #[test]
+    fn test_borrowed_predicates_match_owned() {
+        assert!(Refined::<&str, Trimmed>::new(" x").is_err());
+        assert!(Refined::<&str, MinLength<2>>::new("x").is_err());
+        assert!(Refined::<Cow<str>, NonEmpty>::new(Cow::Owned(String::new())).is_err());
+
+        let empty: &[u8] = &[];
+        assert!(Refined::<&[u8], NonEmpty>::new(empty).is_err());
+        assert!(Refined::<&[u8], MinSize<1>>::new(&[1]).is_ok());
+    }
+
+    #[test]
+    fn test_to_owned_refined() {
+        let view = Refined::<&str, NonEmpty>::new("ok").unwrap();
+        let owned: Refined<String, NonEmpty> = view.to_owned_refined();
+        assert_eq!(owned.into_inner(), "ok");
+    }
+}
@@ -5,6 +5,7 @@
 //! - [`And<A, B>`]: Both predicates must hold
 //! - [`Or<A, B>`]: At least one predicate must hold
 //! - [`Not<A>`]: Predicate must NOT hold
+//! - [`Each<P>`]: Every element of a `Vec` must satisfy `P`
 //!
 //! # Example
 //!
@@ -19,10 +20,11 @@
 //! assert!(ValidUsername::new("  alice  ".to_string()).is_err());
 //! ```
 
-use std::fmt;
-use std::marker::PhantomData;
+use alloc::vec::Vec;
+use core::fmt;
+use core::marker::PhantomData;
 
-use super::Predicate;
+use super::{Predicate, Refined};
 
 /// Both predicates must hold
 ///
@@ -49,8 +51,8 @@ impl<A, B> fmt::Debug for And<A, B> {
         write!(
             f,
             "And<{}, {}>",
-            std::any::type_name::<A>(),
-            std::any::type_name::<B>()
+            core::any::type_name::<A>(),
+            core::any::type_name::<B>()
         )
     }
 }
@@ -100,7 +102,7 @@ impl<A: fmt::Display, B: fmt::Display> fmt::Display for AndError<A, B> {
     }
 }
 
-impl<A: std::error::Error + 'static, B: std::error::Error + 'static> std::error::Error
+impl<A: core::error::Error + 'static, B: core::error::Error + 'static> core::error::Error
     for AndError<A, B>
 {
 }
@@ -134,8 +136,8 @@ impl<A, B> fmt::Debug for Or<A, B> {
         write!(
             f,
             "Or<{}, {}>",
-            std::any::type_name::<A>(),
-            std::any::type_name::<B>()
+            core::any::type_name::<A>(),
+            core::any::type_name::<B>()
         )
     }
 }
@@ -172,7 +174,7 @@ impl<A: fmt::Display, B: fmt::Display> fmt::Display for OrError<A, B> {
     }
 }
 
-impl<A: std::error::Error + 'static, B: std::error::Error + 'static> std::error::Error
+impl<A: core::error::Error + 'static, B: core::error::Error + 'static> core::error::Error
     for OrError<A, B>
 {
 }
@@ -203,7 +205,7 @@ pub struct Not<A>(PhantomData<A>);
 
 impl<A> fmt::Debug for Not<A> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "Not<{}>", std::any::type_name::<A>())
+        write!(f, "Not<{}>", core::any::type_name::<A>())
     }
 }
 
@@ -232,7 +234,157 @@ impl fmt::Display for NotError {
     }
 }
 
-impl std::error::Error for NotError {}
+impl core::error::Error for NotError {}
+
+/// Every element must satisfy the predicate
+///
+/// The `Each` combinator lifts an element predicate to `Vec<T>`. It checks
+/// every element and reports all failures together with their indices.
+///
+/// # Example
+///
+/// ```rust
+/// use stillwater::refined::{Each, Positive, Refined};
+///
+/// type Quantities = Refined<Vec<i32>, Each<Positive>>;
+///
+/// assert!(Quantities::new(vec![1, 2, 3]).is_ok());
+///
+/// let err = Quantities::new(vec![1, 0, 3, -4]).unwrap_err();
+/// assert_eq!(err.indices(), vec![1, 3]);
+/// ```
+#[derive(Clone, Copy, Default)]
+pub struct Each<P>(PhantomData<P>);
+
+impl<P> fmt::Debug for Each<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Each<{}>", core::any::type_name::<P>())
+    }
+}
+
+impl<T, P> Predicate<Vec<T>> for Each<P>
+where
+    T: Send + Sync + 'static,
+    P: Predicate<T>,
+{
+    type Error = EachError<P::Error>;
+
+    fn check(value: &Vec<T>) -> Result<(), Self::Error> {
+        let errors: Vec<_> = value
+            .iter()
+            .enumerate()
+            .filter_map(|(i, item)| P::check(item).err().map(|e| (i, e)))
+            .collect();
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(EachError(errors))
+        }
+    }
+
+    fn description() -> &'static str {
+        "every element must satisfy the predicate"
+    }
+}
+
+impl<T, P> Refined<Vec<T>, Each<P>>
+where
+    T: Send + Sync + 'static,
+    P: Predicate<T>,
+{
+    /// Split into individually refined elements.
+    ///
+    /// No checks are repeated: every element already satisfies `P`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use stillwater::refined::{Each, Positive, Refined};
+    ///
+    /// let all = Refined::<Vec<i32>, Each<Positive>>::new(vec![4, 2]).unwrap();
+    /// let items: Vec<Refined<i32, Positive>> = all.into_elements();
+    /// assert_eq!(*items[0].get(), 4);
+    /// ```
+    pub fn into_elements(self) -> Vec<Refined<T, P>> {
+        self.into_inner()
+            .into_iter()
+            .map(Refined::new_unchecked)
+            .collect()
+    }
+}
+
+/// Error type for Each combinator: the failing elements' indices and errors
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EachError<E>(pub Vec<(usize, E)>);
+
+impl<E> EachError<E> {
+    /// Indices of the elements that failed, in ascending order.
+    pub fn indices(&self) -> Vec<usize> {
+        self.0.iter().map(|(i, _)| *i).collect()
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for EachError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (n, (i, e)) in self.0.iter().enumerate() {
+            if n > 0 {
+                write!(f, "; ")?;
+            }
+            write!(f, "[{}]: {}", i, e)?;
+        }
+        Ok(())
+    }
+}
+
+impl<E: core::error::Error + 'static> core::error::Error for EachError<E> {}
+
+/// Extension trait for collecting iterators into refined values
+pub trait RefinedIteratorExt: Iterator + Sized {
+    /// Refine every item with `P`, collecting all failures.
+    ///
+    /// Returns every refined item if all pass, or an [`EachError`] with the
+    /// index and error of each failing item.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use stillwater::refined::{NonEmpty, Refined, RefinedIteratorExt};
+    ///
+    /// let tags = vec!["rust", "", "fp", ""].into_iter().map(String::from);
+    /// let err = tags.try_collect_refined::<NonEmpty>().unwrap_err();
+    /// assert_eq!(err.indices(), vec![1, 3]);
+    ///
+    /// let ok: Vec<Refined<String, NonEmpty>> = vec!["a".to_string()]
+    ///     .into_iter()
+    ///     .try_collect_refined()
+    ///     .unwrap();
+    /// assert_eq!(ok.len(), 1);
+    /// ```
+    #[allow(clippy::type_complexity)]
+    fn try_collect_refined<P>(self) -> Result<Vec<Refined<Self::Item, P>>, EachError<P::Error>>
+    where
+        P: Predicate<Self::Item>,
+    {
+        let mut refined = Vec::new();
+        let mut errors = Vec::new();
+        for (i, item) in self.enumerate() {
+            match Refined::new(item) {
+                Ok(value) if errors.is_empty() => refined.push(value),
+                Ok(_) => {}
+                Err(e) => errors.push((i, e)),
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(refined)
+        } else {
+            Err(EachError(errors))
+        }
+    }
+}
+
+impl<I: Iterator> RefinedIteratorExt for I {}
 
 #[cfg(test)]
 mod tests {
@@ -354,6 +506,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_each_reports_all_failures() {
+        type Names = Refined<Vec<String>, Each<And<NonEmpty, Trimmed>>>;
+        let err = Names::new(vec!["ok".into(), "".into(), " x".into()]).unwrap_err();
+        assert_eq!(err.indices(), vec![1, 2]);
+        assert!(matches!(err.0[1].1, AndError::Second(_)));
+        assert!(Names::new(vec![]).is_ok());
+    }
+
+    #[test]
+    fn test_each_error_display() {
+        let err = EachError(vec![(0, "bad"), (2, "worse")]);
+        assert_eq!(format!("{}", err), "[0]: bad; [2]: worse");
+    }
+
+    #[test]
+    fn test_try_collect_refined() {
+        let values = (1..=3).try_collect_refined::<Positive>().unwrap();
+        assert_eq!(values.iter().map(|v| *v.get()).sum::<i32>(), 6);
+
+        let err = [3, -1, 0].into_iter().try_collect_refined::<Positive>();
+        assert_eq!(err.unwrap_err().indices(), vec![1, 2]);
+    }
+
     #[test]
     fn test_not_error_display() {
         let err = NotError("positive number (> 0)");
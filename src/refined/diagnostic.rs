@@ -0,0 +1,157 @@
+//! Rendering field errors against their source with [`miette`].
+//!
+//! Validators for parsed input, such as config files, can record where each
+//! bad value came from with [`FieldError::with_span`] or
+//! [`ValidationFieldExt::with_field_at`](super::ValidationFieldExt::with_field_at).
+//! [`SourceDiagnostic`] pairs those errors with the source text so a miette
+//! report handler can show an annotated snippet pointing at each value.
+//!
+//! Requires the `miette` feature.
+//!
+//! # Example
+//!
+//! ```rust
+//! use stillwater::Validation;
+//! use stillwater::refined::{InRange, Refined, SourceDiagnostic, Span, ValidationFieldExt};
+//!
+//! let source = "port = 0\nworkers = 64\n";
+//!
+//! let port = Refined::<u16, InRange<1, 65535>>::validate(0).with_field_at("port", Span::new(7, 1));
+//! let workers = Refined::<u8, InRange<1, 16>>::validate(64).with_field_at("workers", Span::new(19, 2));
+//!
+//! let result = port.map_err(|e| vec![e]).and(workers.map_err(|e| vec![e]));
+//! if let Validation::Failure(errors) = result {
+//!     let report = SourceDiagnostic::new("app.toml", source, errors);
+//!     assert_eq!(report.to_string(), "2 invalid fields in app.toml");
+//!     // With miette's `fancy` feature, `miette::Report::new(report)` prints
+//!     // the source with each bad value underlined.
+//! }
+//! ```
+
+use std::fmt;
+
+use miette::{Diagnostic, LabeledSpan, NamedSource, SourceCode};
+
+use super::validation::FieldError;
+
+/// Field errors together with the source text they refer to.
+///
+/// Implements [`miette::Diagnostic`]: every error with a
+/// [`span`](FieldError::span) becomes a label on the source, and errors
+/// without one are listed in the help text.
+pub struct SourceDiagnostic<E> {
+    errors: Vec<FieldError<E>>,
+    source: NamedSource<String>,
+}
+
+impl<E> SourceDiagnostic<E> {
+    /// Errors found in `source`, which is shown to users as `name`.
+    pub fn new(
+        name: impl AsRef<str>,
+        source: impl Into<String>,
+        errors: impl IntoIterator<Item = FieldError<E>>,
+    ) -> Self {
+        SourceDiagnostic {
+            errors: errors.into_iter().collect(),
+            source: NamedSource::new(name, source.into()),
+        }
+    }
+
+    /// The field errors being reported.
+    pub fn errors(&self) -> &[FieldError<E>] {
+        &self.errors
+    }
+
+    /// Consume the diagnostic, returning the field errors.
+    pub fn into_errors(self) -> Vec<FieldError<E>> {
+        self.errors
+    }
+}
+
+impl<E: fmt::Debug> fmt::Debug for SourceDiagnostic<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SourceDiagnostic")
+            .field("name", &self.source.name())
+            .field("errors", &self.errors)
+            .finish()
+    }
+}
+
+impl<E> fmt::Display for SourceDiagnostic<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.errors.len() {
+            1 => write!(f, "invalid field in {}", self.source.name()),
+            n => write!(f, "{} invalid fields in {}", n, self.source.name()),
+        }
+    }
+}
+
+impl<E: fmt::Debug + fmt::Display> std::error::Error for SourceDiagnostic<E> {}
+
+impl<E: fmt::Debug + fmt::Display> Diagnostic for SourceDiagnostic<E> {
+    fn source_code(&self) -> Option<&dyn SourceCode> {
+        Some(&self.source)
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        let mut labels = self.errors.iter().filter_map(|e| {
+            let span = e.span?;
+            Some(LabeledSpan::new(Some(e.to_string()), span.offset, span.len))
+        });
+        let first = labels.next()?;
+        Some(Box::new(std::iter::once(first).chain(labels)))
+    }
+
+    fn help(&self) -> Option<Box<dyn fmt::Display + '_>> {
+        let unlocated: Vec<String> = self
+            .errors
+            .iter()
+            .filter(|e| e.span.is_none())
+            .map(ToString::to_string)
+            .collect();
+        if unlocated.is_empty() {
+            None
+        } else {
+            Some(Box::new(unlocated.join("\n")))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::refined::Span;
+    use miette::{NarratableReportHandler, ReportHandler};
+
+    struct Render<'a>(&'a dyn Diagnostic);
+
+    impl fmt::Display for Render<'_> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            NarratableReportHandler::new().debug(self.0, f)
+        }
+    }
+
+    #[test]
+    fn test_labels_point_at_spans() {
+        let source = "name = \"\"\nport = 0\n";
+        let diagnostic = SourceDiagnostic::new(
+            "app.toml",
+            source,
+            vec![
+                FieldError::new("name", "cannot be empty").with_span(Span::new(7, 2)),
+                FieldError::new("port", "must be positive").with_span(Span::new(17, 1)),
+                FieldError::new("owner", "is required"),
+            ],
+        );
+
+        let labels: Vec<_> = diagnostic.labels().unwrap().collect();
+        assert_eq!(labels.len(), 2);
+        assert_eq!(labels[1].offset(), 17);
+        assert_eq!(labels[1].label(), Some("port: must be positive"));
+
+        let rendered = Render(&diagnostic).to_string();
+        assert!(rendered.contains("3 invalid fields in app.toml"));
+        assert!(rendered.contains("port = 0"));
+        assert!(rendered.contains("owner: is required"));
+    }
+}
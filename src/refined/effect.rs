@@ -3,6 +3,11 @@
 //! This module provides integration between refined types and stillwater's
 //! Effect system for validation at effect boundaries.
 //!
+//! [`EffectExt::refine`](crate::effect::EffectExt::refine) and
+//! [`EffectExt::refine_accumulate`](crate::effect::EffectExt::refine_accumulate)
+//! promote the output of an effect, such as a row read from a database, to
+//! a refined type inline in a pipeline.
+//!
 //! # Example
 //!
 //! ```rust,ignore
@@ -14,7 +19,11 @@
 //!     .and_then(|s| refine::<_, NonEmpty, ()>(s).map_err(|e| e.to_string()));
 //! ```
 
+use std::marker::PhantomData;
+
 use super::{Predicate, Refined};
+use crate::effect::Effect;
+use crate::Validation;
 
 /// Type alias for the validation effect returned by refined type operations.
 ///
@@ -46,7 +55,7 @@ where
     /// ```
     pub fn validate_effect<Env>(value: T) -> ValidationEffect<T, P, Env>
     where
-        Env: Clone + Send + Sync,
+        Env: Send + Sync,
     {
         crate::from_fn(Box::new(move |_env: &Env| Self::new(value))
             as Box<dyn FnOnce(&Env) -> Result<Self, P::Error> + Send>)
@@ -73,7 +82,7 @@ where
     T: Send + 'static,
     P: Predicate<T>,
     P::Error: Send + 'static,
-    Env: Clone + Send + Sync,
+    Env: Send + Sync,
 {
     crate::from_fn(Box::new(move |_env: &Env| Refined::new(value))
         as Box<dyn FnOnce(&Env) -> Result<Refined<T, P>, P::Error> + Send>)
@@ -102,11 +111,104 @@ where
     T: Send + 'static,
     P: Predicate<T>,
     E: Send + 'static,
-    Env: Clone + Send + Sync,
+    Env: Send + Sync,
 {
     crate::pure(refined)
 }
 
+/// Refine combinator - checks an effect's output against a predicate.
+///
+/// Created by [`EffectExt::refine`](crate::effect::EffectExt::refine).
+/// Predicate failures are converted into the effect's error type.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use stillwater::effect::prelude::*;
+/// use stillwater::refined::{NonEmpty, Refined};
+///
+/// let effect = pure::<_, String, ()>("alice".to_string()).refine::<NonEmpty>();
+/// let name: Refined<String, NonEmpty> = effect.execute(&()).await?;
+/// ```
+pub struct Refine<Inner, P> {
+    pub(crate) inner: Inner,
+    pub(crate) _predicate: PhantomData<P>,
+}
+
+impl<Inner, P> std::fmt::Debug for Refine<Inner, P> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Refine")
+            .field("inner", &"<effect>")
+            .field("predicate", &std::any::type_name::<P>())
+            .finish()
+    }
+}
+
+impl<Inner, P> Effect for Refine<Inner, P>
+where
+    Inner: Effect,
+    P: Predicate<Inner::Output>,
+    P::Error: Into<Inner::Error>,
+{
+    type Output = Refined<Inner::Output, P>;
+    type Error = Inner::Error;
+    type Env = Inner::Env;
+
+    async fn run(self, env: &Self::Env) -> Result<Self::Output, Self::Error> {
+        let value = self.inner.run(env).await?;
+        Refined::new(value).map_err(Into::into)
+    }
+}
+
+/// RefineAccumulate combinator - checks an effect's output against a
+/// predicate, keeping predicate failures as a [`Validation`].
+///
+/// Created by
+/// [`EffectExt::refine_accumulate`](crate::effect::EffectExt::refine_accumulate).
+/// The effect only fails with its own errors; predicate failures end up in
+/// the `Validation`, ready to combine with other validations.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use stillwater::effect::prelude::*;
+/// use stillwater::refined::{NonEmpty, Positive};
+///
+/// let name = fetch_name().refine_accumulate::<NonEmpty>();
+/// let age = fetch_age().refine_accumulate::<Positive>();
+///
+/// let user = name.zip(age).map(|(n, a)| n.and(a));
+/// ```
+pub struct RefineAccumulate<Inner, P> {
+    pub(crate) inner: Inner,
+    pub(crate) _predicate: PhantomData<P>,
+}
+
+impl<Inner, P> std::fmt::Debug for RefineAccumulate<Inner, P> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RefineAccumulate")
+            .field("inner", &"<effect>")
+            .field("predicate", &std::any::type_name::<P>())
+            .finish()
+    }
+}
+
+impl<Inner, P> Effect for RefineAccumulate<Inner, P>
+where
+    Inner: Effect,
+    P: Predicate<Inner::Output>,
+    P::Error: Send,
+{
+    type Output = Validation<Refined<Inner::Output, P>, Vec<P::Error>>;
+    type Error = Inner::Error;
+    type Env = Inner::Env;
+
+    async fn run(self, env: &Self::Env) -> Result<Self::Output, Self::Error> {
+        let value = self.inner.run(env).await?;
+        Ok(Refined::validate_vec(value))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -165,6 +267,30 @@ mod tests {
         assert_eq!(result, Ok(5));
     }
 
+    #[tokio::test]
+    async fn test_refine_combinator() {
+        let ok = pure::<_, String, ()>(7).refine::<Positive>();
+        assert_eq!(*ok.run(&()).await.unwrap().get(), 7);
+
+        let err = pure::<_, String, ()>(-7).refine::<Positive>();
+        assert_eq!(err.run(&()).await.unwrap_err(), "value must be positive");
+
+        let io_err = fail::<i32, String, ()>("db down".to_string()).refine::<Positive>();
+        assert_eq!(io_err.run(&()).await.unwrap_err(), "db down");
+    }
+
+    #[tokio::test]
+    async fn test_refine_accumulate_combines_failures() {
+        let name = pure::<_, String, ()>(String::new()).refine_accumulate::<NonEmpty>();
+        let age = pure::<_, String, ()>(-1).refine_accumulate::<Positive>();
+
+        let result = name.zip(age).map(|(n, a)| n.and(a)).run(&()).await;
+        assert_eq!(
+            result.unwrap(),
+            Validation::Failure(vec!["string cannot be empty", "value must be positive"])
+        );
+    }
+
     #[tokio::test]
     async fn test_effect_chain_failure() {
         let effect = pure::<_, &str, ()>("".to_string())
@@ -101,26 +101,49 @@
 //! ```
 
 mod aliases;
+mod borrowed;
 mod combinators;
+#[cfg(feature = "miette")]
+mod diagnostic;
+#[cfg(feature = "std")]
 mod effect;
 pub mod predicates;
+mod sanitize;
 #[cfg(feature = "serde")]
 mod serde_impl;
 mod validation;
 
-use std::cmp::Ordering;
-use std::fmt;
-use std::hash::{Hash, Hasher};
-use std::marker::PhantomData;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::str::FromStr;
 
 // Re-export core types
 pub use aliases::*;
-pub use combinators::{And, AndError, Not, NotError, Or, OrError};
-pub use effect::{pure_refined, refine};
-pub use predicates::collection::{MaxSize, MinSize};
+pub use combinators::{
+    And, AndError, Each, EachError, Not, NotError, Or, OrError, RefinedIteratorExt,
+};
+#[cfg(feature = "miette")]
+pub use diagnostic::SourceDiagnostic;
+#[cfg(feature = "std")]
+pub use effect::{pure_refined, refine, Refine, RefineAccumulate};
+pub use predicates::collection::{KeyedError, KeysSatisfy, MaxSize, MinSize, ValuesSatisfy};
+#[cfg(feature = "rust_decimal")]
+pub use predicates::decimal::{CurrencyCode, MaxScale, NonNegativeAmount};
+#[cfg(feature = "url")]
+pub use predicates::net::ValidUrl;
+pub use predicates::net::{ValidHostname, ValidIpAddr, ValidPort, ValidUuid};
 pub use predicates::numeric::{InRange, Negative, NonNegative, NonZero, Positive};
-pub use predicates::string::{MaxLength, MinLength, NonEmpty, Trimmed};
-pub use validation::{FieldError, RefinedValidationExt, ValidationFieldExt};
+#[cfg(feature = "unicode")]
+pub use predicates::string::NormalizedNfc;
+pub use predicates::string::{Lowercase, MaxLength, MinLength, NonEmpty, Trimmed};
+#[cfg(feature = "chrono")]
+pub use predicates::time::{InFuture, InPast, ValidRfc3339, WithinDays};
+pub use sanitize::{Sanitize, Sanitized};
+pub use validation::{FieldError, RefinedValidationExt, Span, ValidationFieldExt};
 
 /// A predicate that constrains values of type T.
 ///
@@ -161,7 +184,7 @@ pub trait Predicate<T>: Send + Sync + 'static {
 
     /// Human-readable description of what this predicate requires
     fn description() -> &'static str {
-        std::any::type_name::<Self>()
+        core::any::type_name::<Self>()
     }
 }
 
@@ -313,7 +336,7 @@ impl<T: fmt::Debug, P: Predicate<T>> fmt::Debug for Refined<T, P> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Refined")
             .field("value", &self.value)
-            .field("predicate", &std::any::type_name::<P>())
+            .field("predicate", &core::any::type_name::<P>())
             .finish()
     }
 }
@@ -366,7 +389,7 @@ impl<T, P: Predicate<T>> AsRef<T> for Refined<T, P> {
 }
 
 // Deref for ergonomic access
-impl<T, P: Predicate<T>> std::ops::Deref for Refined<T, P> {
+impl<T, P: Predicate<T>> core::ops::Deref for Refined<T, P> {
     type Target = T;
 
     fn deref(&self) -> &T {
@@ -381,6 +404,105 @@ impl<T: fmt::Display, P: Predicate<T>> fmt::Display for Refined<T, P> {
     }
 }
 
+// TryFrom for generic conversion code and `#[serde(try_from = "...")]`.
+//
+// A blanket `impl<T> TryFrom<T> for Refined<T, P>` overlaps with core's
+// `impl<T, U: Into<T>> TryFrom<U> for T`, so the impls are per type.
+macro_rules! impl_try_from {
+    ($(<$($gen:ident $(: $bound:path)?),*> $ty:ty),+ $(,)?) => {
+        $(
+            impl<$($gen $(: $bound)?,)* P: Predicate<$ty>> TryFrom<$ty> for Refined<$ty, P> {
+                type Error = P::Error;
+
+                fn try_from(value: $ty) -> Result<Self, Self::Error> {
+                    Self::new(value)
+                }
+            }
+        )+
+    };
+}
+
+impl_try_from!(
+    <> i8, <> i16, <> i32, <> i64, <> i128, <> isize,
+    <> u8, <> u16, <> u32, <> u64, <> u128, <> usize,
+    <> f32, <> f64, <> bool, <> char, <> String,
+    <E> Vec<E>,
+    <K, V> alloc::collections::BTreeMap<K, V>,
+);
+
+#[cfg(feature = "std")]
+impl_try_from!(<K, V, S> std::collections::HashMap<K, V, S>);
+
+impl<'a, P: Predicate<&'a str>> TryFrom<&'a str> for Refined<&'a str, P> {
+    type Error = P::Error;
+
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+#[cfg(feature = "rust_decimal")]
+impl_try_from!(<> rust_decimal::Decimal);
+
+#[cfg(feature = "chrono")]
+impl_try_from!(<Tz: chrono::TimeZone> chrono::DateTime<Tz>);
+
+/// Error from parsing a refined value with [`str::parse`].
+///
+/// Parsing fails either because the string isn't a valid `T`, or because
+/// the parsed value doesn't satisfy the predicate.
+///
+/// # Example
+///
+/// ```rust
+/// use stillwater::refined::{Positive, Refined, RefinedParseError};
+///
+/// type Port = Refined<u16, stillwater::refined::NonZero>;
+/// type Count = Refined<i32, Positive>;
+///
+/// let port: Port = "8080".parse().unwrap();
+/// assert_eq!(*port.get(), 8080);
+///
+/// assert!(matches!("abc".parse::<Count>(), Err(RefinedParseError::Parse(_))));
+/// assert_eq!(
+///     "-3".parse::<Count>().unwrap_err(),
+///     RefinedParseError::Predicate("value must be positive")
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RefinedParseError<PE, E> {
+    /// The string could not be parsed as the underlying type
+    Parse(PE),
+    /// The parsed value failed the predicate
+    Predicate(E),
+}
+
+impl<PE: fmt::Display, E: fmt::Display> fmt::Display for RefinedParseError<PE, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RefinedParseError::Parse(e) => write!(f, "parse error: {}", e),
+            RefinedParseError::Predicate(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl<PE, E> core::error::Error for RefinedParseError<PE, E>
+where
+    PE: core::error::Error + 'static,
+    E: core::error::Error + 'static,
+{
+}
+
+// FromStr so refined types work with `parse()` and CLI argument parsers
+impl<T: FromStr, P: Predicate<T>> FromStr for Refined<T, P> {
+    type Err = RefinedParseError<T::Err, P::Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value = s.parse().map_err(RefinedParseError::Parse)?;
+        Self::new(value).map_err(RefinedParseError::Predicate)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -506,6 +628,30 @@ mod tests {
         assert_eq!(format!("{}", n), "42");
     }
 
+    #[test]
+    fn test_try_from() {
+        let n: EvenI32 = 42.try_into().unwrap();
+        assert_eq!(*n.get(), 42);
+        assert_eq!(EvenI32::try_from(41).unwrap_err(), "value must be even");
+
+        let name = Refined::<&str, NonEmpty>::try_from("Ada").unwrap();
+        assert_eq!(*name.get(), "Ada");
+        assert!(Refined::<Vec<u8>, NonEmpty>::try_from(vec![]).is_err());
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(*"42".parse::<EvenI32>().unwrap().get(), 42);
+        assert_eq!(
+            "41".parse::<EvenI32>().unwrap_err(),
+            RefinedParseError::Predicate("value must be even")
+        );
+
+        let err = "forty".parse::<EvenI32>().unwrap_err();
+        assert!(matches!(err, RefinedParseError::Parse(_)));
+        assert!(err.to_string().starts_with("parse error: "));
+    }
+
     #[test]
     fn test_debug() {
         let n = EvenI32::new(42).unwrap();
@@ -1,9 +1,11 @@
 //! Collection predicates for refined types
 //!
 //! This module provides predicates for constraining collections:
-//! - [`NonEmpty`] from string module also works for `Vec<T>`
-//! - [`MaxSize<N>`]: Collection size <= N
-//! - [`MinSize<N>`]: Collection size >= N
+//! - [`NonEmpty`] from string module also works for `Vec<T>` and `&[T]`
+//! - [`MaxSize<N>`]: Collection size <= N (`Vec<T>` or `&[T]`)
+//! - [`MinSize<N>`]: Collection size >= N (`Vec<T>` or `&[T]`)
+//! - [`KeysSatisfy<P>`]: Every key of a `HashMap`/`BTreeMap` satisfies `P`
+//! - [`ValuesSatisfy<P>`]: Every value of a `HashMap`/`BTreeMap` satisfies `P`
 //!
 //! # Example
 //!
@@ -20,6 +22,17 @@
 //! let small = SmallList::<i32>::new(vec![1, 2, 3]).unwrap();
 //! ```
 
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+#[cfg(feature = "std")]
+use core::hash::BuildHasher;
+use core::marker::PhantomData;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
 use super::super::Predicate;
 use super::string::NonEmpty;
 
@@ -40,6 +53,22 @@ impl<T: Send + Sync + 'static> Predicate<Vec<T>> for NonEmpty {
     }
 }
 
+impl<'a, T> Predicate<&'a [T]> for NonEmpty {
+    type Error = &'static str;
+
+    fn check(value: &&'a [T]) -> Result<(), Self::Error> {
+        if value.is_empty() {
+            Err("collection cannot be empty")
+        } else {
+            Ok(())
+        }
+    }
+
+    fn description() -> &'static str {
+        "non-empty collection"
+    }
+}
+
 /// Collection size must be at most N
 ///
 /// # Example
@@ -75,6 +104,26 @@ impl<const N: usize, T: Send + Sync + 'static> Predicate<Vec<T>> for MaxSize<N>
     }
 }
 
+impl<'a, const N: usize, T> Predicate<&'a [T]> for MaxSize<N> {
+    type Error = String;
+
+    fn check(value: &&'a [T]) -> Result<(), Self::Error> {
+        if value.len() <= N {
+            Ok(())
+        } else {
+            Err(format!(
+                "collection size {} exceeds maximum {}",
+                value.len(),
+                N
+            ))
+        }
+    }
+
+    fn description() -> &'static str {
+        "collection with maximum size"
+    }
+}
+
 /// Collection size must be at least N
 ///
 /// # Example
@@ -110,6 +159,201 @@ impl<const N: usize, T: Send + Sync + 'static> Predicate<Vec<T>> for MinSize<N>
     }
 }
 
+impl<'a, const N: usize, T> Predicate<&'a [T]> for MinSize<N> {
+    type Error = String;
+
+    fn check(value: &&'a [T]) -> Result<(), Self::Error> {
+        if value.len() >= N {
+            Ok(())
+        } else {
+            Err(format!(
+                "collection size {} is less than minimum {}",
+                value.len(),
+                N
+            ))
+        }
+    }
+
+    fn description() -> &'static str {
+        "collection with minimum size"
+    }
+}
+
+/// Every key of a map must satisfy the predicate
+///
+/// Works for `HashMap` and `BTreeMap`. Failures are reported per key in a
+/// [`KeyedError`].
+///
+/// # Example
+///
+/// ```rust
+/// use std::collections::BTreeMap;
+/// use stillwater::refined::{KeysSatisfy, NonEmpty, Refined};
+///
+/// type Labels = Refined<BTreeMap<String, String>, KeysSatisfy<NonEmpty>>;
+///
+/// let ok = BTreeMap::from([("team".to_string(), "core".to_string())]);
+/// assert!(Labels::new(ok).is_ok());
+///
+/// let bad = BTreeMap::from([(String::new(), "core".to_string())]);
+/// assert_eq!(Labels::new(bad).unwrap_err().keys(), vec![&String::new()]);
+/// ```
+#[derive(Clone, Copy, Default)]
+pub struct KeysSatisfy<P>(PhantomData<P>);
+
+impl<P> fmt::Debug for KeysSatisfy<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "KeysSatisfy<{}>", core::any::type_name::<P>())
+    }
+}
+
+/// Every value of a map must satisfy the predicate
+///
+/// Works for `HashMap` and `BTreeMap`. Failures are reported per key in a
+/// [`KeyedError`].
+///
+/// # Example
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use stillwater::refined::{And, KeysSatisfy, NonEmpty, NonZero, Refined, ValuesSatisfy};
+///
+/// type Limits = Refined<HashMap<String, u32>, And<KeysSatisfy<NonEmpty>, ValuesSatisfy<NonZero>>>;
+///
+/// let ok = HashMap::from([("requests".to_string(), 100)]);
+/// assert!(Limits::new(ok).is_ok());
+///
+/// let bad = HashMap::from([("requests".to_string(), 0)]);
+/// assert!(Limits::new(bad).is_err());
+/// ```
+#[derive(Clone, Copy, Default)]
+pub struct ValuesSatisfy<P>(PhantomData<P>);
+
+impl<P> fmt::Debug for ValuesSatisfy<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ValuesSatisfy<{}>", core::any::type_name::<P>())
+    }
+}
+
+/// Error type for map predicates: the offending keys and their errors
+///
+/// Entries appear in the map's iteration order, which is sorted for
+/// `BTreeMap` and unspecified for `HashMap`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyedError<K, E>(pub Vec<(K, E)>);
+
+impl<K, E> KeyedError<K, E> {
+    /// Keys of the entries that failed.
+    pub fn keys(&self) -> Vec<&K> {
+        self.0.iter().map(|(k, _)| k).collect()
+    }
+}
+
+impl<K: fmt::Debug, E: fmt::Display> fmt::Display for KeyedError<K, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (n, (k, e)) in self.0.iter().enumerate() {
+            if n > 0 {
+                write!(f, "; ")?;
+            }
+            write!(f, "[{:?}]: {}", k, e)?;
+        }
+        Ok(())
+    }
+}
+
+impl<K: fmt::Debug, E: core::error::Error + 'static> core::error::Error for KeyedError<K, E> {}
+
+fn check_entries<'a, K, T, E>(
+    entries: impl Iterator<Item = (&'a K, &'a T)>,
+    check: impl Fn(&T) -> Result<(), E>,
+) -> Result<(), KeyedError<K, E>>
+where
+    K: Clone + 'a,
+    T: 'a,
+{
+    let errors: Vec<_> = entries
+        .filter_map(|(k, item)| check(item).err().map(|e| (k.clone(), e)))
+        .collect();
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(KeyedError(errors))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K, V, S, P> Predicate<HashMap<K, V, S>> for KeysSatisfy<P>
+where
+    K: Clone + Send + Sync + 'static,
+    V: Send + Sync + 'static,
+    S: BuildHasher + Send + Sync + 'static,
+    P: Predicate<K>,
+{
+    type Error = KeyedError<K, P::Error>;
+
+    fn check(value: &HashMap<K, V, S>) -> Result<(), Self::Error> {
+        check_entries(value.keys().map(|k| (k, k)), P::check)
+    }
+
+    fn description() -> &'static str {
+        "every key must satisfy the predicate"
+    }
+}
+
+impl<K, V, P> Predicate<BTreeMap<K, V>> for KeysSatisfy<P>
+where
+    K: Clone + Send + Sync + 'static,
+    V: Send + Sync + 'static,
+    P: Predicate<K>,
+{
+    type Error = KeyedError<K, P::Error>;
+
+    fn check(value: &BTreeMap<K, V>) -> Result<(), Self::Error> {
+        check_entries(value.keys().map(|k| (k, k)), P::check)
+    }
+
+    fn description() -> &'static str {
+        "every key must satisfy the predicate"
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K, V, S, P> Predicate<HashMap<K, V, S>> for ValuesSatisfy<P>
+where
+    K: Clone + Send + Sync + 'static,
+    V: Send + Sync + 'static,
+    S: BuildHasher + Send + Sync + 'static,
+    P: Predicate<V>,
+{
+    type Error = KeyedError<K, P::Error>;
+
+    fn check(value: &HashMap<K, V, S>) -> Result<(), Self::Error> {
+        check_entries(value.iter(), P::check)
+    }
+
+    fn description() -> &'static str {
+        "every value must satisfy the predicate"
+    }
+}
+
+impl<K, V, P> Predicate<BTreeMap<K, V>> for ValuesSatisfy<P>
+where
+    K: Clone + Send + Sync + 'static,
+    V: Send + Sync + 'static,
+    P: Predicate<V>,
+{
+    type Error = KeyedError<K, P::Error>;
+
+    fn check(value: &BTreeMap<K, V>) -> Result<(), Self::Error> {
+        check_entries(value.iter(), P::check)
+    }
+
+    fn description() -> &'static str {
+        "every value must satisfy the predicate"
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -158,6 +402,30 @@ mod tests {
         assert!(AtLeastTwo::new(vec![1]).is_err());
     }
 
+    #[test]
+    fn test_values_satisfy_reports_keys() {
+        use crate::refined::predicates::numeric::Positive;
+
+        let config = BTreeMap::from([("a", 1), ("b", 0), ("c", -2)]);
+        let err = Refined::<_, ValuesSatisfy<Positive>>::new(config).unwrap_err();
+        assert_eq!(err.keys(), vec![&"b", &"c"]);
+        assert_eq!(
+            format!("{}", err),
+            "[\"b\"]: value must be positive; [\"c\"]: value must be positive"
+        );
+    }
+
+    #[test]
+    fn test_keys_satisfy_hash_map() {
+        let mut map = HashMap::new();
+        map.insert("x".to_string(), ());
+        assert!(Refined::<_, KeysSatisfy<NonEmpty>>::new(map.clone()).is_ok());
+
+        map.insert(String::new(), ());
+        let err = Refined::<_, KeysSatisfy<NonEmpty>>::new(map).unwrap_err();
+        assert_eq!(err.0, vec![(String::new(), "string cannot be empty")]);
+    }
+
     #[test]
     fn test_descriptions() {
         // Test NonEmpty for Vec (different description than for String)
@@ -0,0 +1,243 @@
+//! Decimal and money predicates for refined types (feature-gated)
+//!
+//! This module provides predicates for constraining `rust_decimal` amounts
+//! and currency codes. Feature-gated behind `#[cfg(feature = "rust_decimal")]`.
+//! - [`MaxScale<N>`]: Decimal has at most N fractional digits
+//! - [`NonNegativeAmount`]: Decimal >= 0
+//! - [`CurrencyCode`]: String is a three-letter ISO 4217 style code
+//!
+//! The [`Money`](crate::refined::Money) alias combines the first two, and
+//! refined decimals gain `checked_add`, `checked_sub`, and `checked_mul`
+//! helpers that re-check the predicate on the result.
+//!
+//! # Example
+//!
+//! ```rust
+//! use rust_decimal::Decimal;
+//! use stillwater::refined::{CurrencyCodeString, Money};
+//!
+//! let price = Money::new(Decimal::new(1999, 2)).unwrap(); // 19.99
+//! let total = price.checked_mul(Decimal::from(3)).unwrap();
+//! assert_eq!(*total.get(), Decimal::new(5997, 2));
+//!
+//! assert!(Money::new(Decimal::new(-1, 0)).is_err());
+//! assert!(CurrencyCodeString::new("EUR".to_string()).is_ok());
+//! ```
+
+use rust_decimal::Decimal;
+
+use super::super::{Predicate, Refined};
+
+/// Decimal must have at most N fractional digits
+///
+/// Trailing zeros are ignored, so `1.500` satisfies `MaxScale<2>`.
+///
+/// # Example
+///
+/// ```rust
+/// use rust_decimal::Decimal;
+/// use stillwater::refined::{Refined, MaxScale};
+///
+/// type Cents = Refined<Decimal, MaxScale<2>>;
+///
+/// assert!(Cents::new(Decimal::new(1050, 2)).is_ok()); // 10.50
+/// assert!(Cents::new(Decimal::new(10505, 3)).is_err()); // 10.505
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MaxScale<const N: u32>;
+
+impl<const N: u32> Predicate<Decimal> for MaxScale<N> {
+    type Error = String;
+
+    fn check(value: &Decimal) -> Result<(), Self::Error> {
+        let scale = value.normalize().scale();
+        if scale <= N {
+            Ok(())
+        } else {
+            Err(format!(
+                "decimal has {} fractional digits, more than {}",
+                scale, N
+            ))
+        }
+    }
+
+    fn description() -> &'static str {
+        "decimal with at most N fractional digits"
+    }
+}
+
+/// Decimal must be non-negative (>= 0)
+///
+/// # Example
+///
+/// ```rust
+/// use rust_decimal::Decimal;
+/// use stillwater::refined::{Refined, NonNegativeAmount};
+///
+/// type Balance = Refined<Decimal, NonNegativeAmount>;
+///
+/// assert!(Balance::new(Decimal::ZERO).is_ok());
+/// assert!(Balance::new(Decimal::new(-5, 1)).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NonNegativeAmount;
+
+impl Predicate<Decimal> for NonNegativeAmount {
+    type Error = &'static str;
+
+    fn check(value: &Decimal) -> Result<(), Self::Error> {
+        if value.is_sign_negative() && !value.is_zero() {
+            Err("amount must be non-negative")
+        } else {
+            Ok(())
+        }
+    }
+
+    fn description() -> &'static str {
+        "non-negative amount (>= 0)"
+    }
+}
+
+/// String must be a three-letter uppercase currency code, such as `USD`
+///
+/// Only the ISO 4217 format is checked, not membership in the list of
+/// assigned codes.
+///
+/// # Example
+///
+/// ```rust
+/// use stillwater::refined::{Refined, CurrencyCode};
+///
+/// type Currency = Refined<String, CurrencyCode>;
+///
+/// assert!(Currency::new("JPY".to_string()).is_ok());
+/// assert!(Currency::new("usd".to_string()).is_err());
+/// assert!(Currency::new("EURO".to_string()).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CurrencyCode;
+
+impl Predicate<String> for CurrencyCode {
+    type Error = &'static str;
+
+    fn check(value: &String) -> Result<(), Self::Error> {
+        if value.len() == 3 && value.bytes().all(|b| b.is_ascii_uppercase()) {
+            Ok(())
+        } else {
+            Err("currency code must be three uppercase ASCII letters")
+        }
+    }
+
+    fn description() -> &'static str {
+        "three-letter currency code"
+    }
+}
+
+impl<P: Predicate<Decimal>> Refined<Decimal, P> {
+    /// Add two refined decimals, re-checking the predicate.
+    ///
+    /// Returns `None` on overflow or if the sum doesn't satisfy the
+    /// predicate.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rust_decimal::Decimal;
+    /// use stillwater::refined::Money;
+    ///
+    /// let a = Money::new(Decimal::new(150, 2)).unwrap();
+    /// let b = Money::new(Decimal::new(275, 2)).unwrap();
+    /// assert_eq!(*a.checked_add(&b).unwrap().get(), Decimal::new(425, 2));
+    /// ```
+    pub fn checked_add(&self, other: &Self) -> Option<Self> {
+        self.get()
+            .checked_add(*other.get())
+            .and_then(|sum| Self::new(sum).ok())
+    }
+
+    /// Subtract a refined decimal, re-checking the predicate.
+    ///
+    /// Returns `None` on overflow or if the difference doesn't satisfy the
+    /// predicate, such as a [`Money`](crate::refined::Money) going negative.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rust_decimal::Decimal;
+    /// use stillwater::refined::Money;
+    ///
+    /// let balance = Money::new(Decimal::new(1000, 2)).unwrap();
+    /// let charge = Money::new(Decimal::new(2500, 2)).unwrap();
+    /// assert!(balance.checked_sub(&charge).is_none());
+    /// assert!(charge.checked_sub(&balance).is_some());
+    /// ```
+    pub fn checked_sub(&self, other: &Self) -> Option<Self> {
+        self.get()
+            .checked_sub(*other.get())
+            .and_then(|difference| Self::new(difference).ok())
+    }
+
+    /// Multiply by a plain decimal factor, re-checking the predicate.
+    ///
+    /// Returns `None` on overflow or if the product doesn't satisfy the
+    /// predicate. The product is not rounded: multiplying
+    /// [`Money`](crate::refined::Money) by a fractional rate can exceed
+    /// two fractional digits, so round the factor or the raw product first.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rust_decimal::Decimal;
+    /// use stillwater::refined::Money;
+    ///
+    /// let unit = Money::new(Decimal::new(125, 2)).unwrap();
+    /// assert_eq!(*unit.checked_mul(Decimal::from(4)).unwrap().get(), Decimal::from(5));
+    /// assert!(unit.checked_mul(Decimal::new(15, 1)).is_none()); // 1.875
+    /// ```
+    pub fn checked_mul(&self, factor: Decimal) -> Option<Self> {
+        self.get()
+            .checked_mul(factor)
+            .and_then(|product| Self::new(product).ok())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::refined::Money;
+
+    #[test]
+    fn test_max_scale_ignores_trailing_zeros() {
+        assert!(<MaxScale<2> as Predicate<Decimal>>::check(&Decimal::new(1500, 3)).is_ok());
+        assert_eq!(
+            <MaxScale<0> as Predicate<Decimal>>::check(&Decimal::new(15, 1)),
+            Err("decimal has 1 fractional digits, more than 0".to_string())
+        );
+    }
+
+    #[test]
+    fn test_non_negative_amount_accepts_negative_zero() {
+        let mut negative_zero = Decimal::ZERO;
+        negative_zero.set_sign_negative(true);
+        assert!(<NonNegativeAmount as Predicate<Decimal>>::check(&negative_zero).is_ok());
+        assert!(<NonNegativeAmount as Predicate<Decimal>>::check(&Decimal::new(-1, 2)).is_err());
+    }
+
+    #[test]
+    fn test_currency_code() {
+        assert!(Refined::<String, CurrencyCode>::new("GBP".into()).is_ok());
+        assert!(Refined::<String, CurrencyCode>::new("GB".into()).is_err());
+        assert!(Refined::<String, CurrencyCode>::new("GÜP".into()).is_err());
+    }
+
+    #[test]
+    fn test_money_arithmetic_overflow() {
+        let max = Money::new(Decimal::MAX).unwrap();
+        assert!(max.checked_add(&max).is_none());
+        assert!(max.checked_mul(Decimal::from(2)).is_none());
+        assert_eq!(
+            max.checked_sub(&max).map(|m| m.into_inner()),
+            Some(Decimal::ZERO)
+        );
+    }
+}
@@ -2,8 +2,11 @@
 //!
 //! This module provides ready-to-use predicates for:
 //! - **Numeric constraints**: [`numeric::Positive`], [`numeric::NonNegative`], [`numeric::Negative`], [`numeric::NonZero`], [`numeric::InRange`]
-//! - **String constraints**: [`string::NonEmpty`], [`string::Trimmed`], [`string::MaxLength`], [`string::MinLength`]
-//! - **Collection constraints**: [`collection::MaxSize`], [`collection::MinSize`] (for `Vec<T>`)
+//! - **String constraints**: [`string::NonEmpty`], [`string::Trimmed`], [`string::MaxLength`], [`string::MinLength`], [`string::Lowercase`], `string::NormalizedNfc` (feature `unicode`)
+//! - **Collection constraints**: [`collection::MaxSize`], [`collection::MinSize`] (for `Vec<T>`), [`collection::KeysSatisfy`], [`collection::ValuesSatisfy`] (for maps)
+//! - **Network constraints**: [`net::ValidUuid`], [`net::ValidIpAddr`], [`net::ValidHostname`], [`net::ValidPort`], `net::ValidUrl` (feature `url`)
+//! - **Time constraints** (feature `chrono`): `time::InPast`, `time::InFuture`, `time::WithinDays`, `time::ValidRfc3339`
+//! - **Decimal constraints** (feature `rust_decimal`): `decimal::MaxScale`, `decimal::NonNegativeAmount`, `decimal::CurrencyCode`
 //!
 //! # Example
 //!
@@ -24,5 +27,10 @@
 //! ```
 
 pub mod collection;
+#[cfg(feature = "rust_decimal")]
+pub mod decimal;
+pub mod net;
 pub mod numeric;
 pub mod string;
+#[cfg(feature = "chrono")]
+pub mod time;
@@ -0,0 +1,174 @@
+//! Network and identifier predicates for refined types
+//!
+//! This module provides predicates for constraining identifier strings:
+//! - [`ValidUuid`]: String is a UUID
+//! - [`ValidIpAddr`]: String is an IPv4 or IPv6 address
+//! - [`ValidHostname`]: String is an RFC 1123 hostname
+//! - [`ValidPort`]: String is a port number from 1 to 65535
+//! - `ValidUrl`: String is an absolute URL (feature `url`)
+//!
+//! The rules match the runtime predicates of the same names in
+//! [`crate::predicate`].
+//!
+//! # Example
+//!
+//! ```rust
+//! use stillwater::refined::{HostnameString, UuidString};
+//!
+//! let id = UuidString::new("67e55044-10b1-426f-9247-bb680e5fe0c8".to_string()).unwrap();
+//! assert!(HostnameString::new("bad host".to_string()).is_err());
+//! ```
+
+use alloc::string::String;
+
+use super::super::Predicate;
+use crate::predicate::Predicate as _;
+
+macro_rules! net_predicate {
+    ($(#[$meta:meta])* $name:ident, $runtime:expr, $error:literal, $description:literal) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, Default)]
+        pub struct $name;
+
+        impl Predicate<String> for $name {
+            type Error = &'static str;
+
+            fn check(value: &String) -> Result<(), Self::Error> {
+                if $runtime.check(value) {
+                    Ok(())
+                } else {
+                    Err($error)
+                }
+            }
+
+            fn description() -> &'static str {
+                $description
+            }
+        }
+    };
+}
+
+net_predicate!(
+    /// String must be a UUID, hyphenated or simple
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use stillwater::refined::{Refined, ValidUuid};
+    ///
+    /// type RequestId = Refined<String, ValidUuid>;
+    ///
+    /// assert!(RequestId::new("67e55044-10b1-426f-9247-bb680e5fe0c8".to_string()).is_ok());
+    /// assert!(RequestId::new("not-a-uuid".to_string()).is_err());
+    /// ```
+    ValidUuid,
+    crate::predicate::valid_uuid(),
+    "string must be a UUID",
+    "UUID string"
+);
+
+net_predicate!(
+    /// String must be an IPv4 or IPv6 address
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use stillwater::refined::{Refined, ValidIpAddr};
+    ///
+    /// type ClientIp = Refined<String, ValidIpAddr>;
+    ///
+    /// assert!(ClientIp::new("203.0.113.7".to_string()).is_ok());
+    /// assert!(ClientIp::new("203.0.113".to_string()).is_err());
+    /// ```
+    ValidIpAddr,
+    crate::predicate::valid_ip_addr(),
+    "string must be an IP address",
+    "IP address string"
+);
+
+net_predicate!(
+    /// String must be an RFC 1123 hostname
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use stillwater::refined::{Refined, ValidHostname};
+    ///
+    /// type Host = Refined<String, ValidHostname>;
+    ///
+    /// assert!(Host::new("db-1.internal".to_string()).is_ok());
+    /// assert!(Host::new("db_1.internal".to_string()).is_err());
+    /// ```
+    ValidHostname,
+    crate::predicate::valid_hostname(),
+    "string must be a valid hostname",
+    "hostname string"
+);
+
+net_predicate!(
+    /// String must be a port number from 1 to 65535
+    ///
+    /// For numeric ports, use the [`Port`](crate::refined::Port) alias.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use stillwater::refined::{Refined, ValidPort};
+    ///
+    /// type PortString = Refined<String, ValidPort>;
+    ///
+    /// assert!(PortString::new("5432".to_string()).is_ok());
+    /// assert!(PortString::new("0".to_string()).is_err());
+    /// ```
+    ValidPort,
+    crate::predicate::valid_port(),
+    "string must be a port number from 1 to 65535",
+    "port number string"
+);
+
+#[cfg(feature = "url")]
+net_predicate!(
+    /// String must be an absolute URL
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use stillwater::refined::{Refined, ValidUrl};
+    ///
+    /// type Webhook = Refined<String, ValidUrl>;
+    ///
+    /// assert!(Webhook::new("https://hooks.example.com/x".to_string()).is_ok());
+    /// assert!(Webhook::new("hooks.example.com/x".to_string()).is_err());
+    /// ```
+    ValidUrl,
+    crate::predicate::valid_url(),
+    "string must be an absolute URL",
+    "URL string"
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::refined::Refined;
+
+    #[test]
+    fn test_errors_and_descriptions() {
+        assert_eq!(
+            Refined::<String, ValidIpAddr>::new("nope".to_string()).unwrap_err(),
+            "string must be an IP address"
+        );
+        assert_eq!(
+            <ValidHostname as Predicate<String>>::description(),
+            "hostname string"
+        );
+    }
+
+    #[test]
+    fn test_valid_values() {
+        assert!(
+            Refined::<String, ValidUuid>::new("67e5504410b1426f9247bb680e5fe0c8".into()).is_ok()
+        );
+        assert!(Refined::<String, ValidPort>::new("65535".into()).is_ok());
+        assert!(Refined::<String, ValidPort>::new("65536".into()).is_err());
+    }
+}
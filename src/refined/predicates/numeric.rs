@@ -24,6 +24,9 @@
 //! assert!(Percentage::new(150).is_err());
 //! ```
 
+use alloc::format;
+use alloc::string::String;
+
 use super::super::Predicate;
 
 /// Value must be positive (> 0)
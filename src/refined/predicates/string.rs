@@ -5,6 +5,8 @@
 //! - [`Trimmed`]: String has no leading/trailing whitespace
 //! - [`MaxLength<N>`]: String length <= N
 //! - [`MinLength<N>`]: String length >= N
+//! - [`Lowercase`]: String has no uppercase characters
+//! - `NormalizedNfc`: String is in Unicode NFC form (feature `unicode`)
 //!
 //! # Example
 //!
@@ -27,11 +29,61 @@
 //! let user = Username::new("alice".to_string()).unwrap();
 //! ```
 
+use alloc::borrow::Cow;
+use alloc::format;
+use alloc::string::String;
+
 use super::super::Predicate;
 
+// Implements a string predicate for `String`, `&str`, and `Cow<str>` from a
+// single check on `&str`, so borrowed input can be refined without copying.
+macro_rules! impl_str_predicate {
+    ($pred:ty $(, const $n:ident)?, $error:ty, $desc:expr, |$value:ident| $check:expr) => {
+        impl<$(const $n: usize)?> Predicate<String> for $pred {
+            type Error = $error;
+
+            fn check(value: &String) -> Result<(), Self::Error> {
+                let $value: &str = value;
+                $check
+            }
+
+            fn description() -> &'static str {
+                $desc
+            }
+        }
+
+        impl<'a $(, const $n: usize)?> Predicate<&'a str> for $pred {
+            type Error = $error;
+
+            fn check(value: &&'a str) -> Result<(), Self::Error> {
+                let $value: &str = value;
+                $check
+            }
+
+            fn description() -> &'static str {
+                $desc
+            }
+        }
+
+        impl<'a $(, const $n: usize)?> Predicate<Cow<'a, str>> for $pred {
+            type Error = $error;
+
+            fn check(value: &Cow<'a, str>) -> Result<(), Self::Error> {
+                let $value: &str = value;
+                $check
+            }
+
+            fn description() -> &'static str {
+                $desc
+            }
+        }
+    };
+}
+
 /// String must not be empty
 ///
-/// This predicate works for both `String` and `&str`.
+/// Like every string predicate here, this works for `String`, `&str`, and
+/// `Cow<str>`.
 ///
 /// # Example
 ///
@@ -46,37 +98,13 @@ use super::super::Predicate;
 #[derive(Debug, Clone, Copy, Default)]
 pub struct NonEmpty;
 
-impl Predicate<String> for NonEmpty {
-    type Error = &'static str;
-
-    fn check(value: &String) -> Result<(), Self::Error> {
-        if value.is_empty() {
-            Err("string cannot be empty")
-        } else {
-            Ok(())
-        }
-    }
-
-    fn description() -> &'static str {
-        "non-empty string"
-    }
-}
-
-impl Predicate<&str> for NonEmpty {
-    type Error = &'static str;
-
-    fn check(value: &&str) -> Result<(), Self::Error> {
-        if value.is_empty() {
-            Err("string cannot be empty")
-        } else {
-            Ok(())
-        }
-    }
-
-    fn description() -> &'static str {
-        "non-empty string"
+impl_str_predicate!(NonEmpty, &'static str, "non-empty string", |value| {
+    if value.is_empty() {
+        Err("string cannot be empty")
+    } else {
+        Ok(())
     }
-}
+});
 
 /// String equals its trimmed form (no leading/trailing whitespace)
 ///
@@ -94,21 +122,18 @@ impl Predicate<&str> for NonEmpty {
 #[derive(Debug, Clone, Copy, Default)]
 pub struct Trimmed;
 
-impl Predicate<String> for Trimmed {
-    type Error = &'static str;
-
-    fn check(value: &String) -> Result<(), Self::Error> {
+impl_str_predicate!(
+    Trimmed,
+    &'static str,
+    "trimmed string (no leading/trailing whitespace)",
+    |value| {
         if value.trim() == value {
             Ok(())
         } else {
             Err("string has leading or trailing whitespace")
         }
     }
-
-    fn description() -> &'static str {
-        "trimmed string (no leading/trailing whitespace)"
-    }
-}
+);
 
 /// String length must be at most N bytes
 ///
@@ -125,10 +150,11 @@ impl Predicate<String> for Trimmed {
 #[derive(Debug, Clone, Copy, Default)]
 pub struct MaxLength<const N: usize>;
 
-impl<const N: usize> Predicate<String> for MaxLength<N> {
-    type Error = String;
-
-    fn check(value: &String) -> Result<(), Self::Error> {
+impl_str_predicate!(
+    MaxLength<N>, const N,
+    String,
+    "string with maximum length",
+    |value| {
         if value.len() <= N {
             Ok(())
         } else {
@@ -139,11 +165,7 @@ impl<const N: usize> Predicate<String> for MaxLength<N> {
             ))
         }
     }
-
-    fn description() -> &'static str {
-        "string with maximum length"
-    }
-}
+);
 
 /// String length must be at least N bytes
 ///
@@ -160,10 +182,11 @@ impl<const N: usize> Predicate<String> for MaxLength<N> {
 #[derive(Debug, Clone, Copy, Default)]
 pub struct MinLength<const N: usize>;
 
-impl<const N: usize> Predicate<String> for MinLength<N> {
-    type Error = String;
-
-    fn check(value: &String) -> Result<(), Self::Error> {
+impl_str_predicate!(
+    MinLength<N>, const N,
+    String,
+    "string with minimum length",
+    |value| {
         if value.len() >= N {
             Ok(())
         } else {
@@ -174,11 +197,60 @@ impl<const N: usize> Predicate<String> for MinLength<N> {
             ))
         }
     }
+);
+
+/// String must not contain uppercase characters
+///
+/// # Example
+///
+/// ```rust
+/// use stillwater::refined::{Refined, Lowercase};
+///
+/// type Slug = Refined<String, Lowercase>;
+///
+/// assert!(Slug::new("release-notes".to_string()).is_ok());
+/// assert!(Slug::new("Release-Notes".to_string()).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Lowercase;
 
-    fn description() -> &'static str {
-        "string with minimum length"
+impl_str_predicate!(Lowercase, &'static str, "lowercase string", |value| {
+    if value.chars().any(char::is_uppercase) {
+        Err("string contains uppercase characters")
+    } else {
+        Ok(())
     }
-}
+});
+
+/// String must be in Unicode Normalization Form C (feature `unicode`)
+///
+/// # Example
+///
+/// ```rust
+/// use stillwater::refined::{Refined, NormalizedNfc};
+///
+/// type DisplayName = Refined<String, NormalizedNfc>;
+///
+/// assert!(DisplayName::new("caf\u{e9}".to_string()).is_ok());
+/// assert!(DisplayName::new("cafe\u{301}".to_string()).is_err());
+/// ```
+#[cfg(feature = "unicode")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NormalizedNfc;
+
+#[cfg(feature = "unicode")]
+impl_str_predicate!(
+    NormalizedNfc,
+    &'static str,
+    "NFC-normalized string",
+    |value| {
+        if unicode_normalization::is_nfc(value) {
+            Ok(())
+        } else {
+            Err("string is not in Unicode NFC form")
+        }
+    }
+);
 
 #[cfg(test)]
 mod tests {
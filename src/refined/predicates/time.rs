@@ -0,0 +1,198 @@
+//! Date and time predicates for refined types (feature-gated)
+//!
+//! This module provides predicates for constraining `chrono` timestamps and
+//! timestamp strings. Feature-gated behind `#[cfg(feature = "chrono")]`.
+//! - [`InPast`]: Timestamp is before now
+//! - [`InFuture`]: Timestamp is after now
+//! - [`WithinDays<N>`]: Timestamp is at most N days before or after now
+//! - [`ValidRfc3339`]: String is an RFC 3339 timestamp
+//!
+//! Checks against the current time happen once, at construction: a
+//! `Refined<DateTime<Utc>, InFuture>` records that the timestamp was in the
+//! future when it was validated.
+//!
+//! # Example
+//!
+//! ```rust
+//! use chrono::{Duration, Utc};
+//! use stillwater::refined::{PastDateTime, Rfc3339String};
+//!
+//! let created_at = PastDateTime::new(Utc::now() - Duration::days(1)).unwrap();
+//! assert!(PastDateTime::new(Utc::now() + Duration::days(1)).is_err());
+//!
+//! let raw = Rfc3339String::new("2024-05-01T12:00:00Z".to_string()).unwrap();
+//! ```
+
+use chrono::{DateTime, TimeZone, Utc};
+
+use super::super::Predicate;
+
+/// Timestamp must be before now
+///
+/// # Example
+///
+/// ```rust
+/// use chrono::{DateTime, Duration, Utc};
+/// use stillwater::refined::{Refined, InPast};
+///
+/// type Birthday = Refined<DateTime<Utc>, InPast>;
+///
+/// assert!(Birthday::new(Utc::now() - Duration::days(10_000)).is_ok());
+/// assert!(Birthday::new(Utc::now() + Duration::days(1)).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct InPast;
+
+impl<Tz: TimeZone> Predicate<DateTime<Tz>> for InPast {
+    type Error = &'static str;
+
+    fn check(value: &DateTime<Tz>) -> Result<(), Self::Error> {
+        if *value < Utc::now() {
+            Ok(())
+        } else {
+            Err("timestamp must be in the past")
+        }
+    }
+
+    fn description() -> &'static str {
+        "timestamp in the past"
+    }
+}
+
+/// Timestamp must be after now
+///
+/// # Example
+///
+/// ```rust
+/// use chrono::{DateTime, Duration, Utc};
+/// use stillwater::refined::{Refined, InFuture};
+///
+/// type Deadline = Refined<DateTime<Utc>, InFuture>;
+///
+/// assert!(Deadline::new(Utc::now() + Duration::hours(2)).is_ok());
+/// assert!(Deadline::new(Utc::now() - Duration::hours(2)).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct InFuture;
+
+impl<Tz: TimeZone> Predicate<DateTime<Tz>> for InFuture {
+    type Error = &'static str;
+
+    fn check(value: &DateTime<Tz>) -> Result<(), Self::Error> {
+        if *value > Utc::now() {
+            Ok(())
+        } else {
+            Err("timestamp must be in the future")
+        }
+    }
+
+    fn description() -> &'static str {
+        "timestamp in the future"
+    }
+}
+
+/// Timestamp must be at most N days before or after now
+///
+/// # Example
+///
+/// ```rust
+/// use chrono::{DateTime, Duration, Utc};
+/// use stillwater::refined::{Refined, WithinDays};
+///
+/// type RecentEvent = Refined<DateTime<Utc>, WithinDays<30>>;
+///
+/// assert!(RecentEvent::new(Utc::now() - Duration::days(3)).is_ok());
+/// assert!(RecentEvent::new(Utc::now() - Duration::days(45)).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WithinDays<const N: u32>;
+
+impl<const N: u32, Tz: TimeZone> Predicate<DateTime<Tz>> for WithinDays<N> {
+    type Error = String;
+
+    fn check(value: &DateTime<Tz>) -> Result<(), Self::Error> {
+        let distance = value
+            .clone()
+            .signed_duration_since(Utc::now())
+            .num_seconds()
+            .unsigned_abs();
+        if distance <= u64::from(N) * 86_400 {
+            Ok(())
+        } else {
+            Err(format!(
+                "timestamp is {} days from now, more than {}",
+                (distance + 43_200) / 86_400,
+                N
+            ))
+        }
+    }
+
+    fn description() -> &'static str {
+        "timestamp within N days of now"
+    }
+}
+
+/// String must be an RFC 3339 timestamp
+///
+/// # Example
+///
+/// ```rust
+/// use stillwater::refined::{Refined, ValidRfc3339};
+///
+/// type Timestamp = Refined<String, ValidRfc3339>;
+///
+/// assert!(Timestamp::new("2024-05-01T12:00:00+02:00".to_string()).is_ok());
+/// assert!(Timestamp::new("yesterday".to_string()).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ValidRfc3339;
+
+impl Predicate<String> for ValidRfc3339 {
+    type Error = String;
+
+    fn check(value: &String) -> Result<(), Self::Error> {
+        DateTime::parse_from_rfc3339(value)
+            .map(|_| ())
+            .map_err(|e| format!("invalid RFC 3339 timestamp: {}", e))
+    }
+
+    fn description() -> &'static str {
+        "RFC 3339 timestamp"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::refined::Refined;
+    use chrono::{Duration, FixedOffset};
+
+    #[test]
+    fn test_past_and_future() {
+        let hour_ago = Utc::now() - Duration::hours(1);
+        assert!(<InPast as Predicate<DateTime<Utc>>>::check(&hour_ago).is_ok());
+        assert_eq!(
+            <InFuture as Predicate<DateTime<Utc>>>::check(&hour_ago),
+            Err("timestamp must be in the future")
+        );
+
+        let offset = FixedOffset::west_opt(5 * 3600).unwrap();
+        let soon = (Utc::now() + Duration::hours(1)).with_timezone(&offset);
+        assert!(Refined::<_, InFuture>::new(soon).is_ok());
+    }
+
+    #[test]
+    fn test_within_days_error_message() {
+        let result = Refined::<_, WithinDays<7>>::new(Utc::now() + Duration::days(10));
+        assert_eq!(
+            result.unwrap_err(),
+            "timestamp is 10 days from now, more than 7"
+        );
+    }
+
+    #[test]
+    fn test_valid_rfc3339() {
+        assert!(Refined::<_, ValidRfc3339>::new("2024-02-29T00:00:00Z".to_string()).is_ok());
+        assert!(Refined::<_, ValidRfc3339>::new("2023-02-29T00:00:00Z".to_string()).is_err());
+    }
+}
@@ -0,0 +1,225 @@
+//! Normalization before validation
+//!
+//! Some predicates describe values that are easy to fix rather than reject:
+//! a string with stray whitespace can be trimmed, a mixed-case slug can be
+//! lowercased. The [`Sanitize`] trait lets such predicates normalize a value
+//! first, and [`Refined::new_sanitized`] runs that normalization before the
+//! predicate check, recording which transformations actually changed the
+//! value.
+//!
+//! Built-in implementations:
+//! - [`Trimmed`]: trims leading and trailing whitespace (`"trim"`)
+//! - [`Lowercase`]: lowercases the string (`"lowercase"`)
+//! - `NormalizedNfc`: converts to Unicode NFC (`"nfc"`, feature `unicode`)
+//! - [`NonEmpty`], [`MaxLength`], [`MinLength`]: no normalization
+//! - [`And<A, B>`]: applies `A`'s normalization, then `B`'s
+//!
+//! # Example
+//!
+//! ```rust
+//! use stillwater::refined::{And, Lowercase, NonEmpty, Refined, Trimmed};
+//!
+//! type Email = Refined<String, And<NonEmpty, And<Trimmed, Lowercase>>>;
+//!
+//! let email = Email::new_sanitized("  Alice@Example.com ".to_string()).unwrap();
+//! assert_eq!(email.value.get(), "alice@example.com");
+//! assert_eq!(email.applied, vec!["trim", "lowercase"]);
+//!
+//! // Sanitization can't fix everything
+//! assert!(Email::new_sanitized("   ".to_string()).is_err());
+//! ```
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use super::combinators::And;
+#[cfg(feature = "unicode")]
+use super::predicates::string::NormalizedNfc;
+use super::predicates::string::{Lowercase, MaxLength, MinLength, NonEmpty, Trimmed};
+use super::{Predicate, Refined};
+
+/// A predicate that can normalize values before they are checked.
+///
+/// Implementations push a short name for each transformation onto
+/// `applied`, but only when the transformation changed the value.
+///
+/// # Example
+///
+/// ```rust
+/// use stillwater::refined::{Predicate, Refined, Sanitize};
+///
+/// struct Digits;
+///
+/// impl Predicate<String> for Digits {
+///     type Error = &'static str;
+///
+///     fn check(value: &String) -> Result<(), Self::Error> {
+///         if value.chars().all(|c| c.is_ascii_digit()) {
+///             Ok(())
+///         } else {
+///             Err("only digits allowed")
+///         }
+///     }
+/// }
+///
+/// impl Sanitize<String> for Digits {
+///     fn sanitize(value: String, applied: &mut Vec<&'static str>) -> String {
+///         let digits: String = value.chars().filter(|c| !matches!(c, ' ' | '-')).collect();
+///         if digits != value {
+///             applied.push("strip_separators");
+///         }
+///         digits
+///     }
+/// }
+///
+/// let card = Refined::<String, Digits>::new_sanitized("4111-1111 1111".to_string()).unwrap();
+/// assert_eq!(card.value.get(), "411111111111");
+/// assert_eq!(card.applied, vec!["strip_separators"]);
+/// ```
+pub trait Sanitize<T>: Predicate<T> {
+    /// Normalize the value, recording the transformations that changed it.
+    fn sanitize(value: T, applied: &mut Vec<&'static str>) -> T;
+}
+
+/// A refined value produced by [`Refined::new_sanitized`], along with the
+/// transformations that were applied to reach it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sanitized<T, P: Predicate<T>> {
+    /// The validated value
+    pub value: Refined<T, P>,
+    /// Names of the transformations that changed the input, in order
+    pub applied: Vec<&'static str>,
+}
+
+impl<T, P: Predicate<T>> Sanitized<T, P> {
+    /// Returns `true` if no transformation changed the input.
+    pub fn was_clean(&self) -> bool {
+        self.applied.is_empty()
+    }
+
+    /// Discard the transformation record, returning the refined value.
+    pub fn into_refined(self) -> Refined<T, P> {
+        self.value
+    }
+}
+
+impl<T, P: Sanitize<T>> Refined<T, P> {
+    /// Normalize the value with [`Sanitize`], then check the predicate.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use stillwater::refined::{Refined, Trimmed};
+    ///
+    /// let name = Refined::<String, Trimmed>::new_sanitized("  Alice ".to_string()).unwrap();
+    /// assert_eq!(name.value.get(), "Alice");
+    /// assert_eq!(name.applied, vec!["trim"]);
+    /// ```
+    pub fn new_sanitized(value: T) -> Result<Sanitized<T, P>, P::Error> {
+        let mut applied = Vec::new();
+        let value = Self::new(P::sanitize(value, &mut applied))?;
+        Ok(Sanitized { value, applied })
+    }
+}
+
+impl Sanitize<String> for Trimmed {
+    fn sanitize(value: String, applied: &mut Vec<&'static str>) -> String {
+        let trimmed = value.trim();
+        if trimmed.len() == value.len() {
+            value
+        } else {
+            applied.push("trim");
+            trimmed.to_string()
+        }
+    }
+}
+
+impl Sanitize<String> for Lowercase {
+    fn sanitize(value: String, applied: &mut Vec<&'static str>) -> String {
+        if value.chars().any(char::is_uppercase) {
+            applied.push("lowercase");
+            value.to_lowercase()
+        } else {
+            value
+        }
+    }
+}
+
+#[cfg(feature = "unicode")]
+impl Sanitize<String> for NormalizedNfc {
+    fn sanitize(value: String, applied: &mut Vec<&'static str>) -> String {
+        use unicode_normalization::UnicodeNormalization;
+
+        if unicode_normalization::is_nfc(&value) {
+            value
+        } else {
+            applied.push("nfc");
+            value.nfc().collect()
+        }
+    }
+}
+
+impl Sanitize<String> for NonEmpty {
+    fn sanitize(value: String, _applied: &mut Vec<&'static str>) -> String {
+        value
+    }
+}
+
+impl<const N: usize> Sanitize<String> for MaxLength<N> {
+    fn sanitize(value: String, _applied: &mut Vec<&'static str>) -> String {
+        value
+    }
+}
+
+impl<const N: usize> Sanitize<String> for MinLength<N> {
+    fn sanitize(value: String, _applied: &mut Vec<&'static str>) -> String {
+        value
+    }
+}
+
+impl<T, A, B> Sanitize<T> for And<A, B>
+where
+    A: Sanitize<T>,
+    B: Sanitize<T>,
+{
+    fn sanitize(value: T, applied: &mut Vec<&'static str>) -> T {
+        B::sanitize(A::sanitize(value, applied), applied)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clean_input_records_nothing() {
+        let result =
+            Refined::<String, And<Trimmed, Lowercase>>::new_sanitized("ok".into()).unwrap();
+        assert!(result.was_clean());
+        assert_eq!(result.into_refined().into_inner(), "ok");
+    }
+
+    #[test]
+    fn test_sanitize_runs_before_length_check() {
+        type Code = Refined<String, And<Trimmed, MaxLength<3>>>;
+        assert!(Code::new(" abc ".into()).is_err());
+        assert_eq!(
+            Code::new_sanitized(" abc ".into()).unwrap().value.get(),
+            "abc"
+        );
+    }
+
+    #[test]
+    fn test_error_after_sanitizing() {
+        type Name = Refined<String, And<Trimmed, NonEmpty>>;
+        assert!(Name::new_sanitized(" \t ".into()).is_err());
+    }
+
+    #[cfg(feature = "unicode")]
+    #[test]
+    fn test_nfc() {
+        let result = Refined::<String, NormalizedNfc>::new_sanitized("e\u{301}".into()).unwrap();
+        assert_eq!(result.value.get(), "\u{e9}");
+        assert_eq!(result.applied, vec!["nfc"]);
+    }
+}
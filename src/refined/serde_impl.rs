@@ -28,8 +28,9 @@
 //! assert!(result.is_err());
 //! ```
 
+use core::fmt;
+
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
-use std::fmt;
 
 use super::{Predicate, Refined};
 
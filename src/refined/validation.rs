@@ -23,7 +23,10 @@
 //! assert!(result.is_failure());
 //! ```
 
-use std::fmt;
+use alloc::borrow::Cow;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
 
 use super::{Predicate, Refined};
 use crate::Validation;
@@ -79,27 +82,127 @@ impl<T, P: Predicate<T>> Refined<T, P> {
     }
 }
 
+/// A location in source text, as a byte offset and length.
+///
+/// Attach one to a [`FieldError`] so the error can point at the bad value
+/// in the input it came from, such as a config file. Parsers that report
+/// lines and columns instead can convert with [`Span::from_line_col`].
+///
+/// # Example
+///
+/// ```rust
+/// use stillwater::refined::Span;
+///
+/// let source = "name = \"app\"\nport = 0\n";
+/// let span = Span::from_line_col(source, 2, 8, 1).unwrap();
+///
+/// assert_eq!(span, Span::new(20, 1));
+/// assert_eq!(span.line_col(source), (2, 8));
+/// assert_eq!(&source[span.range()], "0");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Span {
+    /// Byte offset of the start of the span
+    pub offset: usize,
+    /// Length of the span in bytes
+    pub len: usize,
+}
+
+impl Span {
+    /// A span of `len` bytes starting at byte `offset`.
+    pub fn new(offset: usize, len: usize) -> Self {
+        Span { offset, len }
+    }
+
+    /// A span of `len` bytes starting at a 1-based line and column of
+    /// `source`, with the column counted in characters.
+    ///
+    /// Returns `None` if the position is past the end of its line.
+    pub fn from_line_col(source: &str, line: usize, col: usize, len: usize) -> Option<Self> {
+        let line_start = if line <= 1 {
+            0
+        } else {
+            source
+                .match_indices('\n')
+                .nth(line - 2)
+                .map(|(i, _)| i + 1)?
+        };
+        let line_text = source[line_start..].split('\n').next().unwrap_or("");
+        let col_offset = if col <= 1 {
+            0
+        } else {
+            match line_text.char_indices().nth(col - 1) {
+                Some((i, _)) => i,
+                None if line_text.chars().count() == col - 1 => line_text.len(),
+                None => return None,
+            }
+        };
+        Some(Span::new(line_start + col_offset, len))
+    }
+
+    /// The byte range covered by the span.
+    pub fn range(&self) -> core::ops::Range<usize> {
+        self.offset..self.offset + self.len
+    }
+
+    /// The 1-based line and column (in characters) where the span starts
+    /// in `source`.
+    pub fn line_col(&self, source: &str) -> (usize, usize) {
+        let before = &source[..self.offset.min(source.len())];
+        let line = before.matches('\n').count() + 1;
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        (line, before[line_start..].chars().count() + 1)
+    }
+}
+
 /// Error with field context
 ///
-/// Wraps an error with a field name for better error messages.
+/// Wraps an error with a field name for better error messages, and
+/// optionally the [`Span`] of the offending value in the source input.
 ///
 /// # Example
 ///
 /// ```rust
-/// use stillwater::refined::FieldError;
+/// use stillwater::refined::{FieldError, Span};
 ///
-/// let err = FieldError {
-///     field: "username",
-///     error: "cannot be empty",
-/// };
+/// let err = FieldError::new("username", "cannot be empty");
 /// assert_eq!(format!("{}", err), "username: cannot be empty");
+///
+/// let located = err.with_span(Span::new(11, 2));
+/// assert_eq!(located.span, Some(Span::new(11, 2)));
 /// ```
+///
+/// The struct is `#[non_exhaustive]`: build it with [`FieldError::new`] and
+/// match it with `..`, so that adding context later is not a breaking change.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
 pub struct FieldError<E> {
-    /// The field name
-    pub field: &'static str,
+    /// The field name, or the path to the field in nested input
+    pub field: Cow<'static, str>,
     /// The underlying error
     pub error: E,
+    /// Where the field's value appears in the source input, if known
+    pub span: Option<Span>,
+}
+
+impl<E> FieldError<E> {
+    /// An error for `field` with no source location.
+    ///
+    /// `field` is usually a field name literal, but can also be a path
+    /// built at runtime, such as `users[2].email`.
+    pub fn new(field: impl Into<Cow<'static, str>>, error: E) -> Self {
+        FieldError {
+            field: field.into(),
+            error,
+            span: None,
+        }
+    }
+
+    /// Set where the field's value appears in the source input.
+    pub fn with_span(mut self, span: Span) -> Self {
+        self.span = Some(span);
+        self
+    }
 }
 
 impl<E: fmt::Display> fmt::Display for FieldError<E> {
@@ -108,8 +211,8 @@ impl<E: fmt::Display> fmt::Display for FieldError<E> {
     }
 }
 
-impl<E: std::error::Error + 'static> std::error::Error for FieldError<E> {
-    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+impl<E: core::error::Error + 'static> core::error::Error for FieldError<E> {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
         Some(&self.error)
     }
 }
@@ -130,7 +233,7 @@ impl<T, P: Predicate<T>> RefinedValidationExt<T, P> for Refined<T, P> {
     ) -> Validation<Refined<T, P>, FieldError<P::Error>> {
         match Refined::new(value) {
             Ok(refined) => Validation::Success(refined),
-            Err(e) => Validation::Failure(FieldError { field, error: e }),
+            Err(e) => Validation::Failure(FieldError::new(field, e)),
         }
     }
 }
@@ -139,6 +242,9 @@ impl<T, P: Predicate<T>> RefinedValidationExt<T, P> for Refined<T, P> {
 pub trait ValidationFieldExt<T, E> {
     /// Add field context to a validation error
     fn with_field(self, field: &'static str) -> Validation<T, FieldError<E>>;
+
+    /// Add field context and the value's location in the source input
+    fn with_field_at(self, field: &'static str, span: Span) -> Validation<T, FieldError<E>>;
 }
 
 impl<T, E> ValidationFieldExt<T, E> for Validation<T, E> {
@@ -164,9 +270,27 @@ impl<T, E> ValidationFieldExt<T, E> for Validation<T, E> {
     fn with_field(self, field: &'static str) -> Validation<T, FieldError<E>> {
         match self {
             Validation::Success(v) => Validation::Success(v),
-            Validation::Failure(e) => Validation::Failure(FieldError { field, error: e }),
+            Validation::Failure(e) => Validation::Failure(FieldError::new(field, e)),
         }
     }
+
+    /// Add field context and the value's location in the source input.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use stillwater::{Validation, refined::{Refined, Positive, Span, ValidationFieldExt}};
+    ///
+    /// let source = "port = -1";
+    /// let result = Refined::<i32, Positive>::validate(-1).with_field_at("port", Span::new(7, 2));
+    ///
+    /// if let Validation::Failure(err) = result {
+    ///     assert_eq!(&source[err.span.unwrap().range()], "-1");
+    /// }
+    /// ```
+    fn with_field_at(self, field: &'static str, span: Span) -> Validation<T, FieldError<E>> {
+        self.with_field(field).map_err(|e| e.with_span(span))
+    }
 }
 
 #[cfg(test)]
@@ -253,13 +377,22 @@ mod tests {
 
     #[test]
     fn test_field_error_display() {
-        let err = FieldError {
-            field: "username",
-            error: "cannot be empty",
-        };
+        let err = FieldError::new("username", "cannot be empty");
         assert_eq!(format!("{}", err), "username: cannot be empty");
     }
 
+    #[test]
+    fn test_span_line_col_round_trip() {
+        let source = "a = 1\nnamé = \"x\"\n";
+        let span = Span::from_line_col(source, 2, 8, 3).unwrap();
+
+        assert_eq!(&source[span.range()], "\"x\"");
+        assert_eq!(span.line_col(source), (2, 8));
+        assert_eq!(Span::from_line_col(source, 1, 6, 1), Some(Span::new(5, 1)));
+        assert_eq!(Span::from_line_col(source, 1, 7, 1), None);
+        assert_eq!(Span::from_line_col(source, 9, 1, 1), None);
+    }
+
     #[test]
     fn test_combined_field_validation() {
         let v1 = NonEmptyString::validate("".to_string())
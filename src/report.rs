@@ -0,0 +1,359 @@
+//! Structured error reports
+//!
+//! This module turns the crate's error types into a common [`ErrorReport`]
+//! that can be printed for people or serialized for machines:
+//!
+//! - [`ContextError`]: the inner error, its context trail, and request id
+//! - [`BracketError`]: the failing phase, or both errors when use and
+//!   cleanup failed
+//! - [`RetryExhausted`]: the final error with attempt count and elapsed time
+//! - `Vec<E>` and [`NonEmptyVec<E>`]: accumulated validation failures, one
+//!   cause per failure
+//!
+//! Reports render as plain text through `Display`, with Unicode tree
+//! markers through [`ErrorReport::render`], and as JSON through
+//! [`ErrorReport::to_json`]. With the `serde` feature, `ErrorReport` also
+//! implements `Serialize` and `Deserialize` in the same shape.
+//!
+//! # Examples
+//!
+//! ```
+//! use stillwater::report::ToReport;
+//! use stillwater::ContextError;
+//!
+//! let err = ContextError::new("connection refused")
+//!     .context("connecting to database")
+//!     .context("loading user profile");
+//!
+//! let report = err.to_report();
+//! assert_eq!(
+//!     report.to_string(),
+//!     "error: connection refused\n  -> connecting to database\n  -> loading user profile"
+//! );
+//! assert_eq!(
+//!     report.to_json(),
+//!     r#"{"message":"connection refused","context":["connecting to database","loading user profile"],"fields":{},"causes":[]}"#
+//! );
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt::{self, Write as _};
+
+use crate::context::ContextError;
+use crate::effect::bracket::BracketError;
+use crate::nonempty::NonEmptyVec;
+use crate::retry::RetryExhausted;
+
+/// A structured description of an error, independent of how it is shown
+///
+/// # Examples
+///
+/// ```
+/// use stillwater::report::{ErrorReport, ReportStyle};
+///
+/// let report = ErrorReport::new("payment declined")
+///     .with_context("charging order 17")
+///     .with_field("attempts", "3");
+///
+/// assert_eq!(
+///     report.render(ReportStyle::Unicode),
+///     "× payment declined\n╰─▶ charging order 17\n  attempts: 3"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ErrorReport {
+    /// The error message
+    pub message: String,
+    /// What was being attempted, innermost first
+    pub context: Vec<String>,
+    /// Labeled details, such as a request id or attempt count
+    pub fields: BTreeMap<String, String>,
+    /// Underlying errors, such as each failure of a validation
+    pub causes: Vec<ErrorReport>,
+}
+
+/// How [`ErrorReport::render`] draws a report
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReportStyle {
+    /// ASCII output, the same as `Display`
+    #[default]
+    Plain,
+    /// Unicode markers in the style of terminal diagnostic tools
+    Unicode,
+}
+
+impl ErrorReport {
+    /// Create a report with the given message
+    pub fn new(message: impl Into<String>) -> Self {
+        ErrorReport {
+            message: message.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Append a context message
+    pub fn with_context(mut self, context: impl Into<String>) -> Self {
+        self.context.push(context.into());
+        self
+    }
+
+    /// Set a labeled detail
+    pub fn with_field(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.fields.insert(key.into(), value.into());
+        self
+    }
+
+    /// Append an underlying error
+    pub fn with_cause(mut self, cause: ErrorReport) -> Self {
+        self.causes.push(cause);
+        self
+    }
+
+    /// Build a report for accumulated failures, with one cause per failure
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stillwater::report::ErrorReport;
+    ///
+    /// let report = ErrorReport::from_failures(["name is empty", "age is negative"]);
+    /// assert_eq!(report.message, "2 validation errors");
+    /// assert_eq!(report.causes.len(), 2);
+    /// ```
+    pub fn from_failures<I>(failures: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: fmt::Display,
+    {
+        let causes: Vec<_> = failures
+            .into_iter()
+            .map(|failure| ErrorReport::new(failure.to_string()))
+            .collect();
+        let message = match causes.len() {
+            1 => "1 validation error".to_string(),
+            n => format!("{} validation errors", n),
+        };
+        ErrorReport {
+            message,
+            causes,
+            ..Self::default()
+        }
+    }
+
+    /// Render the report as text in the given style
+    pub fn render(&self, style: ReportStyle) -> String {
+        let mut out = String::new();
+        self.render_into(&mut out, style, 0);
+        out
+    }
+
+    fn render_into(&self, out: &mut String, style: ReportStyle, depth: usize) {
+        let indent = "    ".repeat(depth);
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        match style {
+            ReportStyle::Plain => write!(out, "{}error: {}", indent, self.message),
+            ReportStyle::Unicode => write!(out, "{}× {}", indent, self.message),
+        }
+        .expect("writing to a String cannot fail");
+
+        for (i, context) in self.context.iter().enumerate() {
+            let marker = match style {
+                ReportStyle::Plain => "  ->",
+                ReportStyle::Unicode if i + 1 == self.context.len() => "╰─▶",
+                ReportStyle::Unicode => "├─▶",
+            };
+            out.push_str(&format!("\n{}{} {}", indent, marker, context));
+        }
+        for (key, value) in &self.fields {
+            out.push_str(&format!("\n{}  {}: {}", indent, key, value));
+        }
+        if !self.causes.is_empty() {
+            out.push_str(&format!("\n{}  caused by:", indent));
+            for cause in &self.causes {
+                cause.render_into(out, style, depth + 1);
+            }
+        }
+    }
+
+    /// Render the report as a single-line JSON object
+    ///
+    /// The object has the keys `message`, `context`, `fields`, and
+    /// `causes`, matching the `serde` representation.
+    pub fn to_json(&self) -> String {
+        let mut out = String::new();
+        self.json_into(&mut out);
+        out
+    }
+
+    fn json_into(&self, out: &mut String) {
+        out.push_str("{\"message\":");
+        json_string(out, &self.message);
+        out.push_str(",\"context\":[");
+        for (i, context) in self.context.iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            json_string(out, context);
+        }
+        out.push_str("],\"fields\":{");
+        for (i, (key, value)) in self.fields.iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            json_string(out, key);
+            out.push(':');
+            json_string(out, value);
+        }
+        out.push_str("},\"causes\":[");
+        for (i, cause) in self.causes.iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            cause.json_into(out);
+        }
+        out.push_str("]}");
+    }
+}
+
+fn json_string(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                write!(out, "\\u{:04x}", c as u32).expect("writing to a String cannot fail")
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+impl fmt::Display for ErrorReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.render(ReportStyle::Plain))
+    }
+}
+
+/// Conversion of an error into an [`ErrorReport`]
+pub trait ToReport {
+    /// Build a report describing this error
+    fn to_report(&self) -> ErrorReport;
+}
+
+impl ToReport for ErrorReport {
+    fn to_report(&self) -> ErrorReport {
+        self.clone()
+    }
+}
+
+impl<E: fmt::Display> ToReport for ContextError<E> {
+    fn to_report(&self) -> ErrorReport {
+        let mut report = ErrorReport::new(self.inner().to_string());
+        report.context = self.context_trail().to_vec();
+        match self.request_id() {
+            Some(request_id) => report.with_field("request_id", request_id),
+            None => report,
+        }
+    }
+}
+
+/// The failing phase is recorded in the `phase` field; when both use and
+/// cleanup fail, each error becomes a cause.
+impl<E: fmt::Display> ToReport for BracketError<E> {
+    fn to_report(&self) -> ErrorReport {
+        match self {
+            BracketError::AcquireError(e) => {
+                ErrorReport::new(e.to_string()).with_field("phase", "acquire")
+            }
+            BracketError::UseError(e) => ErrorReport::new(e.to_string()).with_field("phase", "use"),
+            BracketError::CleanupError(e) => {
+                ErrorReport::new(e.to_string()).with_field("phase", "cleanup")
+            }
+            BracketError::Both {
+                use_error,
+                cleanup_error,
+            } => ErrorReport::new("use and cleanup both failed")
+                .with_cause(ErrorReport::new(use_error.to_string()).with_field("phase", "use"))
+                .with_cause(
+                    ErrorReport::new(cleanup_error.to_string()).with_field("phase", "cleanup"),
+                ),
+        }
+    }
+}
+
+impl<E: fmt::Display> ToReport for RetryExhausted<E> {
+    fn to_report(&self) -> ErrorReport {
+        ErrorReport::new(self.final_error.to_string())
+            .with_context(format!("retrying failed after {} attempts", self.attempts))
+            .with_field("attempts", self.attempts.to_string())
+            .with_field("elapsed", format!("{:?}", self.total_duration))
+    }
+}
+
+impl<E: fmt::Display> ToReport for Vec<E> {
+    fn to_report(&self) -> ErrorReport {
+        ErrorReport::from_failures(self)
+    }
+}
+
+impl<E: fmt::Display> ToReport for NonEmptyVec<E> {
+    fn to_report(&self) -> ErrorReport {
+        ErrorReport::from_failures(self.iter())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_bracket_both_renders_causes() {
+        let err = BracketError::Both {
+            use_error: "write failed",
+            cleanup_error: "close failed",
+        };
+        assert_eq!(
+            err.to_report().to_string(),
+            "error: use and cleanup both failed\n  caused by:\n    error: write failed\n      phase: use\n    error: close failed\n      phase: cleanup"
+        );
+    }
+
+    #[test]
+    fn test_retry_exhausted_fields() {
+        let report = RetryExhausted::new("timeout", 3, Duration::from_millis(250)).to_report();
+        assert_eq!(report.fields["attempts"], "3");
+        assert_eq!(report.fields["elapsed"], "250ms");
+        assert_eq!(report.context, vec!["retrying failed after 3 attempts"]);
+    }
+
+    #[test]
+    fn test_json_escapes_and_parses() {
+        let report = vec!["bad \"quote\"\n", "tab\there"]
+            .to_report()
+            .with_field("request_id", "r\\1");
+        let value: serde_json::Value = serde_json::from_str(&report.to_json()).unwrap();
+
+        assert_eq!(value["message"], "2 validation errors");
+        assert_eq!(value["fields"]["request_id"], "r\\1");
+        assert_eq!(value["causes"][0]["message"], "bad \"quote\"\n");
+        assert_eq!(value["causes"][1]["message"], "tab\there");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_matches_to_json() {
+        let report = NonEmptyVec::new("missing name", vec!["short password"])
+            .to_report()
+            .with_context("registering user");
+        assert_eq!(serde_json::to_string(&report).unwrap(), report.to_json());
+    }
+}
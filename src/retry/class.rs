@@ -0,0 +1,267 @@
+//! Error classification for resilience decisions.
+//!
+//! [`ErrorClass`] gives an error type one place to say whether a failure is
+//! worth retrying, so retry policies and metrics agree instead of each
+//! carrying its own matching closure.
+
+use std::fmt;
+use std::io;
+
+use super::error::{RetryExhausted, TimeoutError};
+use crate::context::ContextError;
+use crate::effect::bracket::BracketError;
+
+/// How a failure should be treated by resilience tooling.
+///
+/// # Example
+///
+/// ```rust
+/// use stillwater::retry::Class;
+///
+/// assert!(Class::Transient.is_retryable());
+/// assert!(Class::Throttled.is_retryable());
+/// assert!(!Class::Permanent.is_retryable());
+/// assert_eq!(Class::Canceled.as_str(), "canceled");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Class {
+    /// A temporary failure, such as a dropped connection, that may succeed
+    /// on retry
+    Transient,
+    /// A failure that will recur on retry, such as invalid input
+    Permanent,
+    /// The callee asked the caller to slow down; retry after backing off
+    Throttled,
+    /// The operation was canceled and should not be retried
+    Canceled,
+}
+
+impl Class {
+    /// Returns `true` for [`Class::Transient`] and [`Class::Throttled`].
+    pub fn is_retryable(self) -> bool {
+        matches!(self, Class::Transient | Class::Throttled)
+    }
+
+    /// Lowercase name, suitable as a metrics label.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Class::Transient => "transient",
+            Class::Permanent => "permanent",
+            Class::Throttled => "throttled",
+            Class::Canceled => "canceled",
+        }
+    }
+}
+
+impl fmt::Display for Class {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Classifies an error for retry and metrics decisions.
+///
+/// Implement it by hand, or with the [`error_class!`](crate::error_class)
+/// macro for enums.
+///
+/// # Example
+///
+/// ```rust
+/// use stillwater::retry::{Class, ErrorClass};
+///
+/// enum FetchError {
+///     Timeout,
+///     NotFound,
+/// }
+///
+/// impl ErrorClass for FetchError {
+///     fn class(&self) -> Class {
+///         match self {
+///             FetchError::Timeout => Class::Transient,
+///             FetchError::NotFound => Class::Permanent,
+///         }
+///     }
+/// }
+///
+/// assert!(FetchError::Timeout.is_retryable());
+/// assert!(!FetchError::NotFound.is_retryable());
+/// ```
+pub trait ErrorClass {
+    /// The class of this error.
+    fn class(&self) -> Class;
+
+    /// Whether retrying might succeed; see [`Class::is_retryable`].
+    fn is_retryable(&self) -> bool {
+        self.class().is_retryable()
+    }
+}
+
+/// Implement [`ErrorClass`] for a type by matching on its values.
+///
+/// Each arm maps a pattern to a [`Class`] variant name. Patterns are
+/// written against `Self`, and a trailing `_` arm can supply a default.
+///
+/// # Example
+///
+/// ```rust
+/// use stillwater::error_class;
+/// use stillwater::retry::{Class, ErrorClass};
+///
+/// enum ApiError {
+///     Timeout,
+///     RateLimited { retry_after_secs: u64 },
+///     Status(u16),
+///     Canceled,
+/// }
+///
+/// error_class!(ApiError {
+///     Self::Timeout => Transient,
+///     Self::RateLimited { .. } => Throttled,
+///     Self::Status(500..=599) => Transient,
+///     Self::Canceled => Canceled,
+///     _ => Permanent,
+/// });
+///
+/// assert_eq!(ApiError::Status(503).class(), Class::Transient);
+/// assert_eq!(ApiError::Status(404).class(), Class::Permanent);
+/// assert!(ApiError::RateLimited { retry_after_secs: 5 }.is_retryable());
+/// ```
+#[macro_export]
+macro_rules! error_class {
+    ($ty:ty { $($pat:pat => $class:ident),+ $(,)? }) => {
+        impl $crate::retry::ErrorClass for $ty {
+            fn class(&self) -> $crate::retry::Class {
+                match self {
+                    $($pat => $crate::retry::Class::$class,)+
+                }
+            }
+        }
+    };
+}
+
+impl<E: ErrorClass + ?Sized> ErrorClass for &E {
+    fn class(&self) -> Class {
+        (**self).class()
+    }
+}
+
+impl<E: ErrorClass + ?Sized> ErrorClass for Box<E> {
+    fn class(&self) -> Class {
+        (**self).class()
+    }
+}
+
+/// Timeouts are [`Class::Transient`]; inner errors keep their own class.
+impl<E: ErrorClass> ErrorClass for TimeoutError<E> {
+    fn class(&self) -> Class {
+        match self {
+            TimeoutError::Timeout { .. } => Class::Transient,
+            TimeoutError::Inner(e) => e.class(),
+        }
+    }
+}
+
+/// Exhausted retries are [`Class::Permanent`]: the retry budget at this
+/// layer is spent, so outer layers should not retry again.
+impl<E> ErrorClass for RetryExhausted<E> {
+    fn class(&self) -> Class {
+        Class::Permanent
+    }
+}
+
+/// Context doesn't change the class of the wrapped error.
+impl<E: ErrorClass> ErrorClass for ContextError<E> {
+    fn class(&self) -> Class {
+        self.inner().class()
+    }
+}
+
+/// Classified by the error of the failing phase; when both use and cleanup
+/// fail, the use error decides.
+impl<E: ErrorClass> ErrorClass for BracketError<E> {
+    fn class(&self) -> Class {
+        match self {
+            BracketError::AcquireError(e)
+            | BracketError::UseError(e)
+            | BracketError::CleanupError(e)
+            | BracketError::Both { use_error: e, .. } => e.class(),
+        }
+    }
+}
+
+/// Connection and timing failures are [`Class::Transient`]; everything else
+/// is [`Class::Permanent`].
+impl ErrorClass for io::Error {
+    fn class(&self) -> Class {
+        match self.kind() {
+            io::ErrorKind::TimedOut
+            | io::ErrorKind::Interrupted
+            | io::ErrorKind::WouldBlock
+            | io::ErrorKind::ConnectionReset
+            | io::ErrorKind::ConnectionAborted
+            | io::ErrorKind::ConnectionRefused
+            | io::ErrorKind::BrokenPipe
+            | io::ErrorKind::UnexpectedEof => Class::Transient,
+            _ => Class::Permanent,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[derive(Debug)]
+    enum TestError {
+        Busy,
+        Invalid(u16),
+    }
+
+    crate::error_class!(TestError {
+        Self::Busy => Throttled,
+        Self::Invalid(429) => Throttled,
+        Self::Invalid(_) => Permanent,
+    });
+
+    #[test]
+    fn test_macro_impl() {
+        assert_eq!(TestError::Busy.class(), Class::Throttled);
+        assert!(TestError::Invalid(429).is_retryable());
+        assert!(!TestError::Invalid(400).is_retryable());
+
+        let boxed: Box<TestError> = Box::new(TestError::Busy);
+        assert!(boxed.is_retryable());
+    }
+
+    #[test]
+    fn test_wrapper_errors() {
+        let timeout: TimeoutError<TestError> = TimeoutError::timeout(Duration::from_secs(1));
+        assert_eq!(timeout.class(), Class::Transient);
+        assert_eq!(
+            TimeoutError::inner(TestError::Invalid(400)).class(),
+            Class::Permanent
+        );
+
+        let exhausted = RetryExhausted::new(TestError::Busy, 3, Duration::ZERO);
+        assert!(!exhausted.is_retryable());
+    }
+
+    #[test]
+    fn test_wrappers_delegate() {
+        let err = ContextError::new(TestError::Busy).context("polling queue");
+        assert_eq!(err.class(), Class::Throttled);
+
+        let err = BracketError::Both {
+            use_error: TestError::Invalid(400),
+            cleanup_error: TestError::Busy,
+        };
+        assert_eq!(err.class(), Class::Permanent);
+    }
+
+    #[test]
+    fn test_io_errors() {
+        assert!(io::Error::from(io::ErrorKind::ConnectionReset).is_retryable());
+        assert!(!io::Error::from(io::ErrorKind::NotFound).is_retryable());
+    }
+}
@@ -56,19 +56,95 @@ impl<E> RetryExhausted<E> {
         self.final_error
     }
 
+    /// Extract the final error, discarding metadata.
+    ///
+    /// The same as [`into_error`](Self::into_error), named like the other
+    /// wrappers' `into_inner`.
+    pub fn into_inner(self) -> E {
+        self.final_error
+    }
+
     /// Get a reference to the final error.
     pub fn error(&self) -> &E {
         &self.final_error
     }
 
-    /// Extract the success value (for symmetry with Result).
-    ///
-    /// Since RetryExhausted is always an error, this returns the inner error.
+    /// Extract the final error.
+    #[deprecated(
+        since = "2.0.0",
+        note = "successful retries now return `RetrySuccess`; use `into_error` or `into_inner`"
+    )]
     pub fn into_value(self) -> E {
         self.final_error
     }
 }
 
+/// The result of an operation that succeeded, possibly after retries.
+///
+/// Returned on the success path by `retry`, `retry_with_hooks`,
+/// [`retry_sync`](crate::retry::retry_sync), and
+/// [`retry_blocking`](crate::retry::retry_blocking), alongside
+/// [`RetryExhausted`] or [`SyncRetryError`] on the failure path.
+///
+/// # Examples
+///
+/// ```rust
+/// use stillwater::retry::retry_sync;
+/// use stillwater::RetryPolicy;
+/// use std::time::Duration;
+///
+/// let success = retry_sync(
+///     || Ok::<_, String>("ready"),
+///     RetryPolicy::constant(Duration::from_millis(1)).with_max_retries(2),
+/// )
+/// .unwrap();
+///
+/// assert_eq!(success.attempts, 1);
+/// assert_eq!(success.into_value(), "ready");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetrySuccess<T> {
+    /// The value from the successful attempt.
+    pub value: T,
+    /// Total number of attempts made, including the successful one.
+    pub attempts: u32,
+    /// Total time from the first attempt to success.
+    pub total_duration: Duration,
+}
+
+impl<T> RetrySuccess<T> {
+    /// Create a new RetrySuccess.
+    pub fn new(value: T, attempts: u32, total_duration: Duration) -> Self {
+        Self {
+            value,
+            attempts,
+            total_duration,
+        }
+    }
+
+    /// Extract the value, discarding metadata.
+    pub fn into_value(self) -> T {
+        self.value
+    }
+
+    /// Extract the value, discarding metadata.
+    ///
+    /// The same as [`into_value`](Self::into_value).
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+
+    /// Get a reference to the value.
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    /// Whether the first attempt succeeded.
+    pub fn first_try(&self) -> bool {
+        self.attempts <= 1
+    }
+}
+
 impl<E: std::fmt::Display> std::fmt::Display for RetryExhausted<E> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -150,6 +226,44 @@ impl<E> TimeoutError<E> {
             Self::Timeout { .. } => None,
         }
     }
+
+    /// Collapse into the inner error type, building the error for a
+    /// timeout with `on_timeout`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use stillwater::TimeoutError;
+    /// use std::time::Duration;
+    ///
+    /// let err: TimeoutError<String> = TimeoutError::timeout(Duration::from_secs(2));
+    /// let message = err.into_inner_or_else(|d| format!("gave up after {:?}", d));
+    /// assert_eq!(message, "gave up after 2s");
+    /// ```
+    pub fn into_inner_or_else(self, on_timeout: impl FnOnce(Duration) -> E) -> E {
+        match self {
+            Self::Inner(e) => e,
+            Self::Timeout { duration } => on_timeout(duration),
+        }
+    }
+}
+
+/// Timeouts become [`std::io::ErrorKind::TimedOut`]; inner errors convert
+/// with their own `Into<std::io::Error>`.
+///
+/// A blanket `From<TimeoutError<E>> for E` is not allowed by Rust's
+/// coherence rules. Applications implement it for their own error type
+/// instead, usually in terms of [`TimeoutError::into_inner_or_else`].
+impl<E: Into<std::io::Error>> From<TimeoutError<E>> for std::io::Error {
+    fn from(err: TimeoutError<E>) -> Self {
+        match err {
+            TimeoutError::Timeout { duration } => std::io::Error::new(
+                std::io::ErrorKind::TimedOut,
+                format!("operation timed out after {:?}", duration),
+            ),
+            TimeoutError::Inner(e) => e.into(),
+        }
+    }
 }
 
 impl<E: std::fmt::Display> std::fmt::Display for TimeoutError<E> {
@@ -170,6 +284,89 @@ impl<E: std::error::Error + 'static> std::error::Error for TimeoutError<E> {
     }
 }
 
+/// Error returned by [`retry_sync`](crate::retry::retry_sync) and
+/// [`retry_blocking`](crate::retry::retry_blocking).
+///
+/// A blocking call cannot be canceled, so these functions cannot apply a
+/// policy's [attempt timeout](crate::RetryPolicy::with_attempt_timeout).
+/// They reject such a policy up front instead of ignoring the timeout.
+///
+/// # Examples
+///
+/// ```rust
+/// use stillwater::retry::{retry_sync, SyncRetryError};
+/// use stillwater::RetryPolicy;
+/// use std::time::Duration;
+///
+/// let result = retry_sync(
+///     || Err::<(), _>("busy"),
+///     RetryPolicy::constant(Duration::from_millis(1))
+///         .with_max_retries(1)
+///         .with_attempt_timeout(Duration::from_secs(1)),
+/// );
+///
+/// assert_eq!(
+///     result,
+///     Err(SyncRetryError::AttemptTimeoutUnsupported {
+///         timeout: Duration::from_secs(1)
+///     })
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SyncRetryError<E> {
+    /// Every attempt failed.
+    Exhausted(RetryExhausted<E>),
+    /// The policy sets an attempt timeout, which a blocking call cannot
+    /// honor. No attempt was made.
+    AttemptTimeoutUnsupported {
+        /// The attempt timeout set on the policy.
+        timeout: Duration,
+    },
+}
+
+impl<E> SyncRetryError<E> {
+    /// Returns true if every attempt failed.
+    pub fn is_exhausted(&self) -> bool {
+        matches!(self, Self::Exhausted(_))
+    }
+
+    /// Get the exhausted retries, or `None` if the policy was rejected.
+    pub fn into_exhausted(self) -> Option<RetryExhausted<E>> {
+        match self {
+            Self::Exhausted(exhausted) => Some(exhausted),
+            Self::AttemptTimeoutUnsupported { .. } => None,
+        }
+    }
+}
+
+impl<E> From<RetryExhausted<E>> for SyncRetryError<E> {
+    fn from(exhausted: RetryExhausted<E>) -> Self {
+        Self::Exhausted(exhausted)
+    }
+}
+
+impl<E: std::fmt::Display> std::fmt::Display for SyncRetryError<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Exhausted(exhausted) => write!(f, "{}", exhausted),
+            Self::AttemptTimeoutUnsupported { timeout } => write!(
+                f,
+                "attempt timeout of {:?} cannot be applied to a blocking call",
+                timeout
+            ),
+        }
+    }
+}
+
+impl<E: std::error::Error + 'static> std::error::Error for SyncRetryError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Exhausted(exhausted) => Some(exhausted),
+            Self::AttemptTimeoutUnsupported { .. } => None,
+        }
+    }
+}
+
 #[cfg(test)]
 mod error_tests {
     use super::*;
@@ -189,6 +386,25 @@ mod error_tests {
         assert_eq!(err.into_error(), "test error");
     }
 
+    #[test]
+    fn test_timeout_into_io_error() {
+        let timed_out: std::io::Error =
+            TimeoutError::<std::io::Error>::timeout(Duration::from_millis(50)).into();
+        assert_eq!(timed_out.kind(), std::io::ErrorKind::TimedOut);
+
+        let inner: std::io::Error =
+            TimeoutError::inner(std::io::Error::from(std::io::ErrorKind::NotFound)).into();
+        assert_eq!(inner.kind(), std::io::ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn test_retry_success_accessors() {
+        let success = RetrySuccess::new(7, 1, Duration::ZERO);
+        assert!(success.first_try());
+        assert_eq!(success.value(), &7);
+        assert_eq!(success.into_inner(), 7);
+    }
+
     #[test]
     fn test_timeout_error_timeout() {
         let err: TimeoutError<String> = TimeoutError::timeout(Duration::from_secs(5));
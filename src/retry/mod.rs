@@ -55,16 +55,50 @@
 //!     .with_max_retries(5);
 //! ```
 //!
+//! # Error Classification
+//!
+//! Implement [`ErrorClass`] (by hand or with [`error_class!`](crate::error_class))
+//! to classify errors as [`Class::Transient`], [`Class::Permanent`],
+//! [`Class::Throttled`], or [`Class::Canceled`]. `retry_classified` retries
+//! only retryable classes, and [`Class::as_str`] provides a stable metrics
+//! label.
+//!
+//! # Without Effects
+//!
+//! [`retry_sync`] retries a plain closure returning `Result`, sleeping the
+//! thread between attempts. With the `async` feature, [`retry_blocking`]
+//! does the same from async code, running each attempt on Tokio's blocking
+//! pool. Both use the same policies as the effect combinators, so code can
+//! adopt them before it is migrated to effects. A blocking call cannot be
+//! canceled, so a policy with an attempt timeout is rejected with
+//! [`SyncRetryError::AttemptTimeoutUnsupported`].
+//!
+//! # Surviving Restarts
+//!
+//! For backoffs long enough to span a deploy, `retry_persistent` saves its
+//! progress as a [`RetryState`] in a [`PersistentRetryState`] store after
+//! each failure and resumes from it when run again.
+//!
 //! # Error Types
 //!
+//! - [`RetrySuccess`]: Returned when an attempt succeeds, contains the value and metadata
 //! - [`RetryExhausted`]: Returned when all retries fail, contains the final error and metadata
+//! - [`SyncRetryError`]: Returned by the blocking retries, which reject attempt timeouts
 //! - [`TimeoutError`]: Returned when an effect times out
 
+mod class;
 mod error;
+mod persist;
 mod policy;
+mod sync;
 
-pub use error::{RetryExhausted, TimeoutError};
+pub use class::{Class, ErrorClass};
+pub use error::{RetryExhausted, RetrySuccess, SyncRetryError, TimeoutError};
+pub use persist::{InMemoryRetryStore, PersistentRetryState, RetryState};
 pub use policy::{JitterStrategy, RetryEvent, RetryPolicy, RetryStrategy};
+#[cfg(feature = "async")]
+pub use sync::retry_blocking;
+pub use sync::retry_sync;
 
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,187 @@
+//! Retry progress that survives process restarts.
+//!
+//! With backoffs measured in hours, a deploy or crash in the middle of a
+//! retry loop would otherwise start the schedule over at the first attempt.
+//! [`retry_persistent`](crate::effect::retry::retry_persistent) records its
+//! progress in a [`PersistentRetryState`] store under a caller-chosen key
+//! after every failed attempt, and resumes from it on the next run.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+
+use crate::effect::BoxFuture;
+
+/// Progress of a retry loop, as saved between attempts.
+///
+/// With the `serde` feature, serializes with `prev_delay` in milliseconds
+/// and `next_retry_at` in milliseconds since the Unix epoch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(into = "serde_repr::StateRepr", from = "serde_repr::StateRepr")
+)]
+pub struct RetryState {
+    /// Number of attempts that have failed so far.
+    pub attempt: u32,
+    /// The delay chosen after the last failure, used by decorrelated
+    /// jitter.
+    pub prev_delay: Option<Duration>,
+    /// When the next attempt is due.
+    pub next_retry_at: SystemTime,
+}
+
+impl RetryState {
+    /// Time left until the next attempt is due, zero if it is overdue.
+    pub fn remaining(&self) -> Duration {
+        self.next_retry_at
+            .duration_since(SystemTime::now())
+            .unwrap_or(Duration::ZERO)
+    }
+}
+
+/// A store for [`RetryState`], keyed by the name of the retried operation.
+///
+/// Implementations typically write to a database, a key-value store, or a
+/// file. Failures to read or write are not reported to the retry loop: a
+/// store that cannot load a state should return `None`, and the loop then
+/// starts over as it would without a store.
+pub trait PersistentRetryState: Send + Sync {
+    /// Load the state saved under `key`, if any.
+    fn load<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Option<RetryState>>;
+
+    /// Save `state` under `key`, replacing any earlier state.
+    fn save<'a>(&'a self, key: &'a str, state: RetryState) -> BoxFuture<'a, ()>;
+
+    /// Remove the state saved under `key`, once the loop has finished.
+    fn clear<'a>(&'a self, key: &'a str) -> BoxFuture<'a, ()>;
+}
+
+/// A [`PersistentRetryState`] store held in memory.
+///
+/// Progress does not outlive the process, so this is for tests and for
+/// sharing progress between retry loops within one process.
+#[derive(Debug, Default)]
+pub struct InMemoryRetryStore {
+    states: Mutex<HashMap<String, RetryState>>,
+}
+
+impl InMemoryRetryStore {
+    /// An empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The state saved under `key`, if any.
+    pub fn get(&self, key: &str) -> Option<RetryState> {
+        self.states.lock().unwrap().get(key).copied()
+    }
+
+    /// Save `state` under `key`, replacing any earlier state.
+    pub fn insert(&self, key: impl Into<String>, state: RetryState) {
+        self.states.lock().unwrap().insert(key.into(), state);
+    }
+}
+
+impl PersistentRetryState for InMemoryRetryStore {
+    fn load<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Option<RetryState>> {
+        let state = self.get(key);
+        Box::pin(async move { state })
+    }
+
+    fn save<'a>(&'a self, key: &'a str, state: RetryState) -> BoxFuture<'a, ()> {
+        self.insert(key, state);
+        Box::pin(async {})
+    }
+
+    fn clear<'a>(&'a self, key: &'a str) -> BoxFuture<'a, ()> {
+        self.states.lock().unwrap().remove(key);
+        Box::pin(async {})
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serde_repr {
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    use serde::{Deserialize, Serialize};
+
+    use super::RetryState;
+
+    fn millis(d: Duration) -> u64 {
+        u64::try_from(d.as_millis()).unwrap_or(u64::MAX)
+    }
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(deny_unknown_fields)]
+    pub(super) struct StateRepr {
+        attempt: u32,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        prev_delay_ms: Option<u64>,
+        next_retry_at_ms: u64,
+    }
+
+    impl From<RetryState> for StateRepr {
+        fn from(state: RetryState) -> Self {
+            StateRepr {
+                attempt: state.attempt,
+                prev_delay_ms: state.prev_delay.map(millis),
+                next_retry_at_ms: state
+                    .next_retry_at
+                    .duration_since(UNIX_EPOCH)
+                    .map(millis)
+                    .unwrap_or(0),
+            }
+        }
+    }
+
+    impl From<StateRepr> for RetryState {
+        fn from(repr: StateRepr) -> Self {
+            RetryState {
+                attempt: repr.attempt,
+                prev_delay: repr.prev_delay_ms.map(Duration::from_millis),
+                next_retry_at: SystemTime::UNIX_EPOCH
+                    + Duration::from_millis(repr.next_retry_at_ms),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remaining_is_zero_once_overdue() {
+        let overdue = RetryState {
+            attempt: 1,
+            prev_delay: None,
+            next_retry_at: SystemTime::now() - Duration::from_secs(5),
+        };
+        assert_eq!(overdue.remaining(), Duration::ZERO);
+
+        let pending = RetryState {
+            next_retry_at: SystemTime::now() + Duration::from_secs(3600),
+            ..overdue
+        };
+        assert!(pending.remaining() > Duration::from_secs(3500));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_uses_milliseconds() {
+        let state = RetryState {
+            attempt: 4,
+            prev_delay: Some(Duration::from_secs(2)),
+            next_retry_at: SystemTime::UNIX_EPOCH + Duration::from_millis(1_700_000_000_123),
+        };
+
+        let json = serde_json::to_string(&state).unwrap();
+        assert_eq!(
+            json,
+            r#"{"attempt":4,"prev_delay_ms":2000,"next_retry_at_ms":1700000000123}"#
+        );
+        assert_eq!(serde_json::from_str::<RetryState>(&json).unwrap(), state);
+    }
+}
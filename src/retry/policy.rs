@@ -32,16 +32,62 @@ use std::time::Duration;
 /// let policy = RetryPolicy::constant(Duration::from_millis(500))
 ///     .with_max_delay(Duration::from_secs(30));
 /// ```
+///
+/// # Configuration Files
+///
+/// With the `serde` feature, policies serialize with durations in
+/// milliseconds, and deserializing runs [`validate`](Self::validate), so
+/// an unbounded policy in a config file is rejected when it is loaded:
+///
+/// ```rust
+/// # #[cfg(feature = "serde")]
+/// # {
+/// use stillwater::RetryPolicy;
+/// use std::time::Duration;
+///
+/// let policy: RetryPolicy = serde_json::from_str(
+///     r#"{
+///         "strategy": { "type": "exponential", "base_ms": 100 },
+///         "max_retries": 5,
+///         "max_delay_ms": 30000,
+///         "jitter": { "type": "full" },
+///         "name": "payments"
+///     }"#,
+/// )
+/// .unwrap();
+///
+/// assert_eq!(policy.max_delay(), Some(Duration::from_secs(30)));
+/// assert_eq!(policy.name(), "payments");
+///
+/// let unbounded = r#"{ "strategy": { "type": "constant", "delay_ms": 10 } }"#;
+/// assert!(serde_json::from_str::<RetryPolicy>(unbounded).is_err());
+/// # }
+/// ```
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(into = "serde_repr::PolicyRepr", try_from = "serde_repr::PolicyRepr")
+)]
 pub struct RetryPolicy {
     strategy: RetryStrategy,
     max_retries: Option<u32>,
     max_delay: Option<Duration>,
     jitter: JitterStrategy,
+    name: Option<String>,
+    attempt_timeout: Option<Duration>,
 }
 
 /// The backoff strategy for retry delays.
+///
+/// With the `serde` feature, serialized with a `type` tag and the duration
+/// in milliseconds, for example `{ "type": "linear", "base_ms": 250 }`.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(into = "serde_repr::StrategyRepr", from = "serde_repr::StrategyRepr")
+)]
 pub enum RetryStrategy {
     /// Fixed delay between attempts.
     Constant(Duration),
@@ -63,7 +109,15 @@ pub enum RetryStrategy {
 }
 
 /// Strategy for adding randomness to delays.
+///
+/// With the `serde` feature, serialized with a `type` tag, for example
+/// `{ "type": "proportional", "factor": 0.25 }` or `{ "type": "full" }`.
 #[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(into = "serde_repr::JitterRepr", from = "serde_repr::JitterRepr")
+)]
 pub enum JitterStrategy {
     /// No jitter applied.
     #[default]
@@ -72,7 +126,8 @@ pub enum JitterStrategy {
     Proportional(f64),
     /// Random delay between 0 and calculated delay (AWS recommended).
     Full,
-    /// Decorrelated jitter (AWS style).
+    /// Decorrelated jitter (AWS style): random between the first delay and
+    /// three times the previous delay, independent of the backoff schedule.
     Decorrelated,
 }
 
@@ -113,6 +168,8 @@ impl RetryPolicy {
             max_retries: None,
             max_delay: None,
             jitter: JitterStrategy::None,
+            name: None,
+            attempt_timeout: None,
         }
     }
 
@@ -140,6 +197,8 @@ impl RetryPolicy {
             max_retries: None,
             max_delay: None,
             jitter: JitterStrategy::None,
+            name: None,
+            attempt_timeout: None,
         }
     }
 
@@ -167,6 +226,8 @@ impl RetryPolicy {
             max_retries: None,
             max_delay: None,
             jitter: JitterStrategy::None,
+            name: None,
+            attempt_timeout: None,
         }
     }
 
@@ -196,6 +257,8 @@ impl RetryPolicy {
             max_retries: None,
             max_delay: None,
             jitter: JitterStrategy::None,
+            name: None,
+            attempt_timeout: None,
         }
     }
 
@@ -290,8 +353,10 @@ impl RetryPolicy {
 
     /// Use decorrelated jitter (AWS style).
     ///
-    /// Each delay is random between base and 3x the previous delay.
-    /// This provides good spread while maintaining progression.
+    /// Each delay is random between the first delay of the strategy and 3x
+    /// the previous delay, capped by `max_delay`. Delays grow from the
+    /// previous random value rather than the backoff schedule, which spreads
+    /// a fleet of clients further apart than proportional jitter.
     ///
     /// **Note**: Requires the `jitter` feature. Without it, this method does nothing.
     ///
@@ -310,6 +375,96 @@ impl RetryPolicy {
         self
     }
 
+    /// Set the jitter strategy.
+    ///
+    /// Equivalent to the `with_*_jitter` methods, for when the strategy is
+    /// chosen at runtime. A proportional factor is clamped to `0.0..=1.0`,
+    /// as in [`with_jitter`](Self::with_jitter).
+    ///
+    /// **Note**: Requires the `jitter` feature. Without it, jitter is not
+    /// applied.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use stillwater::{JitterStrategy, RetryPolicy};
+    /// use std::time::Duration;
+    ///
+    /// let policy = RetryPolicy::exponential(Duration::from_millis(100))
+    ///     .with_jitter_strategy(JitterStrategy::Decorrelated)
+    ///     .with_max_retries(5);
+    ///
+    /// assert_eq!(policy.jitter(), &JitterStrategy::Decorrelated);
+    /// ```
+    pub fn with_jitter_strategy(mut self, jitter: JitterStrategy) -> Self {
+        self.jitter = match jitter {
+            JitterStrategy::Proportional(factor) => {
+                JitterStrategy::Proportional(factor.clamp(0.0, 1.0))
+            }
+            other => other,
+        };
+        self
+    }
+
+    /// Name the policy for logs and tracing events.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use stillwater::RetryPolicy;
+    /// use std::time::Duration;
+    ///
+    /// let policy = RetryPolicy::exponential(Duration::from_millis(100))
+    ///     .with_max_retries(5)
+    ///     .with_name("payments-api");
+    ///
+    /// assert_eq!(policy.name(), "payments-api");
+    /// ```
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Bound each attempt to `timeout`.
+    ///
+    /// An attempt still running when the timeout elapses is canceled and
+    /// counts as a failed attempt, so a hung call is retried like any other
+    /// failure. The effect retries in [`crate::effect::retry`] report errors
+    /// as [`TimeoutError`](crate::TimeoutError) to tell the two kinds of
+    /// failure apart. A blocking call cannot be canceled, so
+    /// [`retry_sync`](crate::retry::retry_sync) and
+    /// [`retry_blocking`](crate::retry::retry_blocking) reject a policy that
+    /// sets one with [`SyncRetryError`](crate::retry::SyncRetryError).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use stillwater::RetryPolicy;
+    /// use std::time::Duration;
+    ///
+    /// let policy = RetryPolicy::exponential(Duration::from_millis(100))
+    ///     .with_max_retries(3)
+    ///     .with_attempt_timeout(Duration::from_secs(2));
+    ///
+    /// assert_eq!(policy.attempt_timeout(), Some(Duration::from_secs(2)));
+    /// ```
+    pub fn with_attempt_timeout(mut self, timeout: Duration) -> Self {
+        self.attempt_timeout = Some(timeout);
+        self
+    }
+
+    /// Get the policy name, or the strategy (such as `"exponential"`) if
+    /// none was set.
+    pub fn name(&self) -> &str {
+        match (&self.name, &self.strategy) {
+            (Some(name), _) => name,
+            (None, RetryStrategy::Constant(_)) => "constant",
+            (None, RetryStrategy::Linear { .. }) => "linear",
+            (None, RetryStrategy::Exponential { .. }) => "exponential",
+            (None, RetryStrategy::Fibonacci { .. }) => "fibonacci",
+        }
+    }
+
     /// Get the maximum number of retries.
     pub fn max_retries(&self) -> Option<u32> {
         self.max_retries
@@ -320,6 +475,11 @@ impl RetryPolicy {
         self.max_delay
     }
 
+    /// Get the timeout for each attempt.
+    pub fn attempt_timeout(&self) -> Option<Duration> {
+        self.attempt_timeout
+    }
+
     /// Get the jitter strategy.
     pub fn jitter(&self) -> &JitterStrategy {
         &self.jitter
@@ -385,19 +545,68 @@ impl RetryPolicy {
         prev_delay: Option<Duration>,
     ) -> Option<Duration> {
         let base_delay = self.delay_for_attempt(attempt)?;
+        // Decorrelated jitter grows from the previous delay, so its lower
+        // bound stays at the first delay instead of following the schedule.
+        let base_delay = match self.jitter {
+            JitterStrategy::Decorrelated => self.delay_for_attempt(0).unwrap_or(base_delay),
+            _ => base_delay,
+        };
         Some(self.jitter.apply(base_delay, prev_delay, self.max_delay))
     }
 
-    /// Validate that the policy has at least one bound.
+    /// Validate that the policy has at least one bound and, with
+    /// proportional jitter, a factor between 0 and 1.
     ///
     /// Returns an error message if the policy is invalid.
     pub fn validate(&self) -> Result<(), &'static str> {
         if self.max_retries.is_none() && self.max_delay.is_none() {
-            Err("RetryPolicy must have at least one bound (max_retries or max_delay)")
-        } else {
-            Ok(())
+            return Err("RetryPolicy must have at least one bound (max_retries or max_delay)");
+        }
+        match self.jitter {
+            JitterStrategy::Proportional(factor) if !(0.0..=1.0).contains(&factor) => {
+                Err("RetryPolicy jitter factor must be between 0 and 1")
+            }
+            _ => Ok(()),
         }
     }
+
+    /// Build a policy from its parts, checking it with
+    /// [`validate`](Self::validate).
+    ///
+    /// Use this when the parameters come from outside the program, such as
+    /// a config file or command-line flags.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use stillwater::{JitterStrategy, RetryPolicy, RetryStrategy};
+    /// use std::time::Duration;
+    ///
+    /// let strategy = RetryStrategy::Exponential { base: Duration::from_millis(50) };
+    ///
+    /// let policy = RetryPolicy::try_new(strategy.clone(), Some(3), None, JitterStrategy::Full);
+    /// assert_eq!(policy.unwrap().max_retries(), Some(3));
+    ///
+    /// let unbounded = RetryPolicy::try_new(strategy, None, None, JitterStrategy::None);
+    /// assert!(unbounded.is_err());
+    /// ```
+    pub fn try_new(
+        strategy: RetryStrategy,
+        max_retries: Option<u32>,
+        max_delay: Option<Duration>,
+        jitter: JitterStrategy,
+    ) -> Result<Self, &'static str> {
+        let policy = RetryPolicy {
+            strategy,
+            max_retries,
+            max_delay,
+            jitter,
+            name: None,
+            attempt_timeout: None,
+        };
+        policy.validate()?;
+        Ok(policy)
+    }
 }
 
 impl JitterStrategy {
@@ -482,6 +691,145 @@ fn fibonacci(n: u32) -> u32 {
     b
 }
 
+/// Config-file representations, with durations in milliseconds.
+#[cfg(feature = "serde")]
+mod serde_repr {
+    use std::time::Duration;
+
+    use serde::{Deserialize, Serialize};
+
+    use super::{JitterStrategy, RetryPolicy, RetryStrategy};
+
+    fn millis(d: Duration) -> u64 {
+        u64::try_from(d.as_millis()).unwrap_or(u64::MAX)
+    }
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(deny_unknown_fields)]
+    pub(super) struct PolicyRepr {
+        strategy: RetryStrategy,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max_retries: Option<u32>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max_delay_ms: Option<u64>,
+        #[serde(default)]
+        jitter: JitterStrategy,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        name: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        attempt_timeout_ms: Option<u64>,
+    }
+
+    impl From<RetryPolicy> for PolicyRepr {
+        fn from(policy: RetryPolicy) -> Self {
+            PolicyRepr {
+                strategy: policy.strategy,
+                max_retries: policy.max_retries,
+                max_delay_ms: policy.max_delay.map(millis),
+                jitter: policy.jitter,
+                name: policy.name,
+                attempt_timeout_ms: policy.attempt_timeout.map(millis),
+            }
+        }
+    }
+
+    impl TryFrom<PolicyRepr> for RetryPolicy {
+        type Error = &'static str;
+
+        fn try_from(repr: PolicyRepr) -> Result<Self, Self::Error> {
+            let policy = RetryPolicy::try_new(
+                repr.strategy,
+                repr.max_retries,
+                repr.max_delay_ms.map(Duration::from_millis),
+                repr.jitter,
+            )?;
+            Ok(RetryPolicy {
+                name: repr.name,
+                attempt_timeout: repr.attempt_timeout_ms.map(Duration::from_millis),
+                ..policy
+            })
+        }
+    }
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+    pub(super) enum StrategyRepr {
+        Constant { delay_ms: u64 },
+        Linear { base_ms: u64 },
+        Exponential { base_ms: u64 },
+        Fibonacci { base_ms: u64 },
+    }
+
+    impl From<RetryStrategy> for StrategyRepr {
+        fn from(strategy: RetryStrategy) -> Self {
+            match strategy {
+                RetryStrategy::Constant(delay) => StrategyRepr::Constant {
+                    delay_ms: millis(delay),
+                },
+                RetryStrategy::Linear { base } => StrategyRepr::Linear {
+                    base_ms: millis(base),
+                },
+                RetryStrategy::Exponential { base } => StrategyRepr::Exponential {
+                    base_ms: millis(base),
+                },
+                RetryStrategy::Fibonacci { base } => StrategyRepr::Fibonacci {
+                    base_ms: millis(base),
+                },
+            }
+        }
+    }
+
+    impl From<StrategyRepr> for RetryStrategy {
+        fn from(repr: StrategyRepr) -> Self {
+            match repr {
+                StrategyRepr::Constant { delay_ms } => {
+                    RetryStrategy::Constant(Duration::from_millis(delay_ms))
+                }
+                StrategyRepr::Linear { base_ms } => RetryStrategy::Linear {
+                    base: Duration::from_millis(base_ms),
+                },
+                StrategyRepr::Exponential { base_ms } => RetryStrategy::Exponential {
+                    base: Duration::from_millis(base_ms),
+                },
+                StrategyRepr::Fibonacci { base_ms } => RetryStrategy::Fibonacci {
+                    base: Duration::from_millis(base_ms),
+                },
+            }
+        }
+    }
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+    pub(super) enum JitterRepr {
+        None,
+        Proportional { factor: f64 },
+        Full,
+        Decorrelated,
+    }
+
+    impl From<JitterStrategy> for JitterRepr {
+        fn from(jitter: JitterStrategy) -> Self {
+            match jitter {
+                JitterStrategy::None => JitterRepr::None,
+                JitterStrategy::Proportional(factor) => JitterRepr::Proportional { factor },
+                JitterStrategy::Full => JitterRepr::Full,
+                JitterStrategy::Decorrelated => JitterRepr::Decorrelated,
+            }
+        }
+    }
+
+    impl From<JitterRepr> for JitterStrategy {
+        fn from(repr: JitterRepr) -> Self {
+            match repr {
+                JitterRepr::None => JitterStrategy::None,
+                JitterRepr::Proportional { factor } => JitterStrategy::Proportional(factor),
+                JitterRepr::Full => JitterStrategy::Full,
+                JitterRepr::Decorrelated => JitterStrategy::Decorrelated,
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod policy_tests {
     use super::*;
@@ -505,6 +853,83 @@ mod policy_tests {
         assert_eq!(policy.delay_for_attempt(3), None);
     }
 
+    #[test]
+    fn test_name_defaults_to_strategy() {
+        let policy = RetryPolicy::fibonacci(Duration::from_millis(10)).with_max_retries(2);
+        assert_eq!(policy.name(), "fibonacci");
+        assert_eq!(policy.with_name("search").name(), "search");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let policy = RetryPolicy::linear(Duration::from_millis(250))
+            .with_max_retries(4)
+            .with_max_delay(Duration::from_secs(2))
+            .with_jitter(0.5)
+            .with_name("search")
+            .with_attempt_timeout(Duration::from_secs(1));
+
+        let json = serde_json::to_string(&policy).unwrap();
+        assert_eq!(
+            json,
+            r#"{"strategy":{"type":"linear","base_ms":250},"max_retries":4,"max_delay_ms":2000,"jitter":{"type":"proportional","factor":0.5},"name":"search","attempt_timeout_ms":1000}"#
+        );
+        assert_eq!(serde_json::from_str::<RetryPolicy>(&json).unwrap(), policy);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_deserialize_rejects_invalid_policy() {
+        let bad_factor = r#"{
+            "strategy": { "type": "constant", "delay_ms": 5 },
+            "max_retries": 2,
+            "jitter": { "type": "proportional", "factor": 3.0 }
+        }"#;
+        let err = serde_json::from_str::<RetryPolicy>(bad_factor).unwrap_err();
+        assert!(err.to_string().contains("jitter factor"));
+    }
+
+    #[cfg(feature = "jitter")]
+    #[test]
+    fn test_full_jitter_stays_below_schedule() {
+        let policy = RetryPolicy::exponential(Duration::from_millis(100))
+            .with_max_retries(4)
+            .with_jitter_strategy(JitterStrategy::Full);
+
+        for attempt in 0..4 {
+            let delay = policy.delay_with_jitter(attempt, None).unwrap();
+            assert!(delay <= policy.delay_for_attempt(attempt).unwrap());
+        }
+    }
+
+    #[cfg(feature = "jitter")]
+    #[test]
+    fn test_decorrelated_jitter_bounds() {
+        let policy = RetryPolicy::exponential(Duration::from_millis(100))
+            .with_max_retries(8)
+            .with_max_delay(Duration::from_secs(1))
+            .with_jitter_strategy(JitterStrategy::Decorrelated);
+
+        let mut prev = None;
+        for attempt in 0..8 {
+            let delay = policy.delay_with_jitter(attempt, prev).unwrap();
+            let upper = prev.unwrap_or(Duration::from_millis(100)) * 3;
+            assert!(delay >= Duration::from_millis(100), "{:?}", delay);
+            assert!(delay <= upper.min(Duration::from_secs(1)), "{:?}", delay);
+            prev = Some(delay);
+        }
+        assert_eq!(policy.delay_with_jitter(8, prev), None);
+    }
+
+    #[test]
+    fn test_with_jitter_strategy_clamps_factor() {
+        let policy = RetryPolicy::constant(Duration::from_millis(10))
+            .with_max_retries(1)
+            .with_jitter_strategy(JitterStrategy::Proportional(4.0));
+        assert_eq!(policy.jitter(), &JitterStrategy::Proportional(1.0));
+    }
+
     #[test]
     fn test_linear_delay() {
         let policy = RetryPolicy::linear(Duration::from_millis(100)).with_max_retries(5);
@@ -0,0 +1,244 @@
+//! Retry for plain functions returning `Result`.
+//!
+//! These helpers apply a [`RetryPolicy`] to code that has not been moved to
+//! effects yet, returning the same [`RetrySuccess`] and [`RetryExhausted`]
+//! metadata as the effect combinators in [`crate::effect::retry`].
+
+use std::time::{Duration, Instant};
+
+use super::{RetryExhausted, RetryPolicy, RetrySuccess, SyncRetryError};
+
+/// Retry a synchronous operation, sleeping the current thread between
+/// attempts.
+///
+/// The closure is called once, then again after each delay the policy
+/// allows, until it succeeds or the policy gives up.
+///
+/// # Errors
+///
+/// Returns [`SyncRetryError::Exhausted`] once the policy gives up, and
+/// [`SyncRetryError::AttemptTimeoutUnsupported`] without calling the
+/// closure if the policy has an
+/// [attempt timeout](RetryPolicy::with_attempt_timeout), since the blocked
+/// thread cannot be interrupted.
+///
+/// # Examples
+///
+/// ```rust
+/// use stillwater::retry::retry_sync;
+/// use stillwater::RetryPolicy;
+/// use std::time::Duration;
+///
+/// let mut calls = 0;
+/// let result = retry_sync(
+///     || {
+///         calls += 1;
+///         if calls < 3 { Err("busy") } else { Ok(calls) }
+///     },
+///     RetryPolicy::constant(Duration::from_millis(1)).with_max_retries(5),
+/// );
+///
+/// let success = result.unwrap();
+/// assert_eq!(success.attempts, 3);
+/// assert_eq!(success.into_value(), 3);
+/// ```
+pub fn retry_sync<T, E, F>(
+    mut operation: F,
+    policy: RetryPolicy,
+) -> Result<RetrySuccess<T>, SyncRetryError<E>>
+where
+    F: FnMut() -> Result<T, E>,
+{
+    reject_attempt_timeout(&policy)?;
+    let start = Instant::now();
+    let mut attempt = 0u32;
+    let mut prev_delay: Option<Duration> = None;
+
+    loop {
+        match operation() {
+            Ok(value) => return Ok(RetrySuccess::new(value, attempt + 1, start.elapsed())),
+            Err(error) => match policy.delay_with_jitter(attempt, prev_delay) {
+                Some(d) => {
+                    std::thread::sleep(d);
+                    prev_delay = Some(d);
+                    attempt += 1;
+                }
+                None => {
+                    let exhausted = RetryExhausted::new(error, attempt + 1, start.elapsed());
+                    return Err(exhausted.into());
+                }
+            },
+        }
+    }
+}
+
+/// Retry a blocking operation from async code.
+///
+/// Each attempt runs on Tokio's blocking thread pool, and the delays
+/// between attempts are async sleeps, so the calling task never blocks a
+/// runtime worker. A panic in the operation is resumed in the caller.
+///
+/// # Errors
+///
+/// Returns [`SyncRetryError::Exhausted`] once the policy gives up, and
+/// [`SyncRetryError::AttemptTimeoutUnsupported`] without calling the
+/// closure if the policy has an
+/// [attempt timeout](RetryPolicy::with_attempt_timeout): a blocking task
+/// cannot be canceled, so a hung attempt would keep the closure.
+///
+/// # Examples
+///
+/// ```rust
+/// use stillwater::retry::retry_blocking;
+/// use stillwater::RetryPolicy;
+/// use std::time::Duration;
+///
+/// # tokio_test::block_on(async {
+/// let result = retry_blocking(
+///     || std::fs::read_to_string("/definitely/missing.toml"),
+///     RetryPolicy::constant(Duration::from_millis(1)).with_max_retries(2),
+/// )
+/// .await;
+///
+/// assert_eq!(result.unwrap_err().into_exhausted().unwrap().attempts, 3);
+/// # });
+/// ```
+#[cfg(feature = "async")]
+pub async fn retry_blocking<T, E, F>(
+    mut operation: F,
+    policy: RetryPolicy,
+) -> Result<RetrySuccess<T>, SyncRetryError<E>>
+where
+    T: Send + 'static,
+    E: Send + 'static,
+    F: FnMut() -> Result<T, E> + Send + 'static,
+{
+    reject_attempt_timeout(&policy)?;
+    let start = Instant::now();
+    let mut attempt = 0u32;
+    let mut prev_delay: Option<Duration> = None;
+
+    loop {
+        let joined = tokio::task::spawn_blocking(move || {
+            let result = operation();
+            (result, operation)
+        })
+        .await;
+        let result = match joined {
+            Ok((result, returned)) => {
+                operation = returned;
+                result
+            }
+            Err(join_error) => std::panic::resume_unwind(join_error.into_panic()),
+        };
+
+        match result {
+            Ok(value) => return Ok(RetrySuccess::new(value, attempt + 1, start.elapsed())),
+            Err(error) => match policy.delay_with_jitter(attempt, prev_delay) {
+                Some(d) => {
+                    tokio::time::sleep(d).await;
+                    prev_delay = Some(d);
+                    attempt += 1;
+                }
+                None => {
+                    let exhausted = RetryExhausted::new(error, attempt + 1, start.elapsed());
+                    return Err(exhausted.into());
+                }
+            },
+        }
+    }
+}
+
+fn reject_attempt_timeout<E>(policy: &RetryPolicy) -> Result<(), SyncRetryError<E>> {
+    match policy.attempt_timeout() {
+        Some(timeout) => Err(SyncRetryError::AttemptTimeoutUnsupported { timeout }),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_sync_exhausts_policy() {
+        let mut calls = 0;
+        let result: Result<RetrySuccess<()>, _> = retry_sync(
+            || {
+                calls += 1;
+                Err(calls)
+            },
+            RetryPolicy::constant(Duration::ZERO).with_max_retries(2),
+        );
+
+        let exhausted = result.unwrap_err().into_exhausted().unwrap();
+        assert_eq!(exhausted.final_error, 3);
+        assert_eq!(exhausted.attempts, 3);
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_retry_blocking_keeps_closure_state() {
+        let mut calls = 0;
+        let result = retry_blocking(
+            move || {
+                calls += 1;
+                if calls < 3 {
+                    Err("not yet")
+                } else {
+                    Ok(calls)
+                }
+            },
+            RetryPolicy::constant(Duration::from_millis(1)).with_max_retries(5),
+        )
+        .await;
+
+        assert_eq!(result.unwrap().into_value(), 3);
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    #[should_panic(expected = "corrupt input")]
+    async fn test_retry_blocking_resumes_panics() {
+        let _ = retry_blocking(
+            || -> Result<(), ()> { panic!("corrupt input") },
+            RetryPolicy::constant(Duration::ZERO).with_max_retries(1),
+        )
+        .await;
+    }
+
+    #[test]
+    fn test_retry_sync_rejects_attempt_timeout() {
+        let mut calls = 0;
+        let result = retry_sync(
+            || {
+                calls += 1;
+                Ok::<_, ()>(())
+            },
+            RetryPolicy::constant(Duration::ZERO).with_attempt_timeout(Duration::from_secs(1)),
+        );
+
+        assert_eq!(
+            result,
+            Err(SyncRetryError::AttemptTimeoutUnsupported {
+                timeout: Duration::from_secs(1)
+            })
+        );
+        assert_eq!(calls, 0);
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_retry_blocking_rejects_attempt_timeout() {
+        let result = retry_blocking(
+            || Ok::<_, ()>(()),
+            RetryPolicy::constant(Duration::ZERO).with_attempt_timeout(Duration::from_secs(1)),
+        )
+        .await;
+
+        assert!(matches!(
+            result,
+            Err(SyncRetryError::AttemptTimeoutUnsupported { .. })
+        ));
+    }
+}
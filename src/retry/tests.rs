@@ -46,7 +46,7 @@ async fn test_retry_succeeds_on_third_attempt() {
     let result = effect.execute(&()).await;
 
     assert!(result.is_ok());
-    assert_eq!(result.unwrap().final_error, "success");
+    assert_eq!(result.unwrap().value, "success");
     assert_eq!(attempts.load(Ordering::SeqCst), 3);
 }
 
@@ -62,7 +62,7 @@ async fn test_retry_exhausted_returns_final_error() {
     assert!(result.is_err());
     let exhausted = result.unwrap_err();
     assert_eq!(exhausted.attempts, 4); // 1 initial + 3 retries
-    assert_eq!(exhausted.final_error, "always fails");
+    assert_eq!(exhausted.final_error, TimeoutError::Inner("always fails"));
 }
 
 #[tokio::test]
@@ -89,7 +89,7 @@ async fn test_retry_if_skips_non_retryable_errors() {
 
     let result = effect.execute(&()).await;
 
-    assert_eq!(result, Err(RetryTestError::Permanent));
+    assert_eq!(result, Err(TimeoutError::Inner(RetryTestError::Permanent)));
     assert_eq!(attempts.load(Ordering::SeqCst), 1); // No retries for permanent error
 }
 
@@ -151,7 +151,7 @@ async fn test_retry_with_hooks_calls_hook() {
         RetryPolicy::constant(Duration::from_millis(1)).with_max_retries(5),
         {
             let hook_calls = hook_calls.clone();
-            move |_event: &RetryEvent<'_, &str>| {
+            move |_event: &RetryEvent<'_, TimeoutError<&str>>| {
                 hook_calls.fetch_add(1, Ordering::SeqCst);
             }
         },
@@ -251,10 +251,8 @@ async fn test_retry_preserves_success_value() {
 
     let result = effect.execute(&()).await;
 
-    assert!(result.is_ok());
-    // The success value is wrapped in Ok, and we get RetryExhausted on error
-    // For success, we need to check the value is preserved
     let success = result.unwrap();
+    assert_eq!(success.attempts, 1);
     assert_eq!(success.into_value(), 42);
 }
 
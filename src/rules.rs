@@ -0,0 +1,354 @@
+//! Named rule sets built from predicates
+//!
+//! A [`RuleSet`] is a list of named predicate + error pairs. Evaluating it
+//! against a value checks every rule and collects all violations, like a
+//! chain of [`Validation::ensure`] calls, but the rules can also be
+//! inspected: [`RuleSet::metadata`] lists them, and [`RuleSet::evaluate`]
+//! returns a [`RuleReport`] recording which rules passed and which were
+//! violated. With the `serde` feature, [`RuleMeta`] and [`RuleReport`] are
+//! serializable for audit output.
+//!
+//! # Example
+//!
+//! ```rust
+//! use stillwater::predicate::*;
+//! use stillwater::rules::RuleSet;
+//! use stillwater::Validation;
+//!
+//! let username = RuleSet::new()
+//!     .rule("min_length", len_min(3), "too short")
+//!     .rule("max_length", len_max(20), "too long")
+//!     .rule("charset", all_chars(|c: char| c.is_alphanumeric()), "invalid characters")
+//!     .describe("only letters and digits are allowed");
+//!
+//! assert_eq!(
+//!     username.validate(String::from("a!")),
+//!     Validation::Failure(vec!["too short", "invalid characters"])
+//! );
+//!
+//! let report = username.evaluate(&String::from("a!"));
+//! assert_eq!(report.violated().collect::<Vec<_>>(), vec!["min_length", "charset"]);
+//! ```
+
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::predicate::{Explanation, Predicate};
+use crate::Validation;
+
+/// Descriptive metadata for a rule in a [`RuleSet`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RuleMeta {
+    /// The rule's name.
+    pub name: String,
+    /// An optional human-readable description.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub description: Option<String>,
+}
+
+/// The result of checking one rule, as recorded in a [`RuleReport`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RuleResult {
+    /// The rule's name.
+    pub name: String,
+    /// Whether the value satisfied the rule.
+    pub passed: bool,
+}
+
+/// The outcome of every rule in a [`RuleSet`] for one value, in rule order.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RuleReport {
+    /// One result per rule.
+    pub results: Vec<RuleResult>,
+}
+
+impl RuleReport {
+    /// Returns `true` if no rule was violated.
+    pub fn is_pass(&self) -> bool {
+        self.results.iter().all(|result| result.passed)
+    }
+
+    /// Names of the rules that passed.
+    pub fn passed(&self) -> impl Iterator<Item = &str> {
+        self.results
+            .iter()
+            .filter(|result| result.passed)
+            .map(|result| result.name.as_str())
+    }
+
+    /// Names of the rules that were violated.
+    pub fn violated(&self) -> impl Iterator<Item = &str> {
+        self.results
+            .iter()
+            .filter(|result| !result.passed)
+            .map(|result| result.name.as_str())
+    }
+}
+
+struct Rule<T, E> {
+    meta: RuleMeta,
+    predicate: Box<dyn Predicate<T>>,
+    error: Box<dyn Fn(&T) -> E + Send + Sync>,
+}
+
+/// A list of named rules, each a predicate paired with the error it
+/// produces when violated.
+///
+/// Rules are checked in the order they were added, and every rule is
+/// checked; violations are never short-circuited.
+pub struct RuleSet<T, E> {
+    rules: Vec<Rule<T, E>>,
+}
+
+impl<T, E> RuleSet<T, E> {
+    /// Create an empty rule set.
+    pub fn new() -> Self {
+        RuleSet { rules: Vec::new() }
+    }
+
+    /// Add a rule that produces a fixed error when violated.
+    pub fn rule<P>(self, name: impl Into<String>, predicate: P, error: E) -> Self
+    where
+        P: Predicate<T> + 'static,
+        E: Clone + Send + Sync + 'static,
+    {
+        self.rule_with(name, predicate, move |_| error.clone())
+    }
+
+    /// Add a rule whose error is built from the violating value.
+    pub fn rule_with<P, F>(mut self, name: impl Into<String>, predicate: P, error: F) -> Self
+    where
+        P: Predicate<T> + 'static,
+        F: Fn(&T) -> E + Send + Sync + 'static,
+    {
+        self.rules.push(Rule {
+            meta: RuleMeta {
+                name: name.into(),
+                description: None,
+            },
+            predicate: Box::new(predicate),
+            error: Box::new(error),
+        });
+        self
+    }
+
+    /// Set the description of the most recently added rule.
+    ///
+    /// Does nothing if the rule set is empty.
+    pub fn describe(mut self, description: impl Into<String>) -> Self {
+        if let Some(rule) = self.rules.last_mut() {
+            rule.meta.description = Some(description.into());
+        }
+        self
+    }
+
+    /// The number of rules.
+    pub fn len(&self) -> usize {
+        self.rules.len()
+    }
+
+    /// Returns `true` if the rule set has no rules.
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Metadata for every rule, in rule order.
+    pub fn metadata(&self) -> Vec<RuleMeta> {
+        self.rules.iter().map(|rule| rule.meta.clone()).collect()
+    }
+
+    /// Check every rule and record which passed and which were violated.
+    pub fn evaluate(&self, value: &T) -> RuleReport {
+        RuleReport {
+            results: self
+                .rules
+                .iter()
+                .map(|rule| RuleResult {
+                    name: rule.meta.name.clone(),
+                    passed: rule.predicate.check(value),
+                })
+                .collect(),
+        }
+    }
+
+    /// The errors of every violated rule, in rule order.
+    pub fn violations(&self, value: &T) -> Vec<E> {
+        self.rules
+            .iter()
+            .filter(|rule| !rule.predicate.check(value))
+            .map(|rule| (rule.error)(value))
+            .collect()
+    }
+
+    /// Validate a value, accumulating the errors of every violated rule.
+    pub fn validate(&self, value: T) -> Validation<T, Vec<E>> {
+        let errors = self.violations(&value);
+        if errors.is_empty() {
+            Validation::Success(value)
+        } else {
+            Validation::Failure(errors)
+        }
+    }
+}
+
+impl<T, E> Default for RuleSet<T, E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, E> Predicate<T> for RuleSet<T, E> {
+    fn check(&self, value: &T) -> bool {
+        self.rules.iter().all(|rule| rule.predicate.check(value))
+    }
+
+    fn explain(&self, value: &T) -> Explanation
+    where
+        T: fmt::Debug,
+    {
+        let children: Vec<_> = self
+            .rules
+            .iter()
+            .map(|rule| {
+                let mut explanation = rule.predicate.explain(value);
+                explanation.label = format!("{}: {}", rule.meta.name, explanation.label);
+                explanation
+            })
+            .collect();
+        Explanation::node("rule_set", children.iter().all(|c| c.passed), children)
+    }
+}
+
+impl<T, E> fmt::Debug for RuleSet<T, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RuleSet")
+            .field(
+                "rules",
+                &self
+                    .rules
+                    .iter()
+                    .map(|rule| &rule.meta.name)
+                    .collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::predicate::{ge, le, PredicateExt};
+
+    fn age_rules() -> RuleSet<i32, String> {
+        RuleSet::new()
+            .rule(
+                "non_negative",
+                ge(0),
+                "age must not be negative".to_string(),
+            )
+            .describe("ages start at zero")
+            .rule_with("plausible", le(150), |age| {
+                format!("{age} is not plausible")
+            })
+            .rule("adult", ge(18), "must be an adult".to_string())
+    }
+
+    #[test]
+    fn test_validate_collects_all_violations() {
+        assert_eq!(
+            age_rules().validate(200),
+            Validation::Failure(vec!["200 is not plausible".to_string()])
+        );
+        assert_eq!(
+            age_rules().validate(-1),
+            Validation::Failure(vec![
+                "age must not be negative".to_string(),
+                "must be an adult".to_string(),
+            ])
+        );
+        assert_eq!(age_rules().validate(30), Validation::Success(30));
+    }
+
+    #[test]
+    fn test_evaluate_reports_every_rule() {
+        let report = age_rules().evaluate(&10);
+        assert!(!report.is_pass());
+        assert_eq!(
+            report.passed().collect::<Vec<_>>(),
+            vec!["non_negative", "plausible"]
+        );
+        assert_eq!(report.violated().collect::<Vec<_>>(), vec!["adult"]);
+        assert!(age_rules().evaluate(&40).is_pass());
+    }
+
+    #[test]
+    fn test_metadata() {
+        let rules = age_rules();
+        assert_eq!(rules.len(), 3);
+        assert_eq!(
+            rules.metadata()[0],
+            RuleMeta {
+                name: "non_negative".to_string(),
+                description: Some("ages start at zero".to_string()),
+            }
+        );
+        assert_eq!(rules.metadata()[1].description, None);
+        assert_eq!(
+            format!("{rules:?}"),
+            r#"RuleSet { rules: ["non_negative", "plausible", "adult"] }"#
+        );
+    }
+
+    #[test]
+    fn test_rule_set_is_a_predicate() {
+        let rules = age_rules().and(le(65));
+        assert!(rules.check(&30));
+        assert!(!rules.check(&70));
+        assert!(!rules.check(&5));
+    }
+
+    #[test]
+    fn test_explain_labels_children_with_rule_names() {
+        let explanation = age_rules().explain(&10);
+        assert_eq!(
+            explanation.render(),
+            "✗ rule_set\n  ✓ non_negative: ge(0) (actual: 10)\n  ✓ plausible: le(150) (actual: 10)\n  ✗ adult: ge(18) (actual: 10)\n"
+        );
+    }
+
+    #[test]
+    fn test_empty_rule_set_passes() {
+        let rules: RuleSet<i32, String> = RuleSet::new().describe("ignored");
+        assert!(rules.is_empty());
+        assert_eq!(rules.validate(1), Validation::Success(1));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_report_and_metadata_serialize() {
+        let json = serde_json::to_value(age_rules().evaluate(&10)).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "results": [
+                    { "name": "non_negative", "passed": true },
+                    { "name": "plausible", "passed": true },
+                    { "name": "adult", "passed": false },
+                ]
+            })
+        );
+
+        let meta = serde_json::to_value(age_rules().metadata()).unwrap();
+        assert_eq!(meta[0]["description"], "ages start at zero");
+        assert!(meta[1].get("description").is_none());
+    }
+}
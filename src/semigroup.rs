@@ -50,6 +50,9 @@
 //! }
 //! ```
 
+use alloc::string::String;
+use alloc::vec::Vec;
+
 /// A type that supports an associative binary operation
 ///
 /// # Laws
@@ -125,8 +128,10 @@ impl_semigroup_tuple!(0 T1, 1 T2, 2 T3, 3 T4, 4 T5, 5 T6, 6 T7, 7 T8, 8 T9, 9 T1
 impl_semigroup_tuple!(0 T1, 1 T2, 2 T3, 3 T4, 4 T5, 5 T6, 6 T7, 7 T8, 8 T9, 9 T10, 10 T11, 11 T12);
 
 // Implementation for HashMap<K, V>
+#[cfg(feature = "std")]
+use core::hash::Hash;
+#[cfg(feature = "std")]
 use std::collections::HashMap;
-use std::hash::Hash;
 
 /// Semigroup for HashMap that merges maps, combining values with the same key.
 ///
@@ -154,6 +159,7 @@ use std::hash::Hash;
 /// //   "info": ["info1"]                 // From map2
 /// // }
 /// ```
+#[cfg(feature = "std")]
 impl<K, V> Semigroup for HashMap<K, V>
 where
     K: Eq + Hash + Clone,
@@ -172,6 +178,7 @@ where
 }
 
 // Implementation for HashSet<T>
+#[cfg(feature = "std")]
 use std::collections::HashSet;
 
 /// Semigroup for HashSet using union.
@@ -188,6 +195,7 @@ use std::collections::HashSet;
 /// let combined = set1.combine(set2);
 /// assert_eq!(combined.len(), 5); // {1, 2, 3, 4, 5}
 /// ```
+#[cfg(feature = "std")]
 impl<T> Semigroup for HashSet<T>
 where
     T: Eq + Hash,
@@ -199,7 +207,7 @@ where
 }
 
 // Implementation for BTreeMap<K, V>
-use std::collections::BTreeMap;
+use alloc::collections::BTreeMap;
 
 /// Semigroup for BTreeMap that merges maps, combining values with the same key.
 ///
@@ -240,7 +248,7 @@ where
 }
 
 // Implementation for BTreeSet<T>
-use std::collections::BTreeSet;
+use alloc::collections::BTreeSet;
 
 /// Semigroup for BTreeSet using union.
 ///
@@ -365,6 +373,7 @@ impl<T> Semigroup for Last<T> {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Intersection<S>(pub S);
 
+#[cfg(feature = "std")]
 impl<T> Semigroup for Intersection<HashSet<T>>
 where
     T: Eq + Hash + Clone,
@@ -383,6 +392,131 @@ where
     }
 }
 
+/// Wrapper for set union, the counterpart of [`Intersection`].
+///
+/// Sets already combine by union; the wrapper names that choice where a
+/// reader might otherwise wonder which one was meant.
+///
+/// # Example
+///
+/// ```
+/// use std::collections::BTreeSet;
+/// use stillwater::{Semigroup, Union};
+///
+/// let missing = Union(BTreeSet::from(["email"]));
+/// let more = Union(BTreeSet::from(["email", "name"]));
+///
+/// assert_eq!(missing.combine(more).0, BTreeSet::from(["email", "name"]));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Union<S>(pub S);
+
+#[cfg(feature = "std")]
+impl<T> Semigroup for Union<HashSet<T>>
+where
+    T: Eq + Hash,
+{
+    fn combine(self, other: Self) -> Self {
+        Union(self.0.combine(other.0))
+    }
+}
+
+impl<T> Semigroup for Union<BTreeSet<T>>
+where
+    T: Ord,
+{
+    fn combine(self, other: Self) -> Self {
+        Union(self.0.combine(other.0))
+    }
+}
+
+/// Wrapper that concatenates vectors, dropping repeated elements.
+///
+/// The first occurrence of each element is kept, in order. Useful when the
+/// same validation error can be reported by several checks.
+///
+/// # Example
+///
+/// ```
+/// use stillwater::{Dedup, Semigroup};
+///
+/// let a = Dedup(vec!["email is required", "name is too short"]);
+/// let b = Dedup(vec!["email is required", "age must be positive"]);
+///
+/// assert_eq!(
+///     a.combine(b).0,
+///     vec!["email is required", "name is too short", "age must be positive"]
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Dedup<S>(pub S);
+
+impl<T> Semigroup for Dedup<Vec<T>>
+where
+    T: PartialEq,
+{
+    fn combine(self, other: Self) -> Self {
+        let mut merged: Vec<T> = Vec::with_capacity(self.0.len() + other.0.len());
+        for item in self.0.into_iter().chain(other.0) {
+            if !merged.contains(&item) {
+                merged.push(item);
+            }
+        }
+        Dedup(merged)
+    }
+}
+
+/// Wrapper for an ordered map whose values combine on matching keys.
+///
+/// Unlike the `Semigroup` instances of `HashMap` and `BTreeMap`, neither keys
+/// nor values need to be `Clone`, and iteration order is by key, which
+/// keeps field-keyed error reports stable.
+///
+/// # Example
+///
+/// ```
+/// use stillwater::{MergeMap, Semigroup};
+///
+/// let errors = MergeMap::single("email", vec!["is required"])
+///     .combine(MergeMap::single("age", vec!["must be positive"]))
+///     .combine(MergeMap::single("email", vec!["is malformed"]));
+///
+/// assert_eq!(errors.0["email"], vec!["is required", "is malformed"]);
+/// assert_eq!(errors.0.keys().collect::<Vec<_>>(), vec![&"age", &"email"]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MergeMap<K, V>(pub BTreeMap<K, V>);
+
+impl<K: Ord, V> MergeMap<K, V> {
+    /// A map holding a single entry.
+    pub fn single(key: K, value: V) -> Self {
+        MergeMap(BTreeMap::from([(key, value)]))
+    }
+}
+
+impl<K, V> Default for MergeMap<K, V> {
+    fn default() -> Self {
+        MergeMap(BTreeMap::new())
+    }
+}
+
+impl<K, V> Semigroup for MergeMap<K, V>
+where
+    K: Ord,
+    V: Semigroup,
+{
+    fn combine(mut self, other: Self) -> Self {
+        for (key, value) in other.0 {
+            let merged = match self.0.remove(&key) {
+                Some(existing) => existing.combine(value),
+                None => value,
+            };
+            self.0.insert(key, merged);
+        }
+        self
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -742,6 +876,33 @@ mod tests {
         assert_eq!(left.0, right.0);
     }
 
+    #[test]
+    fn test_union_sets() {
+        let hash = Union(HashSet::from([1, 2])).combine(Union(HashSet::from([2, 3])));
+        assert_eq!(hash.0, HashSet::from([1, 2, 3]));
+
+        let btree = Union(BTreeSet::from([1, 2])).combine(Union(BTreeSet::from([2, 3])));
+        assert_eq!(btree.0, BTreeSet::from([1, 2, 3]));
+    }
+
+    #[test]
+    fn test_dedup_keeps_first_occurrences_in_order() {
+        let result = Dedup(vec![3, 1, 3]).combine(Dedup(vec![2, 1, 4]));
+        assert_eq!(result.0, vec![3, 1, 2, 4]);
+    }
+
+    #[test]
+    fn test_merge_map_combines_matching_keys() {
+        let result = MergeMap::single("a", vec![1])
+            .combine(MergeMap::single("b", vec![2]))
+            .combine(MergeMap::single("a", vec![3]));
+
+        assert_eq!(
+            result.0,
+            BTreeMap::from([("a", vec![1, 3]), ("b", vec![2])])
+        );
+    }
+
     // Property-based tests
     #[cfg(test)]
     mod proptests {
@@ -838,6 +999,26 @@ mod tests {
                 );
                 prop_assert_eq!(left.0, right.0);
             }
+
+            #[test]
+            fn prop_dedup_associative(a: Vec<u8>, b: Vec<u8>, c: Vec<u8>) {
+                let left = Dedup(a.clone()).combine(Dedup(b.clone())).combine(Dedup(c.clone()));
+                let right = Dedup(a).combine(Dedup(b).combine(Dedup(c)));
+                prop_assert_eq!(left, right);
+            }
+
+            #[test]
+            fn prop_merge_map_associative(
+                a: BTreeMap<u8, Vec<i32>>,
+                b: BTreeMap<u8, Vec<i32>>,
+                c: BTreeMap<u8, Vec<i32>>,
+            ) {
+                let left = MergeMap(a.clone())
+                    .combine(MergeMap(b.clone()))
+                    .combine(MergeMap(c.clone()));
+                let right = MergeMap(a).combine(MergeMap(b).combine(MergeMap(c)));
+                prop_assert_eq!(left, right);
+            }
         }
     }
 }
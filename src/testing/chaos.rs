@@ -0,0 +1,390 @@
+//! Fault injection for resilience tests.
+//!
+//! [`inject_faults`] wraps an effect factory, the same kind of factory
+//! [`retry`](crate::effect::retry::retry) takes, so that each effect it
+//! builds first consults a [`FaultPlan`]. The plan decides, call by call,
+//! whether that effect runs normally, fails without running, runs after a
+//! delay, or times out. A plan is either a fixed script or a set of
+//! probabilities drawn from a seeded generator, so the same plan always
+//! produces the same faults in the same order.
+//!
+//! Delays and timeouts sleep on the tokio timer; run the test with
+//! `tokio::time::pause()`, `#[tokio::test(start_paused = true)]`, or
+//! `testing::virtual_time` to keep them instant and
+//! deterministic.
+//!
+//! Requires the `async` feature.
+//!
+//! # Example
+//!
+//! ```rust
+//! use std::time::Duration;
+//! use stillwater::effect::prelude::*;
+//! use stillwater::effect::retry::retry;
+//! use stillwater::testing::chaos::{inject_faults, Fault, FaultPlan, InjectedFault};
+//! use stillwater::RetryPolicy;
+//!
+//! # tokio_test::block_on(async {
+//! let plan = FaultPlan::script([Fault::Fail, Fault::Timeout(Duration::from_millis(5))]);
+//! let flaky_fetch = inject_faults(|| pure::<_, InjectedFault, ()>("payload"), plan.clone());
+//!
+//! let result = retry(
+//!     flaky_fetch,
+//!     RetryPolicy::constant(Duration::from_millis(1)).with_max_retries(3),
+//! )
+//! .run(&())
+//! .await
+//! .unwrap();
+//!
+//! assert_eq!(result.attempts, 3);
+//! assert_eq!(
+//!     plan.history(),
+//!     vec![Fault::Fail, Fault::Timeout(Duration::from_millis(5)), Fault::Pass]
+//! );
+//! # });
+//! ```
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+use crate::effect::Effect;
+
+/// What happens to one effect built by [`inject_faults`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fault {
+    /// The effect runs normally.
+    Pass,
+    /// The effect fails with [`InjectedFault::Failure`] without running.
+    Fail,
+    /// The effect runs after this delay.
+    Delay(Duration),
+    /// After this long, the effect fails with [`InjectedFault::Timeout`]
+    /// without running.
+    Timeout(Duration),
+}
+
+/// The error an injected [`Fault`] produces.
+///
+/// Effects wrapped by [`inject_faults`] need an error type that converts
+/// from it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InjectedFault {
+    /// From [`Fault::Fail`].
+    Failure,
+    /// From [`Fault::Timeout`].
+    Timeout {
+        /// How long the effect waited before timing out
+        after: Duration,
+    },
+}
+
+impl fmt::Display for InjectedFault {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InjectedFault::Failure => f.write_str("injected failure"),
+            InjectedFault::Timeout { after } => write!(f, "injected timeout after {:?}", after),
+        }
+    }
+}
+
+impl std::error::Error for InjectedFault {}
+
+/// Decides which [`Fault`] each wrapped effect gets.
+///
+/// Scripted faults are used first, one per effect, in order. After that,
+/// each effect draws from the probabilistic rules, using a generator seeded
+/// by [`FaultPlan::seeded`], and passes if no rule fires.
+///
+/// Cloning a plan gives another handle to the same plan, so a test can
+/// keep one to read the [`history`](Self::history) after handing the other
+/// to [`inject_faults`].
+#[derive(Clone)]
+pub struct FaultPlan {
+    state: Arc<Mutex<PlanState>>,
+}
+
+struct PlanState {
+    script: VecDeque<Fault>,
+    rules: Vec<(f64, Fault)>,
+    rng: SplitMix64,
+    history: Vec<Fault>,
+}
+
+impl FaultPlan {
+    /// A plan with no faults, drawing from a generator seeded with `seed`.
+    pub fn seeded(seed: u64) -> Self {
+        FaultPlan {
+            state: Arc::new(Mutex::new(PlanState {
+                script: VecDeque::new(),
+                rules: Vec::new(),
+                rng: SplitMix64(seed),
+                history: Vec::new(),
+            })),
+        }
+    }
+
+    /// A plan that injects `faults` in order, then lets every effect pass.
+    pub fn script(faults: impl IntoIterator<Item = Fault>) -> Self {
+        Self::seeded(0).then(faults)
+    }
+
+    /// Inject `faults` in order after any already scripted.
+    pub fn then(self, faults: impl IntoIterator<Item = Fault>) -> Self {
+        self.state().script.extend(faults);
+        self
+    }
+
+    /// Fail each unscripted effect with probability `probability`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the plan's probabilities add up to more than 1.
+    pub fn fail_rate(self, probability: f64) -> Self {
+        self.rule(probability, Fault::Fail)
+    }
+
+    /// Delay each unscripted effect by `delay` with probability
+    /// `probability`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the plan's probabilities add up to more than 1.
+    pub fn delay_rate(self, probability: f64, delay: Duration) -> Self {
+        self.rule(probability, Fault::Delay(delay))
+    }
+
+    /// Time out each unscripted effect after `after` with probability
+    /// `probability`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the plan's probabilities add up to more than 1.
+    pub fn timeout_rate(self, probability: f64, after: Duration) -> Self {
+        self.rule(probability, Fault::Timeout(after))
+    }
+
+    fn rule(self, probability: f64, fault: Fault) -> Self {
+        {
+            let mut state = self.state();
+            assert!(
+                (0.0..=1.0).contains(&probability),
+                "fault probability must be between 0 and 1, got {}",
+                probability
+            );
+            let total: f64 = state.rules.iter().map(|(p, _)| p).sum::<f64>() + probability;
+            assert!(
+                total <= 1.0 + f64::EPSILON,
+                "fault probabilities add up to {}, more than 1",
+                total
+            );
+            state.rules.push((probability, fault));
+        }
+        self
+    }
+
+    /// Decide the fault for the next effect and record it.
+    pub fn next_fault(&self) -> Fault {
+        let mut state = self.state();
+        let fault = match state.script.pop_front() {
+            Some(fault) => fault,
+            None if state.rules.is_empty() => Fault::Pass,
+            None => {
+                let roll = state.rng.next_f64();
+                let mut threshold = 0.0;
+                state
+                    .rules
+                    .iter()
+                    .find(|(probability, _)| {
+                        threshold += probability;
+                        roll < threshold
+                    })
+                    .map_or(Fault::Pass, |(_, fault)| *fault)
+            }
+        };
+        state.history.push(fault);
+        fault
+    }
+
+    /// The faults decided so far, in order.
+    pub fn history(&self) -> Vec<Fault> {
+        self.state().history.clone()
+    }
+
+    fn state(&self) -> MutexGuard<'_, PlanState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl fmt::Debug for FaultPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.state();
+        f.debug_struct("FaultPlan")
+            .field("script", &state.script)
+            .field("rules", &state.rules)
+            .field("history", &state.history)
+            .finish_non_exhaustive()
+    }
+}
+
+/// SplitMix64, a small seedable generator; good enough to spread faults.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `[0, 1)`.
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// Effect with a [`Fault`] applied to it.
+///
+/// Built by the factory [`inject_faults`] returns.
+pub struct Faulty<Eff> {
+    inner: Eff,
+    fault: Fault,
+}
+
+impl<Eff> fmt::Debug for Faulty<Eff> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Faulty")
+            .field("inner", &"<effect>")
+            .field("fault", &self.fault)
+            .finish()
+    }
+}
+
+impl<Eff> Effect for Faulty<Eff>
+where
+    Eff: Effect,
+    Eff::Error: From<InjectedFault>,
+{
+    type Output = Eff::Output;
+    type Error = Eff::Error;
+    type Env = Eff::Env;
+
+    async fn run(self, env: &Self::Env) -> Result<Self::Output, Self::Error> {
+        match self.fault {
+            Fault::Pass => self.inner.run(env).await,
+            Fault::Fail => Err(InjectedFault::Failure.into()),
+            Fault::Delay(delay) => {
+                tokio::time::sleep(delay).await;
+                self.inner.run(env).await
+            }
+            Fault::Timeout(after) => {
+                tokio::time::sleep(after).await;
+                Err(InjectedFault::Timeout { after }.into())
+            }
+        }
+    }
+}
+
+/// Wrap `make_effect` so each effect it builds gets the next fault from
+/// `plan`.
+///
+/// The fault is decided when the effect is built, so the plan's order
+/// follows the order the factory is called in, as it is by
+/// [`retry`](crate::effect::retry::retry).
+pub fn inject_faults<F, Eff>(
+    make_effect: F,
+    plan: FaultPlan,
+) -> impl Fn() -> Faulty<Eff> + Send + Sync
+where
+    F: Fn() -> Eff + Send + Sync,
+    Eff: Effect,
+    Eff::Error: From<InjectedFault>,
+{
+    move || Faulty {
+        inner: make_effect(),
+        fault: plan.next_fault(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::effect::constructors::pure;
+    use crate::effect::retry::retry;
+    use crate::RetryPolicy;
+
+    #[test]
+    fn test_seeded_plan_is_reproducible() {
+        let draw = |seed| {
+            let plan = FaultPlan::seeded(seed)
+                .fail_rate(0.3)
+                .timeout_rate(0.2, Duration::from_secs(1));
+            (0..50).map(|_| plan.next_fault()).collect::<Vec<_>>()
+        };
+
+        let first = draw(42);
+        assert_eq!(first, draw(42));
+        assert_ne!(first, draw(43));
+
+        let failures = first.iter().filter(|f| **f == Fault::Fail).count();
+        let timeouts = first
+            .iter()
+            .filter(|f| matches!(f, Fault::Timeout(_)))
+            .count();
+        assert!((5..=25).contains(&failures), "{} failures", failures);
+        assert!((2..=20).contains(&timeouts), "{} timeouts", timeouts);
+    }
+
+    #[test]
+    fn test_script_runs_before_rules() {
+        let plan = FaultPlan::seeded(7)
+            .then([Fault::Pass, Fault::Fail])
+            .fail_rate(1.0);
+
+        let faults: Vec<_> = (0..4).map(|_| plan.next_fault()).collect();
+
+        assert_eq!(
+            faults,
+            vec![Fault::Pass, Fault::Fail, Fault::Fail, Fault::Fail]
+        );
+        assert_eq!(plan.history(), faults);
+    }
+
+    #[test]
+    #[should_panic(expected = "fault probabilities add up to")]
+    fn test_rejects_probabilities_over_one() {
+        let _ = FaultPlan::seeded(1)
+            .fail_rate(0.6)
+            .delay_rate(0.6, Duration::from_millis(1));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_retry_exhausts_on_persistent_faults() {
+        let plan = FaultPlan::seeded(3).fail_rate(1.0);
+        let effect = retry(
+            inject_faults(|| pure::<_, InjectedFault, ()>(1), plan.clone()),
+            RetryPolicy::constant(Duration::from_millis(10)).with_max_retries(2),
+        );
+
+        let exhausted = effect.run(&()).await.unwrap_err();
+
+        assert_eq!(
+            exhausted.final_error,
+            crate::TimeoutError::Inner(InjectedFault::Failure)
+        );
+        assert_eq!(plan.history().len(), 3);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_delay_runs_inner_effect_late() {
+        let plan = FaultPlan::script([Fault::Delay(Duration::from_secs(30))]);
+        let make = inject_faults(|| pure::<_, InjectedFault, ()>("slow"), plan);
+
+        let started = tokio::time::Instant::now();
+        assert_eq!(make().run(&()).await, Ok("slow"));
+        assert_eq!(started.elapsed(), Duration::from_secs(30));
+    }
+}
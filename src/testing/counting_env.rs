@@ -0,0 +1,217 @@
+//! An environment wrapper that records which services are used.
+
+use std::any::{type_name, TypeId};
+use std::fmt;
+use std::sync::{Mutex, MutexGuard};
+
+/// Wraps an environment and records each service extracted from it.
+///
+/// Effects reach their dependencies through `AsRef<T>`, as with
+/// [`IO::read`](crate::IO::read) and [`IO::write`](crate::IO::write).
+/// `CountingEnv<E>` implements `AsRef<T>` for every service `E` provides,
+/// passing each call through to `E` and logging it, so a test can run the
+/// real environment and then check which services were used, how often,
+/// and in what order, without mocking any of them.
+///
+/// Accesses that bypass `AsRef`, such as reading fields through
+/// [`inner`](Self::inner), are not recorded.
+///
+/// # Example
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use stillwater::testing::CountingEnv;
+/// use stillwater::effect::prelude::*;
+/// use stillwater::IO;
+///
+/// struct Cache(HashMap<u64, String>);
+/// struct Database;
+///
+/// struct AppEnv {
+///     cache: Cache,
+///     db: Database,
+/// }
+///
+/// impl AsRef<Cache> for AppEnv {
+///     fn as_ref(&self) -> &Cache {
+///         &self.cache
+///     }
+/// }
+///
+/// impl AsRef<Database> for AppEnv {
+///     fn as_ref(&self) -> &Database {
+///         &self.db
+///     }
+/// }
+///
+/// # tokio_test::block_on(async {
+/// let env = CountingEnv::new(AppEnv {
+///     cache: Cache(HashMap::new()),
+///     db: Database,
+/// });
+///
+/// let lookup = IO::read(|cache: &Cache| cache.0.get(&7).cloned()).and_then(|hit| {
+///     IO::read(move |_db: &Database| hit.unwrap_or_else(|| "from db".to_string()))
+/// });
+///
+/// assert_eq!(lookup.run(&env).await.unwrap(), "from db");
+/// assert!(env.accessed_before::<Cache, Database>());
+/// assert_eq!(env.count::<Database>(), 1);
+/// # });
+/// ```
+pub struct CountingEnv<E> {
+    inner: E,
+    accesses: Mutex<Vec<Access>>,
+}
+
+#[derive(Clone, Copy)]
+struct Access {
+    id: TypeId,
+    name: &'static str,
+}
+
+impl<E> CountingEnv<E> {
+    /// Wrap `inner`, starting with no recorded accesses.
+    pub fn new(inner: E) -> Self {
+        CountingEnv {
+            inner,
+            accesses: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// The wrapped environment. Accesses through it are not recorded.
+    pub fn inner(&self) -> &E {
+        &self.inner
+    }
+
+    /// Unwrap the environment, discarding the recorded accesses.
+    pub fn into_inner(self) -> E {
+        self.inner
+    }
+
+    /// How many times service `T` was extracted.
+    pub fn count<T: ?Sized + 'static>(&self) -> usize {
+        let id = TypeId::of::<T>();
+        self.log().iter().filter(|access| access.id == id).count()
+    }
+
+    /// The type names of the services extracted, in order.
+    pub fn accesses(&self) -> Vec<&'static str> {
+        self.log().iter().map(|access| access.name).collect()
+    }
+
+    /// Whether `A` was first extracted before `B` was.
+    ///
+    /// True if `A` was extracted and `B` never was.
+    pub fn accessed_before<A: ?Sized + 'static, B: ?Sized + 'static>(&self) -> bool {
+        let log = self.log();
+        let first = |id| log.iter().position(|access| access.id == id);
+        match (first(TypeId::of::<A>()), first(TypeId::of::<B>())) {
+            (Some(a), Some(b)) => a < b,
+            (Some(_), None) => true,
+            (None, _) => false,
+        }
+    }
+
+    /// Forget the accesses recorded so far.
+    pub fn reset(&self) {
+        self.log().clear();
+    }
+
+    fn log(&self) -> MutexGuard<'_, Vec<Access>> {
+        self.accesses.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl<E, T> AsRef<T> for CountingEnv<E>
+where
+    E: AsRef<T>,
+    T: ?Sized + 'static,
+{
+    fn as_ref(&self) -> &T {
+        self.log().push(Access {
+            id: TypeId::of::<T>(),
+            name: type_name::<T>(),
+        });
+        self.inner.as_ref()
+    }
+}
+
+impl<E: fmt::Debug> fmt::Debug for CountingEnv<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CountingEnv")
+            .field("inner", &self.inner)
+            .field("accesses", &self.accesses())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::effect::Effect;
+    use crate::IO;
+
+    struct Cache;
+    struct Database;
+
+    struct Env {
+        cache: Cache,
+        db: Database,
+    }
+
+    impl AsRef<Cache> for Env {
+        fn as_ref(&self) -> &Cache {
+            &self.cache
+        }
+    }
+
+    impl AsRef<Database> for Env {
+        fn as_ref(&self) -> &Database {
+            &self.db
+        }
+    }
+
+    fn env() -> CountingEnv<Env> {
+        CountingEnv::new(Env {
+            cache: Cache,
+            db: Database,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_records_accesses_in_order() {
+        let env = env();
+        let effect = IO::read(|_: &Database| ())
+            .and_then(|_| IO::read(|_: &Cache| ()))
+            .and_then(|_| IO::read(|_: &Database| ()));
+
+        effect.run(&env).await.unwrap();
+
+        assert_eq!(env.count::<Database>(), 2);
+        assert_eq!(env.count::<Cache>(), 1);
+        assert!(env.accessed_before::<Database, Cache>());
+        assert!(!env.accessed_before::<Cache, Database>());
+        assert_eq!(
+            env.accesses()
+                .iter()
+                .map(|name| name.rsplit("::").next().unwrap())
+                .collect::<Vec<_>>(),
+            vec!["Database", "Cache", "Database"]
+        );
+    }
+
+    #[test]
+    fn test_inner_and_reset_are_not_recorded() {
+        let env = env();
+        let _: &Cache = env.as_ref();
+        let _: &Database = &env.inner().db;
+
+        assert_eq!(env.count::<Database>(), 0);
+        assert!(env.accessed_before::<Cache, Database>());
+
+        env.reset();
+        assert!(env.accesses().is_empty());
+        assert!(!env.accessed_before::<Cache, Database>());
+    }
+}
@@ -47,6 +47,42 @@
 //! assert_eq!(test_effect.run_standalone().await, Ok(42));
 //! # });
 //! ```
+//!
+//! ## CountingEnv
+//!
+//! [`CountingEnv`] wraps a real environment and records which services
+//! effects extract from it through `AsRef`, for asserting on call order
+//! without mocks.
+//!
+//! ## Resource Laws
+//!
+//! [`resource_laws!`](crate::resource_laws) generates tests checking that an
+//! acquire/use/release triple is released exactly once under injected
+//! failures and cancellation; see [`ResourceLaws`].
+//!
+//! ## Fault Injection
+//!
+//! With the `async` feature, [`chaos::inject_faults`] wraps an effect
+//! factory so each effect it builds fails, stalls, or times out according
+//! to a scripted or seeded [`chaos::FaultPlan`], for exercising retry
+//! policies deterministically.
+//!
+//! ## Virtual Time
+//!
+//! With the `virtual_time` feature, [`virtual_time::run`] runs an effect on
+//! a paused tokio clock that skips ahead to each timer, so tests of
+//! timeouts, races, and retry backoff take no real time.
+
+#[cfg(feature = "async")]
+pub mod chaos;
+mod counting_env;
+mod resource_laws;
+#[cfg(feature = "virtual_time")]
+pub mod virtual_time;
+
+pub use crate::resource_laws;
+pub use counting_env::CountingEnv;
+pub use resource_laws::ResourceLaws;
 
 use crate::BoxedEffect;
 
@@ -154,7 +190,7 @@ impl<T, E, Env> TestEffect<T, E, Env> {
     where
         T: Send + 'static,
         E: Send + 'static,
-        Env: Clone + Send + Sync + 'static,
+        Env: Send + Sync + 'static,
     {
         crate::effect::Effect::run(self.effect, env).await
     }
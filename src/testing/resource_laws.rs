@@ -0,0 +1,566 @@
+//! Checking that a resource is released exactly once, whatever goes wrong.
+
+use std::fmt;
+use std::future::Future;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+
+use crate::effect::boxed::BoxFuture;
+use crate::effect::bracket::bracket;
+use crate::effect::constructors::fail;
+use crate::effect::{Effect, EffectExt};
+
+/// Polls to try before giving up on cancelling a bracket at every point.
+const DEFAULT_MAX_POLLS: usize = 64;
+
+/// Resource-safety checks for an acquire/use/release triple.
+///
+/// Runs the triple through [`bracket`](crate::effect::bracket::bracket) in
+/// every situation the bracket has to survive: the use effect succeeding or
+/// failing, acquire or release failing, and the whole bracket being
+/// cancelled after each possible number of polls. After each run it checks
+/// that release was called exactly once for every resource acquired, and,
+/// given a [`live`](ResourceLaws::live) probe, that the resources
+/// themselves are back where they started.
+///
+/// The runs are deterministic: the triple is driven on the test thread by a
+/// local executor, with yield points between acquire, use, and release so
+/// that cancellation lands at each boundary. The triple should therefore
+/// work against in-memory fakes rather than a tokio runtime, and the checks
+/// must not run inside one.
+///
+/// Each law is a method that panics with a message starting "resource law
+/// violated" if it does not hold. [`resource_laws!`](crate::resource_laws)
+/// generates one `#[test]` per law.
+///
+/// # Example
+///
+/// ```rust
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use stillwater::effect::prelude::*;
+/// use stillwater::testing::ResourceLaws;
+///
+/// static CHECKED_OUT: AtomicUsize = AtomicUsize::new(0);
+///
+/// struct Conn;
+///
+/// ResourceLaws::new(
+///     (),
+///     || from_fn(|_: &()| {
+///         CHECKED_OUT.fetch_add(1, Ordering::SeqCst);
+///         Ok::<_, String>(Conn)
+///     }),
+///     |_conn: Conn| async {
+///         CHECKED_OUT.fetch_sub(1, Ordering::SeqCst);
+///         Ok(())
+///     },
+///     |_conn: &Conn| pure::<_, String, ()>("row"),
+///     || "injected".to_string(),
+/// )
+/// .live(|| CHECKED_OUT.load(Ordering::SeqCst))
+/// .check_all();
+/// ```
+pub struct ResourceLaws<Env, Acq, Rel, Use, Fault> {
+    env: Env,
+    acquire: Acq,
+    release: Rel,
+    use_fn: Use,
+    fault: Fault,
+    live: Option<Box<dyn Fn() -> usize + Send + Sync>>,
+    max_polls: usize,
+}
+
+impl<Env, Acq, Rel, Use, Fault> fmt::Debug for ResourceLaws<Env, Acq, Rel, Use, Fault> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResourceLaws")
+            .field("live", &self.live.as_ref().map(|_| "<function>"))
+            .field("max_polls", &self.max_polls)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<Env, Acq, Rel, Use, Fault, AcqEff, RelFut, UseEff, R, E>
+    ResourceLaws<Env, Acq, Rel, Use, Fault>
+where
+    Env: Send + Sync,
+    Acq: Fn() -> AcqEff + Sync,
+    AcqEff: Effect<Output = R, Error = E, Env = Env>,
+    Rel: Fn(R) -> RelFut + Sync,
+    RelFut: Future<Output = Result<(), E>> + Send + 'static,
+    Use: Fn(&R) -> UseEff + Sync,
+    UseEff: Effect<Error = E, Env = Env>,
+    Fault: Fn() -> E + Sync,
+    R: Send,
+    E: Send + fmt::Debug + 'static,
+{
+    /// Check the triple `acquire`, `use_fn`, `release`, run with `env`.
+    ///
+    /// `fault` builds the error injected when a law needs a step to fail.
+    pub fn new(env: Env, acquire: Acq, release: Rel, use_fn: Use, fault: Fault) -> Self {
+        ResourceLaws {
+            env,
+            acquire,
+            release,
+            use_fn,
+            fault,
+            live: None,
+            max_polls: DEFAULT_MAX_POLLS,
+        }
+    }
+
+    /// Also check the resources' own bookkeeping.
+    ///
+    /// `live` reports how many resources are currently held, for example
+    /// the connections checked out of a pool. After each run it must be
+    /// back to its value before the run.
+    pub fn live(mut self, live: impl Fn() -> usize + Send + Sync + 'static) -> Self {
+        self.live = Some(Box::new(live));
+        self
+    }
+
+    /// Give up cancelling at later points after this many polls.
+    ///
+    /// Only matters for triples that never finish under the local executor.
+    /// Defaults to 64.
+    pub fn max_polls(mut self, max_polls: usize) -> Self {
+        self.max_polls = max_polls;
+        self
+    }
+
+    /// Check every law.
+    pub fn check_all(&self) {
+        self.releases_once_on_success();
+        self.releases_once_on_use_failure();
+        self.releases_nothing_on_acquire_failure();
+        self.releases_once_on_release_failure();
+        self.releases_once_on_cancellation();
+    }
+
+    /// Release runs once after the use effect succeeds.
+    pub fn releases_once_on_success(&self) {
+        let run = self.start_run("use succeeded");
+        let effect = bracket(
+            self.counted_acquire(&run),
+            self.counted_release(&run),
+            |r| self.yielding_use(r),
+        );
+
+        let result = futures::executor::block_on(effect.run(&self.env));
+        if let Err(error) = result {
+            panic!(
+                "resource law violated (use succeeded): the triple failed without an injected fault: {:?}",
+                error
+            );
+        }
+        run.finish(1);
+    }
+
+    /// Release runs once when the use effect fails, before or after it ran.
+    pub fn releases_once_on_use_failure(&self) {
+        let run = self.start_run("use failed before running");
+        let effect = bracket(
+            self.counted_acquire(&run),
+            self.counted_release(&run),
+            |_| fail::<(), E, Env>((self.fault)()),
+        );
+        assert_failed(futures::executor::block_on(effect.run(&self.env)), &run);
+        run.finish(1);
+
+        let run = self.start_run("use failed after running");
+        let effect = bracket(
+            self.counted_acquire(&run),
+            self.counted_release(&run),
+            |r| {
+                self.yielding_use(r)
+                    .and_then(|_| fail::<(), E, Env>((self.fault)()))
+            },
+        );
+        assert_failed(futures::executor::block_on(effect.run(&self.env)), &run);
+        run.finish(1);
+    }
+
+    /// Nothing is released when acquire fails.
+    pub fn releases_nothing_on_acquire_failure(&self) {
+        let run = self.start_run("acquire failed");
+        let acquire = YieldOnce::new().and_then(|_| fail::<R, E, Env>((self.fault)()));
+        let effect = bracket(acquire, self.counted_release(&run), |r| {
+            self.yielding_use(r)
+        });
+        assert_failed(futures::executor::block_on(effect.run(&self.env)), &run);
+        run.finish(0);
+    }
+
+    /// Release runs once, and is not retried, when it fails.
+    pub fn releases_once_on_release_failure(&self) {
+        let run = self.start_run("release failed");
+        let released = Arc::clone(&run.released);
+        let release = move |resource: R| {
+            released.fetch_add(1, Ordering::SeqCst);
+            let releasing = (self.release)(resource);
+            let error = (self.fault)();
+            async move {
+                let _ = releasing.await;
+                Err(error)
+            }
+        };
+        let effect = bracket(self.counted_acquire(&run), release, |r| {
+            self.yielding_use(r)
+        });
+
+        if let Err(error) = futures::executor::block_on(effect.run(&self.env)) {
+            panic!(
+                "resource law violated (release failed): a release failure replaced the use result: {:?}",
+                error
+            );
+        }
+        run.finish(1);
+    }
+
+    /// Release runs once per acquired resource wherever the bracket is
+    /// cancelled.
+    pub fn releases_once_on_cancellation(&self) {
+        for polls in 0..=self.max_polls {
+            let run = self.start_run(format!("cancelled after {} polls", polls));
+            let effect = bracket(
+                self.counted_acquire(&run),
+                self.counted_release(&run),
+                |r| self.yielding_use(r),
+            );
+
+            let mut running = Box::pin(effect.run(&self.env));
+            let mut cx = Context::from_waker(futures::task::noop_waker_ref());
+            let finished = (0..polls).any(|_| running.as_mut().poll(&mut cx).is_ready());
+            drop(running);
+            run.finish_rescued();
+
+            let acquired = run.acquired.load(Ordering::SeqCst);
+            run.finish(acquired);
+            if finished {
+                return;
+            }
+        }
+    }
+
+    fn start_run(&self, scenario: impl Into<String>) -> Run<'_> {
+        #[cfg(feature = "async")]
+        assert!(
+            tokio::runtime::Handle::try_current().is_err(),
+            "ResourceLaws must run outside a tokio runtime, so that cancelled releases are left for the checks to finish"
+        );
+
+        Run {
+            scenario: scenario.into(),
+            acquired: Arc::new(AtomicUsize::new(0)),
+            released: Arc::new(AtomicUsize::new(0)),
+            rescued: Arc::new(Mutex::new(Vec::new())),
+            live: self.live.as_deref(),
+            live_before: self.live.as_ref().map(|live| live()),
+        }
+    }
+
+    fn counted_acquire<'a>(
+        &'a self,
+        run: &Run<'_>,
+    ) -> impl Effect<Output = R, Error = E, Env = Env> + 'a {
+        let acquired = Arc::clone(&run.acquired);
+        YieldOnce::new()
+            .and_then(move |_| (self.acquire)())
+            .map(move |resource| {
+                acquired.fetch_add(1, Ordering::SeqCst);
+                resource
+            })
+    }
+
+    fn counted_release<'a>(
+        &'a self,
+        run: &Run<'_>,
+    ) -> impl FnOnce(R) -> Rescued<Result<(), E>> + Send + 'a {
+        let released = Arc::clone(&run.released);
+        let rescued = Arc::clone(&run.rescued);
+        move |resource| {
+            released.fetch_add(1, Ordering::SeqCst);
+            Rescued {
+                release: Some(Box::pin((self.release)(resource))),
+                rescued,
+            }
+        }
+    }
+
+    fn yielding_use(
+        &self,
+        resource: &R,
+    ) -> impl Effect<Output = UseEff::Output, Error = E, Env = Env> {
+        let using = (self.use_fn)(resource);
+        YieldOnce::new()
+            .and_then(move |_| using)
+            .and_then(|value| YieldOnce::new().map(move |_| value))
+    }
+}
+
+/// The counters for one run of the triple.
+struct Run<'a> {
+    scenario: String,
+    acquired: Arc<AtomicUsize>,
+    released: Arc<AtomicUsize>,
+    rescued: Arc<Mutex<Vec<BoxFuture<'static, ()>>>>,
+    live: Option<&'a (dyn Fn() -> usize + Send + Sync)>,
+    live_before: Option<usize>,
+}
+
+impl Run<'_> {
+    /// Finish the releases a cancelled bracket dropped unrun.
+    fn finish_rescued(&self) {
+        let rescued = std::mem::take(&mut *self.rescued.lock().unwrap());
+        for release in rescued {
+            futures::executor::block_on(release);
+        }
+    }
+
+    fn finish(self, expected_releases: usize) {
+        let acquired = self.acquired.load(Ordering::SeqCst);
+        let released = self.released.load(Ordering::SeqCst);
+        assert_eq!(
+            released, expected_releases,
+            "resource law violated ({}): release ran {} times after {} acquisitions",
+            self.scenario, released, acquired
+        );
+
+        if let (Some(live), Some(before)) = (self.live, self.live_before) {
+            let after = live();
+            assert_eq!(
+                after, before,
+                "resource law violated ({}): {} resources live before the run, {} after",
+                self.scenario, before, after
+            );
+        }
+    }
+}
+
+fn assert_failed<T, E>(result: Result<T, E>, run: &Run<'_>) {
+    assert!(
+        result.is_err(),
+        "resource law violated ({}): the bracket succeeded despite the injected fault",
+        run.scenario
+    );
+}
+
+/// Release future that is handed to its [`Run`] if dropped unfinished.
+///
+/// A bracket cancelled outside a tokio runtime has nowhere to spawn its
+/// release and drops it unrun; the checks stand in for that runtime.
+struct Rescued<T: 'static> {
+    release: Option<BoxFuture<'static, T>>,
+    rescued: Arc<Mutex<Vec<BoxFuture<'static, ()>>>>,
+}
+
+impl<T> Future for Rescued<T> {
+    type Output = T;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        let release = self.release.as_mut().expect("polled after completion");
+        let output = std::task::ready!(release.as_mut().poll(cx));
+        self.release = None;
+        Poll::Ready(output)
+    }
+}
+
+impl<T: 'static> Drop for Rescued<T> {
+    fn drop(&mut self) {
+        if let Some(release) = self.release.take() {
+            let release = Box::pin(async move {
+                let _ = release.await;
+            });
+            self.rescued.lock().unwrap().push(release);
+        }
+    }
+}
+
+/// Effect that returns `Pending` once before succeeding, giving a
+/// cancellation point.
+struct YieldOnce<E, Env> {
+    _phantom: PhantomData<fn() -> (E, Env)>,
+}
+
+impl<E, Env> YieldOnce<E, Env> {
+    fn new() -> Self {
+        YieldOnce {
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<E: Send, Env: Send + Sync> Effect for YieldOnce<E, Env> {
+    type Output = ();
+    type Error = E;
+    type Env = Env;
+
+    async fn run(self, _env: &Self::Env) -> Result<(), E> {
+        let mut yielded = false;
+        std::future::poll_fn(|cx| {
+            if yielded {
+                Poll::Ready(())
+            } else {
+                yielded = true;
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+        })
+        .await;
+        Ok(())
+    }
+}
+
+/// Generate a `#[test]` for each [`ResourceLaws`] law.
+///
+/// Expands to a module named `$name` with one test per law, each building
+/// the triple afresh. `env` defaults to `()`; `live` is optional. The
+/// expressions are evaluated inside the generated module, which imports
+/// everything from the enclosing one.
+///
+/// # Example
+///
+/// ```rust
+/// use stillwater::effect::prelude::*;
+///
+/// stillwater::testing::resource_laws!(scratch_buffer {
+///     acquire: || pure::<_, String, ()>(vec![0u8; 1024]),
+///     release: |buffer: Vec<u8>| async move {
+///         drop(buffer);
+///         Ok(())
+///     },
+///     use_fn: |buffer: &Vec<u8>| pure::<_, String, ()>(buffer.len()),
+///     fault: || "injected".to_string(),
+/// });
+/// ```
+#[macro_export]
+macro_rules! resource_laws {
+    (
+        $name:ident {
+            $(env: $env:expr,)?
+            acquire: $acquire:expr,
+            release: $release:expr,
+            use_fn: $use_fn:expr,
+            fault: $fault:expr
+            $(, live: $live:expr)?
+            $(,)?
+        }
+    ) => {
+        mod $name {
+            #[allow(unused_imports)]
+            use super::*;
+
+            $crate::resource_laws!(@test $name releases_once_on_success [$($env)?] [$($live)?] $acquire, $release, $use_fn, $fault);
+            $crate::resource_laws!(@test $name releases_once_on_use_failure [$($env)?] [$($live)?] $acquire, $release, $use_fn, $fault);
+            $crate::resource_laws!(@test $name releases_nothing_on_acquire_failure [$($env)?] [$($live)?] $acquire, $release, $use_fn, $fault);
+            $crate::resource_laws!(@test $name releases_once_on_release_failure [$($env)?] [$($live)?] $acquire, $release, $use_fn, $fault);
+            $crate::resource_laws!(@test $name releases_once_on_cancellation [$($env)?] [$($live)?] $acquire, $release, $use_fn, $fault);
+        }
+    };
+    (@test $name:ident $law:ident [$($env:expr)?] [$($live:expr)?] $acquire:expr, $release:expr, $use_fn:expr, $fault:expr) => {
+        #[test]
+        fn $law() {
+            let laws = $crate::testing::ResourceLaws::new(
+                $crate::resource_laws!(@env $($env)?),
+                $acquire,
+                $release,
+                $use_fn,
+                $fault,
+            );
+            $(let laws = laws.live($live);)?
+            laws.$law();
+        }
+    };
+    (@env) => {
+        ()
+    };
+    (@env $env:expr) => {
+        $env
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::effect::constructors::{from_fn, pure};
+
+    static POOL: AtomicUsize = AtomicUsize::new(0);
+
+    struct Conn(u32);
+
+    crate::resource_laws!(pool_connection {
+        acquire: || from_fn(|_: &()| {
+            POOL.fetch_add(1, Ordering::SeqCst);
+            Ok::<_, String>(Conn(7))
+        }),
+        release: |_conn: Conn| async {
+            POOL.fetch_sub(1, Ordering::SeqCst);
+            Ok(())
+        },
+        use_fn: |conn: &Conn| pure::<_, String, ()>(conn.0),
+        fault: || "injected".to_string(),
+        live: || POOL.load(Ordering::SeqCst),
+    });
+
+    #[derive(Debug)]
+    struct Config {
+        retries: u32,
+    }
+
+    crate::resource_laws!(with_env {
+        env: Config { retries: 3 },
+        acquire: || from_fn(|config: &Config| Ok::<_, String>(config.retries)),
+        release: |_: u32| async { Ok(()) },
+        use_fn: |retries: &u32| pure::<_, String, Config>(*retries * 2),
+        fault: || "injected".to_string(),
+    });
+
+    #[test]
+    #[should_panic(expected = "resource law violated (use succeeded): 0 resources live")]
+    fn test_detects_resource_left_live() {
+        let held = Arc::new(AtomicUsize::new(0));
+        let acquired = Arc::clone(&held);
+
+        ResourceLaws::new(
+            (),
+            move || {
+                let held = Arc::clone(&acquired);
+                from_fn(move |_: &()| {
+                    held.fetch_add(1, Ordering::SeqCst);
+                    Ok::<_, String>(())
+                })
+            },
+            // Never gives the resource back.
+            |_: ()| async { Ok(()) },
+            |_: &()| pure::<_, String, ()>(()),
+            || "injected".to_string(),
+        )
+        .live(move || held.load(Ordering::SeqCst))
+        .check_all();
+    }
+
+    #[test]
+    fn test_cancellation_covers_every_poll() {
+        let polls = Arc::new(AtomicUsize::new(0));
+        let counted = Arc::clone(&polls);
+
+        ResourceLaws::new(
+            (),
+            move || {
+                counted.fetch_add(1, Ordering::SeqCst);
+                pure::<_, String, ()>(1)
+            },
+            |_: i32| async { Ok(()) },
+            |n: &i32| pure::<_, String, ()>(*n),
+            || "injected".to_string(),
+        )
+        .releases_once_on_cancellation();
+
+        // Acquire runs from the second poll on and the bracket finishes on
+        // the fourth, so the runs cancelled after 2 and 3 polls and the one
+        // that finished each acquired once.
+        assert_eq!(polls.load(Ordering::SeqCst), 3);
+    }
+}
@@ -0,0 +1,174 @@
+//! Running effects against a virtual clock.
+//!
+//! Timeouts, retry backoff, and delays all sleep on tokio's timer, so a
+//! test of how [`race`](crate::effect::parallel::race),
+//! [`with_timeout`](crate::effect::retry::with_timeout), and
+//! [`retry`](crate::effect::retry::retry) interact would normally wait out
+//! every one of those sleeps. [`run`] and [`block_on`] instead drive the
+//! effect on a fresh runtime whose clock is paused: whenever every task is
+//! waiting on a timer, the clock jumps straight to the earliest deadline.
+//! A test covering minutes of backoff finishes in milliseconds, and the
+//! order in which timers fire is fixed by their deadlines rather than by
+//! scheduling noise.
+//!
+//! The clock only jumps while nothing else can make progress, so an effect
+//! blocked on real I/O or on a blocking thread holds virtual time still
+//! until that work completes.
+//!
+//! Requires the `virtual_time` feature, which enables tokio's `test-util`.
+//!
+//! # Example
+//!
+//! ```rust
+//! use std::time::Duration;
+//! use stillwater::effect::prelude::*;
+//! use stillwater::effect::retry::with_timeout;
+//! use stillwater::testing::virtual_time;
+//! use stillwater::TimeoutError;
+//!
+//! let slow = from_async(|_: &()| async {
+//!     tokio::time::sleep(Duration::from_secs(3600)).await;
+//!     Ok::<_, String>("done")
+//! });
+//!
+//! let timed = virtual_time::run(with_timeout(slow, Duration::from_secs(30)), &());
+//!
+//! assert!(matches!(timed.output, Err(TimeoutError::Timeout { .. })));
+//! assert_eq!(timed.elapsed, Duration::from_secs(30));
+//! ```
+
+use std::future::Future;
+use std::time::Duration;
+
+use crate::effect::Effect;
+
+/// The outcome of a run on the virtual clock.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VirtualRun<T> {
+    /// What the effect or future produced.
+    pub output: T,
+    /// How far the virtual clock advanced during the run.
+    pub elapsed: Duration,
+}
+
+/// Run `effect` on a virtual clock, returning its result and the virtual
+/// time it took.
+///
+/// # Panics
+///
+/// Panics if called from within an async runtime.
+pub fn run<Eff: Effect>(
+    effect: Eff,
+    env: &Eff::Env,
+) -> VirtualRun<Result<Eff::Output, Eff::Error>> {
+    block_on(effect.run(env))
+}
+
+/// Drive `future` to completion on a virtual clock, returning its output
+/// and the virtual time it took.
+///
+/// Use this for the parallel helpers that are plain async functions, such
+/// as [`race`](crate::effect::parallel::race) and
+/// [`par_all`](crate::effect::parallel::par_all).
+///
+/// # Panics
+///
+/// Panics if called from within an async runtime.
+pub fn block_on<F: Future>(future: F) -> VirtualRun<F::Output> {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .start_paused(true)
+        .build()
+        .expect("failed to build virtual time runtime");
+
+    runtime.block_on(async {
+        let started = tokio::time::Instant::now();
+        let output = future.await;
+        VirtualRun {
+            output,
+            elapsed: started.elapsed(),
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::effect::constructors::from_async;
+    use crate::effect::parallel::{par_all, race};
+    use crate::effect::retry::{retry, with_timeout};
+    use crate::effect::{BoxedEffect, EffectExt};
+    use crate::{RetryPolicy, TimeoutError};
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Arc;
+
+    fn sleep_then(secs: u64, value: u64) -> BoxedEffect<u64, String, ()> {
+        from_async(move |_: &()| async move {
+            tokio::time::sleep(Duration::from_secs(secs)).await;
+            Ok(value)
+        })
+        .boxed()
+    }
+
+    #[test]
+    fn test_race_resolves_by_virtual_deadline() {
+        let raced = block_on(race(
+            vec![sleep_then(600, 1), sleep_then(5, 2), sleep_then(60, 3)],
+            &(),
+        ));
+
+        assert_eq!(raced.output, Ok(2));
+        assert_eq!(raced.elapsed, Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_par_all_takes_longest_delay() {
+        let all = block_on(par_all::<_, _, _, Vec<_>>(
+            vec![sleep_then(10, 1), sleep_then(90, 2), sleep_then(30, 3)],
+            &(),
+        ));
+
+        assert_eq!(all.output, Ok(vec![1, 2, 3]));
+        assert_eq!(all.elapsed, Duration::from_secs(90));
+    }
+
+    #[test]
+    fn test_timeout_beats_slow_effect() {
+        let timed = run(
+            with_timeout(sleep_then(120, 1), Duration::from_secs(45)),
+            &(),
+        );
+
+        assert_eq!(
+            timed.output,
+            Err(TimeoutError::Timeout {
+                duration: Duration::from_secs(45)
+            })
+        );
+        assert_eq!(timed.elapsed, Duration::from_secs(45));
+    }
+
+    #[test]
+    fn test_retry_backoff_is_virtual() {
+        let attempts = Arc::new(AtomicU32::new(0));
+        let counter = Arc::clone(&attempts);
+        let effect = retry(
+            move || {
+                let counter = Arc::clone(&counter);
+                from_async(move |_: &()| async move {
+                    if counter.fetch_add(1, Ordering::SeqCst) < 3 {
+                        Err("not yet".to_string())
+                    } else {
+                        Ok(())
+                    }
+                })
+            },
+            RetryPolicy::exponential(Duration::from_secs(10)).with_max_retries(5),
+        );
+
+        let retried = run(effect, &());
+
+        assert_eq!(retried.output.unwrap().attempts, 4);
+        assert_eq!(retried.elapsed, Duration::from_secs(10 + 20 + 40));
+    }
+}
@@ -13,6 +13,17 @@
 //! - **`traverse`**: Map a function over a collection and sequence the results
 //!   - Equivalent to `map(f).sequence()` but more efficient
 //!
+//! - **`sequence_option`** / **`sequence_result`**: Run an effect that may be
+//!   absent, without boxing to unify the branches
+//!   - `Option<Eff>` → `Effect<Output = Option<T>>`
+//!   - `Result<Eff, X>` → `Effect<Output = Result<T, X>>`
+//!
+//! - **`traverse_with_dlq`**: Process every item, routing failures to a
+//!   dead-letter sink instead of stopping the batch
+//!
+//! - **`traverse_resumable`**: Persist progress to a [`CheckpointStore`] so a
+//!   long traversal resumes where it stopped instead of starting over
+//!
 //! # Examples
 //!
 //! ## Validation
@@ -53,6 +64,16 @@
 //! # });
 //! ```
 
+use std::convert::Infallible;
+use std::fmt;
+use std::future::Future;
+use std::marker::PhantomData;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use crate::effect::combinators::{SequenceOption, SequenceResult};
+use crate::effect::Effect;
+use crate::retry::{Class, ErrorClass};
 use crate::{BoxedEffect, Semigroup, Validation};
 
 /// Traverse a collection with a validation function.
@@ -151,10 +172,12 @@ where
 /// * `Env` - Environment type
 /// * `F` - Function type that transforms `T` into `BoxedEffect<U, E, Env>`
 /// * `I` - Input iterator type
+/// * `C` - Output collection, any `FromIterator<U>`, chosen by inference
 ///
 /// # Examples
 ///
 /// ```
+/// use std::collections::HashSet;
 /// use stillwater::{BoxedEffect, traverse::traverse_effect};
 /// use stillwater::effect::prelude::*;
 ///
@@ -165,27 +188,147 @@ where
 ///
 /// let result = traverse_effect(vec![1, 2, 3], double);
 /// assert_eq!(result.run(&()).await, Ok(vec![2, 4, 6]));
+///
+/// let unique: HashSet<i32> = traverse_effect(vec![1, 1, 2], double).run(&()).await.unwrap();
+/// assert_eq!(unique, HashSet::from([2, 4]));
 /// # });
 /// ```
-pub fn traverse_effect<T, U, E, Env, F, I>(iter: I, f: F) -> BoxedEffect<Vec<U>, E, Env>
+pub fn traverse_effect<T, U, E, Env, F, I, C>(iter: I, f: F) -> BoxedEffect<C, E, Env>
 where
+    C: FromIterator<U> + Send + 'static,
     I: IntoIterator<Item = T>,
     F: Fn(T) -> BoxedEffect<U, E, Env> + Clone + Send + 'static,
     T: Send + 'static,
     U: Send + 'static,
     E: Send + 'static,
-    Env: Clone + Send + Sync + 'static,
+    Env: Send + Sync + 'static,
+{
+    use crate::effect::prelude::*;
+    let effects = iter.into_iter().map(f).collect();
+    TryAll {
+        effects,
+        _collect: PhantomData,
+    }
+    .boxed()
+}
+
+/// The effect built by [`traverse_effect`] and [`sequence_effect`].
+struct TryAll<C, T, E, Env> {
+    effects: Vec<BoxedEffect<T, E, Env>>,
+    _collect: PhantomData<fn() -> C>,
+}
+
+impl<C, T, E, Env> Effect for TryAll<C, T, E, Env>
+where
+    C: FromIterator<T> + Send,
+    T: Send + 'static,
+    E: Send + 'static,
+    Env: Send + Sync + 'static,
+{
+    type Output = C;
+    type Error = E;
+    type Env = Env;
+
+    async fn run(self, env: &Env) -> Result<C, E> {
+        crate::effect::parallel::par_try_all(self.effects, env)
+            .await
+            .map(|values| values.into_iter().collect())
+    }
+}
+
+/// Traverse a collection with an effectful function using a bounded work queue.
+///
+/// Items are pulled from a queue as workers free up, with at most `limit`
+/// effects running at once, so one slow item never holds back the rest of
+/// a chunk. Results are returned in **completion order**, not input order.
+/// Fails fast: the first error is returned and queued items are not started.
+///
+/// `f` is only called when an item is dequeued, so effects for items that
+/// are never reached are never built. A `limit` of 0 is treated as 1.
+///
+/// # Type Parameters
+///
+/// * `T` - Input element type
+/// * `U` - Output element type
+/// * `E` - Error type
+/// * `Env` - Environment type
+/// * `F` - Function type that transforms `T` into `BoxedEffect<U, E, Env>`
+/// * `I` - Input iterator type
+///
+/// # Examples
+///
+/// ```
+/// use stillwater::{BoxedEffect, traverse::par_traverse_unordered};
+/// use stillwater::effect::prelude::*;
+///
+/// # tokio_test::block_on(async {
+/// fn double(x: i32) -> BoxedEffect<i32, String, ()> {
+///     pure(x * 2).boxed()
+/// }
+///
+/// let mut result = par_traverse_unordered(vec![1, 2, 3], double, 2)
+///     .run(&())
+///     .await
+///     .unwrap();
+/// result.sort();
+/// assert_eq!(result, vec![2, 4, 6]);
+/// # });
+/// ```
+pub fn par_traverse_unordered<T, U, E, Env, F, I>(
+    iter: I,
+    f: F,
+    limit: usize,
+) -> BoxedEffect<Vec<U>, E, Env>
+where
+    I: IntoIterator<Item = T>,
+    F: Fn(T) -> BoxedEffect<U, E, Env> + Send + 'static,
+    T: Send + 'static,
+    U: Send + 'static,
+    E: Send + 'static,
+    Env: Send + Sync + 'static,
 {
     use crate::effect::prelude::*;
-    let items: Vec<_> = iter.into_iter().collect();
-    let effects: Vec<BoxedEffect<U, E, Env>> = items.into_iter().map(f).collect();
-    from_async(move |env: &Env| {
-        let env = env.clone();
-        async move { par_try_all(effects, &env).await }
-    })
+
+    TraverseUnordered {
+        items: iter.into_iter().collect(),
+        f,
+        limit,
+    }
     .boxed()
 }
 
+/// The effect built by [`par_traverse_unordered`].
+struct TraverseUnordered<T, F> {
+    items: Vec<T>,
+    f: F,
+    limit: usize,
+}
+
+impl<T, U, E, Env, F> Effect for TraverseUnordered<T, F>
+where
+    F: Fn(T) -> BoxedEffect<U, E, Env> + Send,
+    T: Send,
+    U: Send + 'static,
+    E: Send + 'static,
+    Env: Send + Sync + 'static,
+{
+    type Output = Vec<U>;
+    type Error = E;
+    type Env = Env;
+
+    async fn run(self, env: &Env) -> Result<Vec<U>, E> {
+        use crate::effect::parallel::run_item;
+        use futures::stream::{self, StreamExt, TryStreamExt};
+
+        let f = self.f;
+        stream::iter(self.items)
+            .map(move |item| run_item(f(item), env))
+            .buffer_unordered(self.limit.max(1))
+            .try_collect()
+            .await
+    }
+}
+
 /// Sequence a collection of effects.
 ///
 /// Converts a collection of effects into an effect of a collection.
@@ -220,17 +363,617 @@ where
     I::IntoIter: Send,
     T: Send + 'static,
     E: Send + 'static,
-    Env: Clone + Send + Sync + 'static,
+    Env: Send + Sync + 'static,
+{
+    use crate::effect::prelude::*;
+    TryAll {
+        effects: iter.into_iter().collect(),
+        _collect: PhantomData,
+    }
+    .boxed()
+}
+
+/// Sequence an optional effect.
+///
+/// Runs the effect if there is one, producing `Some` of its output, and
+/// succeeds with `None` otherwise. Both branches have the same concrete
+/// type, so no boxing is needed.
+///
+/// # Examples
+///
+/// ```
+/// use stillwater::effect::prelude::*;
+/// use stillwater::traverse::sequence_option;
+///
+/// # tokio_test::block_on(async {
+/// let present = sequence_option(Some(pure::<_, String, ()>(1)));
+/// assert_eq!(present.run(&()).await, Ok(Some(1)));
+///
+/// let absent = sequence_option(None::<Pure<i32, String, ()>>);
+/// assert_eq!(absent.run(&()).await, Ok(None));
+/// # });
+/// ```
+pub fn sequence_option<Eff: Effect>(effect: Option<Eff>) -> SequenceOption<Eff> {
+    SequenceOption { inner: effect }
+}
+
+/// Traverse an `Option` with an effectful function.
+///
+/// Equivalent to `sequence_option(value.map(f))`: `f` is only called, and
+/// its effect only run, when there is a value.
+///
+/// # Examples
+///
+/// ```
+/// use stillwater::effect::prelude::*;
+/// use stillwater::traverse::traverse_option;
+///
+/// # tokio_test::block_on(async {
+/// fn load_avatar(id: u32) -> impl Effect<Output = String, Error = String, Env = ()> {
+///     pure(format!("avatar-{}.png", id))
+/// }
+///
+/// let avatar_id: Option<u32> = Some(7);
+/// let effect = traverse_option(avatar_id, load_avatar);
+/// assert_eq!(effect.run(&()).await, Ok(Some("avatar-7.png".to_string())));
+/// # });
+/// ```
+pub fn traverse_option<T, Eff, F>(value: Option<T>, f: F) -> SequenceOption<Eff>
+where
+    Eff: Effect,
+    F: FnOnce(T) -> Eff,
+{
+    sequence_option(value.map(f))
+}
+
+/// Sequence an effect wrapped in a `Result`.
+///
+/// Runs the effect on `Ok`, producing `Ok` of its output, and succeeds with
+/// the `Err` value otherwise. The effect's own failures are still failures;
+/// only the outer `Err` becomes part of the output.
+///
+/// # Examples
+///
+/// ```
+/// use stillwater::effect::prelude::*;
+/// use stillwater::traverse::sequence_result;
+///
+/// # tokio_test::block_on(async {
+/// let ready: Result<_, &str> = Ok(pure::<_, String, ()>(1));
+/// assert_eq!(sequence_result(ready).run(&()).await, Ok(Ok(1)));
+///
+/// let skipped: Result<Pure<i32, String, ()>, &str> = Err("disabled");
+/// assert_eq!(sequence_result(skipped).run(&()).await, Ok(Err("disabled")));
+/// # });
+/// ```
+pub fn sequence_result<Eff, X>(effect: Result<Eff, X>) -> SequenceResult<Eff, X>
+where
+    Eff: Effect,
+    X: Send,
+{
+    SequenceResult { inner: effect }
+}
+
+/// Traverse a `Result` with an effectful function.
+///
+/// Equivalent to `sequence_result(value.map(f))`: `f` is only called, and
+/// its effect only run, for an `Ok` value.
+///
+/// # Examples
+///
+/// ```
+/// use stillwater::effect::prelude::*;
+/// use stillwater::traverse::traverse_result;
+///
+/// # tokio_test::block_on(async {
+/// let parsed: Result<i32, std::num::ParseIntError> = "21".parse();
+/// let effect = traverse_result(parsed, |n| pure::<_, String, ()>(n * 2));
+/// assert_eq!(effect.run(&()).await, Ok(Ok(42)));
+/// # });
+/// ```
+pub fn traverse_result<T, X, Eff, F>(value: Result<T, X>, f: F) -> SequenceResult<Eff, X>
+where
+    Eff: Effect,
+    X: Send,
+    F: FnOnce(T) -> Eff,
+{
+    sequence_result(value.map(f))
+}
+
+/// A failed item from [`traverse_with_dlq`], either routed to the dead-letter
+/// sink or returned to the caller as deferred.
+///
+/// With the `serde` feature, dead letters can be serialized for storage in
+/// a queue, table, or file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeadLetter<T, E> {
+    /// Position of the item in the input
+    pub index: usize,
+    /// The item that failed
+    pub item: T,
+    /// The error it failed with
+    pub error: E,
+}
+
+/// Counts and positions of failed items from [`traverse_with_dlq`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DlqSummary {
+    /// Number of items processed
+    pub total: usize,
+    /// Input positions of the items sent to the dead-letter sink
+    pub dead_lettered: Vec<usize>,
+    /// Input positions of the items that failed without being permanent,
+    /// returned to the caller instead
+    pub deferred: Vec<usize>,
+}
+
+impl DlqSummary {
+    /// Number of items that succeeded.
+    pub fn succeeded(&self) -> usize {
+        self.total - self.dead_lettered.len() - self.deferred.len()
+    }
+
+    /// Returns true if every item succeeded.
+    pub fn is_clean(&self) -> bool {
+        self.dead_lettered.is_empty() && self.deferred.is_empty()
+    }
+}
+
+/// Traverse a collection, sending permanently failed items to a
+/// dead-letter sink while the traversal continues.
+///
+/// Items are processed sequentially in input order. When `f` fails for an
+/// item, the error's [`ErrorClass`] decides where the item goes:
+///
+/// - A [`Class::Permanent`] failure would recur on retry, so a
+///   [`DeadLetter`] holding the item, its index, and the error is passed to
+///   `dlq_sink`.
+/// - Any other failure is returned to the caller as a deferred
+///   [`DeadLetter`], to be retried later or reported.
+///
+/// The returned effect cannot fail. It yields the successful outputs and
+/// the deferred failures, both in input order, together with a
+/// [`DlqSummary`].
+///
+/// `f` receives a clone of each item so the original can be dead-lettered.
+/// To retry transient errors before deferring an item, build the effect in
+/// `f` with [`retry_classified`](crate::effect::retry_classified).
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::{Arc, Mutex};
+/// use stillwater::{error_class, BoxedEffect, traverse::traverse_with_dlq};
+/// use stillwater::effect::prelude::*;
+///
+/// #[derive(Debug, PartialEq)]
+/// enum ImportError {
+///     Malformed(String),
+///     Unavailable,
+/// }
+///
+/// error_class!(ImportError {
+///     Self::Malformed(_) => Permanent,
+///     Self::Unavailable => Transient,
+/// });
+///
+/// fn import(raw: String) -> BoxedEffect<i32, ImportError, ()> {
+///     if raw == "busy" {
+///         return fail(ImportError::Unavailable).boxed();
+///     }
+///     match raw.parse::<i32>() {
+///         Ok(n) => pure(n).boxed(),
+///         Err(e) => fail(ImportError::Malformed(e.to_string())).boxed(),
+///     }
+/// }
+///
+/// # tokio_test::block_on(async {
+/// let dlq = Arc::new(Mutex::new(Vec::new()));
+/// let sink = dlq.clone();
+///
+/// let items: Vec<String> = ["1", "x", "busy", "4"].map(String::from).into();
+/// let (imported, deferred, summary) = traverse_with_dlq(items, import, move |letter| {
+///     let sink = sink.clone();
+///     async move { sink.lock().unwrap().push(letter) }
+/// })
+/// .run(&())
+/// .await
+/// .unwrap();
+///
+/// assert_eq!(imported, vec![1, 4]);
+/// assert_eq!(summary.dead_lettered, vec![1]);
+/// assert_eq!(dlq.lock().unwrap()[0].item, "x");
+/// assert_eq!(summary.deferred, vec![2]);
+/// assert_eq!(deferred[0].error, ImportError::Unavailable);
+/// # });
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn traverse_with_dlq<T, U, E, Env, F, I, S, Fut>(
+    iter: I,
+    f: F,
+    dlq_sink: S,
+) -> BoxedEffect<(Vec<U>, Vec<DeadLetter<T, E>>, DlqSummary), Infallible, Env>
+where
+    I: IntoIterator<Item = T>,
+    F: Fn(T) -> BoxedEffect<U, E, Env> + Send + 'static,
+    S: Fn(DeadLetter<T, E>) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = ()> + Send,
+    T: Clone + Send + 'static,
+    U: Send + 'static,
+    E: ErrorClass + Send + 'static,
+    Env: Send + Sync + 'static,
+{
+    use crate::effect::prelude::*;
+
+    TraverseWithDlq {
+        items: iter.into_iter().collect(),
+        f,
+        dlq_sink,
+    }
+    .boxed()
+}
+
+/// The effect built by [`traverse_with_dlq`].
+struct TraverseWithDlq<T, F, S> {
+    items: Vec<T>,
+    f: F,
+    dlq_sink: S,
+}
+
+impl<T, U, E, Env, F, S, Fut> Effect for TraverseWithDlq<T, F, S>
+where
+    F: Fn(T) -> BoxedEffect<U, E, Env> + Send,
+    S: Fn(DeadLetter<T, E>) -> Fut + Send,
+    Fut: Future<Output = ()> + Send,
+    T: Clone + Send,
+    U: Send + 'static,
+    E: ErrorClass + Send + 'static,
+    Env: Send + Sync + 'static,
+{
+    type Output = (Vec<U>, Vec<DeadLetter<T, E>>, DlqSummary);
+    type Error = Infallible;
+    type Env = Env;
+
+    async fn run(self, env: &Env) -> Result<Self::Output, Infallible> {
+        use crate::effect::parallel::run_item;
+
+        let TraverseWithDlq { items, f, dlq_sink } = self;
+        let mut successes = Vec::with_capacity(items.len());
+        let mut deferred = Vec::new();
+        let mut summary = DlqSummary {
+            total: items.len(),
+            ..DlqSummary::default()
+        };
+
+        for (index, item) in items.into_iter().enumerate() {
+            match run_item(f(item.clone()), env).await {
+                Ok(value) => successes.push(value),
+                Err(error) if error.class() == Class::Permanent => {
+                    summary.dead_lettered.push(index);
+                    dlq_sink(DeadLetter { index, item, error }).await;
+                }
+                Err(error) => {
+                    summary.deferred.push(index);
+                    deferred.push(DeadLetter { index, item, error });
+                }
+            }
+        }
+
+        Ok((successes, deferred, summary))
+    }
+}
+
+/// Persists how far a [`traverse_resumable`] run has progressed.
+///
+/// The checkpoint is the number of leading items that completed, which is
+/// also the index of the next item to process.
+///
+/// # Example
+///
+/// ```
+/// use std::future::Future;
+/// use stillwater::traverse::CheckpointStore;
+///
+/// /// Stores the checkpoint in a database row.
+/// struct MigrationProgress {
+///     // connection pool, migration name, ...
+/// }
+///
+/// impl CheckpointStore for MigrationProgress {
+///     type Error = String;
+///
+///     fn load(&self) -> impl Future<Output = Result<Option<usize>, String>> + Send {
+///         async { Ok(None) } // SELECT completed FROM progress WHERE ...
+///     }
+///
+///     fn save(&self, completed: usize) -> impl Future<Output = Result<(), String>> + Send {
+///         async move {
+///             let _ = completed; // UPDATE progress SET completed = ...
+///             Ok(())
+///         }
+///     }
+/// }
+/// ```
+pub trait CheckpointStore: Send + Sync {
+    /// Error returned when the checkpoint can't be read or written.
+    type Error: Send;
+
+    /// Load the saved checkpoint, or `None` if no progress was recorded.
+    fn load(&self) -> impl Future<Output = Result<Option<usize>, Self::Error>> + Send;
+
+    /// Record that the first `completed` items are done.
+    fn save(&self, completed: usize) -> impl Future<Output = Result<(), Self::Error>> + Send;
+}
+
+/// A [`CheckpointStore`] kept in memory, shared between clones.
+///
+/// Progress does not survive a restart of the process; use it for tests
+/// and for retrying a traversal within one process.
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryCheckpointStore {
+    completed: Arc<Mutex<Option<usize>>>,
+}
+
+impl InMemoryCheckpointStore {
+    /// Create an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The saved checkpoint, if any.
+    pub fn get(&self) -> Option<usize> {
+        *self.completed.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl CheckpointStore for InMemoryCheckpointStore {
+    type Error = std::convert::Infallible;
+
+    async fn load(&self) -> Result<Option<usize>, Self::Error> {
+        Ok(self.get())
+    }
+
+    async fn save(&self, completed: usize) -> Result<(), Self::Error> {
+        *self.completed.lock().unwrap_or_else(|e| e.into_inner()) = Some(completed);
+        Ok(())
+    }
+}
+
+/// A [`CheckpointStore`] that keeps the checkpoint in a file.
+///
+/// Each save goes through the same steps as
+/// [`write_atomic`](crate::io::fs::write_atomic): the checkpoint is written
+/// and synced to a uniquely named temporary file next to `path`, renamed
+/// over `path`, and the directory is synced, so a crash leaves either the
+/// previous checkpoint or the new one. With the `async` feature, inside a
+/// tokio runtime, the file I/O runs on the blocking thread pool; otherwise
+/// it runs on the calling task.
+#[derive(Debug, Clone)]
+pub struct FileCheckpointStore {
+    path: PathBuf,
+}
+
+impl FileCheckpointStore {
+    /// Create a store backed by the file at `path`.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl CheckpointStore for FileCheckpointStore {
+    type Error = std::io::Error;
+
+    async fn load(&self) -> Result<Option<usize>, Self::Error> {
+        let path = self.path.clone();
+        let read = run_blocking(move || match std::fs::read_to_string(&path) {
+            Ok(contents) => Ok(Some(contents)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        });
+        match read.await? {
+            Some(contents) => contents
+                .trim()
+                .parse()
+                .map(Some)
+                .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e)),
+            None => Ok(None),
+        }
+    }
+
+    async fn save(&self, completed: usize) -> Result<(), Self::Error> {
+        let path = self.path.clone();
+        run_blocking(move || crate::io::fs::replace_file(&path, completed.to_string().as_bytes()))
+            .await
+    }
+}
+
+/// Run blocking file I/O on tokio's blocking pool when called inside a
+/// runtime, and on the calling task otherwise.
+async fn run_blocking<T, F>(operation: F) -> std::io::Result<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> std::io::Result<T> + Send + 'static,
+{
+    #[cfg(feature = "async")]
+    if let Ok(handle) = tokio::runtime::Handle::try_current() {
+        return match handle.spawn_blocking(operation).await {
+            Ok(result) => result,
+            Err(join_error) if join_error.is_panic() => {
+                std::panic::resume_unwind(join_error.into_panic())
+            }
+            Err(join_error) => Err(std::io::Error::other(join_error)),
+        };
+    }
+    operation()
+}
+
+/// Error from [`traverse_resumable`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResumableError<E, C> {
+    /// Processing the item at `index` failed; the checkpoint points at it,
+    /// so the next run starts with this item.
+    Item {
+        /// Position of the failed item in the input
+        index: usize,
+        /// The item's error
+        error: E,
+    },
+    /// Loading or saving the checkpoint failed.
+    Checkpoint(C),
+}
+
+impl<E: fmt::Display, C: fmt::Display> fmt::Display for ResumableError<E, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Item { index, error } => write!(f, "item {} failed: {}", index, error),
+            Self::Checkpoint(e) => write!(f, "checkpoint error: {}", e),
+        }
+    }
+}
+
+impl<E, C> std::error::Error for ResumableError<E, C>
+where
+    E: std::error::Error + 'static,
+    C: std::error::Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Item { error, .. } => Some(error),
+            Self::Checkpoint(e) => Some(e),
+        }
+    }
+}
+
+/// Outputs of a [`traverse_resumable`] run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Resumed<U> {
+    /// Number of leading items skipped because an earlier run completed them
+    pub skipped: usize,
+    /// Outputs of the items processed in this run, in input order
+    pub results: Vec<U>,
+}
+
+/// Traverse a collection sequentially, saving progress after every item so
+/// that a later run can resume after a failure or crash.
+///
+/// On start, the checkpoint is loaded from `store` and that many leading
+/// items are skipped. After each item succeeds, the new count of completed
+/// items is saved. The first failing item stops the traversal with
+/// [`ResumableError::Item`]; running again with the same input and store
+/// retries from that item.
+///
+/// The input must be in the same order on every run, and `f` should be
+/// idempotent: an item whose effect finished just before a crash, but
+/// whose checkpoint wasn't saved, is processed again.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::atomic::{AtomicBool, Ordering};
+/// use std::sync::Arc;
+/// use stillwater::{BoxedEffect, traverse::{traverse_resumable, InMemoryCheckpointStore}};
+/// use stillwater::effect::prelude::*;
+///
+/// # tokio_test::block_on(async {
+/// let store = InMemoryCheckpointStore::new();
+/// let flaky = Arc::new(AtomicBool::new(true));
+///
+/// let migrate = {
+///     let flaky = flaky.clone();
+///     move |row: i32| -> BoxedEffect<i32, String, ()> {
+///         if row == 3 && flaky.swap(false, Ordering::SeqCst) {
+///             fail("connection reset".to_string()).boxed()
+///         } else {
+///             pure(row * 100).boxed()
+///         }
+///     }
+/// };
+///
+/// // The first run stops at row 3, after rows 1 and 2 are checkpointed
+/// let first = traverse_resumable(vec![1, 2, 3, 4], migrate.clone(), store.clone());
+/// assert!(first.run(&()).await.is_err());
+/// assert_eq!(store.get(), Some(2));
+///
+/// // The second run resumes at row 3
+/// let second = traverse_resumable(vec![1, 2, 3, 4], migrate, store.clone());
+/// let resumed = second.run(&()).await.unwrap();
+/// assert_eq!(resumed.skipped, 2);
+/// assert_eq!(resumed.results, vec![300, 400]);
+/// # });
+/// ```
+pub fn traverse_resumable<T, U, E, Env, F, I, S>(
+    iter: I,
+    f: F,
+    store: S,
+) -> BoxedEffect<Resumed<U>, ResumableError<E, S::Error>, Env>
+where
+    I: IntoIterator<Item = T>,
+    F: Fn(T) -> BoxedEffect<U, E, Env> + Send + 'static,
+    S: CheckpointStore + 'static,
+    T: Send + 'static,
+    U: Send + 'static,
+    E: Send + 'static,
+    Env: Send + Sync + 'static,
 {
     use crate::effect::prelude::*;
-    let effects: Vec<BoxedEffect<T, E, Env>> = iter.into_iter().collect();
-    from_async(move |env: &Env| {
-        let env = env.clone();
-        async move { par_try_all(effects, &env).await }
-    })
+
+    TraverseResumable {
+        items: iter.into_iter().collect(),
+        f,
+        store,
+    }
     .boxed()
 }
 
+/// The effect built by [`traverse_resumable`].
+struct TraverseResumable<T, F, S> {
+    items: Vec<T>,
+    f: F,
+    store: S,
+}
+
+impl<T, U, E, Env, F, S> Effect for TraverseResumable<T, F, S>
+where
+    F: Fn(T) -> BoxedEffect<U, E, Env> + Send,
+    S: CheckpointStore,
+    T: Send,
+    U: Send + 'static,
+    E: Send + 'static,
+    Env: Send + Sync + 'static,
+{
+    type Output = Resumed<U>;
+    type Error = ResumableError<E, S::Error>;
+    type Env = Env;
+
+    async fn run(self, env: &Env) -> Result<Resumed<U>, ResumableError<E, S::Error>> {
+        use crate::effect::parallel::run_item;
+
+        let TraverseResumable { items, f, store } = self;
+        let skipped = store
+            .load()
+            .await
+            .map_err(ResumableError::Checkpoint)?
+            .unwrap_or(0)
+            .min(items.len());
+
+        let mut results = Vec::with_capacity(items.len() - skipped);
+        for (index, item) in items.into_iter().enumerate().skip(skipped) {
+            let value = run_item(f(item), env)
+                .await
+                .map_err(|error| ResumableError::Item { index, error })?;
+            results.push(value);
+            store
+                .save(index + 1)
+                .await
+                .map_err(ResumableError::Checkpoint)?;
+        }
+
+        Ok(Resumed { skipped, results })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -338,7 +1081,7 @@ mod tests {
             }
         }
 
-        let result = traverse_effect(vec![1, -2, 3], check_positive);
+        let result: BoxedEffect<Vec<_>, _, _> = traverse_effect(vec![1, -2, 3], check_positive);
         assert!(result.run(&()).await.is_err());
     }
 
@@ -353,6 +1096,106 @@ mod tests {
         assert_eq!(result.run(&()).await, Ok(vec![]));
     }
 
+    // Unordered traverse tests
+    #[tokio::test]
+    async fn test_par_traverse_unordered_returns_completion_order() {
+        use crate::effect::prelude::*;
+        use std::time::Duration;
+
+        fn sleep_for(ms: u64) -> BoxedEffect<u64, String, ()> {
+            from_async(move |_: &()| async move {
+                tokio::time::sleep(Duration::from_millis(ms)).await;
+                Ok(ms)
+            })
+            .boxed()
+        }
+
+        let result = par_traverse_unordered(vec![60, 5, 30], sleep_for, 3);
+        assert_eq!(result.run(&()).await, Ok(vec![5, 30, 60]));
+    }
+
+    #[tokio::test]
+    async fn test_par_traverse_unordered_feeds_free_workers() {
+        use crate::effect::prelude::*;
+        use std::time::{Duration, Instant};
+
+        // One slow item and many fast ones: with a work queue the fast items
+        // flow through the second worker while the slow one is still running.
+        fn work(ms: u64) -> BoxedEffect<u64, String, ()> {
+            from_async(move |_: &()| async move {
+                tokio::time::sleep(Duration::from_millis(ms)).await;
+                Ok(ms)
+            })
+            .boxed()
+        }
+
+        let items = vec![100, 10, 10, 10, 10, 10];
+        let start = Instant::now();
+        let result = par_traverse_unordered(items, work, 2)
+            .run(&())
+            .await
+            .unwrap();
+
+        assert!(start.elapsed() < Duration::from_millis(150));
+        assert_eq!(result.last(), Some(&100));
+        assert_eq!(result.len(), 6);
+    }
+
+    #[tokio::test]
+    async fn test_par_traverse_unordered_respects_limit() {
+        use crate::effect::prelude::*;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        let active = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        let (a, p) = (active.clone(), peak.clone());
+
+        let effect = par_traverse_unordered(
+            0..10,
+            move |i: i32| {
+                let (active, peak) = (a.clone(), p.clone());
+                from_async(move |_: &()| async move {
+                    let now = active.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(now, Ordering::SeqCst);
+                    tokio::task::yield_now().await;
+                    active.fetch_sub(1, Ordering::SeqCst);
+                    Ok::<_, String>(i)
+                })
+                .boxed()
+            },
+            3,
+        );
+
+        assert_eq!(effect.run(&()).await.map(|v| v.len()), Ok(10));
+        assert!(peak.load(Ordering::SeqCst) <= 3);
+    }
+
+    #[tokio::test]
+    async fn test_par_traverse_unordered_fails_fast() {
+        use crate::effect::prelude::*;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        let started = Arc::new(AtomicUsize::new(0));
+        let counter = started.clone();
+        let effect = par_traverse_unordered(
+            vec![1, -2, 3, 4, 5],
+            move |x: i32| {
+                counter.fetch_add(1, Ordering::SeqCst);
+                if x > 0 {
+                    pure(x).boxed()
+                } else {
+                    fail(format!("{} is not positive", x)).boxed()
+                }
+            },
+            1,
+        );
+
+        assert_eq!(effect.run(&()).await, Err("-2 is not positive".to_string()));
+        assert_eq!(started.load(Ordering::SeqCst), 2);
+    }
+
     // Effect sequence tests
     #[tokio::test]
     async fn test_sequence_effect_all_success() {
@@ -386,6 +1229,31 @@ mod tests {
         assert_eq!(result.run(&()).await, Ok(vec![]));
     }
 
+    // Option and Result sequence tests
+    #[tokio::test]
+    async fn test_traverse_option_skips_function_for_none() {
+        use crate::effect::prelude::*;
+        let effect = traverse_option(None::<i32>, |_| -> Pure<i32, String, ()> {
+            panic!("should not be called")
+        });
+        assert_eq!(effect.run(&()).await, Ok(None));
+    }
+
+    #[tokio::test]
+    async fn test_sequence_option_propagates_failure() {
+        use crate::effect::prelude::*;
+        let effect = sequence_option(Some(fail::<i32, _, ()>("boom".to_string())));
+        assert_eq!(effect.run(&()).await, Err("boom".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_traverse_result_keeps_outer_error_as_output() {
+        use crate::effect::prelude::*;
+        let effect = traverse_result(Err::<i32, _>("missing"), pure::<_, String, ()>);
+        assert_eq!(format!("{:?}", effect), "Pure -> SequenceResult");
+        assert_eq!(effect.run(&()).await, Ok(Err("missing")));
+    }
+
     // Integration tests
     #[test]
     fn test_traverse_with_parse() {
@@ -408,7 +1276,7 @@ mod tests {
     async fn test_traverse_effect_with_env() {
         use crate::effect::prelude::*;
 
-        #[derive(Clone)]
+        // Not `Clone`: the environment is only borrowed
         struct Env {
             multiplier: i32,
         }
@@ -421,4 +1289,147 @@ mod tests {
         let result = traverse_effect(vec![1, 2, 3], multiply);
         assert_eq!(result.run(&env).await, Ok(vec![3, 6, 9]));
     }
+
+    #[tokio::test]
+    async fn test_traverse_with_dlq_continues_after_failures() {
+        use crate::effect::prelude::*;
+        use std::sync::{Arc, Mutex};
+
+        #[derive(Debug, Clone, PartialEq)]
+        enum CheckError {
+            Odd(i32),
+            Busy,
+        }
+
+        crate::error_class!(CheckError {
+            Self::Odd(_) => Permanent,
+            Self::Busy => Throttled,
+        });
+
+        fn check(x: i32) -> BoxedEffect<i32, CheckError, ()> {
+            if x == 5 {
+                fail(CheckError::Busy).boxed()
+            } else if x % 2 == 0 {
+                pure(x * 10).boxed()
+            } else {
+                fail(CheckError::Odd(x)).boxed()
+            }
+        }
+
+        let dlq = Arc::new(Mutex::new(Vec::new()));
+        let sink = dlq.clone();
+        let effect = traverse_with_dlq(vec![1, 2, 3, 4, 5], check, move |letter| {
+            let sink = sink.clone();
+            async move { sink.lock().unwrap().push(letter) }
+        });
+
+        let Ok((values, deferred, summary)) = effect.run(&()).await;
+        assert_eq!(values, vec![20, 40]);
+        assert_eq!(summary.total, 5);
+        assert_eq!(summary.succeeded(), 2);
+        assert!(!summary.is_clean());
+        assert_eq!(
+            *dlq.lock().unwrap(),
+            vec![
+                DeadLetter {
+                    index: 0,
+                    item: 1,
+                    error: CheckError::Odd(1)
+                },
+                DeadLetter {
+                    index: 2,
+                    item: 3,
+                    error: CheckError::Odd(3)
+                },
+            ]
+        );
+        assert_eq!(summary.dead_lettered, vec![0, 2]);
+        assert_eq!(
+            deferred,
+            vec![DeadLetter {
+                index: 4,
+                item: 5,
+                error: CheckError::Busy
+            }]
+        );
+        assert_eq!(summary.deferred, vec![4]);
+    }
+
+    #[tokio::test]
+    async fn test_traverse_resumable_completed_run_skips_everything() {
+        use crate::effect::prelude::*;
+
+        fn double(x: i32) -> BoxedEffect<i32, String, ()> {
+            pure(x * 2).boxed()
+        }
+
+        let store = InMemoryCheckpointStore::new();
+        let first = traverse_resumable(vec![1, 2, 3], double, store.clone());
+        assert_eq!(first.run(&()).await.unwrap().results, vec![2, 4, 6]);
+        assert_eq!(store.get(), Some(3));
+
+        let again = traverse_resumable(vec![1, 2, 3], double, store.clone());
+        let resumed = again.run(&()).await.unwrap();
+        assert_eq!(resumed.skipped, 3);
+        assert!(resumed.results.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_traverse_resumable_reports_failed_index() {
+        use crate::effect::prelude::*;
+
+        fn reject_negative(x: i32) -> BoxedEffect<i32, String, ()> {
+            if x < 0 {
+                fail(format!("{} is negative", x)).boxed()
+            } else {
+                pure(x).boxed()
+            }
+        }
+
+        let store = InMemoryCheckpointStore::new();
+        let effect = traverse_resumable(vec![5, -1, 7], reject_negative, store.clone());
+        assert_eq!(
+            effect.run(&()).await.unwrap_err(),
+            ResumableError::Item {
+                index: 1,
+                error: "-1 is negative".to_string()
+            }
+        );
+        assert_eq!(store.get(), Some(1));
+    }
+
+    #[tokio::test]
+    async fn test_file_checkpoint_store_round_trip() {
+        let path =
+            std::env::temp_dir().join(format!("stillwater-checkpoint-{}.txt", std::process::id()));
+        let store = FileCheckpointStore::new(&path);
+
+        assert_eq!(store.load().await.unwrap(), None);
+        store.save(42).await.unwrap();
+        assert_eq!(store.load().await.unwrap(), Some(42));
+
+        std::fs::write(&path, "not a number").unwrap();
+        assert!(store.load().await.is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_file_checkpoint_store_leaves_no_temporary_files() {
+        let dir =
+            std::env::temp_dir().join(format!("stillwater-checkpoint-dir-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("progress");
+        let store = FileCheckpointStore::new(&path);
+
+        store.save(1).await.unwrap();
+        store.save(2).await.unwrap();
+
+        let entries: Vec<_> = std::fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect();
+        assert_eq!(entries, vec![std::ffi::OsString::from("progress")]);
+        assert_eq!(store.load().await.unwrap(), Some(2));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
 }
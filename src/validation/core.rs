@@ -83,6 +83,8 @@
 //! 2. Enable the feature in `Cargo.toml`: `features = ["try_trait"]`
 //! 3. Add `#![feature(try_trait_v2)]` to your crate root
 
+use alloc::vec::Vec;
+
 use crate::either::Either;
 use crate::nonempty::NonEmptyVec;
 use crate::Semigroup;
@@ -552,6 +554,105 @@ impl<T, E> Validation<T, E> {
         }
     }
 
+    /// Swap the success and failure variants.
+    ///
+    /// Useful when the "interesting" side is the error, for example to
+    /// collect failures with combinators that operate on successes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stillwater::Validation;
+    ///
+    /// let v = Validation::<i32, String>::failure("bad".to_string());
+    /// assert_eq!(v.swap(), Validation::Success("bad".to_string()));
+    ///
+    /// let v = Validation::<i32, String>::success(5);
+    /// assert_eq!(v.swap(), Validation::Failure(5));
+    /// ```
+    #[inline]
+    pub fn swap(self) -> Validation<E, T> {
+        match self {
+            Validation::Success(value) => Validation::Failure(value),
+            Validation::Failure(error) => Validation::Success(error),
+        }
+    }
+
+    /// Transform both variants with fallible functions.
+    ///
+    /// Like [`bimap`](Validation::bimap), but the functions return a
+    /// `Result`, and an `Err` from whichever function runs is returned
+    /// instead of the Validation.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - Function to transform the error (Failure case)
+    /// * `g` - Function to transform the value (Success case)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stillwater::Validation;
+    ///
+    /// let v = Validation::<_, String>::success("42");
+    /// let result = v.bitraverse(|e| Ok(e.len()), |s| s.parse::<i32>());
+    /// assert_eq!(result, Ok(Validation::Success(42)));
+    ///
+    /// let v = Validation::<_, String>::success("x");
+    /// assert!(v.bitraverse(|e| Ok(e.len()), |s| s.parse::<i32>()).is_err());
+    /// ```
+    #[inline]
+    pub fn bitraverse<U, E2, X, F, G>(self, f: F, g: G) -> Result<Validation<U, E2>, X>
+    where
+        F: FnOnce(E) -> Result<E2, X>,
+        G: FnOnce(T) -> Result<U, X>,
+    {
+        match self {
+            Validation::Success(value) => g(value).map(Validation::Success),
+            Validation::Failure(error) => f(error).map(Validation::Failure),
+        }
+    }
+
+    // ========== partition ==========
+
+    /// Split validations into success values and errors.
+    ///
+    /// Both sides keep their input order. Use this when successes and
+    /// failures are reported separately, e.g. in import jobs. See
+    /// [`partition_combined`](Validation::partition_combined) to combine the
+    /// errors with their `Semigroup` instead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stillwater::Validation;
+    ///
+    /// let rows = vec![
+    ///     Validation::success(1),
+    ///     Validation::failure("row 2: missing id"),
+    ///     Validation::success(3),
+    /// ];
+    /// let (imported, rejected) = Validation::partition(rows);
+    /// assert_eq!(imported, vec![1, 3]);
+    /// assert_eq!(rejected, vec!["row 2: missing id"]);
+    /// ```
+    pub fn partition<I>(validations: I) -> (Vec<T>, Vec<E>)
+    where
+        I: IntoIterator<Item = Validation<T, E>>,
+    {
+        let mut successes = Vec::new();
+        let mut failures = Vec::new();
+
+        for validation in validations {
+            match validation {
+                Validation::Success(value) => successes.push(value),
+                Validation::Failure(error) => failures.push(error),
+            }
+        }
+
+        (successes, failures)
+    }
+
     // ========== fold ==========
 
     /// Fold this Validation into a single value.
@@ -765,7 +866,7 @@ impl<T, E> Validation<T, E> {
     #[inline]
     pub fn unwrap_err(self) -> E
     where
-        T: std::fmt::Debug,
+        T: core::fmt::Debug,
     {
         match self {
             Validation::Success(value) => panic!(
@@ -780,7 +881,7 @@ impl<T, E> Validation<T, E> {
     #[inline]
     pub fn expect_err(self, msg: &str) -> E
     where
-        T: std::fmt::Debug,
+        T: core::fmt::Debug,
     {
         match self {
             Validation::Success(value) => panic!("{}: {:?}", msg, value),
@@ -789,6 +890,33 @@ impl<T, E> Validation<T, E> {
     }
 }
 
+// ========== bisequence ==========
+
+impl<T, E, X> Validation<Result<T, X>, Result<E, X>> {
+    /// Turn a Validation of Results inside out.
+    ///
+    /// Returns the `Err` from whichever side is present, otherwise the
+    /// Validation of the unwrapped values. Equivalent to
+    /// `self.bitraverse(|e| e, |t| t)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stillwater::Validation;
+    ///
+    /// let v: Validation<Result<i32, &str>, Result<String, &str>> = Validation::success(Ok(5));
+    /// assert_eq!(v.bisequence(), Ok(Validation::Success(5)));
+    ///
+    /// let v: Validation<Result<i32, &str>, Result<String, &str>> =
+    ///     Validation::failure(Err("io error"));
+    /// assert_eq!(v.bisequence(), Err("io error"));
+    /// ```
+    #[inline]
+    pub fn bisequence(self) -> Result<Validation<T, E>, X> {
+        self.bitraverse(|e| e, |t| t)
+    }
+}
+
 // ========== merge for same types ==========
 
 impl<T> Validation<T, T> {
@@ -836,6 +964,19 @@ impl<T, E> Validation<T, NonEmptyVec<E>> {
     }
 }
 
+// Body of the `productN` functions: accumulate the errors of every
+// validation, then apply `f` to the success values.
+macro_rules! product {
+    ($f:ident; $($v:ident),+) => {{
+        let mut errors = None;
+        $(let $v = $v.take_success(&mut errors);)+
+        match errors {
+            Some(errors) => Validation::Failure(errors),
+            None => Validation::Success($f($($v.unwrap()),+)),
+        }
+    }};
+}
+
 impl<T, E: Semigroup> Validation<T, E> {
     /// Combine two validations, accumulating errors using the Semigroup instance
     ///
@@ -868,6 +1009,194 @@ impl<T, E: Semigroup> Validation<T, E> {
         }
     }
 
+    /// Combine two validations with a function, accumulating errors
+    ///
+    /// Like [`and`](Validation::and), but the two values are passed to `f`
+    /// instead of being paired in a tuple.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stillwater::Validation;
+    ///
+    /// let width = Validation::<u32, Vec<&str>>::success(4);
+    /// let height = Validation::<u32, Vec<&str>>::success(3);
+    /// assert_eq!(width.zip_with(height, |w, h| w * h), Validation::Success(12));
+    ///
+    /// let width = Validation::<u32, _>::failure(vec!["bad width"]);
+    /// let height = Validation::<u32, _>::failure(vec!["bad height"]);
+    /// assert_eq!(
+    ///     width.zip_with(height, |w, h| w * h),
+    ///     Validation::Failure(vec!["bad width", "bad height"])
+    /// );
+    /// ```
+    pub fn zip_with<U, R, F>(self, other: Validation<U, E>, f: F) -> Validation<R, E>
+    where
+        F: FnOnce(T, U) -> R,
+    {
+        match (self, other) {
+            (Validation::Success(a), Validation::Success(b)) => Validation::Success(f(a, b)),
+            (Validation::Failure(e1), Validation::Failure(e2)) => {
+                Validation::Failure(e1.combine(e2))
+            }
+            (Validation::Failure(e), _) => Validation::Failure(e),
+            (_, Validation::Failure(e)) => Validation::Failure(e),
+        }
+    }
+
+    // Take the success value, or fold the error into `errors` in input order.
+    fn take_success(self, errors: &mut Option<E>) -> Option<T> {
+        match self {
+            Validation::Success(value) => Some(value),
+            Validation::Failure(error) => {
+                *errors = Some(match errors.take() {
+                    Some(acc) => acc.combine(error),
+                    None => error,
+                });
+                None
+            }
+        }
+    }
+
+    /// Combine three validations with a function, accumulating errors
+    ///
+    /// `f` receives the success values directly, so there are no nested
+    /// tuples to take apart as with chained [`and`](Validation::and) calls.
+    /// If any validation fails, all errors are combined in argument order.
+    /// `product4` to `product8` take more validations.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stillwater::Validation;
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// struct User {
+    ///     name: String,
+    ///     age: u8,
+    ///     email: String,
+    /// }
+    ///
+    /// let user = Validation::product3(
+    ///     Validation::<_, Vec<&str>>::success("Ada".to_string()),
+    ///     Validation::success(36),
+    ///     Validation::success("ada@example.com".to_string()),
+    ///     |name, age, email| User { name, age, email },
+    /// );
+    /// assert_eq!(user.into_result().unwrap().age, 36);
+    ///
+    /// let user = Validation::product3(
+    ///     Validation::<String, _>::failure(vec!["name is empty"]),
+    ///     Validation::success(36),
+    ///     Validation::failure(vec!["email has no @"]),
+    ///     |name, age, email| User { name, age, email },
+    /// );
+    /// assert_eq!(user, Validation::Failure(vec!["name is empty", "email has no @"]));
+    /// ```
+    pub fn product3<A, B, C, F>(
+        a: Validation<A, E>,
+        b: Validation<B, E>,
+        c: Validation<C, E>,
+        f: F,
+    ) -> Self
+    where
+        F: FnOnce(A, B, C) -> T,
+    {
+        product!(f; a, b, c)
+    }
+
+    /// Combine four validations with a function, accumulating errors
+    ///
+    /// See [`product3`](Validation::product3).
+    pub fn product4<A, B, C, D, F>(
+        a: Validation<A, E>,
+        b: Validation<B, E>,
+        c: Validation<C, E>,
+        d: Validation<D, E>,
+        f: F,
+    ) -> Self
+    where
+        F: FnOnce(A, B, C, D) -> T,
+    {
+        product!(f; a, b, c, d)
+    }
+
+    /// Combine five validations with a function, accumulating errors
+    ///
+    /// See [`product3`](Validation::product3).
+    pub fn product5<A, B, C, D, G, F>(
+        a: Validation<A, E>,
+        b: Validation<B, E>,
+        c: Validation<C, E>,
+        d: Validation<D, E>,
+        e: Validation<G, E>,
+        f: F,
+    ) -> Self
+    where
+        F: FnOnce(A, B, C, D, G) -> T,
+    {
+        product!(f; a, b, c, d, e)
+    }
+
+    /// Combine six validations with a function, accumulating errors
+    ///
+    /// See [`product3`](Validation::product3).
+    pub fn product6<A, B, C, D, G, H, F>(
+        a: Validation<A, E>,
+        b: Validation<B, E>,
+        c: Validation<C, E>,
+        d: Validation<D, E>,
+        e: Validation<G, E>,
+        g: Validation<H, E>,
+        f: F,
+    ) -> Self
+    where
+        F: FnOnce(A, B, C, D, G, H) -> T,
+    {
+        product!(f; a, b, c, d, e, g)
+    }
+
+    /// Combine seven validations with a function, accumulating errors
+    ///
+    /// See [`product3`](Validation::product3).
+    #[allow(clippy::too_many_arguments)]
+    pub fn product7<A, B, C, D, G, H, I, F>(
+        a: Validation<A, E>,
+        b: Validation<B, E>,
+        c: Validation<C, E>,
+        d: Validation<D, E>,
+        e: Validation<G, E>,
+        g: Validation<H, E>,
+        h: Validation<I, E>,
+        f: F,
+    ) -> Self
+    where
+        F: FnOnce(A, B, C, D, G, H, I) -> T,
+    {
+        product!(f; a, b, c, d, e, g, h)
+    }
+
+    /// Combine eight validations with a function, accumulating errors
+    ///
+    /// See [`product3`](Validation::product3).
+    #[allow(clippy::too_many_arguments)]
+    pub fn product8<A, B, C, D, G, H, I, J, F>(
+        a: Validation<A, E>,
+        b: Validation<B, E>,
+        c: Validation<C, E>,
+        d: Validation<D, E>,
+        e: Validation<G, E>,
+        g: Validation<H, E>,
+        h: Validation<I, E>,
+        i: Validation<J, E>,
+        f: F,
+    ) -> Self
+    where
+        F: FnOnce(A, B, C, D, G, H, I, J) -> T,
+    {
+        product!(f; a, b, c, d, e, g, h, i)
+    }
+
     /// Chain a dependent validation
     ///
     /// Similar to `Result::and_then`, but for validations. The function is only called
@@ -946,6 +1275,77 @@ impl<T, E: Semigroup> Validation<T, E> {
             )
         }
     }
+
+    /// Split validations into success values and combined errors.
+    ///
+    /// Like [`partition`](Validation::partition), but all errors are combined
+    /// with `Semigroup::combine` in input order. The error side is `None` if
+    /// every validation succeeded. Unlike [`all_vec`](Validation::all_vec),
+    /// the successes are kept even when some validations fail.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stillwater::Validation;
+    ///
+    /// let rows = vec![
+    ///     Validation::success(1),
+    ///     Validation::failure(vec!["row 2: missing id"]),
+    ///     Validation::success(3),
+    ///     Validation::failure(vec!["row 4: bad date"]),
+    /// ];
+    /// let (imported, errors) = Validation::partition_combined(rows);
+    /// assert_eq!(imported, vec![1, 3]);
+    /// assert_eq!(errors, Some(vec!["row 2: missing id", "row 4: bad date"]));
+    /// ```
+    pub fn partition_combined<I>(validations: I) -> (Vec<T>, Option<E>)
+    where
+        I: IntoIterator<Item = Validation<T, E>>,
+    {
+        let (successes, failures) = Self::partition(validations);
+        (
+            successes,
+            failures.into_iter().reduce(|acc, e| acc.combine(e)),
+        )
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<T: Send, E: Semigroup + Send> Validation<T, E> {
+    /// Validate every item on the rayon thread pool and combine the results
+    ///
+    /// The parallel counterpart of mapping `f` over `items` and calling
+    /// [`all_vec`](Validation::all_vec), for CPU-bound validators such as
+    /// hashing or parsing. Success values keep their input order, and errors
+    /// are combined in input order, so the result is the same as the
+    /// sequential version.
+    ///
+    /// Requires the `rayon` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stillwater::Validation;
+    ///
+    /// let inputs = vec!["1", "2", "x", "4", "y"];
+    /// let result = Validation::par_all_vec(inputs, |s: &str| match s.parse::<u32>() {
+    ///     Ok(n) => Validation::success(n),
+    ///     Err(_) => Validation::failure(vec![format!("not a number: {}", s)]),
+    /// });
+    /// assert_eq!(
+    ///     result,
+    ///     Validation::Failure(vec!["not a number: x".to_string(), "not a number: y".to_string()])
+    /// );
+    /// ```
+    pub fn par_all_vec<I, F>(items: I, f: F) -> Validation<Vec<T>, E>
+    where
+        I: rayon::iter::IntoParallelIterator,
+        F: Fn(I::Item) -> Validation<T, E> + Sync + Send,
+    {
+        use rayon::iter::ParallelIterator;
+
+        Self::all_vec(items.into_par_iter().map(f).collect())
+    }
 }
 
 // Free function for combining validations in a tuple
@@ -953,7 +1353,8 @@ impl<T, E> Validation<T, E> {
     /// Combine all validations in a tuple
     ///
     /// This is a convenience method that delegates to the `ValidateAll` trait.
-    /// It works with tuples of validations up to size 12.
+    /// It works with tuples of validations up to size 16 and with arrays of
+    /// validations of any length.
     ///
     /// # Examples
     ///
@@ -978,8 +1379,9 @@ impl<T, E> Validation<T, E> {
 
 /// Trait for combining multiple validations in a tuple
 ///
-/// This trait is implemented for tuples of validations, allowing the `Validation::all`
-/// method to work with heterogeneous validation types.
+/// This trait is implemented for tuples of up to 16 validations, allowing the
+/// `Validation::all` method to work with heterogeneous validation types, and
+/// for arrays `[Validation<T, E>; N]` of any length.
 pub trait ValidateAll<E: Semigroup> {
     /// The output type when all validations succeed
     type Output;
@@ -988,392 +1390,28 @@ pub trait ValidateAll<E: Semigroup> {
     fn validate_all(self) -> Validation<Self::Output, E>;
 }
 
-// Macro to implement ValidateAll for tuples of different sizes
+// Implement ValidateAll for tuples: each element is matched once and its
+// error accumulated, so no nested tuples need reshaping.
 macro_rules! impl_validate_all {
-    // Base case: single element
-    ($T1:ident) => {
-        impl<E: Semigroup, $T1> ValidateAll<E> for (Validation<$T1, E>,) {
-            type Output = ($T1,);
-
-            fn validate_all(self) -> Validation<Self::Output, E> {
-                self.0.map(|v| (v,))
-            }
-        }
-    };
-
-    // Two elements
-    ($T1:ident, $T2:ident) => {
-        impl<E: Semigroup, $T1, $T2> ValidateAll<E> for (Validation<$T1, E>, Validation<$T2, E>) {
-            type Output = ($T1, $T2);
-
-            #[allow(non_snake_case)]
-            fn validate_all(self) -> Validation<Self::Output, E> {
-                let ($T1, $T2) = self;
-                $T1.and($T2)
-            }
-        }
-    };
-
-    // Three elements
-    ($T1:ident, $T2:ident, $T3:ident) => {
-        impl<E: Semigroup, $T1, $T2, $T3> ValidateAll<E>
-            for (Validation<$T1, E>, Validation<$T2, E>, Validation<$T3, E>)
-        {
-            type Output = ($T1, $T2, $T3);
-
-            #[allow(non_snake_case)]
-            fn validate_all(self) -> Validation<Self::Output, E> {
-                let ($T1, $T2, $T3) = self;
-                $T1.and($T2)
-                    .map(|(a, b)| (a, b))
-                    .and($T3)
-                    .map(|((a, b), c)| (a, b, c))
-            }
-        }
-    };
-
-    // Four elements
-    ($T1:ident, $T2:ident, $T3:ident, $T4:ident) => {
-        impl<E: Semigroup, $T1, $T2, $T3, $T4> ValidateAll<E>
-            for (
-                Validation<$T1, E>,
-                Validation<$T2, E>,
-                Validation<$T3, E>,
-                Validation<$T4, E>,
-            )
-        {
-            type Output = ($T1, $T2, $T3, $T4);
+    ($($T:ident),+) => {
+        impl<E: Semigroup, $($T),+> ValidateAll<E> for ($(Validation<$T, E>,)+) {
+            type Output = ($($T,)+);
 
             #[allow(non_snake_case)]
             fn validate_all(self) -> Validation<Self::Output, E> {
-                let ($T1, $T2, $T3, $T4) = self;
-                $T1.and($T2)
-                    .and($T3)
-                    .map(|((a, b), c)| (a, b, c))
-                    .and($T4)
-                    .map(|((a, b, c), d)| (a, b, c, d))
-            }
-        }
-    };
-
-    // Five elements
-    ($T1:ident, $T2:ident, $T3:ident, $T4:ident, $T5:ident) => {
-        impl<E: Semigroup, $T1, $T2, $T3, $T4, $T5> ValidateAll<E>
-            for (
-                Validation<$T1, E>,
-                Validation<$T2, E>,
-                Validation<$T3, E>,
-                Validation<$T4, E>,
-                Validation<$T5, E>,
-            )
-        {
-            type Output = ($T1, $T2, $T3, $T4, $T5);
-
-            #[allow(non_snake_case)]
-            fn validate_all(self) -> Validation<Self::Output, E> {
-                let ($T1, $T2, $T3, $T4, $T5) = self;
-                $T1.and($T2)
-                    .and($T3)
-                    .map(|((a, b), c)| (a, b, c))
-                    .and($T4)
-                    .map(|((a, b, c), d)| (a, b, c, d))
-                    .and($T5)
-                    .map(|((a, b, c, d), e)| (a, b, c, d, e))
-            }
-        }
-    };
-
-    // Six elements
-    ($T1:ident, $T2:ident, $T3:ident, $T4:ident, $T5:ident, $T6:ident) => {
-        impl<E: Semigroup, $T1, $T2, $T3, $T4, $T5, $T6> ValidateAll<E>
-            for (
-                Validation<$T1, E>,
-                Validation<$T2, E>,
-                Validation<$T3, E>,
-                Validation<$T4, E>,
-                Validation<$T5, E>,
-                Validation<$T6, E>,
-            )
-        {
-            type Output = ($T1, $T2, $T3, $T4, $T5, $T6);
-
-            #[allow(non_snake_case)]
-            fn validate_all(self) -> Validation<Self::Output, E> {
-                let ($T1, $T2, $T3, $T4, $T5, $T6) = self;
-                $T1.and($T2)
-                    .and($T3)
-                    .map(|((a, b), c)| (a, b, c))
-                    .and($T4)
-                    .map(|((a, b, c), d)| (a, b, c, d))
-                    .and($T5)
-                    .map(|((a, b, c, d), e)| (a, b, c, d, e))
-                    .and($T6)
-                    .map(|((a, b, c, d, e), f)| (a, b, c, d, e, f))
-            }
-        }
-    };
-
-    // Seven elements
-    ($T1:ident, $T2:ident, $T3:ident, $T4:ident, $T5:ident, $T6:ident, $T7:ident) => {
-        impl<E: Semigroup, $T1, $T2, $T3, $T4, $T5, $T6, $T7> ValidateAll<E>
-            for (
-                Validation<$T1, E>,
-                Validation<$T2, E>,
-                Validation<$T3, E>,
-                Validation<$T4, E>,
-                Validation<$T5, E>,
-                Validation<$T6, E>,
-                Validation<$T7, E>,
-            )
-        {
-            type Output = ($T1, $T2, $T3, $T4, $T5, $T6, $T7);
-
-            #[allow(non_snake_case)]
-            fn validate_all(self) -> Validation<Self::Output, E> {
-                let ($T1, $T2, $T3, $T4, $T5, $T6, $T7) = self;
-                $T1.and($T2)
-                    .and($T3)
-                    .map(|((a, b), c)| (a, b, c))
-                    .and($T4)
-                    .map(|((a, b, c), d)| (a, b, c, d))
-                    .and($T5)
-                    .map(|((a, b, c, d), e)| (a, b, c, d, e))
-                    .and($T6)
-                    .map(|((a, b, c, d, e), f)| (a, b, c, d, e, f))
-                    .and($T7)
-                    .map(|((a, b, c, d, e, f), g)| (a, b, c, d, e, f, g))
-            }
-        }
-    };
-
-    // Eight elements
-    ($T1:ident, $T2:ident, $T3:ident, $T4:ident, $T5:ident, $T6:ident, $T7:ident, $T8:ident) => {
-        impl<E: Semigroup, $T1, $T2, $T3, $T4, $T5, $T6, $T7, $T8> ValidateAll<E>
-            for (
-                Validation<$T1, E>,
-                Validation<$T2, E>,
-                Validation<$T3, E>,
-                Validation<$T4, E>,
-                Validation<$T5, E>,
-                Validation<$T6, E>,
-                Validation<$T7, E>,
-                Validation<$T8, E>,
-            )
-        {
-            type Output = ($T1, $T2, $T3, $T4, $T5, $T6, $T7, $T8);
-
-            #[allow(non_snake_case)]
-            fn validate_all(self) -> Validation<Self::Output, E> {
-                let ($T1, $T2, $T3, $T4, $T5, $T6, $T7, $T8) = self;
-                $T1.and($T2)
-                    .and($T3)
-                    .map(|((a, b), c)| (a, b, c))
-                    .and($T4)
-                    .map(|((a, b, c), d)| (a, b, c, d))
-                    .and($T5)
-                    .map(|((a, b, c, d), e)| (a, b, c, d, e))
-                    .and($T6)
-                    .map(|((a, b, c, d, e), f)| (a, b, c, d, e, f))
-                    .and($T7)
-                    .map(|((a, b, c, d, e, f), g)| (a, b, c, d, e, f, g))
-                    .and($T8)
-                    .map(|((a, b, c, d, e, f, g), h)| (a, b, c, d, e, f, g, h))
-            }
-        }
-    };
-
-    // Nine elements
-    ($T1:ident, $T2:ident, $T3:ident, $T4:ident, $T5:ident, $T6:ident, $T7:ident, $T8:ident, $T9:ident) => {
-        impl<E: Semigroup, $T1, $T2, $T3, $T4, $T5, $T6, $T7, $T8, $T9> ValidateAll<E>
-            for (
-                Validation<$T1, E>,
-                Validation<$T2, E>,
-                Validation<$T3, E>,
-                Validation<$T4, E>,
-                Validation<$T5, E>,
-                Validation<$T6, E>,
-                Validation<$T7, E>,
-                Validation<$T8, E>,
-                Validation<$T9, E>,
-            )
-        {
-            type Output = ($T1, $T2, $T3, $T4, $T5, $T6, $T7, $T8, $T9);
-
-            #[allow(non_snake_case)]
-            fn validate_all(self) -> Validation<Self::Output, E> {
-                let ($T1, $T2, $T3, $T4, $T5, $T6, $T7, $T8, $T9) = self;
-                $T1.and($T2)
-                    .and($T3)
-                    .map(|((a, b), c)| (a, b, c))
-                    .and($T4)
-                    .map(|((a, b, c), d)| (a, b, c, d))
-                    .and($T5)
-                    .map(|((a, b, c, d), e)| (a, b, c, d, e))
-                    .and($T6)
-                    .map(|((a, b, c, d, e), f)| (a, b, c, d, e, f))
-                    .and($T7)
-                    .map(|((a, b, c, d, e, f), g)| (a, b, c, d, e, f, g))
-                    .and($T8)
-                    .map(|((a, b, c, d, e, f, g), h)| (a, b, c, d, e, f, g, h))
-                    .and($T9)
-                    .map(|((a, b, c, d, e, f, g, h), i)| (a, b, c, d, e, f, g, h, i))
-            }
-        }
-    };
-
-    // Ten elements
-    ($T1:ident, $T2:ident, $T3:ident, $T4:ident, $T5:ident, $T6:ident, $T7:ident, $T8:ident, $T9:ident, $T10:ident) => {
-        impl<E: Semigroup, $T1, $T2, $T3, $T4, $T5, $T6, $T7, $T8, $T9, $T10> ValidateAll<E>
-            for (
-                Validation<$T1, E>,
-                Validation<$T2, E>,
-                Validation<$T3, E>,
-                Validation<$T4, E>,
-                Validation<$T5, E>,
-                Validation<$T6, E>,
-                Validation<$T7, E>,
-                Validation<$T8, E>,
-                Validation<$T9, E>,
-                Validation<$T10, E>,
-            )
-        {
-            type Output = ($T1, $T2, $T3, $T4, $T5, $T6, $T7, $T8, $T9, $T10);
-
-            #[allow(non_snake_case)]
-            fn validate_all(self) -> Validation<Self::Output, E> {
-                let ($T1, $T2, $T3, $T4, $T5, $T6, $T7, $T8, $T9, $T10) = self;
-                $T1.and($T2)
-                    .and($T3)
-                    .map(|((a, b), c)| (a, b, c))
-                    .and($T4)
-                    .map(|((a, b, c), d)| (a, b, c, d))
-                    .and($T5)
-                    .map(|((a, b, c, d), e)| (a, b, c, d, e))
-                    .and($T6)
-                    .map(|((a, b, c, d, e), f)| (a, b, c, d, e, f))
-                    .and($T7)
-                    .map(|((a, b, c, d, e, f), g)| (a, b, c, d, e, f, g))
-                    .and($T8)
-                    .map(|((a, b, c, d, e, f, g), h)| (a, b, c, d, e, f, g, h))
-                    .and($T9)
-                    .map(|((a, b, c, d, e, f, g, h), i)| (a, b, c, d, e, f, g, h, i))
-                    .and($T10)
-                    .map(|((a, b, c, d, e, f, g, h, i), j)| (a, b, c, d, e, f, g, h, i, j))
-            }
-        }
-    };
-
-    // Eleven elements
-    ($T1:ident, $T2:ident, $T3:ident, $T4:ident, $T5:ident, $T6:ident, $T7:ident, $T8:ident, $T9:ident, $T10:ident, $T11:ident) => {
-        impl<E: Semigroup, $T1, $T2, $T3, $T4, $T5, $T6, $T7, $T8, $T9, $T10, $T11> ValidateAll<E>
-            for (
-                Validation<$T1, E>,
-                Validation<$T2, E>,
-                Validation<$T3, E>,
-                Validation<$T4, E>,
-                Validation<$T5, E>,
-                Validation<$T6, E>,
-                Validation<$T7, E>,
-                Validation<$T8, E>,
-                Validation<$T9, E>,
-                Validation<$T10, E>,
-                Validation<$T11, E>,
-            )
-        {
-            type Output = ($T1, $T2, $T3, $T4, $T5, $T6, $T7, $T8, $T9, $T10, $T11);
-
-            #[allow(non_snake_case)]
-            fn validate_all(self) -> Validation<Self::Output, E> {
-                let ($T1, $T2, $T3, $T4, $T5, $T6, $T7, $T8, $T9, $T10, $T11) = self;
-                $T1.and($T2)
-                    .and($T3)
-                    .map(|((a, b), c)| (a, b, c))
-                    .and($T4)
-                    .map(|((a, b, c), d)| (a, b, c, d))
-                    .and($T5)
-                    .map(|((a, b, c, d), e)| (a, b, c, d, e))
-                    .and($T6)
-                    .map(|((a, b, c, d, e), f)| (a, b, c, d, e, f))
-                    .and($T7)
-                    .map(|((a, b, c, d, e, f), g)| (a, b, c, d, e, f, g))
-                    .and($T8)
-                    .map(|((a, b, c, d, e, f, g), h)| (a, b, c, d, e, f, g, h))
-                    .and($T9)
-                    .map(|((a, b, c, d, e, f, g, h), i)| (a, b, c, d, e, f, g, h, i))
-                    .and($T10)
-                    .map(|((a, b, c, d, e, f, g, h, i), j)| (a, b, c, d, e, f, g, h, i, j))
-                    .and($T11)
-                    .map(|((a, b, c, d, e, f, g, h, i, j), k)| (a, b, c, d, e, f, g, h, i, j, k))
-            }
-        }
-    };
-
-    // Twelve elements
-    ($T1:ident, $T2:ident, $T3:ident, $T4:ident, $T5:ident, $T6:ident, $T7:ident, $T8:ident, $T9:ident, $T10:ident, $T11:ident, $T12:ident) => {
-        impl<E: Semigroup, $T1, $T2, $T3, $T4, $T5, $T6, $T7, $T8, $T9, $T10, $T11, $T12>
-            ValidateAll<E>
-            for (
-                Validation<$T1, E>,
-                Validation<$T2, E>,
-                Validation<$T3, E>,
-                Validation<$T4, E>,
-                Validation<$T5, E>,
-                Validation<$T6, E>,
-                Validation<$T7, E>,
-                Validation<$T8, E>,
-                Validation<$T9, E>,
-                Validation<$T10, E>,
-                Validation<$T11, E>,
-                Validation<$T12, E>,
-            )
-        {
-            type Output = (
-                $T1,
-                $T2,
-                $T3,
-                $T4,
-                $T5,
-                $T6,
-                $T7,
-                $T8,
-                $T9,
-                $T10,
-                $T11,
-                $T12,
-            );
-
-            #[allow(non_snake_case)]
-            fn validate_all(self) -> Validation<Self::Output, E> {
-                let ($T1, $T2, $T3, $T4, $T5, $T6, $T7, $T8, $T9, $T10, $T11, $T12) = self;
-                $T1.and($T2)
-                    .and($T3)
-                    .map(|((a, b), c)| (a, b, c))
-                    .and($T4)
-                    .map(|((a, b, c), d)| (a, b, c, d))
-                    .and($T5)
-                    .map(|((a, b, c, d), e)| (a, b, c, d, e))
-                    .and($T6)
-                    .map(|((a, b, c, d, e), f)| (a, b, c, d, e, f))
-                    .and($T7)
-                    .map(|((a, b, c, d, e, f), g)| (a, b, c, d, e, f, g))
-                    .and($T8)
-                    .map(|((a, b, c, d, e, f, g), h)| (a, b, c, d, e, f, g, h))
-                    .and($T9)
-                    .map(|((a, b, c, d, e, f, g, h), i)| (a, b, c, d, e, f, g, h, i))
-                    .and($T10)
-                    .map(|((a, b, c, d, e, f, g, h, i), j)| (a, b, c, d, e, f, g, h, i, j))
-                    .and($T11)
-                    .map(|((a, b, c, d, e, f, g, h, i, j), k)| (a, b, c, d, e, f, g, h, i, j, k))
-                    .and($T12)
-                    .map(|((a, b, c, d, e, f, g, h, i, j, k), l)| {
-                        (a, b, c, d, e, f, g, h, i, j, k, l)
-                    })
+                let ($($T,)+) = self;
+                let mut errors = None;
+                $(let $T = $T.take_success(&mut errors);)+
+                match errors {
+                    Some(errors) => Validation::Failure(errors),
+                    None => Validation::Success(($($T.unwrap(),)+)),
+                }
             }
         }
     };
 }
 
-// Generate implementations for tuples of size 1 through 12
+// Generate implementations for tuples of size 1 through 16
 impl_validate_all!(T1);
 impl_validate_all!(T1, T2);
 impl_validate_all!(T1, T2, T3);
@@ -1386,6 +1424,49 @@ impl_validate_all!(T1, T2, T3, T4, T5, T6, T7, T8, T9);
 impl_validate_all!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10);
 impl_validate_all!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11);
 impl_validate_all!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12);
+impl_validate_all!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13);
+impl_validate_all!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14);
+impl_validate_all!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14, T15);
+impl_validate_all!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14, T15, T16);
+
+/// Arrays of validations of any length, for many fields of the same type.
+///
+/// Unlike [`Validation::all_vec`], the length is kept in the type and
+/// nothing is allocated.
+///
+/// # Example
+///
+/// ```
+/// use stillwater::{Validation, validation::ValidateAll};
+///
+/// let scores = [
+///     Validation::<u8, Vec<&str>>::success(7),
+///     Validation::failure(vec!["score 2 is missing"]),
+///     Validation::failure(vec!["score 3 is out of range"]),
+/// ];
+/// assert_eq!(
+///     scores.validate_all(),
+///     Validation::Failure(vec!["score 2 is missing", "score 3 is out of range"])
+/// );
+///
+/// let [a, b] = [Validation::<u8, Vec<&str>>::success(1), Validation::success(2)]
+///     .validate_all()
+///     .into_result()
+///     .unwrap();
+/// assert_eq!(a + b, 3);
+/// ```
+impl<E: Semigroup, T, const N: usize> ValidateAll<E> for [Validation<T, E>; N] {
+    type Output = [T; N];
+
+    fn validate_all(self) -> Validation<Self::Output, E> {
+        let mut errors = None;
+        let values = self.map(|validation| validation.take_success(&mut errors));
+        match errors {
+            Some(errors) => Validation::Failure(errors),
+            None => Validation::Success(values.map(|value| value.unwrap())),
+        }
+    }
+}
 
 // Try trait support for ? operator (requires nightly + try_trait feature)
 //
@@ -1397,8 +1478,8 @@ impl_validate_all!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12);
 #[cfg(feature = "try_trait")]
 mod try_impl {
     use super::*;
-    use std::convert::Infallible;
-    use std::ops::{ControlFlow, FromResidual, Try};
+    use core::convert::Infallible;
+    use core::ops::{ControlFlow, FromResidual, Try};
 
     /// Try trait implementation for Validation
     ///
@@ -1549,6 +1630,63 @@ mod tests {
     }
 
     // and_then tests
+    #[test]
+    fn test_zip_with() {
+        let both =
+            Validation::<_, Vec<&str>>::success(2).zip_with(Validation::success(3), |a, b| a * b);
+        let second_failed = Validation::<i32, _>::success(2)
+            .zip_with(Validation::<i32, _>::failure(vec!["e2"]), |a, b| a * b);
+        let both_failed = Validation::<i32, _>::failure(vec!["e1"])
+            .zip_with(Validation::<i32, _>::failure(vec!["e2"]), |a, b| a * b);
+
+        assert_eq!(both, Validation::Success(6));
+        assert_eq!(second_failed, Validation::Failure(vec!["e2"]));
+        assert_eq!(both_failed, Validation::Failure(vec!["e1", "e2"]));
+    }
+
+    #[test]
+    fn test_product3_applies_function_to_values() {
+        let result = Validation::product3(
+            Validation::<_, Vec<&str>>::success(1),
+            Validation::success("two"),
+            Validation::success(3.0),
+            |a, b, c| format!("{} {} {}", a, b, c),
+        );
+
+        assert_eq!(result, Validation::Success("1 two 3".to_string()));
+    }
+
+    #[test]
+    fn test_product8_accumulates_errors_in_order() {
+        let ok = Validation::<i32, Vec<&str>>::success;
+        let err = |e| Validation::<i32, Vec<&str>>::failure(vec![e]);
+        let result = Validation::product8(
+            err("e1"),
+            ok(2),
+            ok(3),
+            err("e4"),
+            ok(5),
+            ok(6),
+            ok(7),
+            err("e8"),
+            |a, b, c, d, e, f, g, h| a + b + c + d + e + f + g + h,
+        );
+        let sum = Validation::product8(
+            ok(1),
+            ok(2),
+            ok(3),
+            ok(4),
+            ok(5),
+            ok(6),
+            ok(7),
+            ok(8),
+            |a, b, c, d, e, f, g, h| a + b + c + d + e + f + g + h,
+        );
+
+        assert_eq!(result, Validation::Failure(vec!["e1", "e4", "e8"]));
+        assert_eq!(sum, Validation::Success(36));
+    }
+
     #[test]
     fn test_and_then_success() {
         let v = Validation::<_, Vec<&str>>::success(5);
@@ -2187,6 +2325,102 @@ mod tests {
         );
     }
 
+    // === 13- to 16-tuple and array tests ===
+
+    #[test]
+    fn test_validate_all_16tuple_all_success() {
+        use crate::validation::ValidateAll;
+        let result = (
+            Validation::<_, Vec<&str>>::success(1),
+            Validation::<_, Vec<&str>>::success(2),
+            Validation::<_, Vec<&str>>::success(3),
+            Validation::<_, Vec<&str>>::success(4),
+            Validation::<_, Vec<&str>>::success(5),
+            Validation::<_, Vec<&str>>::success(6),
+            Validation::<_, Vec<&str>>::success(7),
+            Validation::<_, Vec<&str>>::success(8),
+            Validation::<_, Vec<&str>>::success(9),
+            Validation::<_, Vec<&str>>::success(10),
+            Validation::<_, Vec<&str>>::success(11),
+            Validation::<_, Vec<&str>>::success(12),
+            Validation::<_, Vec<&str>>::success(13),
+            Validation::<_, Vec<&str>>::success(14),
+            Validation::<_, Vec<&str>>::success("fifteen"),
+            Validation::<_, Vec<&str>>::success(true),
+        )
+            .validate_all();
+
+        // Tuples this wide implement neither `PartialEq` nor `Debug`.
+        let (a, _, _, _, _, _, _, _, _, _, _, _, _, n, o, p) = result.into_result().unwrap();
+        assert_eq!((a, n, o, p), (1, 14, "fifteen", true));
+    }
+
+    #[test]
+    #[allow(clippy::type_complexity)]
+    fn test_validate_all_13tuple_mixed() {
+        use crate::validation::ValidateAll;
+        let result: Validation<
+            (
+                i32,
+                i32,
+                i32,
+                i32,
+                i32,
+                i32,
+                i32,
+                i32,
+                i32,
+                i32,
+                i32,
+                i32,
+                i32,
+            ),
+            Vec<&str>,
+        > = (
+            Validation::Failure(vec!["e1"]),
+            Validation::Success(2),
+            Validation::Success(3),
+            Validation::Success(4),
+            Validation::Success(5),
+            Validation::Success(6),
+            Validation::Failure(vec!["e7"]),
+            Validation::Success(8),
+            Validation::Success(9),
+            Validation::Success(10),
+            Validation::Success(11),
+            Validation::Success(12),
+            Validation::Failure(vec!["e13"]),
+        )
+            .validate_all();
+
+        match result {
+            Validation::Failure(errors) => assert_eq!(errors, vec!["e1", "e7", "e13"]),
+            Validation::Success(_) => panic!("expected failure"),
+        }
+    }
+
+    #[test]
+    fn test_validate_all_array() {
+        use crate::validation::ValidateAll;
+        let success = [
+            Validation::<_, Vec<&str>>::success(1),
+            Validation::success(2),
+            Validation::success(3),
+        ]
+        .validate_all();
+        let failure = [
+            Validation::<i32, _>::failure(vec!["e1"]),
+            Validation::success(2),
+            Validation::failure(vec!["e3"]),
+        ]
+        .validate_all();
+        let empty: [Validation<i32, Vec<&str>>; 0] = [];
+
+        assert_eq!(success, Validation::Success([1, 2, 3]));
+        assert_eq!(failure, Validation::Failure(vec!["e1", "e3"]));
+        assert_eq!(empty.validate_all(), Validation::Success([]));
+    }
+
     // ========== bimap tests ==========
 
     #[test]
@@ -2346,6 +2580,89 @@ mod tests {
         assert_eq!(v.merge(), 3);
     }
 
+    // ========== swap / bitraverse tests ==========
+
+    #[test]
+    fn test_swap_is_involution() {
+        let v: Validation<i32, String> = Validation::failure("err".to_string());
+        assert_eq!(v.clone().swap(), Validation::Success("err".to_string()));
+        assert_eq!(v.clone().swap().swap(), v);
+    }
+
+    #[test]
+    fn test_bitraverse_success_and_failure() {
+        let v = Validation::<_, &str>::success("7");
+        assert_eq!(
+            v.bitraverse(|e| Ok(e.len()), |s| s.parse::<i32>()),
+            Ok(Validation::Success(7))
+        );
+
+        let v = Validation::<&str, _>::failure("bad");
+        assert_eq!(
+            v.bitraverse(|e| Ok::<_, String>(e.len()), |s| Ok(s.len())),
+            Ok(Validation::Failure(3))
+        );
+
+        let v = Validation::<&str, _>::failure("bad");
+        assert_eq!(
+            v.bitraverse(|_| Err("io"), |s| Ok::<_, &str>(s.len())),
+            Err::<Validation<usize, usize>, _>("io")
+        );
+    }
+
+    #[test]
+    fn test_bisequence() {
+        let v: Validation<Result<i32, &str>, Result<String, &str>> =
+            Validation::failure(Ok("invalid".to_string()));
+        assert_eq!(
+            v.bisequence(),
+            Ok(Validation::Failure("invalid".to_string()))
+        );
+
+        let v: Validation<Result<i32, &str>, Result<String, &str>> = Validation::success(Err("io"));
+        assert_eq!(v.bisequence(), Err("io"));
+    }
+
+    // ========== partition tests ==========
+
+    #[test]
+    fn test_partition_keeps_order() {
+        let validations = vec![
+            Validation::failure("a"),
+            Validation::success(1),
+            Validation::failure("b"),
+            Validation::success(2),
+        ];
+        assert_eq!(
+            Validation::partition(validations),
+            (vec![1, 2], vec!["a", "b"])
+        );
+    }
+
+    #[test]
+    fn test_partition_empty() {
+        let validations: Vec<Validation<i32, String>> = vec![];
+        assert_eq!(Validation::partition(validations), (vec![], vec![]));
+    }
+
+    #[test]
+    fn test_partition_combined() {
+        let all_ok = vec![
+            Validation::<_, Vec<&str>>::success(1),
+            Validation::success(2),
+        ];
+        assert_eq!(Validation::partition_combined(all_ok), (vec![1, 2], None));
+
+        let mixed = vec![
+            Validation::success(1),
+            Validation::failure(NonEmptyVec::singleton("a")),
+            Validation::failure(NonEmptyVec::new("b", vec!["c"])),
+        ];
+        let (successes, errors) = Validation::partition_combined(mixed);
+        assert_eq!(successes, vec![1]);
+        assert_eq!(errors.unwrap().into_vec(), vec!["a", "b", "c"]);
+    }
+
     // ========== Bifunctor law tests ==========
 
     #[test]
@@ -2405,4 +2722,30 @@ mod tests {
 
         assert_eq!(result, "result: 10");
     }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_par_all_vec_matches_sequential() {
+        let validate = |n: u32| {
+            if n % 7 == 0 {
+                Validation::failure(vec![n])
+            } else {
+                Validation::success(n * 2)
+            }
+        };
+
+        let parallel = Validation::par_all_vec((1..1000).collect::<Vec<_>>(), validate);
+        let sequential = Validation::all_vec((1..1000).map(validate).collect());
+
+        assert_eq!(parallel, sequential);
+        assert_eq!(parallel.unwrap_err().len(), 142);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_par_all_vec_success_preserves_order() {
+        let result =
+            Validation::<_, Vec<String>>::par_all_vec(0..100, |n: i32| Validation::success(n));
+        assert_eq!(result, Validation::Success((0..100).collect()));
+    }
 }
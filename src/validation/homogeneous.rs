@@ -77,6 +77,9 @@
 //! }
 //! ```
 
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
 use crate::{Semigroup, Validation};
 
 /// Validate that all items in a collection have the same discriminant.
@@ -427,8 +430,8 @@ impl TypeMismatchError {
     }
 }
 
-impl std::fmt::Display for TypeMismatchError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for TypeMismatchError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(
             f,
             "Type mismatch at index {}: expected {}, got {}",
@@ -437,12 +440,12 @@ impl std::fmt::Display for TypeMismatchError {
     }
 }
 
-impl std::error::Error for TypeMismatchError {}
+impl core::error::Error for TypeMismatchError {}
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::mem::discriminant;
+    use core::mem::discriminant;
 
     #[derive(Clone, Debug, PartialEq)]
     enum TestEnum {
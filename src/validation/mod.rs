@@ -3,9 +3,21 @@
 //! This module provides:
 //! - The core `Validation` type for error accumulation
 //! - Homogeneous validation utilities for ensuring collections are type-consistent
+//! - The `Validated` type for successes that carry non-fatal warnings
+//! - The `ValidationOutcome` type for rules that may not apply
+//! - Conversion of deserialization errors into field errors (requires the
+//!   `serde_path_to_error` feature)
 
 pub mod core;
 pub mod homogeneous;
+pub mod outcome;
+#[cfg(feature = "serde_path_to_error")]
+pub mod serde_errors;
+pub mod warnings;
 
 // Re-export core validation types
 pub use core::*;
+pub use outcome::{traverse_outcomes, ValidationOutcome};
+#[cfg(feature = "serde_path_to_error")]
+pub use serde_errors::from_serde_path_errors;
+pub use warnings::{Validated, WarningPolicy};
@@ -0,0 +1,305 @@
+//! Three-state validation outcomes
+//!
+//! Some rules only apply to some records: a VAT number is only checked for
+//! business customers, a shipping address only for physical goods. Modelling
+//! "not applicable" as a success or a failure pollutes that branch, so this
+//! module provides [`ValidationOutcome`], which adds a third `Skipped` state
+//! carrying the reason the rule didn't apply.
+//!
+//! Skipped outcomes are ignored when combining with
+//! [`all_vec`](ValidationOutcome::all_vec) and [`traverse_outcomes`], and can
+//! be collected separately for reporting.
+//!
+//! # Example
+//!
+//! ```
+//! use stillwater::validation::ValidationOutcome;
+//! use stillwater::Validation;
+//!
+//! struct Customer { business: bool, vat: Option<&'static str> }
+//!
+//! fn check_vat(c: &Customer) -> ValidationOutcome<&'static str, Vec<&'static str>> {
+//!     ValidationOutcome::when(c.business, "not a business customer", || {
+//!         match c.vat {
+//!             Some(vat) => Validation::success(vat),
+//!             None => Validation::failure(vec!["business customers need a VAT number"]),
+//!         }
+//!     })
+//! }
+//!
+//! let customers = [
+//!     Customer { business: true, vat: Some("GB123") },
+//!     Customer { business: false, vat: None },
+//! ];
+//! let outcomes: Vec<_> = customers.iter().map(check_vat).collect();
+//! assert_eq!(
+//!     ValidationOutcome::all_vec_with_skipped(outcomes),
+//!     Validation::Success((vec!["GB123"], vec!["not a business customer".to_string()]))
+//! );
+//! ```
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use super::core::Validation;
+use crate::Semigroup;
+
+/// The result of a validation rule that may not apply.
+///
+/// # Type Parameters
+///
+/// * `T` - The success value type
+/// * `E` - The error type
+/// * `R` - The skip reason type (defaults to `String`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationOutcome<T, E, R = String> {
+    /// The rule applied and passed.
+    Success(T),
+    /// The rule applied and failed.
+    Failure(E),
+    /// The rule did not apply.
+    Skipped(R),
+}
+
+impl<T, E, R> ValidationOutcome<T, E, R> {
+    /// Create a skipped outcome.
+    pub fn skip(reason: impl Into<R>) -> Self {
+        ValidationOutcome::Skipped(reason.into())
+    }
+
+    /// Run `validate` only if `applies` is true; otherwise skip with `reason`.
+    pub fn when<F>(applies: bool, reason: impl Into<R>, validate: F) -> Self
+    where
+        F: FnOnce() -> Validation<T, E>,
+    {
+        if applies {
+            validate().into()
+        } else {
+            ValidationOutcome::Skipped(reason.into())
+        }
+    }
+
+    /// Returns `true` if the rule applied and passed.
+    pub fn is_success(&self) -> bool {
+        matches!(self, ValidationOutcome::Success(_))
+    }
+
+    /// Returns `true` if the rule applied and failed.
+    pub fn is_failure(&self) -> bool {
+        matches!(self, ValidationOutcome::Failure(_))
+    }
+
+    /// Returns `true` if the rule did not apply.
+    pub fn is_skipped(&self) -> bool {
+        matches!(self, ValidationOutcome::Skipped(_))
+    }
+
+    /// Transform the success value.
+    pub fn map<U, F>(self, f: F) -> ValidationOutcome<U, E, R>
+    where
+        F: FnOnce(T) -> U,
+    {
+        match self {
+            ValidationOutcome::Success(value) => ValidationOutcome::Success(f(value)),
+            ValidationOutcome::Failure(error) => ValidationOutcome::Failure(error),
+            ValidationOutcome::Skipped(reason) => ValidationOutcome::Skipped(reason),
+        }
+    }
+
+    /// Transform the error.
+    pub fn map_err<E2, F>(self, f: F) -> ValidationOutcome<T, E2, R>
+    where
+        F: FnOnce(E) -> E2,
+    {
+        match self {
+            ValidationOutcome::Success(value) => ValidationOutcome::Success(value),
+            ValidationOutcome::Failure(error) => ValidationOutcome::Failure(f(error)),
+            ValidationOutcome::Skipped(reason) => ValidationOutcome::Skipped(reason),
+        }
+    }
+
+    /// Convert to a [`Validation`], with `None` for skipped outcomes.
+    pub fn into_validation(self) -> Validation<Option<T>, E> {
+        match self {
+            ValidationOutcome::Success(value) => Validation::Success(Some(value)),
+            ValidationOutcome::Failure(error) => Validation::Failure(error),
+            ValidationOutcome::Skipped(_) => Validation::Success(None),
+        }
+    }
+
+    /// Convert to a [`Validation`], using `default` for skipped outcomes.
+    pub fn unwrap_skipped_or(self, default: T) -> Validation<T, E> {
+        self.into_validation().map(|value| value.unwrap_or(default))
+    }
+}
+
+impl<T, E: Semigroup, R> ValidationOutcome<T, E, R> {
+    /// Combine outcomes, accumulating errors and ignoring skipped ones.
+    ///
+    /// Like [`Validation::all_vec`], but skipped outcomes contribute neither
+    /// a value nor an error.
+    pub fn all_vec(outcomes: Vec<ValidationOutcome<T, E, R>>) -> Validation<Vec<T>, E> {
+        Self::all_vec_with_skipped(outcomes).map(|(values, _)| values)
+    }
+
+    /// Combine outcomes, accumulating errors and collecting skip reasons.
+    ///
+    /// On success, returns the values of the rules that applied and the
+    /// reasons of those that were skipped, each in input order.
+    pub fn all_vec_with_skipped(
+        outcomes: Vec<ValidationOutcome<T, E, R>>,
+    ) -> Validation<(Vec<T>, Vec<R>), E> {
+        let mut values = Vec::new();
+        let mut skipped = Vec::new();
+        let mut errors: Option<E> = None;
+
+        for outcome in outcomes {
+            match outcome {
+                ValidationOutcome::Success(value) => values.push(value),
+                ValidationOutcome::Skipped(reason) => skipped.push(reason),
+                ValidationOutcome::Failure(error) => {
+                    errors = Some(match errors {
+                        Some(acc) => acc.combine(error),
+                        None => error,
+                    })
+                }
+            }
+        }
+
+        match errors {
+            Some(errors) => Validation::Failure(errors),
+            None => Validation::Success((values, skipped)),
+        }
+    }
+}
+
+impl<T, E, R> From<Validation<T, E>> for ValidationOutcome<T, E, R> {
+    fn from(validation: Validation<T, E>) -> Self {
+        match validation {
+            Validation::Success(value) => ValidationOutcome::Success(value),
+            Validation::Failure(error) => ValidationOutcome::Failure(error),
+        }
+    }
+}
+
+/// Apply a conditional validator to each element, accumulating errors.
+///
+/// Elements whose outcome is skipped are left out of the result.
+///
+/// # Example
+///
+/// ```
+/// use stillwater::validation::{traverse_outcomes, ValidationOutcome};
+/// use stillwater::Validation;
+///
+/// let result = traverse_outcomes(vec![-1, 4, 9], |n: i32| {
+///     ValidationOutcome::<_, Vec<String>>::when(n >= 0, "negative", || {
+///         Validation::success((n as f64).sqrt())
+///     })
+/// });
+/// assert_eq!(result, Validation::Success(vec![2.0, 3.0]));
+/// ```
+pub fn traverse_outcomes<T, U, E, R, F, I>(iter: I, f: F) -> Validation<Vec<U>, E>
+where
+    I: IntoIterator<Item = T>,
+    F: FnMut(T) -> ValidationOutcome<U, E, R>,
+    E: Semigroup,
+{
+    ValidationOutcome::all_vec(iter.into_iter().map(f).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Outcome = ValidationOutcome<i32, Vec<&'static str>, &'static str>;
+
+    #[test]
+    fn test_when_runs_or_skips() {
+        let ran: Outcome = ValidationOutcome::when(true, "n/a", || Validation::success(1));
+        assert_eq!(ran, ValidationOutcome::Success(1));
+
+        let skipped: Outcome = ValidationOutcome::when(false, "n/a", || {
+            panic!("validator must not run when skipped")
+        });
+        assert_eq!(skipped, ValidationOutcome::Skipped("n/a"));
+        assert!(skipped.is_skipped());
+    }
+
+    #[test]
+    fn test_all_vec_ignores_skipped() {
+        let outcomes: Vec<Outcome> = vec![
+            ValidationOutcome::Success(1),
+            ValidationOutcome::skip("n/a"),
+            ValidationOutcome::Success(3),
+        ];
+        assert_eq!(
+            ValidationOutcome::all_vec(outcomes),
+            Validation::Success(vec![1, 3])
+        );
+    }
+
+    #[test]
+    fn test_all_vec_accumulates_errors() {
+        let outcomes: Vec<Outcome> = vec![
+            ValidationOutcome::Failure(vec!["a"]),
+            ValidationOutcome::skip("n/a"),
+            ValidationOutcome::Success(1),
+            ValidationOutcome::Failure(vec!["b"]),
+        ];
+        assert_eq!(
+            ValidationOutcome::all_vec(outcomes),
+            Validation::Failure(vec!["a", "b"])
+        );
+    }
+
+    #[test]
+    fn test_all_vec_with_skipped_reports_reasons() {
+        let outcomes: Vec<Outcome> = vec![
+            ValidationOutcome::skip("first"),
+            ValidationOutcome::Success(2),
+            ValidationOutcome::skip("second"),
+        ];
+        assert_eq!(
+            ValidationOutcome::all_vec_with_skipped(outcomes),
+            Validation::Success((vec![2], vec!["first", "second"]))
+        );
+    }
+
+    #[test]
+    fn test_all_skipped_is_empty_success() {
+        let outcomes: Vec<Outcome> = vec![ValidationOutcome::skip("x")];
+        assert_eq!(
+            ValidationOutcome::all_vec(outcomes),
+            Validation::Success(vec![])
+        );
+    }
+
+    #[test]
+    fn test_conversions() {
+        let skipped: Outcome = ValidationOutcome::skip("n/a");
+        assert_eq!(skipped.clone().into_validation(), Validation::Success(None));
+        assert_eq!(skipped.unwrap_skipped_or(0), Validation::Success(0));
+
+        let from: Outcome = Validation::failure(vec!["e"]).into();
+        assert!(from.is_failure());
+        assert_eq!(
+            from.map(|x| x + 1).map_err(|e| e.len()),
+            ValidationOutcome::Failure(1)
+        );
+    }
+
+    #[test]
+    fn test_traverse_outcomes() {
+        let result = traverse_outcomes(vec![1, -2, 3, 0], |n: i32| -> Outcome {
+            if n == 0 {
+                ValidationOutcome::skip("zero")
+            } else if n < 0 {
+                ValidationOutcome::Failure(vec!["negative"])
+            } else {
+                ValidationOutcome::Success(n)
+            }
+        });
+        assert_eq!(result, Validation::Failure(vec!["negative"]));
+    }
+}
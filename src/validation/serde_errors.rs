@@ -0,0 +1,120 @@
+//! Deserialization errors as field errors
+//!
+//! A serde deserializer stops at the first malformed value and reports it
+//! without saying where it was. Wrapped with
+//! [`serde_path_to_error`](https://docs.rs/serde_path_to_error), it also
+//! records the path to that value, and [`from_serde_path_errors`] turns the
+//! result into [`FieldError`]s keyed by that path. Decoding failures and
+//! domain validation failures can then share one error type and be
+//! reported together.
+//!
+//! # Example
+//!
+//! ```
+//! use serde::Deserialize;
+//! use stillwater::refined::FieldError;
+//! use stillwater::validation::from_serde_path_errors;
+//! use stillwater::Validation;
+//!
+//! #[derive(Debug, Deserialize)]
+//! struct Server {
+//!     host: String,
+//!     port: u16,
+//! }
+//!
+//! #[derive(Debug, Deserialize)]
+//! struct Config {
+//!     servers: Vec<Server>,
+//! }
+//!
+//! fn decode(json: &str) -> Validation<Config, Vec<FieldError<String>>> {
+//!     let de = &mut serde_json::Deserializer::from_str(json);
+//!     match serde_path_to_error::deserialize(de) {
+//!         Ok(config) => Validation::success(config),
+//!         Err(error) => Validation::failure(from_serde_path_errors(error)),
+//!     }
+//! }
+//!
+//! fn check(config: Config) -> Validation<Config, Vec<FieldError<String>>> {
+//!     let errors: Vec<_> = config
+//!         .servers
+//!         .iter()
+//!         .enumerate()
+//!         .filter(|(_, server)| server.host.is_empty())
+//!         .map(|(i, _)| FieldError::new(format!("servers[{}].host", i), "is empty".to_string()))
+//!         .collect();
+//!     if errors.is_empty() {
+//!         Validation::success(config)
+//!     } else {
+//!         Validation::failure(errors)
+//!     }
+//! }
+//!
+//! let bad_port = r#"{"servers": [{"host": "a", "port": 80}, {"host": "b", "port": "x"}]}"#;
+//! let errors = decode(bad_port).and_then(check).into_result().unwrap_err();
+//! assert_eq!(errors[0].field, "servers[1].port");
+//!
+//! let empty_host = r#"{"servers": [{"host": "", "port": 80}]}"#;
+//! let errors = decode(empty_host).and_then(check).into_result().unwrap_err();
+//! assert_eq!(errors[0].to_string(), "servers[0].host: is empty");
+//! ```
+
+use std::fmt;
+
+use crate::refined::FieldError;
+
+/// Convert a deserialization error with its path into field errors.
+///
+/// The field is the path to the value that failed to deserialize, such as
+/// `servers[1].port`, or `.` when the input as a whole was rejected. The
+/// error is the deserializer's message. Serde stops at the first error, so
+/// the result holds exactly one entry; it is a `Vec` so that it can be
+/// accumulated with other validation errors directly.
+pub fn from_serde_path_errors<E: fmt::Display>(
+    error: serde_path_to_error::Error<E>,
+) -> Vec<FieldError<String>> {
+    let path = error.path().to_string();
+    vec![FieldError::new(path, error.into_inner().to_string())]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Address {
+        city: String,
+        zip: u32,
+    }
+
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Person {
+        name: String,
+        addresses: Vec<Address>,
+    }
+
+    fn decode(json: &str) -> Result<Person, Vec<FieldError<String>>> {
+        let de = &mut serde_json::Deserializer::from_str(json);
+        serde_path_to_error::deserialize(de).map_err(from_serde_path_errors)
+    }
+
+    #[test]
+    fn test_nested_error_keeps_full_path() {
+        let errors =
+            decode(r#"{"name": "a", "addresses": [{"city": "b", "zip": -1}]}"#).unwrap_err();
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].field, "addresses[0].zip");
+        assert!(errors[0].error.contains("-1"));
+    }
+
+    #[test]
+    fn test_top_level_error_uses_root_path() {
+        let errors = decode("[]").unwrap_err();
+
+        assert_eq!(errors[0].field, ".");
+    }
+}
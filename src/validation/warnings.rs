@@ -0,0 +1,376 @@
+//! Validations that can succeed with warnings
+//!
+//! This module provides [`Validated`], a [`Validation`] that also carries
+//! accumulated non-fatal warnings. Warnings are combined with their
+//! `Semigroup` instance, just like errors, and are kept on both successes
+//! and failures. A [`WarningPolicy`] decides at the end whether warnings are
+//! acceptable or should become hard failures.
+//!
+//! This gives linters and importers "succeeded with warnings" semantics:
+//!
+//! ```
+//! use stillwater::validation::{Validated, WarningPolicy};
+//! use stillwater::Validation;
+//!
+//! fn check_name(name: &str) -> Validated<String, Vec<String>, Vec<String>> {
+//!     if name.is_empty() {
+//!         Validated::failure(vec!["name is required".to_string()])
+//!     } else if name.len() > 20 {
+//!         Validated::warn(name.to_string(), vec!["name is unusually long".to_string()])
+//!     } else {
+//!         Validated::success(name.to_string())
+//!     }
+//! }
+//!
+//! let result = check_name("Bartholomew Fitzgerald III");
+//! assert!(result.is_success());
+//! assert!(result.has_warnings());
+//!
+//! // Strict mode turns warnings into errors
+//! let strict = result.resolve(WarningPolicy::Deny, |warnings| warnings);
+//! assert_eq!(
+//!     strict,
+//!     Validation::Failure(vec!["name is unusually long".to_string()])
+//! );
+//! ```
+
+use alloc::vec::Vec;
+
+use super::core::Validation;
+use crate::Semigroup;
+
+/// Whether warnings are acceptable when resolving a [`Validated`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WarningPolicy {
+    /// Warnings are dropped; only errors cause failure.
+    #[default]
+    Allow,
+    /// Warnings are converted to errors and cause failure.
+    Deny,
+}
+
+/// A [`Validation`] that also carries accumulated warnings.
+///
+/// # Type Parameters
+///
+/// * `T` - The success value type
+/// * `W` - The warnings type (combined with `Semigroup`)
+/// * `E` - The error type (combined with `Semigroup`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Validated<T, W, E> {
+    outcome: Validation<T, E>,
+    warnings: Option<W>,
+}
+
+impl<T, W, E> Validated<T, W, E> {
+    /// Create a success without warnings.
+    pub fn success(value: T) -> Self {
+        Validated {
+            outcome: Validation::Success(value),
+            warnings: None,
+        }
+    }
+
+    /// Create a success that carries a warning.
+    pub fn warn(value: T, warning: W) -> Self {
+        Validated {
+            outcome: Validation::Success(value),
+            warnings: Some(warning),
+        }
+    }
+
+    /// Create a failure without warnings.
+    pub fn failure(error: E) -> Self {
+        Validated {
+            outcome: Validation::Failure(error),
+            warnings: None,
+        }
+    }
+
+    /// Returns `true` if there are no errors (warnings are allowed).
+    pub fn is_success(&self) -> bool {
+        self.outcome.is_success()
+    }
+
+    /// Returns `true` if there are errors.
+    pub fn is_failure(&self) -> bool {
+        self.outcome.is_failure()
+    }
+
+    /// Returns `true` if any warnings were recorded.
+    pub fn has_warnings(&self) -> bool {
+        self.warnings.is_some()
+    }
+
+    /// The accumulated warnings, if any.
+    pub fn warnings(&self) -> Option<&W> {
+        self.warnings.as_ref()
+    }
+
+    /// The outcome, ignoring warnings.
+    pub fn outcome(&self) -> &Validation<T, E> {
+        &self.outcome
+    }
+
+    /// Split into the outcome and the accumulated warnings.
+    pub fn into_parts(self) -> (Validation<T, E>, Option<W>) {
+        (self.outcome, self.warnings)
+    }
+
+    /// Discard the warnings.
+    pub fn into_validation(self) -> Validation<T, E> {
+        self.outcome
+    }
+
+    /// Transform the success value, keeping warnings.
+    pub fn map<U, F>(self, f: F) -> Validated<U, W, E>
+    where
+        F: FnOnce(T) -> U,
+    {
+        Validated {
+            outcome: self.outcome.map(f),
+            warnings: self.warnings,
+        }
+    }
+
+    /// Transform the errors, keeping warnings.
+    pub fn map_err<E2, F>(self, f: F) -> Validated<T, W, E2>
+    where
+        F: FnOnce(E) -> E2,
+    {
+        Validated {
+            outcome: self.outcome.map_err(f),
+            warnings: self.warnings,
+        }
+    }
+
+    /// Transform the warnings.
+    pub fn map_warnings<W2, F>(self, f: F) -> Validated<T, W2, E>
+    where
+        F: FnOnce(W) -> W2,
+    {
+        Validated {
+            outcome: self.outcome,
+            warnings: self.warnings.map(f),
+        }
+    }
+}
+
+impl<T, W: Semigroup, E> Validated<T, W, E> {
+    /// Add a warning, combining it with any existing warnings.
+    pub fn with_warning(mut self, warning: W) -> Self {
+        self.warnings = combine(self.warnings, Some(warning));
+        self
+    }
+
+    /// Chain a dependent validation, accumulating warnings from both.
+    ///
+    /// Like [`Validation::and_then`], this short-circuits on failure.
+    pub fn and_then<U, F>(self, f: F) -> Validated<U, W, E>
+    where
+        F: FnOnce(T) -> Validated<U, W, E>,
+    {
+        match self.outcome {
+            Validation::Success(value) => {
+                let next = f(value);
+                Validated {
+                    outcome: next.outcome,
+                    warnings: combine(self.warnings, next.warnings),
+                }
+            }
+            Validation::Failure(error) => Validated {
+                outcome: Validation::Failure(error),
+                warnings: self.warnings,
+            },
+        }
+    }
+
+    /// Resolve into a plain [`Validation`] according to `policy`.
+    ///
+    /// With [`WarningPolicy::Deny`], warnings are converted with `to_error`
+    /// and cause failure, combined after any existing errors.
+    pub fn resolve<F>(self, policy: WarningPolicy, to_error: F) -> Validation<T, E>
+    where
+        E: Semigroup,
+        F: FnOnce(W) -> E,
+    {
+        match (policy, self.warnings) {
+            (WarningPolicy::Deny, Some(warnings)) => {
+                let warnings = to_error(warnings);
+                match self.outcome {
+                    Validation::Success(_) => Validation::Failure(warnings),
+                    Validation::Failure(error) => Validation::Failure(error.combine(warnings)),
+                }
+            }
+            _ => self.outcome,
+        }
+    }
+}
+
+impl<T, W: Semigroup, E: Semigroup> Validated<T, W, E> {
+    /// Combine two validations, accumulating errors and warnings.
+    pub fn and<U>(self, other: Validated<U, W, E>) -> Validated<(T, U), W, E> {
+        Validated {
+            outcome: self.outcome.and(other.outcome),
+            warnings: combine(self.warnings, other.warnings),
+        }
+    }
+
+    /// Combine all validations in a Vec, accumulating errors and warnings.
+    pub fn all_vec(validations: Vec<Validated<T, W, E>>) -> Validated<Vec<T>, W, E> {
+        let mut outcomes = Vec::with_capacity(validations.len());
+        let mut warnings = None;
+        for validated in validations {
+            outcomes.push(validated.outcome);
+            warnings = combine(warnings, validated.warnings);
+        }
+        Validated {
+            outcome: Validation::all_vec(outcomes),
+            warnings,
+        }
+    }
+}
+
+impl<T, W, E> From<Validation<T, E>> for Validated<T, W, E> {
+    fn from(outcome: Validation<T, E>) -> Self {
+        Validated {
+            outcome,
+            warnings: None,
+        }
+    }
+}
+
+impl<T, E> Validation<T, E> {
+    /// Attach warnings to this validation.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stillwater::Validation;
+    ///
+    /// let v = Validation::<_, Vec<&str>>::success(5).with_warnings(vec!["deprecated field"]);
+    /// assert!(v.is_success());
+    /// assert_eq!(v.warnings(), Some(&vec!["deprecated field"]));
+    /// ```
+    pub fn with_warnings<W>(self, warnings: W) -> Validated<T, W, E> {
+        Validated {
+            outcome: self,
+            warnings: Some(warnings),
+        }
+    }
+}
+
+fn combine<W: Semigroup>(a: Option<W>, b: Option<W>) -> Option<W> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.combine(b)),
+        (a, b) => a.or(b),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type V<T> = Validated<T, Vec<&'static str>, Vec<&'static str>>;
+
+    #[test]
+    fn test_success_without_warnings() {
+        let v: V<i32> = Validated::success(1);
+        assert!(v.is_success());
+        assert!(!v.has_warnings());
+        assert_eq!(v.into_validation(), Validation::Success(1));
+    }
+
+    #[test]
+    fn test_warnings_accumulate_across_and() {
+        let a: V<i32> = Validated::warn(1, vec!["w1"]);
+        let b: V<i32> = Validated::success(2).with_warning(vec!["w2"]);
+        let c: V<i32> = Validated::failure(vec!["e1"]).with_warning(vec!["w3"]);
+
+        let combined = a.and(b).and(c);
+        assert_eq!(combined.warnings(), Some(&vec!["w1", "w2", "w3"]));
+        assert_eq!(combined.outcome(), &Validation::Failure(vec!["e1"]));
+    }
+
+    #[test]
+    fn test_and_then_keeps_warnings_from_both_steps() {
+        let v: V<i32> = Validated::warn(2, vec!["first"]);
+        let result = v.and_then(|x| Validated::warn(x * 10, vec!["second"]));
+
+        assert_eq!(
+            result.into_parts(),
+            (Validation::Success(20), Some(vec!["first", "second"]))
+        );
+    }
+
+    #[test]
+    fn test_and_then_short_circuits_on_failure() {
+        let v: V<i32> = Validated::failure(vec!["bad"]).with_warning(vec!["w"]);
+        let result = v.and_then(|x| Validated::warn(x, vec!["never"]));
+
+        assert_eq!(
+            result.into_parts(),
+            (Validation::Failure(vec!["bad"]), Some(vec!["w"]))
+        );
+    }
+
+    #[test]
+    fn test_all_vec() {
+        let result = V::all_vec(vec![
+            Validated::success(1),
+            Validated::warn(2, vec!["w"]),
+            Validated::success(3),
+        ]);
+        assert_eq!(
+            result.into_parts(),
+            (Validation::Success(vec![1, 2, 3]), Some(vec!["w"]))
+        );
+    }
+
+    #[test]
+    fn test_resolve_allow_drops_warnings() {
+        let v: V<i32> = Validated::warn(1, vec!["w"]);
+        assert_eq!(
+            v.resolve(WarningPolicy::Allow, |w| w),
+            Validation::Success(1)
+        );
+    }
+
+    #[test]
+    fn test_resolve_deny_fails_on_warnings() {
+        let v: V<i32> = Validated::warn(1, vec!["w"]);
+        assert_eq!(
+            v.resolve(WarningPolicy::Deny, |w| w),
+            Validation::Failure(vec!["w"])
+        );
+
+        let v: V<i32> = Validated::failure(vec!["e"]).with_warning(vec!["w"]);
+        assert_eq!(
+            v.resolve(WarningPolicy::Deny, |w| w),
+            Validation::Failure(vec!["e", "w"])
+        );
+
+        let clean: V<i32> = Validated::success(1);
+        assert_eq!(
+            clean.resolve(WarningPolicy::Deny, |w| w),
+            Validation::Success(1)
+        );
+    }
+
+    #[test]
+    fn test_from_validation_and_with_warnings() {
+        let v: V<i32> = Validation::success(1).into();
+        assert!(!v.has_warnings());
+
+        let v = Validation::<i32, Vec<&str>>::failure(vec!["e"]).with_warnings(vec!["w"]);
+        assert!(v.is_failure());
+        assert_eq!(v.warnings(), Some(&vec!["w"]));
+    }
+
+    #[test]
+    fn test_map_variants() {
+        let v: V<i32> = Validated::warn(2, vec!["w"]);
+        let v = v.map(|x| x + 1).map_warnings(|w| w.len());
+        assert_eq!(v.into_parts(), (Validation::Success(3), Some(1)));
+    }
+}
@@ -0,0 +1,42 @@
+#![cfg(feature = "alloc_stats")]
+
+//! Allocation counts for running boxed effects, measured with the
+//! counting allocator from `effect::profile`.
+
+use stillwater::effect::prelude::*;
+use stillwater::effect::profile::{CountingAllocator, ProfileReport};
+
+#[global_allocator]
+static ALLOC: CountingAllocator = CountingAllocator::new();
+
+fn allocations_while_running<Eff>(effect: Eff) -> u64
+where
+    Eff: Effect<Output = u64, Error = String, Env = ()>,
+{
+    // Start the boxed effect from a continuation so that boxing its future
+    // happens while the profiled stage is being polled.
+    let report = ProfileReport::new();
+    let staged = pure::<_, String, ()>(()).and_then(move |()| effect);
+    let result = tokio_test::block_on(staged.profile("run", &report).run(&()));
+    assert!(result.is_ok());
+    report.stages()[0].allocations.unwrap()
+}
+
+#[test]
+fn test_single_boxed_effect_allocates_only_its_future() {
+    let effect = from_fn(|_: &()| Ok::<u64, String>(42)).boxed();
+
+    assert_eq!(allocations_while_running(effect), 1);
+}
+
+#[test]
+fn test_boxed_chain_allocates_per_step() {
+    let one_step = pure::<u64, String, ()>(1).boxed().map(|n| n + 1);
+    let two_steps = pure::<u64, String, ()>(1)
+        .boxed()
+        .map(|n| n + 1)
+        .map(|n| n + 1);
+
+    let per_step = allocations_while_running(two_steps) - allocations_while_running(one_step);
+    assert_eq!(per_step, 1);
+}
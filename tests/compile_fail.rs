@@ -0,0 +1,12 @@
+//! Compile-fail tests for the type-level guarantees of resource and
+//! capability tracking.
+//!
+//! Each file in `tests/compile_fail/` must fail to compile with the error in
+//! its `.stderr` file. After an intended change to the diagnostics, refresh
+//! the expectations with `TRYBUILD=overwrite cargo test --test compile_fail`.
+
+#[test]
+fn compile_fail() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/compile_fail/*.rs");
+}
@@ -0,0 +1,15 @@
+// A pure-core function cannot chain into an effect that reads the clock.
+use stillwater::effect::caps::*;
+use stillwater::effect::prelude::*;
+
+fn now() -> impl EffectCaps<Output = u64, Error = String, Env = (), Caps = Uses<ClockCap>> {
+    pure(0).uses::<ClockCap>()
+}
+
+fn age(born: u64) -> impl EffectCaps<Output = u64, Error = String, Env = (), Caps = Empty> {
+    now().and_then(move |today| pure(today - born))
+}
+
+fn main() {
+    let _ = age(1970);
+}
@@ -0,0 +1,7 @@
+// An effect that uses the network cannot pass as pure.
+use stillwater::effect::caps::*;
+use stillwater::effect::prelude::*;
+
+fn main() {
+    let _ = assert_pure(pure::<_, String, ()>(1).uses::<NetCap>());
+}
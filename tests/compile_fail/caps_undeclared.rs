@@ -0,0 +1,12 @@
+// A function declared to touch only the filesystem cannot use the network.
+use stillwater::effect::caps::*;
+use stillwater::effect::prelude::*;
+
+fn load_config() -> impl EffectCaps<Output = String, Error = String, Env = (), Caps = Uses<FsCap>>
+{
+    pure("remote".to_string()).uses::<NetCap>()
+}
+
+fn main() {
+    let _ = load_config();
+}
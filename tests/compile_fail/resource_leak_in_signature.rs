@@ -0,0 +1,17 @@
+// A function promising a resource-neutral effect cannot acquire a
+// transaction without releasing it.
+use stillwater::effect::prelude::*;
+use stillwater::effect::resource::*;
+
+fn begin_tx() -> impl ResourceEffect<Output = u32, Error = String, Env = (), Acquires = Has<TxRes>>
+{
+    pure(1).acquires::<TxRes>()
+}
+
+fn transfer() -> impl ResourceEffect<Output = u32, Error = String, Env = (), Acquires = Empty> {
+    begin_tx().map(|tx| tx + 1)
+}
+
+fn main() {
+    let _ = transfer();
+}
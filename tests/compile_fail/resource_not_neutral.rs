@@ -0,0 +1,8 @@
+// An effect that acquires a file handle is not resource-neutral.
+use stillwater::effect::prelude::*;
+use stillwater::effect::resource::*;
+
+fn main() {
+    let open = pure::<_, String, ()>("handle").acquires::<FileRes>();
+    let _ = assert_resource_neutral(open);
+}
@@ -0,0 +1,12 @@
+// A bracket over file handles cannot be given an effect that acquires a
+// database connection.
+use stillwater::effect::prelude::*;
+use stillwater::effect::resource::*;
+
+fn main() {
+    let _ = tracked_resource_bracket::<FileRes, _, _, _, _, _, _, _, _, _>(
+        pure::<_, String, ()>(1).acquires::<DbRes>(),
+        |_conn: u32| async move { Ok(()) },
+        |conn: &u32| pure::<_, String, ()>(*conn),
+    );
+}